@@ -0,0 +1,29 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Build script, stamps the build with the git commit it came from
+
+The short hash of the commit being built goes into the BLACKJACK_GIT_HASH
+environment variable for the compiler, read with env!() in modules/version.rs.
+A build from a source download without git, or without a .git folder, says
+"unknown" instead of failing.
+
+It runs again whenever the checked out commit changes.
+*/
+use std::process::Command;
+
+fn main() {
+    let hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=BLACKJACK_GIT_HASH={}", hash);
+    // HEAD changes on a checkout, the branch's ref on a commit
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+}
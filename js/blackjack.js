@@ -0,0 +1,182 @@
+/*
+By: <tyler>
+Date: 2025-11-19
+Program Details: Browser-side helpers for the black jack web build
+
+Registered as a miniquad plugin, so index.html has to load this file after
+mq_js_bundle.js and before calling load("pkg/blackjack.wasm").
+The matching Rust declarations live in src/modules/web_bridge.rs.
+*/
+"use strict";
+
+// Bytes waiting to be copied into wasm memory by blackjack_take_pending
+var blackjack_pending_bytes = new Uint8Array(0);
+
+function blackjack_read_string(ptr, len) {
+    return new TextDecoder().decode(new Uint8Array(wasm_memory.buffer, ptr, len));
+}
+
+// The page may be gone after this, so the game writes its session to localStorage now
+function blackjack_page_hidden() {
+    if (typeof wasm_exports !== "undefined" && wasm_exports.blackjack_page_hidden) {
+        wasm_exports.blackjack_page_hidden();
+    }
+}
+
+function blackjack_register_plugin(importObject) {
+    // Closing the tab, switching away from it or reloading all save the session first
+    document.addEventListener("visibilitychange", function () {
+        if (document.visibilityState === "hidden") {
+            blackjack_page_hidden();
+        }
+    });
+    window.addEventListener("pagehide", blackjack_page_hidden);
+    window.addEventListener("beforeunload", blackjack_page_hidden);
+
+    // Save bytes from wasm memory as a file download in the browser
+    importObject.env.blackjack_download_file = function (name_ptr, name_len, data_ptr, data_len, mime_ptr, mime_len) {
+        var name = blackjack_read_string(name_ptr, name_len);
+        var mime = blackjack_read_string(mime_ptr, mime_len);
+        // slice() copies the bytes out of wasm memory before the blob is built
+        var data = new Uint8Array(wasm_memory.buffer, data_ptr, data_len).slice();
+        var url = URL.createObjectURL(new Blob([data], { type: mime }));
+        var link = document.createElement("a");
+        link.href = url;
+        link.download = name;
+        document.body.appendChild(link);
+        link.click();
+        document.body.removeChild(link);
+        setTimeout(function () { URL.revokeObjectURL(url); }, 1000);
+    };
+
+    // Shoe seeds and salts, nothing the game picked before can give these away
+    importObject.env.blackjack_random_bytes = function (buffer_ptr, buffer_len) {
+        crypto.getRandomValues(new Uint8Array(wasm_memory.buffer, buffer_ptr, buffer_len));
+    };
+
+    // Save games live in localStorage, every key gets a "blackjack." prefix
+    importObject.env.blackjack_storage_set = function (key_ptr, key_len, value_ptr, value_len) {
+        var key = "blackjack." + blackjack_read_string(key_ptr, key_len);
+        try {
+            localStorage.setItem(key, blackjack_read_string(value_ptr, value_len));
+        } catch (e) {
+            console.warn("Could not save " + key + ": " + e);
+        }
+    };
+
+    // Returns the length of the stored value in bytes, or -1 if there isn't one
+    importObject.env.blackjack_storage_get = function (key_ptr, key_len) {
+        var value = null;
+        try {
+            value = localStorage.getItem("blackjack." + blackjack_read_string(key_ptr, key_len));
+        } catch (e) {
+            console.warn("localStorage is not available: " + e);
+        }
+        if (value === null) {
+            return -1;
+        }
+        blackjack_pending_bytes = new TextEncoder().encode(value);
+        return blackjack_pending_bytes.length;
+    };
+
+    // Copy the bytes waiting in blackjack_pending_bytes into a buffer Rust allocated
+    importObject.env.blackjack_take_pending = function (buffer_ptr, buffer_len) {
+        new Uint8Array(wasm_memory.buffer, buffer_ptr, buffer_len).set(blackjack_pending_bytes.subarray(0, buffer_len));
+        blackjack_pending_bytes = new Uint8Array(0);
+    };
+
+    importObject.env.blackjack_pending_len = function () {
+        return blackjack_pending_bytes.length;
+    };
+
+    // Blocking request for the cloud save backend, returns the status or -1 if the
+    // server could not be reached. The response body is left in blackjack_pending_bytes.
+    importObject.env.blackjack_http_request = function (method_ptr, method_len, url_ptr, url_len, body_ptr, body_len) {
+        var method = blackjack_read_string(method_ptr, method_len);
+        var url = blackjack_read_string(url_ptr, url_len);
+        var body = blackjack_read_string(body_ptr, body_len);
+        var xhr = new XMLHttpRequest();
+        try {
+            xhr.open(method, url, false);
+            xhr.setRequestHeader("Content-Type", "application/json");
+            xhr.send(body.length > 0 ? body : null);
+        } catch (e) {
+            console.warn(method + " " + url + " failed: " + e);
+            blackjack_pending_bytes = new Uint8Array(0);
+            return -1;
+        }
+        blackjack_pending_bytes = new TextEncoder().encode(xhr.responseText || "");
+        return xhr.status;
+    };
+
+    // The game panicked: cover the frozen canvas with the report and a copy button
+    importObject.env.blackjack_show_error = function (report_ptr, report_len) {
+        var report = blackjack_read_string(report_ptr, report_len);
+        var screen = document.createElement("div");
+        screen.style.cssText = "position:fixed;inset:0;z-index:10;overflow:auto;padding:40px;" +
+            "background:#0b3d0b;color:white;font:18px sans-serif;";
+        var title = document.createElement("h1");
+        title.textContent = "Something went wrong";
+        var details = document.createElement("pre");
+        details.style.cssText = "white-space:pre-wrap;background:rgba(0,0,0,0.4);padding:16px;";
+        details.textContent = report;
+        var copy = document.createElement("button");
+        copy.textContent = "Copy details";
+        copy.style.cssText = "font-size:18px;padding:8px 16px;margin-right:12px;";
+        copy.onclick = function () {
+            navigator.clipboard.writeText(report).then(function () { copy.textContent = "Copied"; });
+        };
+        var reload = document.createElement("button");
+        reload.textContent = "Reload";
+        reload.style.cssText = copy.style.cssText;
+        reload.onclick = function () { location.reload(); };
+        screen.append(title, details, copy, reload);
+        document.body.appendChild(screen);
+    };
+
+    // Show text the player can copy, a prompt's input box is already selected.
+    // The clipboard is tried as well but browsers may refuse it.
+    importObject.env.blackjack_show_text = function (title_ptr, title_len, text_ptr, text_len) {
+        var title = blackjack_read_string(title_ptr, title_len);
+        var text = blackjack_read_string(text_ptr, text_len);
+        if (navigator.clipboard) {
+            navigator.clipboard.writeText(text).catch(function (e) {
+                console.warn("Could not copy to the clipboard: " + e);
+            });
+        }
+        window.prompt(title, text);
+    };
+
+    // Ask for text, returns its length in bytes or -1 if the player cancelled.
+    // The text is left in blackjack_pending_bytes.
+    importObject.env.blackjack_ask_text = function (title_ptr, title_len) {
+        var text = window.prompt(blackjack_read_string(title_ptr, title_len), "");
+        if (text === null) {
+            return -1;
+        }
+        blackjack_pending_bytes = new TextEncoder().encode(text);
+        return blackjack_pending_bytes.length;
+    };
+
+    // Say a sentence with the browser's speech synthesis, queued after anything still being said
+    importObject.env.blackjack_speak = function (text_ptr, text_len) {
+        if (!("speechSynthesis" in window)) {
+            return;
+        }
+        window.speechSynthesis.speak(new SpeechSynthesisUtterance(blackjack_read_string(text_ptr, text_len)));
+    };
+
+    importObject.env.blackjack_storage_remove = function (key_ptr, key_len) {
+        try {
+            localStorage.removeItem("blackjack." + blackjack_read_string(key_ptr, key_len));
+        } catch (e) {
+            console.warn("localStorage is not available: " + e);
+        }
+    };
+}
+
+miniquad_add_plugin({
+    register_plugin: blackjack_register_plugin,
+    name: "blackjack",
+    version: 8
+});
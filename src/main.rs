@@ -5,30 +5,1489 @@ Program Details: <black jack>
 */
 
 mod modules;
-use crate::miniquad::date;
 use crate::modules::label::Label;
 use crate::modules::still_image::StillImage;
 use crate::modules::text_button::TextButton;
+use crate::modules::text_cache::measure_text_cached;
+use crate::modules::shapes::ShapeBatch;
+use crate::modules::scroll_panel::ScrollPanel;
+use crate::modules::widget_layout::{Column, Placeable, Row};
+use crate::modules::widget_style;
 use macroquad::prelude::*;
 use crate::modules::preload_image::TextureManager;
-use crate::modules::preload_image::LoadingScreenOptions;
-use crate::modules::scale::use_virtual_resolution;
+use crate::modules::preload_image::{CancelToken, LoadingScreen, LoadingScreenOptions};
+use crate::modules::layout::{TableLayout, TableLayouts};
+use crate::modules::screenshot::capture_screenshot;
+use crate::modules::cards::{self, Card, EMPTY_CARD_PATH};
+use crate::modules::shoe::{Shoe, ShoePosition};
+use crate::modules::shoe_view::ShoeView;
+use crate::modules::shoe_meter::ShoeMeter;
+use crate::modules::scene_fade;
+use crate::modules::materials;
+use crate::modules::analytics::Analytics;
+use crate::modules::frame::Frame;
+use crate::modules::animation::{self, Easing, Fade, Timeline};
+use crate::modules::frame_pacer::{self, FramePacer};
+use crate::modules::quality::{self, QualityMonitor};
+use crate::modules::theme::{self, THEMES};
+use crate::modules::hand_badge::{self, HandBadge};
+use crate::modules::streak_indicator::StreakIndicator;
+use crate::modules::dialog::Dialog;
+use crate::modules::history::HandHistory;
+use crate::modules::low_bankroll::{LowBankroll, LowBankrollAction};
+use crate::modules::quick_settings::QuickSettings;
+use crate::modules::decision_timer::DecisionTimer;
+use crate::modules::strategy::{basic_strategy, Action};
+use crate::modules::rules::{self, Payout, Ruleset};
+use crate::modules::rules_editor::{EditorAction, RulesEditor};
+use crate::modules::felt::{Felt, CHROMA_GREEN};
+use crate::modules::results_strip::ResultsStrip;
+use crate::modules::slider::Slider;
+use crate::modules::audio::Audio;
+use crate::modules::hole_card::HoleCard;
+use crate::modules::squeeze::Squeeze;
+use crate::modules::advisor::{self, Advisor};
+use crate::modules::strategy_chart::StrategyChart;
+use crate::modules::input::{pointer, Gesture, GestureDetector};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::modules::input_recorder;
+use crate::modules::bet::{payout, percent_bet, Bet, PERCENT_BETS, RECENT_BETS};
+use crate::modules::bankroll::Ledger;
+use crate::modules::version;
+use crate::modules::card_face;
+use crate::modules::asset_check;
+use crate::modules::hand_snapshot::{HandSnapshots, SNAPSHOT_SIZE};
+use crate::modules::chip::{ChipAction, ChipAtlas, InteractiveChip, CHIP_ATLAS_PATH, DENOMINATIONS};
+#[cfg(all(feature = "gif_recorder", not(target_arch = "wasm32")))]
+use crate::modules::gif_recorder::GifRecorder;
+use crate::modules::count_trainer::CountTrainer;
+use crate::modules::drills::DrillTracker;
+use crate::modules::speech::Announcer;
+use crate::modules::keys::{self, KeyAction, KeyMap};
+use crate::modules::odds::{Composition, OddsPanel};
+use crate::modules::ev::EvEstimator;
+use crate::modules::famous_hands::{self, FamousHand};
+use crate::modules::goals::{SessionGoals, GOALS};
+use crate::modules::pacing::Pacing;
+use crate::modules::cursor::{self, CursorKind};
+use crate::modules::achievements::ACHIEVEMENTS;
+use crate::modules::shuffle_animation::ShuffleAnimation;
+use crate::modules::events::{EventBus, EventListener, GameEvent, Outcome, Seat};
+use crate::modules::stats::{Stats, Tally, START_ROWS, UP_CARDS};
+use crate::modules::profile::{Profile, ProfileInfo, ProfileList, AVATAR_PATHS};
+use crate::modules::profile_menu::ProfileMenu;
+use crate::modules::nameplate::Nameplate;
+use crate::modules::profile_transfer::{export_profile, import_profile};
+use crate::modules::storage;
+use crate::modules::autosave::{self, RoundSave};
+use crate::modules::layers::{self, Layer};
+use crate::modules::ui;
+#[cfg(debug_assertions)]
+use crate::modules::debug_overlay::DebugOverlay;
+#[cfg(debug_assertions)]
+use crate::modules::dev_console::{self, Command, DevConsole};
+#[cfg(debug_assertions)]
+use crate::modules::cards::SUITS;
+#[cfg(debug_assertions)]
+use crate::modules::scenario;
+use crate::modules::hand::{self, explain, settle_hands, Hand, MAX_HANDS};
+use crate::modules::i18n::{self, tr};
+use crate::modules::audit::{AuditLog, Verification};
+use crate::modules::entropy;
+use crate::modules::settings::Settings;
+use crate::modules::log::{self, Level};
+use crate::modules::crash;
+use crate::modules::card_pool::CardSpritePool;
 /// Set up window settings before the app runs
 fn window_conf() -> Conf {
+    // Fullscreen, vsync and MSAA come from the profile played last, vsync and MSAA can't be changed after this
+    let settings = ProfileList::load().last_used_settings().unwrap_or_default();
     Conf {
         window_title: "Black Jack".to_string(),
         window_width: 1124,
         window_height: 768,
-        fullscreen: false,
+        fullscreen: settings.fullscreen,
         high_dpi: true,
         window_resizable: true,
-        sample_count: 4, // MSAA: makes shapes look smoother
+        sample_count: if settings.low_quality() { 1 } else { 4 }, // MSAA: makes shapes look smoother
+        platform: miniquad::conf::Platform {
+            swap_interval: Some(if settings.vsync { 1 } else { 0 }),
+            ..Default::default()
+        },
         ..Default::default()
     }
 }
+
+// All of the widgets on the table, grouped so a layout preset can move them in one go
+struct Table {
+    player_cards: Vec<Vec<StillImage>>, // Card slots for each hand being played, taken from the pool
+    dealer_cards: Vec<StillImage>,
+    card_pool: CardSpritePool,
+    btn_exit: TextButton,
+    btn_settings: TextButton,
+    btn_screenshot: TextButton,
+    btn_fairness: TextButton,
+    btn_deal: TextButton,
+    btn_hit: TextButton,
+    btn_stand: TextButton,
+    btn_replay: TextButton,
+    btn_rebet: TextButton,
+    btn_rebet_deal: TextButton,
+    btn_quick_bets: Vec<TextButton>, // One per recent bet amount
+    btn_percent_bets: Vec<TextButton>, // One per PERCENT_BETS, beside the chip tray
+    btn_layout_prev: TextButton,
+    btn_layout_next: TextButton,
+    btn_log_level: TextButton,
+    btn_decision_time: TextButton,
+    btn_timeout_action: TextButton,
+    btn_no_contest: TextButton,
+    btn_dealer_peeks: TextButton,
+    btn_hand_count: TextButton,
+    btn_animation_speed: TextButton,
+    btn_currency: TextButton,
+    btn_trainer: TextButton,
+    btn_odds: TextButton,
+    btn_squeeze: TextButton,
+    btn_advisor: TextButton,
+    btn_settings_close: TextButton,
+    sld_music: Slider,
+    sld_sfx: Slider,
+    sld_duck: Slider,
+    btn_switch_profile: TextButton,
+    btn_export_profile: TextButton,
+    btn_import_profile: TextButton,
+    btn_display: TextButton,
+    btn_rules: TextButton,
+    btn_goals: TextButton,
+    btn_whats_new: TextButton,
+    btn_fullscreen: TextButton,
+    btn_resolution: TextButton,
+    btn_target_fps: TextButton,
+    btn_vsync: TextButton,
+    btn_reduced_motion: TextButton,
+    btn_power_saving: TextButton,
+    btn_cursor: TextButton,
+    btn_stream_mode: TextButton,
+    btn_card_style: TextButton,
+    btn_announce: TextButton,
+    btn_quality: TextButton,
+    btn_hand_totals: TextButton,
+    btn_theme: TextButton,
+    btn_scene_fade: TextButton,
+    btn_text_scale: TextButton,
+    btn_display_close: TextButton,
+    btn_rules_close: TextButton,
+    btn_table_rules: TextButton, // Picks the next built-in or saved table
+    btn_edit_rules: TextButton,
+    btn_stakes: TextButton,
+    btn_low_bankroll: TextButton,
+    btn_chart: TextButton,
+    btn_chart_close: TextButton,
+    btn_goal_picks: Vec<TextButton>, // One for each of GOALS
+    btn_goals_close: TextButton,
+    btn_stats: TextButton,
+    btn_stats_close: TextButton,
+    btn_play_log: TextButton,
+    btn_play_log_clear: TextButton,
+    btn_whats_new_close: TextButton,
+    btn_reveal_shoe: TextButton,
+    btn_fairness_close: TextButton,
+    btn_hand_close: TextButton,
+    btn_copy_seed: TextButton,
+    btn_famous: TextButton,
+    btn_famous_picks: Vec<TextButton>, // One per famous hand, made once they are loaded
+    btn_famous_stop: TextButton,
+    btn_famous_close: TextButton,
+    btn_theme_picks: Vec<TextButton>, // One per theme, in the felt's colour
+    btn_theme_sample: TextButton,     // Never does anything, it shows how buttons look in the theme
+    btn_theme_apply: TextButton,
+    btn_theme_cancel: TextButton,
+    lbl_theme_sample: Label,
+    lbl_dealerhand: Label,
+    lbl_winner: Label,
+    lbl_explainer: Label,
+    lbl_playerhand: Label,
+    lbl_notice: Label,
+    lbl_sync: Label,
+    lbl_version: Label, // The build, in the bottom left corner for bug reports
+    nameplate: Nameplate, // The player's seat
+    shoe_view: ShoeView,
+    shoe_meter: ShoeMeter, // How much of the shoe is dealt, under the shoe
+    player_badges: Vec<HandBadge>,
+    dealer_badge: HandBadge,
+    hole_card: HoleCard,
+    squeeze: Squeeze,
+    squeeze_slot: (usize, usize), // Hand and slot of the card being squeezed
+    felt: Felt,
+    chips: Vec<InteractiveChip>, // The chip tray, one chip of each denomination
+    chip_atlas: ChipAtlas,
+    streak: StreakIndicator,
+    clean_hud: bool, // Streaming: leave the banners, counters and streak off the table
+    #[cfg(all(feature = "gif_recorder", not(target_arch = "wasm32")))]
+    recorder: GifRecorder, // The last few seconds of play, F9 saves them as a GIF
+    results: ResultsStrip,
+    snapshots: HandSnapshots, // Pictures of finished hands for the results strip and the hand panel
+    layout: TableLayout,
+    hands: usize,               // Player hands on the table
+    active_hand: Option<usize>, // The hand being played, marked when there is more than one
+}
+
+impl Table {
+    fn new(tm: &TextureManager) -> Self {
+        let mut card_pool = CardSpritePool::new(tm);
+        let player_cards = vec![card_pool.take_row(CARD_SLOTS)];
+        let dealer_cards = card_pool.take_row(CARD_SLOTS);
+        let mut btn_exit = TextButton::new(0.0, 0.0, 200.0, 65.0, "Exit", BLACK, DARKGRAY, 35);
+        btn_exit.with_round(3.0);
+        let mut btn_settings = TextButton::new(0.0, 0.0, 200.0, 65.0, "Settings", BLACK, DARKGRAY, 35);
+        btn_settings.with_round(3.0);
+        let mut btn_screenshot = TextButton::new(0.0, 0.0, 200.0, 65.0, "Screenshot", BLACK, DARKGRAY, 30);
+        btn_screenshot.with_round(3.0);
+        let mut btn_fairness = TextButton::new(0.0, 0.0, 200.0, 65.0, "Fairness", BLACK, DARKGRAY, 30);
+        btn_fairness.with_round(3.0);
+        let mut btn_deal = TextButton::new(0.0, 0.0, 200.0, 65.0, "Deal", BLACK, DARKGRAY, 35);
+        btn_deal.set_disabled_reason(FINISH_HAND_FIRST);
+        let mut btn_hit = TextButton::new(0.0, 0.0, 170.0, 65.0, "Hit", BLACK, DARKGRAY, 35);
+        btn_hit.enabled = false;
+        btn_hit.set_disabled_reason(DEAL_FIRST);
+        let mut btn_stand = TextButton::new(0.0, 0.0, 170.0, 65.0, "Stand", BLACK, DARKGRAY, 35);
+        btn_stand.enabled = false;
+        btn_stand.set_disabled_reason(DEAL_FIRST);
+        let mut btn_replay = TextButton::new(0.0, 0.0, 200.0, 65.0, "Play Again", BLACK, DARKGRAY, 30);
+        btn_replay.with_wrap(true).with_padding(8.0, 8.0, 4.0, 4.0);
+        btn_replay.set_disabled_reason(FINISH_HAND_FIRST);
+        let btn_rebet = TextButton::new(0.0, 0.0, 170.0, 65.0, "Rebet", BLACK, DARKGRAY, 35);
+        let btn_rebet_deal = TextButton::new(0.0, 0.0, 170.0, 65.0, "Rebet & Deal", BLACK, DARKGRAY, 28);
+        let btn_quick_bets = (0..RECENT_BETS).map(|_| TextButton::new(0.0, 0.0, 66.0, 65.0, "", BLACK, DARKGRAY, 28)).collect();
+        let btn_percent_bets = PERCENT_BETS.iter().map(|percent| TextButton::new(0.0, 0.0, 56.0, 30.0, format!("{}%", percent), BLACK, DARKGRAY, 24)).collect();
+        let mut btn_layout_prev = TextButton::new(0.0, 0.0, 50.0, 45.0, "<", BLACK, DARKGREEN, 35);
+        btn_layout_prev.with_layer(Layer::Overlay);
+        let mut btn_layout_next = TextButton::new(0.0, 0.0, 50.0, 45.0, ">", BLACK, DARKGREEN, 35);
+        btn_layout_next.with_layer(Layer::Overlay);
+        let mut btn_log_level = TextButton::new(0.0, 0.0, 270.0, 45.0, "info", BLACK, DARKGREEN, 30);
+        btn_log_level.with_layer(Layer::Overlay);
+        let mut btn_decision_time = TextButton::new(0.0, 0.0, 270.0, 45.0, "Off", BLACK, DARKGREEN, 30);
+        btn_decision_time.with_layer(Layer::Overlay);
+        let mut btn_timeout_action = TextButton::new(0.0, 0.0, 270.0, 45.0, "Stand", BLACK, DARKGREEN, 30);
+        btn_timeout_action.with_layer(Layer::Overlay);
+        btn_timeout_action.set_disabled_reason("Turn the decision timer on first");
+        let mut btn_no_contest = TextButton::new(0.0, 0.0, 270.0, 45.0, "Settle at once", BLACK, DARKGREEN, 30);
+        btn_no_contest.with_layer(Layer::Overlay);
+        let mut btn_dealer_peeks = TextButton::new(0.0, 0.0, 270.0, 45.0, "Yes (US)", BLACK, DARKGREEN, 30);
+        btn_dealer_peeks.with_layer(Layer::Overlay);
+        btn_dealer_peeks.set_disabled_reason(FINISH_HAND_FIRST);
+        let mut btn_hand_count = TextButton::new(0.0, 0.0, 270.0, 45.0, "1 hand", BLACK, DARKGREEN, 30);
+        btn_hand_count.with_layer(Layer::Overlay);
+        btn_hand_count.set_disabled_reason(FINISH_HAND_FIRST);
+        let mut btn_animation_speed = TextButton::new(0.0, 0.0, 270.0, 45.0, "1x", BLACK, DARKGREEN, 30);
+        btn_animation_speed.with_layer(Layer::Overlay);
+        let mut btn_currency = TextButton::new(0.0, 0.0, 270.0, 45.0, "Off", BLACK, DARKGREEN, 30);
+        btn_currency.with_layer(Layer::Overlay);
+        let mut btn_trainer = TextButton::new(0.0, 0.0, 270.0, 45.0, "Off", BLACK, DARKGREEN, 30);
+        btn_trainer.with_layer(Layer::Overlay);
+        let mut btn_odds = TextButton::new(0.0, 0.0, 270.0, 45.0, "Off", BLACK, DARKGREEN, 30);
+        btn_odds.with_layer(Layer::Overlay);
+        let mut btn_squeeze = TextButton::new(0.0, 0.0, 270.0, 45.0, "Off", BLACK, DARKGREEN, 30);
+        btn_squeeze.with_layer(Layer::Overlay);
+        let mut btn_advisor = TextButton::new(0.0, 0.0, 270.0, 45.0, "Off", BLACK, DARKGREEN, 30);
+        btn_advisor.with_layer(Layer::Overlay);
+        let mut sld_music = Slider::new(0.0, 0.0, 200.0, 0.5);
+        sld_music.with_layer(Layer::Overlay);
+        let mut sld_sfx = Slider::new(0.0, 0.0, 200.0, 0.8);
+        sld_sfx.with_layer(Layer::Overlay);
+        let mut sld_duck = Slider::new(0.0, 0.0, 200.0, 0.3);
+        sld_duck.with_layer(Layer::Overlay);
+        let mut btn_settings_close = TextButton::new(0.0, 0.0, 200.0, 60.0, "Close", BLACK, DARKGREEN, 35);
+        btn_settings_close.with_layer(Layer::Overlay);
+        let mut btn_switch_profile = TextButton::new(0.0, 0.0, 200.0, 60.0, "Switch Profile", BLACK, DARKGREEN, 28);
+        btn_switch_profile.with_layer(Layer::Overlay);
+        btn_switch_profile.set_disabled_reason(FINISH_HAND_FIRST);
+        let mut btn_export_profile = TextButton::new(0.0, 0.0, 200.0, 55.0, "Export Profile", BLACK, DARKGREEN, 28);
+        btn_export_profile.with_layer(Layer::Overlay);
+        let mut btn_import_profile = TextButton::new(0.0, 0.0, 200.0, 55.0, "Import Profile", BLACK, DARKGREEN, 28);
+        btn_import_profile.with_layer(Layer::Overlay);
+        btn_import_profile.set_disabled_reason(FINISH_HAND_FIRST);
+        let mut btn_display = TextButton::new(0.0, 0.0, 150.0, 55.0, "Display", BLACK, DARKGREEN, 28);
+        btn_display.with_layer(Layer::Overlay);
+        let mut btn_rules = TextButton::new(0.0, 0.0, 130.0, 55.0, "Rules", BLACK, DARKGREEN, 28);
+        btn_rules.with_layer(Layer::Overlay);
+        let mut btn_goals = TextButton::new(0.0, 0.0, 130.0, 55.0, "Goals", BLACK, DARKGREEN, 28);
+        btn_goals.with_layer(Layer::Overlay);
+        let mut btn_whats_new = TextButton::new(0.0, 0.0, 130.0, 55.0, "What's New", BLACK, DARKGREEN, 28);
+        btn_whats_new.with_layer(Layer::Overlay);
+        let mut btn_fullscreen = TextButton::new(0.0, 0.0, 270.0, 45.0, "Off", BLACK, DARKGREEN, 30);
+        btn_fullscreen.with_layer(Layer::Overlay);
+        let mut btn_resolution = TextButton::new(0.0, 0.0, 270.0, 45.0, "Layout's own", BLACK, DARKGREEN, 30);
+        btn_resolution.with_layer(Layer::Overlay);
+        let mut btn_target_fps = TextButton::new(0.0, 0.0, 270.0, 45.0, "No cap", BLACK, DARKGREEN, 30);
+        btn_target_fps.with_layer(Layer::Overlay);
+        let mut btn_vsync = TextButton::new(0.0, 0.0, 270.0, 45.0, "On", BLACK, DARKGREEN, 30);
+        btn_vsync.with_layer(Layer::Overlay);
+        // The browser draws at the screen's refresh rate whatever the game asks for
+        #[cfg(target_arch = "wasm32")]
+        for button in [&mut btn_target_fps, &mut btn_vsync] {
+            button.enabled = false;
+            button.set_disabled_reason("The browser sets the frame rate");
+        }
+        let mut btn_reduced_motion = TextButton::new(0.0, 0.0, 270.0, 45.0, "Off", BLACK, DARKGREEN, 30);
+        btn_reduced_motion.with_layer(Layer::Overlay);
+        let mut btn_power_saving = TextButton::new(0.0, 0.0, 270.0, 45.0, "On", BLACK, DARKGREEN, 30);
+        btn_power_saving.with_layer(Layer::Overlay);
+        let mut btn_cursor = TextButton::new(0.0, 0.0, 270.0, 45.0, "Game", BLACK, DARKGREEN, 30);
+        btn_cursor.with_layer(Layer::Overlay);
+        let mut btn_stream_mode = TextButton::new(0.0, 0.0, 270.0, 45.0, "Off", BLACK, DARKGREEN, 30);
+        btn_stream_mode.with_layer(Layer::Overlay);
+        let mut btn_card_style = TextButton::new(0.0, 0.0, 270.0, 45.0, "Pictures", BLACK, DARKGREEN, 30);
+        btn_card_style.with_layer(Layer::Overlay);
+        let mut btn_announce = TextButton::new(0.0, 0.0, 270.0, 45.0, "Off", BLACK, DARKGREEN, 30);
+        btn_announce.with_layer(Layer::Overlay);
+        let mut btn_quality = TextButton::new(0.0, 0.0, 270.0, 45.0, "Auto", BLACK, DARKGREEN, 30);
+        btn_quality.with_layer(Layer::Overlay);
+        let mut btn_hand_totals = TextButton::new(0.0, 0.0, 270.0, 45.0, "7/17", BLACK, DARKGREEN, 30);
+        btn_hand_totals.with_layer(Layer::Overlay);
+        let mut btn_theme = TextButton::new(0.0, 0.0, 270.0, 45.0, "Green", BLACK, DARKGREEN, 30);
+        btn_theme.with_layer(Layer::Overlay);
+        let mut btn_scene_fade = TextButton::new(0.0, 0.0, 270.0, 45.0, "Crossfade", BLACK, DARKGREEN, 30);
+        btn_scene_fade.with_layer(Layer::Overlay);
+        let mut btn_text_scale = TextButton::new(0.0, 0.0, 270.0, 45.0, "100%", BLACK, DARKGREEN, 30);
+        btn_text_scale.with_layer(Layer::Overlay);
+        let mut btn_display_close = TextButton::new(0.0, 0.0, 200.0, 60.0, "Back", BLACK, DARKGREEN, 35);
+        btn_display_close.with_layer(Layer::Overlay);
+        let mut btn_rules_close = TextButton::new(0.0, 0.0, 200.0, 60.0, "Back", BLACK, DARKGREEN, 35);
+        btn_rules_close.with_layer(Layer::Overlay);
+        let mut btn_table_rules = TextButton::new(0.0, 0.0, 270.0, 45.0, "Standard", BLACK, DARKGREEN, 30);
+        btn_table_rules.with_layer(Layer::Overlay);
+        btn_table_rules.set_disabled_reason(FINISH_HAND_FIRST);
+        let mut btn_edit_rules = TextButton::new(0.0, 0.0, 190.0, 45.0, "Make your own", BLACK, DARKGREEN, 26);
+        btn_edit_rules.with_layer(Layer::Overlay);
+        let mut btn_stakes = TextButton::new(0.0, 0.0, 270.0, 45.0, "$5 to $2,500", BLACK, DARKGREEN, 30);
+        btn_stakes.with_layer(Layer::Overlay);
+        btn_stakes.set_disabled_reason(FINISH_HAND_FIRST);
+        let mut btn_low_bankroll = TextButton::new(0.0, 0.0, 190.0, 45.0, "Warn: 20 bets", BLACK, DARKGREEN, 26);
+        btn_low_bankroll.with_layer(Layer::Overlay);
+        let mut btn_chart = TextButton::new(0.0, 0.0, 260.0, 60.0, "Strategy chart", BLACK, DARKGREEN, 35);
+        btn_chart.with_layer(Layer::Overlay);
+        let mut btn_chart_close = TextButton::new(0.0, 0.0, 200.0, 60.0, "Close", BLACK, DARKGREEN, 35);
+        btn_chart_close.with_layer(Layer::Overlay);
+        let btn_goal_picks = GOALS
+            .iter()
+            .map(|_| {
+                let mut button = TextButton::new(0.0, 0.0, 90.0, 40.0, "Off", BLACK, DARKGREEN, 28);
+                button.with_layer(Layer::Overlay);
+                button.set_disabled_reason(FINISH_HAND_FIRST);
+                button
+            })
+            .collect();
+        let mut btn_goals_close = TextButton::new(0.0, 0.0, 200.0, 60.0, "Back", BLACK, DARKGREEN, 35);
+        btn_goals_close.with_layer(Layer::Overlay);
+        let mut btn_stats = TextButton::new(0.0, 0.0, 200.0, 60.0, "Stats", BLACK, DARKGREEN, 35);
+        btn_stats.with_layer(Layer::Overlay);
+        let mut btn_stats_close = TextButton::new(0.0, 0.0, 200.0, 60.0, "Back", BLACK, DARKGREEN, 35);
+        btn_stats_close.with_layer(Layer::Overlay);
+        let mut btn_play_log = TextButton::new(0.0, 0.0, 110.0, 45.0, "Off", BLACK, DARKGREEN, 30);
+        btn_play_log.with_layer(Layer::Overlay);
+        let mut btn_play_log_clear = TextButton::new(0.0, 0.0, 200.0, 45.0, "Clear (0)", BLACK, DARKGREEN, 26);
+        btn_play_log_clear.with_layer(Layer::Overlay);
+        let mut btn_whats_new_close = TextButton::new(0.0, 0.0, 200.0, 60.0, "Back", BLACK, DARKGREEN, 35);
+        btn_whats_new_close.with_layer(Layer::Overlay);
+        let mut btn_reveal_shoe = TextButton::new(0.0, 0.0, 200.0, 60.0, "Reveal Shoe", BLACK, DARKGREEN, 30);
+        btn_reveal_shoe.with_layer(Layer::Overlay);
+        let mut btn_hand_close = TextButton::new(0.0, 0.0, 200.0, 60.0, "Close", BLACK, DARKGREEN, 35);
+        btn_hand_close.with_layer(Layer::Overlay);
+        let mut btn_copy_seed = TextButton::new(0.0, 0.0, 200.0, 60.0, "Copy seed", BLACK, DARKGREEN, 30);
+        btn_copy_seed.with_layer(Layer::Overlay);
+        let mut btn_famous = TextButton::new(0.0, 0.0, 180.0, 60.0, "Famous hands", BLACK, DARKGREEN, 30);
+        btn_famous.with_layer(Layer::Overlay);
+        let mut btn_famous_stop = TextButton::new(0.0, 0.0, 240.0, 60.0, "Play normally", BLACK, DARKGREEN, 30);
+        btn_famous_stop.with_layer(Layer::Overlay);
+        btn_famous_stop.set_disabled_reason("No famous hand is being played");
+        let mut btn_famous_close = TextButton::new(0.0, 0.0, 200.0, 60.0, "Back", BLACK, DARKGREEN, 35);
+        btn_famous_close.with_layer(Layer::Overlay);
+        let btn_theme_picks = THEMES
+            .iter()
+            .map(|theme| {
+                let felt = theme.felt;
+                let lit = Color::new(felt.r + 0.1, felt.g + 0.1, felt.b + 0.1, 1.0);
+                let mut button = TextButton::new(0.0, 0.0, 165.0, 50.0, theme.name, felt, lit, 30);
+                button.with_layer(Layer::Overlay);
+                button
+            })
+            .collect();
+        let mut btn_theme_sample = TextButton::new(0.0, 0.0, 170.0, 65.0, "Hit", BLACK, DARKGRAY, 35);
+        btn_theme_sample.with_layer(Layer::Overlay);
+        let mut btn_theme_apply = TextButton::new(0.0, 0.0, 200.0, 60.0, "Apply", BLACK, DARKGREEN, 35);
+        btn_theme_apply.with_layer(Layer::Overlay);
+        let mut btn_theme_cancel = TextButton::new(0.0, 0.0, 200.0, 60.0, "Cancel", BLACK, DARKGREEN, 35);
+        btn_theme_cancel.with_layer(Layer::Overlay);
+        let mut lbl_theme_sample = Label::new("Dealer shows a King", 0.0, 0.0, 28);
+        lbl_theme_sample.with_colors(WHITE, Some(Color::new(0.0, 0.0, 0.0, 0.4))).with_round(5.0).with_layer(Layer::Overlay);
+        let mut lbl_explainer = Label::new("", 0.0, 0.0, 24);
+        lbl_explainer.with_colors(WHITE, Some(Color::new(0.0, 0.0, 0.0, 0.5))).with_round(4.0).set_visible(false);
+        let mut lbl_version = Label::new(version::build_text(), 0.0, 0.0, 18);
+        lbl_version.with_colors(LIGHTGRAY, None);
+        let chip_atlas = ChipAtlas::new(tm);
+        let mut felt = Felt::new(&TableLayout::compact());
+        felt.set_chip_atlas(chip_atlas.clone());
+        let mut btn_fairness_close = TextButton::new(0.0, 0.0, 200.0, 60.0, "Close", BLACK, DARKGREEN, 35);
+        btn_fairness_close.with_layer(Layer::Overlay);
+        let mut table = Self {
+            player_cards,
+            dealer_cards,
+            card_pool,
+            btn_exit,
+            btn_settings,
+            btn_screenshot,
+            btn_fairness,
+            btn_deal,
+            btn_hit,
+            btn_stand,
+            btn_replay,
+            btn_rebet,
+            btn_rebet_deal,
+            btn_quick_bets,
+            btn_percent_bets,
+            btn_layout_prev,
+            btn_layout_next,
+            btn_log_level,
+            btn_decision_time,
+            btn_timeout_action,
+            btn_no_contest,
+            btn_dealer_peeks,
+            btn_hand_count,
+            btn_animation_speed,
+            btn_currency,
+            btn_trainer,
+            btn_odds,
+            btn_squeeze,
+            btn_advisor,
+            btn_settings_close,
+            sld_music,
+            sld_sfx,
+            sld_duck,
+            btn_switch_profile,
+            btn_export_profile,
+            btn_import_profile,
+            btn_display,
+            btn_rules,
+            btn_goals,
+            btn_whats_new,
+            btn_fullscreen,
+            btn_resolution,
+            btn_target_fps,
+            btn_vsync,
+            btn_reduced_motion,
+            btn_power_saving,
+            btn_cursor,
+            btn_stream_mode,
+            btn_card_style,
+            btn_announce,
+            btn_quality,
+            btn_hand_totals,
+            btn_theme,
+            btn_scene_fade,
+            btn_text_scale,
+            btn_display_close,
+            btn_rules_close,
+            btn_table_rules,
+            btn_edit_rules,
+            btn_stakes,
+            btn_low_bankroll,
+            btn_chart,
+            btn_chart_close,
+            btn_goal_picks,
+            btn_goals_close,
+            btn_stats,
+            btn_stats_close,
+            btn_play_log,
+            btn_play_log_clear,
+            btn_whats_new_close,
+            btn_reveal_shoe,
+            btn_fairness_close,
+            btn_hand_close,
+            btn_copy_seed,
+            btn_famous,
+            btn_famous_picks: Vec::new(),
+            btn_famous_stop,
+            btn_famous_close,
+            btn_theme_picks,
+            btn_theme_sample,
+            btn_theme_apply,
+            btn_theme_cancel,
+            lbl_theme_sample,
+            lbl_dealerhand: Label::new("Dealer's Hand", 0.0, 0.0, 30),
+            lbl_winner: Label::new("", 0.0, 0.0, 50),
+            lbl_explainer,
+            lbl_playerhand: Label::new("Your Hand", 0.0, 0.0, 30),
+            lbl_notice: Label::new("", 0.0, 0.0, 25),
+            lbl_sync: Label::new("", 0.0, 0.0, 25),
+            lbl_version,
+            nameplate: Nameplate::new(tm),
+            shoe_view: ShoeView::new(&TableLayout::compact()),
+            shoe_meter: ShoeMeter::new(&TableLayout::compact()),
+            player_badges: (0..MAX_HANDS).map(|_| HandBadge::new()).collect(),
+            dealer_badge: HandBadge::new(),
+            hole_card: HoleCard::new(),
+            squeeze: Squeeze::new(),
+            squeeze_slot: (0, 0),
+            felt,
+            chips: DENOMINATIONS.iter().map(|value| InteractiveChip::new(*value)).collect(),
+            chip_atlas,
+            streak: StreakIndicator::new(),
+            clean_hud: false,
+            #[cfg(all(feature = "gif_recorder", not(target_arch = "wasm32")))]
+            recorder: GifRecorder::new(),
+            results: ResultsStrip::new(),
+            snapshots: HandSnapshots::new(),
+            layout: TableLayout::compact(),
+            hands: 1,
+            active_hand: None,
+        };
+        table.name_widgets();
+        table
+    }
+
+    // Give every button and label its field's name as its ID, so modules::ui can find them
+    fn name_widgets(&mut self) {
+        self.for_each_widget(
+            |id, button| {
+                button.with_id(id);
+            },
+            |id, label| {
+                label.with_id(id);
+            },
+        );
+    }
+
+    // Fit every button's and label's text to the text size setting again (see widget_style.rs)
+    fn rescale_text(&mut self) {
+        self.for_each_widget(
+            |_, button| {
+                button.rescale_text();
+            },
+            |_, label| {
+                label.rescale_text();
+            },
+        );
+        for button in self.btn_famous_picks.iter_mut() {
+            button.rescale_text();
+        }
+        for badge in self.player_badges.iter_mut().chain([&mut self.dealer_badge]) {
+            badge.rescale_text();
+        }
+    }
+
+    // Call `button` with every button and `label` with every label, with their field's name
+    fn for_each_widget(&mut self, mut button: impl FnMut(&str, &mut TextButton), mut label: impl FnMut(&str, &mut Label)) {
+        let buttons: [(&str, &mut TextButton); 71] = [
+            ("btn_exit", &mut self.btn_exit),
+            ("btn_settings", &mut self.btn_settings),
+            ("btn_screenshot", &mut self.btn_screenshot),
+            ("btn_fairness", &mut self.btn_fairness),
+            ("btn_deal", &mut self.btn_deal),
+            ("btn_hit", &mut self.btn_hit),
+            ("btn_stand", &mut self.btn_stand),
+            ("btn_replay", &mut self.btn_replay),
+            ("btn_rebet", &mut self.btn_rebet),
+            ("btn_rebet_deal", &mut self.btn_rebet_deal),
+            ("btn_layout_prev", &mut self.btn_layout_prev),
+            ("btn_layout_next", &mut self.btn_layout_next),
+            ("btn_log_level", &mut self.btn_log_level),
+            ("btn_decision_time", &mut self.btn_decision_time),
+            ("btn_timeout_action", &mut self.btn_timeout_action),
+            ("btn_no_contest", &mut self.btn_no_contest),
+            ("btn_dealer_peeks", &mut self.btn_dealer_peeks),
+            ("btn_hand_count", &mut self.btn_hand_count),
+            ("btn_animation_speed", &mut self.btn_animation_speed),
+            ("btn_currency", &mut self.btn_currency),
+            ("btn_trainer", &mut self.btn_trainer),
+            ("btn_odds", &mut self.btn_odds),
+            ("btn_squeeze", &mut self.btn_squeeze),
+            ("btn_advisor", &mut self.btn_advisor),
+            ("btn_settings_close", &mut self.btn_settings_close),
+            ("btn_switch_profile", &mut self.btn_switch_profile),
+            ("btn_export_profile", &mut self.btn_export_profile),
+            ("btn_import_profile", &mut self.btn_import_profile),
+            ("btn_display", &mut self.btn_display),
+            ("btn_rules", &mut self.btn_rules),
+            ("btn_goals", &mut self.btn_goals),
+            ("btn_whats_new", &mut self.btn_whats_new),
+            ("btn_fullscreen", &mut self.btn_fullscreen),
+            ("btn_resolution", &mut self.btn_resolution),
+            ("btn_target_fps", &mut self.btn_target_fps),
+            ("btn_vsync", &mut self.btn_vsync),
+            ("btn_reduced_motion", &mut self.btn_reduced_motion),
+            ("btn_power_saving", &mut self.btn_power_saving),
+            ("btn_cursor", &mut self.btn_cursor),
+            ("btn_stream_mode", &mut self.btn_stream_mode),
+            ("btn_card_style", &mut self.btn_card_style),
+            ("btn_announce", &mut self.btn_announce),
+            ("btn_quality", &mut self.btn_quality),
+            ("btn_hand_totals", &mut self.btn_hand_totals),
+            ("btn_theme", &mut self.btn_theme),
+            ("btn_scene_fade", &mut self.btn_scene_fade),
+            ("btn_text_scale", &mut self.btn_text_scale),
+            ("btn_display_close", &mut self.btn_display_close),
+            ("btn_rules_close", &mut self.btn_rules_close),
+            ("btn_table_rules", &mut self.btn_table_rules),
+            ("btn_stakes", &mut self.btn_stakes),
+            ("btn_low_bankroll", &mut self.btn_low_bankroll),
+            ("btn_edit_rules", &mut self.btn_edit_rules),
+            ("btn_chart", &mut self.btn_chart),
+            ("btn_chart_close", &mut self.btn_chart_close),
+            ("btn_goals_close", &mut self.btn_goals_close),
+            ("btn_stats", &mut self.btn_stats),
+            ("btn_stats_close", &mut self.btn_stats_close),
+            ("btn_play_log", &mut self.btn_play_log),
+            ("btn_play_log_clear", &mut self.btn_play_log_clear),
+            ("btn_whats_new_close", &mut self.btn_whats_new_close),
+            ("btn_reveal_shoe", &mut self.btn_reveal_shoe),
+            ("btn_fairness_close", &mut self.btn_fairness_close),
+            ("btn_hand_close", &mut self.btn_hand_close),
+            ("btn_copy_seed", &mut self.btn_copy_seed),
+            ("btn_famous", &mut self.btn_famous),
+            ("btn_famous_stop", &mut self.btn_famous_stop),
+            ("btn_famous_close", &mut self.btn_famous_close),
+            ("btn_theme_sample", &mut self.btn_theme_sample),
+            ("btn_theme_apply", &mut self.btn_theme_apply),
+            ("btn_theme_cancel", &mut self.btn_theme_cancel),
+        ];
+        for (id, widget) in buttons {
+            button(id, widget);
+        }
+        let rows: [(&str, &mut Vec<TextButton>); 4] = [
+            ("btn_quick_bets", &mut self.btn_quick_bets),
+            ("btn_percent_bets", &mut self.btn_percent_bets),
+            ("btn_goal_picks", &mut self.btn_goal_picks),
+            ("btn_theme_picks", &mut self.btn_theme_picks),
+        ];
+        for (id, row) in rows {
+            for (i, widget) in row.iter_mut().enumerate() {
+                button(&format!("{}_{}", id, i), widget);
+            }
+        }
+        let labels: [(&str, &mut Label); 8] = [
+            ("lbl_dealerhand", &mut self.lbl_dealerhand),
+            ("lbl_winner", &mut self.lbl_winner),
+            ("lbl_explainer", &mut self.lbl_explainer),
+            ("lbl_playerhand", &mut self.lbl_playerhand),
+            ("lbl_notice", &mut self.lbl_notice),
+            ("lbl_sync", &mut self.lbl_sync),
+            ("lbl_version", &mut self.lbl_version),
+            ("lbl_theme_sample", &mut self.lbl_theme_sample),
+        ];
+        for (id, widget) in labels {
+            label(id, widget);
+        }
+    }
+
+    // A button for each famous hand in the gallery
+    fn show_famous_hands(&mut self, hands: &[FamousHand]) {
+        self.btn_famous_picks = hands
+            .iter()
+            .enumerate()
+            .map(|(i, hand)| {
+                let mut button = TextButton::new(0.0, 0.0, 340.0, 45.0, hand.name.as_str(), BLACK, DARKGREEN, 28);
+                button.with_layer(Layer::Overlay).with_id(&format!("btn_famous_picks_{}", i));
+                button
+            })
+            .collect();
+    }
+
+    // Show a theme on the sample widgets without applying it
+    fn preview_theme(&mut self, index: usize) {
+        let theme = &THEMES[index];
+        self.btn_theme_sample.with_style(&theme.button_style());
+        self.lbl_theme_sample.set_text(format!("{} felt: dealer shows a King", theme.name));
+    }
+
+    // Move every widget to where the layout preset says it goes, the cards glide there if `animate` is set
+    fn apply_layout(&mut self, layout: &TableLayout, animate: bool) {
+        let move_time = if animate { LAYOUT_MOVE_TIME } else { 0.0 };
+        for (hand, slots) in self.player_cards.iter_mut().enumerate() {
+            for (i, card) in slots.iter_mut().enumerate() {
+                card.move_to(layout.hand_card_pos(hand, self.hands, i), move_time, Easing::EaseInOutQuad);
+                card.set_size(layout.card_width, layout.card_height);
+            }
+        }
+        for (i, card) in self.dealer_cards.iter_mut().enumerate() {
+            card.move_to(layout.dealer_card_pos(i), move_time, Easing::EaseInOutQuad);
+            card.set_size(layout.card_width, layout.card_height);
+        }
+        let buttons = [
+            (&mut self.btn_exit, layout.exit),
+            (&mut self.btn_settings, layout.settings),
+            (&mut self.btn_screenshot, layout.screenshot),
+            (&mut self.btn_fairness, layout.fairness),
+            (&mut self.btn_deal, layout.deal),
+            (&mut self.btn_hit, layout.hit),
+            (&mut self.btn_stand, layout.stand),
+            (&mut self.btn_replay, layout.replay),
+            (&mut self.btn_rebet, layout.rebet),
+            (&mut self.btn_rebet_deal, layout.rebet_deal),
+        ];
+        for (button, area) in buttons {
+            button.update_position(area.x, area.y, Some(area.w), Some(area.h));
+        }
+        // The recent bets share their row evenly
+        let gap = 10.0;
+        let area = layout.quick_bets;
+        let width = (area.w - gap * (RECENT_BETS - 1) as f32) / RECENT_BETS as f32;
+        let mut quick_bets: Vec<&mut dyn Placeable> = self
+            .btn_quick_bets
+            .iter_mut()
+            .map(|button| button.update_position(0.0, 0.0, Some(width), Some(area.h)) as &mut dyn Placeable)
+            .collect();
+        Row::new(area.x, area.y).spacing(gap).place(&mut quick_bets);
+        let labels = [
+            (&mut self.lbl_dealerhand, layout.dealer_hand),
+            (&mut self.lbl_playerhand, layout.player_hand),
+            (&mut self.lbl_winner, layout.winner),
+            (&mut self.lbl_explainer, layout.explainer),
+            (&mut self.lbl_notice, layout.notice),
+            (&mut self.lbl_sync, layout.sync_status),
+        ];
+        for (label, spot) in labels {
+            label.set_position(spot.x, spot.y);
+        }
+        let seat = layout.seats.first().map_or(Rect::default(), |seat| Rect::new(seat.x, seat.y, seat.w, seat.h));
+        self.nameplate.set_area(seat);
+        self.lbl_version.set_position(10.0, layout.virtual_height - 10.0);
+        // The chips share the tray along its longer side
+        let tray = layout.chip_tray;
+        let count = self.chips.len() as f32;
+        let across = tray.w >= tray.h;
+        let step = if across { tray.w / count } else { tray.h / count };
+        let radius = (step / 2.0).min(tray.w.min(tray.h) / 2.0) - 2.0;
+        for (i, chip) in self.chips.iter_mut().enumerate() {
+            let along = step * (i as f32 + 0.5);
+            let center = if across { vec2(tray.x + along, tray.y + tray.h / 2.0) } else { vec2(tray.x + tray.w / 2.0, tray.y + along) };
+            chip.set_position(center, radius);
+        }
+        // The bankroll bets share their area the same way, with a gap between them
+        let area = layout.percent_bets;
+        let count = self.btn_percent_bets.len() as f32;
+        let gap = 4.0;
+        let across = area.w >= area.h;
+        let step = if across { (area.w + gap) / count } else { (area.h + gap) / count };
+        for (i, button) in self.btn_percent_bets.iter_mut().enumerate() {
+            let along = step * i as f32;
+            if across {
+                button.update_position(area.x + along, area.y, Some(step - gap), Some(area.h));
+            } else {
+                button.update_position(area.x, area.y + along, Some(area.w), Some(step - gap));
+            }
+        }
+        self.streak.set_position(layout.streak.x, layout.streak.y);
+        self.results.set_position(layout.results.x, layout.results.y);
+        self.shoe_view.set_layout(layout);
+        self.shoe_meter.set_layout(layout);
+        self.felt.set_layout(layout);
+        self.layout = layout.clone();
+    }
+
+    // Play a different number of hands: take or give back card slots, and move them and the bet spots to match
+    fn set_hands(&mut self, hands: usize) {
+        self.hands = hands;
+        while self.player_cards.len() > hands {
+            let slots = self.player_cards.pop().unwrap();
+            self.card_pool.give_back_all(slots);
+        }
+        while self.player_cards.len() < hands {
+            self.player_cards.push(self.card_pool.take_row(CARD_SLOTS));
+        }
+        self.shoe_view.set_hands(hands);
+        self.felt.set_bets(&vec![0; hands]);
+        let layout = self.layout.clone();
+        self.apply_layout(&layout, false);
+    }
+
+    // Move each hand's total badge onto its newest card
+    fn show_hands(&mut self, player_hands: &[Hand], dealer_hand: &Hand) {
+        let layout = &self.layout;
+        let corner = vec2(layout.card_width, 0.0);
+        for (hand, badge) in self.player_badges.iter_mut().enumerate() {
+            let Some(player_hand) = player_hands.get(hand) else {
+                badge.show(&Hand::new(), Vec2::ZERO);
+                continue;
+            };
+            // The last round's hands are still shown when the hand count drops, with no slots left under them
+            let slots = self.player_cards.get(hand).map_or(CARD_SLOTS, Vec::len);
+            let player_last = player_hand.len().clamp(1, slots) - 1;
+            badge.show(player_hand, layout.hand_card_pos(hand, self.hands, player_last) + corner);
+        }
+        let dealer_last = dealer_hand.len().clamp(1, self.dealer_cards.len()) - 1;
+        self.dealer_badge.show(dealer_hand, layout.dealer_card_pos(dealer_last) + corner);
+    }
+
+    // Move the cards that are still flying out of the shoe or gliding to a new layout.
+    // The peek waits until they have landed.
+    fn update(&mut self, dt: f32) -> bool {
+        for card in self.player_cards.iter_mut().flatten().chain(self.dealer_cards.iter_mut()) {
+            card.update(dt);
+        }
+        self.shoe_view.update(dt);
+        self.shoe_view.apply(&mut self.player_cards, &mut self.dealer_cards);
+        !self.shoe_view.is_busy() && self.hole_card.update(dt)
+    }
+
+    // Lay a hit card face down over its slot for the player to squeeze
+    fn start_squeeze(&mut self, tm: &TextureManager, hand: usize, slot: usize, card: &Card) {
+        self.squeeze_slot = (hand, slot);
+        self.squeeze.start(tm.get_texture(&card.texture_path()));
+    }
+
+    // Follow the player's drag on the squeezed card, true once when it has been turned over
+    fn update_squeeze(&mut self) -> bool {
+        let (hand, slot) = self.squeeze_slot;
+        let Some(card) = self.player_cards.get(hand).and_then(|cards| cards.get(slot)) else {
+            return false;
+        };
+        self.squeeze.update(card.pos(), card.size())
+    }
+
+    // Show the cards already on the table again in the card style now in use
+    fn restyle_cards(&mut self, tm: &TextureManager, player_hands: &[Hand], dealer_hand: &Hand) {
+        self.snapshots.clear();
+        let player_slots = self.player_cards.iter_mut().zip(player_hands);
+        let hands = player_slots.map(|(slots, hand)| (hand, slots)).chain([(dealer_hand, &mut self.dealer_cards)]);
+        for (hand, slots) in hands {
+            // A slot still showing the empty picture is waiting for its card to land
+            for (card, slot) in hand.cards().iter().zip(slots.iter_mut()) {
+                if slot.get_filename() != EMPTY_CARD_PATH {
+                    self.card_pool.show(tm, slot, card);
+                }
+            }
+        }
+    }
+
+    // Slide the cards from every hand into the discard tray and empty the slots
+    fn clear_cards(&mut self, tm: &TextureManager, player_hands: &[Hand], dealer_hand: &Hand) {
+        self.squeeze.cancel();
+        self.shoe_view.clear_table(&mut self.player_cards, &mut self.dealer_cards);
+        let player_slots = self.player_cards.iter_mut().zip(player_hands);
+        let hands = player_slots.map(|(slots, hand)| (hand, slots)).chain([(dealer_hand, &mut self.dealer_cards)]);
+        for (hand, slots) in hands {
+            for (card, slot) in hand.cards().iter().zip(slots.iter()) {
+                if let Some(texture) = tm.get_texture(&card.texture_path()) {
+                    self.shoe_view.discard(texture, slot.pos());
+                }
+            }
+            for slot in slots.iter_mut() {
+                self.card_pool.clear(slot);
+            }
+        }
+    }
+
+    // Draw the felt, shoe, cards and labels (buttons draw themselves when click() is called)
+    fn draw(&self, shoe: &Shoe) {
+        self.felt.draw();
+        self.shoe_view.draw_shoe(shoe);
+        // A glow behind the cards of the hand being played, or a gold bar under it without the shader
+        if let Some(hand) = self.active_hand
+            && self.hands > 1
+        {
+            let layout = &self.layout;
+            let start = layout.hand_card_pos(hand, self.hands, 0);
+            let width = layout.card_width + layout.hand_card_spacing * 4.0;
+            let y = start.y + layout.card_height + 6.0;
+            let cards = self.player_cards.get(hand).map_or(Rect::new(start.x, start.y, 0.0, 0.0), |slots| {
+                slots.iter().filter(|card| card.get_filename() != EMPTY_CARD_PATH).fold(Rect::new(start.x, start.y, 0.0, 0.0), |around, card| {
+                    around.combine_with(Rect::new(card.pos().x, card.pos().y, card.size().x, card.size().y))
+                })
+            });
+            layers::push(Layer::Table, move || {
+                if !materials::draw_glow(cards, ACTIVE_GLOW, Color::new(1.0, 0.84, 0.0, 0.55)) {
+                    draw_rectangle(start.x, y, width, 5.0, GOLD);
+                }
+            });
+        }
+        for (hand, cards) in self.player_cards.iter().take(self.hands).enumerate() {
+            for (slot, card) in cards.iter().enumerate() {
+                // The card being squeezed is drawn face down by the squeeze until it is turned over
+                if !self.squeeze.is_active() || self.squeeze_slot != (hand, slot) {
+                    card.draw();
+                }
+            }
+        }
+        // The hole card's slot stays empty while the card is being turned over on top of it
+        for (slot, card) in self.dealer_cards.iter().enumerate() {
+            if slot != 1 || !self.hole_card.is_turning() {
+                card.draw();
+            }
+        }
+        self.hole_card.draw(self.dealer_cards[1].pos(), self.dealer_cards[1].size());
+        if let Some(card) = self.player_cards.get(self.squeeze_slot.0).and_then(|cards| cards.get(self.squeeze_slot.1)) {
+            self.squeeze.draw(card.pos(), card.size());
+        }
+        self.shoe_view.draw_discards();
+        self.lbl_dealerhand.draw();
+        self.lbl_playerhand.draw();
+        for badge in self.player_badges.iter().take(self.hands) {
+            badge.draw();
+        }
+        self.dealer_badge.draw();
+        self.nameplate.draw();
+        // A clean HUD for streaming keeps to the cards, hands and bankroll
+        if self.clean_hud {
+            return;
+        }
+        self.shoe_meter.draw(shoe);
+        self.lbl_winner.draw();
+        self.lbl_explainer.draw();
+        self.results.draw();
+        self.lbl_notice.draw();
+        self.lbl_sync.draw();
+        self.streak.draw();
+        self.lbl_version.draw();
+    }
+
+    // Handle and draw the chip tray, returns the chip the player did something with and what
+    fn update_chips(&mut self, long_press_seconds: f32) -> Option<(i64, ChipAction)> {
+        // Every chip has to be updated to be drawn, so look at all of them before picking one
+        let actions: Vec<(i64, Option<ChipAction>)> =
+            self.chips.iter_mut().map(|chip| (chip.value(), chip.update(&self.chip_atlas, long_press_seconds))).collect();
+        actions.into_iter().find_map(|(value, action)| Some((value, action?)))
+    }
+
+    // A small picture of the hand under the pointer in the results strip, just below the strip
+    fn show_hovered_hand(&mut self, tm: &TextureManager, history: &HandHistory) {
+        let hovered = self.results.hovered_hand().and_then(|number| history.hands().find(|record| record.number == number));
+        let Some(record) = hovered.filter(|_| !self.clean_hud) else {
+            return;
+        };
+        let texture = self.snapshots.get(tm, record);
+        let pos = vec2(self.layout.results.x, self.layout.results.y + 40.0);
+        layers::push(Layer::Hud, move || {
+            let params = DrawTextureParams { dest_size: Some(SNAPSHOT_SIZE * 0.5), ..Default::default() };
+            draw_texture_ex(&texture, pos.x, pos.y, WHITE, params);
+        });
+    }
+
+    // The keyboard shortcut on each button that has one, for while Tab is held. Hit and Stand
+    // share their places with Rebet and Rebet & Deal, so only the pair on the table gets them.
+    fn draw_key_hints(&self, keys: &KeyMap, playing: bool, round_over: bool) {
+        let mut buttons = vec![(&self.btn_deal, KeyAction::Deal), (&self.btn_replay, KeyAction::Replay), (&self.btn_screenshot, KeyAction::Screenshot)];
+        if playing {
+            buttons.extend([(&self.btn_hit, KeyAction::Hit), (&self.btn_stand, KeyAction::Stand)]);
+        }
+        if round_over {
+            buttons.extend([(&self.btn_rebet, KeyAction::Rebet), (&self.btn_rebet_deal, KeyAction::RebetDeal)]);
+        }
+        for (button, action) in buttons {
+            keys.draw_hint(button, action);
+        }
+    }
+
+    // The line under the result banner, hidden while there is nothing to explain
+    fn show_explainer(&mut self, text: impl Into<String>) {
+        let text = text.into();
+        self.lbl_explainer.set_visible(!text.is_empty());
+        self.lbl_explainer.set_text(text);
+    }
+
+    // Show who is playing and how much they have to spend, bets in play left out
+    fn show_profile(&mut self, info: &ProfileInfo, ledger: &Ledger) {
+        self.nameplate.set_player(&info.name, info.avatar_path());
+        self.nameplate.set_bankroll(ledger.available(info.bankroll));
+    }
+
+    // Rebet needs a wager from the last round that the bankroll still covers
+    fn show_rebet(&mut self, bet: &Bet, bankroll: i64) {
+        let last = bet.last_total(self.hands);
+        let reason = if last == 0 {
+            Some("There was no bet last round")
+        } else if last > bankroll {
+            Some("Your bankroll can't cover the last bet")
+        } else {
+            None
+        };
+        for button in [&mut self.btn_rebet, &mut self.btn_rebet_deal] {
+            button.enabled = reason.is_none();
+            match reason {
+                Some(reason) => button.set_disabled_reason(reason),
+                None => button.clear_disabled_reason(),
+            };
+        }
+    }
+
+    // Label the recent bet buttons, the ones the bankroll can't cover on every hand are greyed out
+    fn show_quick_bets(&mut self, bet: &Bet, bankroll: i64) {
+        for (i, button) in self.btn_quick_bets.iter_mut().enumerate() {
+            let Some(amount) = bet.recent().get(i) else {
+                button.set_text("");
+                continue;
+            };
+            button.set_text(i18n::money(*amount));
+            button.enabled = amount * self.hands as i64 <= bankroll;
+            if button.enabled {
+                button.clear_disabled_reason();
+            } else {
+                button.set_disabled_reason("Your bankroll can't cover that bet on every hand");
+            }
+        }
+    }
+
+    // Work the bankroll bets out from the bankroll, the ones it can't cover on every hand are greyed out
+    fn show_percent_bets(&mut self, bankroll: i64) {
+        for (button, percent) in self.btn_percent_bets.iter_mut().zip(PERCENT_BETS) {
+            let amount = percent_bet(bankroll, percent);
+            button.enabled = amount * self.hands as i64 <= bankroll;
+            if button.enabled {
+                button.clear_disabled_reason();
+            } else {
+                button.set_disabled_reason("Your bankroll can't cover that bet on every hand");
+            }
+        }
+    }
+
+    // Show each hand's bet on its spot
+    fn show_bets(&mut self, bet: &Bet) {
+        let amounts: Vec<i64> = (0..self.hands).map(|hand| bet.amount(hand)).collect();
+        self.felt.set_bets(&amounts);
+    }
+
+    // Show which goals are picked on the achievements panel's buttons
+    fn show_goals(&mut self, goals: &SessionGoals) {
+        for (button, goal) in self.btn_goal_picks.iter_mut().zip(&GOALS) {
+            button.set_text(if goals.is_picked(goal.id) { "On" } else { "Off" });
+        }
+    }
+
+    // Show the current values on the settings panel's buttons
+    fn show_settings(&mut self, settings: &Settings) {
+        self.btn_log_level.set_text(settings.log_level().name());
+        self.btn_decision_time.set_text(settings.decision_time_text());
+        self.btn_timeout_action.set_text(settings.timeout_action_text());
+        self.btn_timeout_action.enabled = settings.decision_seconds > 0;
+        self.btn_no_contest.set_text(settings.no_contest_text());
+        self.btn_dealer_peeks.set_text(settings.rules.dealer_peeks_text());
+        self.btn_table_rules.set_text(settings.rules_name());
+        self.btn_stakes.set_text(settings.rules.limits.text());
+        self.btn_low_bankroll.set_text(settings.low_bankroll_text());
+        self.btn_hand_count.set_text(settings.hand_count_text());
+        self.btn_animation_speed.set_text(settings.animation_speed_text());
+        self.btn_currency.set_text(settings.currency_text());
+        self.btn_trainer.set_text(settings.counting_trainer_text());
+        self.btn_odds.set_text(settings.odds_text());
+        self.btn_squeeze.set_text(settings.squeeze_text());
+        self.btn_advisor.set_text(settings.advisor_text());
+        self.btn_play_log.set_text(settings.analytics_text());
+        self.btn_fullscreen.set_text(settings.fullscreen_text());
+        self.btn_resolution.set_text(settings.resolution_text());
+        self.btn_target_fps.set_text(settings.target_fps_text());
+        self.btn_vsync.set_text(settings.vsync_text());
+        self.btn_reduced_motion.set_text(settings.reduced_motion_text());
+        self.btn_power_saving.set_text(settings.power_saving_text());
+        self.btn_cursor.set_text(settings.cursor_text());
+        self.btn_stream_mode.set_text(settings.stream_mode_text());
+        self.btn_card_style.set_text(settings.card_style_text());
+        self.btn_announce.set_text(settings.announce_text());
+        self.btn_quality.set_text(settings.quality_text());
+        self.btn_hand_totals.set_text(settings.hand_totals_text());
+        self.btn_theme.set_text(settings.theme_text());
+        self.btn_scene_fade.set_text(settings.scene_fade_text());
+        self.btn_text_scale.set_text(settings.text_scale_text());
+        self.clean_hud = settings.clean_hud();
+        self.felt.set_green_screen(settings.green_screen());
+        self.felt.set_rules_text(settings.rules.felt_text());
+        self.sld_music.set_value(settings.music_volume);
+        self.sld_sfx.set_value(settings.sfx_volume);
+        self.sld_duck.set_value(settings.duck_level);
+    }
+
+    // Show whether saves are reaching the cloud backend, and when unsent ones go again
+    fn show_sync_status(&mut self) {
+        let mut text = storage::sync_status().text();
+        if let Some(seconds) = storage::retry_in() {
+            text = format!("{}, retrying in {:.0}s", text, seconds.ceil());
+        }
+        if self.lbl_sync.get_text() != text {
+            self.lbl_sync.set_text(text);
+        }
+    }
+
+    // Keep the streak indicator in step with the profile's stats and the results strip with the history
+    fn show_stats(&mut self, stats: &Stats, history: &HandHistory) {
+        self.streak.show(stats);
+        self.results.show(history);
+    }
+}
+
+impl EventListener for Table {
+    fn on_event(&mut self, event: &GameEvent) {
+        self.shoe_view.on_event(event);
+        #[cfg(all(feature = "gif_recorder", not(target_arch = "wasm32")))]
+        self.recorder.on_event(event);
+    }
+}
+
+// A breakdown cell: what its hands came to, coloured by whether they made money
+fn tally_text(tally: Tally) -> (String, Color) {
+    match tally.net {
+        _ if tally.hands == 0 => ("-".to_string(), GRAY),
+        net if net > 0 => (i18n::signed_money(net), GREEN),
+        net if net < 0 => (i18n::signed_money(net), Color::new(1.0, 0.4, 0.4, 1.0)),
+        net => (i18n::money(net), WHITE),
+    }
+}
+
+// Text centred in a column of the stats grid that starts at `x`
+fn stats_cell_text(text: String, x: f32, y: f32, color: Color) {
+    let width = measure_text_cached(&text, None, 18).width;
+    overlay_text(text, x + (STATS_CELL_WIDTH - width) / 2.0, y, 18.0, color);
+}
+
+// The result banner's text
+fn outcome_text(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::PlayerWin => "You Win!",
+        Outcome::DealerWin => "Dealer Wins!",
+        Outcome::Push => "Draw!",
+        Outcome::NoWinner => "No Winner!",
+    }
+}
+
+// Where the current round is up to
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum GamePhase {
+    WaitingToDeal,
+    PlayerTurn,
+    RoundOver,
+}
+
+// Steps of the dealing and the dealer's turn, played out by a Timeline
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Cue {
+    PlayerCard(usize),
+    DealerUpCard,
+    StartPlayerTurn,
+    RevealHoleCard,
+    DealerDraw,
+}
+
+// Gap between cards going out during the deal, and between the dealer's moves
+const DEAL_GAP: f32 = 0.15;
+const DEALER_PAUSE: f32 = 0.6;
+const NO_CONTEST_PAUSE: f32 = 0.15; // Between the dealer's moves when they can't change the results
+
+// How long the dealer waits before the next move. Hurried when every hand is bust or a blackjack, unless the player
+// wants it played out; with the draw skipped there is only the hole card to wait for.
+fn dealer_pause(no_contest: bool, settings: &Settings) -> f32 {
+    if no_contest && settings.hurries_no_contest() { NO_CONTEST_PAUSE } else { DEALER_PAUSE }
+}
+
+// How long the table takes to dim behind a panel, and to come back
+const PANEL_FADE_SECONDS: f32 = 0.2;
+
+// Card slots in each hand and in the dealer's
+const CARD_SLOTS: usize = 5;
+
+// How far the glow around the hand being played reaches past its cards
+const ACTIVE_GLOW: f32 = 14.0;
+
+// How long the cards take to glide to their spots in a new layout
+const LAYOUT_MOVE_TIME: f32 = 0.4;
+
+// One tap on the bet spot adds this much
+const BET_STEP: i64 = 10;
+
+// How long the player gets to answer the even money question before it counts as "No"
+const EVEN_MONEY_SECONDS: f32 = 10.0;
+
+// The stats grid's columns and rows
+const STATS_CELL_WIDTH: f32 = 68.0;
+const STATS_ROW_HEIGHT: f32 = 22.0;
+
+// Tooltips for buttons that can't be pressed right now
+const DEAL_FIRST: &str = "Press Deal to start a hand first";
+const FINISH_HAND_FIRST: &str = "Finish the current hand first";
+const ROUND_OVER: &str = "The round is over, press Play Again";
+const DEALER_PLAYING: &str = "Wait for the dealer to finish";
+const SQUEEZE_FIRST: &str = "Drag the card up to see it first";
+
+// Why a hand's seed can't be copied
+const SEED_SECRET: &str = "Shown once this shoe is reshuffled, or revealed from the fairness log";
+const SEED_SET_UP: &str = "The cards were set up for this hand, a seed won't deal them again";
+
+// Under the play log switch on the Stats panel
+#[cfg(not(target_arch = "wasm32"))]
+const PLAY_LOG_NOTE: &str = "Your moves and results, written to save/analytics.jsonl on this computer. Nothing is sent anywhere.";
+#[cfg(target_arch = "wasm32")]
+const PLAY_LOG_NOTE: &str = "Your moves and results, kept in this browser. Nothing is sent anywhere.";
+
+// Why Hit is greyed out once the player's hand stops taking cards
+fn hit_disabled_reason(hand: &Hand, card_slots: usize) -> &'static str {
+    if hand.is_bust() {
+        "You busted, the hand is over"
+    } else if hand.value() == 21 {
+        "You already have 21"
+    } else if hand.len() >= card_slots {
+        "There is no room for another card"
+    } else {
+        DEAL_FIRST
+    }
+}
+
+// Turn Hit on or off for the hand being played. Stand always ends the hand.
+fn show_actions(table: &mut Table, hand: &Hand) {
+    let card_slots = table.player_cards[0].len();
+    table.btn_hit.enabled = hand.value() < 21 && hand.len() < card_slots;
+    table.btn_hit.set_disabled_reason(hit_disabled_reason(hand, card_slots));
+    table.btn_stand.enabled = true;
+}
+
+// Put a hit card into the hand, once it can be seen
+fn add_hit_card(table: &mut Table, bus: &mut EventBus, player_hands: &mut [Hand], active: usize, dealer_hand: &Hand, card: Card) {
+    let player_hand = &mut player_hands[active];
+    player_hand.add(card);
+    if player_hand.is_bust() {
+        bus.emit(GameEvent::PlayerBusted { total: player_hand.value() });
+    }
+    show_actions(table, &player_hands[active]);
+    table.show_hands(player_hands, dealer_hand);
+}
+
+// Dim the table and draw a titled panel in the middle (overlay layer), returns the panel's top left corner
+fn draw_panel(layout: &TableLayout, width: f32, height: f32, title: &str, backdrop: f32) -> (f32, f32) {
+    let (screen_width, screen_height) = (layout.virtual_width, layout.virtual_height);
+    let panel_x = screen_width / 2.0 - width / 2.0;
+    let panel_y = screen_height / 2.0 - height / 2.0;
+    draw_backdrop(layout, backdrop);
+    // Under the panel's own buttons and text whenever they are queued, and nothing under it takes the pointer
+    ui::block(Layer::Overlay, layers::BACKGROUND, Rect::new(0.0, 0.0, screen_width, screen_height));
+    layers::push_at(Layer::Overlay, layers::BACKGROUND, move || {
+        let panel = Rect::new(panel_x, panel_y, width, height);
+        let mut shapes = ShapeBatch::new();
+        shapes.fill(panel, 0.0, DARKGRAY);
+        shapes.outline(panel, 0.0, 3.0, GOLD);
+        shapes.draw();
+    });
+    overlay_text(title, panel_x + 20.0, panel_y + 50.0, 45.0, WHITE);
+    (panel_x, panel_y)
+}
+
+// Dim the table behind the panels, `shown` from 0.0 (not at all) to 1.0
+fn draw_backdrop(layout: &TableLayout, shown: f32) {
+    let (screen_width, screen_height) = (layout.virtual_width, layout.virtual_height);
+    layers::push_at(Layer::Overlay, layers::BACKGROUND, move || {
+        draw_rectangle(0.0, 0.0, screen_width, screen_height, Color::new(0.0, 0.0, 0.0, 0.6 * shown));
+    });
+}
+
+// Text on top of a panel
+fn overlay_text(text: impl Into<String>, x: f32, y: f32, font_size: f32, color: Color) {
+    let text = text.into();
+    layers::push(Layer::Overlay, move || {
+        draw_text(&text, x, y, font_size, color);
+    });
+}
+
+// The round is over: let everyone know how each hand went and only leave Play Again pressable
+fn end_round(table: &mut Table, bus: &mut EventBus, results: &[(Outcome, bool)], player_hands: &[Hand], dealer_hand: &Hand, bet: &Bet, blackjack_pays: Payout) {
+    for (hand, (player_hand, (outcome, blackjack))) in player_hands.iter().zip(results).enumerate() {
+        bus.emit(GameEvent::RoundSettled {
+            hand,
+            outcome: *outcome,
+            blackjack: *blackjack,
+            player_total: player_hand.value(),
+            dealer_total: dealer_hand.value(),
+            change: payout(bet.amount(hand), *outcome, *blackjack, blackjack_pays),
+        });
+    }
+    bus.emit(GameEvent::RoundFinished);
+    table.btn_hit.enabled = false;
+    table.btn_hit.set_disabled_reason(ROUND_OVER);
+    table.btn_stand.enabled = false;
+    table.btn_stand.set_disabled_reason(ROUND_OVER);
+    table.btn_replay.enabled = true;
+    table.active_hand = None;
+    if let ([(outcome, _)], [player_hand]) = (results, player_hands) {
+        table.lbl_winner.set_text(outcome_text(*outcome));
+        table.show_explainer(explain(player_hand, dealer_hand));
+        return;
+    }
+    // Several hands: the banner goes to whoever won more of them
+    let count = |wanted: &[Outcome]| results.iter().filter(|(outcome, _)| wanted.contains(outcome)).count();
+    let (won, pushed, lost) = (count(&[Outcome::PlayerWin]), count(&[Outcome::Push]), count(&[Outcome::DealerWin, Outcome::NoWinner]));
+    let banner = match won.cmp(&lost) {
+        std::cmp::Ordering::Greater => Outcome::PlayerWin,
+        std::cmp::Ordering::Less => Outcome::DealerWin,
+        std::cmp::Ordering::Equal => Outcome::Push,
+    };
+    table.lbl_winner.set_text(outcome_text(banner));
+    table.show_explainer(tr("explain.hands", &[&won, &pushed, &lost]));
+}
+
+// Pay or take every hand's bet and show the new bankroll
+fn settle_bet(table: &mut Table, bus: &mut EventBus, profile: &mut Profile, ledger: &mut Ledger, bet: &mut Bet, results: &[(Outcome, bool)]) {
+    let total = bet.total();
+    let change = bet.settle(results, profile.settings.rules.blackjack_pays);
+    // Committing the round's transaction is the only place the bankroll moves
+    match ledger.commit(&mut profile.info.bankroll, change) {
+        Ok(_) => {
+            bus.emit(GameEvent::BetSettled { change });
+            log_info!("bets of ${} settled, bankroll {:+}", total, change);
+        }
+        Err(err) => log_error!("bets of ${} not settled: {}", total, err),
+    }
+    profile.info.recent_bets = bet.recent().to_vec();
+    // Saved before the round is forgotten, closing the game in between can't deal a settled round again
+    profile.save();
+    autosave::end_round(&profile.round_key());
+    table.show_profile(&profile.info, ledger);
+    table.show_bets(bet);
+    table.show_rebet(bet, profile.info.bankroll);
+}
+
+// US rules: the dealer's second card goes face down, and gets peeked at under an ace or ten
+fn deal_hole_card(shoe: &mut Shoe, bus: &mut EventBus, table: &mut Table, rules: &Ruleset, up_card: Card) -> Card {
+    let card = deal_card(shoe, bus, Seat::Dealer);
+    table.hole_card.hide();
+    if rules.dealer_checks(up_card) {
+        table.hole_card.start_peek();
+    }
+    card
+}
+
+// Turn the hole card over and put it in the dealer's hand
+fn reveal_hole_card(table: &mut Table, tm: &TextureManager, hole_card: &mut Option<Card>, dealer_hand: &mut Hand) {
+    let Some(card) = hole_card.take() else {
+        return;
+    };
+    table.hole_card.reveal(tm.get_texture(&card.texture_path()));
+    table.card_pool.show(tm, &mut table.dealer_cards[dealer_hand.len()], &card);
+    dealer_hand.add(card);
+}
+
+// Apply a developer console command to the shoe or profile
+#[cfg(debug_assertions)]
+async fn run_console_command(command: Command, console: &mut DevConsole, shoe: &mut Shoe, profile: &mut Profile, ledger: &mut Ledger, table: &mut Table, phase: GamePhase) {
+    // Rigging the shoe mid-round would land the cards in the wrong places
+    if matches!(command, Command::Deal(_) | Command::ForceDealer(_) | Command::Seed(_)) && phase == GamePhase::PlayerTurn {
+        console.print("Finish the round first");
+        return;
+    }
+    // The cut card is out, so shuffle now rather than at Replay, which would undo the stacking
+    if matches!(command, Command::Deal(_) | Command::ForceDealer(_)) && shoe.needs_reshuffle() {
+        shoe.reshuffle();
+    }
+    match command {
+        Command::Deal(cards) => {
+            let codes: Vec<String> = cards.iter().map(|card| card.code()).collect();
+            if shoe.stack(0, &cards) {
+                console.print(format!("Next out of the shoe: {}", codes.join(" ")));
+            } else {
+                console.print("The shoe doesn't have all of those cards left");
+            }
+        }
+        Command::ForceDealer(total) => {
+            // Two cards to every hand come out before the dealer's. Any suits will do.
+            let [first, second] = dev_console::ranks_for_total(total);
+            let stacked = SUITS.iter().any(|first_suit| {
+                SUITS.iter().any(|second_suit| shoe.stack(table.hands * 2, &[Card::new(first, *first_suit), Card::new(second, *second_suit)]))
+            });
+            if stacked {
+                console.print(format!("The dealer gets {} and {} next round", first.name(), second.name()));
+            } else {
+                console.print("The shoe doesn't have the cards left for that total");
+            }
+        }
+        Command::SetBankroll(amount) => {
+            // The new bankroll might not cover the bets in play, so they go back first
+            ledger.rollback(profile.info.bankroll, "bankroll set from the console");
+            profile.info.bankroll = amount;
+            profile.mark_changed();
+            table.show_profile(&profile.info, ledger);
+            console.print(format!("Bankroll set to {}", i18n::money(amount)));
+        }
+        Command::Seed(seed) => {
+            shoe.shuffle_with_seed(seed);
+            console.print(format!("Shuffled with seed {}", seed));
+        }
+        // Read again every time, so a scenario can be added without restarting
+        Command::RunScenarios => match scenario::load(scenario::SCENARIOS_PATH).await {
+            Ok(scenarios) => {
+                let mut passed = 0;
+                for scenario in scenarios.iter() {
+                    let failures = scenario.run();
+                    if failures.is_empty() {
+                        passed += 1;
+                    } else {
+                        console.print(format!("FAILED {}: {}", scenario.name, failures.join(", ")));
+                    }
+                }
+                console.print(format!("{} of {} scenarios passed", passed, scenarios.len()));
+            }
+            Err(err) => console.print(err),
+        },
+    }
+}
+
+// Put a profile's settings into effect, when the game starts and when a profile is switched to or imported
+fn apply_profile(table: &mut Table, profile: &Profile, tm: &TextureManager, layout: &TableLayout, ledger: &Ledger) {
+    let settings = &profile.settings;
+    set_fullscreen(settings.fullscreen);
+    animation::set_reduced_motion(settings.reduced_motion);
+    animation::set_animation_speed(settings.animation_speed);
+    i18n::set_currency(settings.currency());
+    frame_pacer::set_power_saving(settings.power_saving);
+    quality::set_low(settings.low_quality());
+    theme::set_theme(settings.theme);
+    scene_fade::set_style(settings.scene_fade_style());
+    hand_badge::set_total_style(settings.hand_totals_style());
+    cursor::set_enabled(!settings.system_cursor);
+    widget_style::set_text_scale(settings.text_scale_factor());
+    apply_card_style(tm, settings.classic_cards);
+    table.rescale_text();
+    table.apply_layout(layout, false);
+    table.set_hands(settings.hands());
+    table.show_profile(&profile.info, ledger);
+    table.show_settings(settings);
+    log::configure(settings.log_level());
+}
+
+// Pictures or the classic faces drawn in code, the classic ones are drawn the first time they're picked
+fn apply_card_style(tm: &TextureManager, classic: bool) {
+    if classic {
+        card_face::make_classic_faces(tm);
+    }
+    cards::set_classic_faces(classic);
+}
+
+// Take the next card from the shoe and let everyone know where it went
+fn deal_card(shoe: &mut Shoe, bus: &mut EventBus, seat: Seat) -> Card {
+    let card = shoe.deal();
+    // An empty shoe reshuffles itself mid-round, announce that before the card
+    if shoe.take_reshuffle_event() {
+        bus.emit(GameEvent::ShoeShuffled { seed: shoe.seed() });
+    }
+    bus.emit(GameEvent::CardDealt { card, seat });
+    card
+}
+
 #[macroquad::main(window_conf)]
 async fn main() {
-    rand::srand(date::now() as u64);
+    crash::install_hook();
+    crash::run_guarded(run_game()).await;
+}
+
+async fn run_game() {
+    log::configure(Level::Info);
+    // A recording being played back brings its own seed, so the same cards come out
+    #[cfg(not(target_arch = "wasm32"))]
+    let seed = input_recorder::start_from_env(entropy::new_seed());
+    #[cfg(target_arch = "wasm32")]
+    let seed = entropy::new_seed();
+    // Recordings reshuffle from their seed too, every other game from the system's random source
+    #[cfg(not(target_arch = "wasm32"))]
+    if input_recorder::active() {
+        entropy::set_repeatable(seed);
+    }
+    rand::srand(seed);
+    // Every button on the table and the panels has the same rounded corners, in the
+    // first theme's colours until the player's profile picks its own
+    theme::set_theme(0);
+    materials::load();
     let tm = TextureManager::new();
    let loading_options = LoadingScreenOptions {
        title: Some("Black Jack".to_string()),
@@ -37,268 +1496,1666 @@ async fn main() {
        // Use default values for other options
        ..Default::default()
    };
-   tm.preload_with_loading_screen(&["assets/Two-of-clubs.png","assets/Two-of-hearts.png", "assets/Two-of-spades.png", "assets/Two-of-diamonds.png","assets/Three-of-hearts.png", "assets/Three-of-diamonds.png", "assets/Three-of-clubs.png", "assets/Three-of-spades.png", "assets/Four-of-hearts.png", "assets/Four-of-diamonds.png","assets/Four-of-clubs.png","assets/Four-of-spades.png","assets/Five-of-hearts.png","assets/Five-of-diamonds.png","assets/Five-of-clubs.png","assets/Five-of-spades.png","assets/Six-of-hearts.png","assets/Six-of-diamonds.png","assets/Six-of-spades.png", "assets/Six-of-clubs.png","assets/Seven-of-hearts.png","assets/Seven-of-diamonds.png", "assets/Seven-of-clubs.png", "assets/Seven-of-spades.png", "assets/Eight-of-hearts.png", "assets/Eight-of-diamonds.png", "assets/Eight-of-spades.png", "assets/Eight-of-clubs.png", "assets/Nine-of-hearts.png", "assets/Nine-of-diamonds.png", "assets/Nine-of-clubs.png", "assets/Nine-of-spades.png", "assets/Ten-of-hearts.png", "assets/Ten-of-diamonds.png", "assets/Ten-of-spades.png", "assets/Ten-of-clubs.png", "assets/Ace-of-hearts.png", "assets/Ace-of-diamonds.png", "assets/Ace-of-spades.png", "assets/Ace-of-clubs.png", "assets/Jack-of-hearts.png", "assets/Jack-of-diamonds.png", "assets/Jack-of-spades.png", "assets/Jack-of-clubs.png", "assets/Queen-of-hearts.png", "assets/Queen-of-diamonds.png", "assets/Queen-of-spades.png", "assets/Queen-of-clubs.png", "assets/King-of-hearts.png", "assets/King-of-diamonds.png", "assets/King-of-spades.png", "assets/King-of-clubs.png", "assets/Empty.png"], Some(loading_options)).await;
- 
-    let mut cards: Vec<&str> = vec![
-        "assets/Two-of-clubs.png",
-        "assets/Two-of-hearts.png",
-        "assets/Two-of-spades.png",
-        "assets/Two-of-diamonds.png",
-        "assets/Three-of-hearts.png",
-        "assets/Three-of-diamonds.png",
-        "assets/Three-of-clubs.png",
-        "assets/Three-of-spades.png",
-        "assets/Four-of-hearts.png",
-        "assets/Four-of-diamonds.png",
-        "assets/Four-of-clubs.png",
-        "assets/Four-of-spades.png",
-        "assets/Five-of-hearts.png",
-        "assets/Five-of-diamonds.png",
-        "assets/Five-of-clubs.png",
-        "assets/Five-of-spades.png",
-        "assets/Six-of-hearts.png",
-        "assets/Six-of-diamonds.png",
-        "assets/Six-of-spades.png",
-        "assets/Six-of-clubs.png",
-        "assets/Seven-of-hearts.png",
-        "assets/Seven-of-diamonds.png",
-        "assets/Seven-of-clubs.png",
-        "assets/Seven-of-spades.png",
-        "assets/Eight-of-hearts.png",
-        "assets/Eight-of-diamonds.png",
-        "assets/Eight-of-spades.png",
-        "assets/Eight-of-clubs.png",
-        "assets/Nine-of-hearts.png",
-        "assets/Nine-of-diamonds.png",
-        "assets/Nine-of-clubs.png",
-        "assets/Nine-of-spades.png",
-        "assets/Ten-of-hearts.png",
-        "assets/Ten-of-diamonds.png",
-        "assets/Ten-of-spades.png",
-        "assets/Ten-of-clubs.png",
-        "assets/Ace-of-hearts.png",
-        "assets/Ace-of-diamonds.png",
-        "assets/Ace-of-spades.png",
-        "assets/Ace-of-clubs.png",
-        "assets/Jack-of-hearts.png",
-        "assets/Jack-of-diamonds.png",
-        "assets/Jack-of-spades.png",
-        "assets/Jack-of-clubs.png",
-        "assets/Queen-of-hearts.png",
-        "assets/Queen-of-diamonds.png",
-        "assets/Queen-of-spades.png",
-        "assets/Queen-of-clubs.png",
-        "assets/King-of-hearts.png",
-        "assets/King-of-diamonds.png",
-        "assets/King-of-spades.png",
-        "assets/King-of-clubs.png",
-    ];
-    cards.push("assets/empty.png");
-    let mut scores = vec![
-        2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 6, 6, 6, 6, 7, 7, 7, 7, 8, 8, 8, 8, 9, 9, 9, 9, 10, 10, 10, 10, 11, 11, 11, 11, 10, 10, 10,
-        10, 10, 10, 10, 10, 10, 10, 10, 10,
-    ];
-    scores.push(0);
-
-    let mut first_card = StillImage::new("assets/Empty.png", 110.0, 160.0, 100.0, 500.0, true, 1.0).await;
-    let mut second_card = StillImage::new("assets/Empty.png", 110.0, 160.0, 225.0, 500.0, true, 1.0).await;
-    let mut dealer_card1 = StillImage::new("assets/Empty.png", 110.0, 160.0, 100.0, 100.0, true, 1.0).await;
-    let mut dealer_card2 = StillImage::new("assets/Empty.png", 110.0, 160.0, 225.0, 100.0, true, 1.0).await;
-    let mut dealer_card3 = StillImage::new("assets/Empty.png", 110.0, 160.0, 350.0, 100.0, true, 1.0).await;
-    let mut dealer_card4 = StillImage::new("assets/Empty.png", 110.0, 160.0, 475.0, 100.0, true, 1.0).await;
-    let mut dealer_card5 = StillImage::new("assets/Empty.png", 110.0, 160.0, 600.0, 100.0, true, 1.0).await;
-    let mut fourth_card = StillImage::new("assets/Empty.png", 110.0, 160.0, 475.0, 500.0, true, 1.0).await;
-    let mut third_card = StillImage::new("assets/Empty.png", 110.0, 160.0, 350.0, 500.0, true, 1.0).await;
-    let mut btn_exit = TextButton::new(780.0, 0.0, 200.0, 65.0, "Exit", BLACK, DARKGRAY, 35);
-        btn_exit.with_round(3.0);
-    let mut fifth_card = StillImage::new("assets/Empty.png", 110.0, 160.0, 600.0, 500.0, true, 1.0).await;
-    let mut btn_deal = TextButton::new(100.0, 350.0, 200.0, 65.0, "Deal", BLACK, DARKGRAY, 35);
-        btn_deal.with_round(5.0);
-    let mut btn_hit = TextButton::new(330.0, 350.0, 170.0, 65.0, "Hit", BLACK, DARKGRAY, 35);
-    btn_hit.with_round(5.0);
-    btn_hit.enabled = false;
-    let mut btn_stand = TextButton::new(530.0, 350.0, 170.0, 65.0, "Stand", BLACK, DARKGRAY, 35);
-        btn_stand.with_round(5.0);
-    btn_stand.enabled = false;
-    let mut btn_replay = TextButton::new(750.0, 350.0, 200.0, 65.0, "Play Again", BLACK, DARKGRAY, 30);
-        btn_replay.with_round(5.0);
-    let lbl_dealerhand = Label::new("Dealer's Hand", 70.0, 80.0, 30);
-    let mut lbl_winner = Label::new("", 485.0, 60.0, 50);
-    let lbl_playerhand = Label::new("Your Hand", 70.0, 475.0, 30);
-    let mut lbl_playerscore = Label::new("", 300.0, 475.0, 40);
-    let mut lbl_dealerscore = Label::new("", 300.0, 80.0, 40);
-    let lbl_playerwins: Label = Label::new("Your Wins:", 750.0, 100.0, 30);
-    let lbl_dealerwins: Label = Label::new("Dealer Wins:", 725.0, 140.0, 30);
-    let mut lbl_playercounter: Label = Label::new("0", 890.0, 100.0, 30);
-    let mut lbl_dealercounter: Label = Label::new("0", 890.0, 140.0, 30);
-    let mut numofhits = 0;
-    let mut playertotal = 0;
-    let mut dealertotal = 0;
+   let mut loading_screen = LoadingScreen::new(loading_options);
+    #[cfg(debug_assertions)]
+    let mut debug_overlay = DebugOverlay::new();
+    #[cfg(debug_assertions)]
+    let mut console = DevConsole::new();
+    tm.load_manifest("assets/manifest.json").await;
+    // The manifest lists the groups, these are for when it is missing or leaves one out
+    if tm.group_paths("cards").is_empty() {
+        let card_paths = Card::all_texture_paths();
+        tm.set_group("cards", &card_paths.iter().map(|path| path.as_str()).collect::<Vec<_>>());
+    }
+    if tm.group_paths("menu").is_empty() {
+        tm.set_group("menu", &AVATAR_PATHS);
+    }
+    if tm.group_paths("chips").is_empty() {
+        tm.set_group("chips", &[CHIP_ATLAS_PATH]);
+    }
+    // A card picture with the wrong name is easier to fix from a list than from a blank card mid-game
+    if asset_check::wanted() {
+        let problems = asset_check::check_cards("assets", &tm.group_paths("cards"));
+        for problem in &problems {
+            log_warn!("card assets: {}", problem.text());
+        }
+        if problems.is_empty() {
+            log_info!("card assets: all {} pictures found", Card::all_texture_paths().len());
+        } else if !crash::problem_screen(&asset_check::report(&problems)).await {
+            return;
+        }
+    }
+    // Escape on the loading screen quits without waiting for the rest of the cards
+    let cancel_loading = CancelToken::new();
+    tm.preload_groups(&["cards", "menu", "chips"], &cancel_loading, |event| {
+        if is_key_pressed(KeyCode::Escape) {
+            cancel_loading.cancel();
+        }
+        loading_screen.on_event(event);
+        #[cfg(debug_assertions)]
+        debug_overlay.on_load_event(event);
+    })
+    .await;
+    if cancel_loading.is_cancelled() {
+        return;
+    }
+    loading_screen.finish().await;
+    // Any card picture that didn't load is drawn instead, so the game can still be played
+    card_face::fill_missing_faces(&tm);
+
+    // A recording needs the cards its seed deals, otherwise the shoe carries on where the last game left it
+    #[cfg(not(target_arch = "wasm32"))]
+    let recording = input_recorder::active();
+    #[cfg(target_arch = "wasm32")]
+    let recording = false;
+    // `--seed` (copied from a hand in the history) deals that hand's cards again
+    #[cfg(not(target_arch = "wasm32"))]
+    let replay = ShoePosition::from_args(std::env::args());
+    #[cfg(target_arch = "wasm32")]
+    let replay: Option<ShoePosition> = None;
+    let mut shoe = match (replay, autosave::load_shoe()) {
+        (Some(position), _) => Shoe::new(6, position.seed).with_dealt(position.dealt),
+        (None, Some(saved)) if !recording => Shoe::new(6, saved.seed).with_dealt(saved.dealt),
+        _ => Shoe::new(6, seed),
+    };
+    let mut shuffle_anim = ShuffleAnimation::new();
+    let mut bus = EventBus::new();
+    let mut layouts = TableLayouts::load("assets/layouts.json").await;
+    layouts.set_aspect(screen_width() / screen_height());
+    storage::configure_sync();
+    let mut profiles = ProfileList::load();
+    let mut profile_menu = ProfileMenu::new();
+    let Some(mut profile) = profile_menu.run(&mut profiles, &tm, layouts.get(0)).await else {
+        return;
+    };
+    let mut table = Table::new(&tm);
+    layouts.set_resolution(profile.settings.resolution_size());
+    let mut ledger = Ledger::new(); // Holds the bets in play until the round settles
+    apply_profile(&mut table, &profile, &tm, layouts.get(profile.settings.layout_preset), &ledger);
+    let mut audio = Audio::new().await;
+    let settings = &profile.settings;
+    audio.set_levels(settings.music_volume, settings.sfx_volume, settings.duck_level);
+    audio.start_music();
+    let mut settings_open = false;
+    let mut display_open = false;
+    let mut rules_open = profile_menu.wants_tour(); // A new player can ask to see the rules before the first hand
+    let mut theme_open = false; // The theme picker, from the Display panel
+    let mut theme_pick = 0; // The theme being previewed there
+    let mut rules_editor_open = false; // Making a table's rules by hand, from the Rules panel
+    let mut rules_editor = RulesEditor::new();
+    let mut chart_open = false; // The basic strategy chart, from the Rules panel or the C key
+    let mut chart = StrategyChart::new();
+    let mut goals_open = false;
+    let mut stats_open = false;
+    let mut stats_scroll = ScrollPanel::new(0.0, 0.0, 900.0, 430.0);
+    stats_scroll.with_layer(Layer::Overlay);
+    let mut whats_new_open = false;
+    let mut whats_new_scroll = ScrollPanel::new(0.0, 0.0, 660.0, 420.0);
+    whats_new_scroll.with_layer(Layer::Overlay);
+    let releases = version::changelog();
+    let mut relayout = false; // Lay the presets out again for a new resolution at the top of the next frame
+    let mut pacer = FramePacer::new();
+    let mut quality_monitor = QualityMonitor::new(); // Lowers the effects on Auto when the frame rate can't keep up
+    let mut fairness_open = false;
+    let mut fairness_scroll = ScrollPanel::new(0.0, 0.0, 600.0, 320.0);
+    fairness_scroll.with_layer(Layer::Overlay);
+    let mut hand_open: Option<u32> = None; // Hand number picked from the results strip
+    let mut seed_copied_until = 0.0;
+    let mut famous_open = false; // The gallery of famous hands, from the Rules panel
+    let famous_hands = famous_hands::load(famous_hands::PATH).await;
+    table.show_famous_hands(&famous_hands);
+    let mut famous: Option<usize> = None; // The famous hand every deal is stacked for
+    let mut famous_checked = false; // The first move of this round's famous hand has had its verdict
+    let mut famous_ev = EvEstimator::new(); // Kept apart from the one over the buttons, it always works from a full deck
+    let mut notice_until = 0.0;
+    let panel_fade = Fade::new(PANEL_FADE_SECONDS); // Dims the table as panels open and lets it back as they close
+    let mut transfer_message = String::new(); // How the last profile export or import went
+    let mut low_bankroll = LowBankroll::new();
+    let mut quick_settings = QuickSettings::new();
+    let mut audit = AuditLog::new(shoe.decks());
+    // Commit to the first shoe before any card comes out of it
+    audit.on_event(&GameEvent::ShoeShuffled { seed: shoe.seed() });
+    let mut player_hands = vec![Hand::new()];
+    let mut active = 0; // Index of the hand being played
+    let mut dealer_hand = Hand::new();
+    let mut phase = GamePhase::WaitingToDeal;
+    let mut history = HandHistory::new();
+    let mut even_money: Option<Dialog> = None;
+    let mut hole_card: Option<Card> = None;
+    let mut squeezed: Option<Card> = None; // A hit card the player hasn't turned over yet
+    let mut script: Timeline<Cue> = Timeline::new();
+    let mut decision_timer = DecisionTimer::new();
+    let mut bet = Bet::new();
+    bet.set_recent(&profile.info.recent_bets);
+    let mut trainer = CountTrainer::new(shoe.decks());
+    let mut drills = DrillTracker::new();
+    let mut announcer = Announcer::new();
+    announcer.set_enabled(profile.settings.announce);
+    #[cfg_attr(not(debug_assertions), allow(unused_mut))] // Only the developer console pauses it
+    let mut keys = KeyMap::new();
+    let mut odds = OddsPanel::new();
+    let mut ev = EvEstimator::new();
+    let mut advisor = Advisor::new();
+    let mut goals = SessionGoals::new();
+    let mut pacing = Pacing::new();
+    let mut analytics = Analytics::new();
+    analytics.set_enabled(profile.settings.analytics);
+    let mut gestures = GestureDetector::new(profile.settings.gesture_thresholds());
+    let mut chip_hand = 0; // The spot a chip from the tray goes on when clicked, the last one tapped
+    let mut staged_round: Option<(usize, usize, bool)> = None; // Cards dealt, hand being played and whether the turn had started
+    // The round the game was closed in the middle of is dealt back as it was left
+    let round_key = profile.round_key();
+    if let Some(saved) = autosave::load_round(&round_key).filter(|_| !recording && replay.is_none()) {
+        let fits = |(hands, dealer, _): &(Vec<Hand>, Hand, Option<Card>)| {
+            hands.len() == table.hands && hands.iter().zip(&table.player_cards).all(|(hand, slots)| hand.len() <= slots.len()) && dealer.len() < table.dealer_cards.len()
+        };
+        match (saved.cards().filter(fits), ledger.reserve(profile.info.bankroll, saved.stake())) {
+            (Some((hands, dealer, hole)), Ok(_)) => {
+                player_hands = hands;
+                dealer_hand = dealer;
+                hole_card = hole;
+                active = saved.active.min(player_hands.len());
+                for (hand, amount) in saved.bets.iter().enumerate() {
+                    bet.add(hand, *amount, profile.info.bankroll);
+                }
+                for (hand, player_hand) in player_hands.iter().enumerate() {
+                    for (slot, card) in player_hand.cards().iter().enumerate() {
+                        table.card_pool.show(&tm, &mut table.player_cards[hand][slot], card);
+                    }
+                }
+                for (slot, card) in dealer_hand.cards().iter().enumerate() {
+                    table.card_pool.show(&tm, &mut table.dealer_cards[slot], card);
+                }
+                if let (Some(_), Some(up_card)) = (hole_card, dealer_hand.cards().first()) {
+                    table.hole_card.hide();
+                    if profile.settings.rules.dealer_checks(*up_card) {
+                        table.hole_card.start_peek();
+                    }
+                }
+                history.start_round(None);
+                phase = GamePhase::PlayerTurn;
+                table.btn_deal.enabled = false;
+                table.btn_replay.enabled = false;
+                table.btn_hit.set_disabled_reason(DEALER_PLAYING);
+                table.btn_stand.set_disabled_reason(DEALER_PLAYING);
+                if !saved.started {
+                    // The rest of the deal, then the turn starts (and even money is asked) again
+                    for pass in 0..2 {
+                        for (hand, player_hand) in player_hands.iter().enumerate() {
+                            if player_hand.len() <= pass {
+                                script.then(Cue::PlayerCard(hand)).wait(DEAL_GAP);
+                            }
+                        }
+                    }
+                    if dealer_hand.is_empty() {
+                        script.then(Cue::DealerUpCard).wait(DEAL_GAP);
+                    }
+                    script.then(Cue::StartPlayerTurn);
+                } else if active < player_hands.len() {
+                    table.active_hand = Some(active);
+                    show_actions(&mut table, &player_hands[active]);
+                    decision_timer.start(profile.settings.decision_seconds as f32);
+                } else {
+                    // The last hand stays marked while the dealer plays, as it does after it stands
+                    table.active_hand = Some(active - 1);
+                    script.then(Cue::RevealHoleCard).wait(DEALER_PAUSE).then(Cue::DealerDraw);
+                }
+                table.show_hands(&player_hands, &dealer_hand);
+                table.show_bets(&bet);
+                table.show_profile(&profile.info, &ledger);
+                log_info!("carrying on the round left open, ${} bet", saved.stake());
+            }
+            // Its cards are gone from the shoe either way, it is lost like a hand played out and lost
+            (None, Ok(_)) => {
+                ledger.forfeit(&mut profile.info.bankroll, "the saved round doesn't fit the table");
+                profile.save();
+                autosave::end_round(&round_key);
+            }
+            (_, Err(err)) => {
+                log_warn!("the saved round can't be carried on: {}", err);
+                autosave::end_round(&round_key);
+            }
+        }
+    }
+    // Closing the window asks first, so the session can be saved (the web build saves from js/blackjack.js)
+    #[cfg(not(target_arch = "wasm32"))]
+    prevent_quit();
 
     loop {
-        // Set the virtual resolution to 1024x768
-        use_virtual_resolution(1000.0, 700.0);
-        clear_background(DARKGREEN);
-        let random_card_1 = rand::gen_range(1, 52);
-        let random_card_2 = rand::gen_range(1, 52);
-                if playertotal > 20 {
-                    btn_hit.enabled = false;
-                }
-        if btn_exit.click() {
+        pacer.wait(profile.settings.min_frame_seconds());
+        let frame = Frame::begin();
+        if is_quit_requested() {
+            // A round being played stays open, it is saved with the shoe and dealt back next time
+            profile.save();
+            break;
+        }
+        // Whatever isn't saved yet is written if the game is closed before the next save
+        if profile.has_changes() {
+            autosave::stage(profile.parts());
+        }
+        if !recording {
+            autosave::stage_shoe(&shoe);
+            // The round only changes when a card comes out or the player moves on
+            let started = table.active_hand.is_some() && even_money.is_none();
+            if phase == GamePhase::PlayerTurn && staged_round != Some((shoe.dealt(), active, started)) {
+                staged_round = Some((shoe.dealt(), active, started));
+                autosave::stage_round(&profile.round_key(), RoundSave::new(&player_hands, squeezed, &dealer_hand, hole_card, &bet, active, started));
+            }
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if input_recorder::playback_finished() {
+            log_info!("playback finished");
+            return;
+        }
+        if profile.settings.quality == 0 && !quality::low() && quality_monitor.update(pacer.busy_seconds()) {
+            log_info!("frame rate under {} fps, lowering the effects", quality::MIN_FPS);
+            profile.settings.slow_device = true;
+            profile.mark_changed();
+            quality::set_low(true);
+            table.show_settings(&profile.settings);
+            table.lbl_notice.set_text("Effects lowered to keep the game smooth, change it in Settings > Display");
+            notice_until = get_time() + 5.0;
+        }
+        // Between rounds nothing moves unless the player does something (or a tween is running)
+        if phase != GamePhase::PlayerTurn && even_money.is_none() && !script.is_running() {
+            frame_pacer::declare_static();
+        }
+        // Turning a phone upright (or dragging a window narrow) swaps in the portrait table
+        if layouts.set_aspect(screen_width() / screen_height()) {
+            relayout = true;
+        }
+        if relayout {
+            relayout = false;
+            layouts.set_resolution(profile.settings.resolution_size());
+            table.apply_layout(layouts.get(profile.settings.layout_preset), true);
+            table.show_hands(&player_hands, &dealer_hand);
+        }
+        let layout = layouts.get(profile.settings.layout_preset);
+        frame.layout(layout.virtual_width, layout.virtual_height, if profile.settings.green_screen() { CHROMA_GREEN } else { theme::felt_color() });
+        scene_fade::draw();
+        audio.update(get_frame_time());
+        // The chart can be looked at in the middle of a hand, but not over another panel
+        if keys.pressed(KeyAction::Chart) && (chart_open || !(settings_open || display_open || theme_open || rules_open || rules_editor_open || famous_open || goals_open || stats_open || whats_new_open || fairness_open || hand_open.is_some())) {
+            chart_open = !chart_open;
+            if chart_open {
+                bus.emit(GameEvent::ChartOpened { in_hand: phase == GamePhase::PlayerTurn });
+            }
+        }
+        let panel_open = chart_open || settings_open || display_open || theme_open || rules_open || rules_editor_open || famous_open || goals_open || stats_open || whats_new_open || fairness_open || hand_open.is_some();
+        let backdrop = panel_fade.update(panel_open);
+        // The quick settings are for the table, they go away when anything is put over it
+        if panel_open || even_money.is_some() {
+            quick_settings.close();
+        }
+        if !panel_open && backdrop > 0.0 {
+            // The panel has gone, the dimming fades out after it
+            draw_backdrop(layout, backdrop);
+        }
+
+        #[cfg(debug_assertions)]
+        {
+            debug_overlay.update();
+            debug_overlay.draw(
+                &tm,
+                &[
+                    ("Phase", format!("{:?}", phase)),
+                    ("Player hand", {
+                        let hand = &player_hands[active.min(player_hands.len() - 1)];
+                        format!("#{} of {}: {} ({} cards{})", active + 1, player_hands.len(), hand.value(), hand.len(), if hand.is_soft() { ", soft" } else { "" })
+                    }),
+                    ("Dealer hand", format!("{} ({} cards)", dealer_hand.value(), dealer_hand.len())),
+                    ("Shoe", format!("{} left, {:.0}% dealt, cut at {}", shoe.remaining(), shoe.dealt_fraction() * 100.0, shoe.cut_card_index())),
+                    ("Profile", format!("{} (#{})", profile.info.name, profile.id)),
+                    ("Cloud", format!("{:?}", storage::sync_status())),
+                ],
+            );
+            // Typing into the console shouldn't hit or stand
+            keys.set_paused(console.is_open());
+            if let Some(command) = console.update() {
+                run_console_command(command, &mut console, &mut shoe, &mut profile, &mut ledger, &mut table, phase).await;
+            }
+            console.draw(layout.virtual_width);
+        }
+
+        // Queued up front so it stays on the table behind the panels and dialogs
+        let clean_hud = profile.settings.clean_hud();
+        if profile.settings.counting_trainer && !clean_hud {
+            let area = layout.trainer;
+            trainer.draw(Rect::new(area.x, area.y, area.w, area.h), hole_card);
+        }
+        if profile.settings.odds > 0 && phase == GamePhase::PlayerTurn && !clean_hud {
+            let area = layout.odds;
+            let unseen = if profile.settings.odds == 1 { Composition::full_deck() } else { Composition::from_ranks(trainer.unseen(hole_card)) };
+            let up_card = dealer_hand.cards().first().copied();
+            let peeked = up_card.is_some_and(|card| profile.settings.rules.dealer_checks(card));
+            odds.draw(Rect::new(area.x, area.y, area.w, area.h), player_hands.get(active), up_card, &unseen, peeked);
+            // What hitting and standing are worth, over the buttons that do them
+            if let (Some(hand), Some(up_card)) = (player_hands.get(active), up_card) {
+                ev.update(hand, up_card, &unseen, peeked);
+                let (hit, stand) = (layout.hit, layout.stand);
+                ev.draw(Rect::new(hit.x, hit.y, hit.w, hit.h), Rect::new(stand.x, stand.y, stand.w, stand.h));
+            }
+        }
+
+        // Bet sizing is only worth knowing before the bet goes down, and its title is clickable so not under a panel
+        if profile.settings.advisor && phase == GamePhase::WaitingToDeal && !clean_hud && !panel_open {
+            let area = layout.advisor;
+            let true_count = profile.settings.counting_trainer.then(|| trainer.counts(hole_card).1);
+            let edge = advisor.edge(&profile.settings.rules, true_count);
+            let staked = if bet.total() > 0 { bet.total() } else { bet.last_total(profile.settings.hands()) };
+            let advice = advisor::advise(edge, profile.info.bankroll, staked);
+            advisor.draw(Rect::new(area.x, area.y, area.w, area.h), &advice);
+        }
+
+        // The player's nameplate follows the bet and lights up while it's their turn
+        table.nameplate.set_bet(bet.total());
+        table.nameplate.set_turn(phase == GamePhase::PlayerTurn);
+
+        // Hold the table while the shoe is being shuffled
+        if shuffle_anim.is_running() {
+            table.draw(&shoe);
+            shuffle_anim.update(get_frame_time());
+            shuffle_anim.draw(layout.virtual_width / 2.0, layout.virtual_height / 2.0);
+            frame.end().await;
+            continue;
+        }
+
+        if settings_open {
+            // Keep updating so the cards glide to a newly picked layout behind the panel
+            table.update(get_frame_time());
+            table.draw(&shoe);
+            let (panel_x, panel_y) = draw_panel(layout, 900.0, 660.0, "Settings", backdrop);
+            overlay_text(transfer_message.clone(), panel_x + 240.0, panel_y + 50.0, 24.0, LIGHTGRAY);
+            overlay_text("Table layout:", panel_x + 20.0, panel_y + 125.0, 30.0, WHITE);
+            let name_width = measure_text_cached(&layout.name, None, 30).width;
+            overlay_text(layout.name.clone(), panel_x + 330.0 - name_width / 2.0, panel_y + 125.0, 30.0, GOLD);
+            table.btn_layout_prev.update_position(panel_x + 200.0, panel_y + 95.0, None, None);
+            table.btn_layout_next.update_position(panel_x + 420.0, panel_y + 95.0, None, None);
+            let rows = Column::new(panel_x + 200.0, panel_y + 155.0).spacing(8.0).place(&mut [
+                &mut table.btn_log_level,
+                &mut table.btn_decision_time,
+                &mut table.btn_timeout_action,
+                &mut table.btn_dealer_peeks,
+                &mut table.btn_hand_count,
+                &mut table.btn_animation_speed,
+                &mut table.btn_no_contest,
+                &mut table.btn_currency,
+            ]);
+            for (name, row) in ["Log level:", "Decide in:", "Time's up:", "Dealer peeks:", "Hands:", "Speed:", "No contest:", "Money:"].into_iter().zip(rows) {
+                overlay_text(name, panel_x + 20.0, row.y + 30.0, 30.0, WHITE);
+            }
+            // Sound in the right-hand column
+            let rows = Column::new(panel_x + 610.0, panel_y + 105.0).spacing(36.0).place(&mut [&mut table.sld_music, &mut table.sld_sfx, &mut table.sld_duck]);
+            for (name, row) in ["Music:", "Effects:", "Ducking:"].into_iter().zip(rows) {
+                overlay_text(name, panel_x + 500.0, row.y + 20.0, 30.0, WHITE);
+            }
+            overlay_text("Music volume while a win or loss sound plays", panel_x + 500.0, panel_y + 265.0, 20.0, LIGHTGRAY);
+            let rows = Column::new(panel_x + 610.0, panel_y + 283.0).spacing(8.0).place(&mut [&mut table.btn_trainer, &mut table.btn_odds, &mut table.btn_squeeze, &mut table.btn_advisor]);
+            for (name, row) in ["Trainer:", "Odds:", "Squeeze:", "Advisor:"].into_iter().zip(rows) {
+                overlay_text(name, panel_x + 500.0, row.y + 30.0, 30.0, WHITE);
+            }
+            table.btn_switch_profile.update_position(panel_x + 480.0, panel_y + 505.0, None, None);
+            table.btn_settings_close.update_position(panel_x + 690.0, panel_y + 505.0, None, None);
+            Row::new(panel_x + 20.0, panel_y + 580.0)
+                .spacing(15.0)
+                .place(&mut [&mut table.btn_export_profile, &mut table.btn_import_profile, &mut table.btn_display, &mut table.btn_rules, &mut table.btn_goals, &mut table.btn_whats_new]);
+            // Only between rounds, so a hand never gets split across two profiles or rule sets
+            table.btn_switch_profile.enabled = phase == GamePhase::WaitingToDeal;
+            // The portrait table stands in for every preset until the window is turned back
+            table.btn_layout_prev.enabled = !layouts.is_portrait();
+            table.btn_layout_next.enabled = !layouts.is_portrait();
+            table.btn_dealer_peeks.enabled = phase == GamePhase::WaitingToDeal;
+            table.btn_hand_count.enabled = phase == GamePhase::WaitingToDeal;
+            table.btn_import_profile.enabled = phase == GamePhase::WaitingToDeal;
+            if table.btn_layout_prev.click() {
+                profile.settings.previous_layout(layouts.count());
+                profile.mark_changed();
+                table.apply_layout(layouts.get(profile.settings.layout_preset), true);
+                table.show_hands(&player_hands, &dealer_hand);
+            }
+            if table.btn_layout_next.click() {
+                profile.settings.next_layout(layouts.count());
+                profile.mark_changed();
+                table.apply_layout(layouts.get(profile.settings.layout_preset), true);
+                table.show_hands(&player_hands, &dealer_hand);
+            }
+            if table.btn_log_level.click() {
+                profile.settings.next_log_level();
+                profile.mark_changed();
+                log::configure(profile.settings.log_level());
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_decision_time.click() {
+                profile.settings.next_decision_time();
+                profile.mark_changed();
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_no_contest.click() {
+                profile.settings.next_no_contest();
+                profile.mark_changed();
+                table.show_settings(&profile.settings);
+            }
+            // Takes effect straight away, even part way through a round
+            if table.btn_animation_speed.click() {
+                profile.settings.next_animation_speed();
+                profile.mark_changed();
+                animation::set_animation_speed(profile.settings.animation_speed);
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_currency.click() {
+                profile.settings.next_currency();
+                profile.mark_changed();
+                i18n::set_currency(profile.settings.currency());
+                table.show_profile(&profile.info, &ledger);
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_timeout_action.click() {
+                profile.settings.timeout_plays_strategy = !profile.settings.timeout_plays_strategy;
+                profile.mark_changed();
+                table.show_settings(&profile.settings);
+            }
+            let music = table.sld_music.update();
+            let sfx = table.sld_sfx.update();
+            let duck = table.sld_duck.update();
+            if music.is_some() || sfx.is_some() || duck.is_some() {
+                let settings = &mut profile.settings;
+                settings.music_volume = music.unwrap_or(settings.music_volume);
+                settings.sfx_volume = sfx.unwrap_or(settings.sfx_volume);
+                settings.duck_level = duck.unwrap_or(settings.duck_level);
+                audio.set_levels(settings.music_volume, settings.sfx_volume, settings.duck_level);
+                profile.mark_changed();
+            }
+            if table.btn_dealer_peeks.click() {
+                profile.settings.rules.dealer_peeks = !profile.settings.rules.dealer_peeks;
+                profile.mark_changed();
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_trainer.click() {
+                profile.settings.next_trainer();
+                profile.mark_changed();
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_odds.click() {
+                profile.settings.next_odds();
+                profile.mark_changed();
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_squeeze.click() {
+                profile.settings.squeeze = !profile.settings.squeeze;
+                profile.mark_changed();
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_advisor.click() {
+                profile.settings.advisor = !profile.settings.advisor;
+                profile.mark_changed();
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_hand_count.click() {
+                profile.settings.next_hand_count();
+                profile.mark_changed();
+                table.show_settings(&profile.settings);
+                table.set_hands(profile.settings.hands());
+                bet.keep_hands(profile.settings.hands());
+                table.show_bets(&bet);
+            }
+            if table.btn_switch_profile.click() {
+                // Only offered between rounds, anything still held is from a round that never settled
+                ledger.rollback(profile.info.bankroll, "profile switched");
+                profile.save();
+                settings_open = false;
+                scene_fade::capture(theme::felt_color());
+                let Some(chosen) = profile_menu.run(&mut profiles, &tm, layout).await else {
+                    break;
+                };
+                profile = chosen;
+                apply_profile(&mut table, &profile, &tm, layouts.get(profile.settings.layout_preset), &ledger);
+                analytics.set_enabled(profile.settings.analytics);
+                announcer.set_enabled(profile.settings.announce);
+                relayout = true;
+                table.show_hands(&player_hands, &dealer_hand);
+                table.restyle_cards(&tm, &player_hands, &dealer_hand);
+                let settings = &profile.settings;
+                audio.set_levels(settings.music_volume, settings.sfx_volume, settings.duck_level);
+                gestures.set_thresholds(settings.gesture_thresholds());
+                // The new bankroll might not cover the old profile's bets
+                bet.keep_hands(0);
+                // A new player sits down, with no goals picked yet
+                goals = SessionGoals::new();
+                pacing = Pacing::new();
+                bet.set_recent(&profile.info.recent_bets);
+                table.show_bets(&bet);
+            }
+            if table.btn_export_profile.click() {
+                profile.save_if_changed();
+                transfer_message = match export_profile(&profile) {
+                    Ok(message) => message,
+                    Err(err) => format!("Export failed: {}", err),
+                };
+            }
+            if table.btn_import_profile.click() {
+                let bankroll = profile.info.bankroll;
+                match import_profile(&mut profile) {
+                    Ok(true) => {
+                        ledger.rollback(bankroll, "profile imported");
+                        transfer_message = format!("Imported {}", profile.info.name);
+                        apply_profile(&mut table, &profile, &tm, layouts.get(profile.settings.layout_preset), &ledger);
+                        analytics.set_enabled(profile.settings.analytics);
+                        announcer.set_enabled(profile.settings.announce);
+                        relayout = true;
+                        table.show_hands(&player_hands, &dealer_hand);
+                        table.restyle_cards(&tm, &player_hands, &dealer_hand);
+                        let settings = &profile.settings;
+                        audio.set_levels(settings.music_volume, settings.sfx_volume, settings.duck_level);
+                        gestures.set_thresholds(settings.gesture_thresholds());
+                        bet.keep_hands(0);
+                        bet.set_recent(&profile.info.recent_bets);
+                        table.show_bets(&bet);
+                    }
+                    Ok(false) => {}
+                    Err(err) => transfer_message = format!("Import failed: {}", err),
+                }
+            }
+            if table.btn_display.click() {
+                settings_open = false;
+                display_open = true;
+            }
+            if table.btn_rules.click() {
+                settings_open = false;
+                rules_open = true;
+            }
+            if table.btn_goals.click() {
+                settings_open = false;
+                goals_open = true;
+            }
+            if table.btn_whats_new.click() {
+                settings_open = false;
+                whats_new_open = true;
+                whats_new_scroll.scroll_to(0.0);
+            }
+            if table.btn_settings_close.click() {
+                profile.save_if_changed();
+                settings_open = false;
+            }
+            frame.end().await;
+            continue;
+        }
+
+        if display_open {
+            table.update(get_frame_time());
+            table.draw(&shoe);
+            // Two columns, so every row fits on the shortest table
+            let (panel_x, panel_y) = draw_panel(layout, 960.0, 650.0, "Display", backdrop);
+            let left = Column::new(panel_x + 220.0, panel_y + 95.0).spacing(8.0).place(&mut [
+                &mut table.btn_fullscreen,
+                &mut table.btn_resolution,
+                &mut table.btn_target_fps,
+                &mut table.btn_vsync,
+                &mut table.btn_power_saving,
+                &mut table.btn_quality,
+                &mut table.btn_hand_totals,
+            ]);
+            let right = Column::new(panel_x + 680.0, panel_y + 95.0).spacing(8.0).place(&mut [
+                &mut table.btn_reduced_motion,
+                &mut table.btn_cursor,
+                &mut table.btn_stream_mode,
+                &mut table.btn_card_style,
+                &mut table.btn_announce,
+                &mut table.btn_theme,
+                &mut table.btn_scene_fade,
+                &mut table.btn_text_scale,
+            ]);
+            let left_names = ["Fullscreen:", "Resolution:", "Frame rate cap:", "VSync:", "Power saving:", "Effects:", "Hand totals:"];
+            for (name, row) in left_names.into_iter().zip(left) {
+                overlay_text(name, panel_x + 20.0, row.y + 30.0, 30.0, WHITE);
+            }
+            let right_names = ["Reduced motion:", "Mouse cursor:", "Streaming:", "Cards:", "Read aloud:", "Theme:", "Screen fades:", "Text size:"];
+            for (name, row) in right_names.into_iter().zip(right) {
+                overlay_text(name, panel_x + 505.0, row.y + 30.0, 30.0, WHITE);
+            }
+            overlay_text("VSync and edge smoothing change on the next start", panel_x + 20.0, panel_y + 555.0, 20.0, LIGHTGRAY);
+            overlay_text("Power saving draws less often while nothing moves", panel_x + 20.0, panel_y + 580.0, 20.0, LIGHTGRAY);
+            table.btn_display_close.update_position(panel_x + 740.0, panel_y + 570.0, None, None);
+            if table.btn_fullscreen.click() {
+                profile.settings.fullscreen = !profile.settings.fullscreen;
+                profile.mark_changed();
+                set_fullscreen(profile.settings.fullscreen);
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_resolution.click() {
+                profile.settings.next_resolution();
+                profile.mark_changed();
+                relayout = true;
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_target_fps.click() {
+                profile.settings.next_target_fps();
+                profile.mark_changed();
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_vsync.click() {
+                profile.settings.vsync = !profile.settings.vsync;
+                profile.mark_changed();
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_reduced_motion.click() {
+                profile.settings.reduced_motion = !profile.settings.reduced_motion;
+                profile.mark_changed();
+                animation::set_reduced_motion(profile.settings.reduced_motion);
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_power_saving.click() {
+                profile.settings.power_saving = !profile.settings.power_saving;
+                profile.mark_changed();
+                frame_pacer::set_power_saving(profile.settings.power_saving);
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_cursor.click() {
+                profile.settings.system_cursor = !profile.settings.system_cursor;
+                profile.mark_changed();
+                cursor::set_enabled(!profile.settings.system_cursor);
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_stream_mode.click() {
+                profile.settings.next_stream_mode();
+                profile.mark_changed();
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_card_style.click() {
+                profile.settings.classic_cards = !profile.settings.classic_cards;
+                profile.mark_changed();
+                apply_card_style(&tm, profile.settings.classic_cards);
+                table.restyle_cards(&tm, &player_hands, &dealer_hand);
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_announce.click() {
+                profile.settings.announce = !profile.settings.announce;
+                profile.mark_changed();
+                announcer.set_enabled(profile.settings.announce);
+                announcer.say("Reading aloud");
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_quality.click() {
+                profile.settings.next_quality();
+                profile.mark_changed();
+                quality::set_low(profile.settings.low_quality());
+                quality_monitor.reset();
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_theme.click() {
+                theme_pick = profile.settings.theme;
+                table.preview_theme(theme_pick);
+                display_open = false;
+                theme_open = true;
+            }
+            if table.btn_hand_totals.click() {
+                profile.settings.next_hand_totals();
+                profile.mark_changed();
+                hand_badge::set_total_style(profile.settings.hand_totals_style());
+                table.show_hands(&player_hands, &dealer_hand);
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_scene_fade.click() {
+                profile.settings.next_scene_fade();
+                profile.mark_changed();
+                scene_fade::set_style(profile.settings.scene_fade_style());
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_text_scale.click() {
+                profile.settings.next_text_scale();
+                profile.mark_changed();
+                widget_style::set_text_scale(profile.settings.text_scale_factor());
+                table.show_settings(&profile.settings);
+                table.rescale_text();
+                relayout = true;
+            }
+            if table.btn_display_close.click() {
+                display_open = false;
+                settings_open = true;
+            }
+            frame.end().await;
+            continue;
+        }
+
+        // Themes are tried on a sample of the table first, the whole game only changes on Apply
+        if theme_open {
+            table.update(get_frame_time());
+            table.draw(&shoe);
+            let (panel_x, panel_y) = draw_panel(layout, 760.0, 560.0, "Theme", backdrop);
+            let mut picks: Vec<&mut dyn Placeable> = table.btn_theme_picks.iter_mut().map(|button| button as &mut dyn Placeable).collect();
+            let places = Row::new(panel_x + 20.0, panel_y + 80.0).spacing(15.0).place(&mut picks);
+            let picked = places[theme_pick];
+            let felt = THEMES[theme_pick].felt;
+            let atlas = table.chip_atlas.clone();
+            layers::push(Layer::Overlay, move || {
+                draw_rectangle_lines(picked.x - 4.0, picked.y - 4.0, picked.w + 8.0, picked.h + 8.0, 3.0, GOLD);
+                let sample = Rect::new(panel_x + 20.0, panel_y + 155.0, 720.0, 300.0);
+                draw_rectangle(sample.x, sample.y, sample.w, sample.h, felt);
+                draw_rectangle_lines(sample.x, sample.y, sample.w, sample.h, 2.0, GOLD);
+                for (i, code) in ["KS", "9H"].into_iter().enumerate() {
+                    if let Some(card) = Card::parse(code) {
+                        card_face::draw_card_face(&card, Rect::new(sample.x + 30.0 + i as f32 * 60.0, sample.y + 40.0, 120.0, 175.0));
+                    }
+                }
+                atlas.draw_chip(25, vec2(sample.x + 310.0, sample.y + 225.0), 38.0);
+                atlas.draw_chip(100, vec2(sample.x + 395.0, sample.y + 225.0), 38.0);
+            });
+            table.lbl_theme_sample.set_position(panel_x + 290.0, panel_y + 200.0);
+            table.lbl_theme_sample.draw();
+            table.btn_theme_sample.update_position(panel_x + 540.0, panel_y + 355.0, None, None);
+            overlay_text("Point at the button to see it lit", panel_x + 20.0, panel_y + 485.0, 20.0, LIGHTGRAY);
+            table.btn_theme_apply.update_position(panel_x + 320.0, panel_y + 480.0, None, None);
+            table.btn_theme_cancel.update_position(panel_x + 540.0, panel_y + 480.0, None, None);
+            let clicks: Vec<bool> = table.btn_theme_picks.iter().map(|button| button.click()).collect();
+            table.btn_theme_sample.click();
+            if let Some(i) = clicks.iter().position(|clicked| *clicked) {
+                theme_pick = i;
+                table.preview_theme(theme_pick);
+            }
+            if table.btn_theme_apply.click() {
+                profile.settings.theme = theme_pick;
+                profile.mark_changed();
+                theme::set_theme(profile.settings.theme);
+                table.show_settings(&profile.settings);
+                theme_open = false;
+                display_open = true;
+            }
+            if table.btn_theme_cancel.click() {
+                theme_open = false;
+                display_open = true;
+            }
+            frame.end().await;
+            continue;
+        }
+
+        // The table's rules in plain words, written from the ruleset being played
+        if rules_open {
+            table.update(get_frame_time());
+            table.draw(&shoe);
+            let (panel_x, panel_y) = draw_panel(layout, 700.0, 660.0, "Rules", backdrop);
+            for (row, (heading, line)) in profile.settings.rules.reference().into_iter().enumerate() {
+                let y = panel_y + 100.0 + row as f32 * 23.0;
+                overlay_text(heading, panel_x + 20.0, y, 26.0, GOLD);
+                overlay_text(line, panel_x + 200.0, y, 24.0, WHITE);
+            }
+            overlay_text("Table:", panel_x + 20.0, panel_y + 480.0, 30.0, WHITE);
+            overlay_text("Stakes:", panel_x + 20.0, panel_y + 533.0, 30.0, WHITE);
+            table.btn_table_rules.update_position(panel_x + 200.0, panel_y + 450.0, None, None);
+            table.btn_edit_rules.update_position(panel_x + 490.0, panel_y + 450.0, None, None);
+            table.btn_stakes.update_position(panel_x + 200.0, panel_y + 503.0, None, None);
+            table.btn_low_bankroll.update_position(panel_x + 490.0, panel_y + 503.0, None, None);
+            table.btn_rules_close.update_position(panel_x + 480.0, panel_y + 580.0, None, None);
+            table.btn_chart.update_position(panel_x + 20.0, panel_y + 580.0, None, None);
+            table.btn_famous.update_position(panel_x + 290.0, panel_y + 580.0, None, None);
+            // Like Dealer peeks, the table and its stakes only change between rounds
+            table.btn_table_rules.enabled = phase == GamePhase::WaitingToDeal;
+            table.btn_stakes.enabled = phase == GamePhase::WaitingToDeal;
+            if table.btn_table_rules.click() {
+                profile.settings.next_rules();
+                profile.mark_changed();
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_stakes.click() {
+                profile.settings.next_stakes();
+                profile.mark_changed();
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_low_bankroll.click() {
+                profile.settings.next_low_bankroll();
+                profile.mark_changed();
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_edit_rules.click() {
+                rules_editor.open(&profile.settings);
+                rules_open = false;
+                rules_editor_open = true;
+            }
+            if table.btn_rules_close.click() {
+                rules_open = false;
+                settings_open = true;
+            }
+            if table.btn_chart.click() {
+                rules_open = false;
+                chart_open = true;
+                bus.emit(GameEvent::ChartOpened { in_hand: phase == GamePhase::PlayerTurn });
+            }
+            if table.btn_famous.click() {
+                rules_open = false;
+                famous_open = true;
+            }
+            frame.end().await;
+            continue;
+        }
+
+        // Famous hands to deal again and again, the first move of each gets the EV engine's verdict
+        if famous_open {
+            table.update(get_frame_time());
+            table.draw(&shoe);
+            let (panel_x, panel_y) = draw_panel(layout, 900.0, 630.0, "Famous hands", backdrop);
+            overlay_text("Pick one, then bet and deal: every deal is that hand", panel_x + 340.0, panel_y + 45.0, 20.0, LIGHTGRAY);
+            let mut picks: Vec<&mut dyn Placeable> = table.btn_famous_picks.iter_mut().map(|button| button as &mut dyn Placeable).collect();
+            let rows = Column::new(panel_x + 20.0, panel_y + 75.0).spacing(6.0).place(&mut picks);
+            for (hand, row) in famous_hands.iter().zip(&rows) {
+                overlay_text(hand.note.clone(), panel_x + 380.0, row.y + 29.0, 20.0, WHITE);
+            }
+            if let Some(row) = famous.and_then(|i| rows.get(i)).copied() {
+                layers::push(Layer::Overlay, move || draw_rectangle_lines(row.x - 4.0, row.y - 4.0, row.w + 8.0, row.h + 8.0, 3.0, GOLD));
+            }
+            if famous_hands.is_empty() {
+                overlay_text(format!("No famous hands, {} didn't load", famous_hands::PATH), panel_x + 20.0, panel_y + 110.0, 24.0, ORANGE);
+            }
+            table.btn_famous_stop.update_position(panel_x + 420.0, panel_y + 550.0, None, None);
+            table.btn_famous_close.update_position(panel_x + 680.0, panel_y + 550.0, None, None);
+            table.btn_famous_stop.enabled = famous.is_some();
+            let clicks: Vec<bool> = table.btn_famous_picks.iter().map(|button| button.click()).collect();
+            if let Some(i) = clicks.iter().position(|clicked| *clicked) {
+                famous = Some(i);
+                famous_open = false;
+                table.lbl_notice.set_text(format!("{}: bet and deal to play it", famous_hands[i].name));
+                notice_until = get_time() + 4.0;
+            }
+            if table.btn_famous_stop.click() {
+                famous = None;
+            }
+            if table.btn_famous_close.click() {
+                famous_open = false;
+                rules_open = true;
+            }
+            frame.end().await;
+            continue;
+        }
+
+        // A table's rules made by hand, saved next to the built-in ones
+        if rules_editor_open {
+            table.update(get_frame_time());
+            table.draw(&shoe);
+            let size = RulesEditor::SIZE;
+            let (panel_x, panel_y) = draw_panel(layout, size.x, size.y, "Make your own table", backdrop);
+            match rules_editor.update(panel_x, panel_y, &profile.settings, phase == GamePhase::WaitingToDeal) {
+                Some(EditorAction::Save(name, rules)) => match profile.settings.save_rules(&name, rules) {
+                    Ok(()) => {
+                        profile.mark_changed();
+                        table.show_settings(&profile.settings);
+                        log_info!("playing the table {}: {}", name, profile.settings.rules.felt_text());
+                        rules_editor_open = false;
+                        rules_open = true;
+                    }
+                    Err(reason) => rules_editor.set_error(reason),
+                },
+                Some(EditorAction::Delete(name)) => {
+                    profile.settings.delete_rules(&name);
+                    profile.mark_changed();
+                    table.show_settings(&profile.settings);
+                }
+                Some(EditorAction::Close) => {
+                    rules_editor_open = false;
+                    rules_open = true;
+                }
+                None => {}
+            }
+            frame.end().await;
+            continue;
+        }
+
+        // Basic strategy for every hand, the one being played outlined
+        if chart_open {
+            table.update(get_frame_time());
+            table.draw(&shoe);
+            let size = StrategyChart::size();
+            let (panel_x, panel_y) = draw_panel(layout, size.x + 60.0, size.y + 165.0, "Basic strategy", backdrop);
+            let current = match (phase, player_hands.get(active), dealer_hand.cards().first()) {
+                (GamePhase::PlayerTurn, Some(hand), Some(up_card)) => Some((hand.value(), hand.is_soft(), up_card.value())),
+                _ => None,
+            };
+            chart.draw(panel_x + 30.0, panel_y + 75.0, current);
+            overlay_text("H hit, S stand. C to close.", panel_x + 30.0, panel_y + size.y + 130.0, 22.0, LIGHTGRAY);
+            table.btn_chart_close.update_position(panel_x + size.x - 170.0, panel_y + size.y + 90.0, None, None);
+            if table.btn_chart_close.click() {
+                chart_open = false;
+            }
+            frame.end().await;
+            continue;
+        }
+
+        // Session goals to pick, the rewards they have paid and every achievement
+        if goals_open {
+            table.update(get_frame_time());
+            table.draw(&shoe);
+            let (panel_x, panel_y) = draw_panel(layout, 860.0, 640.0, "Achievements", backdrop);
+            overlay_text("Goals for this session", panel_x + 20.0, panel_y + 100.0, 28.0, GOLD);
+            let mut picks: Vec<&mut dyn Placeable> = table.btn_goal_picks.iter_mut().map(|button| button as &mut dyn Placeable).collect();
+            let rows = Column::new(panel_x + 20.0, panel_y + 120.0).spacing(12.0).place(&mut picks);
+            for (goal, row) in GOALS.iter().zip(rows) {
+                overlay_text(goal.name, panel_x + 125.0, row.y + 20.0, 22.0, WHITE);
+                let progress = goals.progress_text(goal.id);
+                let progress = if progress.is_empty() { format!("{} stars", goal.stars) } else { progress };
+                overlay_text(progress, panel_x + 125.0, row.y + 40.0, 18.0, LIGHTGRAY);
+            }
+            // Newest rewards first
+            let stars = profile.achievements.stars();
+            overlay_text(format!("Rewards: {} stars", stars), panel_x + 20.0, panel_y + 430.0, 28.0, GOLD);
+            for (row, reward) in profile.achievements.rewards.iter().rev().take(4).enumerate() {
+                let name = GOALS.iter().find(|goal| goal.id == reward.goal).map_or(reward.goal.as_str(), |goal| goal.name);
+                overlay_text(format!("+{}  {}", reward.stars, name), panel_x + 20.0, panel_y + 460.0 + row as f32 * 24.0, 20.0, WHITE);
+            }
+            let unlocked = profile.achievements.unlocked_count();
+            overlay_text(format!("Achievements: {}/{}", unlocked, ACHIEVEMENTS.len()), panel_x + 470.0, panel_y + 100.0, 28.0, GOLD);
+            for (row, achievement) in ACHIEVEMENTS.iter().enumerate() {
+                let y = panel_y + 135.0 + row as f32 * 46.0;
+                let color = if profile.achievements.is_unlocked(achievement.id) { GOLD } else { GRAY };
+                overlay_text(achievement.name, panel_x + 470.0, y, 24.0, color);
+                overlay_text(achievement.description, panel_x + 470.0, y + 20.0, 18.0, LIGHTGRAY);
+            }
+            table.btn_stats.update_position(panel_x + 220.0, panel_y + 565.0, None, None);
+            table.btn_goals_close.update_position(panel_x + 440.0, panel_y + 565.0, None, None);
+            // Picked between rounds, so a goal never starts half way through a hand
+            for (button, goal) in table.btn_goal_picks.iter_mut().zip(&GOALS) {
+                button.enabled = phase == GamePhase::WaitingToDeal;
+                if button.click() {
+                    goals.toggle(goal.id);
+                }
+            }
+            table.show_goals(&goals);
+            if table.btn_stats.click() {
+                goals_open = false;
+                stats_open = true;
+                stats_scroll.scroll_to(0.0);
+            }
+            if table.btn_goals_close.click() {
+                goals_open = false;
+                settings_open = true;
+            }
+            frame.end().await;
+            continue;
+        }
+
+        // What each starting hand has won or lost against each dealer up card
+        if stats_open {
+            table.update(get_frame_time());
+            table.draw(&shoe);
+            let (panel_x, panel_y) = draw_panel(layout, 940.0, 680.0, "Stats", backdrop);
+            let stats = &profile.stats;
+            overlay_text(
+                format!(
+                    "{} hands: {} won, {} lost, {} pushed, bankroll ran low {} times",
+                    stats.hands_played, stats.player_wins, stats.dealer_wins, stats.pushes, stats.low_bankroll_warnings
+                ),
+                panel_x + 200.0,
+                panel_y + 48.0,
+                22.0,
+                LIGHTGRAY,
+            );
+            overlay_text("Won or lost by your first two cards (down) and the dealer's up card (across)", panel_x + 20.0, panel_y + 82.0, 20.0, LIGHTGRAY);
+            let column_x = |column: usize| panel_x + 130.0 + column as f32 * STATS_CELL_WIDTH;
+            for (column, name) in UP_CARDS.iter().chain(std::iter::once(&"All")).enumerate() {
+                stats_cell_text(name.to_string(), column_x(column), panel_y + 112.0, GOLD);
+            }
+            stats_scroll.set_area(panel_x + 20.0, panel_y + 122.0, 900.0, 380.0);
+            stats_scroll.set_content_height(START_ROWS.len() as f32 * STATS_ROW_HEIGHT);
+            stats_scroll.update();
+            stats_scroll.begin();
+            for (row, name) in START_ROWS.iter().enumerate() {
+                let row_y = row as f32 * STATS_ROW_HEIGHT;
+                if !stats_scroll.shows(row_y, STATS_ROW_HEIGHT) {
+                    continue;
+                }
+                let y = stats_scroll.content_pos(0.0, row_y + 17.0).y;
+                overlay_text(*name, panel_x + 20.0, y, 18.0, WHITE);
+                for column in 0..UP_CARDS.len() {
+                    let (text, color) = tally_text(stats.cell(row, column));
+                    stats_cell_text(text, column_x(column), y, color);
+                }
+                let (text, color) = tally_text(stats.row_total(row));
+                stats_cell_text(text, column_x(UP_CARDS.len()), y, color);
+            }
+            stats_scroll.end();
+            overlay_text("All", panel_x + 20.0, panel_y + 530.0, 18.0, GOLD);
+            for column in 0..UP_CARDS.len() {
+                let (text, color) = tally_text(stats.column_total(column));
+                stats_cell_text(text, column_x(column), panel_y + 530.0, color);
+            }
+            // How fast this session is going, and how long each kind of hand takes to decide
+            overlay_text("This session:", panel_x + 20.0, panel_y + 560.0, 20.0, GOLD);
+            overlay_text(pacing.summary_text(), panel_x + 150.0, panel_y + 560.0, 20.0, WHITE);
+            overlay_text("Deciding:", panel_x + 20.0, panel_y + 584.0, 20.0, GOLD);
+            overlay_text(pacing.decisions_text(), panel_x + 150.0, panel_y + 584.0, 18.0, LIGHTGRAY);
+            table.btn_stats_close.update_position(panel_x + 370.0, panel_y + 605.0, None, None);
+            // The play log, for the player to study their own game
+            overlay_text("Play log:", panel_x + 20.0, panel_y + 640.0, 26.0, WHITE);
+            table.btn_play_log.update_position(panel_x + 135.0, panel_y + 610.0, None, None);
+            table.btn_play_log_clear.update_position(panel_x + 720.0, panel_y + 610.0, None, None);
+            table.btn_play_log_clear.set_text(format!("Clear ({})", analytics.lines()));
+            table.btn_play_log_clear.enabled = analytics.lines() > 0;
+            overlay_text(PLAY_LOG_NOTE, panel_x + 20.0, panel_y + 672.0, 16.0, LIGHTGRAY);
+            if table.btn_stats_close.click() {
+                stats_open = false;
+                goals_open = true;
+            }
+            if table.btn_play_log.click() {
+                profile.settings.analytics = !profile.settings.analytics;
+                profile.mark_changed();
+                analytics.set_enabled(profile.settings.analytics);
+                table.show_settings(&profile.settings);
+            }
+            if table.btn_play_log_clear.click()
+                && let Err(err) = analytics.clear()
+            {
+                log_warn!("could not clear the play log: {}", err);
+            }
+            frame.end().await;
+            continue;
+        }
+
+        // The changelog built into this build, newest release first
+        if whats_new_open {
+            table.update(get_frame_time());
+            table.draw(&shoe);
+            let (panel_x, panel_y) = draw_panel(layout, 700.0, 600.0, "What's New", backdrop);
+            overlay_text(version::build_text(), panel_x + 20.0, panel_y + 80.0, 22.0, LIGHTGRAY);
+            let rows: Vec<(&str, bool)> = releases
+                .iter()
+                .flat_map(|release| std::iter::once((release.version, true)).chain(release.changes.iter().map(|change| (*change, false))))
+                .collect();
+            whats_new_scroll.set_area(panel_x + 20.0, panel_y + 95.0, 660.0, 420.0);
+            whats_new_scroll.set_content_height(rows.len() as f32 * 28.0);
+            whats_new_scroll.update();
+            whats_new_scroll.begin();
+            for (row, (text, heading)) in rows.into_iter().enumerate() {
+                let row_y = row as f32 * 28.0;
+                if !whats_new_scroll.shows(row_y, 28.0) {
+                    continue;
+                }
+                let Vec2 { x, y } = whats_new_scroll.content_pos(0.0, row_y + 22.0);
+                if heading {
+                    overlay_text(format!("Version {}", text), x, y, 26.0, GOLD);
+                } else {
+                    overlay_text(format!("- {}", text), x + 10.0, y, 22.0, WHITE);
+                }
+            }
+            whats_new_scroll.end();
+            table.btn_whats_new_close.update_position(panel_x + 480.0, panel_y + 530.0, None, None);
+            if table.btn_whats_new_close.click() {
+                whats_new_open = false;
+                settings_open = true;
+            }
+            frame.end().await;
+            continue;
+        }
+
+        if fairness_open {
+            table.draw(&shoe);
+            let (panel_x, panel_y) = draw_panel(layout, 640.0, 520.0, "Fairness Log", backdrop);
+            let chain_text = if audit.verify_chain() { "Log chain intact" } else { "Log chain BROKEN" };
+            overlay_text(chain_text, panel_x + 20.0, panel_y + 90.0, 26.0, GOLD);
+            let rounds = audit.verify_rounds();
+            if rounds.is_empty() {
+                overlay_text("No rounds played yet", panel_x + 20.0, panel_y + 130.0, 26.0, WHITE);
+            }
+            // Newest rounds first, the older ones are a scroll away
+            fairness_scroll.set_area(panel_x + 20.0, panel_y + 105.0, 600.0, 320.0);
+            fairness_scroll.set_content_height(rounds.len() as f32 * 32.0);
+            fairness_scroll.update();
+            fairness_scroll.begin();
+            for (row, (round, status)) in rounds.iter().rev().enumerate() {
+                let row_y = row as f32 * 32.0;
+                if !fairness_scroll.shows(row_y, 32.0) {
+                    continue;
+                }
+                let Vec2 { x, y } = fairness_scroll.content_pos(0.0, row_y + 25.0);
+                let (status_text, color) = match status {
+                    Verification::Verified => ("verified".to_string(), GREEN),
+                    Verification::PendingReveal => ("waiting for shoe reveal".to_string(), LIGHTGRAY),
+                    Verification::Failed(reason) => (format!("FAILED: {}", reason), RED),
+                };
+                let outcomes: Vec<String> = round.outcomes.iter().map(|outcome| format!("{:?}", outcome)).collect();
+                let line = format!("Round {}  shoe {}  {}", round.number, round.shoe_id, outcomes.join(", "));
+                overlay_text(line, x, y, 24.0, WHITE);
+                overlay_text(status_text, x + 330.0, y, 22.0, color);
+            }
+            fairness_scroll.end();
+            table.btn_reveal_shoe.update_position(panel_x + 60.0, panel_y + 440.0, None, None);
+            table.btn_fairness_close.update_position(panel_x + 380.0, panel_y + 440.0, None, None);
+            // Retiring the shoe early reveals its seed so its rounds can be checked now
+            table.btn_reveal_shoe.enabled = phase == GamePhase::WaitingToDeal && audit.has_pending_rounds();
+            table.btn_reveal_shoe.set_disabled_reason(if phase != GamePhase::WaitingToDeal {
+                FINISH_HAND_FIRST
+            } else {
+                "No rounds from this shoe are waiting to be checked"
+            });
+            if table.btn_reveal_shoe.click() {
+                shoe.reshuffle();
+                fairness_open = false;
+            }
+            if table.btn_fairness_close.click() {
+                fairness_open = false;
+            }
+            if shoe.take_reshuffle_event() {
+                bus.emit(GameEvent::ShoeShuffled { seed: shoe.seed() });
+                bus.dispatch(&mut [&mut profile, &mut shuffle_anim, &mut audit, &mut history, &mut audio, &mut trainer, &mut drills, &mut announcer, &mut goals, &mut pacing, &mut analytics, &mut table]);
+            }
+            frame.end().await;
+            continue;
+        }
+
+        // One hand from the history, opened from the results strip
+        if let Some(record) = hand_open.and_then(|number| history.hands().find(|record| record.number == number)) {
+            table.draw(&shoe);
+            let (panel_x, panel_y) = draw_panel(layout, 620.0, 560.0, &format!("Hand #{}", record.number), backdrop);
+            // The dealer's cards along the top of the picture, the player's along the bottom
+            let snapshot = table.snapshots.get(&tm, record);
+            let snapshot_size = SNAPSHOT_SIZE * 0.85;
+            let snapshot_pos = vec2(panel_x + (620.0 - snapshot_size.x) / 2.0, panel_y + 80.0);
+            layers::push(Layer::Overlay, move || {
+                let params = DrawTextureParams { dest_size: Some(snapshot_size), ..Default::default() };
+                draw_texture_ex(&snapshot, snapshot_pos.x, snapshot_pos.y, WHITE, params);
+            });
+            let reason = explain(&Hand::from_cards(&record.player_cards), &Hand::from_cards(&record.dealer_cards));
+            let result = format!("{} {}", outcome_text(record.outcome.unwrap_or(Outcome::NoWinner)), reason);
+            overlay_text(result, panel_x + 20.0, panel_y + 355.0, 24.0, GOLD);
+            for (row, decision) in record.decisions.iter().enumerate() {
+                overlay_text(decision.clone(), panel_x + 20.0, panel_y + 385.0 + row as f32 * 24.0, 22.0, LIGHTGRAY);
+            }
+            // The seed stays hidden while its shoe is in play, it would give away the cards to come. A retired
+            // shoe's seed is safe to hand out, every shoe gets a seed of its own (see entropy.rs) and an
+            // older save that didn't isn't carried on (autosave::load_shoe).
+            let seed_shown = record.shoe.filter(|position| position.seed != shoe.seed());
+            let seed_text = match (record.shoe, seed_shown) {
+                (_, Some(position)) => format!("Shoe seed {}, from card {}", position.seed, position.dealt + 1),
+                (Some(_), None) => "Shoe seed hidden until this shoe is reshuffled".to_string(),
+                (None, None) => "Cards set up for this hand, no shoe seed".to_string(),
+            };
+            overlay_text(seed_text, panel_x + 20.0, panel_y + 460.0, 22.0, LIGHTGRAY);
+            table.btn_copy_seed.enabled = seed_shown.is_some();
+            table.btn_copy_seed.set_disabled_reason(if record.shoe.is_some() { SEED_SECRET } else { SEED_SET_UP });
+            table.btn_copy_seed.update_position(panel_x + 90.0, panel_y + 480.0, None, None);
+            table.btn_hand_close.update_position(panel_x + 330.0, panel_y + 480.0, None, None);
+            if table.btn_copy_seed.click()
+                && let Some(position) = seed_shown
+            {
+                // Everything to start the game here: the shoe's seed and the cards already dealt from it
+                miniquad::window::clipboard_set(&position.args());
+                seed_copied_until = get_time() + 2.0;
+            }
+            if get_time() < seed_copied_until {
+                overlay_text("Copied, start the game with it to deal these cards again", panel_x + 20.0, panel_y + 435.0, 22.0, GOLD);
+            }
+            if table.btn_hand_close.click() {
+                hand_open = None;
+            }
+            frame.end().await;
+            continue;
+        }
+
+        // Blackjack against an ace: the hand waits until even money is taken or turned down
+        if let Some(dialog) = even_money.as_mut() {
+            table.update(get_frame_time());
+            table.draw(&shoe);
+            if let Some(answer) = dialog.update(layout.virtual_width, layout.virtual_height) {
+                let taken = answer == 0;
+                bus.emit(GameEvent::EvenMoney { taken, timed_out: dialog.timed_out() });
+                if taken {
+                    decision_timer.stop();
+                    let results = [(Outcome::PlayerWin, false)];
+                    end_round(&mut table, &mut bus, &results, &player_hands, &dealer_hand, &bet, profile.settings.rules.blackjack_pays);
+                    settle_bet(&mut table, &mut bus, &mut profile, &mut ledger, &mut bet, &results);
+                    table.show_explainer(tr("explain.even_money", &[]));
+                    phase = GamePhase::RoundOver;
+                } else if profile.settings.rules.dealer_peeks {
+                    // The hole card only comes out once even money is turned down
+                    let up_card = dealer_hand.cards()[0];
+                    hole_card = Some(deal_hole_card(&mut shoe, &mut bus, &mut table, &profile.settings.rules, up_card));
+                }
+                even_money = None;
+            }
+            bus.dispatch(&mut [&mut profile, &mut shuffle_anim, &mut audit, &mut history, &mut audio, &mut trainer, &mut drills, &mut announcer, &mut goals, &mut pacing, &mut analytics, &mut table]);
+            frame.end().await;
+            continue;
+        }
+
+        // The dealer checks the hole card before the player can act
+        if table.hole_card.is_peeking() {
+            if table.update(get_frame_time()) && let Some(card) = hole_card {
+                let mut peeked = dealer_hand.clone();
+                peeked.add(card);
+                if peeked.is_blackjack() {
+                    log_info!("dealer peeked and has blackjack");
+                    decision_timer.stop();
+                    reveal_hole_card(&mut table, &tm, &mut hole_card, &mut dealer_hand);
+                    table.show_hands(&player_hands, &dealer_hand);
+                    let results = settle_hands(&player_hands, &dealer_hand);
+                    end_round(&mut table, &mut bus, &results, &player_hands, &dealer_hand, &bet, profile.settings.rules.blackjack_pays);
+                    settle_bet(&mut table, &mut bus, &mut profile, &mut ledger, &mut bet, &results);
+                    phase = GamePhase::RoundOver;
+                }
+            }
+            table.draw(&shoe);
+            bus.dispatch(&mut [&mut profile, &mut shuffle_anim, &mut audit, &mut history, &mut audio, &mut trainer, &mut drills, &mut announcer, &mut goals, &mut pacing, &mut analytics, &mut table]);
+            frame.end().await;
+            continue;
+        }
+
+        // The deal and the dealer's turn play out a step at a time
+        for cue in script.update(get_frame_time()) {
+            match cue {
+                Cue::PlayerCard(hand) => {
+                    let card = deal_card(&mut shoe, &mut bus, Seat::Player(hand));
+                    let player_hand = &mut player_hands[hand];
+                    table.card_pool.show(&tm, &mut table.player_cards[hand][player_hand.len()], &card);
+                    player_hand.add(card);
+                    table.show_hands(&player_hands, &dealer_hand);
+                }
+                Cue::DealerUpCard => {
+                    let card = deal_card(&mut shoe, &mut bus, Seat::Dealer);
+                    table.card_pool.show(&tm, &mut table.dealer_cards[0], &card);
+                    dealer_hand.add(card);
+                    table.show_hands(&player_hands, &dealer_hand);
+                }
+                Cue::StartPlayerTurn => {
+                    active = 0;
+                    table.active_hand = Some(active);
+                    show_actions(&mut table, &player_hands[active]);
+                    decision_timer.start(profile.settings.decision_seconds as f32);
+                    let up_card = dealer_hand.cards()[0];
+                    // Even money is only offered when a single hand is played
+                    if rules::offers_even_money(&player_hands, up_card) {
+                        even_money = Some(
+                            Dialog::new("Even Money?", "The dealer shows an ace. Take even money?", &["Yes", "No"])
+                                .with_timeout(EVEN_MONEY_SECONDS, 1),
+                        );
+                    } else if profile.settings.rules.dealer_peeks {
+                        hole_card = Some(deal_hole_card(&mut shoe, &mut bus, &mut table, &profile.settings.rules, up_card));
+                    }
+                }
+                Cue::RevealHoleCard => {
+                    reveal_hole_card(&mut table, &tm, &mut hole_card, &mut dealer_hand);
+                    table.show_hands(&player_hands, &dealer_hand);
+                }
+                // Dealer draws one card at a time until reaching DEALER_STANDS_ON, then the round is settled
+                // When every hand is bust or a settled blackjack the dealer's cards can't change anything, so the draw is skipped or hurried
+                Cue::DealerDraw => {
+                    let no_contest = !hand::dealer_can_change_results(&player_hands, &dealer_hand, hole_card.is_some());
+                    let skip = no_contest && profile.settings.skips_no_contest();
+                    if !skip && dealer_hand.len() < table.dealer_cards.len() && rules::dealer_draws(&dealer_hand) {
+                        let card = deal_card(&mut shoe, &mut bus, Seat::Dealer);
+                        table.card_pool.show(&tm, &mut table.dealer_cards[dealer_hand.len()], &card);
+                        dealer_hand.add(card);
+                        table.show_hands(&player_hands, &dealer_hand);
+                        script.wait(dealer_pause(no_contest, &profile.settings)).then(Cue::DealerDraw);
+                    } else {
+                        let results = settle_hands(&player_hands, &dealer_hand);
+                        end_round(&mut table, &mut bus, &results, &player_hands, &dealer_hand, &bet, profile.settings.rules.blackjack_pays);
+                        settle_bet(&mut table, &mut bus, &mut profile, &mut ledger, &mut bet, &results);
+                        phase = GamePhase::RoundOver;
+                    }
+                }
+            }
+        }
+        if table.btn_exit.click() {
+            profile.save();
             break;
         }
-        if btn_deal.click() {
-           // first_card.set_texture(cards[random_card_1]).await;
-               first_card.set_preload(tm.get_preload(cards[random_card_1]).unwrap());
-            //second_card.set_texture(cards[random_card_2]).await;
-               second_card.set_preload(tm.get_preload(cards[random_card_2]).unwrap());
-            playertotal = scores[random_card_1] + scores[random_card_2];
-            lbl_playerscore.set_text(format!("{}", playertotal));
-            
-            if playertotal > 20 {
-                btn_hit.enabled = false;
-            if playertotal > 21 {
-                (playertotal  = playertotal -10);
-            }
-            }
-            let random_dealer_1 = rand::gen_range(1, 52);
-            //dealer_card1.set_texture(cards[random_dealer_1]).await;
-               dealer_card1.set_preload(tm.get_preload(cards[random_dealer_1]).unwrap());
-            dealertotal = scores[random_dealer_1];
-            lbl_dealerscore.set_text(format!("{}", dealertotal));
-            if dealertotal > 21 {
-                (dealertotal = dealertotal -10);
-            }
-            btn_deal.enabled = false;
-            btn_hit.enabled = true;
-            btn_stand.enabled = true;
-            btn_replay.enabled = false;
-        }
-        if btn_hit.click() {
-            numofhits += 1;
-             let random_card_3 = rand::gen_range(1, 52);
-
-            if numofhits == 1 {
-                //third_card.set_texture(cards[random_card_3]).await;
-                third_card.set_preload(tm.get_preload(cards[random_card_3]).unwrap());
-                playertotal += scores[random_card_3];
-                if playertotal > 22 {
-                    btn_hit.enabled = false;
-                }
-
-                lbl_playerscore.set_text(format!("{}", playertotal));
-
-            } else if numofhits==2 {
-                 //fourth_card.set_texture(cards[random_card_3]).await;
-                fourth_card.set_preload(tm.get_preload(cards[random_card_3]).unwrap());
-                playertotal += scores[random_card_3];
-                lbl_playerscore.set_text(format!("{}", playertotal));
-                if playertotal > 20 {
-                    btn_hit.enabled = false;
-                }
-            } else if numofhits==3 {
-                btn_hit.enabled = false;
-                // fifth_card.set_texture(cards[random_card_3]).await;
-                fifth_card.set_preload(tm.get_preload(cards[random_card_3]).unwrap());
-                playertotal += scores[random_card_3];
-                lbl_playerscore.set_text(format!("{}", playertotal));
-                if playertotal > 20 {
-                    btn_hit.enabled = false;
-                }
-            }
-        }
-        if btn_stand.click() {
-            let random_dealer_2 = rand::gen_range(1, 52);
-            let random_dealer_3 = rand::gen_range(1, 52);
-            let random_dealer_4 = rand::gen_range(1, 52);
-            let random_dealer_5 = rand::gen_range(1, 52);
-            //dealer_card2.set_texture(cards[random_dealer_2]).await;
-            dealer_card2.set_preload(tm.get_preload(cards[random_dealer_2]).unwrap());
-            dealertotal += scores[random_dealer_2];
-            lbl_dealerscore.set_text(format!("{}", dealertotal));
-            if dealertotal < 16 {
-            //dealer_card3.set_texture(cards[random_dealer_3]).await;
-            dealer_card3.set_preload(tm.get_preload(cards[random_dealer_3]).unwrap());
-            dealertotal += scores[random_dealer_3];
-            lbl_dealerscore.set_text(format!("{}", dealertotal));
-            }
-            if dealertotal < 16 {
-            //dealer_card4.set_texture(cards[random_dealer_4]).await;
-            dealer_card4.set_preload(tm.get_preload(cards[random_dealer_4]).unwrap());
-            dealertotal += scores[random_dealer_4];
-            lbl_dealerscore.set_text(format!("{}", dealertotal));
-            }
-            if dealertotal < 16 {
-            //dealer_card5.set_texture(cards[random_dealer_5]).await;
-            dealer_card5.set_preload(tm.get_preload(cards[random_dealer_5]).unwrap());
-            dealertotal += scores[random_dealer_5];
-            lbl_dealerscore.set_text(format!("{}", dealertotal));
-            }
-
-            if playertotal > 21 && dealertotal < 22 {
-                lbl_winner.set_text("Dealer Wins!");
-                lbl_dealercounter.set_text(format!("{}", lbl_dealercounter.get_text().parse::<i32>().unwrap() + 1));
-            } else if dealertotal > 21 && playertotal < 22 {
-                lbl_winner.set_text("You Win!");
-                lbl_playercounter.set_text(format!("{}", lbl_playercounter.get_text().parse::<i32>().unwrap() + 1));
-            } else if dealertotal > playertotal && dealertotal < 22 {
-                lbl_winner.set_text("Dealer Wins!");
-                lbl_dealercounter.set_text(format!("{}", lbl_dealercounter.get_text().parse::<i32>().unwrap() + 1));
-            } else if dealertotal < playertotal && playertotal < 22 {
-                lbl_winner.set_text("You Win!");
-                lbl_playercounter.set_text(format!("{}", lbl_playercounter.get_text().parse::<i32>().unwrap() + 1));
-            } else if dealertotal > 21 && playertotal > 21 {
-                lbl_winner.set_text("No Winner!");
+        if table.btn_settings.click() {
+            settings_open = true;
+            transfer_message.clear();
+        }
+        if table.btn_fairness.click() {
+            fairness_open = true;
+            fairness_scroll.scroll_to(0.0);
+        }
+        table.show_hovered_hand(&tm, &history);
+        if let Some(number) = table.results.clicked() {
+            hand_open = Some(number);
+        }
+        // Between hands the bet spots take chips: tap to add (and pick the spot for the tray's chips), double-click to rebet, hold to clear
+        if phase == GamePhase::WaitingToDeal {
+            let (pos, down) = pointer();
+            if (0..table.hands).any(|hand| layout.hand_bet_spot(hand, table.hands).contains(pos)) {
+                cursor::want(if down { CursorKind::Grab } else { CursorKind::Pointer });
+            }
+        }
+        let hands = table.hands;
+        let spot_at = |pos: Vec2| (0..hands).find(|hand| layout.hand_bet_spot(*hand, hands).contains(pos));
+        chip_hand = chip_hand.min(hands - 1);
+        // The chip tray: click a chip onto the chosen spot, drag it onto any spot, right click or hold to take it off
+        if phase == GamePhase::WaitingToDeal
+            && let Some((value, action)) = table.update_chips(profile.settings.gesture_thresholds().long_press_seconds)
+        {
+            let bankroll = profile.info.bankroll;
+            let before = bet.total();
+            let covered = match action {
+                ChipAction::Add => bet.add(chip_hand, value, bankroll),
+                ChipAction::Remove => {
+                    bet.remove(chip_hand, value);
+                    true
+                }
+                ChipAction::Dropped(pos) => match spot_at(pos) {
+                    Some(hand) => {
+                        chip_hand = hand;
+                        bet.add(hand, value, bankroll)
+                    }
+                    None => true,
+                },
+            };
+            if !covered {
+                table.lbl_notice.set_text(format!("Your bankroll of {} can't cover that bet", i18n::money(bankroll)));
+                notice_until = get_time() + 3.0;
+            }
+            if bet.total() > before {
+                bus.emit(GameEvent::BetPlaced { amount: bet.total() - before });
+            }
+            table.show_bets(&bet);
+        }
+        if let Some(gesture) = gestures.update() && phase == GamePhase::WaitingToDeal {
+            let bankroll = profile.info.bankroll;
+            let before = bet.total();
+            if let Gesture::Tap(pos) = gesture
+                && let Some(hand) = spot_at(pos)
+            {
+                chip_hand = hand;
+            }
+            let covered = match gesture {
+                Gesture::Tap(pos) => spot_at(pos).is_none_or(|hand| bet.add(hand, BET_STEP, bankroll)),
+                // With no last bets to put back, the second tap just adds another chip
+                Gesture::DoubleClick(pos) => spot_at(pos).is_none_or(|hand| bet.rebet(hands, bankroll) || bet.add(hand, BET_STEP, bankroll)),
+                Gesture::LongPress(pos) => {
+                    if let Some(hand) = spot_at(pos) {
+                        bet.clear(hand);
+                    }
+                    true
+                }
+            };
+            if !covered {
+                table.lbl_notice.set_text(format!("Your bankroll of {} can't cover that bet", i18n::money(bankroll)));
+                notice_until = get_time() + 3.0;
+            }
+            if bet.total() > before {
+                bus.emit(GameEvent::BetPlaced { amount: bet.total() - before });
+            }
+            table.show_bets(&bet);
+        }
+        let screenshot_requested = keys.press(&table.btn_screenshot, KeyAction::Screenshot);
+        // Out of time: stand, or play basic strategy if the settings say so
+        let mut timed_out_move = None;
+        if decision_timer.update(get_frame_time()) {
+            let strategy_move = basic_strategy(&player_hands[active], dealer_hand.cards()[0]);
+            let hit = profile.settings.timeout_plays_strategy && table.btn_hit.enabled && strategy_move == Action::Hit;
+            timed_out_move = Some(if hit { Action::Hit } else { Action::Stand });
+            log_info!("decision timer ran out, playing {:?}", timed_out_move);
+        }
+        // Above the gap between Hit and Stand
+        decision_timer.draw(vec2((layout.hit.x + layout.hit.w + layout.stand.x) / 2.0, layout.hit.y - 24.0));
+        // Once the round is settled Rebet and Rebet & Deal take the place of Hit and Stand
+        let (rebet_clicked, rebet_deal_clicked) = if phase == GamePhase::RoundOver {
+            (keys.press(&table.btn_rebet, KeyAction::Rebet), keys.press(&table.btn_rebet_deal, KeyAction::RebetDeal))
+        } else {
+            (false, false)
+        };
+        // Between rounds a percentage of the bankroll can be bet in one click, beside the chips
+        if phase == GamePhase::WaitingToDeal {
+            table.show_percent_bets(profile.info.bankroll);
+            let clicks: Vec<bool> = table.btn_percent_bets.iter().map(|button| button.click()).collect();
+            if let Some(i) = clicks.iter().position(|clicked| *clicked)
+                && bet.bet_each(table.hands, percent_bet(profile.info.bankroll, PERCENT_BETS[i]), profile.info.bankroll)
+            {
+                bus.emit(GameEvent::BetPlaced { amount: bet.total() });
+                table.show_bets(&bet);
+            }
+        }
+        // Between rounds the recent bets take the place of Hit and Stand, once there are some
+        let quick_bets_shown = phase == GamePhase::WaitingToDeal && !bet.recent().is_empty();
+        if quick_bets_shown {
+            table.show_quick_bets(&bet, profile.info.bankroll);
+            // Every button has to be clicked to be drawn, so look at all of them before picking one
+            let clicks: Vec<bool> = table.btn_quick_bets.iter().take(bet.recent().len()).map(|button| button.click()).collect();
+            if let Some(i) = clicks.iter().position(|clicked| *clicked)
+                && bet.bet_each(table.hands, bet.recent()[i], profile.info.bankroll)
+            {
+                bus.emit(GameEvent::BetPlaced { amount: bet.total() });
+                table.show_bets(&bet);
+            }
+        }
+        if keys.press(&table.btn_replay, KeyAction::Replay) || rebet_clicked || rebet_deal_clicked {
+            table.clear_cards(&tm, &player_hands, &dealer_hand);
+            // The cut card came out last round, so shuffle before the next deal
+            if shoe.needs_reshuffle() {
+                shoe.reshuffle();
+            }
+            phase = GamePhase::WaitingToDeal;
+            table.btn_deal.enabled = true;
+            table.btn_hit.enabled = false;
+            table.btn_hit.set_disabled_reason(DEAL_FIRST);
+            table.btn_stand.enabled = false;
+            table.btn_stand.set_disabled_reason(DEAL_FIRST);
+            for hand in player_hands.iter_mut() {
+                hand.clear();
+            }
+            dealer_hand.clear();
+            table.show_hands(&player_hands, &dealer_hand);
+            table.lbl_winner.set_text("");
+            table.show_explainer("");
+            if (rebet_clicked || rebet_deal_clicked) && bet.rebet(table.hands, profile.info.bankroll) {
+                bus.emit(GameEvent::BetPlaced { amount: bet.total() });
+                table.show_bets(&bet);
+            }
+        }
+        let deal_clicked = keys.press(&table.btn_deal, KeyAction::Deal) || rebet_deal_clicked;
+        if deal_clicked {
+            // Anything still held is from a round that never settled
+            ledger.rollback(profile.info.bankroll, "the last round never settled");
+        }
+        // Every bet has to be within the table's limits, a spot left empty is played for fun
+        let limits = profile.settings.rules.limits;
+        let within_limits = (0..table.hands).all(|hand| limits.allows(bet.amount(hand)));
+        if deal_clicked && !within_limits {
+            table.lbl_notice.set_text(format!("This table takes bets of {} a hand", limits.text()));
+            notice_until = get_time() + 3.0;
+        }
+        // The bets are held from the deal until the round settles
+        let reserved = deal_clicked
+            && within_limits
+            && match ledger.reserve(profile.info.bankroll, bet.total()) {
+                Ok(_) => true,
+                Err(err) => {
+                    log_error!("can't deal: {}", err);
+                    table.lbl_notice.set_text(format!("Your bankroll of {} can't cover that bet", i18n::money(ledger.available(profile.info.bankroll))));
+                    notice_until = get_time() + 3.0;
+                    false
+                }
+            };
+        if reserved {
+            table.show_profile(&profile.info, &ledger);
+            // A famous hand, or a practice drill putting one of the player's weak spots, in the first hand
+            let mut set_up = false; // The shoe was stacked, its cards no longer follow its seed
+            if let Some(hand) = famous.and_then(|i| famous_hands.get(i)) {
+                if shoe.needs_reshuffle() {
+                    shoe.reshuffle();
+                }
+                let dealt = hand.deal(&mut shoe, table.hands);
+                set_up = dealt;
+                // Without the hand there is nothing to give a verdict on
+                famous_checked = !dealt;
+                let notice = if dealt { format!("Famous hand: {}", hand.name) } else { "Famous hand skipped, the shoe is short of the cards for it".to_string() };
+                table.lbl_notice.set_text(notice);
+                notice_until = get_time() + 3.0;
+            } else if profile.settings.drills() {
+                if shoe.needs_reshuffle() {
+                    shoe.reshuffle();
+                }
+                let notice = match drills.deal_drill(&mut shoe, table.hands) {
+                    Some(situation) => {
+                        set_up = true;
+                        format!("Drill: {}", situation.text())
+                    }
+                    None => "Drill skipped, the shoe is short of the cards for it".to_string(),
+                };
+                table.lbl_notice.set_text(notice);
+                notice_until = get_time() + 3.0;
+            }
+            history.start_round(if set_up { None } else { Some(shoe.position()) });
+            player_hands = vec![Hand::new(); table.hands];
+            dealer_hand.clear();
+            // The round has started, bets are locked in while the cards go out
+            phase = GamePhase::PlayerTurn;
+            table.btn_deal.enabled = false;
+            table.btn_replay.enabled = false;
+            table.btn_hit.set_disabled_reason(DEALER_PLAYING);
+            table.btn_stand.set_disabled_reason(DEALER_PLAYING);
+            // Two cards to each hand from left to right, then the dealer's up card
+            for _ in 0..2 {
+                for hand in 0..table.hands {
+                    script.then(Cue::PlayerCard(hand)).wait(DEAL_GAP);
+                }
+            }
+            script.then(Cue::DealerUpCard).wait(DEAL_GAP).then(Cue::StartPlayerTurn);
+        }
+        let playing = phase != GamePhase::RoundOver && !quick_bets_shown;
+        let hit_clicked = playing && keys.press(&table.btn_hit, KeyAction::Hit);
+        let stand_clicked = playing && keys.press(&table.btn_stand, KeyAction::Stand);
+        // Holding Tab puts each button's key on its corner
+        if keys::hints_held() {
+            table.draw_key_hints(&keys, playing, phase == GamePhase::RoundOver);
+        }
+        // What the player picked themselves, for the drills (a timeout isn't their decision)
+        if hit_clicked || stand_clicked {
+            let hand = &player_hands[active];
+            bus.emit(GameEvent::PlayerDecision { hand: active, total: hand.value(), soft: hand.is_soft(), dealer_up: dealer_hand.cards()[0], hit: hit_clicked });
+            // The first move on a famous hand, weighed by the EV engine
+            if famous.is_some() && !famous_checked && active == 0 {
+                famous_checked = true;
+                let up_card = dealer_hand.cards()[0];
+                famous_ev.update(hand, up_card, &Composition::full_deck(), profile.settings.rules.dealer_checks(up_card));
+                table.lbl_notice.set_text(famous_hands::verdict(famous_ev.estimate(), hit_clicked));
+                notice_until = get_time() + 5.0;
+            }
+        }
+        if hit_clicked || timed_out_move == Some(Action::Hit) {
+            let card = deal_card(&mut shoe, &mut bus, Seat::Player(active));
+            let slot = player_hands[active].len();
+            table.card_pool.show(&tm, &mut table.player_cards[active][slot], &card);
+            // With the squeeze on the card waits face down, it joins the hand once the player turns it over
+            if profile.settings.squeeze {
+                table.start_squeeze(&tm, active, slot, &card);
+                squeezed = Some(card);
+                decision_timer.stop();
+                for button in [&mut table.btn_hit, &mut table.btn_stand] {
+                    button.enabled = false;
+                    button.set_disabled_reason(SQUEEZE_FIRST);
+                }
             } else {
-                lbl_winner.set_text("Draw!");
-            }
-
-            btn_hit.enabled = false;
-            btn_stand.enabled = false;
-            btn_replay.enabled = true;
-        }
-        if btn_replay.click() {
-            //first_card.set_texture("assets/Empty.png").await;
-            first_card.set_preload(tm.get_preload("assets/Empty.png").unwrap());
-            //second_card.set_texture("assets/Empty.png").await;
-            second_card.set_preload(tm.get_preload("assets/Empty.png").unwrap());
-            //dealer_card1.set_texture("assets/Empty.png").await;
-            dealer_card1.set_preload(tm.get_preload("assets/Empty.png").unwrap());
-            //dealer_card2.set_texture("assets/Empty.png").await;
-            dealer_card2.set_preload(tm.get_preload("assets/Empty.png").unwrap());
-            //dealer_card3.set_texture("assets/Empty.png").await;
-            dealer_card3.set_preload(tm.get_preload("assets/Empty.png").unwrap());
-            //third_card.set_texture("assets/Empty.png").await;
-            third_card.set_preload(tm.get_preload("assets/Empty.png").unwrap());
-            //fourth_card.set_texture("assets/Empty.png").await;
-            fourth_card.set_preload(tm.get_preload("assets/Empty.png").unwrap());
-            //fifth_card.set_texture("assets/Empty.png").await;
-            fifth_card.set_preload(tm.get_preload("assets/Empty.png").unwrap());
-            btn_deal.enabled = true;
-            btn_hit.enabled = false;
-            btn_stand.enabled = false;
-            lbl_playerscore.set_text("");
-            lbl_dealerscore.set_text("");
-            numofhits = 0;
-            lbl_winner.set_text("");
-        }
-        first_card.draw();
-        second_card.draw();
-        third_card.draw();
-        fourth_card.draw();
-        dealer_card1.draw();
-        dealer_card2.draw();
-        lbl_dealerhand.draw();
-        lbl_playerhand.draw();
-        lbl_playerscore.draw();
-        lbl_dealerscore.draw();
-        dealer_card3.draw();
-        fifth_card.draw();
-        lbl_winner.draw();
-        lbl_playerwins.draw();
-        lbl_dealerwins.draw();
-        lbl_dealercounter.draw();
-        lbl_playercounter.draw();
-        next_frame().await;
-    }
-}
\ No newline at end of file
+                add_hit_card(&mut table, &mut bus, &mut player_hands, active, &dealer_hand, card);
+                decision_timer.start(profile.settings.decision_seconds as f32);
+            }
+        }
+        if table.update_squeeze()
+            && let Some(card) = squeezed.take()
+        {
+            add_hit_card(&mut table, &mut bus, &mut player_hands, active, &dealer_hand, card);
+            decision_timer.start(profile.settings.decision_seconds as f32);
+        }
+        if stand_clicked || timed_out_move == Some(Action::Stand) {
+            // On to the next hand, the dealer plays once the last one stands
+            active += 1;
+            if active < player_hands.len() {
+                table.active_hand = Some(active);
+                show_actions(&mut table, &player_hands[active]);
+                decision_timer.start(profile.settings.decision_seconds as f32);
+            } else {
+                decision_timer.stop();
+                for button in [&mut table.btn_hit, &mut table.btn_stand] {
+                    button.enabled = false;
+                    button.set_disabled_reason(DEALER_PLAYING);
+                }
+                let no_contest = !hand::dealer_can_change_results(&player_hands, &dealer_hand, hole_card.is_some());
+                script.then(Cue::RevealHoleCard).wait(dealer_pause(no_contest, &profile.settings)).then(Cue::DealerDraw);
+            }
+        }
+        // The settings changed mid-session, without leaving the table
+        if quick_settings.update(layout, &mut profile.settings) {
+            profile.mark_changed();
+            animation::set_animation_speed(profile.settings.animation_speed);
+            let settings = &profile.settings;
+            audio.set_levels(settings.music_volume, settings.sfx_volume, settings.duck_level);
+            table.show_settings(&profile.settings);
+        }
+        // Running low between rounds: say so, and offer a cheaper table
+        if phase != GamePhase::PlayerTurn {
+            if low_bankroll.check(profile.info.bankroll, &profile.settings) {
+                bus.emit(GameEvent::LowBankroll { bankroll: profile.info.bankroll, minimum: profile.settings.rules.limits.min });
+            }
+            if let Some(LowBankrollAction::Switch(limits)) = low_bankroll.update(layout, profile.info.bankroll, &profile.settings) {
+                profile.settings.rules.limits = limits;
+                profile.mark_changed();
+                table.show_settings(&profile.settings);
+                table.lbl_notice.set_text(format!("Now playing for {} a hand", limits.text()));
+                notice_until = get_time() + 3.0;
+            }
+        }
+        if shoe.take_reshuffle_event() {
+            bus.emit(GameEvent::ShoeShuffled { seed: shoe.seed() });
+        }
+        bus.dispatch(&mut [&mut profile, &mut shuffle_anim, &mut audit, &mut history, &mut audio, &mut trainer, &mut drills, &mut announcer, &mut goals, &mut pacing, &mut analytics, &mut table]);
+        table.show_stats(&profile.stats, &history);
+        for goal in goals.take_completed() {
+            profile.achievements.reward(goal.id, goal.stars);
+            profile.mark_changed();
+            table.lbl_notice.set_text(format!("Goal complete: {} (+{} stars)", goal.name, goal.stars));
+            notice_until = get_time() + 3.0;
+        }
+        profile.save_if_changed();
+        storage::update();
+        table.show_sync_status();
+        for achievement in profile.achievements.take_new() {
+            table.lbl_notice.set_text(format!("Achievement unlocked: {} - {}", achievement.name, achievement.description));
+            notice_until = get_time() + 3.0;
+        }
+
+        if get_time() > notice_until {
+            table.lbl_notice.set_text("");
+        }
+        table.update(get_frame_time());
+        table.draw(&shoe);
+        frame.draw();
+        // Capture last so the picture has the whole table in it
+        if screenshot_requested {
+            match capture_screenshot() {
+                Ok(name) => {
+                    log_info!("saved screenshot {}", name);
+                    table.lbl_notice.set_text(format!("Saved {}", name));
+                }
+                Err(err) => {
+                    log_warn!("screenshot failed: {}", err);
+                    table.lbl_notice.set_text(format!("Screenshot failed: {}", err));
+                }
+            }
+            notice_until = get_time() + 3.0;
+        }
+        #[cfg(all(feature = "gif_recorder", not(target_arch = "wasm32")))]
+        {
+            table.recorder.capture();
+            if table.recorder.take_big_win() {
+                let key = keys.key(KeyAction::SaveGif).map_or("F9".to_string(), keys::key_name);
+                table.lbl_notice.set_text(format!("Big win! Press {} to save it as a GIF", key));
+                notice_until = get_time() + 5.0;
+            }
+            if keys.pressed(KeyAction::SaveGif) {
+                match table.recorder.save() {
+                    Ok(name) => {
+                        log_info!("saved clip {}", name);
+                        table.lbl_notice.set_text(format!("Saved {}", name));
+                    }
+                    Err(err) => {
+                        log_warn!("GIF failed: {}", err);
+                        table.lbl_notice.set_text(format!("GIF failed: {}", err));
+                    }
+                }
+                notice_until = get_time() + 3.0;
+            }
+        }
+        frame.show().await;
+    }
+    // The round may have moved on since it was last staged, in the frame the game was left
+    if !recording && phase == GamePhase::PlayerTurn {
+        let started = table.active_hand.is_some() && even_money.is_none();
+        autosave::stage_round(&profile.round_key(), RoundSave::new(&player_hands, squeezed, &dealer_hand, hole_card, &bet, active, started));
+    }
+    autosave::flush();
+}
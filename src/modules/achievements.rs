@@ -0,0 +1,141 @@
+/*
+By: <tyler>
+Date: 2025-11-23
+Program Details: Achievements unlocked by playing
+
+Each profile keeps its own list of unlocked achievements. The list is checked
+against the profile's stats after every game event, and anything newly unlocked
+is queued so the table can announce it.
+
+The rewards ledger keeps the stars paid for every session goal finished (see
+goals.rs), newest last. A couple of achievements are earned from the ledger
+instead of the stats.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod stats;
+    pub mod achievements;
+
+Then add the following with the use commands:
+use crate::modules::achievements::Achievements;
+
+Usage examples:
+1. Check after the stats have seen an event:
+    achievements.check(&stats, &event);
+
+2. Announce anything new:
+    for achievement in achievements.take_new() {
+        lbl_notice.set_text(format!("Achievement unlocked: {}", achievement.name));
+    }
+
+3. Count what has been unlocked:
+    let text = format!("{}/{}", achievements.unlocked_count(), ACHIEVEMENTS.len());
+
+4. Pay a finished goal's stars into the ledger (this can unlock achievements too):
+    achievements.reward(goal.id, goal.stars);
+    let text = format!("{} stars", achievements.stars());
+*/
+use nanoserde::{DeJson, SerJson};
+use crate::modules::events::{GameEvent, Outcome};
+use crate::modules::stats::{Stats, STREAK_THRESHOLD};
+
+#[derive(Debug)]
+pub struct Achievement {
+    pub id: &'static str, // Saved in the profile, never change one after release
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub const ACHIEVEMENTS: [Achievement; 9] = [
+    Achievement { id: "first_hand", name: "Take a Seat", description: "Play your first hand" },
+    Achievement { id: "first_win", name: "Beginner's Luck", description: "Win a hand" },
+    Achievement { id: "twenty_one", name: "Twenty-One", description: "Win a hand with a total of 21" },
+    Achievement { id: "ten_wins", name: "Card Shark", description: "Win 10 hands" },
+    Achievement { id: "hundred_hands", name: "Regular", description: "Play 100 hands" },
+    Achievement { id: "hot_streak", name: "On Fire", description: "Win 3 hands in a row" },
+    Achievement { id: "five_streak", name: "Unstoppable", description: "Win 5 hands in a row" },
+    Achievement { id: "first_goal", name: "Goal Getter", description: "Finish a session goal" },
+    Achievement { id: "fifty_stars", name: "Star Collector", description: "Earn 50 stars from goals" },
+];
+
+// One line of the rewards ledger: a finished goal and the stars it paid
+#[derive(SerJson, DeJson, Default, Clone, Debug)]
+#[nserde(default)]
+pub struct Reward {
+    pub goal: String, // Goal id, see goals::GOALS
+    pub stars: u32,
+}
+
+#[derive(SerJson, DeJson, Default, Clone, Debug)]
+#[nserde(default)]
+pub struct Achievements {
+    pub unlocked: Vec<String>,
+    pub rewards: Vec<Reward>,
+    #[nserde(skip)]
+    new: Vec<&'static Achievement>,
+}
+
+impl Achievements {
+    // Unlock anything the latest event (or the stats so far) has earned
+    pub fn check(&mut self, stats: &Stats, event: &GameEvent) {
+        let won_with_21 = matches!(event, GameEvent::RoundSettled { outcome: Outcome::PlayerWin, player_total: 21, .. });
+        for achievement in &ACHIEVEMENTS {
+            let earned = match achievement.id {
+                "first_hand" => stats.hands_played >= 1,
+                "first_win" => stats.player_wins >= 1,
+                "twenty_one" => won_with_21,
+                "ten_wins" => stats.player_wins >= 10,
+                "hundred_hands" => stats.hands_played >= 100,
+                "hot_streak" => stats.best_win_streak >= STREAK_THRESHOLD,
+                "five_streak" => stats.best_win_streak >= 5,
+                _ => false,
+            };
+            if earned {
+                self.unlock(achievement);
+            }
+        }
+    }
+
+    // Add a finished goal to the rewards ledger and unlock anything the stars have earned
+    pub fn reward(&mut self, goal: &str, stars: u32) {
+        self.rewards.push(Reward { goal: goal.to_string(), stars });
+        let total = self.stars();
+        for achievement in &ACHIEVEMENTS {
+            let earned = match achievement.id {
+                "first_goal" => true,
+                "fifty_stars" => total >= 50,
+                _ => false,
+            };
+            if earned {
+                self.unlock(achievement);
+            }
+        }
+    }
+
+    // Every star in the rewards ledger
+    pub fn stars(&self) -> u32 {
+        self.rewards.iter().map(|reward| reward.stars).sum()
+    }
+
+    fn unlock(&mut self, achievement: &'static Achievement) {
+        if !self.is_unlocked(achievement.id) {
+            self.unlocked.push(achievement.id.to_string());
+            self.new.push(achievement);
+        }
+    }
+
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.iter().any(|unlocked| unlocked == id)
+    }
+
+    #[allow(unused)]
+    pub fn unlocked_count(&self) -> usize {
+        self.unlocked.len()
+    }
+
+    // Achievements unlocked since the last call, oldest first
+    pub fn take_new(&mut self) -> Vec<&'static Achievement> {
+        std::mem::take(&mut self.new)
+    }
+}
@@ -0,0 +1,155 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Bet sizing advice between rounds, the Kelly bet and the risk of ruin
+
+The advisor takes the player's edge and says how much of the bankroll to bet on
+it and how likely the bet on the table is to lose the whole bankroll before it
+is doubled up many times over.
+
+The edge starts from the rules: a whole round played by basic strategy from a
+full deck (ev::round_ev), worked out once for each rule set. With the counting
+trainer on, each point of the true count is worth EDGE_PER_TRUE_COUNT on top,
+the usual Hi-Lo rule of thumb.
+
+    Kelly bet     bankroll * edge / VARIANCE, nothing while the house has the edge
+    Risk of ruin  exp(-2 * edge * bankroll / (VARIANCE * bet)), certain without an edge
+
+Both treat every round as the same bet at the same edge, which is what the
+textbook formulas assume. Half the Kelly bet is shown as well, most players who
+size bets this way use a fraction of it.
+
+It is drawn as a small HUD panel whose title bar folds it away and back.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod ev;
+    pub mod layers;
+    pub mod rules;
+    pub mod text_button;
+    pub mod advisor;
+
+Then add the following with the use commands:
+use crate::modules::advisor::Advisor;
+
+Usage examples:
+1. Create it before the loop:
+    let mut advisor = Advisor::new();
+
+2. Between rounds, work out the advice and show it:
+    let edge = advisor.edge(&settings.rules, Some(true_count));
+    let advice = advisor::advise(edge, bankroll, bet.total());
+    advisor.draw(area, &advice);
+
+3. The formulas on their own:
+    let fraction = advisor::kelly_fraction(0.01); // of the bankroll
+    let ruin = advisor::risk_of_ruin(0.01, 1000, 25);
+*/
+use macroquad::prelude::*;
+use crate::modules::ev::round_ev;
+use crate::modules::i18n;
+use crate::modules::layers::{self, Layer};
+use crate::modules::odds::Composition;
+use crate::modules::rules::{Payout, Ruleset};
+use crate::modules::text_button::TextButton;
+
+// Variance of one round as a fraction of the bet squared. With only hit and stand there are no
+// doubled or split bets, so nearly every round wins or loses the bet once, blackjacks a little more.
+const VARIANCE: f32 = 1.1;
+const EDGE_PER_TRUE_COUNT: f32 = 0.005;
+const TITLE_HEIGHT: f32 = 22.0;
+const TEXT_SIZE: f32 = 20.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Advice {
+    pub edge: f32,         // The player's edge as a fraction of the bet, negative when the house is ahead
+    pub kelly_bet: i64,    // Whole dollars, 0 with no edge
+    pub ruin: Option<f32>, // Chance of losing the bankroll betting `bet` every round, None with no bet
+    pub bet: i64,
+}
+
+// The share of the bankroll the Kelly criterion bets on this edge
+pub fn kelly_fraction(edge: f32) -> f32 {
+    (edge / VARIANCE).max(0.0)
+}
+
+// The chance of losing all of `bankroll` betting `bet` every round at this edge
+pub fn risk_of_ruin(edge: f32, bankroll: i64, bet: i64) -> f32 {
+    if bet <= 0 {
+        return 0.0;
+    }
+    if edge <= 0.0 || bankroll <= 0 {
+        return 1.0;
+    }
+    let units = bankroll as f32 / bet as f32;
+    (-2.0 * edge * units / VARIANCE).exp().min(1.0)
+}
+
+// Everything the panel shows for this edge, bankroll and bet
+pub fn advise(edge: f32, bankroll: i64, bet: i64) -> Advice {
+    let kelly_bet = (bankroll.max(0) as f32 * kelly_fraction(edge)) as i64;
+    let ruin = (bet > 0).then(|| risk_of_ruin(edge, bankroll, bet));
+    Advice { edge, kelly_bet, ruin, bet }
+}
+
+pub struct Advisor {
+    base_edge: Option<((bool, Payout), f32)>, // The rules' edge, kept until the dealer peeking or the blackjack payout changes
+    toggle: TextButton,
+    collapsed: bool,
+}
+
+impl Advisor {
+    pub fn new() -> Self {
+        let mut toggle = TextButton::new(0.0, 0.0, 100.0, TITLE_HEIGHT, "Advisor", Color::new(0.0, 0.0, 0.0, 0.6), DARKGREEN, 18);
+        toggle.with_layer(Layer::Hud);
+        Self { base_edge: None, toggle, collapsed: false }
+    }
+
+    // The player's edge under these rules, plus the count's worth when there is a true count
+    pub fn edge(&mut self, rules: &Ruleset, true_count: Option<f32>) -> f32 {
+        let key = (rules.dealer_peeks, rules.blackjack_pays);
+        let base = match self.base_edge {
+            Some((rules_key, edge)) if rules_key == key => edge,
+            _ => {
+                let edge = round_ev(&Composition::full_deck(), rules.dealer_peeks, rules.blackjack_pays.ratio());
+                self.base_edge = Some((key, edge));
+                edge
+            }
+        };
+        base + true_count.unwrap_or(0.0) * EDGE_PER_TRUE_COUNT
+    }
+
+    // Queue the panel into the HUD layer, clicking the title folds it away or back
+    pub fn draw(&mut self, area: Rect, advice: &Advice) {
+        let arrow = if self.collapsed { "+" } else { "-" };
+        self.toggle.set_text(format!("{} Advisor: edge {:+.2}%", arrow, advice.edge * 100.0));
+        self.toggle.update_position(area.x, area.y, Some(area.w), Some(TITLE_HEIGHT));
+        if self.toggle.click() {
+            self.collapsed = !self.collapsed;
+        }
+        if self.collapsed {
+            return;
+        }
+        let kelly = if advice.kelly_bet > 0 {
+            format!("Kelly bet: {} (half {})", i18n::money(advice.kelly_bet), i18n::money(advice.kelly_bet / 2))
+        } else {
+            "Kelly bet: none, the house is ahead".to_string()
+        };
+        let ruin = match advice.ruin {
+            Some(ruin) => format!("Risk of ruin at {}: {:.1}%", i18n::money(advice.bet), ruin * 100.0),
+            None => "Risk of ruin: place a bet".to_string(),
+        };
+        let ruin_color = match advice.ruin {
+            Some(ruin) if ruin >= 0.5 => RED,
+            Some(ruin) if ruin >= 0.1 => GOLD,
+            _ => WHITE,
+        };
+        layers::push(Layer::Hud, move || {
+            let body = Rect::new(area.x, area.y + TITLE_HEIGHT, area.w, area.h - TITLE_HEIGHT);
+            draw_rectangle(body.x, body.y, body.w, body.h, Color::new(0.0, 0.0, 0.0, 0.45));
+            draw_text(&kelly, body.x + 6.0, body.y + body.h * 0.42, TEXT_SIZE, WHITE);
+            draw_text(&ruin, body.x + 6.0, body.y + body.h * 0.88, TEXT_SIZE, ruin_color);
+        });
+    }
+}
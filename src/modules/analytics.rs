@@ -0,0 +1,234 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: An opt-in play log, kept on the player's own device for their own analysis
+
+With the play log turned on (Stats panel, off to begin with) every game event
+worth studying is written down as one line of JSON (JSON Lines), so the
+player can load it into a spreadsheet or a script and see how they play:
+    decision    - a hit or stand: the hand's total, whether it was soft, the dealer's
+                  up card, the move made and the basic strategy move
+    result      - how each hand ended: both totals, the outcome, whether it was a blackjack
+    even_money  - taken, turned down, or left to run out
+    hint        - the strategy chart was opened, and whether a hand was being played
+    shuffle     - the shoe was reshuffled
+For example:
+    {"session":3141592,"seconds":12.4,"event":"decision","hand":0,"total":16,"soft":false,"dealer_up":"T","action":"hit","basic":"hit"}
+
+Nothing in it says who played: no profile name, no bankroll or bets, no shuffle
+seeds and no dates. `session` is a random number picked each time the game
+starts, `seconds` counts from then.
+
+The log never leaves the device, it is written locally and nothing is sent:
+- Native: appended to save/analytics.jsonl
+- Web: kept in the browser's localStorage (under "analytics"), the oldest lines
+  are dropped past MAX_WEB_BYTES. It is written straight to localStorage, not
+  through storage.rs, so cloud sync never sees it.
+clear() deletes it.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod events;
+    pub mod strategy;
+    pub mod web_bridge;
+    pub mod analytics;
+In the Cargo.toml file add the following:
+    nanoserde = "0.2.1"
+
+Then add the following with the use commands:
+use crate::modules::analytics::Analytics;
+
+Usage examples:
+1. Create it before the loop, on if the player turned it on:
+    let mut analytics = Analytics::new();
+    analytics.set_enabled(profile.settings.analytics);
+
+2. Give it every game event:
+    bus.dispatch(&mut [&mut analytics]);
+
+3. Delete everything logged so far:
+    if let Err(err) = analytics.clear() {
+        println!("Could not clear the play log: {}", err);
+    }
+*/
+use macroquad::miniquad::date;
+use macroquad::time::get_time;
+use nanoserde::SerJson;
+use crate::modules::events::{EventListener, GameEvent, Outcome};
+use crate::modules::strategy::{basic_strategy_for, Action};
+use crate::log_warn;
+
+#[cfg(not(target_arch = "wasm32"))]
+const LOG_FILE: &str = "save/analytics.jsonl";
+#[cfg(target_arch = "wasm32")]
+const STORAGE_KEY: &str = "analytics";
+#[cfg(target_arch = "wasm32")]
+const MAX_WEB_BYTES: usize = 1_000_000;
+
+// One line of the log, the fields an event doesn't have are left out
+#[derive(SerJson, Default)]
+struct LogLine {
+    session: u32,
+    seconds: f32,
+    event: String,
+    hand: Option<usize>,
+    total: Option<u32>,
+    soft: Option<bool>,
+    dealer_up: Option<String>,
+    dealer_total: Option<u32>,
+    action: Option<String>,
+    basic: Option<String>,
+    outcome: Option<String>,
+    blackjack: Option<bool>,
+    in_hand: Option<bool>,
+}
+
+pub struct Analytics {
+    enabled: bool,
+    session: u32,
+    started: f64,
+    lines: usize, // In the log, this session's and earlier ones
+}
+
+impl Analytics {
+    pub fn new() -> Self {
+        Self { enabled: false, session: session_id(), started: get_time(), lines: read_log().lines().count() }
+    }
+
+    pub fn set_enabled(&mut self, on: bool) {
+        self.enabled = on;
+    }
+
+    // Lines in the log
+    pub fn lines(&self) -> usize {
+        self.lines
+    }
+
+    // Delete the log, on the device and from now on
+    pub fn clear(&mut self) -> Result<(), String> {
+        #[cfg(target_arch = "wasm32")]
+        crate::modules::web_bridge::storage_remove(STORAGE_KEY);
+        #[cfg(not(target_arch = "wasm32"))]
+        match std::fs::remove_file(LOG_FILE) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.to_string()),
+            _ => {}
+        }
+        self.lines = 0;
+        Ok(())
+    }
+
+    fn line(&self, event: &str) -> LogLine {
+        LogLine { session: self.session, seconds: ((get_time() - self.started) * 10.0).round() as f32 / 10.0, event: event.to_string(), ..Default::default() }
+    }
+
+    fn write(&mut self, line: LogLine) {
+        match append_log(&line.serialize_json()) {
+            Ok(dropped) => self.lines = self.lines + 1 - dropped.min(self.lines + 1),
+            Err(err) => log_warn!("could not write the play log: {}", err),
+        }
+    }
+}
+
+impl EventListener for Analytics {
+    fn on_event(&mut self, event: &GameEvent) {
+        if !self.enabled {
+            return;
+        }
+        let line = match event {
+            GameEvent::PlayerDecision { hand, total, soft, dealer_up, hit } => LogLine {
+                hand: Some(*hand),
+                total: Some(*total),
+                soft: Some(*soft),
+                dealer_up: Some(dealer_up.rank.letter().to_string()),
+                action: Some(if *hit { "hit" } else { "stand" }.to_string()),
+                basic: Some(action_name(basic_strategy_for(*total, *soft, *dealer_up)).to_string()),
+                ..self.line("decision")
+            },
+            GameEvent::RoundSettled { hand, outcome, blackjack, player_total, dealer_total, .. } => LogLine {
+                hand: Some(*hand),
+                total: Some(*player_total),
+                dealer_total: Some(*dealer_total),
+                outcome: Some(outcome_name(*outcome).to_string()),
+                blackjack: Some(*blackjack),
+                ..self.line("result")
+            },
+            GameEvent::EvenMoney { taken, timed_out } => {
+                let action = if *timed_out { "timed out" } else if *taken { "taken" } else { "turned down" };
+                LogLine { action: Some(action.to_string()), ..self.line("even_money") }
+            }
+            GameEvent::ChartOpened { in_hand } => LogLine { in_hand: Some(*in_hand), ..self.line("hint") },
+            GameEvent::ShoeShuffled { .. } => self.line("shuffle"),
+            _ => return,
+        };
+        self.write(line);
+    }
+}
+
+fn action_name(action: Action) -> &'static str {
+    match action {
+        Action::Hit => "hit",
+        Action::Stand => "stand",
+    }
+}
+
+fn outcome_name(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::PlayerWin => "win",
+        Outcome::DealerWin => "loss",
+        Outcome::Push => "push",
+        Outcome::NoWinner => "both bust",
+    }
+}
+
+// A number for this run of the game that doesn't give away when it was. Not from macroquad's
+// rand, which the shoe is dealt from, so a recorded game still replays the same.
+fn session_id() -> u32 {
+    let mut x = date::now().to_bits().wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    ((x ^ (x >> 31)) >> 32) as u32
+}
+
+// Everything logged so far, empty if there is no log yet
+fn read_log() -> String {
+    #[cfg(target_arch = "wasm32")]
+    {
+        crate::modules::web_bridge::storage_get(STORAGE_KEY).unwrap_or_default()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::fs::read_to_string(LOG_FILE).unwrap_or_default()
+    }
+}
+
+// Add a line to the end of the log, returns how many old lines were dropped to make room
+fn append_log(line: &str) -> Result<usize, String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let mut text = read_log();
+        text.push_str(line);
+        text.push('\n');
+        // localStorage is small, the oldest lines go first
+        let mut dropped = 0;
+        while text.len() > MAX_WEB_BYTES {
+            let Some(end) = text.find('\n') else {
+                break;
+            };
+            text.drain(..=end);
+            dropped += 1;
+        }
+        crate::modules::web_bridge::storage_set(STORAGE_KEY, &text);
+        Ok(dropped)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        use std::io::Write;
+        if let Some(folder) = std::path::Path::new(LOG_FILE).parent() {
+            std::fs::create_dir_all(folder).map_err(|err| err.to_string())?;
+        }
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(LOG_FILE).map_err(|err| err.to_string())?;
+        writeln!(file, "{}", line).map_err(|err| err.to_string())?;
+        Ok(0)
+    }
+}
@@ -0,0 +1,357 @@
+/*
+By: <tyler>
+Date: 2025-11-20
+Program Details: Easing curves and tweens for simple animations
+
+A tween moves a number from one value to another over a set time. Anything that
+animates (card moves, fades, progress bars) can use one instead of keeping its
+own timers.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod frame_pacer;
+    pub mod quality;
+    pub mod timer;
+    pub mod animation;
+
+Then add the following with the use commands:
+use crate::modules::animation::{Easing, Fade, Timeline, Tween};
+
+Usage examples:
+1. Create a tween that goes from 0 to 1 over half a second:
+    let mut fade = Tween::new(0.0, 1.0, 0.5, Easing::EaseOutQuad);
+
+2. Advance it every frame and read the current value:
+    fade.update(get_frame_time());
+    let alpha = fade.value();
+
+3. Check if it is done, or restart it:
+    if fade.is_finished() {
+        fade.restart();
+    }
+
+4. Use an easing curve directly on a 0-1 progress value:
+    let eased = Easing::EaseInOutQuad.apply(progress);
+
+5. Reduced motion, for slow devices and players who get queasy from movement.
+   While it is on every tween jumps to its end on the next update, so anything
+   built on a Tween becomes an instant change without being touched. Effects
+   that loop forever (flickers, pulses) should read animation_time() instead of
+   get_time(), it stands still while reduced motion is on:
+    set_reduced_motion(settings.reduced_motion);
+    let wobble = (animation_time() * 12.0).sin();
+
+6. A scripted sequence: a Timeline hands back the cues it was given, in order,
+   with waits between them. The caller decides what each cue does, so the steps
+   can touch anything in the game loop. Steps can be added while it runs, a cue
+   can decide what comes after it. Waits go by at the animation speed, and are
+   skipped with reduced motion on.
+    let mut script = Timeline::new();
+    script.then(Cue::FirstCard).wait(0.2).then(Cue::SecondCard).wait(0.2).then(Cue::FlipHoleCard);
+    script.on_complete(|| log_info!("all dealt"));
+    for cue in script.update(get_frame_time()) {
+        match cue { ... }
+    }
+    script.pause();   // script.resume() carries on where it stopped
+    script.cancel();  // drops what is left, the completion callback isn't called
+
+7. Speed every tween, fade and timeline up or down, 0.0 makes them instant
+   (like reduced motion, but looping effects keep going). Anything that counts
+   down its own animation delays should use scaled_time() and motion_skipped():
+    set_animation_speed(settings.animation_speed);
+    flight.delay -= scaled_time(dt);
+
+8. A widget that fades in and out with a flag, for things drawn through &self
+   every frame (buttons, labels). It follows the flag it is given, moves once a
+   frame however many times it is asked, and the first time it is asked it
+   starts where the flag is, so nothing fades in when the game opens:
+    let fade = Fade::new(0.15);
+    let shown = fade.update(self.visible);   // 0.0 gone to 1.0 all there, eased
+    if shown == 0.0 {
+        return;
+    }
+
+A tween or a fade that is still moving, a running timeline, and animation_time(),
+tell modules::frame_pacer the frame is dirty so the game doesn't slow down in the
+middle of an animation.
+*/
+use std::cell::Cell;
+use std::collections::VecDeque;
+use crate::modules::frame_pacer;
+use crate::modules::quality;
+use crate::modules::timer::Timer;
+
+thread_local! {
+    static REDUCED_MOTION: Cell<bool> = const { Cell::new(false) };
+    static ANIMATION_SPEED: Cell<f32> = const { Cell::new(1.0) };
+}
+
+pub fn set_reduced_motion(on: bool) {
+    REDUCED_MOTION.with(|reduced| reduced.set(on));
+}
+
+pub fn reduced_motion() -> bool {
+    REDUCED_MOTION.with(|reduced| reduced.get())
+}
+
+// How much faster than normal animations play, 0.0 for instant
+pub fn set_animation_speed(speed: f32) {
+    ANIMATION_SPEED.with(|cell| cell.set(speed.max(0.0)));
+}
+
+pub fn animation_speed() -> f32 {
+    ANIMATION_SPEED.with(|cell| cell.get())
+}
+
+// Animations jump straight to their end: reduced motion, or the speed set to instant
+pub fn motion_skipped() -> bool {
+    reduced_motion() || animation_speed() == 0.0
+}
+
+// A frame's time at the animation speed
+pub fn scaled_time(dt: f32) -> f32 {
+    dt * animation_speed()
+}
+
+// Seconds since the game started for looping effects, always 0 with reduced motion or low quality on
+pub fn animation_time() -> f32 {
+    if reduced_motion() || quality::low() {
+        return 0.0;
+    }
+    frame_pacer::mark_dirty();
+    macroquad::time::get_time() as f32
+}
+
+// Easing curves, all take a progress from 0.0 to 1.0
+#[allow(unused)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Easing {
+    Linear,
+    EaseInQuad,
+    EaseOutQuad,
+    EaseInOutQuad,
+    EaseOutCubic,
+    EaseOutBack, // Overshoots a little then settles
+}
+
+impl Easing {
+    // Map a linear progress (0.0 - 1.0) onto the curve
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseInQuad => t * t,
+            Easing::EaseOutQuad => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOutQuad => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::EaseOutCubic => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseOutBack => {
+                let c1 = 1.70158;
+                let c3 = c1 + 1.0;
+                1.0 + c3 * (t - 1.0).powi(3) + c1 * (t - 1.0).powi(2)
+            }
+        }
+    }
+}
+
+// Moves a value from `from` to `to` over `duration` seconds
+#[derive(Clone, Debug)]
+pub struct Tween {
+    from: f32,
+    to: f32,
+    duration: f32,
+    elapsed: f32,
+    easing: Easing,
+}
+
+impl Tween {
+    pub fn new(from: f32, to: f32, duration: f32, easing: Easing) -> Self {
+        Self {
+            from,
+            to,
+            duration: duration.max(0.0),
+            elapsed: 0.0,
+            easing,
+        }
+    }
+
+    // Advance the tween by dt seconds at the animation speed, or straight to the end with motion skipped
+    pub fn update(&mut self, dt: f32) {
+        if !self.is_finished() {
+            frame_pacer::mark_dirty();
+        }
+        if motion_skipped() {
+            self.elapsed = self.duration;
+            return;
+        }
+        self.elapsed = (self.elapsed + scaled_time(dt)).min(self.duration);
+    }
+
+    // Linear progress from 0.0 to 1.0
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            self.elapsed / self.duration
+        }
+    }
+
+    // Current value with easing applied
+    pub fn value(&self) -> f32 {
+        self.from + (self.to - self.from) * self.easing.apply(self.progress())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    // Start again from the beginning
+    pub fn restart(&mut self) {
+        self.elapsed = 0.0;
+    }
+
+    // Jump straight to the end
+    #[allow(unused)]
+    pub fn finish(&mut self) {
+        self.elapsed = self.duration;
+    }
+}
+
+// How far something is through appearing, following an on/off flag. Keeps its
+// place in Cells so widgets that only have &self while drawing can own one.
+#[derive(Clone, Debug)]
+pub struct Fade {
+    amount: Cell<f32>,             // 0.0 gone to 1.0 all there, before easing
+    last_moved: Cell<Option<f64>>, // Frame time it last moved, None before the first update
+    duration: f32,
+}
+
+impl Fade {
+    pub fn new(duration: f32) -> Self {
+        Self { amount: Cell::new(0.0), last_moved: Cell::new(None), duration: duration.max(0.0) }
+    }
+
+    // Move toward `on` by this frame's time and return how far in it is, eased
+    pub fn update(&self, on: bool) -> f32 {
+        let target = if on { 1.0 } else { 0.0 };
+        let now = macroquad::time::get_time();
+        match self.last_moved.replace(Some(now)) {
+            // The first time it is asked it starts where the flag is
+            None => self.amount.set(target),
+            Some(last) if last != now && self.amount.get() != target => {
+                frame_pacer::mark_dirty();
+                let amount = if motion_skipped() || self.duration <= 0.0 {
+                    target
+                } else {
+                    let step = scaled_time(macroquad::time::get_frame_time()) / self.duration;
+                    let amount = self.amount.get();
+                    if target > amount { (amount + step).min(target) } else { (amount - step).max(target) }
+                };
+                self.amount.set(amount);
+            }
+            _ => {}
+        }
+        self.value()
+    }
+
+    // How far in it is, eased, without moving it
+    pub fn value(&self) -> f32 {
+        Easing::EaseOutQuad.apply(self.amount.get())
+    }
+}
+
+// One step of a Timeline
+enum Step<T> {
+    Cue(T),
+    Wait(Timer),
+}
+
+// A queue of cues with waits between them, see usage example 6
+pub struct Timeline<T> {
+    steps: VecDeque<Step<T>>,
+    paused: bool,
+    on_complete: Option<Box<dyn FnOnce()>>,
+}
+
+impl<T> Timeline<T> {
+    pub fn new() -> Self {
+        Self { steps: VecDeque::new(), paused: false, on_complete: None }
+    }
+
+    // Add a cue to hand back once everything before it is done
+    pub fn then(&mut self, cue: T) -> &mut Self {
+        self.steps.push_back(Step::Cue(cue));
+        self
+    }
+
+    // Add a pause of `seconds` before the next step
+    pub fn wait(&mut self, seconds: f32) -> &mut Self {
+        self.steps.push_back(Step::Wait(Timer::after(seconds)));
+        self
+    }
+
+    // Called once, by the update that runs out of steps
+    #[allow(unused)]
+    pub fn on_complete(&mut self, callback: impl FnOnce() + 'static) -> &mut Self {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+
+    #[allow(unused)]
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    #[allow(unused)]
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    // Drop the steps that are left without calling the completion callback
+    #[allow(unused)]
+    pub fn cancel(&mut self) {
+        self.steps.clear();
+        self.on_complete = None;
+    }
+
+    // True while there are steps left, paused or not
+    pub fn is_running(&self) -> bool {
+        !self.steps.is_empty()
+    }
+
+    // Advance by dt seconds and return the cues that came up, in order
+    pub fn update(&mut self, dt: f32) -> Vec<T> {
+        let mut due = Vec::new();
+        if self.paused || self.steps.is_empty() {
+            return due;
+        }
+        frame_pacer::mark_dirty();
+        let mut left = scaled_time(dt);
+        while let Some(step) = self.steps.front_mut() {
+            if let Step::Wait(timer) = step
+                && !motion_skipped()
+            {
+                if !timer.tick(left) {
+                    break;
+                }
+                // The rest of the frame goes to the steps after the wait
+                left = timer.overshoot();
+            }
+            if let Some(Step::Cue(cue)) = self.steps.pop_front() {
+                due.push(cue);
+            }
+        }
+        if self.steps.is_empty()
+            && let Some(callback) = self.on_complete.take()
+        {
+            callback();
+        }
+        due
+    }
+}
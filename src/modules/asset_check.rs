@@ -0,0 +1,124 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Checks the card pictures are all there, and named right, before the game starts
+
+Every card picture is found by its name (assets/<Rank>-of-<suit>.png, see
+Card::picture_path), so one file named a little differently is a card that
+shows up blank, and on a case sensitive file system (Linux, and most web
+servers) "empty.png" is not "Empty.png" even though it looks fine on Windows
+and macOS. This looks through the assets folder itself rather than asking
+whether each file exists, so a name that only differs in case is caught on
+every system.
+
+It reports:
+- Missing:   an expected picture isn't there at all
+- WrongCase: it is there, but with different capitals
+- Unknown:   a file that looks like a card picture but isn't one of the 52
+- NotLoaded: a picture the "cards" preload group (assets/manifest.json) leaves out
+
+It is run in debug builds, and in release builds started with --check-assets.
+Only native builds can look in the folder, the web build can't list files.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod cards;
+    pub mod asset_check;
+
+Then add the following with the use commands:
+use crate::modules::asset_check;
+
+Usage examples:
+1. Check once the manifest is loaded, and show what is wrong on the error screen:
+    if asset_check::wanted() {
+        let problems = asset_check::check_cards("assets", &tm.group_paths("cards"));
+        if !problems.is_empty() {
+            crash::problem_screen(&asset_check::report(&problems)).await;
+        }
+    }
+*/
+use crate::modules::cards::Card;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AssetProblem {
+    Missing(String),
+    WrongCase { expected: String, found: String },
+    Unknown(String),
+    NotLoaded(String),
+}
+
+impl AssetProblem {
+    pub fn text(&self) -> String {
+        match self {
+            AssetProblem::Missing(path) => format!("missing: {}", path),
+            AssetProblem::WrongCase { expected, found } => format!("wrong case: {} should be {}", found, expected),
+            AssetProblem::Unknown(path) => format!("not a card picture: {}", path),
+            AssetProblem::NotLoaded(path) => format!("not in the cards preload group: {}", path),
+        }
+    }
+}
+
+// Debug builds always check, release builds when started with --check-assets
+pub fn wanted() -> bool {
+    cfg!(debug_assertions) || std::env::args().any(|arg| arg == "--check-assets")
+}
+
+// Look for every card picture (and the empty slot) in `folder`. `group` is the
+// "cards" preload group, an empty group is left unchecked (the game makes its own).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn check_cards(folder: &str, group: &[String]) -> Vec<AssetProblem> {
+    let files: Vec<String> = match std::fs::read_dir(folder) {
+        Ok(entries) => entries.filter_map(|entry| entry.ok()?.file_name().into_string().ok()).collect(),
+        Err(_) => Vec::new(),
+    };
+    let expected = Card::all_texture_paths();
+    compare(folder, &files, &expected, group)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn check_cards(_folder: &str, _group: &[String]) -> Vec<AssetProblem> {
+    Vec::new()
+}
+
+// The problems, one a line, ready for the error screen
+pub fn report(problems: &[AssetProblem]) -> String {
+    let lines: Vec<String> = problems.iter().map(AssetProblem::text).collect();
+    format!(
+        "{} problem(s) with the card pictures. Cards without a picture are drawn instead.\n\n{}",
+        problems.len(),
+        lines.join("\n")
+    )
+}
+
+// What is wrong with the file names in a folder, given the paths wanted from it
+fn compare(folder: &str, files: &[String], expected: &[String], group: &[String]) -> Vec<AssetProblem> {
+    let prefix = format!("{}/", folder.trim_end_matches('/'));
+    let in_folder = |name: &str| format!("{}{}", prefix, name);
+    let mut problems = Vec::new();
+    for path in expected {
+        let name = path.strip_prefix(&prefix).unwrap_or(path);
+        if files.iter().any(|file| file == name) {
+            continue;
+        }
+        match files.iter().find(|file| file.eq_ignore_ascii_case(name)) {
+            Some(found) => problems.push(AssetProblem::WrongCase { expected: path.clone(), found: in_folder(found) }),
+            None => problems.push(AssetProblem::Missing(path.clone())),
+        }
+    }
+    // Anything named like a card that no card asks for
+    for file in files {
+        let path = in_folder(file);
+        let looks_like_card = file.to_ascii_lowercase().contains("-of-") && file.to_ascii_lowercase().ends_with(".png");
+        if looks_like_card && !expected.iter().any(|wanted| wanted.eq_ignore_ascii_case(&path)) {
+            problems.push(AssetProblem::Unknown(path));
+        }
+    }
+    if !group.is_empty() {
+        for path in expected.iter().filter(|path| !group.contains(path)) {
+            problems.push(AssetProblem::NotLoaded(path.clone()));
+        }
+    }
+    problems
+}
+
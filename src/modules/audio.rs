@@ -0,0 +1,403 @@
+/*
+By: <tyler>
+Date: 2025-12-01
+Program Details: Music and sound effects on two channels
+
+- Music: a soft chord loop that plays the whole time.
+- SFX: table sounds for game events, picked from the sound table.
+Each channel has its own volume. While a stinger plays the music is "ducked":
+it drops to duck_level of its volume and then fades back up, so the stinger
+can be heard.
+
+The sound table, assets/sounds.json, says which sound each event plays. Each
+cue has the event, the sound, a volume, whether a bigger bet plays it louder
+(scale_by_amount) and whether it ducks the music (duck). An event can have
+several cues, and an event with none is silent. The events are:
+    card_dealt, bet_placed, shoe_shuffled, player_busted,
+    hand_won, hand_lost, hand_pushed, blackjack (a hand won with a blackjack, after hand_won)
+The built-in sounds are card, chip, shuffle, murmur, thud, win and lose. They
+are built from sine waves and noise when the game starts, so nothing extra has
+to be downloaded. Any other sound name is loaded as a .wav or .ogg file, so a
+mod can drop its own sounds into assets and point the table at them.
+If the table is missing or broken the built-in one below is used.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod events;
+    pub mod audio;
+
+Then add the following with the use commands:
+use crate::modules::audio::Audio;
+
+Usage examples:
+1. Build the sounds before the loop, set the levels and start the music:
+    let mut audio = Audio::new().await;
+    audio.set_levels(settings.music_volume, settings.sfx_volume, settings.duck_level);
+    audio.start_music();
+
+2. It plays the sound table for the game events, so give it the events:
+    bus.dispatch(&mut [&mut audio]);
+
+3. A line in assets/sounds.json, a louder chip for bigger bets:
+    { "event": "bet_placed", "sound": "chip", "volume": 0.7, "scale_by_amount": true }
+
+4. Every frame, move the ducking along:
+    audio.update(get_frame_time());
+*/
+use std::collections::HashMap;
+use macroquad::audio::{load_sound, load_sound_from_bytes, play_sound, set_sound_volume, PlaySoundParams, Sound};
+use macroquad::file::load_string;
+use macroquad::rand;
+use nanoserde::DeJson;
+use crate::modules::events::{EventListener, GameEvent, Outcome};
+use crate::log_warn;
+
+const SAMPLE_RATE: u32 = 22050;
+const DUCK_DOWN_TIME: f32 = 0.15; // How fast the music drops when a stinger starts
+const DUCK_HOLD_TIME: f32 = 1.0; // How long it stays down
+const DUCK_UP_TIME: f32 = 0.8; // How long it takes to come back
+const SOUND_TABLE_PATH: &str = "assets/sounds.json";
+
+// One line of the sound table: `event` plays `sound`
+#[derive(DeJson, Clone, Debug)]
+#[nserde(default)]
+struct SoundCue {
+    event: String,
+    sound: String,
+    #[nserde(default = "1.0")]
+    volume: f32,           // Times the SFX volume
+    scale_by_amount: bool, // Small bets play quieter, for bet_placed
+    duck: bool,            // Dip the music while it plays
+}
+
+impl Default for SoundCue {
+    fn default() -> Self {
+        Self { event: String::new(), sound: String::new(), volume: 1.0, scale_by_amount: false, duck: false }
+    }
+}
+
+// The file format
+#[derive(DeJson, Clone, Debug, Default)]
+#[nserde(default)]
+struct SoundTable {
+    cues: Vec<SoundCue>,
+}
+
+impl SoundTable {
+    // What the game sounds like without a sounds.json
+    fn built_in() -> Self {
+        let cue = |event: &str, sound: &str, volume: f32| SoundCue { event: event.to_string(), sound: sound.to_string(), volume, ..Default::default() };
+        let cues = vec![
+            cue("card_dealt", "card", 1.0),
+            SoundCue { scale_by_amount: true, ..cue("bet_placed", "chip", 0.8) },
+            cue("shoe_shuffled", "shuffle", 0.8),
+            cue("player_busted", "thud", 1.0),
+            SoundCue { duck: true, ..cue("hand_won", "win", 1.0) },
+            SoundCue { duck: true, ..cue("hand_lost", "lose", 1.0) },
+            cue("blackjack", "murmur", 0.8),
+        ];
+        Self { cues }
+    }
+
+    async fn load(path: &str) -> Self {
+        let text = match load_string(path).await {
+            Ok(text) => text,
+            Err(err) => {
+                log_warn!("could not load {}: {}", path, err);
+                return Self::built_in();
+            }
+        };
+        match SoundTable::deserialize_json(&text) {
+            Ok(table) => table,
+            Err(err) => {
+                log_warn!("could not parse {}: {}", path, err);
+                Self::built_in()
+            }
+        }
+    }
+}
+
+pub struct Audio {
+    music: Option<Sound>,
+    sounds: HashMap<String, Sound>, // Built-in and loaded sounds by name, empty if they could not be built
+    cues: Vec<SoundCue>,
+    music_volume: f32,
+    sfx_volume: f32,
+    duck_level: f32,
+    duck_time: Option<f32>, // Seconds since the last stinger started
+    music_playing: bool,
+}
+
+impl Audio {
+    pub async fn new() -> Self {
+        let (music, mut sounds) = match Self::build_sounds().await {
+            Ok((music, sounds)) => (Some(music), sounds),
+            Err(err) => {
+                log_warn!("could not build the sounds, playing without audio: {:?}", err);
+                (None, HashMap::new())
+            }
+        };
+        let table = SoundTable::load(SOUND_TABLE_PATH).await;
+        // Anything the table names that isn't built in comes from a file
+        if music.is_some() {
+            for cue in table.cues.iter() {
+                if sounds.contains_key(&cue.sound) {
+                    continue;
+                }
+                match load_sound(&cue.sound).await {
+                    Ok(sound) => {
+                        sounds.insert(cue.sound.clone(), sound);
+                    }
+                    Err(err) => log_warn!("could not load sound {} for {}: {:?}", cue.sound, cue.event, err),
+                }
+            }
+        }
+        Self { music, sounds, cues: table.cues, music_volume: 0.5, sfx_volume: 0.8, duck_level: 0.3, duck_time: None, music_playing: false }
+    }
+
+    async fn build_sounds() -> Result<(Sound, HashMap<String, Sound>), macroquad::Error> {
+        let music = load_sound_from_bytes(&wav_bytes(&music_loop())).await?;
+        let built_in = [
+            ("card", card_flick()),
+            ("chip", chip_clack()),
+            ("shuffle", riffle()),
+            ("murmur", murmur()),
+            ("thud", thud()),
+            ("win", arpeggio(&[523.25, 659.25, 783.99, 1046.5])),
+            ("lose", arpeggio(&[392.0, 349.23, 311.13, 261.63])),
+        ];
+        let mut sounds = HashMap::new();
+        for (name, samples) in built_in {
+            sounds.insert(name.to_string(), load_sound_from_bytes(&wav_bytes(&samples)).await?);
+        }
+        Ok((music, sounds))
+    }
+
+    // All levels go from 0.0 to 1.0
+    pub fn set_levels(&mut self, music_volume: f32, sfx_volume: f32, duck_level: f32) {
+        self.music_volume = music_volume.clamp(0.0, 1.0);
+        self.sfx_volume = sfx_volume.clamp(0.0, 1.0);
+        self.duck_level = duck_level.clamp(0.0, 1.0);
+        self.apply_music_volume();
+    }
+
+    pub fn start_music(&mut self) {
+        if let Some(music) = &self.music
+            && !self.music_playing
+        {
+            play_sound(music, PlaySoundParams { looped: true, volume: self.current_music_volume() });
+            self.music_playing = true;
+        }
+    }
+
+    // Play every cue for an event on the SFX channel. `amount` is the bet for cues scaled by it.
+    fn play_cues(&mut self, event: &str, amount: i64) {
+        if self.sfx_volume <= 0.0 {
+            return;
+        }
+        let mut duck = false;
+        for cue in self.cues.iter().filter(|cue| cue.event == event) {
+            let Some(sound) = self.sounds.get(&cue.sound) else {
+                continue;
+            };
+            // $1 is quiet, $1000 and up full volume
+            let scale = if cue.scale_by_amount { 0.3 + 0.7 * ((amount.max(1) as f32).log10() / 3.0).min(1.0) } else { 1.0 };
+            let volume = (self.sfx_volume * cue.volume * scale).clamp(0.0, 1.0);
+            play_sound(sound, PlaySoundParams { looped: false, volume });
+            duck |= cue.duck;
+        }
+        if duck {
+            self.duck_time = Some(0.0);
+            self.apply_music_volume();
+        }
+    }
+
+    // How loud the music is right now, with any ducking
+    fn current_music_volume(&self) -> f32 {
+        let Some(time) = self.duck_time else {
+            return self.music_volume;
+        };
+        // 1.0 is full volume, duck_level is fully ducked
+        let ducked = if time < DUCK_DOWN_TIME {
+            1.0 - (1.0 - self.duck_level) * time / DUCK_DOWN_TIME
+        } else if time < DUCK_DOWN_TIME + DUCK_HOLD_TIME {
+            self.duck_level
+        } else {
+            let t = ((time - DUCK_DOWN_TIME - DUCK_HOLD_TIME) / DUCK_UP_TIME).min(1.0);
+            self.duck_level + (1.0 - self.duck_level) * t
+        };
+        self.music_volume * ducked
+    }
+
+    fn apply_music_volume(&self) {
+        if let Some(music) = &self.music
+            && self.music_playing
+        {
+            set_sound_volume(music, self.current_music_volume());
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        let Some(time) = self.duck_time.as_mut() else {
+            return;
+        };
+        *time += dt;
+        if *time >= DUCK_DOWN_TIME + DUCK_HOLD_TIME + DUCK_UP_TIME {
+            self.duck_time = None;
+        }
+        self.apply_music_volume();
+    }
+}
+
+impl EventListener for Audio {
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::CardDealt { .. } => self.play_cues("card_dealt", 0),
+            GameEvent::BetPlaced { amount } => self.play_cues("bet_placed", *amount),
+            GameEvent::ShoeShuffled { .. } => self.play_cues("shoe_shuffled", 0),
+            GameEvent::PlayerBusted { .. } => self.play_cues("player_busted", 0),
+            GameEvent::RoundSettled { outcome, blackjack, .. } => {
+                match outcome {
+                    Outcome::PlayerWin => self.play_cues("hand_won", 0),
+                    Outcome::Push => self.play_cues("hand_pushed", 0),
+                    Outcome::DealerWin | Outcome::NoWinner => self.play_cues("hand_lost", 0),
+                }
+                if *blackjack && *outcome == Outcome::PlayerWin {
+                    self.play_cues("blackjack", 0);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// A sine note with a quick attack and a fade out, added into `samples` starting at `start` seconds
+fn add_note(samples: &mut [f32], frequency: f32, start: f32, length: f32, volume: f32) {
+    let first = (start * SAMPLE_RATE as f32) as usize;
+    let count = (length * SAMPLE_RATE as f32) as usize;
+    let attack = (0.02 * SAMPLE_RATE as f32).max(1.0);
+    for i in 0..count {
+        let Some(sample) = samples.get_mut(first + i) else {
+            break;
+        };
+        let t = i as f32 / SAMPLE_RATE as f32;
+        let envelope = (i as f32 / attack).min(1.0) * (1.0 - i as f32 / count as f32);
+        *sample += (t * frequency * std::f32::consts::TAU).sin() * volume * envelope;
+    }
+}
+
+// Four slow chords (Am, F, C, G), two seconds each, ends where it starts so it loops cleanly
+fn music_loop() -> Vec<f32> {
+    let chords: [[f32; 3]; 4] = [[220.0, 261.63, 329.63], [174.61, 220.0, 261.63], [130.81, 196.0, 261.63], [196.0, 246.94, 293.66]];
+    let mut samples = vec![0.0; SAMPLE_RATE as usize * 8];
+    for (i, chord) in chords.iter().enumerate() {
+        for frequency in chord {
+            add_note(&mut samples, *frequency, i as f32 * 2.0, 2.0, 0.12);
+        }
+    }
+    samples
+}
+
+// Quick notes one after the other
+fn arpeggio(frequencies: &[f32]) -> Vec<f32> {
+    let step = 0.12;
+    let mut samples = vec![0.0; (SAMPLE_RATE as f32 * (step * frequencies.len() as f32 + 0.4)) as usize];
+    for (i, frequency) in frequencies.iter().enumerate() {
+        add_note(&mut samples, *frequency, i as f32 * step, 0.45, 0.3);
+    }
+    samples
+}
+
+// A short burst of fading noise, like a card sliding across the felt
+fn card_flick() -> Vec<f32> {
+    let count = (0.06 * SAMPLE_RATE as f32) as usize;
+    (0..count)
+        .map(|i| rand::gen_range(-1.0, 1.0) * 0.25 * (1.0 - i as f32 / count as f32))
+        .collect()
+}
+
+// Two clay chips knocking together
+fn chip_clack() -> Vec<f32> {
+    let mut samples = vec![0.0; (0.15 * SAMPLE_RATE as f32) as usize];
+    for (start, frequency) in [(0.0, 2800.0), (0.05, 3300.0)] {
+        add_note(&mut samples, frequency, start, 0.04, 0.25);
+        let first = (start * SAMPLE_RATE as f32) as usize;
+        let count = (0.015 * SAMPLE_RATE as f32) as usize;
+        for (i, sample) in samples.iter_mut().skip(first).take(count).enumerate() {
+            *sample += rand::gen_range(-1.0, 1.0) * 0.2 * (1.0 - i as f32 / count as f32);
+        }
+    }
+    samples
+}
+
+// A riffle shuffle, lots of quick card flicks that speed up and then slow down
+fn riffle() -> Vec<f32> {
+    let length = 0.9;
+    let mut samples = vec![0.0; (length * SAMPLE_RATE as f32) as usize];
+    let flick = card_flick();
+    let flicks = 28;
+    for i in 0..flicks {
+        let t = i as f32 / flicks as f32;
+        // Bunched up in the middle, spread out at the ends
+        let start = length * 0.85 * (t - (t * std::f32::consts::TAU).sin() / 10.0);
+        let first = (start.max(0.0) * SAMPLE_RATE as f32) as usize;
+        for (sample, flick) in samples.iter_mut().skip(first).zip(flick.iter()) {
+            *sample += flick * 0.5;
+        }
+    }
+    samples
+}
+
+// A crowd around the table, low filtered noise that swells and fades
+fn murmur() -> Vec<f32> {
+    let count = (1.8 * SAMPLE_RATE as f32) as usize;
+    let mut low = 0.0;
+    (0..count)
+        .map(|i| {
+            let t = i as f32 / count as f32;
+            // A one pole low pass takes the hiss out of the noise so it sounds like voices far off
+            low += (rand::gen_range(-1.0, 1.0) - low) * 0.05;
+            let swell = (t * std::f32::consts::PI).sin();
+            let chatter = 0.7 + 0.3 * (t * 37.0).sin() * (t * 23.0).sin();
+            low * 1.6 * swell * chatter
+        })
+        .collect()
+}
+
+// A heavy low knock for a bust
+fn thud() -> Vec<f32> {
+    let count = (0.35 * SAMPLE_RATE as f32) as usize;
+    (0..count)
+        .map(|i| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let decay = (-t * 14.0).exp();
+            // The pitch drops as it dies away
+            let frequency = 90.0 - 40.0 * (i as f32 / count as f32);
+            let knock = if i < 200 { rand::gen_range(-1.0, 1.0) * 0.3 * (1.0 - i as f32 / 200.0) } else { 0.0 };
+            (t * frequency * std::f32::consts::TAU).sin() * 0.7 * decay + knock
+        })
+        .collect()
+}
+
+// 16-bit mono PCM WAV file in memory
+fn wav_bytes(samples: &[f32]) -> Vec<u8> {
+    let data_size = samples.len() as u32 * 2;
+    let mut bytes = Vec::with_capacity(44 + data_size as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes()); // Format chunk size
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // Mono
+    bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes()); // Bytes per second
+    bytes.extend_from_slice(&2u16.to_le_bytes()); // Bytes per sample
+    bytes.extend_from_slice(&16u16.to_le_bytes()); // Bits per sample
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_size.to_le_bytes());
+    for sample in samples {
+        bytes.extend_from_slice(&((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes());
+    }
+    bytes
+}
@@ -0,0 +1,321 @@
+/*
+By: <tyler>
+Date: 2025-11-22
+Program Details: Append-only fairness log ("provably fair" style)
+
+How it works:
+- When the shoe is shuffled the log publishes a commitment: the SHA-256 of the
+  number of decks, the shuffle seed and a random salt. The seed itself stays secret.
+  Seeds and salts come from the system's random source (see entropy.rs), so
+  nothing revealed gives away the next shoe.
+- Every round records which shoe it came from, where in the shoe it started,
+  every card dealt (in order) and the result.
+- When that shoe is retired (the next reshuffle) the seed and salt are revealed.
+  Anyone can then hash them to check the commitment, rebuild the shoe from the
+  seed, and replay every round to check the cards and the result.
+- Each entry also stores the hash of the entry before it, so changing or
+  removing an old entry breaks the chain.
+
+On native the log is also appended to fairness_log.txt.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod entropy;
+    pub mod audit;
+In the Cargo.toml file add the following:
+    sha2 = "0.10"
+
+Then add the following with the use commands:
+use crate::modules::audit::AuditLog;
+
+Usage examples:
+1. Create it before the loop and give it every game event:
+    let mut audit = AuditLog::new(6);
+    bus.dispatch(&mut [&mut audit]);
+
+2. Check the rounds so far:
+    for (round, status) in audit.verify_rounds() {
+        println!("{} {:?}", round.number, status);
+    }
+    let chain_ok = audit.verify_chain();
+*/
+use sha2::{Digest, Sha256};
+use crate::modules::cards::Card;
+use crate::modules::entropy;
+use crate::modules::events::{EventListener, GameEvent, Outcome, Seat};
+use crate::modules::hand::{settle, Hand};
+use crate::modules::shoe::Shoe;
+use crate::log_warn;
+
+const LOG_FILE: &str = "fairness_log.txt";
+
+#[derive(Clone, Debug)]
+pub enum AuditRecord {
+    ShoeCommitted { shoe_id: u32, decks: usize, commitment: String },
+    RoundPlayed(RoundRecord),
+    ShoeRevealed { shoe_id: u32, seed: u64, salt: String },
+}
+
+#[derive(Clone, Debug)]
+pub struct RoundRecord {
+    pub number: u32,
+    pub shoe_id: u32,
+    pub start_index: usize, // Position in the shoe of the first card of the round
+    pub cards: Vec<(Seat, Card)>,
+    pub outcomes: Vec<Outcome>, // One for each player hand
+}
+
+#[derive(Clone, Debug)]
+pub struct AuditEntry {
+    pub record: AuditRecord,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+// Result of checking one round
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verification {
+    Verified,
+    PendingReveal, // The shoe is still in play, its seed hasn't been revealed yet
+    Failed(String),
+}
+
+// The seed of the shoe currently in play, kept private until it is retired
+struct ShoeSecret {
+    shoe_id: u32,
+    seed: u64,
+    salt: String,
+}
+
+pub struct AuditLog {
+    entries: Vec<AuditEntry>,
+    decks: usize,
+    secret: Option<ShoeSecret>,
+    next_shoe_id: u32,
+    cards_since_shuffle: usize,
+    round: Option<RoundRecord>,
+    rounds_played: u32,
+}
+
+impl AuditLog {
+    pub fn new(decks: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            decks,
+            secret: None,
+            next_shoe_id: 1,
+            cards_since_shuffle: 0,
+            round: None,
+            rounds_played: 0,
+        }
+    }
+
+    // Every entry so far, oldest first. There is no way to change or remove one.
+    #[allow(unused)]
+    pub fn entries(&self) -> &[AuditEntry] {
+        &self.entries
+    }
+
+    fn append(&mut self, record: AuditRecord) {
+        let prev_hash = self.entries.last().map(|entry| entry.hash.clone()).unwrap_or_else(|| "0".repeat(64));
+        let hash = sha256_hex(&format!("{}|{}", prev_hash, record_text(&record)));
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            use std::io::Write;
+            let line = format!("{}|{}|{}\n", prev_hash, hash, record_text(&record));
+            let file = std::fs::OpenOptions::new().create(true).append(true).open(LOG_FILE);
+            if let Err(err) = file.and_then(|mut file| file.write_all(line.as_bytes())) {
+                log_warn!("could not write {}: {}", LOG_FILE, err);
+            }
+        }
+
+        self.entries.push(AuditEntry { record, prev_hash, hash });
+    }
+
+    // A new shoe: reveal the old one and commit to the new seed before any card is dealt
+    fn on_shuffle(&mut self, seed: u64) {
+        self.reveal_current_shoe();
+        let salt = entropy::salt();
+        let shoe_id = self.next_shoe_id;
+        self.next_shoe_id += 1;
+        let commitment = commitment_hash(self.decks, seed, &salt);
+        self.append(AuditRecord::ShoeCommitted { shoe_id, decks: self.decks, commitment });
+        self.secret = Some(ShoeSecret { shoe_id, seed, salt });
+        self.cards_since_shuffle = 0;
+    }
+
+    fn reveal_current_shoe(&mut self) {
+        if let Some(secret) = self.secret.take() {
+            self.append(AuditRecord::ShoeRevealed {
+                shoe_id: secret.shoe_id,
+                seed: secret.seed,
+                salt: secret.salt,
+            });
+        }
+    }
+
+    fn on_card(&mut self, seat: Seat, card: Card) {
+        let Some(shoe_id) = self.secret.as_ref().map(|secret| secret.shoe_id) else {
+            return;
+        };
+        let start_index = self.cards_since_shuffle;
+        let number = self.rounds_played + 1;
+        self.round
+            .get_or_insert_with(|| RoundRecord { number, shoe_id, start_index, cards: Vec::new(), outcomes: Vec::new() })
+            .cards
+            .push((seat, card));
+        self.cards_since_shuffle += 1;
+    }
+
+    fn on_settled(&mut self, outcome: Outcome) {
+        if let Some(round) = self.round.as_mut() {
+            round.outcomes.push(outcome);
+        }
+    }
+
+    // Every hand has been settled, the round goes into the log
+    fn on_finished(&mut self) {
+        if let Some(round) = self.round.take() {
+            self.rounds_played += 1;
+            self.append(AuditRecord::RoundPlayed(round));
+        }
+    }
+
+    // True if no entry has been changed, removed or reordered
+    pub fn verify_chain(&self) -> bool {
+        let mut prev_hash = "0".repeat(64);
+        for entry in &self.entries {
+            let hash = sha256_hex(&format!("{}|{}", prev_hash, record_text(&entry.record)));
+            if entry.prev_hash != prev_hash || entry.hash != hash {
+                return false;
+            }
+            prev_hash = hash;
+        }
+        true
+    }
+
+    // Check every recorded round against its revealed shoe
+    pub fn verify_rounds(&self) -> Vec<(&RoundRecord, Verification)> {
+        self.entries
+            .iter()
+            .filter_map(|entry| match &entry.record {
+                AuditRecord::RoundPlayed(round) => Some((round, self.verify_round(round))),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn verify_round(&self, round: &RoundRecord) -> Verification {
+        let mut commitment = None;
+        let mut reveal = None;
+        for entry in &self.entries {
+            match &entry.record {
+                AuditRecord::ShoeCommitted { shoe_id, decks, commitment: hash } if *shoe_id == round.shoe_id => {
+                    commitment = Some((*decks, hash.clone()));
+                }
+                AuditRecord::ShoeRevealed { shoe_id, seed, salt } if *shoe_id == round.shoe_id => {
+                    reveal = Some((*seed, salt.clone()));
+                }
+                _ => {}
+            }
+        }
+        let Some((decks, hash)) = commitment else {
+            return Verification::Failed("no commitment for this shoe".to_string());
+        };
+        let Some((seed, salt)) = reveal else {
+            return Verification::PendingReveal;
+        };
+        if commitment_hash(decks, seed, &salt) != hash {
+            return Verification::Failed("revealed seed does not match the commitment".to_string());
+        }
+
+        // Rebuild the shoe and make sure the same cards came out in the same order
+        let mut shoe = Shoe::new(decks, seed);
+        for _ in 0..round.start_index {
+            shoe.deal();
+        }
+        let mut players = vec![Hand::new(); round.outcomes.len()];
+        let mut dealer = Hand::new();
+        for (seat, card) in &round.cards {
+            if shoe.deal() != *card {
+                return Verification::Failed(format!("{} was not the next card in the shoe", card.code()));
+            }
+            match seat {
+                Seat::Player(hand) => match players.get_mut(*hand) {
+                    Some(player) => player.add(*card),
+                    None => return Verification::Failed(format!("{} went to a hand that was never settled", card.code())),
+                },
+                Seat::Dealer => dealer.add(*card),
+            }
+        }
+        if players.iter().zip(&round.outcomes).any(|(player, outcome)| settle(player, &dealer) != *outcome) {
+            return Verification::Failed("recorded result does not match the cards".to_string());
+        }
+        Verification::Verified
+    }
+
+    // True if the shoe in play has rounds waiting for its seed to be revealed
+    pub fn has_pending_rounds(&self) -> bool {
+        let Some(secret) = &self.secret else {
+            return false;
+        };
+        self.entries
+            .iter()
+            .any(|entry| matches!(&entry.record, AuditRecord::RoundPlayed(round) if round.shoe_id == secret.shoe_id))
+    }
+}
+
+impl EventListener for AuditLog {
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::ShoeShuffled { seed } => self.on_shuffle(*seed),
+            GameEvent::CardDealt { card, seat } => self.on_card(*seat, *card),
+            GameEvent::RoundSettled { outcome, .. } => self.on_settled(*outcome),
+            GameEvent::RoundFinished => self.on_finished(),
+            _ => {}
+        }
+    }
+}
+
+fn commitment_hash(decks: usize, seed: u64, salt: &str) -> String {
+    sha256_hex(&format!("{}:{}:{}", decks, seed, salt))
+}
+
+fn sha256_hex(text: &str) -> String {
+    Sha256::digest(text.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// The exact text that gets hashed for an entry
+fn record_text(record: &AuditRecord) -> String {
+    match record {
+        AuditRecord::ShoeCommitted { shoe_id, decks, commitment } => {
+            format!("commit shoe={} decks={} hash={}", shoe_id, decks, commitment)
+        }
+        AuditRecord::RoundPlayed(round) => {
+            let cards: Vec<String> = round
+                .cards
+                .iter()
+                .map(|(seat, card)| {
+                    let who = match seat {
+                        Seat::Player(hand) => format!("P{}", hand + 1),
+                        Seat::Dealer => "D".to_string(),
+                    };
+                    format!("{}:{}", who, card.code())
+                })
+                .collect();
+            format!(
+                "round {} shoe={} start={} cards={} outcomes={:?}",
+                round.number,
+                round.shoe_id,
+                round.start_index,
+                cards.join(","),
+                round.outcomes
+            )
+        }
+        AuditRecord::ShoeRevealed { shoe_id, seed, salt } => {
+            format!("reveal shoe={} seed={} salt={}", shoe_id, seed, salt)
+        }
+    }
+}
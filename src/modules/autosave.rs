@@ -0,0 +1,206 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Saving the session when the game is closed
+
+The profile saves itself as rounds settle, but some things only get written
+later: settings changed on a panel that is still open, and where the shoe is
+(it changes with every card, too often to save each time). The game stages
+them here every frame, and flush() writes whatever is staged straight to
+storage. Profile::save() empties it, so a flush never writes anything older
+than the last save over it.
+
+flush() is called when the game is left or closed:
+- Web: js/blackjack.js calls blackjack_page_hidden() when the tab is hidden,
+  the page is hidden or about to unload. The browser may not come back to the
+  game after that, so everything is written there and then.
+- Native: main.rs stops the window from closing right away (prevent_quit),
+  saves when the close is asked for, then quits.
+
+A round being played when the game is closed is saved too, under the
+profile's round key (Profile::round_key): the cards on the table, the bets and
+how far the round had got. Its bets were never taken from the saved bankroll,
+the next launch holds them again and deals the round back to be finished.
+It is staged whenever a card comes out or the player moves on, and taken out
+of storage as soon as the round settles, so a settled round can't come back.
+
+The shoe is saved as its seed and how many cards were dealt, which is all it
+takes to rebuild it (a famous hand or a drill may have stacked it, those few
+cards come back in the seed's order).
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod storage;
+    pub mod cards;
+    pub mod hand;
+    pub mod shoe;
+    pub mod bet;
+    pub mod autosave;
+In the Cargo.toml file add the following:
+    nanoserde = "0.2.1"
+
+Then add the following with the use commands:
+use crate::modules::autosave;
+
+Usage examples:
+1. Every frame, stage what isn't saved yet:
+    if profile.has_changes() {
+        autosave::stage(profile.parts());
+    }
+    autosave::stage_shoe(&shoe);
+
+2. Stage the round being played when it moves on, and forget it once it settles:
+    autosave::stage_round(&profile.round_key(), RoundSave::new(&player_hands, squeezed, &dealer_hand, hole_card, &bet, active, started));
+    autosave::end_round(&profile.round_key());
+
+3. Write it when the player leaves (the web build does this by itself on close):
+    autosave::flush();
+
+4. Carry on with the saved shoe next time:
+    let shoe = match autosave::load_shoe() {
+        Some(saved) => Shoe::new(6, saved.seed).with_dealt(saved.dealt),
+        None => Shoe::new(6, seed),
+    };
+
+5. Deal back the round the game was closed in:
+    if let Some(saved) = autosave::load_round(&profile.round_key()) {
+        let (player_hands, dealer_hand, hole_card) = saved.cards().ok_or("a card in the save isn't a card")?;
+        ledger.reserve(profile.info.bankroll, saved.stake())?;
+    }
+*/
+use nanoserde::{DeJson, SerJson};
+use std::cell::RefCell;
+use crate::modules::bet::Bet;
+use crate::modules::cards::Card;
+use crate::modules::hand::Hand;
+use crate::modules::shoe::Shoe;
+use crate::modules::storage;
+use crate::log_info;
+
+const SHOE_KEY: &str = "shoe";
+
+// Enough to rebuild the shoe where it was
+#[derive(SerJson, DeJson, Clone, Copy, Debug, PartialEq)]
+pub struct ShoeSave {
+    pub seed: u64,
+    pub dealt: usize,
+}
+
+// A round the game was closed in the middle of, as it was on the table
+#[derive(SerJson, DeJson, Clone, Debug, Default, PartialEq)]
+#[nserde(default)]
+pub struct RoundSave {
+    pub player_hands: Vec<Vec<String>>, // Card codes (Card::code), a hit card still being squeezed is in its hand
+    pub bets: Vec<i64>,                 // One per hand
+    pub active: usize,                  // The hand being played, past the last one once the dealer has started
+    pub dealer_hand: Vec<String>,
+    pub hole_card: String, // Empty when it isn't out
+    pub started: bool, // The player's turn had started, false while the cards go out or even money is asked
+}
+
+impl RoundSave {
+    pub fn new(player_hands: &[Hand], squeezed: Option<Card>, dealer_hand: &Hand, hole_card: Option<Card>, bet: &Bet, active: usize, started: bool) -> Self {
+        let codes = |hand: &Hand| hand.cards().iter().map(|card| card.code()).collect::<Vec<String>>();
+        let mut hands: Vec<Vec<String>> = player_hands.iter().map(codes).collect();
+        if let (Some(card), Some(hand)) = (squeezed, hands.get_mut(active)) {
+            hand.push(card.code());
+        }
+        Self {
+            player_hands: hands,
+            bets: (0..player_hands.len()).map(|hand| bet.amount(hand)).collect(),
+            active,
+            dealer_hand: codes(dealer_hand),
+            hole_card: hole_card.map(|card| card.code()).unwrap_or_default(),
+            started,
+        }
+    }
+
+    // The player's hands, the dealer's and the hole card, None if the save has something that isn't a card
+    pub fn cards(&self) -> Option<(Vec<Hand>, Hand, Option<Card>)> {
+        let hand = |codes: &[String]| codes.iter().map(|code| Card::parse(code)).collect::<Option<Vec<Card>>>().map(|cards| Hand::from_cards(&cards));
+        let player_hands = self.player_hands.iter().map(|codes| hand(codes)).collect::<Option<Vec<Hand>>>()?;
+        let hole_card = match self.hole_card.as_str() {
+            "" => None,
+            code => Some(Card::parse(code)?),
+        };
+        Some((player_hands, hand(&self.dealer_hand)?, hole_card))
+    }
+
+    // Everything bet on the round
+    pub fn stake(&self) -> i64 {
+        self.bets.iter().sum()
+    }
+}
+
+thread_local! {
+    static STAGED: RefCell<Vec<(String, String)>> = const { RefCell::new(Vec::new()) };
+    static SHOE: RefCell<Option<ShoeSave>> = const { RefCell::new(None) };
+    static ROUND: RefCell<Option<(String, RoundSave)>> = const { RefCell::new(None) };
+}
+
+// Keys and values to write on the next flush, replacing anything staged under the same keys
+pub fn stage(parts: Vec<(String, String)>) {
+    STAGED.with(|staged| {
+        let mut staged = staged.borrow_mut();
+        for (key, value) in parts {
+            match staged.iter_mut().find(|(staged_key, _)| *staged_key == key) {
+                Some(entry) => entry.1 = value,
+                None => staged.push((key, value)),
+            }
+        }
+    });
+}
+
+// Forget the staged keys, they have been saved
+pub fn clear() {
+    STAGED.with(|staged| staged.borrow_mut().clear());
+}
+
+// Remember where the shoe is, it is only written on a flush
+pub fn stage_shoe(shoe: &Shoe) {
+    SHOE.with(|saved| *saved.borrow_mut() = Some(ShoeSave { seed: shoe.seed(), dealt: shoe.dealt() }));
+}
+
+// Remember the round being played under `key`, it is only written on a flush
+pub fn stage_round(key: &str, round: RoundSave) {
+    ROUND.with(|saved| *saved.borrow_mut() = Some((key.to_string(), round)));
+}
+
+// The round under `key` has settled, forget it and take it out of storage straight away
+pub fn end_round(key: &str) {
+    ROUND.with(|saved| saved.borrow_mut().take_if(|(staged_key, _)| staged_key == key));
+    storage::remove(key);
+}
+
+// Write everything staged to storage now
+pub fn flush() {
+    let staged = STAGED.with(|staged| std::mem::take(&mut *staged.borrow_mut()));
+    for (key, value) in &staged {
+        storage::save(key, value);
+    }
+    if let Some(shoe) = SHOE.with(|saved| *saved.borrow()) {
+        storage::save(SHOE_KEY, &shoe.serialize_json());
+    }
+    if let Some((key, round)) = ROUND.with(|saved| saved.borrow_mut().take()) {
+        storage::save(&key, &round.serialize_json());
+    }
+    log_info!("session saved ({} parts and the shoe)", staged.len());
+}
+
+// The shoe saved when the game was last closed
+pub fn load_shoe() -> Option<ShoeSave> {
+    ShoeSave::deserialize_json(&storage::load(SHOE_KEY)?).ok()
+}
+
+// The round saved under `key` when the game was last closed in the middle of it
+pub fn load_round(key: &str) -> Option<RoundSave> {
+    RoundSave::deserialize_json(&storage::load(key)?).ok()
+}
+
+// Called by js/blackjack.js when the page is hidden or closed, the game may not get another frame
+#[cfg(target_arch = "wasm32")]
+#[unsafe(no_mangle)]
+pub extern "C" fn blackjack_page_hidden() {
+    flush();
+}
@@ -0,0 +1,181 @@
+/*
+By: <tyler>
+Date: 2025-12-11
+Program Details: Bankroll transactions, so a round can't leave the balance wrong
+
+The saved bankroll only ever changes when a round finishes. When the cards go
+out the bets are reserved: they still belong to the player, but the balance on
+screen leaves them out and nothing else can spend them. When the round settles
+the transaction is committed and the bankroll moves by what the round won or
+lost. If the round can't be finished (the settlement doesn't add up, or a round
+from before is somehow still open) the transaction is rolled back and the bets
+are simply free again.
+
+Closing the game part way through a round doesn't settle it either way: the
+round is saved with the session and dealt back next time (see autosave.rs),
+its bets reserved again. A saved round that can't be dealt back (it doesn't fit
+the table any more) is forfeited instead: the bets are lost, as if the hand had
+been played out and lost. Giving them back would let a player walk away from
+any hand that looks bad.
+
+A settlement that loses more than was reserved is refused and rolled back
+instead, the bankroll is never touched by a change that doesn't add up.
+
+Every reserve, commit, rollback and forfeit goes into a trail (the last
+TRAIL_LIMIT of them) with the balance after it, and into the log.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod log;
+    pub mod bankroll;
+
+Then add the following with the use commands:
+use crate::modules::bankroll::Ledger;
+
+Usage examples:
+1. Create it before the loop:
+    let mut ledger = Ledger::new();
+
+2. When the cards go out, reserve what is on the spots:
+    ledger.reserve(profile.info.bankroll, bet.total())?;
+
+3. Show what the player has to spend:
+    let available = ledger.available(profile.info.bankroll);
+
+4. When the round settles, move the money, or give the bets back if it can't be:
+    ledger.commit(&mut profile.info.bankroll, bet.settle(&results, rules.blackjack_pays))?;
+    ledger.rollback(profile.info.bankroll, "the last round never settled");
+
+5. A round left open can't be played on, the bets are lost:
+    ledger.forfeit(&mut profile.info.bankroll, "the saved round doesn't fit the table");
+
+6. Read the trail:
+    for entry in ledger.trail() {
+        println!("#{} {:?} {} -> ${}", entry.transaction, entry.kind, entry.amount, entry.balance);
+    }
+*/
+use crate::{log_info, log_warn};
+
+const TRAIL_LIMIT: usize = 100;
+
+// Bets reserved for the round being played
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transaction {
+    pub id: u32,
+    pub stake: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EntryKind {
+    Reserve,
+    Commit,
+    Rollback,
+    Forfeit,
+}
+
+#[allow(unused)]
+#[derive(Clone, Debug)]
+pub struct LedgerEntry {
+    pub transaction: u32,
+    pub kind: EntryKind,
+    pub amount: i64,  // The stake reserved or rolled back, or the change committed or forfeited
+    pub balance: i64, // The saved bankroll afterwards
+    pub note: String, // Why a rollback or forfeit happened
+}
+
+pub struct Ledger {
+    open: Option<Transaction>,
+    next_id: u32,
+    trail: Vec<LedgerEntry>,
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        Self { open: None, next_id: 1, trail: Vec::new() }
+    }
+
+    // The round's reserved bets, if one is being played
+    #[allow(unused)]
+    pub fn open(&self) -> Option<Transaction> {
+        self.open
+    }
+
+    // What is reserved right now
+    pub fn held(&self) -> i64 {
+        self.open.map_or(0, |transaction| transaction.stake)
+    }
+
+    // The bankroll less what is reserved, what the player has to spend
+    pub fn available(&self, bankroll: i64) -> i64 {
+        bankroll - self.held()
+    }
+
+    // Oldest first
+    #[allow(unused)]
+    pub fn trail(&self) -> &[LedgerEntry] {
+        &self.trail
+    }
+
+    // Reserve the bets for a round. Refused while another round's are still
+    // reserved, or when the bankroll doesn't cover them.
+    pub fn reserve(&mut self, bankroll: i64, stake: i64) -> Result<Transaction, String> {
+        if let Some(open) = self.open {
+            return Err(format!("transaction #{} is still open", open.id));
+        }
+        if stake < 0 || stake > bankroll {
+            return Err(format!("can't reserve ${} from a ${} bankroll", stake, bankroll));
+        }
+        let transaction = Transaction { id: self.next_id, stake };
+        self.next_id += 1;
+        self.open = Some(transaction);
+        self.record(transaction.id, EntryKind::Reserve, stake, bankroll, "");
+        Ok(transaction)
+    }
+
+    // Settle the open transaction by moving the bankroll by `change`, returns the new
+    // bankroll. A change that loses more than was reserved is rolled back instead.
+    pub fn commit(&mut self, bankroll: &mut i64, change: i64) -> Result<i64, String> {
+        let Some(open) = self.open else {
+            return Err("no transaction to commit".to_string());
+        };
+        if change < -open.stake {
+            let reason = format!("a change of {} is more than the ${} reserved", change, open.stake);
+            self.rollback(*bankroll, &reason);
+            return Err(reason);
+        }
+        self.open = None;
+        *bankroll += change;
+        self.record(open.id, EntryKind::Commit, change, *bankroll, "");
+        Ok(*bankroll)
+    }
+
+    // Give the reserved bets back without settling them, returns the transaction
+    // that was open. The bankroll is only passed in for the trail, it doesn't change.
+    pub fn rollback(&mut self, bankroll: i64, reason: &str) -> Option<Transaction> {
+        let open = self.open.take()?;
+        self.record(open.id, EntryKind::Rollback, open.stake, bankroll, reason);
+        Some(open)
+    }
+
+    // Settle the open transaction as lost, the round can't be played on. Returns the
+    // transaction that was open.
+    pub fn forfeit(&mut self, bankroll: &mut i64, reason: &str) -> Option<Transaction> {
+        let open = self.open.take()?;
+        *bankroll -= open.stake;
+        self.record(open.id, EntryKind::Forfeit, -open.stake, *bankroll, reason);
+        Some(open)
+    }
+
+    fn record(&mut self, transaction: u32, kind: EntryKind, amount: i64, balance: i64, note: &str) {
+        match kind {
+            EntryKind::Rollback => log_warn!("transaction #{} rolled back (${}): {}", transaction, amount, note),
+            EntryKind::Forfeit => log_warn!("transaction #{} forfeited ({}): {}, bankroll ${}", transaction, amount, note, balance),
+            _ => log_info!("transaction #{} {:?} {}, bankroll ${}", transaction, kind, amount, balance),
+        }
+        if self.trail.len() == TRAIL_LIMIT {
+            self.trail.remove(0);
+        }
+        self.trail.push(LedgerEntry { transaction, kind, amount, balance, note: note.to_string() });
+    }
+}
@@ -0,0 +1,56 @@
+/*
+By: <tyler>
+Date: 2025-12-08
+Program Details: A tiny timer for the benchmark tests
+
+The benchmarks are ordinary tests marked #[ignore], so `cargo test` skips them
+and they only run when asked for. Run them optimized, a debug build says little
+about how fast the game is:
+    cargo test --release -- --ignored --nocapture bench_
+
+Each one runs its work a few times to warm up, then long enough to get a steady
+number, and prints how many times a second it managed. Compare the numbers
+before and after a change on the same machine, they aren't checked against a
+limit because every machine is different.
+
+Only tests have it: the module is behind #[cfg(test)] in mod.rs.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    #[cfg(test)]
+    pub mod bench;
+
+Then add the following with the use commands:
+use crate::modules::bench;
+
+Usage examples:
+1. Time something, `per_run` is how many things one run does (hands, widgets, ...):
+    #[test]
+    #[ignore]
+    fn bench_shuffle() {
+        bench::run("shoe shuffles", 1, || Shoe::new(6, 42));
+    }
+*/
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const WARM_UP: usize = 10;
+const MEASURE_FOR: Duration = Duration::from_secs(2);
+
+// Run `work` over and over, print and return how many `per_run` things a second it did
+pub fn run<T>(name: &str, per_run: usize, mut work: impl FnMut() -> T) -> f64 {
+    for _ in 0..WARM_UP {
+        black_box(work());
+    }
+    let start = Instant::now();
+    let mut runs = 0;
+    while start.elapsed() < MEASURE_FOR {
+        black_box(work());
+        runs += 1;
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    let rate = (runs * per_run) as f64 / elapsed;
+    println!("{}: {:.0}/s ({:.2} us per run, {} runs)", name, rate, elapsed * 1_000_000.0 / runs as f64, runs);
+    rate
+}
@@ -0,0 +1,209 @@
+/*
+By: <tyler>
+Date: 2025-12-02
+Program Details: The player's wagers on the bet spots
+
+Each player hand has its own spot. Chips go on the spots between hands, and all
+of them together can't be more than the bankroll. When the round settles every
+bet is paid (1 to 1, or what the table pays a blackjack) or lost, the spots empty and the
+amounts are remembered so the same wagers can be put back with rebet().
+A bet of 0 is allowed, the hand is just played for fun.
+The last RECENT_BETS different amounts bet on a hand are kept too, newest first,
+so the table can offer them as one-click bets. They are saved with the profile.
+The table also offers PERCENT_BETS: a bet of 1%, 2% or 5% of the bankroll,
+rounded down to chips (see percent_bet), worked out again every round.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod events;
+    pub mod hand;
+    pub mod metrics;
+    pub mod chip;
+    pub mod rules;
+    pub mod bet;
+
+Then add the following with the use commands:
+use crate::modules::bet::Bet;
+
+Usage examples:
+1. Create it before the loop:
+    let mut bet = Bet::new();
+
+2. Between hands, change the bets (the bankroll caps the total):
+    bet.add(hand, BET_STEP, profile.info.bankroll);
+    bet.remove(hand, BET_STEP);
+    bet.rebet(hands, profile.info.bankroll);
+    bet.clear(hand);
+
+3. When the round is over, settle every hand at once and move the money:
+    profile.info.bankroll += bet.settle(&[(outcome, player_hand.is_blackjack())], rules.blackjack_pays);
+
+4. Offer the recent amounts, and keep them with the profile:
+    bet.set_recent(&profile.info.recent_bets);
+    for amount in bet.recent() {
+        // button for $amount calls bet.bet_each(hands, *amount, profile.info.bankroll)
+    }
+    profile.info.recent_bets = bet.recent().to_vec();
+
+5. Offer bets sized to the bankroll:
+    for percent in PERCENT_BETS {
+        let amount = percent_bet(profile.info.bankroll, percent);
+        // button for "2%" calls bet.bet_each(hands, amount, profile.info.bankroll)
+    }
+
+6. What one hand's bet comes to, before it is settled:
+    let change = payout(bet.amount(hand), outcome, blackjack, rules.blackjack_pays);
+*/
+use crate::modules::chip::DENOMINATIONS;
+use crate::modules::events::Outcome;
+use crate::modules::hand::MAX_HANDS;
+use crate::modules::metrics::{self, Counter};
+use crate::modules::rules::Payout;
+
+pub const RECENT_BETS: usize = 5;
+pub const PERCENT_BETS: [i64; 3] = [1, 2, 5]; // Bets offered as a percentage of the bankroll
+
+pub struct Bet {
+    amounts: [i64; MAX_HANDS],
+    last: [i64; MAX_HANDS],
+    recent: Vec<i64>, // Different amounts bet on a hand, newest first
+}
+
+impl Bet {
+    pub fn new() -> Self {
+        Self { amounts: [0; MAX_HANDS], last: [0; MAX_HANDS], recent: Vec::new() }
+    }
+
+    pub fn recent(&self) -> &[i64] {
+        &self.recent
+    }
+
+    // Start from saved recent amounts (a profile's), newest first
+    pub fn set_recent(&mut self, recent: &[i64]) {
+        self.recent.clear();
+        for amount in recent.iter().rev() {
+            self.remember(*amount);
+        }
+    }
+
+    pub fn amount(&self, hand: usize) -> i64 {
+        self.amounts.get(hand).copied().unwrap_or(0)
+    }
+
+    // Everything on the spots right now
+    pub fn total(&self) -> i64 {
+        self.amounts.iter().sum()
+    }
+
+    // The wagers from the last settled round on the first `hands` spots, 0 if there weren't any
+    pub fn last_total(&self, hands: usize) -> i64 {
+        self.last.iter().take(hands).sum()
+    }
+
+    // Put more on a hand's spot, returns false if the bankroll can't cover it
+    pub fn add(&mut self, hand: usize, chips: i64, bankroll: i64) -> bool {
+        if hand >= MAX_HANDS || self.total() + chips > bankroll {
+            return false;
+        }
+        self.amounts[hand] += chips;
+        true
+    }
+
+    // Take chips back off a hand's spot, returns false if there aren't that many on it
+    pub fn remove(&mut self, hand: usize, chips: i64) -> bool {
+        match self.amounts.get_mut(hand) {
+            Some(amount) if *amount >= chips => {
+                *amount -= chips;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Put the last round's wagers back on the first `hands` spots,
+    // returns false if there were none or they can't be covered
+    pub fn rebet(&mut self, hands: usize, bankroll: i64) -> bool {
+        let total = self.last_total(hands);
+        if total == 0 || total > bankroll {
+            return false;
+        }
+        self.amounts = [0; MAX_HANDS];
+        for hand in 0..hands.min(MAX_HANDS) {
+            self.amounts[hand] = self.last[hand];
+        }
+        true
+    }
+
+    // Put `amount` on each of the first `hands` spots in place of what is there,
+    // returns false if the bankroll can't cover all of them
+    pub fn bet_each(&mut self, hands: usize, amount: i64, bankroll: i64) -> bool {
+        let hands = hands.min(MAX_HANDS);
+        if amount * hands as i64 > bankroll {
+            return false;
+        }
+        self.amounts = [0; MAX_HANDS];
+        for hand in 0..hands {
+            self.amounts[hand] = amount;
+        }
+        true
+    }
+
+    pub fn clear(&mut self, hand: usize) {
+        if let Some(amount) = self.amounts.get_mut(hand) {
+            *amount = 0;
+        }
+    }
+
+    // Take the bets off the spots from `hands` on, for when fewer hands will be played
+    pub fn keep_hands(&mut self, hands: usize) {
+        for amount in self.amounts.iter_mut().skip(hands) {
+            *amount = 0;
+        }
+    }
+
+    // Pay or take every hand's bet, returns how much the bankroll goes up (or down) by.
+    // Each result is the hand's outcome and whether a win pays `blackjack_pays` instead of 1 to 1.
+    pub fn settle(&mut self, results: &[(Outcome, bool)], blackjack_pays: Payout) -> i64 {
+        metrics::count(Counter::RoundsPlayed, 1);
+        metrics::count(Counter::HandsPlayed, results.len() as u64);
+        self.last = [0; MAX_HANDS];
+        let mut change = 0;
+        for (hand, (outcome, blackjack)) in results.iter().enumerate().take(MAX_HANDS) {
+            let amount = std::mem::take(&mut self.amounts[hand]);
+            self.last[hand] = amount;
+            self.remember(amount);
+            change += payout(amount, *outcome, *blackjack, blackjack_pays);
+        }
+        change
+    }
+
+    // Move an amount to the front of the recent list, dropping the oldest past RECENT_BETS
+    fn remember(&mut self, amount: i64) {
+        if amount <= 0 {
+            return;
+        }
+        self.recent.retain(|other| *other != amount);
+        self.recent.insert(0, amount);
+        self.recent.truncate(RECENT_BETS);
+    }
+}
+
+// `percent` of the bankroll as a bet that is easy to stack: rounded down to a multiple of the
+// biggest chip worth no more than half of it, so it never comes to more than the percentage.
+// At least the smallest chip.
+pub fn percent_bet(bankroll: i64, percent: i64) -> i64 {
+    let exact = bankroll.max(0) * percent;
+    let chip = DENOMINATIONS.iter().rev().find(|chip| **chip * 100 * 2 <= exact).copied().unwrap_or(DENOMINATIONS[0]);
+    (exact / 100 / chip * chip).max(DENOMINATIONS[0])
+}
+
+// What a hand's bet of `amount` wins (or loses, below zero) for its result
+pub fn payout(amount: i64, outcome: Outcome, blackjack: bool, blackjack_pays: Payout) -> i64 {
+    match outcome {
+        Outcome::PlayerWin if blackjack => blackjack_pays.pay(amount),
+        Outcome::PlayerWin => amount,
+        Outcome::Push => 0,
+        Outcome::DealerWin | Outcome::NoWinner => -amount,
+    }
+}
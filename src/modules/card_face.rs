@@ -0,0 +1,245 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Card faces drawn in code, for the classic card style and for missing pictures
+
+A face is a rounded white card with the rank and a small suit in the top left
+corner, the same upside down in the bottom right, and a big suit in the middle
+(picture cards get a gold frame with their letter in it). The suits are built
+from circles and triangles, so nothing but the default font is needed.
+
+Faces are drawn once into a texture each (FACE_SIZE, about twice the size a card
+is shown at) and put into the TextureManager, after that they are used exactly
+like the card pictures:
+- fill_missing_faces() puts a drawn face wherever a card picture (or the empty
+  slot picture) couldn't be loaded, so the game still plays without any PNGs
+- make_classic_faces() draws the classic style's faces under their own paths
+  (Card::classic_path), cards::set_classic_faces(true) makes the game use them
+
+Faces can also be drawn straight to the screen (the theme preview, small cards
+in lists). Text is the slow part of a face, so each corner (rank and small
+suit) is drawn into a tiny texture of its own the first time a card is drawn
+at a size, and that texture is put down after that, turned round for the
+bottom right corner. Corners are kept for the rest of the game, one per card
+and size.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod cards;
+    pub mod shapes;
+    pub mod preload_image;
+    pub mod card_face;
+
+Then add the following with the use commands:
+use crate::modules::card_face;
+
+Usage examples:
+1. After the cards are preloaded, cover for any that didn't load:
+    let drawn = card_face::fill_missing_faces(&tm);
+
+2. Switch to the classic style:
+    card_face::make_classic_faces(&tm);
+    cards::set_classic_faces(true);
+
+3. Draw a face straight to the screen:
+    card_face::draw_card_face(&card, Rect::new(100.0, 100.0, 110.0, 160.0));
+*/
+use macroquad::prelude::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::f32::consts::PI;
+use crate::modules::cards::{Card, Rank, Suit, EMPTY_CARD_PATH};
+use crate::modules::preload_image::TextureManager;
+use crate::modules::shapes::ShapeBatch;
+use crate::modules::text_cache::measure_text_cached;
+use crate::{log_info, log_warn};
+
+pub const FACE_SIZE: Vec2 = vec2(220.0, 320.0);
+const RED_SUIT: Color = Color::new(0.78, 0.08, 0.1, 1.0);
+const EDGE_COLOR: Color = Color::new(0.6, 0.6, 0.6, 1.0);
+const FRAME_COLOR: Color = Color::new(0.85, 0.65, 0.1, 1.0);
+const CORNER: Vec2 = vec2(0.26, 0.3); // The corner block, as a share of the card's width and height
+const CORNER_SCALE: f32 = 2.0;        // Corners are drawn at twice the size they're shown, to stay sharp
+
+thread_local! {
+    // Every corner drawn so far, by card and the size of the face it was drawn for
+    static CORNERS: RefCell<HashMap<(Card, u32, u32), Texture2D>> = RefCell::new(HashMap::new());
+}
+
+// Draw the drawn face for every card whose picture isn't loaded, returns how many
+pub fn fill_missing_faces(tm: &TextureManager) -> usize {
+    let missing: Vec<Card> = Card::deck().into_iter().filter(|card| tm.get_texture(&card.picture_path()).is_none()).collect();
+    for card in &missing {
+        tm.insert_texture(&card.picture_path(), render(|rect| draw_card_face(card, rect)));
+    }
+    let mut drawn = missing.len();
+    if tm.get_texture(EMPTY_CARD_PATH).is_none() {
+        tm.insert_texture(EMPTY_CARD_PATH, render(draw_empty_slot));
+        drawn += 1;
+    }
+    if drawn > 0 {
+        log_warn!("{} card pictures missing, drawing them instead", drawn);
+    }
+    drawn
+}
+
+// Draw the classic style's faces, once. They stay in the TextureManager after that.
+pub fn make_classic_faces(tm: &TextureManager) {
+    let deck = Card::deck();
+    if deck.iter().all(|card| tm.get_texture(&card.classic_path()).is_some()) {
+        return;
+    }
+    for card in &deck {
+        tm.insert_texture(&card.classic_path(), render(|rect| draw_card_face(card, rect)));
+    }
+    log_info!("drew {} classic card faces", deck.len());
+}
+
+// A card's face filling `rect`
+pub fn draw_card_face(card: &Card, rect: Rect) {
+    let color = suit_color(card.suit);
+    let mut shapes = ShapeBatch::new();
+    shapes.fill(rect, rect.w * 0.08, EDGE_COLOR);
+    let inset = (rect.w * 0.01).max(1.0);
+    let inner = Rect::new(rect.x + inset, rect.y + inset, rect.w - inset * 2.0, rect.h - inset * 2.0);
+    shapes.fill(inner, rect.w * 0.08 - inset, WHITE);
+    let picture = matches!(card.rank, Rank::Jack | Rank::Queen | Rank::King);
+    if picture {
+        let frame = Rect::new(rect.x + rect.w * 0.22, rect.y + rect.h * 0.18, rect.w * 0.56, rect.h * 0.64);
+        shapes.outline(frame, rect.w * 0.04, (rect.w * 0.02).max(1.0), FRAME_COLOR);
+    }
+    shapes.draw();
+
+    // Rank and suit in the top left, and the same turned round the middle in the bottom right
+    let corner = corner_texture(card, rect.size());
+    let block = rect.size() * CORNER;
+    let params = |rotation: f32| DrawTextureParams { dest_size: Some(block), rotation, ..Default::default() };
+    draw_texture_ex(&corner, rect.x, rect.y, WHITE, params(0.0));
+    draw_texture_ex(&corner, rect.x + rect.w - block.x, rect.y + rect.h - block.y, WHITE, params(PI));
+
+    let text = rank_text(card.rank);
+    let center = rect.center();
+    if picture {
+        let letter_size = (rect.h * 0.3) as u16;
+        let letter = measure_text_cached(text, None, letter_size);
+        draw_text(text, center.x - letter.width / 2.0, center.y - rect.h * 0.02, letter_size as f32, color);
+        draw_suit(card.suit, center + vec2(0.0, rect.h * 0.16), rect.w * 0.2, color, false);
+    } else {
+        draw_suit(card.suit, center, rect.w * 0.42, color, false);
+    }
+}
+
+// The rank and small suit in the top left corner of a `face` sized card whose corner is at `at`
+fn draw_corner(card: &Card, face: Vec2, at: Vec2) {
+    let color = suit_color(card.suit);
+    let text = rank_text(card.rank);
+    let font_size = (face.y * 0.16) as u16;
+    let text_width = measure_text_cached(text, None, font_size).width;
+    draw_text(text, at.x + face.x * 0.13 - text_width / 2.0, at.y + face.y * 0.15, font_size as f32, color);
+    draw_suit(card.suit, at + vec2(face.x * 0.13, face.y * 0.23), face.x * 0.13, color, false);
+}
+
+// A card's corner for a face of `face` size, drawn into its texture the first time it is asked for
+fn corner_texture(card: &Card, face: Vec2) -> Texture2D {
+    let key = (*card, face.x.round() as u32, face.y.round() as u32);
+    if let Some(texture) = CORNERS.with(|corners| corners.borrow().get(&key).cloned()) {
+        return texture;
+    }
+    let size = (face * CORNER * CORNER_SCALE).max(Vec2::ONE);
+    let texture = render_sized(size, |rect| draw_corner(card, face * CORNER_SCALE, rect.point()));
+    CORNERS.with(|corners| corners.borrow_mut().insert(key, texture.clone()));
+    texture
+}
+
+// Where a card goes, when there isn't one: a faint rounded outline
+fn draw_empty_slot(rect: Rect) {
+    let mut shapes = ShapeBatch::new();
+    shapes.outline(rect, rect.w * 0.08, (rect.w * 0.02).max(1.0), Color::new(1.0, 1.0, 1.0, 0.5));
+    shapes.draw();
+}
+
+// A suit `size` tall around `center`, upside down for the bottom corner
+pub fn draw_suit(suit: Suit, center: Vec2, size: f32, color: Color, upside_down: bool) {
+    let flip = if upside_down { -1.0 } else { 1.0 };
+    let at = |x: f32, y: f32| center + vec2(x * size, y * size * flip);
+    let mut shapes = ShapeBatch::new();
+    let mut triangles: Vec<[Vec2; 3]> = Vec::new();
+    match suit {
+        Suit::Diamonds => {
+            triangles.push([at(0.0, -0.5), at(0.36, 0.0), at(-0.36, 0.0)]);
+            triangles.push([at(0.0, 0.5), at(-0.36, 0.0), at(0.36, 0.0)]);
+        }
+        Suit::Hearts => {
+            shapes.circle(at(-0.22, -0.2), size * 0.25, color);
+            shapes.circle(at(0.22, -0.2), size * 0.25, color);
+            triangles.push([at(-0.46, -0.12), at(0.46, -0.12), at(0.0, 0.5)]);
+        }
+        Suit::Spades => {
+            shapes.circle(at(-0.22, 0.08), size * 0.25, color);
+            shapes.circle(at(0.22, 0.08), size * 0.25, color);
+            triangles.push([at(-0.46, 0.0), at(0.46, 0.0), at(0.0, -0.5)]);
+            triangles.push([at(0.0, 0.1), at(-0.16, 0.5), at(0.16, 0.5)]);
+        }
+        Suit::Clubs => {
+            shapes.circle(at(0.0, -0.24), size * 0.22, color);
+            shapes.circle(at(-0.24, 0.08), size * 0.22, color);
+            shapes.circle(at(0.24, 0.08), size * 0.22, color);
+            shapes.circle(at(0.0, 0.0), size * 0.12, color);
+            triangles.push([at(0.0, 0.0), at(-0.16, 0.5), at(0.16, 0.5)]);
+        }
+    }
+    shapes.draw();
+    for [a, b, c] in triangles {
+        draw_triangle(a, b, c, color);
+    }
+}
+
+fn suit_color(suit: Suit) -> Color {
+    match suit {
+        Suit::Hearts | Suit::Diamonds => RED_SUIT,
+        Suit::Clubs | Suit::Spades => BLACK,
+    }
+}
+
+// What goes in the corner, "10" rather than the T of Rank::letter
+fn rank_text(rank: Rank) -> &'static str {
+    match rank {
+        Rank::Two => "2",
+        Rank::Three => "3",
+        Rank::Four => "4",
+        Rank::Five => "5",
+        Rank::Six => "6",
+        Rank::Seven => "7",
+        Rank::Eight => "8",
+        Rank::Nine => "9",
+        Rank::Ten => "10",
+        Rank::Jack => "J",
+        Rank::Queen => "Q",
+        Rank::King => "K",
+        Rank::Ace => "A",
+    }
+}
+
+// Draw into a new FACE_SIZE texture, leaving whatever camera was in use as it was
+fn render(draw: impl FnOnce(Rect)) -> Texture2D {
+    render_sized(FACE_SIZE, draw)
+}
+
+// Draw into a new texture `size` pixels big, leaving whatever camera was in use as it was
+fn render_sized(size: Vec2, draw: impl FnOnce(Rect)) -> Texture2D {
+    let target = render_target(size.x as u32, size.y as u32);
+    target.texture.set_filter(FilterMode::Linear);
+    push_camera_state();
+    // y grows downwards like the screen, a render target is stored bottom row first
+    set_camera(&Camera2D {
+        zoom: vec2(2.0 / size.x, 2.0 / size.y),
+        target: size / 2.0,
+        render_target: Some(target.clone()),
+        ..Default::default()
+    });
+    clear_background(Color::new(0.0, 0.0, 0.0, 0.0));
+    draw(Rect::new(0.0, 0.0, size.x, size.y));
+    pop_camera_state();
+    target.texture
+}
@@ -0,0 +1,123 @@
+/*
+By: <tyler>
+Date: 2025-12-08
+Program Details: A pool of card sprites that are reused instead of made again
+
+Every card slot on the table is a StillImage. Instead of making new ones when a
+hand is added or the table is set up again, the slots are taken from a
+CardSpritePool and handed back to it when they aren't needed, so the same few
+sprites go round from round to round and hand to hand. Sprites are made from the
+empty card texture the TextureManager already has, nothing is loaded from disk.
+
+A sprite handed back is turned back into an empty card. Showing a card in a
+sprite with show() only swaps the shared texture handle (see TextureEntry in
+preload_image.rs), so dealing doesn't make or copy anything either.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod still_image;
+    pub mod preload_image;
+    pub mod card_pool;
+
+Then add the following with the use commands:
+use crate::modules::card_pool::CardSpritePool;
+
+Usage examples:
+1. Create it once the cards are preloaded:
+    let mut pool = CardSpritePool::new(&tm);
+
+2. Take the slots for a hand, and give them back when the hand goes:
+    let slots = pool.take_row(5);
+    pool.give_back_all(slots);
+
+3. Show a card in a slot, and empty it again:
+    pool.show(&tm, &mut slots[0], &card);
+    pool.clear(&mut slots[0]);
+
+4. See how well it is reusing:
+    println!("{} made, {} reused, {} free", pool.created(), pool.reused(), pool.free());
+*/
+use macroquad::prelude::*;
+use std::sync::Arc;
+use crate::modules::cards::{Card, EMPTY_CARD_PATH};
+use crate::modules::preload_image::{TextureEntry, TextureManager};
+use crate::modules::still_image::{StillImage, TransparencyMask};
+
+// Size new sprites start at, the layout sizes them when they are placed
+const START_SIZE: Vec2 = vec2(110.0, 160.0);
+
+pub struct CardSpritePool {
+    empty: Arc<TextureEntry>,
+    free: Vec<StillImage>,
+    created: usize,
+    reused: usize,
+}
+
+impl CardSpritePool {
+    // The empty card picture has to be preloaded already
+    pub fn new(tm: &TextureManager) -> Self {
+        let empty = tm.get_preload(EMPTY_CARD_PATH).unwrap_or_else(|| {
+            TextureEntry::new(Texture2D::from_rgba8(1, 1, &[0, 0, 0, 0]), TransparencyMask::none(), EMPTY_CARD_PATH)
+        });
+        Self { empty, free: Vec::new(), created: 0, reused: 0 }
+    }
+
+    // An empty card sprite, one handed back before if there is one
+    pub fn take(&mut self) -> StillImage {
+        if let Some(sprite) = self.free.pop() {
+            self.reused += 1;
+            return sprite;
+        }
+        self.created += 1;
+        StillImage::from_preload(self.empty.clone(), START_SIZE.x, START_SIZE.y)
+    }
+
+    // `count` sprites for a row of slots
+    pub fn take_row(&mut self, count: usize) -> Vec<StillImage> {
+        (0..count).map(|_| self.take()).collect()
+    }
+
+    // Keep a sprite for next time, emptied
+    pub fn give_back(&mut self, mut sprite: StillImage) {
+        self.clear(&mut sprite);
+        self.free.push(sprite);
+    }
+
+    pub fn give_back_all(&mut self, sprites: impl IntoIterator<Item = StillImage>) {
+        for sprite in sprites {
+            self.give_back(sprite);
+        }
+    }
+
+    // Show the empty card picture in a slot
+    pub fn clear(&self, sprite: &mut StillImage) {
+        sprite.set_preload(self.empty.clone());
+    }
+
+    // Show a card's face in a slot, empty if its picture isn't loaded
+    pub fn show(&self, tm: &TextureManager, sprite: &mut StillImage, card: &Card) {
+        match tm.get_preload(&card.texture_path()) {
+            Some(picture) => sprite.set_preload(picture),
+            None => self.clear(sprite),
+        }
+    }
+
+    // Sprites made since the pool was created
+    #[allow(unused)]
+    pub fn created(&self) -> usize {
+        self.created
+    }
+
+    // Times a handed back sprite was used again
+    #[allow(unused)]
+    pub fn reused(&self) -> usize {
+        self.reused
+    }
+
+    // Sprites waiting to be taken
+    #[allow(unused)]
+    pub fn free(&self) -> usize {
+        self.free.len()
+    }
+}
@@ -0,0 +1,177 @@
+/*
+By: <tyler>
+Date: 2025-12-07
+Program Details: A card that can be turned over, drawn back or front
+
+A CardView knows which way up a card is and turns it over with a flip: the side
+that is showing gets narrower until it is edge on, then the other side grows
+back to full width, like a card turned over on the table. The flip is a tween
+from 0 (back showing) to 1 (front showing), the first half draws the back and
+the second half the front. With reduced motion on it turns over at once.
+The back is the maroon design used for the shoe and the hole card.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod animation;
+    pub mod layers;
+    pub mod card_view;
+
+Then add the following with the use commands:
+use crate::modules::card_view::CardView;
+
+Usage examples:
+1. A face down card that will show the ace of spades:
+    let mut card = CardView::new(false);
+    card.set_front(texture_manager.get_texture(&ace.texture_path()));
+
+2. Turn it over, and back again:
+    card.flip_up();
+    card.flip_down();
+
+3. Every frame, move the flip along and draw it into a layer:
+    card.update(get_frame_time());
+    card.draw(vec2(400.0, 200.0), vec2(90.0, 130.0), Layer::Table);
+
+4. Draw any flip progress straight away, 0.0 is the back and 1.0 the front:
+    draw_flipped(Some(&texture), 0.25, vec2(400.0, 200.0), vec2(90.0, 130.0));
+
+5. Draw a face down card with its bottom edge lifted, showing a third of the face (see squeeze.rs):
+    draw_peeled(Some(&texture), 0.33, vec2(400.0, 200.0), vec2(90.0, 130.0));
+*/
+use macroquad::prelude::*;
+use crate::modules::animation::{Easing, Tween};
+use crate::modules::layers::{self, Layer};
+
+const FLIP_TIME: f32 = 0.4;
+
+pub struct CardView {
+    front: Option<Texture2D>,
+    flip: Tween,
+}
+
+impl CardView {
+    pub fn new(face_up: bool) -> Self {
+        let side = if face_up { 1.0 } else { 0.0 };
+        Self { front: None, flip: Tween::new(side, side, 0.0, Easing::Linear) }
+    }
+
+    // The picture on the face, None draws a plain white face
+    pub fn set_front(&mut self, front: Option<Texture2D>) {
+        self.front = front;
+    }
+
+    // Turn over to the front, from wherever the card is now
+    pub fn flip_up(&mut self) {
+        self.flip_to(1.0);
+    }
+
+    #[allow(unused)]
+    pub fn flip_down(&mut self) {
+        self.flip_to(0.0);
+    }
+
+    // Show a side straight away, stopping any flip
+    pub fn set_face_up(&mut self, face_up: bool) {
+        let side = if face_up { 1.0 } else { 0.0 };
+        self.flip = Tween::new(side, side, 0.0, Easing::Linear);
+    }
+
+    pub fn is_flipping(&self) -> bool {
+        !self.flip.is_finished()
+    }
+
+    #[allow(unused)]
+    pub fn is_face_up(&self) -> bool {
+        self.flip.value() >= 1.0
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.flip.update(dt);
+    }
+
+    // Queue the card into a layer at its flip so far
+    pub fn draw(&self, pos: Vec2, size: Vec2, layer: Layer) {
+        let front = self.front.clone();
+        let progress = self.flip.value();
+        layers::push(layer, move || draw_flipped(front.as_ref(), progress, pos, size));
+    }
+
+    // A flip part way through goes back the way it came, taking only as long as the part left
+    fn flip_to(&mut self, side: f32) {
+        let from = self.flip.value();
+        self.flip = Tween::new(from, side, FLIP_TIME * (side - from).abs(), Easing::EaseInOutQuad);
+    }
+}
+
+// Draw a card turned `progress` of the way from its back (0.0) to its front (1.0).
+// The card stays centred on its spot while it narrows and widens.
+pub fn draw_flipped(front: Option<&Texture2D>, progress: f32, pos: Vec2, size: Vec2) {
+    let progress = progress.clamp(0.0, 1.0);
+    let width = size.x * (progress * 2.0 - 1.0).abs();
+    if width <= 0.0 {
+        return;
+    }
+    let x = pos.x + (size.x - width) / 2.0;
+    if progress < 0.5 {
+        draw_card_back(x, pos.y, vec2(width, size.y));
+        return;
+    }
+    match front {
+        Some(texture) => {
+            let params = DrawTextureParams { dest_size: Some(vec2(width, size.y)), ..Default::default() };
+            draw_texture_ex(texture, x, pos.y, WHITE, params);
+        }
+        None => {
+            draw_rectangle(x, pos.y, width, size.y, WHITE);
+            draw_rectangle_lines(x, pos.y, width, size.y, 2.0, BLACK);
+        }
+    }
+}
+
+// Draw a face down card peeled up from its bottom edge, `peel` of the way (0.0 - 1.0).
+// The face shows below the fold, the lifted part curls over the back above it.
+pub fn draw_peeled(front: Option<&Texture2D>, peel: f32, pos: Vec2, size: Vec2) {
+    let peel = peel.clamp(0.0, 1.0);
+    if peel <= 0.0 {
+        draw_card_back(pos.x, pos.y, size);
+        return;
+    }
+    let fold_y = pos.y + size.y * (1.0 - peel);
+    let shown = size.y * peel;
+    match front {
+        Some(texture) => {
+            let source = Rect::new(0.0, texture.height() * (1.0 - peel), texture.width(), texture.height() * peel);
+            let params = DrawTextureParams { dest_size: Some(vec2(size.x, shown)), source: Some(source), ..Default::default() };
+            draw_texture_ex(texture, pos.x, fold_y, WHITE, params);
+        }
+        None => draw_rectangle(pos.x, fold_y, size.x, shown, WHITE),
+    }
+    draw_rectangle_lines(pos.x, pos.y, size.x, size.y, 2.0, BLACK);
+    if peel >= 1.0 {
+        return;
+    }
+    draw_card_back(pos.x, pos.y, vec2(size.x, size.y - shown));
+    // The curl: the lifted part seen from underneath, with a shadow along the fold
+    let curl = (shown * 0.5).min(size.y * 0.12);
+    draw_rectangle(pos.x, fold_y - curl, size.x, curl, Color::new(0.93, 0.93, 0.9, 1.0));
+    draw_rectangle_lines(pos.x, fold_y - curl, size.x, curl, 1.0, GRAY);
+    draw_rectangle(pos.x, fold_y, size.x, 3.0_f32.min(shown), Color::new(0.0, 0.0, 0.0, 0.35));
+}
+
+// Maroon back with a white border and a diamond in the middle, same color as the shoe
+pub fn draw_card_back(x: f32, y: f32, size: Vec2) {
+    draw_rectangle(x, y, size.x, size.y, MAROON);
+    // The pattern is left off once the card is too narrow to fit it
+    if size.x > 12.0 {
+        draw_rectangle_lines(x + 6.0, y + 6.0, size.x - 12.0, size.y - 12.0, 2.0, WHITE);
+        let center = vec2(x + size.x / 2.0, y + size.y / 2.0);
+        let (half_w, half_h) = (size.x * 0.25, size.y * 0.25);
+        let points = [center - vec2(0.0, half_h), center + vec2(half_w, 0.0), center + vec2(0.0, half_h), center - vec2(half_w, 0.0)];
+        for i in 0..points.len() {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            draw_line(a.x, a.y, b.x, b.y, 2.0, WHITE);
+        }
+    }
+    draw_rectangle_lines(x, y, size.x, size.y, 2.0, BLACK);
+}
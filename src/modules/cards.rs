@@ -0,0 +1,258 @@
+/*
+By: <tyler>
+Date: 2025-11-20
+Program Details: Playing card model (rank, suit, value and texture path)
+
+With the classic card style on (set_classic_faces) texture_path() gives the
+face drawn in code instead of the picture, see card_face.rs.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod cards;
+
+Then add the following with the use commands:
+use crate::modules::cards::{Card, Rank, Suit};
+
+Usage examples:
+1. Make a card and read its blackjack value:
+    let card = Card::new(Rank::Ace, Suit::Spades);
+    let points = card.value(); // 11, the hand decides when it counts as 1
+
+2. Get the picture for a card (works with the TextureManager):
+    img.set_preload(tm.get_preload(&card.texture_path()).unwrap());
+
+3. Get a short code for logs, e.g. "TD" for the ten of diamonds:
+    let code = card.code();
+
+4. List every card picture, handy for preloading:
+    let paths = Card::all_texture_paths();
+
+5. Use the drawn classic faces (card_face::make_classic_faces draws them first):
+    cards::set_classic_faces(true);
+
+6. Read a card typed in by hand, "A♠", "as", "10H" and "TH" all work:
+    let card = Card::parse("K♦").unwrap();
+*/
+use std::cell::Cell;
+
+thread_local! {
+    static CLASSIC_FACES: Cell<bool> = const { Cell::new(false) };
+}
+
+// Show the faces drawn in code instead of the card pictures
+pub fn set_classic_faces(on: bool) {
+    CLASSIC_FACES.with(|cell| cell.set(on));
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rank {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+pub const SUITS: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+pub const RANKS: [Rank; 13] = [
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+    Rank::Ace,
+];
+
+// Picture shown where there is no card
+pub const EMPTY_CARD_PATH: &str = "assets/Empty.png";
+
+impl Suit {
+    // One letter code, e.g. S for spades
+    pub fn letter(&self) -> char {
+        match self {
+            Suit::Clubs => 'C',
+            Suit::Diamonds => 'D',
+            Suit::Hearts => 'H',
+            Suit::Spades => 'S',
+        }
+    }
+
+    // From its letter or symbol, either case
+    pub fn from_char(ch: char) -> Option<Suit> {
+        match ch.to_ascii_uppercase() {
+            'C' | '♣' | '♧' => Some(Suit::Clubs),
+            'D' | '♦' | '♢' => Some(Suit::Diamonds),
+            'H' | '♥' | '♡' => Some(Suit::Hearts),
+            'S' | '♠' | '♤' => Some(Suit::Spades),
+            _ => None,
+        }
+    }
+
+    // Name used in the asset file names
+    pub fn name(&self) -> &'static str {
+        match self {
+            Suit::Clubs => "clubs",
+            Suit::Diamonds => "diamonds",
+            Suit::Hearts => "hearts",
+            Suit::Spades => "spades",
+        }
+    }
+}
+
+impl Rank {
+    // One letter code, e.g. T for ten and A for ace
+    pub fn letter(&self) -> char {
+        match self {
+            Rank::Two => '2',
+            Rank::Three => '3',
+            Rank::Four => '4',
+            Rank::Five => '5',
+            Rank::Six => '6',
+            Rank::Seven => '7',
+            Rank::Eight => '8',
+            Rank::Nine => '9',
+            Rank::Ten => 'T',
+            Rank::Jack => 'J',
+            Rank::Queen => 'Q',
+            Rank::King => 'K',
+            Rank::Ace => 'A',
+        }
+    }
+
+    // From its letter, either case, or "10" for a ten
+    pub fn from_code(code: &str) -> Option<Rank> {
+        if code == "10" {
+            return Some(Rank::Ten);
+        }
+        let mut chars = code.chars();
+        let letter = chars.next()?.to_ascii_uppercase();
+        if chars.next().is_some() {
+            return None;
+        }
+        RANKS.into_iter().find(|rank| rank.letter() == letter)
+    }
+
+    // Name used in the asset file names
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rank::Two => "Two",
+            Rank::Three => "Three",
+            Rank::Four => "Four",
+            Rank::Five => "Five",
+            Rank::Six => "Six",
+            Rank::Seven => "Seven",
+            Rank::Eight => "Eight",
+            Rank::Nine => "Nine",
+            Rank::Ten => "Ten",
+            Rank::Jack => "Jack",
+            Rank::Queen => "Queen",
+            Rank::King => "King",
+            Rank::Ace => "Ace",
+        }
+    }
+
+    // Blackjack points for the rank (aces count 11 here)
+    pub fn value(&self) -> u32 {
+        match self {
+            Rank::Two => 2,
+            Rank::Three => 3,
+            Rank::Four => 4,
+            Rank::Five => 5,
+            Rank::Six => 6,
+            Rank::Seven => 7,
+            Rank::Eight => 8,
+            Rank::Nine => 9,
+            Rank::Ten | Rank::Jack | Rank::Queen | Rank::King => 10,
+            Rank::Ace => 11,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Card {
+    pub rank: Rank,
+    pub suit: Suit,
+}
+
+impl Card {
+    pub fn new(rank: Rank, suit: Suit) -> Self {
+        Self { rank, suit }
+    }
+
+    // Blackjack points for the card (aces count 11 here)
+    pub fn value(&self) -> u32 {
+        self.rank.value()
+    }
+
+    // Short two letter code, e.g. AS for the ace of spades
+    pub fn code(&self) -> String {
+        format!("{}{}", self.rank.letter(), self.suit.letter())
+    }
+
+    // A rank followed by a suit, e.g. "AS", "a♠" or "10h". None if it isn't a card.
+    #[allow(unused)]
+    pub fn parse(text: &str) -> Option<Card> {
+        let text = text.trim();
+        let suit_char = text.chars().last()?;
+        let rank_code = &text[..text.len() - suit_char.len_utf8()];
+        Some(Card::new(Rank::from_code(rank_code)?, Suit::from_char(suit_char)?))
+    }
+
+    // Path the card's face is kept under in the TextureManager for the card style in use
+    pub fn texture_path(&self) -> String {
+        if CLASSIC_FACES.with(Cell::get) { self.classic_path() } else { self.picture_path() }
+    }
+
+    // Path of the card's picture, e.g. assets/Ace-of-spades.png
+    pub fn picture_path(&self) -> String {
+        format!("assets/{}-of-{}.png", self.rank.name(), self.suit.name())
+    }
+
+    // Made-up path the drawn classic face is kept under, e.g. classic/Ace-of-spades
+    pub fn classic_path(&self) -> String {
+        format!("classic/{}-of-{}", self.rank.name(), self.suit.name())
+    }
+
+    // Every card in a single 52 card deck
+    pub fn deck() -> Vec<Card> {
+        let mut deck = Vec::with_capacity(52);
+        for suit in SUITS {
+            for rank in RANKS {
+                deck.push(Card::new(rank, suit));
+            }
+        }
+        deck
+    }
+
+    // Every card picture plus the empty slot picture
+    pub fn all_texture_paths() -> Vec<String> {
+        let mut paths: Vec<String> = Card::deck().iter().map(|card| card.picture_path()).collect();
+        paths.push(EMPTY_CARD_PATH.to_string());
+        paths
+    }
+}
@@ -0,0 +1,70 @@
+/*
+By: <tyler>
+Date: 2025-12-03
+Program Details: Small chart drawing helpers
+
+Plain shape drawing for the HUD panels that show numbers as pictures:
+- bar_chart draws a row of labelled bars scaled to a maximum
+- heat_color turns -1.0 .. 1.0 into a blue (low) / white / red (high) color
+These draw straight away, so call them from inside a layers::push closure.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod chart;
+
+Then add the following with the use commands:
+use crate::modules::chart::{bar_chart, heat_color, Bar};
+
+Usage examples:
+1. Draw some bars into a rectangle, the tallest possible bar is 10:
+    let bars = vec![Bar::new("A", 4.0, heat_color(0.5)), Bar::new("K", 10.0, WHITE)];
+    layers::push(Layer::Hud, move || bar_chart(Rect::new(20.0, 20.0, 200.0, 80.0), &bars, 10.0));
+*/
+use macroquad::prelude::*;
+use crate::modules::text_cache::measure_text_cached;
+
+const LABEL_SIZE: f32 = 16.0;
+
+// One bar: the text under it, how tall it is and its color
+#[derive(Clone, Debug)]
+pub struct Bar {
+    pub label: String,
+    pub value: f32,
+    pub color: Color,
+}
+
+impl Bar {
+    pub fn new(label: impl Into<String>, value: f32, color: Color) -> Self {
+        Self { label: label.into(), value, color }
+    }
+}
+
+// Bars side by side across `area`, labels along the bottom. A bar of `max` fills the height.
+pub fn bar_chart(area: Rect, bars: &[Bar], max: f32) {
+    if bars.is_empty() {
+        return;
+    }
+    let slot = area.w / bars.len() as f32;
+    let bar_width = slot * 0.7;
+    let plot_height = area.h - LABEL_SIZE;
+    let baseline = area.y + plot_height;
+    for (i, bar) in bars.iter().enumerate() {
+        let x = area.x + slot * i as f32 + (slot - bar_width) / 2.0;
+        let height = if max > 0.0 { plot_height * (bar.value / max).clamp(0.0, 1.0) } else { 0.0 };
+        draw_rectangle(x, baseline - height, bar_width, height, bar.color);
+        draw_rectangle_lines(x, area.y, bar_width, plot_height, 1.0, Color::new(1.0, 1.0, 1.0, 0.2));
+        let label_width = measure_text_cached(&bar.label, None, LABEL_SIZE as u16).width;
+        draw_text(&bar.label, x + (bar_width - label_width) / 2.0, area.y + area.h - 2.0, LABEL_SIZE, WHITE);
+    }
+}
+
+// -1.0 is blue, 0.0 is white and 1.0 is red, anything past the ends is clamped
+pub fn heat_color(amount: f32) -> Color {
+    let amount = amount.clamp(-1.0, 1.0);
+    if amount >= 0.0 {
+        Color::new(1.0, 1.0 - amount, 1.0 - amount, 1.0)
+    } else {
+        Color::new(1.0 + amount, 1.0 + amount, 1.0, 1.0)
+    }
+}
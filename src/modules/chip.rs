@@ -0,0 +1,262 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Betting chips, drawn from a chip atlas, that can be clicked or dragged onto a spot
+
+The chips are one picture, assets/chips.png: a row of ATLAS_CELL square cells,
+one chip per cell in the order of DENOMINATIONS ($1, $5, $25, $100, $500).
+If it isn't loaded the chips are drawn as plain circles in the same colours.
+
+An InteractiveChip is a chip in the tray that the player bets with:
+    click                        - add its value to the bet
+    right click or long press    - take its value off the bet again
+    drag it onto a bet spot      - add its value to that spot's bet
+It only reports what happened (ChipAction), the caller decides which spot a
+click goes to and what the bankroll allows.
+
+draw_stacks() shows a bet as chips in its spot: the amount is broken into
+chips, biggest first, and piled into stacks of up to STACK_LIMIT, side by side
+when there are more (at most MAX_STACKS, the amount is written over them anyway).
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod layers;
+    pub mod shapes;
+    pub mod input;
+    pub mod cursor;
+    pub mod draw;
+    pub mod preload_image;
+    pub mod chip;
+
+Then add the following with the use commands:
+use crate::modules::chip::{ChipAction, ChipAtlas, InteractiveChip, DENOMINATIONS};
+
+Usage examples:
+1. Once the atlas is preloaded, make the atlas and a chip for each denomination:
+    let atlas = ChipAtlas::new(&tm);
+    let mut chips: Vec<InteractiveChip> = DENOMINATIONS.iter().map(|value| InteractiveChip::new(*value)).collect();
+
+2. Each frame, place them, and handle what the player did (this draws them too):
+    chips[0].set_position(vec2(50.0, 200.0), 24.0);
+    match chips[0].update(&atlas, 0.6) {
+        Some(ChipAction::Add) => bet.add(hand, chips[0].value(), bankroll),
+        Some(ChipAction::Remove) => bet.remove(hand, chips[0].value()),
+        Some(ChipAction::Dropped(pos)) => { /* add to the spot under pos, if there is one */ }
+        None => {}
+    }
+
+3. Show a bet of $135 in a spot:
+    draw_stacks(&atlas, vec2(400.0, 460.0), 26.0, 135);
+*/
+use macroquad::prelude::*;
+use crate::modules::cursor::{self, CursorKind};
+use crate::modules::draw::draw_smooth_circle;
+use crate::modules::input::{is_mouse_button_pressed, pointer};
+use crate::modules::layers::{self, Layer};
+use crate::modules::preload_image::TextureManager;
+use crate::modules::shapes::ShapeBatch;
+use crate::modules::text_cache::measure_text_cached;
+use crate::modules::i18n;
+
+pub const CHIP_ATLAS_PATH: &str = "assets/chips.png";
+pub const DENOMINATIONS: [i64; 5] = [1, 5, 25, 100, 500];
+const ATLAS_CELL: f32 = 128.0;
+const ATLAS_CHIP_RADIUS: f32 = 60.0; // The chip's radius inside its cell
+const STACK_LIMIT: usize = 10;
+const MAX_STACKS: usize = 4;
+const STACK_STEP: f32 = 0.16; // How far up each chip in a stack sits, as a fraction of its radius
+const DRAG_DISTANCE: f32 = 12.0; // How far a press moves before it is a drag instead of a click
+
+// The chips' colours, for when the atlas isn't loaded: body and edge stripes
+const FALLBACK_COLORS: [(Color, Color); 5] = [
+    (Color::new(0.92, 0.92, 0.9, 1.0), Color::new(0.16, 0.31, 0.67, 1.0)),
+    (Color::new(0.75, 0.12, 0.14, 1.0), WHITE),
+    (Color::new(0.1, 0.51, 0.24, 1.0), WHITE),
+    (Color::new(0.12, 0.12, 0.12, 1.0), WHITE),
+    (Color::new(0.43, 0.18, 0.59, 1.0), Color::new(0.96, 0.78, 0.24, 1.0)),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChipAction {
+    Add,
+    Remove,
+    Dropped(Vec2), // Let go of a drag here
+}
+
+// The chip pictures, or None to draw them as circles
+#[derive(Clone, Default)]
+pub struct ChipAtlas {
+    texture: Option<Texture2D>,
+}
+
+impl ChipAtlas {
+    // The atlas has to be preloaded already
+    pub fn new(tm: &TextureManager) -> Self {
+        Self { texture: tm.get_texture(CHIP_ATLAS_PATH) }
+    }
+
+    // One chip, drawn straight away
+    pub fn draw_chip(&self, value: i64, center: Vec2, radius: f32) {
+        let index = DENOMINATIONS.iter().position(|denomination| *denomination == value).unwrap_or(0);
+        match &self.texture {
+            Some(texture) => {
+                // Scale the whole cell so the chip inside it comes out `radius` big
+                let size = ATLAS_CELL * radius / ATLAS_CHIP_RADIUS;
+                let params = DrawTextureParams {
+                    dest_size: Some(vec2(size, size)),
+                    source: Some(Rect::new(index as f32 * ATLAS_CELL, 0.0, ATLAS_CELL, ATLAS_CELL)),
+                    ..Default::default()
+                };
+                draw_texture_ex(texture, center.x - size / 2.0, center.y - size / 2.0, WHITE, params);
+            }
+            None => {
+                let (body, stripe) = FALLBACK_COLORS[index];
+                let mut shapes = ShapeBatch::new();
+                shapes.circle(center, radius, body);
+                shapes.ring(center, radius * 0.88, radius * 0.2, stripe);
+                shapes.ring(center, radius, 1.0, Color::new(0.0, 0.0, 0.0, 0.5));
+                shapes.draw();
+            }
+        }
+    }
+}
+
+// The fewest chips that make up `amount`, biggest first
+pub fn split(amount: i64) -> Vec<i64> {
+    let mut left = amount.max(0);
+    let mut chips = Vec::new();
+    for value in DENOMINATIONS.iter().rev() {
+        while left >= *value {
+            chips.push(*value);
+            left -= value;
+        }
+    }
+    chips
+}
+
+// A bet as stacks of chips around `center`, each chip `radius` big. Draws straight away.
+pub fn draw_stacks(atlas: &ChipAtlas, center: Vec2, radius: f32, amount: i64) {
+    let chips = split(amount);
+    let stacks: Vec<&[i64]> = chips.chunks(STACK_LIMIT).take(MAX_STACKS).collect();
+    let spacing = radius * 1.7;
+    let left = center.x - spacing * (stacks.len() as f32 - 1.0) / 2.0;
+    for (i, stack) in stacks.iter().enumerate() {
+        let x = left + spacing * i as f32;
+        // Tall stacks start a little lower so they stay centred on the spot
+        let bottom = center.y + radius * STACK_STEP * (stack.len() as f32 - 1.0) / 2.0;
+        for (height, value) in stack.iter().enumerate() {
+            atlas.draw_chip(*value, vec2(x, bottom - radius * STACK_STEP * height as f32), radius);
+        }
+    }
+}
+
+
+// A press on a chip that hasn't been let go yet
+struct Press {
+    start: Vec2,
+    time: f64,
+    dragging: bool,
+    long_press: bool,
+}
+
+pub struct InteractiveChip {
+    value: i64,
+    center: Vec2,
+    radius: f32,
+    press: Option<Press>,
+    was_down: bool, // The pointer was down last frame, so a press only starts when it goes down
+    layer: Layer,
+}
+
+impl InteractiveChip {
+    pub fn new(value: i64) -> Self {
+        Self { value, center: Vec2::ZERO, radius: 24.0, press: None, was_down: false, layer: Layer::Table }
+    }
+
+    #[allow(unused)]
+    pub fn with_layer(&mut self, layer: Layer) -> &mut Self {
+        self.layer = layer;
+        self
+    }
+
+    pub fn value(&self) -> i64 {
+        self.value
+    }
+
+    pub fn set_position(&mut self, center: Vec2, radius: f32) {
+        self.center = center;
+        self.radius = radius;
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.press.as_ref().is_some_and(|press| press.dragging)
+    }
+
+    // Handle the pointer and draw, returns what the player did with the chip this frame.
+    // A press held for `long_press_seconds` without moving takes the chip off.
+    pub fn update(&mut self, atlas: &ChipAtlas, long_press_seconds: f32) -> Option<ChipAction> {
+        let (pos, down) = pointer();
+        let pressed = down && !self.was_down;
+        self.was_down = down;
+        let hovered = pos.distance(self.center) <= self.radius;
+        let mut action = None;
+        if hovered && is_mouse_button_pressed(MouseButton::Right) {
+            action = Some(ChipAction::Remove);
+        }
+        match &mut self.press {
+            None if pressed && hovered => {
+                self.press = Some(Press { start: pos, time: get_time(), dragging: false, long_press: false });
+            }
+            Some(press) if down => {
+                if !press.long_press && press.start.distance(pos) > DRAG_DISTANCE {
+                    press.dragging = true;
+                }
+                if !press.dragging && !press.long_press && get_time() - press.time >= long_press_seconds as f64 {
+                    press.long_press = true;
+                    action = Some(ChipAction::Remove);
+                }
+            }
+            Some(press) => {
+                if press.dragging {
+                    action = Some(ChipAction::Dropped(pos));
+                } else if !press.long_press && hovered {
+                    action = Some(ChipAction::Add);
+                }
+                self.press = None;
+            }
+            None => {}
+        }
+
+        let dragging = self.is_dragging();
+        if dragging {
+            cursor::want(CursorKind::Grab);
+        } else if hovered {
+            cursor::want(CursorKind::Pointer);
+        }
+        // The tray chip rises a little under the pointer, a dragged copy follows the pointer above the table
+        let (value, center, radius) = (self.value, self.center, self.radius);
+        let lift = if hovered && !dragging { radius * 0.1 } else { 0.0 };
+        let tray_atlas = atlas.clone();
+        layers::push(self.layer, move || draw_tray_chip(&tray_atlas, value, center - vec2(0.0, lift), radius));
+        if dragging {
+            let atlas = atlas.clone();
+            layers::push(Layer::Hud, move || {
+                draw_smooth_circle(pos.x + 3.0, pos.y + 5.0, radius, Color::new(0.0, 0.0, 0.0, 0.3));
+                draw_tray_chip(&atlas, value, pos, radius);
+            });
+        }
+        action
+    }
+}
+
+// A chip with its value written on its face
+fn draw_tray_chip(atlas: &ChipAtlas, value: i64, center: Vec2, radius: f32) {
+    atlas.draw_chip(value, center, radius);
+    let label = i18n::short_money(value);
+    let font_size = (radius * 0.7) as u16;
+    let size = measure_text_cached(&label, None, font_size);
+    // The $1 chip is white, the rest are dark enough for white writing
+    let color = if value == DENOMINATIONS[0] { BLACK } else { WHITE };
+    draw_text(&label, center.x - size.width / 2.0, center.y + size.offset_y / 2.0, font_size as f32, color);
+}
@@ -0,0 +1,132 @@
+/*
+By: <tyler>
+Date: 2025-12-03
+Program Details: Card counting trainer panel
+
+Keeps track of what is left in the shoe and shows it as one bar per rank. A bar
+starts full and shrinks as that rank is dealt. Its color says how the rank is
+doing compared to an even shoe: red when more of it is left than usual, blue
+when it has been dealt out faster.
+Next to the bars are the Hi-Lo counts:
+    2 - 6 count +1, 7 - 9 count 0, tens and aces count -1
+    Running count - the total of every card seen since the shuffle
+    True count    - the running count divided by the decks left in the shoe
+A card dealt face down (the dealer's hole card) isn't counted until it is shown.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod chart;
+    pub mod events;
+    pub mod layers;
+    pub mod count_trainer;
+
+Then add the following with the use commands:
+use crate::modules::count_trainer::CountTrainer;
+
+Usage examples:
+1. Create it for the shoe and give it the game events (it resets on ShoeShuffled):
+    let mut trainer = CountTrainer::new(shoe.decks());
+    bus.dispatch(&mut [&mut trainer]);
+
+2. Draw it into an area, passing the card that is still face down:
+    trainer.draw(Rect::new(100.0, 268.0, 560.0, 58.0), hole_card);
+*/
+use macroquad::prelude::*;
+use crate::modules::cards::{Card, Rank, RANKS};
+use crate::modules::chart::{bar_chart, heat_color, Bar};
+use crate::modules::events::{EventListener, GameEvent};
+use crate::modules::layers::{self, Layer};
+
+const COUNT_WIDTH: f32 = 100.0; // Room on the right for the counts
+
+pub struct CountTrainer {
+    decks: usize,
+    left: [u32; 13], // Cards of each rank still in the shoe, in RANKS order
+}
+
+impl CountTrainer {
+    pub fn new(decks: usize) -> Self {
+        Self { decks, left: [decks as u32 * 4; 13] }
+    }
+
+    fn reset(&mut self) {
+        self.left = [self.decks as u32 * 4; 13];
+    }
+
+    fn rank_index(rank: Rank) -> usize {
+        RANKS.iter().position(|r| *r == rank).unwrap_or(0)
+    }
+
+    // Hi-Lo tag for one card
+    fn hi_lo(rank: Rank) -> i32 {
+        match rank.value() {
+            2..=6 => 1,
+            7..=9 => 0,
+            _ => -1,
+        }
+    }
+
+    // Cards left of each rank as the player sees it, with `hidden` still counted as in the shoe
+    fn visible_left(&self, hidden: Option<Card>) -> [u32; 13] {
+        let mut left = self.left;
+        if let Some(card) = hidden {
+            left[Self::rank_index(card.rank)] += 1;
+        }
+        left
+    }
+
+    // Cards left of each rank in RANKS order as the player sees it, for working out odds
+    pub fn unseen(&self, hidden: Option<Card>) -> [u32; 13] {
+        self.visible_left(hidden)
+    }
+
+    // Running and true count, leaving out the face down card
+    pub fn counts(&self, hidden: Option<Card>) -> (i32, f32) {
+        let left = self.visible_left(hidden);
+        let full = self.decks as u32 * 4;
+        let running: i32 = RANKS.iter().zip(left).map(|(rank, left)| (full - left) as i32 * Self::hi_lo(*rank)).sum();
+        let cards_left: u32 = left.iter().sum();
+        // Never divide by less than half a deck, the count swings wildly at the very end of a shoe
+        let decks_left = (cards_left as f32 / 52.0).max(0.5);
+        (running, running as f32 / decks_left)
+    }
+
+    // Queue the panel into the HUD layer
+    pub fn draw(&self, area: Rect, hidden: Option<Card>) {
+        let left = self.visible_left(hidden);
+        let full = self.decks as f32 * 4.0;
+        let cards_left: u32 = left.iter().sum();
+        let expected = cards_left as f32 / 13.0;
+        let bars: Vec<Bar> = RANKS
+            .iter()
+            .zip(left)
+            .map(|(rank, left)| {
+                let heat = if expected > 0.0 { left as f32 / expected - 1.0 } else { 0.0 };
+                Bar::new(rank.letter().to_string(), left as f32, heat_color(heat * 2.0))
+            })
+            .collect();
+        let (running, true_count) = self.counts(hidden);
+        layers::push(Layer::Hud, move || {
+            draw_rectangle(area.x, area.y, area.w, area.h, Color::new(0.0, 0.0, 0.0, 0.45));
+            let chart_area = Rect::new(area.x + 4.0, area.y + 4.0, area.w - COUNT_WIDTH - 8.0, area.h - 8.0);
+            bar_chart(chart_area, &bars, full);
+            let text_x = area.x + area.w - COUNT_WIDTH;
+            draw_text(&format!("RC {:+}", running), text_x, area.y + area.h * 0.4, 24.0, WHITE);
+            draw_text(&format!("TC {:+.1}", true_count), text_x, area.y + area.h * 0.85, 24.0, GOLD);
+        });
+    }
+}
+
+impl EventListener for CountTrainer {
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::ShoeShuffled { .. } => self.reset(),
+            GameEvent::CardDealt { card, .. } => {
+                let left = &mut self.left[Self::rank_index(card.rank)];
+                *left = left.saturating_sub(1);
+            }
+            _ => {}
+        }
+    }
+}
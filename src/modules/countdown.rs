@@ -0,0 +1,128 @@
+/*
+By: <tyler>
+Date: 2025-12-11
+Program Details: A round countdown, a ring that empties as time runs out
+
+The ring starts full and empties clockwise from the top, going from green
+through yellow to red, with the whole seconds left in the middle. While it is
+paused it stops counting and the ring goes grey. When it runs out the
+on_expire callback is called, once, and update() returns true on that frame, so
+a caller can use whichever suits it. Time only moves when update() is given the
+frame's time (it is a modules::timer::Timer underneath), so a countdown that
+isn't updated is as good as paused.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod layers;
+    pub mod timer;
+    pub mod shapes;
+    pub mod draw;
+    pub mod countdown;
+
+Then add the following with the use commands:
+use crate::modules::countdown::Countdown;
+
+Usage examples:
+1. Ten seconds for a turn, with something to do when they're up:
+    let mut turn = Countdown::new(10.0);
+    turn.on_expire(|| log_info!("out of time"));
+
+2. Every frame, move it on and draw it around a point:
+    if turn.update(get_frame_time()) {
+        // out of time
+    }
+    turn.draw(vec2(500.0, 320.0), 20.0);
+
+3. Hold it while a dialog is open:
+    turn.pause();
+    turn.resume();
+*/
+use macroquad::prelude::*;
+use std::f32::consts::{FRAC_PI_2, TAU};
+use crate::modules::layers::{self, Layer};
+use crate::modules::shapes::ShapeBatch;
+use crate::modules::text_cache::measure_text_cached;
+use crate::modules::timer::Timer;
+
+const RING_WIDTH: f32 = 5.0;
+const TEXT_SIZE: u16 = 20;
+
+pub struct Countdown {
+    timer: Timer,
+    on_expire: Option<Box<dyn FnMut()>>,
+    layer: Layer,
+}
+
+impl Countdown {
+    pub fn new(seconds: f32) -> Self {
+        Self { timer: Timer::after(seconds), on_expire: None, layer: Layer::Hud }
+    }
+
+    // Called once, by the update that runs out the time
+    #[allow(unused)]
+    pub fn on_expire(&mut self, callback: impl FnMut() + 'static) -> &mut Self {
+        self.on_expire = Some(Box::new(callback));
+        self
+    }
+
+    #[allow(unused)]
+    pub fn with_layer(&mut self, layer: Layer) -> &mut Self {
+        self.layer = layer;
+        self
+    }
+
+    pub fn pause(&mut self) {
+        self.timer.pause();
+    }
+
+    pub fn resume(&mut self) {
+        self.timer.resume();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.timer.is_paused()
+    }
+
+    // How much of the time is left, 1.0 at the start and 0.0 once it has run out
+    pub fn fraction_left(&self) -> f32 {
+        if self.timer.duration() <= 0.0 {
+            0.0
+        } else {
+            (self.timer.remaining() / self.timer.duration()).clamp(0.0, 1.0)
+        }
+    }
+
+    // Count down by dt seconds, true on the update that runs out the time
+    pub fn update(&mut self, dt: f32) -> bool {
+        let expired = self.timer.tick(dt);
+        if expired && let Some(callback) = self.on_expire.as_mut() {
+            callback();
+        }
+        expired
+    }
+
+    // Queue the ring around `center` into its layer (the HUD unless changed)
+    pub fn draw(&self, center: Vec2, radius: f32) {
+        let fraction = self.fraction_left();
+        // Green to yellow over the first half used up, yellow to red over the second
+        let color = if self.is_paused() {
+            GRAY
+        } else {
+            Color::new((2.0 - fraction * 2.0).min(1.0), (fraction * 2.0).min(1.0), 0.0, 1.0)
+        };
+        let seconds = self.timer.remaining().ceil() as u32;
+        layers::push(self.layer, move || {
+            let mut shapes = ShapeBatch::new();
+            shapes.circle(center, radius, Color::new(0.0, 0.0, 0.0, 0.5));
+            shapes.ring(center, radius - RING_WIDTH / 2.0, RING_WIDTH, Color::new(1.0, 1.0, 1.0, 0.2));
+            if fraction > 0.0 {
+                shapes.arc(center, radius - RING_WIDTH / 2.0, RING_WIDTH, -FRAC_PI_2, TAU * fraction, color);
+            }
+            shapes.draw();
+            let text = seconds.to_string();
+            let size = measure_text_cached(&text, None, TEXT_SIZE);
+            draw_text(&text, center.x - size.width / 2.0, center.y + size.offset_y / 2.0, TEXT_SIZE as f32, WHITE);
+        });
+    }
+}
@@ -0,0 +1,172 @@
+/*
+By: <tyler>
+Date: 2025-11-27
+Program Details: Error screen instead of a frozen window when the game panics
+
+install_hook() records every panic (message, file and line) and logs it.
+run_guarded() runs the game and, if it panics:
+- Native: the panic is caught and an in-game error screen shows the details,
+  with a Copy Details button (to paste into a bug report) and an Exit button.
+- Web: wasm can't recover from a panic, so the hook asks js/blackjack.js to put
+  an HTML error page with the details over the canvas instead.
+
+problem_screen() shows the same screen for something found wrong that the game
+can carry on with (missing assets, say), with a Continue button as well.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod web_bridge;
+    pub mod log;
+    pub mod input;
+    pub mod crash;
+
+Then add the following with the use commands:
+use crate::modules::crash;
+
+Usage examples:
+1. Move the game into its own async fn and start it from main:
+    #[macroquad::main(window_conf)]
+    async fn main() {
+        crash::install_hook();
+        crash::run_guarded(run_game()).await;
+    }
+
+2. Show a problem that isn't fatal, false if the player picked Exit:
+    if !crash::problem_screen("3 card pictures are missing").await {
+        return;
+    }
+*/
+use std::cell::RefCell;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use macroquad::prelude::*;
+use crate::modules::input;
+use crate::modules::layers;
+use crate::modules::scale::use_virtual_resolution;
+use crate::modules::text_button::TextButton;
+use crate::log_error;
+
+const SCREEN_WIDTH: f32 = 1024.0;
+const SCREEN_HEIGHT: f32 = 768.0;
+const FONT_SIZE: f32 = 24.0;
+const MARGIN: f32 = 40.0;
+
+thread_local! {
+    static REPORT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// Record panics so the error screen can show them. The default hook still prints to stderr.
+pub fn install_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let message = if let Some(text) = info.payload().downcast_ref::<&str>() {
+            text.to_string()
+        } else if let Some(text) = info.payload().downcast_ref::<String>() {
+            text.clone()
+        } else {
+            "unknown error".to_string()
+        };
+        let location = info.location().map_or("unknown location".to_string(), |location| location.to_string());
+        let report = format!(
+            "Black Jack {} ({}) stopped because of an error:\n\n{}\n\nat {}",
+            env!("CARGO_PKG_VERSION"),
+            std::env::consts::OS,
+            message,
+            location
+        );
+        log_error!("{}", report);
+        #[cfg(target_arch = "wasm32")]
+        crate::modules::web_bridge::show_error(&report);
+        REPORT.with(|stored| *stored.borrow_mut() = Some(report));
+        default_hook(info);
+    }));
+}
+
+// Run the game, switching to the error screen if it panics
+pub async fn run_guarded(game: impl Future<Output = ()>) {
+    if CatchPanic(Box::pin(game)).await.is_err() {
+        let report = REPORT.with(|stored| stored.borrow_mut().take()).unwrap_or_else(|| "unknown error".to_string());
+        error_screen("Something went wrong", &report, false).await;
+    }
+}
+
+// The error screen for a problem the game can get past, true to carry on and false to exit
+pub async fn problem_screen(report: &str) -> bool {
+    error_screen("Something isn't right", report, true).await
+}
+
+// Polls the game, turning a panic inside it into Err
+struct CatchPanic<F>(Pin<Box<F>>);
+
+impl<F: Future<Output = ()>> Future for CatchPanic<F> {
+    type Output = Result<(), ()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let game = &mut self.0;
+        match panic::catch_unwind(AssertUnwindSafe(|| game.as_mut().poll(cx))) {
+            Ok(Poll::Ready(())) => Poll::Ready(Ok(())),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(_) => Poll::Ready(Err(())),
+        }
+    }
+}
+
+// Returns true if Continue (only there when `can_continue`) was picked, false for Exit
+async fn error_screen(title: &str, report: &str, can_continue: bool) -> bool {
+    // Whatever the game queued before it stopped is only half a frame
+    layers::clear();
+    let lines = wrap(report, SCREEN_WIDTH - MARGIN * 2.0);
+    let mut btn_copy = TextButton::new(MARGIN, SCREEN_HEIGHT - 100.0, 240.0, 60.0, "Copy Details", BLACK, DARKGRAY, 30);
+    btn_copy.with_round(5.0);
+    let mut btn_exit = TextButton::new(MARGIN + 270.0, SCREEN_HEIGHT - 100.0, 200.0, 60.0, "Exit", BLACK, DARKGRAY, 30);
+    btn_exit.with_round(5.0);
+    let mut btn_continue = TextButton::new(SCREEN_WIDTH - MARGIN - 200.0, SCREEN_HEIGHT - 100.0, 200.0, 60.0, "Continue", BLACK, DARKGRAY, 30);
+    btn_continue.with_round(5.0);
+    let mut copied_until = 0.0;
+
+    loop {
+        input::begin_frame();
+        use_virtual_resolution(SCREEN_WIDTH, SCREEN_HEIGHT);
+        clear_background(Color::new(0.05, 0.25, 0.05, 1.0));
+        draw_text(title, MARGIN, 80.0, 50.0, GOLD);
+        for (i, line) in lines.iter().enumerate() {
+            draw_text(line, MARGIN, 140.0 + i as f32 * FONT_SIZE * 1.2, FONT_SIZE, WHITE);
+        }
+        if btn_copy.click() {
+            miniquad::window::clipboard_set(report);
+            copied_until = get_time() + 2.0;
+        }
+        if get_time() < copied_until {
+            draw_text("Copied to the clipboard", MARGIN + 500.0, SCREEN_HEIGHT - 60.0, FONT_SIZE, WHITE);
+        }
+        if btn_exit.click() {
+            return false;
+        }
+        if can_continue && btn_continue.click() {
+            return true;
+        }
+        layers::flush();
+        next_frame().await;
+    }
+}
+
+// Break the report into lines that fit the screen, keeping its own line breaks
+fn wrap(text: &str, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split(' ') {
+            let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+            if !line.is_empty() && measure_text(&candidate, None, FONT_SIZE as u16, 1.0).width > max_width {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            } else {
+                line = candidate;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
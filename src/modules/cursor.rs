@@ -0,0 +1,133 @@
+/*
+By: <tyler>
+Date: 2025-12-10
+Program Details: The game's own mouse cursor, drawn on top of everything
+
+Once turned on, the system cursor is hidden and the game draws its own at the
+mouse position in world coordinates, so it lines up with the buttons under the
+virtual resolution camera whatever size the window is. It is drawn the same size
+on screen at every resolution.
+
+There are three cursors:
+    Arrow   - the normal one
+    Pointer - a pointing hand, over anything that can be clicked
+    Grab    - a closed hand, while something is being held or dragged
+Widgets ask for one while they are being drawn, the same way tooltips work, and
+layers::flush() draws the cursor last. If more than one thing asks in a frame,
+Grab wins over Pointer and Pointer over Arrow. Each frame starts again at Arrow.
+
+Nothing is drawn while a finger is on a touch screen, there is no cursor to show.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod tooltip;
+    pub mod layers;
+    pub mod shapes;
+    pub mod input;
+    pub mod cursor;
+
+Then add the following with the use commands:
+use crate::modules::cursor::{self, CursorKind};
+
+Usage examples:
+1. Turn it on once loading is done (the loading screen keeps the system cursor):
+    cursor::set_enabled(true);
+
+2. From a widget, while the mouse is over it or dragging it:
+    cursor::want(CursorKind::Pointer);
+    cursor::want(CursorKind::Grab);
+
+3. layers::flush() draws it. Without the layers module call this last in the loop:
+    cursor::draw();
+
+TextButton, Slider and ScrollPanel already ask for the right cursor.
+*/
+use macroquad::prelude::*;
+use std::cell::Cell;
+use crate::modules::input::{is_touching, mouse_position};
+use crate::modules::scale::{mouse_position_world, screen_to_world};
+use crate::modules::shapes::ShapeBatch;
+
+const OUTLINE: f32 = 1.5; // Black edge around the white shape, in screen pixels
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CursorKind {
+    Arrow,
+    Pointer,
+    Grab,
+}
+
+thread_local! {
+    static ENABLED: Cell<bool> = const { Cell::new(false) };
+    static WANTED: Cell<CursorKind> = const { Cell::new(CursorKind::Arrow) };
+}
+
+// Swap between the game's cursor and the system one
+pub fn set_enabled(enabled: bool) {
+    ENABLED.with(|cell| cell.set(enabled));
+    show_mouse(!enabled);
+}
+
+// Ask for a cursor this frame. The strongest one asked for is drawn.
+pub fn want(kind: CursorKind) {
+    WANTED.with(|wanted| wanted.set(wanted.get().max(kind)));
+}
+
+// Draw this frame's cursor and go back to the arrow for the next frame
+pub fn draw() {
+    let kind = WANTED.with(|wanted| wanted.replace(CursorKind::Arrow));
+    if !ENABLED.with(Cell::get) || is_touching() {
+        return;
+    }
+    let (mouse_x, mouse_y) = mouse_position();
+    let (x, y) = mouse_position_world();
+    // World units for one screen pixel, so the cursor doesn't grow with the window
+    let pixel = screen_to_world(mouse_x + 1.0, mouse_y).0 - x;
+    let at = |dx: f32, dy: f32| vec2(x + dx * pixel, y + dy * pixel);
+    let size = |length: f32| length * pixel;
+    match kind {
+        CursorKind::Arrow => {
+            // Tip at the mouse position
+            let (tip, left, right) = (at(0.0, 0.0), at(0.0, 17.0), at(12.0, 12.0));
+            let tail = [at(4.5, 13.0), at(7.5, 20.0), at(10.0, 19.0), at(7.0, 12.0)];
+            draw_triangle(tip, left, right, WHITE);
+            draw_triangle(tail[0], tail[1], tail[2], WHITE);
+            draw_triangle(tail[0], tail[2], tail[3], WHITE);
+            for (from, to) in [(tip, left), (left, tail[0]), (tail[0], tail[1]), (tail[1], tail[2]), (tail[2], tail[3]), (tail[3], right), (right, tip)] {
+                draw_line(from.x, from.y, to.x, to.y, size(OUTLINE), BLACK);
+            }
+        }
+        CursorKind::Pointer => {
+            // Fingertip at the mouse position
+            let parts = [
+                (Rect::new(-2.0, 0.0, 4.0, 12.0), 2.0),  // Finger
+                (Rect::new(-6.0, 9.0, 14.0, 11.0), 3.0), // Palm
+                (Rect::new(-9.0, 11.0, 5.0, 4.0), 2.0),  // Thumb
+            ];
+            draw_hand(&parts, at, size);
+        }
+        CursorKind::Grab => {
+            // Middle of the fist at the mouse position
+            let parts = [
+                (Rect::new(-7.0, -4.0, 14.0, 11.0), 3.0), // Palm
+                (Rect::new(-7.0, -7.0, 14.0, 4.0), 2.0),  // Curled fingers
+                (Rect::new(-10.0, -2.0, 4.0, 5.0), 2.0),  // Thumb
+            ];
+            draw_hand(&parts, at, size);
+        }
+    }
+}
+
+// White rounded rectangles with a black edge, given in screen pixels from the mouse position
+fn draw_hand(parts: &[(Rect, f32)], at: impl Fn(f32, f32) -> Vec2, size: impl Fn(f32) -> f32) {
+    let mut shapes = ShapeBatch::new();
+    for (grow, color) in [(OUTLINE, BLACK), (0.0, WHITE)] {
+        for (rect, radius) in parts {
+            let corner = at(rect.x - grow, rect.y - grow);
+            let world = Rect::new(corner.x, corner.y, size(rect.w + grow * 2.0), size(rect.h + grow * 2.0));
+            shapes.fill(world, size(radius + grow), color);
+        }
+    }
+    shapes.draw();
+}
@@ -0,0 +1,154 @@
+/*
+By: <tyler>
+Date: 2025-11-26
+Program Details: F3 debug overlay for development builds
+
+Shows FPS, a graph of recent frame times, how many draw calls each layer drew, the texture count and memory from the TextureManager, how the
+startup asset loading went (from the preload progress events), and a list of
+name/value pairs describing the game state (phase, hand values, shoe
+penetration, ...). Press F3 to show or hide it.
+
+Only debug builds have it: the module is behind #[cfg(debug_assertions)] in
+mod.rs, so `cargo build --release` leaves it out completely.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod layers;
+    pub mod input;
+    #[cfg(debug_assertions)]
+    pub mod debug_overlay;
+
+Then add the following with the use commands:
+#[cfg(debug_assertions)]
+use crate::modules::debug_overlay::DebugOverlay;
+
+Usage examples:
+1. Create it before the loop:
+    #[cfg(debug_assertions)]
+    let mut debug_overlay = DebugOverlay::new();
+
+2. Let it watch the asset loading:
+    tm.preload_with_progress(&assets, &cancel, |event| debug_overlay.on_load_event(event)).await;
+
+3. Every frame (the draw call counts are from the frame before):
+    #[cfg(debug_assertions)]
+    {
+        debug_overlay.update();
+        debug_overlay.draw(&tm, &[("Phase", format!("{:?}", phase)), ("Player", player_hand.value().to_string())]);
+    }
+*/
+use std::collections::VecDeque;
+use macroquad::prelude::*;
+use crate::modules::input::is_key_pressed;
+use crate::modules::layers::{self, Layer, LAYERS};
+use crate::modules::preload_image::{ProgressEvent, TextureManager};
+
+const HISTORY: usize = 120; // Frames shown in the graph
+const PANEL_X: f32 = 10.0;
+const PANEL_Y: f32 = 10.0;
+const PANEL_WIDTH: f32 = 330.0;
+const GRAPH_HEIGHT: f32 = 50.0;
+const LINE_HEIGHT: f32 = 20.0;
+const FAILED_SHOWN: usize = 3; // Failed assets listed by name, the rest are only counted
+
+pub struct DebugOverlay {
+    visible: bool,
+    frame_times: VecDeque<f32>,
+    load_summary: Option<String>,
+    failed_assets: Vec<String>,
+}
+
+impl DebugOverlay {
+    pub fn new() -> Self {
+        Self { visible: false, frame_times: VecDeque::with_capacity(HISTORY), load_summary: None, failed_assets: Vec::new() }
+    }
+
+    // Remember how the asset loading went
+    pub fn on_load_event(&mut self, event: &ProgressEvent) {
+        match event {
+            ProgressEvent::Failed { path, .. } => self.failed_assets.push(path.clone()),
+            ProgressEvent::Done { loaded, failed, skipped: 0, seconds } => {
+                self.load_summary = Some(format!("Assets: {} loaded, {} failed in {:.2}s", loaded, failed, seconds));
+            }
+            ProgressEvent::Done { loaded, skipped, .. } => {
+                self.load_summary = Some(format!("Assets: loading cancelled after {}, {} skipped", loaded, skipped));
+            }
+            _ => {}
+        }
+    }
+
+    // Record this frame's time and handle the F3 toggle
+    pub fn update(&mut self) {
+        if is_key_pressed(KeyCode::F3) {
+            self.visible = !self.visible;
+        }
+        if self.frame_times.len() == HISTORY {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(get_frame_time());
+    }
+
+    #[allow(unused)]
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    // Queue the overlay into the debug layer, with the given state lines at the bottom
+    pub fn draw(&self, tm: &TextureManager, state: &[(&str, String)]) {
+        if !self.visible {
+            return;
+        }
+        let mut lines = vec![
+            format!("FPS: {}", get_fps()),
+            format!("Frame: {:.1} ms (worst {:.1} ms)", self.latest_ms(), self.worst_ms()),
+        ];
+        let draw_calls: Vec<String> = LAYERS.iter().map(|layer| format!("{:?} {}", layer, layers::drawn_last_frame(*layer))).collect();
+        lines.push(format!("Draw calls: {}", draw_calls.join(", ")));
+        lines.push(format!("Textures: {} ({:.1} MB)", tm.texture_count(), tm.memory_bytes() as f32 / 1_048_576.0));
+        lines.extend(self.load_summary.clone());
+        lines.extend(self.failed_assets.iter().take(FAILED_SHOWN).map(|path| format!("  missing {}", path)));
+        lines.extend(state.iter().map(|(name, value)| format!("{}: {}", name, value)));
+
+        let frame_times: Vec<f32> = self.frame_times.iter().copied().collect();
+        layers::push(Layer::Debug, move || draw_panel(&lines, &frame_times));
+    }
+
+    fn latest_ms(&self) -> f32 {
+        self.frame_times.back().copied().unwrap_or(0.0) * 1000.0
+    }
+
+    fn worst_ms(&self) -> f32 {
+        self.frame_times.iter().copied().fold(0.0, f32::max) * 1000.0
+    }
+}
+
+fn draw_panel(lines: &[String], frame_times: &[f32]) {
+    let height = 20.0 + GRAPH_HEIGHT + lines.len() as f32 * LINE_HEIGHT;
+    draw_rectangle(PANEL_X, PANEL_Y, PANEL_WIDTH, height, Color::new(0.0, 0.0, 0.0, 0.75));
+
+    for (i, line) in lines.iter().enumerate() {
+        draw_text(line, PANEL_X + 8.0, PANEL_Y + 20.0 + i as f32 * LINE_HEIGHT, 20.0, WHITE);
+    }
+
+    // Frame time graph: one bar per frame, 33 ms (30 FPS) fills the graph.
+    // Green is under 60 FPS budget, yellow under 30 FPS, red slower.
+    let graph_y = PANEL_Y + 10.0 + lines.len() as f32 * LINE_HEIGHT;
+    let bar_width = (PANEL_WIDTH - 16.0) / HISTORY as f32;
+    for (i, time) in frame_times.iter().enumerate() {
+        let ms = time * 1000.0;
+        let bar_height = (ms / 33.3).min(1.0) * GRAPH_HEIGHT;
+        let color = if ms <= 16.7 {
+            GREEN
+        } else if ms <= 33.3 {
+            YELLOW
+        } else {
+            RED
+        };
+        let x = PANEL_X + 8.0 + i as f32 * bar_width;
+        draw_rectangle(x, graph_y + GRAPH_HEIGHT - bar_height, bar_width, bar_height, color);
+    }
+    // 60 FPS line
+    let budget_y = graph_y + GRAPH_HEIGHT - 16.7 / 33.3 * GRAPH_HEIGHT;
+    draw_line(PANEL_X + 8.0, budget_y, PANEL_X + PANEL_WIDTH - 8.0, budget_y, 1.0, GRAY);
+}
@@ -0,0 +1,96 @@
+/*
+By: <tyler>
+Date: 2025-11-30
+Program Details: Countdown for the player's decisions
+
+Optional pressure: each decision gets a set number of seconds, shown as a ring
+that empties (and goes from green to red) above the action buttons, see
+countdown.rs. When it runs out the game plays the move for the player.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod layers;
+    pub mod timer;
+    pub mod countdown;
+    pub mod decision_timer;
+
+Then add the following with the use commands:
+use crate::modules::decision_timer::DecisionTimer;
+
+Usage examples:
+1. Create it before the loop:
+    let mut timer = DecisionTimer::new();
+
+2. Start it whenever the player has to decide (0 seconds turns it off), stop it when they're done:
+    timer.start(settings.decision_seconds as f32);
+    timer.stop();
+
+3. Every frame, check for the time running out and draw the ring around a point:
+    if timer.update(get_frame_time()) {
+        // out of time, play for them
+    }
+    timer.draw(vec2(ring_x, ring_y));
+
+4. Hold the clock without losing the time left:
+    timer.pause();
+    timer.resume();
+*/
+use macroquad::prelude::*;
+use crate::modules::countdown::Countdown;
+
+const RING_RADIUS: f32 = 18.0;
+
+pub struct DecisionTimer {
+    countdown: Option<Countdown>, // None while it is off
+}
+
+impl DecisionTimer {
+    pub fn new() -> Self {
+        Self { countdown: None }
+    }
+
+    // Start counting down from `seconds`, or stay off if it is 0
+    pub fn start(&mut self, seconds: f32) {
+        self.countdown = (seconds > 0.0).then(|| Countdown::new(seconds));
+    }
+
+    pub fn stop(&mut self) {
+        self.countdown = None;
+    }
+
+    #[allow(unused)]
+    pub fn is_running(&self) -> bool {
+        self.countdown.is_some()
+    }
+
+    #[allow(unused)]
+    pub fn pause(&mut self) {
+        if let Some(countdown) = self.countdown.as_mut() {
+            countdown.pause();
+        }
+    }
+
+    #[allow(unused)]
+    pub fn resume(&mut self) {
+        if let Some(countdown) = self.countdown.as_mut() {
+            countdown.resume();
+        }
+    }
+
+    // Count down, returns true once when the time runs out
+    pub fn update(&mut self, dt: f32) -> bool {
+        let out_of_time = self.countdown.as_mut().is_some_and(|countdown| countdown.update(dt));
+        if out_of_time {
+            self.countdown = None;
+        }
+        out_of_time
+    }
+
+    // Queue the ring around `center` into the HUD, full at the start and empty at the end
+    pub fn draw(&self, center: Vec2) {
+        if let Some(countdown) = &self.countdown {
+            countdown.draw(center, RING_RADIUS);
+        }
+    }
+}
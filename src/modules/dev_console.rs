@@ -0,0 +1,278 @@
+/*
+By: <tyler>
+Date: 2025-12-08
+Program Details: Drop-down developer console for cheat and test commands
+
+Press ` (backtick) to drop the console down from the top of the screen, type a
+command and press Enter. Up and Down go through the commands typed before,
+Escape or ` closes it again. It makes awkward hands quick to set up instead of
+dealing until they turn up.
+
+    deal AS KD ...      the next cards out of the shoe, in order (A♠ K♦ work too)
+    force dealer 16     the dealer's two cards next round add up to 16
+    set bankroll 5000   the profile's bankroll
+    seed 42             reshuffle the shoe with a seed
+    scenarios           play the golden-hand scenarios (modules::scenario)
+    metrics             the engine's counters and timings (modules::metrics)
+    widgets             the widgets with an ID on screen (modules::ui)
+    press btn_hit       press a widget by its ID, as if it was clicked
+    help, clear
+
+Cards are a rank (2-9, 10 or T, J, Q, K, A) and a suit (C, D, H, S or the
+symbol), either case. The console only reads the command, the game applies it
+to the shoe and profile and answers with print().
+
+The cards of a round come out in this order: two to each hand, left to right
+and one card at a time, then the dealer's up card and the hole card. With dealer
+peeks off the hole card comes out after the player's hits, so stand straight
+away to get the stacked one. A stacked shoe doesn't match its seed any more, so
+the fairness log flags those rounds.
+
+Only debug builds have it: the module is behind #[cfg(debug_assertions)] in
+mod.rs, so `cargo build --release` leaves it out completely.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod cards;
+    pub mod layers;
+    pub mod input;
+    pub mod metrics;
+    pub mod ui;
+    #[cfg(debug_assertions)]
+    pub mod dev_console;
+
+Then add the following with the use commands:
+#[cfg(debug_assertions)]
+use crate::modules::dev_console::{Command, DevConsole};
+
+Usage examples:
+1. Create it before the loop:
+    #[cfg(debug_assertions)]
+    let mut console = DevConsole::new();
+
+2. Every frame, run what was typed and draw it over the screen width:
+    #[cfg(debug_assertions)]
+    if let Some(command) = console.update() {
+        match command {
+            Command::Seed(seed) => {
+                shoe.shuffle_with_seed(seed);
+                console.print(format!("shuffled with seed {}", seed));
+            }
+            ...
+        }
+    }
+    console.draw(layout.virtual_width);
+*/
+use std::collections::VecDeque;
+use macroquad::prelude::*;
+use crate::modules::cards::{Card, Rank};
+use crate::modules::input::{get_char_pressed, is_key_pressed};
+use crate::modules::layers::{self, Layer};
+use crate::modules::metrics;
+use crate::modules::ui;
+
+const HEIGHT: f32 = 260.0;
+const LINE_HEIGHT: f32 = 22.0;
+const FONT_SIZE: f32 = 22.0;
+const MAX_LINES: usize = 9; // Output lines kept, the oldest scroll off the top
+const MAX_HISTORY: usize = 20;
+const HELP: [&str; 10] = [
+    "deal AS KD ...     next cards out of the shoe",
+    "force dealer 16    dealer's two cards next round",
+    "set bankroll 5000  profile bankroll",
+    "seed 42            reshuffle with a seed",
+    "scenarios          play assets/scenarios.json",
+    "metrics            engine counters and timings",
+    "widgets            widgets with an ID on screen",
+    "press btn_hit      press a widget by its ID",
+    "clear              empty the console",
+    "` or Escape        close",
+];
+
+// What the game has to do for a command
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    Deal(Vec<Card>),
+    ForceDealer(u32),
+    SetBankroll(i64),
+    Seed(u64),
+    RunScenarios,
+}
+
+pub struct DevConsole {
+    open: bool,
+    input: String,
+    lines: VecDeque<String>,
+    history: Vec<String>,
+    history_pos: Option<usize>, // Which old command Up has got to
+}
+
+impl DevConsole {
+    pub fn new() -> Self {
+        let mut console = Self { open: false, input: String::new(), lines: VecDeque::new(), history: Vec::new(), history_pos: None };
+        console.print("Type help for the commands");
+        console
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    // Add a line under the commands
+    pub fn print(&mut self, line: impl Into<String>) {
+        if self.lines.len() == MAX_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line.into());
+    }
+
+    // Handle the toggle and the typing. Returns a command when Enter was pressed on one.
+    pub fn update(&mut self) -> Option<Command> {
+        if is_key_pressed(KeyCode::GraveAccent) || (self.open && is_key_pressed(KeyCode::Escape)) {
+            self.open = !self.open;
+            // Don't leave what was typed while closed waiting for the next open
+            while get_char_pressed().is_some() {}
+            return None;
+        }
+        if !self.open {
+            return None;
+        }
+        while let Some(ch) = get_char_pressed() {
+            if !ch.is_control() && ch != '`' {
+                self.input.push(ch);
+            }
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            self.input.pop();
+        }
+        if is_key_pressed(KeyCode::Up) && !self.history.is_empty() {
+            let pos = self.history_pos.map_or(self.history.len() - 1, |pos| pos.saturating_sub(1));
+            self.history_pos = Some(pos);
+            self.input = self.history[pos].clone();
+        }
+        if is_key_pressed(KeyCode::Down) && let Some(pos) = self.history_pos {
+            self.history_pos = (pos + 1 < self.history.len()).then_some(pos + 1);
+            self.input = self.history_pos.map(|pos| self.history[pos].clone()).unwrap_or_default();
+        }
+        if !is_key_pressed(KeyCode::Enter) && !is_key_pressed(KeyCode::KpEnter) {
+            return None;
+        }
+        let line = std::mem::take(&mut self.input);
+        let line = line.trim();
+        self.history_pos = None;
+        if line.is_empty() {
+            return None;
+        }
+        if self.history.len() == MAX_HISTORY {
+            self.history.remove(0);
+        }
+        self.history.push(line.to_string());
+        self.print(format!("> {}", line));
+        match line {
+            "help" => self.show_help(),
+            "clear" => self.lines.clear(),
+            "metrics" => {
+                for line in metrics::summary() {
+                    self.print(line);
+                }
+            }
+            "widgets" => self.show_widgets(),
+            _ if line.starts_with("press ") => {
+                let id = line["press ".len()..].trim();
+                match ui::scene().widgets().iter().find(|widget| &*widget.id == id) {
+                    Some(widget) if widget.visible && widget.enabled => {
+                        ui::press(id);
+                        self.print(format!("pressed {}", id));
+                    }
+                    Some(_) => self.print(format!("{} is hidden or disabled", id)),
+                    None => self.print(format!("No widget {} on screen, type widgets for the list", id)),
+                }
+            }
+            _ => match parse(line) {
+                Ok(command) => return Some(command),
+                Err(err) => self.print(err),
+            },
+        }
+        None
+    }
+
+    // Queue the console into the debug layer, across the top of the screen
+    pub fn draw(&self, width: f32) {
+        if !self.open {
+            return;
+        }
+        let lines: Vec<String> = self.lines.iter().cloned().collect();
+        let cursor = if get_time() % 1.0 < 0.5 { "_" } else { "" };
+        let input = format!("> {}{}", self.input, cursor);
+        layers::push(Layer::Debug, move || {
+            draw_rectangle(0.0, 0.0, width, HEIGHT, Color::new(0.0, 0.0, 0.0, 0.85));
+            for (i, line) in lines.iter().enumerate() {
+                draw_text(line, 10.0, 24.0 + i as f32 * LINE_HEIGHT, FONT_SIZE, LIGHTGRAY);
+            }
+            draw_line(0.0, HEIGHT - 34.0, width, HEIGHT - 34.0, 1.0, GRAY);
+            draw_text(&input, 10.0, HEIGHT - 10.0, FONT_SIZE, WHITE);
+        });
+    }
+
+    // The widgets with an ID, the ones that can't be used in brackets, wrapped to the console's lines
+    fn show_widgets(&mut self) {
+        let scene = ui::scene();
+        let ids: Vec<String> = scene
+            .widgets()
+            .iter()
+            .map(|widget| if widget.visible && widget.enabled { widget.id.to_string() } else { format!("({})", widget.id) })
+            .collect();
+        if ids.is_empty() {
+            self.print("No widgets with an ID on screen");
+        }
+        for line in ids.chunks(6) {
+            self.print(line.join(" "));
+        }
+    }
+
+    fn show_help(&mut self) {
+        for line in HELP {
+            self.print(line);
+        }
+    }
+}
+
+fn parse(line: &str) -> Result<Command, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    match words.as_slice() {
+        ["deal", cards @ ..] if !cards.is_empty() => {
+            let cards = cards.iter().map(|code| Card::parse(code).ok_or(format!("{} isn't a card", code))).collect::<Result<_, _>>()?;
+            Ok(Command::Deal(cards))
+        }
+        ["force", "dealer", total] => match total.parse() {
+            Ok(total) if (4..=21).contains(&total) => Ok(Command::ForceDealer(total)),
+            _ => Err("The dealer's total has to be from 4 to 21".to_string()),
+        },
+        ["set", "bankroll", amount] => amount.parse().map(Command::SetBankroll).map_err(|_| format!("{} isn't an amount", amount)),
+        ["scenarios"] => Ok(Command::RunScenarios),
+        ["seed", seed] => seed.parse().map(Command::Seed).map_err(|_| format!("{} isn't a seed", seed)),
+        _ => Err(format!("Unknown command: {}, type help for the list", line)),
+    }
+}
+
+// Two ranks that add up to `total` (4 - 21), a ten and the rest where it fits
+pub fn ranks_for_total(total: u32) -> [Rank; 2] {
+    let by_value = |value: u32| match value {
+        2 => Rank::Two,
+        3 => Rank::Three,
+        4 => Rank::Four,
+        5 => Rank::Five,
+        6 => Rank::Six,
+        7 => Rank::Seven,
+        8 => Rank::Eight,
+        9 => Rank::Nine,
+        10 => Rank::Ten,
+        _ => Rank::Ace,
+    };
+    if total >= 12 {
+        [Rank::Ten, by_value(total - 10)]
+    } else {
+        [Rank::Two, by_value(total - 2)]
+    }
+}
@@ -0,0 +1,130 @@
+/*
+By: <tyler>
+Date: 2025-11-29
+Program Details: Modal dialogs - a question with a row of answer buttons
+
+While a dialog is open the game should only draw the table underneath and wait
+for an answer, the same way the settings screen works. A dialog can have a
+countdown: when it runs out the default answer is picked for the player, and a
+bar under the message shows the time left.
+
+Everything is drawn in the overlay layer.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod text_button;
+    pub mod layers;
+    pub mod ui;
+    pub mod dialog;
+
+Then add the following with the use commands:
+use crate::modules::dialog::Dialog;
+
+Usage examples:
+1. Open one (keep it in an Option so the loop knows when one is showing):
+    let mut dialog = Some(Dialog::new("Even Money?", "Take a guaranteed win?", &["Yes", "No"]).with_timeout(10.0, 1));
+
+2. In the loop, draw the table, then ask the dialog for an answer:
+    if let Some(open) = dialog.as_mut() {
+        table.draw();
+        if let Some(answer) = open.update(layout.virtual_width, layout.virtual_height) {
+            // answer is the index of the button, 0 for "Yes" here
+            let picked_by_timer = open.timed_out();
+            dialog = None;
+        }
+        layers::flush();
+        next_frame().await;
+        continue;
+    }
+*/
+use macroquad::prelude::*;
+use crate::modules::layers::{self, Layer};
+use crate::modules::text_button::TextButton;
+use crate::modules::ui;
+use crate::modules::shapes::ShapeBatch;
+use crate::modules::widget_layout::{Align, Placeable, Row};
+
+const WIDTH: f32 = 560.0;
+const HEIGHT: f32 = 280.0;
+const BUTTON_WIDTH: f32 = 160.0;
+const BUTTON_HEIGHT: f32 = 60.0;
+const BUTTON_GAP: f32 = 30.0;
+
+pub struct Dialog {
+    title: String,
+    message: String,
+    buttons: Vec<TextButton>,
+    timeout: Option<(f32, f32, usize)>, // Total seconds, seconds left, answer picked at 0
+}
+
+impl Dialog {
+    pub fn new(title: &str, message: &str, answers: &[&str]) -> Self {
+        let buttons = answers
+            .iter()
+            .map(|answer| {
+                let mut button = TextButton::new(0.0, 0.0, BUTTON_WIDTH, BUTTON_HEIGHT, *answer, BLACK, DARKGREEN, 32);
+                button.with_layer(Layer::Overlay);
+                button
+            })
+            .collect();
+        Self { title: title.to_string(), message: message.to_string(), buttons, timeout: None }
+    }
+
+    // Pick the answer at `default` by itself after `seconds`
+    pub fn with_timeout(mut self, seconds: f32, default: usize) -> Self {
+        self.timeout = Some((seconds, seconds, default.min(self.buttons.len().saturating_sub(1))));
+        self
+    }
+
+    // Draw the dialog centered on the screen and return the answer once there is one
+    pub fn update(&mut self, screen_width: f32, screen_height: f32) -> Option<usize> {
+        let x = screen_width / 2.0 - WIDTH / 2.0;
+        let y = screen_height / 2.0 - HEIGHT / 2.0;
+        let (title, message) = (self.title.clone(), self.message.clone());
+        let countdown = self.timeout.map(|(total, left, _)| (left / total, left.ceil() as u32));
+        let default_name = self.timeout.and_then(|(_, _, default)| self.buttons.get(default)).map(|button| button.get_text().to_string());
+        // Nothing under the dimmed screen takes the pointer while the dialog is up
+        ui::block(Layer::Overlay, layers::BACKGROUND, Rect::new(0.0, 0.0, screen_width, screen_height));
+        layers::push(Layer::Overlay, move || {
+            let panel = Rect::new(x, y, WIDTH, HEIGHT);
+            let mut shapes = ShapeBatch::new();
+            shapes.fill(Rect::new(0.0, 0.0, screen_width, screen_height), 0.0, Color::new(0.0, 0.0, 0.0, 0.6));
+            shapes.fill(panel, 0.0, DARKGRAY);
+            shapes.outline(panel, 0.0, 3.0, GOLD);
+            shapes.draw();
+            draw_text(&title, x + 20.0, y + 50.0, 45.0, WHITE);
+            draw_text(&message, x + 20.0, y + 100.0, 28.0, WHITE);
+            if let (Some((fraction, seconds)), Some(default_name)) = (countdown, default_name) {
+                draw_rectangle(x + 20.0, y + 120.0, (WIDTH - 40.0) * fraction, 10.0, GOLD);
+                draw_rectangle_lines(x + 20.0, y + 120.0, WIDTH - 40.0, 10.0, 1.0, WHITE);
+                draw_text(&format!("\"{}\" in {}s", default_name, seconds), x + 20.0, y + 160.0, 24.0, LIGHTGRAY);
+            }
+        });
+
+        let mut row: Vec<&mut dyn Placeable> = self.buttons.iter_mut().map(|button| button as &mut dyn Placeable).collect();
+        Row::new(screen_width / 2.0, y + HEIGHT - BUTTON_HEIGHT - 20.0).spacing(BUTTON_GAP).anchor(Align::Center).place(&mut row);
+        let mut answer = None;
+        for (i, button) in self.buttons.iter().enumerate() {
+            if button.click() && answer.is_none() {
+                answer = Some(i);
+            }
+        }
+        if answer.is_some() {
+            return answer;
+        }
+
+        if let Some((_, left, default)) = self.timeout.as_mut() {
+            *left -= get_frame_time();
+            if *left <= 0.0 {
+                return Some(*default);
+            }
+        }
+        None
+    }
+
+    // True if the answer was picked because time ran out
+    pub fn timed_out(&self) -> bool {
+        self.timeout.is_some_and(|(_, left, _)| left <= 0.0)
+    }
+}
@@ -0,0 +1,92 @@
+/*
+By: <tyler>
+Date: 2025-12-11
+Program Details: Smooth circles, rings and arcs
+
+macroquad's draw_circle is a fan with a fixed 20 sides and a hard edge, so a big
+chip shows its flat sides and a small one stair-steps even with MSAA on. These
+are built from a ShapeBatch instead (see shapes.rs): the number of sides grows
+with the size the circle is on screen, so no side is more than a quarter of a
+pixel off the curve, and every edge fades out over one screen pixel so it
+doesn't step at any window size. Rounded rectangles from ShapeBatch get the same
+soft edges.
+
+A ring is drawn centered on its radius, half the thickness each side, like
+draw_circle_lines. Angles are in radians, 0 points right and they go clockwise
+on screen (y grows downwards).
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod scale;
+    pub mod shapes;
+    pub mod draw;
+
+Then add the following with the use commands:
+use crate::modules::draw::{draw_smooth_arc, draw_smooth_circle, draw_smooth_ring};
+
+Usage examples:
+1. A chip, a filled circle with a ring inside its edge:
+    draw_smooth_circle(x, y, 30.0, MAROON);
+    draw_smooth_ring(x, y, 26.0, 2.0, WHITE);
+
+2. Three quarters of a ring starting at the top, for a countdown:
+    draw_smooth_arc(x, y, 20.0, 4.0, -PI / 2.0, TAU * 0.75, GOLD);
+
+3. Several at once in one draw call:
+    let mut batch = ShapeBatch::new();
+    batch.circle(vec2(x, y), 30.0, MAROON);
+    batch.ring(vec2(x, y), 26.0, 2.0, WHITE);
+    batch.draw();
+*/
+use macroquad::prelude::*;
+use crate::modules::scale::world_to_screen;
+use crate::modules::shapes::ShapeBatch;
+
+// How far (in screen pixels) a side may cut inside the true curve
+const CURVE_TOLERANCE: f32 = 0.25;
+const MIN_SEGMENTS: usize = 12;
+const MAX_SEGMENTS: usize = 512;
+
+// World units that one screen pixel covers with the current virtual resolution
+pub fn pixel_size() -> f32 {
+    let pixels = world_to_screen(1.0, 0.0).0 - world_to_screen(0.0, 0.0).0;
+    if pixels > 0.0 { 1.0 / pixels } else { 1.0 }
+}
+
+// Sides needed for `sweep` radians of a circle of `radius` world units to look round
+pub fn arc_segments(radius: f32, sweep: f32) -> usize {
+    let radius_pixels = radius / pixel_size();
+    if radius_pixels <= CURVE_TOLERANCE {
+        return MIN_SEGMENTS;
+    }
+    // A side spanning `step` radians cuts radius * (1 - cos(step / 2)) inside the curve
+    let step = 2.0 * (1.0 - CURVE_TOLERANCE / radius_pixels).acos();
+    let full_circle = (std::f32::consts::TAU / step).ceil() as usize;
+    let segments = (full_circle as f32 * sweep.abs() / std::f32::consts::TAU).ceil() as usize;
+    segments.clamp(MIN_SEGMENTS, MAX_SEGMENTS)
+}
+
+// A filled circle
+#[allow(unused)]
+pub fn draw_smooth_circle(x: f32, y: f32, radius: f32, color: Color) {
+    let mut batch = ShapeBatch::new();
+    batch.circle(vec2(x, y), radius, color);
+    batch.draw();
+}
+
+// A circle's outline `thickness` wide, centered on `radius`
+#[allow(unused)]
+pub fn draw_smooth_ring(x: f32, y: f32, radius: f32, thickness: f32, color: Color) {
+    let mut batch = ShapeBatch::new();
+    batch.ring(vec2(x, y), radius, thickness, color);
+    batch.draw();
+}
+
+// Part of a ring, from `start` radians round by `sweep` radians (negative goes anticlockwise)
+#[allow(unused)]
+pub fn draw_smooth_arc(x: f32, y: f32, radius: f32, thickness: f32, start: f32, sweep: f32, color: Color) {
+    let mut batch = ShapeBatch::new();
+    batch.arc(vec2(x, y), radius, thickness, start, sweep, color);
+    batch.draw();
+}
@@ -0,0 +1,214 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Practice drills that deal the hands the player gets wrong most
+
+Every hit or stand the player picks (GameEvent::PlayerDecision) is checked
+against basic strategy and counted by situation: the hand's total, whether it
+is soft, and the dealer's up card. When drills are on the next round is dealt
+to be one of those situations, picked at random but weighted towards the ones
+the player gets wrong: each mistake makes a situation MISTAKE_WEIGHT times more
+likely than one that has never been missed. Until there are mistakes to work on
+the drills come from a list of the well known hard spots (CLASSIC), like 16
+against a 10 or soft 18 against a 9.
+
+A drill is dealt by stacking the shoe (Shoe::stack): the first hand's two cards
+and the dealer's up card are moved to where the deal will find them. The rest
+of the round plays out as usual. A stacked shoe no longer matches its seed, so
+the fairness log flags drilled rounds.
+
+The counts are kept for the session only.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod cards;
+    pub mod events;
+    pub mod hand;
+    pub mod shoe;
+    pub mod strategy;
+    pub mod drills;
+
+Then add the following with the use commands:
+use crate::modules::drills::DrillTracker;
+
+Usage examples:
+1. Create it before the loop and give it the game events:
+    let mut drills = DrillTracker::new();
+    bus.dispatch(&mut [&mut drills]);
+
+2. Tell it about each decision the player makes:
+    bus.emit(GameEvent::PlayerDecision { hand, total: hand.value(), soft: hand.is_soft(), dealer_up, hit: true });
+
+3. Before the cards go out, set the shoe up for a drill:
+    if let Some(situation) = drills.deal_drill(&mut shoe, hands) {
+        println!("Drill: {}", situation.text());
+    }
+*/
+use std::collections::HashMap;
+use macroquad::rand::RandGenerator;
+use crate::modules::cards::{Card, Rank, RANKS, SUITS};
+use crate::modules::events::{EventListener, GameEvent};
+use crate::modules::shoe::Shoe;
+use crate::modules::strategy::{basic_strategy_for, Action};
+
+const MISTAKE_WEIGHT: u32 = 5;
+const DEAL_TRIES: usize = 20; // Card picks to try before giving up, when the shoe is short of some
+const TEN_RANKS: [Rank; 4] = [Rank::Ten, Rank::Jack, Rank::Queen, Rank::King];
+
+// Hands that are easy to get wrong, drilled until the player's own mistakes take over
+const CLASSIC: [Situation; 8] = [
+    Situation { total: 16, soft: false, dealer: 10 },
+    Situation { total: 16, soft: false, dealer: 7 },
+    Situation { total: 15, soft: false, dealer: 10 },
+    Situation { total: 12, soft: false, dealer: 2 },
+    Situation { total: 12, soft: false, dealer: 3 },
+    Situation { total: 13, soft: false, dealer: 2 },
+    Situation { total: 18, soft: true, dealer: 9 },
+    Situation { total: 18, soft: true, dealer: 10 },
+];
+
+// A decision: the hand's total, soft or hard, against the dealer's up card (2 - 11, aces are 11)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Situation {
+    pub total: u32,
+    pub soft: bool,
+    pub dealer: u32,
+}
+
+impl Situation {
+    // "soft 18 against a 9", "16 against an ace"
+    pub fn text(&self) -> String {
+        let kind = if self.soft { "soft " } else { "" };
+        let dealer = match self.dealer {
+            11 => "an ace".to_string(),
+            8 => "an 8".to_string(),
+            value => format!("a {}", value),
+        };
+        format!("{}{} against {}", kind, self.total, dealer)
+    }
+
+    // Whether basic strategy hits here
+    fn should_hit(&self) -> bool {
+        basic_strategy_for(self.total, self.soft, card_worth(self.dealer, Rank::Ten, 0)) == Action::Hit
+    }
+
+    // Can it be dealt as two cards? Soft totals are an ace and one other card, hard ones two non-aces.
+    fn can_deal(&self) -> bool {
+        let dealer_ok = (2..=11).contains(&self.dealer);
+        if self.soft { (13..=20).contains(&self.total) && dealer_ok } else { (4..=20).contains(&self.total) && dealer_ok }
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Record {
+    seen: u32,
+    wrong: u32,
+}
+
+pub struct DrillTracker {
+    records: HashMap<Situation, Record>,
+    rng: RandGenerator,
+}
+
+impl DrillTracker {
+    pub fn new() -> Self {
+        let rng = RandGenerator::new();
+        rng.srand(macroquad::miniquad::date::now() as u64);
+        Self { records: HashMap::new(), rng }
+    }
+
+    // Count a decision, returns true if it was the basic strategy move
+    pub fn record(&mut self, situation: Situation, hit: bool) -> bool {
+        let right = situation.should_hit() == hit;
+        let record = self.records.entry(situation).or_default();
+        record.seen += 1;
+        if !right {
+            record.wrong += 1;
+        }
+        right
+    }
+
+    // The situations missed so far with how many times each was missed and seen, most missed first
+    #[allow(unused)]
+    pub fn weaknesses(&self) -> Vec<(Situation, u32, u32)> {
+        let mut missed: Vec<(Situation, u32, u32)> =
+            self.records.iter().filter(|(_, record)| record.wrong > 0).map(|(situation, record)| (*situation, record.wrong, record.seen)).collect();
+        missed.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+        missed
+    }
+
+    // Pick the next drill, weighted towards the player's mistakes
+    pub fn next_drill(&mut self) -> Situation {
+        let mut choices: Vec<(Situation, u32)> = self
+            .records
+            .iter()
+            .filter(|(situation, record)| record.wrong > 0 && situation.can_deal())
+            .map(|(situation, record)| (*situation, record.wrong * MISTAKE_WEIGHT))
+            .collect();
+        for situation in CLASSIC {
+            if !choices.iter().any(|(chosen, _)| *chosen == situation) {
+                choices.push((situation, 1));
+            }
+        }
+        let total: u32 = choices.iter().map(|(_, weight)| weight).sum();
+        let mut pick = self.rng.gen_range(0, total.max(1));
+        for (situation, weight) in &choices {
+            if pick < *weight {
+                return *situation;
+            }
+            pick -= weight;
+        }
+        CLASSIC[0]
+    }
+
+    // Stack the shoe so the first of `hands` hands is dealt a drill, returns which one.
+    // None if the shoe doesn't have the cards for it.
+    pub fn deal_drill(&mut self, shoe: &mut Shoe, hands: usize) -> Option<Situation> {
+        let situation = self.next_drill();
+        for _ in 0..DEAL_TRIES {
+            let [first, second] = self.player_cards(situation);
+            let up = card_worth(situation.dealer, TEN_RANKS[self.rng.gen_range(0, TEN_RANKS.len())], self.rng.gen_range(0, SUITS.len()));
+            // Each hand gets a card, then each hand a second, then the dealer
+            if shoe.stack(0, &[first]) && shoe.stack(hands, &[second]) && shoe.stack(hands * 2, &[up]) {
+                return Some(situation);
+            }
+        }
+        None
+    }
+
+    // Two cards making the situation's total, in a random order with random suits
+    fn player_cards(&self, situation: Situation) -> [Card; 2] {
+        let values: [u32; 2] = if situation.soft {
+            [11, situation.total - 11]
+        } else {
+            // Neither card an ace, each 2 to 10
+            let low = situation.total.saturating_sub(10).max(2);
+            let high = (situation.total - 2).min(10);
+            let first = self.rng.gen_range(low, high + 1);
+            [first, situation.total - first]
+        };
+        let card = |value: u32| {
+            let ten = TEN_RANKS[self.rng.gen_range(0, TEN_RANKS.len())];
+            card_worth(value, ten, self.rng.gen_range(0, SUITS.len()))
+        };
+        if self.rng.gen_range(0, 2) == 0 { [card(values[0]), card(values[1])] } else { [card(values[1]), card(values[0])] }
+    }
+}
+
+impl EventListener for DrillTracker {
+    fn on_event(&mut self, event: &GameEvent) {
+        if let GameEvent::PlayerDecision { total, soft, dealer_up, hit, .. } = event {
+            self.record(Situation { total: *total, soft: *soft, dealer: dealer_up.value() }, *hit);
+        }
+    }
+}
+
+// A card worth `value` (11 for an ace), `ten` picks which ten-card, `suit` indexes SUITS
+fn card_worth(value: u32, ten: Rank, suit: usize) -> Card {
+    let rank = match value {
+        10 => ten,
+        _ => RANKS.iter().copied().find(|rank| rank.value() == value).unwrap_or(Rank::Two),
+    };
+    Card::new(rank, SUITS[suit % SUITS.len()])
+}
@@ -0,0 +1,74 @@
+/*
+By: <tyler>
+Date: 2025-11-22
+Program Details: Random numbers nobody can work out, for shoe seeds and the fairness log's salts
+
+macroquad's rand (and the shoe's own generator) are fine for shuffling, but the
+numbers they give next follow from the ones before. The fairness log reveals
+each shoe's seed once it is retired, so the seed of the shoe after it, and the
+salt hiding it, must not follow from anything revealed. Both come from here:
+- Native: the operating system's random source (the getrandom crate)
+- Web: the browser's crypto.getRandomValues (through js/blackjack.js)
+
+A recording (see input_recorder.rs) has to deal the same cards when it is played
+back, so while one is made or played the seeds come from its own seed instead
+(set_repeatable). Those sessions are for testing, not for the fairness log.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod web_bridge;
+    pub mod entropy;
+In the Cargo.toml file add the following:
+    [target.'cfg(not(target_arch = "wasm32"))'.dependencies]
+    getrandom = "0.2"
+
+Then add the following with the use commands:
+use crate::modules::entropy;
+
+Usage examples:
+1. A seed for a new shoe:
+    shoe.shuffle_with_seed(entropy::new_seed());
+
+2. A secret salt:
+    let salt = entropy::salt();
+
+3. Deal the same shoes every time a recording is played (native only):
+    entropy::set_repeatable(recorded_seed);
+*/
+use macroquad::rand::RandGenerator;
+use std::cell::RefCell;
+
+thread_local! {
+    // Set while a recording is made or played back, the seeds follow from its seed
+    static REPEATABLE: RefCell<Option<RandGenerator>> = const { RefCell::new(None) };
+}
+
+// Give new_seed() the same numbers every run started from `seed`, for recordings.
+// Only the native build can make or play one.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn set_repeatable(seed: u64) {
+    let rng = RandGenerator::new();
+    rng.srand(seed);
+    REPEATABLE.with(|repeatable| *repeatable.borrow_mut() = Some(rng));
+}
+
+// A seed for a new shoe that no earlier seed gives away
+pub fn new_seed() -> u64 {
+    let repeatable = REPEATABLE.with(|repeatable| repeatable.borrow().as_ref().map(|rng| ((rng.rand() as u64) << 32) | rng.rand() as u64));
+    repeatable.unwrap_or_else(|| u64::from_le_bytes(random_bytes()))
+}
+
+// 128 random bits as hex, always from the system however the seeds are picked
+pub fn salt() -> String {
+    random_bytes::<16>().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    #[cfg(not(target_arch = "wasm32"))]
+    getrandom::getrandom(&mut bytes).expect("the system has no random source");
+    #[cfg(target_arch = "wasm32")]
+    crate::modules::web_bridge::random_bytes(&mut bytes);
+    bytes
+}
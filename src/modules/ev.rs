@@ -0,0 +1,321 @@
+/*
+By: <tyler>
+Date: 2025-12-09
+Program Details: Expected value of hitting and standing, shown over the buttons
+
+For the hand being played, how much each move wins or loses on average, as a
+fraction of the bet (+0.25 wins a quarter of the bet in the long run, -0.50 loses
+half). This table only has Hit and Stand, so those are the two worked out. After a
+hit the rest of the hand is played by basic strategy, the same moves the trainer
+recommends, so the numbers say why the recommended move is the better one and by
+how much.
+
+Standing is always worked out exactly from the dealer's odds (see odds.rs).
+Hitting is exact too when the odds come from a full deck, the chances never
+change so every hand the player can reach is only a total and whether it is
+soft. From the cards left in this shoe every card drawn changes the odds for the
+next, so hitting is estimated instead: a few thousand hands are dealt from the
+unseen cards each frame and the average builds up over the next frames, without
+holding up any one of them. The estimate starts again when anything it depends
+on changes, and stops once it has enough hands.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod odds;
+    pub mod strategy;
+    pub mod ev;
+
+Then add the following with the use commands:
+use crate::modules::ev::EvEstimator;
+
+Usage examples:
+1. Create it before the loop:
+    let mut ev = EvEstimator::new();
+
+2. Every frame of the player's turn, move the estimate on and show it over the buttons:
+    ev.update(&player_hand, up_card, &unseen, peeked);
+    ev.draw(hit_button_area, stand_button_area);
+
+3. Read the numbers:
+    let estimate = ev.estimate();
+    println!("hit {:+.2} (+/- {:.2}), stand {:+.2}", estimate.hit, estimate.hit_error, estimate.stand);
+*/
+use macroquad::prelude::*;
+use macroquad::rand::RandGenerator;
+use crate::modules::cards::{Card, Rank, Suit};
+use crate::modules::hand::{settle, Hand, Outcome};
+use crate::modules::layers::{self, Layer};
+use crate::modules::odds::{best_total, card_value, Composition, DealerOdds, OddsCalculator};
+use crate::modules::rules::{dealer_draws, DEALER_STANDS_ON};
+use crate::modules::strategy::{basic_strategy_for, Action};
+use crate::modules::text_cache::measure_text_cached;
+
+const HANDS_PER_FRAME: u32 = 2000; // Estimated hands dealt each frame
+const MAX_HANDS: u32 = 200_000;    // Enough for the estimate to be within about 0.005
+const TEXT_SIZE: f32 = 22.0;
+const SEED: u64 = 21; // The estimates don't need to be unpredictable, only spread evenly
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EvEstimate {
+    pub hit: f32,
+    pub stand: f32,
+    pub hit_error: f32, // Standard error of an estimated hit, 0 when it is exact
+    pub hands: u32,     // Hands the hit estimate is from, 0 when it is exact
+}
+
+// Everything the numbers depend on, the estimate starts again when any of it changes
+#[derive(Clone, PartialEq)]
+struct Situation {
+    player: Vec<Card>,
+    up_card: Card,
+    unseen: Composition,
+    peeked: bool,
+}
+
+pub struct EvEstimator {
+    calculator: OddsCalculator,
+    situation: Option<Situation>,
+    estimate: EvEstimate,
+    sum: f64,
+    sum_squares: f64,
+    rng: RandGenerator,
+}
+
+impl EvEstimator {
+    pub fn new() -> Self {
+        let rng = RandGenerator::new();
+        rng.srand(SEED);
+        Self { calculator: OddsCalculator::new(), situation: None, estimate: EvEstimate::default(), sum: 0.0, sum_squares: 0.0, rng }
+    }
+
+    #[allow(unused)]
+    pub fn estimate(&self) -> EvEstimate {
+        self.estimate
+    }
+
+    // Work out the numbers for a new situation, or deal more estimated hands for the current one
+    pub fn update(&mut self, player: &Hand, up_card: Card, unseen: &Composition, peeked: bool) {
+        let situation = Situation { player: player.cards().to_vec(), up_card, unseen: *unseen, peeked };
+        if self.situation.as_ref() != Some(&situation) {
+            self.start(player, &situation);
+            self.situation = Some(situation);
+            return;
+        }
+        if unseen.runs_out() && self.estimate.hands < MAX_HANDS {
+            self.deal_hands(player, &situation, HANDS_PER_FRAME);
+        }
+    }
+
+    fn start(&mut self, player: &Hand, situation: &Situation) {
+        let dealer = self.calculator.dealer_odds(situation.up_card, &situation.unseen, situation.peeked);
+        let hard: u32 = player.cards().iter().map(|card| card_value(*card)).sum();
+        let has_ace = player.cards().iter().any(|card| card.rank == Rank::Ace);
+        let stand = stand_ev(best_total(hard, has_ace), player.is_blackjack(), &dealer);
+        self.sum = 0.0;
+        self.sum_squares = 0.0;
+        self.estimate = EvEstimate { stand, ..Default::default() };
+        if !situation.unseen.runs_out() {
+            let mut memo = [[None; 2]; 22];
+            self.estimate.hit = hit_ev(hard, has_ace, situation.up_card, &situation.unseen, &dealer, &mut memo);
+        }
+    }
+
+    // Deal `count` more hands where the player hits once, then plays basic strategy
+    fn deal_hands(&mut self, player: &Hand, situation: &Situation, count: u32) {
+        for _ in 0..count {
+            let result = self.deal_hand(player, situation);
+            self.sum += result;
+            self.sum_squares += result * result;
+        }
+        let hands = self.estimate.hands + count;
+        let mean = self.sum / hands as f64;
+        let variance = (self.sum_squares / hands as f64 - mean * mean).max(0.0);
+        self.estimate.hands = hands;
+        self.estimate.hit = mean as f32;
+        self.estimate.hit_error = (variance / hands as f64).sqrt() as f32;
+    }
+
+    // One hand from the unseen cards, what it won as a fraction of the bet
+    fn deal_hand(&self, player: &Hand, situation: &Situation) -> f64 {
+        let mut left: [u32; 10] = std::array::from_fn(|i| situation.unseen.count(i as u32 + 1));
+        let mut hand = player.clone();
+        hand.add(self.take_card(&mut left, |_| true));
+        while !hand.is_bust() && basic_strategy_for(hand.value(), hand.is_soft(), situation.up_card) == Action::Hit {
+            hand.add(self.take_card(&mut left, |_| true));
+        }
+        if hand.is_bust() {
+            return -1.0;
+        }
+        // The hole card comes from the same unseen cards, it just can't be one the peek would have found
+        let up_value = card_value(situation.up_card);
+        let up_ace = situation.up_card.rank == Rank::Ace;
+        let peeked = situation.peeked;
+        let mut dealer = Hand::from_cards(&[situation.up_card]);
+        dealer.add(self.take_card(&mut left, |value| !(peeked && best_total(up_value + value, up_ace || value == 1) == 21)));
+        while dealer_draws(&dealer) {
+            dealer.add(self.take_card(&mut left, |_| true));
+        }
+        match settle(&hand, &dealer) {
+            Outcome::PlayerWin => 1.0,
+            Outcome::Push => 0.0,
+            Outcome::DealerWin | Outcome::NoWinner => -1.0,
+        }
+    }
+
+    // Take a random card from `left` among the values `allowed` lets through. With
+    // nothing left to take, a card is picked as if from a full deck.
+    fn take_card(&self, left: &mut [u32; 10], allowed: impl Fn(u32) -> bool) -> Card {
+        let full = Composition::full_deck();
+        let available: u32 = (1..=10).filter(|value| allowed(*value)).map(|value| left[value as usize - 1]).sum();
+        let weight = |value: u32| if available > 0 { left[value as usize - 1] } else { full.count(value) };
+        let total: u32 = (1..=10).filter(|value| allowed(*value)).map(weight).sum();
+        let mut pick = self.rng.gen_range(0, total.max(1));
+        let mut drawn = 10;
+        for value in (1..=10).filter(|value| allowed(*value)) {
+            let count = weight(value);
+            if pick < count {
+                drawn = value;
+                break;
+            }
+            pick -= count;
+        }
+        if available > 0 {
+            left[drawn as usize - 1] -= 1;
+        }
+        card_of_value(drawn)
+    }
+
+    // Queue the numbers over the Hit and Stand buttons, the better move in gold
+    pub fn draw(&self, hit_area: Rect, stand_area: Rect) {
+        if self.situation.is_none() {
+            return;
+        }
+        let estimate = self.estimate;
+        let hit_text = if estimate.hands > 0 {
+            format!("EV {:+.2} +/-{:.2}", estimate.hit, estimate.hit_error * 2.0)
+        } else {
+            format!("EV {:+.2}", estimate.hit)
+        };
+        let stand_text = format!("EV {:+.2}", estimate.stand);
+        let (hit_color, stand_color) = if estimate.hit > estimate.stand { (GOLD, LIGHTGRAY) } else { (LIGHTGRAY, GOLD) };
+        layers::push(Layer::Hud, move || {
+            for (text, area, color) in [(&hit_text, hit_area, hit_color), (&stand_text, stand_area, stand_color)] {
+                let width = measure_text_cached(text, None, TEXT_SIZE as u16).width;
+                draw_text(text, area.x + (area.w - width) / 2.0, area.y - 6.0, TEXT_SIZE, color);
+            }
+        });
+    }
+}
+
+// Average won by a whole round played by basic strategy, as a fraction of the bet: the house
+// edge the player faces, negative when the house is ahead. Only for odds that never change,
+// every pair of first cards is played against every up card. When the dealer peeks a
+// blackjack under a ten or an ace ends the round before the player plays. A blackjack wins
+// `blackjack_pays` bets.
+pub fn round_ev(unseen: &Composition, dealer_peeks: bool, blackjack_pays: f32) -> f32 {
+    let mut calculator = OddsCalculator::new();
+    let total_cards = unseen.total() as f32;
+    let chance = |value: u32| unseen.count(value) as f32 / total_cards;
+    let mut ev = 0.0;
+    for up in 1..=10 {
+        let up_card = card_of_value(up);
+        let peeked = dealer_peeks && (up == 1 || up == 10);
+        let dealer_blackjack = match up {
+            1 if peeked => chance(10),
+            10 if peeked => chance(1),
+            _ => 0.0,
+        };
+        let dealer = calculator.dealer_odds(up_card, unseen, peeked);
+        let mut memo = [[None; 2]; 22];
+        let mut up_ev = 0.0;
+        for first in 1..=10 {
+            for second in 1..=10 {
+                let hard = first + second;
+                let has_ace = first == 1 || second == 1;
+                let total = best_total(hard, has_ace);
+                let blackjack = total == 21;
+                // A blackjack beats any dealer 21 but a blackjack of the dealer's own, which pushes it
+                let played = if blackjack {
+                    blackjack_pays * (1.0 - dealer.blackjack)
+                } else {
+                    match basic_strategy_for(total, total != hard, up_card) {
+                        Action::Hit => hit_ev(hard, has_ace, up_card, unseen, &dealer, &mut memo),
+                        Action::Stand => stand_ev(total, false, &dealer),
+                    }
+                };
+                // A peeked blackjack pushes with the player's own and beats anything else
+                let against_blackjack = if blackjack { 0.0 } else { -1.0 };
+                up_ev += chance(first) * chance(second) * (dealer_blackjack * against_blackjack + (1.0 - dealer_blackjack) * played);
+            }
+        }
+        ev += chance(up) * up_ev;
+    }
+    ev
+}
+
+// Average won by standing on `total` against the dealer's odds
+fn stand_ev(total: u32, blackjack: bool, dealer: &DealerOdds) -> f32 {
+    let win = if blackjack { 1.5 } else { 1.0 };
+    let mut ev = dealer.bust * win;
+    for (i, chance) in dealer.totals.iter().enumerate() {
+        let dealer_total = DEALER_STANDS_ON + i as u32;
+        // A blackjack beats every dealer total, a 21 drawn to as well
+        if blackjack || total > dealer_total {
+            ev += chance * win;
+        } else if total < dealer_total {
+            ev -= chance;
+        }
+    }
+    // A dealer blackjack beats every hand but a blackjack, which it pushes (see hand::settle)
+    if !blackjack {
+        ev -= dealer.blackjack;
+    }
+    ev
+}
+
+// Average won by hitting a hand with this hard total and then playing basic strategy.
+// Only for odds that never change, so a hand is nothing more than its hard total and
+// whether it has an ace, and `memo` keeps each one once it is worked out.
+fn hit_ev(hard: u32, has_ace: bool, up_card: Card, unseen: &Composition, dealer: &DealerOdds, memo: &mut [[Option<f32>; 2]; 22]) -> f32 {
+    if let Some(ev) = memo[hard as usize][has_ace as usize] {
+        return ev;
+    }
+    let total_cards = unseen.total() as f32;
+    let mut ev = 0.0;
+    for value in 1..=10 {
+        let chance = unseen.count(value) as f32 / total_cards;
+        let next_hard = hard + value;
+        let next_ace = has_ace || value == 1;
+        let outcome = if next_hard > 21 {
+            -1.0
+        } else {
+            let total = best_total(next_hard, next_ace);
+            let soft = total != next_hard;
+            match basic_strategy_for(total, soft, up_card) {
+                Action::Hit => hit_ev(next_hard, next_ace, up_card, unseen, dealer, memo),
+                Action::Stand => stand_ev(total, false, dealer),
+            }
+        };
+        ev += chance * outcome;
+    }
+    memo[hard as usize][has_ace as usize] = Some(ev);
+    ev
+}
+
+// A card with this blackjack value, 1 for an ace. The suit makes no difference to the odds.
+fn card_of_value(value: u32) -> Card {
+    let rank = match value {
+        1 => Rank::Ace,
+        2 => Rank::Two,
+        3 => Rank::Three,
+        4 => Rank::Four,
+        5 => Rank::Five,
+        6 => Rank::Six,
+        7 => Rank::Seven,
+        8 => Rank::Eight,
+        9 => Rank::Nine,
+        _ => Rank::Ten,
+    };
+    Card::new(rank, Suit::Spades)
+}
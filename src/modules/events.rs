@@ -0,0 +1,121 @@
+/*
+By: <tyler>
+Date: 2025-11-21
+Program Details: Event bus so the game can announce what happened without knowing who cares
+
+The game emits typed events (a card was dealt, the player busted, the round was
+settled, the shoe was shuffled). Anything that wants to react - animations, stats,
+labels, sounds - implements EventListener and gets every event once per frame.
+
+Every event is stamped with the time it was emitted, so a listener that times
+things (see pacing.rs) isn't off by however late in the frame it was dispatched.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod events;
+
+Then add the following with the use commands:
+use crate::modules::events::{EventBus, EventListener, GameEvent};
+
+Usage examples:
+1. Create the bus before the loop:
+    let mut bus = EventBus::new();
+
+2. Emit events from the game code:
+    bus.emit(GameEvent::PlayerBusted { total: 23 });
+
+3. Make something listen:
+    impl EventListener for Stats {
+        fn on_event(&mut self, event: &GameEvent) {
+            if let GameEvent::RoundSettled { outcome, .. } = event {
+                // count it
+            }
+        }
+    }
+
+4. Once per frame, hand the queued events to every listener:
+    bus.dispatch(&mut [&mut stats, &mut shuffle_anim]);
+
+5. Listen with the time each event was emitted, in seconds:
+    impl EventListener for Pacing {
+        fn on_event(&mut self, event: &GameEvent) {
+            self.on_timed_event(event, events::now());
+        }
+        fn on_timed_event(&mut self, event: &GameEvent, time: f64) {
+            // time it
+        }
+    }
+*/
+use macroquad::miniquad::date;
+use crate::modules::cards::Card;
+pub use crate::modules::hand::Outcome;
+
+// Who a card went to. The player can have more than one hand, numbered from 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Seat {
+    Player(usize),
+    Dealer,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    CardDealt { card: Card, seat: Seat },
+    PlayerBusted { total: u32 },
+    BetPlaced { amount: i64 }, // Chips put down on the spots, all of them together
+    RoundSettled { hand: usize, outcome: Outcome, blackjack: bool, player_total: u32, dealer_total: u32, change: i64 }, // One for each player hand, `change` is what its bet won or lost
+    RoundFinished, // After the last hand's RoundSettled
+    BetSettled { change: i64 }, // What the round did to the bankroll, every hand together
+    ShoeShuffled { seed: u64 },
+    EvenMoney { taken: bool, timed_out: bool }, // Player's answer when offered even money
+    PlayerDecision { hand: usize, total: u32, soft: bool, dealer_up: Card, hit: bool }, // Hit or stand picked by the player, before the card comes
+    ChartOpened { in_hand: bool }, // The strategy chart was opened, `in_hand` while a hand was being played
+    LowBankroll { bankroll: i64, minimum: i64 }, // The bankroll went under the warning line for a table whose smallest bet is `minimum`
+}
+
+// Anything that wants to hear about game events
+pub trait EventListener {
+    fn on_event(&mut self, event: &GameEvent);
+
+    // The same, with when the event was emitted (see now()). Listeners that time things override this one.
+    fn on_timed_event(&mut self, event: &GameEvent, time: f64) {
+        let _ = time;
+        self.on_event(event);
+    }
+}
+
+// The clock events are stamped with, in seconds. Only the time between two readings means anything.
+pub fn now() -> f64 {
+    date::now()
+}
+
+#[derive(Default)]
+pub struct EventBus {
+    queue: Vec<(f64, GameEvent)>, // Each with the time it was emitted
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Queue an event, listeners get it on the next dispatch
+    pub fn emit(&mut self, event: GameEvent) {
+        self.queue.push((now(), event));
+    }
+
+    // Send every queued event to every listener, in the order they were emitted
+    pub fn dispatch(&mut self, listeners: &mut [&mut dyn EventListener]) {
+        for (time, event) in self.queue.drain(..) {
+            for listener in listeners.iter_mut() {
+                listener.on_timed_event(&event, time);
+            }
+        }
+    }
+
+    // True if nothing is waiting to be dispatched
+    #[allow(unused)]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
@@ -0,0 +1,146 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: A gallery of famous hands to play again and again
+
+Some spots come up in every blackjack book: 16 against a 10, soft 18 against a
+9, 12 against a 2. The gallery lists them from assets/famous_hands.json, and
+picking one stacks the shoe so every deal gives the first hand those two cards
+against that up card, until the player goes back to normal play. The rest of
+the round plays out as usual.
+
+The first move on the hand is checked with the EV engine (modules::ev). It works
+from a full deck, where the numbers are exact and ready on the first frame, and
+verdict() says which of Hit and Stand was worth more and by how much.
+
+A hand in the file is a name, a one-line note and three cards in the order
+they come out of the shoe, the player's two then the dealer's up card. The
+table has no splitting or doubling yet, so only hitting and standing are
+weighed; a pair of 8s is still a 16.
+    {
+        "name": "16 against a 10",
+        "note": "The hand everyone hates",
+        "cards": ["TS", "6H", "KD"]
+    }
+Hands with cards that don't read are left out, with a warning in the log.
+
+Like the drills the cards are stacked with Shoe::stack, so the fairness log
+flags the rounds.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod cards;
+    pub mod shoe;
+    pub mod ev;
+    pub mod famous_hands;
+
+Then add the following with the use commands:
+use crate::modules::famous_hands::{self, FamousHand};
+
+Usage examples:
+1. Load them before the loop:
+    let hands = famous_hands::load(famous_hands::PATH).await;
+
+2. Before the cards go out, stack the shoe for the picked one:
+    if !hands[picked].deal(&mut shoe, table_hands) {
+        println!("The shoe is short of the cards for it");
+    }
+
+3. Say how the first move compared:
+    ev.update(&hand, up_card, &Composition::full_deck(), peeked);
+    println!("{}", famous_hands::verdict(ev.estimate(), hit));
+*/
+use macroquad::file::load_string;
+use nanoserde::DeJson;
+use crate::modules::cards::Card;
+use crate::modules::ev::EvEstimate;
+use crate::modules::shoe::Shoe;
+use crate::log_warn;
+
+pub const PATH: &str = "assets/famous_hands.json";
+
+#[derive(DeJson, Clone, Debug, Default)]
+#[nserde(default)]
+pub struct FamousHand {
+    pub name: String,
+    pub note: String,
+    pub cards: Vec<String>, // The player's two cards, then the dealer's up card
+}
+
+impl FamousHand {
+    // The three cards, None if there aren't three that read
+    pub fn cards(&self) -> Option<[Card; 3]> {
+        let cards: Vec<Card> = self.cards.iter().map(|code| Card::parse(code)).collect::<Option<_>>()?;
+        cards.try_into().ok()
+    }
+
+    // Stack the shoe so the first of `hands` hands is dealt this one. False if the shoe doesn't have the cards.
+    pub fn deal(&self, shoe: &mut Shoe, hands: usize) -> bool {
+        let Some([first, second, up]) = self.cards() else {
+            return false;
+        };
+        // Each hand gets a card, then each hand a second, then the dealer
+        shoe.stack(0, &[first]) && shoe.stack(hands, &[second]) && shoe.stack(hands * 2, &[up])
+    }
+}
+
+// The file format
+#[derive(DeJson, Default)]
+#[nserde(default)]
+struct FamousHandsFile {
+    hands: Vec<FamousHand>,
+}
+
+pub fn parse(text: &str) -> Result<Vec<FamousHand>, String> {
+    let file = FamousHandsFile::deserialize_json(text).map_err(|err| err.to_string())?;
+    let (good, bad): (Vec<FamousHand>, Vec<FamousHand>) = file.hands.into_iter().partition(|hand| hand.cards().is_some());
+    for hand in bad {
+        log_warn!("famous hand {} left out, it needs three cards: {:?}", hand.name, hand.cards);
+    }
+    Ok(good)
+}
+
+// The gallery, empty when the file is missing or broken
+pub async fn load(path: &str) -> Vec<FamousHand> {
+    let text = match load_string(path).await {
+        Ok(text) => text,
+        Err(err) => {
+            log_warn!("could not load {}: {}", path, err);
+            return Vec::new();
+        }
+    };
+    parse(&text).unwrap_or_else(|err| {
+        log_warn!("could not parse {}: {}", path, err);
+        Vec::new()
+    })
+}
+
+// How the move the player made compares: "Right! Hitting is worth -0.51 a bet, standing -0.54"
+pub fn verdict(estimate: EvEstimate, hit: bool) -> String {
+    let hit_better = estimate.hit > estimate.stand;
+    let (picked, other) = if hit { (estimate.hit, estimate.stand) } else { (estimate.stand, estimate.hit) };
+    let (picked_name, other_name) = if hit { ("Hitting", "standing") } else { ("Standing", "hitting") };
+    if hit == hit_better {
+        format!("Right! {} is worth {:+.2} a bet, {} {:+.2}", picked_name, picked, other_name, other)
+    } else {
+        let better = if hit_better { "Hitting" } else { "Standing" };
+        format!("{} was better by {:.2} a bet: {:+.2} against {:+.2}", better, other - picked, other, picked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_famous_hand_reads() {
+        let text = include_str!("../../assets/famous_hands.json");
+        let file = FamousHandsFile::deserialize_json(text).unwrap();
+        assert!(!file.hands.is_empty());
+        for hand in &file.hands {
+            assert!(hand.cards().is_some(), "{} doesn't have three cards", hand.name);
+            assert!(!hand.note.is_empty(), "{} has no note", hand.name);
+        }
+    }
+}
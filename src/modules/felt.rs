@@ -0,0 +1,215 @@
+/*
+By: <tyler>
+Date: 2025-12-01
+Program Details: The table felt and its printed markings
+
+Everything is drawn with shapes instead of textures:
+- the felt itself in the theme's colour (see theme.rs), a little darker towards
+  the edges with the vignette shader (see materials.rs), or in bands where it
+  didn't compile (flat in low quality, see quality.rs)
+- the rules printed in an arc across the middle of the table, with the
+  insurance line in a gold band just outside it
+- the betting circle in front of the player, with the current bet stacked in
+  it as chips from the chip atlas (see chip.rs) and the amount over it, or one
+  smaller circle per hand when the player is playing more than one
+Where each marking goes comes from the layout preset (felt_arc, bet_spot and hand_spots).
+
+For streaming the felt can be turned into a green screen: one flat CHROMA_GREEN
+with no shading, so a streaming program can key it out evenly. The bet spots
+are still drawn on it, the rules and insurance line are left off.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod layers;
+    pub mod layout;
+    pub mod shapes;
+    pub mod chip;
+    pub mod quality;
+    pub mod materials;
+    pub mod theme;
+    pub mod felt;
+
+Then add the following with the use commands:
+use crate::modules::felt::Felt;
+
+Usage examples:
+1. Create it with the layout and the rules text:
+    let mut felt = Felt::new(layout);
+    felt.set_rules_text(profile.settings.rules.felt_text());
+    felt.set_chip_atlas(ChipAtlas::new(&tm));
+
+2. Show each hand's bet on its spot (0 shows the empty spot):
+    felt.set_bets(&[bet.amount(0), bet.amount(1)]);
+
+3. Draw it first each frame, everything else goes on top:
+    felt.draw();
+
+4. Key it out for streaming (clear the screen with CHROMA_GREEN too, for the edges):
+    felt.set_green_screen(true);
+*/
+use macroquad::prelude::*;
+use crate::modules::chip::{self, ChipAtlas};
+use crate::modules::shapes::ShapeBatch;
+use crate::modules::text_cache::measure_text_cached;
+use crate::modules::layers::{self, Layer};
+use crate::modules::layout::{Circle, TableLayout};
+use crate::modules::hand::MAX_HANDS;
+use crate::modules::i18n;
+use crate::modules::materials;
+use crate::modules::quality;
+use crate::modules::theme;
+
+const PRINT_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.75);
+const TRIM_COLOR: Color = Color::new(1.0, 0.8, 0.0, 0.6);
+const RULES_FONT_SIZE: f32 = 22.0;
+const INSURANCE_TEXT: &str = "INSURANCE PAYS 2 TO 1";
+const INSURANCE_GAP: f32 = 35.0; // How far outside the rules text the insurance line runs
+const VIGNETTE_STEPS: usize = 12;
+pub const CHROMA_GREEN: Color = Color::new(0.0, 1.0, 0.0, 1.0);
+
+pub struct Felt {
+    width: f32,
+    height: f32,
+    arc: Circle,
+    layout: TableLayout,
+    rules_text: String,
+    bets: Vec<i64>, // One per player hand
+    chip_atlas: ChipAtlas,
+    green_screen: bool,
+}
+
+impl Felt {
+    pub fn new(layout: &TableLayout) -> Self {
+        Self {
+            width: layout.virtual_width,
+            height: layout.virtual_height,
+            arc: layout.felt_arc,
+            layout: layout.clone(),
+            rules_text: String::new(),
+            bets: vec![0],
+            chip_atlas: ChipAtlas::default(),
+            green_screen: false,
+        }
+    }
+
+    pub fn set_layout(&mut self, layout: &TableLayout) {
+        self.width = layout.virtual_width;
+        self.height = layout.virtual_height;
+        self.arc = layout.felt_arc;
+        self.layout = layout.clone();
+    }
+
+    pub fn set_rules_text(&mut self, text: impl Into<String>) {
+        self.rules_text = text.into();
+    }
+
+    // The bet on each player hand's spot, which also says how many spots there are
+    pub fn set_bets(&mut self, amounts: &[i64]) {
+        self.bets = amounts.iter().copied().take(MAX_HANDS).collect();
+    }
+
+    // The chip pictures the bets are stacked with, plain circles until this is called
+    pub fn set_chip_atlas(&mut self, atlas: ChipAtlas) {
+        self.chip_atlas = atlas;
+    }
+
+    pub fn set_green_screen(&mut self, on: bool) {
+        self.green_screen = on;
+    }
+
+    // Queue the felt into the table layer. Call before anything else goes on the table.
+    pub fn draw(&self) {
+        let (width, height, arc) = (self.width, self.height, self.arc);
+        let rules_text = self.rules_text.clone();
+        let hands = self.bets.len();
+        let spots: Vec<(Circle, i64)> = self.bets.iter().enumerate().map(|(hand, bet)| (self.layout.hand_bet_spot(hand, hands), *bet)).collect();
+        let green_screen = self.green_screen;
+        let low_quality = quality::low();
+        let felt_color = theme::felt_color();
+        let atlas = self.chip_atlas.clone();
+        layers::push(Layer::Table, move || {
+            if green_screen {
+                draw_rectangle(0.0, 0.0, width, height, CHROMA_GREEN);
+                for (spot, bet) in spots {
+                    draw_bet_spot(spot, bet, &atlas);
+                }
+                return;
+            }
+            // Darker towards the edges, so the middle of the table looks lit (flat in low quality).
+            // Banded when the vignette shader isn't there.
+            if !materials::draw_vignette(0.0, 0.0, width, height, felt_color) {
+                draw_rectangle(0.0, 0.0, width, height, felt_color);
+                let steps = if low_quality { 0 } else { VIGNETTE_STEPS };
+                let band = width.min(height) * 0.15 / VIGNETTE_STEPS as f32;
+                for step in 0..steps {
+                    let inset = step as f32 * band;
+                    let alpha = 0.05 * (1.0 - step as f32 / VIGNETTE_STEPS as f32);
+                    draw_rectangle_lines(inset, inset, width - inset * 2.0, height - inset * 2.0, band * 2.0, Color::new(0.0, 0.0, 0.0, alpha));
+                }
+            }
+
+            draw_arc_text(&rules_text, arc.x, arc.y, arc.r, RULES_FONT_SIZE, PRINT_COLOR);
+            // The insurance line: a gold band with the payout printed inside it
+            let insurance_r = arc.r + INSURANCE_GAP;
+            let half_angle = arc_text_width(INSURANCE_TEXT, insurance_r, RULES_FONT_SIZE) / 2.0 + 0.05;
+            draw_arc_line(arc.x, arc.y, insurance_r - RULES_FONT_SIZE, half_angle, TRIM_COLOR);
+            draw_arc_line(arc.x, arc.y, insurance_r + 8.0, half_angle, TRIM_COLOR);
+            draw_arc_text(INSURANCE_TEXT, arc.x, arc.y, insurance_r, RULES_FONT_SIZE * 0.8, TRIM_COLOR);
+
+            for (spot, bet) in spots {
+                draw_bet_spot(spot, bet, &atlas);
+            }
+        });
+    }
+}
+
+// Angle (radians) a line of text takes up when bent around a circle of radius `r`
+fn arc_text_width(text: &str, r: f32, font_size: f32) -> f32 {
+    measure_text_cached(text, None, font_size as u16).width / r
+}
+
+// Text bent along the bottom of a circle, centered under it and read left to right
+fn draw_arc_text(text: &str, cx: f32, cy: f32, r: f32, font_size: f32, color: Color) {
+    let mut angle = -arc_text_width(text, r, font_size) / 2.0;
+    let mut buffer = [0u8; 4];
+    for letter in text.chars() {
+        let letter = letter.encode_utf8(&mut buffer);
+        let advance = measure_text_cached(letter, None, font_size as u16).width / r;
+        let x = cx + r * angle.sin();
+        let y = cy + r * angle.cos();
+        draw_text_ex(letter, x, y, TextParams { font_size: font_size as u16, rotation: -angle, color, ..Default::default() });
+        angle += advance;
+    }
+}
+
+// Part of a circle's bottom edge, `half_angle` either side of straight down
+fn draw_arc_line(cx: f32, cy: f32, r: f32, half_angle: f32, color: Color) {
+    let segments = 40;
+    let point = |angle: f32| vec2(cx + r * angle.sin(), cy + r * angle.cos());
+    for i in 0..segments {
+        let a = point(-half_angle + half_angle * 2.0 * i as f32 / segments as f32);
+        let b = point(-half_angle + half_angle * 2.0 * (i + 1) as f32 / segments as f32);
+        draw_line(a.x, a.y, b.x, b.y, 2.0, color);
+    }
+}
+
+// A ring on the felt where the chips go, with the bet stacked in it and the amount written over it
+fn draw_bet_spot(spot: Circle, bet: i64, atlas: &ChipAtlas) {
+    let center = vec2(spot.x, spot.y);
+    let mut shapes = ShapeBatch::new();
+    shapes.circle(center, spot.r, Color::new(0.0, 0.0, 0.0, 0.15));
+    shapes.ring(center, spot.r, 3.0, PRINT_COLOR);
+    shapes.ring(center, spot.r - 6.0, 1.0, TRIM_COLOR);
+    if bet == 0 {
+        shapes.draw();
+        let caption_width = measure_text_cached("BET", None, 20).width;
+        draw_text("BET", spot.x - caption_width / 2.0, spot.y + 7.0, 20.0, PRINT_COLOR);
+        return;
+    }
+    shapes.draw();
+    chip::draw_stacks(atlas, center, spot.r * 0.6, bet);
+    let text = i18n::money(bet);
+    let text_width = measure_text_cached(&text, None, 20).width;
+    draw_text(&text, spot.x - text_width / 2.0, spot.y - spot.r - 6.0, 20.0, WHITE);
+}
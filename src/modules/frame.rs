@@ -0,0 +1,91 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: The steps every frame of a screen goes through, in order
+
+A screen's loop (the table, the profile menu) runs each frame as:
+    1. Gather input  - Frame::begin() reads the mouse, keyboard and touch screen
+                       once (see input.rs) and keeps where every widget was last
+                       frame, to tell which one the pointer is over (see ui.rs)
+    2. Update        - the game moves on: timers, tweens, the dealer's cues
+    3. Layout        - frame.layout() picks the virtual screen and clears it, then
+                       widgets are placed and their click() / draw() queue them
+    4. Draw          - frame.end() draws the layers (see layers.rs) and shows the frame
+
+Widgets only queue their drawing in step 3, and are drawn in step 4 by layer
+and depth rather than in the order they were queued, and which one takes a
+click is decided by where they were drawn last frame. So the order the
+click() calls run in doesn't change what ends up on top or what gets clicked,
+a button pressed before its panel is drawn still sits on the panel.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod input;
+    pub mod layers;
+    pub mod scale;
+    pub mod ui;
+    pub mod frame;
+
+Then add the following with the use commands:
+use crate::modules::frame::Frame;
+
+Usage examples:
+1. A screen's loop:
+    loop {
+        let frame = Frame::begin();
+        game.update(get_frame_time());
+        frame.layout(layout.virtual_width, layout.virtual_height, theme::felt_color());
+        if btn_deal.click() {
+            deal();
+        }
+        frame.end().await;
+    }
+
+2. Read the frame back before it goes to the screen:
+    frame.draw();
+    capture_screenshot();
+    frame.show().await;
+
+3. A panel that takes the screen over this frame, the rest of the loop is skipped:
+    if settings_open {
+        draw_panel(...);
+        frame.end().await;
+        continue;
+    }
+*/
+use macroquad::prelude::*;
+use crate::modules::input;
+use crate::modules::layers;
+use crate::modules::scale::use_virtual_resolution;
+
+pub struct Frame;
+
+impl Frame {
+    // Step 1: read the devices for this frame. Call before anything reads input.
+    pub fn begin() -> Self {
+        input::begin_frame();
+        Self
+    }
+
+    // Step 3: draw on a `width` by `height` virtual screen, cleared to `background`
+    pub fn layout(&self, width: f32, height: f32, background: Color) {
+        use_virtual_resolution(width, height);
+        clear_background(background);
+    }
+
+    // Step 4: draw what was queued, by layer and depth, and wait for the next frame
+    pub async fn end(&self) {
+        self.draw();
+        self.show().await;
+    }
+
+    // end() in two halves, for reading back the drawn frame (a screenshot) before it is shown
+    pub fn draw(&self) {
+        layers::flush();
+    }
+
+    pub async fn show(&self) {
+        next_frame().await;
+    }
+}
@@ -0,0 +1,110 @@
+/*
+By: <tyler>
+Date: 2025-12-05
+Program Details: Frame rate cap and slowing down while nothing is happening
+
+The game draws every frame from scratch, even when the screen would come out
+exactly the same. To save battery a scene can say it has nothing moving on its
+own by calling declare_static() while it runs. If the frame was static and
+nothing marked it dirty, the pacer waits long enough to only draw IDLE_FPS
+frames a second. A frame is dirty when:
+    - the player touched the mouse, keyboard or screen
+    - a Tween moved (modules::animation marks it), or anything else called mark_dirty()
+The next frame after that runs at full speed again. Both flags reset every frame.
+
+The same wait also does the player's frame rate cap. Waiting means sleeping the
+thread, which the browser doesn't allow, so on the web this does nothing and
+the browser paces the frames itself.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod scale;
+    pub mod input;
+    pub mod frame_pacer;
+
+Then add the following with the use commands:
+use crate::modules::frame_pacer::{self, FramePacer};
+
+Usage examples:
+1. Create one before the loop and wait at the top of every frame, with an optional cap:
+    let mut pacer = FramePacer::new();
+    loop {
+        pacer.wait(settings.min_frame_seconds());
+        ...
+    }
+
+2. In a scene that only changes when the player does something:
+    frame_pacer::declare_static();
+
+3. Something changed that the pacer can't see (a timer ran out, a download finished):
+    frame_pacer::mark_dirty();
+
+4. Turn idle slow down off (the cap still works):
+    frame_pacer::set_power_saving(false);
+
+5. How long the last frame really took, for watching the frame rate:
+    let seconds = pacer.busy_seconds();
+*/
+use macroquad::prelude::*;
+use std::cell::Cell;
+use crate::modules::input::had_input;
+
+pub const IDLE_FPS: f64 = 10.0;
+
+thread_local! {
+    static DIRTY: Cell<bool> = const { Cell::new(false) };
+    static STATIC_SCENE: Cell<bool> = const { Cell::new(false) };
+    static POWER_SAVING: Cell<bool> = const { Cell::new(true) };
+}
+
+// Something on screen changed this frame, draw the next one at full speed
+pub fn mark_dirty() {
+    DIRTY.with(|dirty| dirty.set(true));
+}
+
+// The current scene only changes when the player does something
+pub fn declare_static() {
+    STATIC_SCENE.with(|scene| scene.set(true));
+}
+
+pub fn set_power_saving(on: bool) {
+    POWER_SAVING.with(|saving| saving.set(on));
+}
+
+pub struct FramePacer {
+    frame_start: f64,
+    busy: f64, // How long the last frame took before the wait, see busy_seconds()
+}
+
+impl FramePacer {
+    pub fn new() -> Self {
+        Self { frame_start: get_time(), busy: 0.0 }
+    }
+
+    // How long the last frame took without the pacer's own sleeping: the game's work plus
+    // whatever next_frame() waited for (vsync, or the browser's next animation frame)
+    pub fn busy_seconds(&self) -> f64 {
+        self.busy
+    }
+
+    // Sleep away what is left of the last frame. `min_frame_seconds` is the frame rate cap,
+    // 0 for none. Idle static frames stretch to 1 / IDLE_FPS.
+    pub fn wait(&mut self, min_frame_seconds: f64) {
+        self.busy = get_time() - self.frame_start;
+        let was_static = STATIC_SCENE.with(|scene| scene.replace(false));
+        let was_dirty = DIRTY.with(|dirty| dirty.replace(false));
+        let idle = POWER_SAVING.with(|saving| saving.get()) && was_static && !was_dirty && !had_input();
+        let frame_seconds = if idle { min_frame_seconds.max(1.0 / IDLE_FPS) } else { min_frame_seconds };
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let spare = frame_seconds - self.busy;
+            if spare > 0.0 {
+                std::thread::sleep(std::time::Duration::from_secs_f64(spare));
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        let _ = frame_seconds;
+        self.frame_start = get_time();
+    }
+}
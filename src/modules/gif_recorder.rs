@@ -0,0 +1,146 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Keeps the last few seconds of play and saves them as an animated GIF
+
+A novelty (and handy for bug reports): the recorder grabs the screen a few times
+a second, shrinks it to GIF_WIDTH pixels across and keeps the last
+CLIP_SECONDS of frames in a ring buffer. Saving writes them out as a looping
+GIF next to the game, named like the screenshots (blackjack-<time>.gif).
+
+After a big win (the round paid at least BIG_WIN_RATIO times what was bet, a
+blackjack or better) it says so once through take_big_win(), so the table can
+offer to save the clip.
+
+It costs memory (about 300 KB a frame, so around 30 MB when full) and a screen
+read every capture, so it is only built with the gif_recorder feature, and only
+for native builds:
+    cargo run --features gif_recorder
+Encoding takes a moment and the game waits for it.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod screenshot;
+    pub mod events;
+    #[cfg(all(feature = "gif_recorder", not(target_arch = "wasm32")))]
+    pub mod gif_recorder;
+In the Cargo.toml file add the following:
+    [features]
+    gif_recorder = ["image/gif"]
+
+Then add the following with the use commands:
+use crate::modules::gif_recorder::GifRecorder;
+
+Usage examples:
+1. Create it before the loop and pass it the game events (for big wins):
+    let mut recorder = GifRecorder::new();
+    bus.dispatch(&mut [&mut recorder]);
+
+2. Capture after everything has been drawn, right before next_frame():
+    layers::flush();
+    recorder.capture();
+
+3. Offer to save after a big win, and save when asked:
+    if recorder.take_big_win() {
+        println!("Big win! Press F9 to save it");
+    }
+    match recorder.save() {
+        Ok(name) => println!("Saved {}", name),
+        Err(err) => println!("GIF failed: {}", err),
+    }
+*/
+use macroquad::prelude::*;
+use std::collections::VecDeque;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage};
+use crate::modules::events::{EventListener, GameEvent};
+use crate::modules::screenshot::timestamp;
+
+const CLIP_SECONDS: f64 = 10.0;
+const FRAMES_PER_SECOND: f64 = 10.0;
+const GIF_WIDTH: u32 = 320;
+const ENCODE_SPEED: i32 = 10; // 1 (best colours) to 30 (fastest)
+const BIG_WIN_RATIO: f64 = 1.5;
+
+struct RecordedFrame {
+    time: f64,
+    image: RgbaImage,
+}
+
+pub struct GifRecorder {
+    frames: VecDeque<RecordedFrame>,
+    last_capture: f64,
+    staked: i64, // The round's bets, from BetPlaced
+    big_win: bool,
+}
+
+impl GifRecorder {
+    pub fn new() -> Self {
+        Self { frames: VecDeque::new(), last_capture: f64::NEG_INFINITY, staked: 0, big_win: false }
+    }
+
+    // Grab the screen if it's time for another frame. Call after everything is drawn.
+    pub fn capture(&mut self) {
+        let now = get_time();
+        if now - self.last_capture < 1.0 / FRAMES_PER_SECOND {
+            return;
+        }
+        self.last_capture = now;
+        self.frames.push_back(RecordedFrame { time: now, image: shrink(&get_screen_data()) });
+        while self.frames.front().is_some_and(|frame| now - frame.time > CLIP_SECONDS) {
+            self.frames.pop_front();
+        }
+    }
+
+    // True once after each big win
+    pub fn take_big_win(&mut self) -> bool {
+        std::mem::take(&mut self.big_win)
+    }
+
+    // Write what's in the buffer to a GIF, returns the file name on success
+    pub fn save(&self) -> Result<String, String> {
+        if self.frames.len() < 2 {
+            return Err("nothing recorded yet".to_string());
+        }
+        let name = format!("blackjack-{}.gif", timestamp());
+        let file = std::fs::File::create(&name).map_err(|err| err.to_string())?;
+        let mut encoder = GifEncoder::new_with_speed(std::io::BufWriter::new(file), ENCODE_SPEED);
+        encoder.set_repeat(Repeat::Infinite).map_err(|err| err.to_string())?;
+        // Each frame stays up until the next one was captured, the last for an ordinary gap
+        let times: Vec<f64> = self.frames.iter().map(|frame| frame.time).collect();
+        for (i, frame) in self.frames.iter().enumerate() {
+            let shown_for = times.get(i + 1).map_or(1.0 / FRAMES_PER_SECOND, |next| next - frame.time);
+            let delay = Delay::from_numer_denom_ms((shown_for * 1000.0).round() as u32, 1);
+            encoder.encode_frame(Frame::from_parts(frame.image.clone(), 0, 0, delay)).map_err(|err| err.to_string())?;
+        }
+        Ok(name)
+    }
+}
+
+impl EventListener for GifRecorder {
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::BetPlaced { amount } => self.staked = *amount,
+            GameEvent::BetSettled { change } => {
+                self.big_win = self.staked > 0 && *change as f64 >= self.staked as f64 * BIG_WIN_RATIO;
+            }
+            _ => {}
+        }
+    }
+}
+
+// Shrink the screen to GIF_WIDTH across (nearest pixel), flipping it the right way up
+// on the way, the GPU hands rows back bottom-up.
+fn shrink(screen: &Image) -> RgbaImage {
+    let (width, height) = (screen.width as u32, screen.height as u32);
+    let out_width = GIF_WIDTH.min(width).max(1);
+    let out_height = (height * out_width / width.max(1)).max(1);
+    RgbaImage::from_fn(out_width, out_height, |x, y| {
+        let source_x = (x * width / out_width).min(width - 1);
+        let source_y = height - 1 - (y * height / out_height).min(height - 1);
+        let i = ((source_y * width + source_x) * 4) as usize;
+        // Opaque, the cleared screen can have any alpha and GIF only has on or off
+        image::Rgba([screen.bytes[i], screen.bytes[i + 1], screen.bytes[i + 2], 255])
+    })
+}
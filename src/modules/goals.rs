@@ -0,0 +1,149 @@
+/*
+By: <tyler>
+Date: 2025-12-10
+Program Details: Session goals the player can pick for a sitting
+
+A goal is something to aim for this session ("win 10 hands", "finish up $500").
+None are on until the player picks them on the Achievements screen, and they can
+only be picked between rounds. Progress comes from the event bus like the stats
+do, so the game code never updates a goal itself. A goal finished is queued
+(take_completed) so the table can announce it, and its stars go into the
+profile's rewards ledger (see achievements.rs). Goals aren't saved, every
+session starts with none picked.
+
+Progress for "in a row" goals goes back to 0 when the run is broken, and the
+money goal goes down as well as up. A goal only finishes once a session.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod events;
+    pub mod goals;
+
+Then add the following with the use commands:
+use crate::modules::goals::{SessionGoals, GOALS};
+
+Usage examples:
+1. Create it before the loop and pass it to the event bus every frame:
+    let mut goals = SessionGoals::new();
+    bus.dispatch(&mut [&mut goals]);
+
+2. Pick a goal, or drop it again:
+    goals.toggle("win_hands");
+
+3. Show how far along each goal is:
+    for goal in &GOALS {
+        println!("{} {}", goal.name, goals.progress_text(goal.id));
+    }
+
+4. Announce finished goals and pay their stars into the ledger:
+    for goal in goals.take_completed() {
+        profile.achievements.reward(goal.id, goal.stars);
+    }
+*/
+use crate::modules::events::{EventListener, GameEvent, Outcome};
+use crate::modules::i18n;
+
+#[derive(Debug)]
+pub struct Goal {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub target: i64,
+    pub stars: u32, // Paid into the rewards ledger when the goal is finished
+}
+
+pub const GOALS: [Goal; 5] = [
+    Goal { id: "win_hands", name: "Win 10 hands", target: 10, stars: 10 },
+    Goal { id: "blackjacks", name: "Get 2 blackjacks", target: 2, stars: 15 },
+    Goal { id: "win_streak", name: "Win 3 hands in a row", target: 3, stars: 15 },
+    Goal { id: "no_bust", name: "10 hands in a row without a bust", target: 10, stars: 10 },
+    Goal { id: "up_500", name: "Finish up $500", target: 500, stars: 25 },
+];
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Progress {
+    picked: bool,
+    value: i64,
+    done: bool,
+}
+
+pub struct SessionGoals {
+    progress: [Progress; GOALS.len()], // Same order as GOALS
+    completed: Vec<&'static Goal>,
+}
+
+impl SessionGoals {
+    pub fn new() -> Self {
+        Self { progress: [Progress::default(); GOALS.len()], completed: Vec::new() }
+    }
+
+    fn index(id: &str) -> Option<usize> {
+        GOALS.iter().position(|goal| goal.id == id)
+    }
+
+    // Pick a goal, or drop it. Picking it again starts it from the beginning.
+    pub fn toggle(&mut self, id: &str) {
+        if let Some(index) = Self::index(id) {
+            let picked = !self.progress[index].picked;
+            self.progress[index] = Progress { picked, ..Default::default() };
+        }
+    }
+
+    pub fn is_picked(&self, id: &str) -> bool {
+        Self::index(id).is_some_and(|index| self.progress[index].picked)
+    }
+
+    // "3/10", "Done" or an empty string for a goal that isn't picked
+    pub fn progress_text(&self, id: &str) -> String {
+        let Some(index) = Self::index(id) else {
+            return String::new();
+        };
+        let progress = self.progress[index];
+        let goal = &GOALS[index];
+        if !progress.picked {
+            String::new()
+        } else if progress.done {
+            "Done".to_string()
+        } else if goal.id == "up_500" {
+            let sign = if progress.value < 0 { "-" } else { "+" };
+            format!("{}{} of {}", sign, i18n::money(progress.value.abs()), i18n::money(goal.target))
+        } else {
+            format!("{}/{}", progress.value, goal.target)
+        }
+    }
+
+    // Goals finished since the last call, oldest first
+    pub fn take_completed(&mut self) -> Vec<&'static Goal> {
+        std::mem::take(&mut self.completed)
+    }
+}
+
+impl EventListener for SessionGoals {
+    // Move every picked goal on by what happened
+    fn on_event(&mut self, event: &GameEvent) {
+        for (goal, progress) in GOALS.iter().zip(self.progress.iter_mut()) {
+            if !progress.picked || progress.done {
+                continue;
+            }
+            match (goal.id, event) {
+                ("win_hands", GameEvent::RoundSettled { outcome: Outcome::PlayerWin, .. }) => progress.value += 1,
+                ("blackjacks", GameEvent::RoundSettled { blackjack: true, .. }) => progress.value += 1,
+                // A push doesn't end a run of wins, the same as the stats streak
+                ("win_streak", GameEvent::RoundSettled { outcome, .. }) => match outcome {
+                    Outcome::PlayerWin => progress.value += 1,
+                    Outcome::Push => {}
+                    Outcome::DealerWin | Outcome::NoWinner => progress.value = 0,
+                },
+                ("no_bust", GameEvent::RoundSettled { player_total, .. }) => {
+                    progress.value = if *player_total > 21 { 0 } else { progress.value + 1 };
+                }
+                ("up_500", GameEvent::BetSettled { change }) => progress.value += change,
+                _ => continue,
+            }
+            if progress.value >= goal.target {
+                progress.done = true;
+                self.completed.push(goal);
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+/*
+By: <tyler>
+Date: 2025-11-22
+Program Details: A blackjack hand and how two hands are compared
+
+Aces count 11 unless that would bust the hand, then they drop to 1. A hand
+that still has an ace counting 11 is "soft".
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod cards;
+    pub mod i18n;
+    pub mod hand;
+
+Then add the following with the use commands:
+use crate::modules::hand::{Hand, Outcome, dealer_can_change_results, explain, settle, settle_hands};
+
+Usage examples:
+1. Build a hand as cards are dealt:
+    let mut hand = Hand::new();
+    hand.add(shoe.deal());
+
+2. Read the total:
+    let total = hand.value();
+    if hand.is_bust() { ... }
+    if hand.is_soft() { ... }
+
+3. Work out who won, and why:
+    let outcome = settle(&player_hand, &dealer_hand);
+    let reason = explain(&player_hand, &dealer_hand); // "Dealer 19 beats your 18"
+
+4. Settle every hand at once, ready for Bet::settle:
+    let results = settle_hands(&player_hands, &dealer_hand);
+
+5. Skip the dealer's draw when it can't change what anyone wins or loses:
+    if !dealer_can_change_results(&player_hands, &dealer_hand, hole_card.is_some()) {
+        let results = settle_hands(&player_hands, &dealer_hand);
+    }
+*/
+use crate::modules::cards::{Card, Rank};
+use crate::modules::i18n::tr;
+
+// Most hands one player can play in a round
+pub const MAX_HANDS: usize = 3;
+
+// How a round ended, from the player's point of view
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    PlayerWin,
+    DealerWin,
+    Push,
+    NoWinner, // Both busted
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Hand {
+    cards: Vec<Card>,
+}
+
+impl Hand {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Make a hand from cards that were already dealt
+    pub fn from_cards(cards: &[Card]) -> Self {
+        Self { cards: cards.to_vec() }
+    }
+
+    pub fn add(&mut self, card: Card) {
+        self.cards.push(card);
+    }
+
+    pub fn clear(&mut self) {
+        self.cards.clear();
+    }
+
+    #[allow(unused)]
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    #[allow(unused)]
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    #[allow(unused)]
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    // Total with aces counted as 11 until that would bust, plus how many aces still count 11
+    fn total_and_soft_aces(&self) -> (u32, u32) {
+        let mut total: u32 = self.cards.iter().map(|card| card.value()).sum();
+        let mut soft_aces = self.cards.iter().filter(|card| card.rank == Rank::Ace).count() as u32;
+        while total > 21 && soft_aces > 0 {
+            total -= 10;
+            soft_aces -= 1;
+        }
+        (total, soft_aces)
+    }
+
+    // Best total for the hand
+    pub fn value(&self) -> u32 {
+        self.total_and_soft_aces().0
+    }
+
+    // True if an ace is still being counted as 11
+    #[allow(unused)]
+    pub fn is_soft(&self) -> bool {
+        self.total_and_soft_aces().1 > 0
+    }
+
+    pub fn is_bust(&self) -> bool {
+        self.value() > 21
+    }
+
+    // Ace and a ten-value card as the first two cards
+    #[allow(unused)]
+    pub fn is_blackjack(&self) -> bool {
+        self.cards.len() == 2 && self.value() == 21
+    }
+}
+
+// Compare a finished player hand against the dealer's hand. A blackjack beats any other 21,
+// two blackjacks push.
+pub fn settle(player: &Hand, dealer: &Hand) -> Outcome {
+    let player_total = player.value();
+    let dealer_total = dealer.value();
+    if player.is_bust() && dealer.is_bust() {
+        Outcome::NoWinner
+    } else if player.is_bust() {
+        Outcome::DealerWin
+    } else if player.is_blackjack() != dealer.is_blackjack() {
+        if player.is_blackjack() { Outcome::PlayerWin } else { Outcome::DealerWin }
+    } else if dealer.is_bust() || player_total > dealer_total {
+        Outcome::PlayerWin
+    } else if dealer_total > player_total {
+        Outcome::DealerWin
+    } else {
+        Outcome::Push
+    }
+}
+
+// Each hand's outcome against the dealer, and whether a win pays the blackjack payout
+pub fn settle_hands(player_hands: &[Hand], dealer_hand: &Hand) -> Vec<(Outcome, bool)> {
+    player_hands.iter().map(|hand| (settle(hand, dealer_hand), hand.is_blackjack())).collect()
+}
+
+// Whether the dealer's cards can still change what any hand wins or loses. A bust loses its bet
+// whatever the dealer ends on (NoWinner pays the same as DealerWin). A blackjack wins unless the
+// dealer has one too, which is known once the dealer has a second card (`hole_card`, dealt face
+// down and already peeked at) or when the up card can't make one.
+pub fn dealer_can_change_results(player_hands: &[Hand], dealer_hand: &Hand, hole_card: bool) -> bool {
+    let dealer_blackjack_possible = matches!(dealer_hand.cards(), [up] if !hole_card && up.value() >= 10);
+    player_hands.iter().any(|hand| !hand.is_bust() && (!hand.is_blackjack() || dealer_blackjack_possible))
+}
+
+// One line saying why the round went the way settle() decided, for under the result banner
+pub fn explain(player: &Hand, dealer: &Hand) -> String {
+    let player_total = player.value();
+    let dealer_total = dealer.value();
+    if player.is_bust() && dealer.is_bust() {
+        tr("explain.both_bust", &[&player_total, &dealer_total])
+    } else if player.is_bust() {
+        tr("explain.player_bust", &[&player_total])
+    } else if dealer.is_bust() {
+        tr("explain.dealer_bust", &[&dealer_total, &player_total])
+    } else if player.is_blackjack() && !dealer.is_blackjack() {
+        tr("explain.player_blackjack", &[&dealer_total])
+    } else if dealer.is_blackjack() && !player.is_blackjack() {
+        tr("explain.dealer_blackjack", &[&player_total])
+    } else if player_total > dealer_total {
+        tr("explain.player_beats", &[&player_total, &dealer_total])
+    } else if dealer_total > player_total {
+        tr("explain.dealer_beats", &[&dealer_total, &player_total])
+    } else {
+        tr("explain.push", &[&player_total])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand(codes: &[&str]) -> Hand {
+        Hand::from_cards(&codes.iter().map(|code| Card::parse(code).unwrap()).collect::<Vec<Card>>())
+    }
+
+    #[test]
+    fn blackjack_beats_a_drawn_21() {
+        let blackjack = hand(&["AS", "KH"]);
+        let drawn = hand(&["7C", "4D", "QS"]);
+        assert_eq!(settle(&blackjack, &drawn), Outcome::PlayerWin);
+        assert_eq!(settle(&drawn, &blackjack), Outcome::DealerWin);
+        assert_eq!(settle(&blackjack, &hand(&["AD", "JC"])), Outcome::Push);
+    }
+
+    #[test]
+    fn blackjacks_and_busts_skip_the_draw() {
+        let hands = [hand(&["AS", "KH"]), hand(&["TC", "6D", "9S"]), hand(&["QD", "AH"])];
+        let six = hand(&["6H"]);
+        let ten = hand(&["TH"]);
+        assert!(!dealer_can_change_results(&hands, &six, false));
+        // A dealer without a hole card can still draw a blackjack of their own under a ten
+        assert!(dealer_can_change_results(&hands, &ten, false));
+        assert!(!dealer_can_change_results(&hands, &ten, true));
+        assert!(dealer_can_change_results(&[hand(&["TC", "8D"])], &six, true));
+    }
+}
@@ -0,0 +1,120 @@
+/*
+By: <tyler>
+Date: 2025-11-28
+Program Details: Small badge showing a hand's total, stuck to the hand's last card
+
+- Soft hands show both totals, e.g. "7/17" for an ace and a six. The total can
+  also be spelled out, "Soft 17 (7/17)", for players still learning how an ace
+  counts, or kept to the best total, "17" (set_total_style, from the Display settings).
+- The badge turns gold on 21 and red on a bust.
+- It moves to the newest card every time the hand is shown, and hides when the
+  hand is empty.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod label;
+    pub mod hand;
+    pub mod hand_badge;
+
+Then add the following with the use commands:
+use crate::modules::hand_badge::HandBadge;
+
+Usage examples:
+1. Create one per hand before the loop:
+    let mut player_badge = HandBadge::new();
+
+2. Whenever the hand changes, pass the top-right corner of its last card:
+    let last = layout.player_card_pos(player_hand.len().max(1) - 1);
+    player_badge.show(&player_hand, last + vec2(layout.card_width, 0.0));
+
+3. In the loop:
+    player_badge.draw();
+
+4. Spell soft totals out on every badge (show the hands again to update them):
+    hand_badge::set_total_style(TotalStyle::Spelled);
+*/
+use macroquad::prelude::*;
+use std::cell::Cell;
+use crate::modules::hand::Hand;
+use crate::modules::label::Label;
+use crate::modules::widget_layout::Placeable;
+
+const FONT_SIZE: u16 = 28;
+const OVERLAP: f32 = 24.0; // How far the badge sits in over the card's corner
+
+// How a soft hand's total is written
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TotalStyle {
+    Both,    // 7/17
+    Spelled, // Soft 17 (7/17)
+    Best,    // 17
+}
+
+thread_local! {
+    static TOTAL_STYLE: Cell<TotalStyle> = const { Cell::new(TotalStyle::Both) };
+}
+
+pub fn set_total_style(style: TotalStyle) {
+    TOTAL_STYLE.with(|current| current.set(style));
+}
+
+pub struct HandBadge {
+    label: Label,
+}
+
+impl HandBadge {
+    pub fn new() -> Self {
+        let mut label = Label::new("", 0.0, 0.0, FONT_SIZE);
+        label.with_round(8.0).with_border(WHITE, 2.0).set_visible(false);
+        Self { label }
+    }
+
+    // Show the hand's total next to `corner`, the top-right corner of its last card
+    pub fn show(&mut self, hand: &Hand, corner: Vec2) {
+        if hand.is_empty() {
+            self.label.set_visible(false);
+            return;
+        }
+        let text = badge_text(hand);
+        if self.label.get_text() != text {
+            self.label.set_text(text);
+        }
+        let (foreground, background) = if hand.is_bust() {
+            (WHITE, RED)
+        } else if hand.value() == 21 {
+            (BLACK, GOLD)
+        } else {
+            (WHITE, Color::new(0.0, 0.0, 0.0, 0.75))
+        };
+        self.label.with_colors(foreground, Some(background));
+        // By the badge's top-left corner, the text's baseline is lower the bigger the text is
+        self.label.place(corner.x - OVERLAP, corner.y - OVERLAP / 2.0);
+        self.label.set_visible(true);
+    }
+
+    // Measure the badge again after the text scale changed, show() puts it back in place
+    pub fn rescale_text(&mut self) {
+        self.label.rescale_text();
+    }
+
+    pub fn draw(&self) {
+        self.label.draw();
+    }
+}
+
+// "17", or while an ace still counts as 11 "7/17" or "Soft 17 (7/17)" (a soft 21 just shows 21)
+pub fn badge_text(hand: &Hand) -> String {
+    total_text(hand.value(), hand.is_soft(), TOTAL_STYLE.with(Cell::get))
+}
+
+fn total_text(total: u32, soft: bool, style: TotalStyle) -> String {
+    if !soft || total >= 21 {
+        return total.to_string();
+    }
+    match style {
+        TotalStyle::Both => format!("{}/{}", total - 10, total),
+        TotalStyle::Spelled => format!("Soft {} ({}/{})", total, total - 10, total),
+        TotalStyle::Best => total.to_string(),
+    }
+}
@@ -0,0 +1,126 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Pictures of finished hands, for thumbnails and the hand details panel
+
+A snapshot is one texture with the whole hand on it: the dealer's cards along
+the top, the player's along the bottom, overlapping a little like they do on the
+table, on a strip of felt edged in the result's colour. The cards are laid out
+once as StillImages and drawn into the texture (still_image::compose), so
+showing a hand again, small or large, is one texture and the live card sprites
+on the table are never touched.
+
+Snapshots are kept by hand number, at most MAX_SNAPSHOTS of them, and are
+dropped when the card style changes so they are drawn again in the new style.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod still_image;
+    pub mod preload_image;
+    pub mod history;
+    pub mod hand_snapshot;
+
+Then add the following with the use commands:
+use crate::modules::hand_snapshot::HandSnapshots;
+
+Usage examples:
+1. Create it before the loop:
+    let mut snapshots = HandSnapshots::new();
+
+2. Get a hand's picture (drawn the first time it is asked for) and show it at any size:
+    let texture = snapshots.get(&tm, record);
+    draw_texture_ex(&texture, x, y, WHITE, DrawTextureParams { dest_size: Some(vec2(150.0, 100.0)), ..Default::default() });
+
+3. The cards look different now, draw them again next time:
+    snapshots.clear();
+*/
+use std::collections::{HashMap, VecDeque};
+use macroquad::prelude::*;
+use crate::modules::cards::Card;
+use crate::modules::events::Outcome;
+use crate::modules::history::HandRecord;
+use crate::modules::preload_image::{TextureEntry, TextureManager};
+use crate::modules::still_image::{self, StillImage, TransparencyMask};
+
+pub const SNAPSHOT_SIZE: Vec2 = vec2(480.0, 300.0);
+const MAX_SNAPSHOTS: usize = 20;
+const CARD_SIZE: Vec2 = vec2(88.0, 128.0);
+const CARD_STEP: f32 = 56.0; // How far right each card sits from the one before, less than a card so they overlap
+const MARGIN: f32 = 14.0;
+const EDGE: f32 = 6.0;
+const FELT: Color = Color::new(0.0, 0.33, 0.16, 1.0);
+
+pub struct HandSnapshots {
+    textures: HashMap<u32, Texture2D>,
+    order: VecDeque<u32>, // Hand numbers, oldest snapshot first
+}
+
+impl HandSnapshots {
+    pub fn new() -> Self {
+        Self { textures: HashMap::new(), order: VecDeque::new() }
+    }
+
+    // The hand's picture, drawn now if it hasn't been yet
+    pub fn get(&mut self, tm: &TextureManager, record: &HandRecord) -> Texture2D {
+        if let Some(texture) = self.textures.get(&record.number) {
+            return texture.clone();
+        }
+        let texture = render(tm, record);
+        self.textures.insert(record.number, texture.clone());
+        self.order.push_back(record.number);
+        while self.order.len() > MAX_SNAPSHOTS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.textures.remove(&oldest);
+            }
+        }
+        texture
+    }
+
+    // Forget every snapshot, for when the cards are drawn differently
+    pub fn clear(&mut self) {
+        self.textures.clear();
+        self.order.clear();
+    }
+}
+
+// Lay the hand out as images and draw them into a new texture
+fn render(tm: &TextureManager, record: &HandRecord) -> Texture2D {
+    let edge = edge_color(record.outcome);
+    // The edge is the background, the felt is a slightly smaller image on top of it
+    let mut images = vec![felt_image()];
+    images.extend(card_row(tm, &record.dealer_cards, MARGIN));
+    images.extend(card_row(tm, &record.player_cards, SNAPSHOT_SIZE.y - MARGIN - CARD_SIZE.y));
+    still_image::compose(&images, SNAPSHOT_SIZE.x, SNAPSHOT_SIZE.y, edge)
+}
+
+// Plain felt filling all but the edge, a one pixel texture stretched out
+fn felt_image() -> StillImage {
+    let pixel: [u8; 4] = FELT.into();
+    let texture = Texture2D::from_rgba8(1, 1, &pixel);
+    let size = SNAPSHOT_SIZE - vec2(EDGE, EDGE) * 2.0;
+    let mut image = StillImage::from_preload(TextureEntry::new(texture, TransparencyMask::none(), ""), size.x, size.y);
+    image.set_position(vec2(EDGE, EDGE));
+    image
+}
+
+// A row of cards starting MARGIN in from the left, `y` down from the top
+fn card_row(tm: &TextureManager, cards: &[Card], y: f32) -> Vec<StillImage> {
+    cards
+        .iter()
+        .enumerate()
+        .filter_map(|(i, card)| {
+            let mut image = StillImage::from_preload(tm.get_preload(&card.texture_path())?, CARD_SIZE.x, CARD_SIZE.y);
+            image.set_position(vec2(MARGIN + CARD_STEP * i as f32, y));
+            Some(image)
+        })
+        .collect()
+}
+
+fn edge_color(outcome: Option<Outcome>) -> Color {
+    match outcome {
+        Some(Outcome::PlayerWin) => GREEN,
+        Some(Outcome::Push) => GRAY,
+        _ => RED,
+    }
+}
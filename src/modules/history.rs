@@ -0,0 +1,125 @@
+/*
+By: <tyler>
+Date: 2025-11-29
+Program Details: History of the hands played this session
+
+Listens to the event bus and keeps a record of every hand: the cards each seat
+got, any decisions the player made along the way (even money, ...) and how the
+hand ended. When the player plays several hands in a round each one gets its
+own record, sharing the dealer's cards. Only the most recent MAX_RECORDS are kept.
+
+Each record also keeps where in the shoe the round started (the shuffle seed and
+how many cards were already gone, see shoe.rs), so the game can be started there
+again to deal the same cards. Rounds whose cards were set up for a famous hand
+or a drill don't match their seed, they have none.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod events;
+    pub mod shoe;
+    pub mod history;
+
+Then add the following with the use commands:
+use crate::modules::history::HandHistory;
+
+Usage examples:
+1. Create it before the loop and pass it to the event bus:
+    let mut history = HandHistory::new();
+    bus.dispatch(&mut [&mut history]);
+
+2. As each round is dealt, tell it where the shoe is (None when the cards were set up):
+    history.start_round(Some(shoe.position()));
+
+3. Read the finished hands, newest last:
+    for hand in history.hands() {
+        println!("#{} {:?} {:?}", hand.number, hand.outcome, hand.decisions);
+    }
+*/
+use std::collections::VecDeque;
+use crate::modules::cards::Card;
+use crate::modules::events::{EventListener, GameEvent, Outcome, Seat};
+use crate::modules::shoe::ShoePosition;
+
+const MAX_RECORDS: usize = 100;
+
+#[derive(Clone, Debug, Default)]
+pub struct HandRecord {
+    pub number: u32,
+    pub player_cards: Vec<Card>,
+    pub dealer_cards: Vec<Card>,
+    pub decisions: Vec<String>, // In the order they were made, e.g. "Even money: declined"
+    pub outcome: Option<Outcome>,
+    pub shoe: Option<ShoePosition>, // Where the round's first card came from, None if the cards were set up
+}
+
+pub struct HandHistory {
+    hands: VecDeque<HandRecord>,
+    current: HandRecord,         // Dealer cards and decisions for the round being played
+    current_hands: Vec<Vec<Card>>, // Each player hand's cards this round
+    next_number: u32,
+}
+
+impl HandHistory {
+    pub fn new() -> Self {
+        Self { hands: VecDeque::new(), current: HandRecord::default(), current_hands: Vec::new(), next_number: 1 }
+    }
+
+    // Finished hands, oldest first
+    #[allow(unused)]
+    pub fn hands(&self) -> impl Iterator<Item = &HandRecord> {
+        self.hands.iter()
+    }
+
+    // The round being played right now. Its player_cards are the first hand's.
+    #[allow(unused)]
+    pub fn current(&self) -> HandRecord {
+        HandRecord { player_cards: self.current_hands.first().cloned().unwrap_or_default(), ..self.current.clone() }
+    }
+
+    // A round is being dealt, starting from `shoe`
+    pub fn start_round(&mut self, shoe: Option<ShoePosition>) {
+        self.current.shoe = shoe;
+    }
+
+    fn finish_hand(&mut self, hand: usize, outcome: Outcome) {
+        let record = HandRecord {
+            number: self.next_number,
+            player_cards: self.current_hands.get(hand).cloned().unwrap_or_default(),
+            outcome: Some(outcome),
+            ..self.current.clone()
+        };
+        self.next_number += 1;
+        self.hands.push_back(record);
+        if self.hands.len() > MAX_RECORDS {
+            self.hands.pop_front();
+        }
+    }
+
+    fn player_card(&mut self, hand: usize, card: Card) {
+        if self.current_hands.len() <= hand {
+            self.current_hands.resize(hand + 1, Vec::new());
+        }
+        self.current_hands[hand].push(card);
+    }
+}
+
+impl EventListener for HandHistory {
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::CardDealt { card, seat: Seat::Player(hand) } => self.player_card(*hand, *card),
+            GameEvent::CardDealt { card, seat: Seat::Dealer } => self.current.dealer_cards.push(*card),
+            GameEvent::EvenMoney { taken, timed_out } => {
+                let choice = if *taken { "taken" } else { "declined" };
+                let how = if *timed_out { " (timed out)" } else { "" };
+                self.current.decisions.push(format!("Even money: {}{}", choice, how));
+            }
+            GameEvent::RoundSettled { hand, outcome, .. } => self.finish_hand(*hand, *outcome),
+            GameEvent::RoundFinished => {
+                self.current = HandRecord::default();
+                self.current_hands.clear();
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,130 @@
+/*
+By: <tyler>
+Date: 2025-11-30
+Program Details: The dealer's face-down hole card and the peek animation
+
+While the hole card is hidden a card back is drawn over its slot. A peek lifts
+the card a little with its corner turned up, like the dealer checking under it,
+then puts it back down. Revealing it turns the card over (see CardView), once
+it has turned the real card in the slot shows through. The slot's own card
+should be left undrawn while is_turning() is true.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod animation;
+    pub mod layers;
+    pub mod card_view;
+    pub mod hole_card;
+
+Then add the following with the use commands:
+use crate::modules::hole_card::HoleCard;
+
+Usage examples:
+1. Create it before the loop:
+    let mut hole_card = HoleCard::new();
+
+2. The card goes down face down, and the dealer may peek at it:
+    hole_card.hide();
+    hole_card.start_peek();
+
+3. Every frame, move the peek along (true once when it is done) and draw over the slot:
+    if hole_card.update(get_frame_time()) {
+        // the dealer has looked
+    }
+    hole_card.draw(dealer_cards[1].pos(), dealer_cards[1].size());
+
+4. Turn it over to show the card's picture:
+    hole_card.reveal(texture_manager.get_texture(&card.texture_path()));
+*/
+use macroquad::prelude::*;
+use crate::modules::animation::{Easing, Tween};
+use crate::modules::card_view::{draw_card_back, CardView};
+use crate::modules::layers::{self, Layer};
+
+const PEEK_TIME: f32 = 0.9;
+const PEEK_LIFT: f32 = 14.0; // How far the card comes up off the table
+const CORNER_SIZE: f32 = 30.0;
+
+pub struct HoleCard {
+    hidden: bool,
+    peek: Option<Tween>,
+    card: CardView, // Turns the card over when it is revealed
+}
+
+impl HoleCard {
+    pub fn new() -> Self {
+        Self { hidden: false, peek: None, card: CardView::new(true) }
+    }
+
+    pub fn hide(&mut self) {
+        self.hidden = true;
+        self.peek = None;
+        self.card.set_face_up(false);
+    }
+
+    // Turn the card over to `front`
+    pub fn reveal(&mut self, front: Option<Texture2D>) {
+        self.hidden = false;
+        self.peek = None;
+        self.card.set_front(front);
+        self.card.flip_up();
+    }
+
+    // The card is part way through turning over
+    pub fn is_turning(&self) -> bool {
+        self.card.is_flipping()
+    }
+
+    pub fn start_peek(&mut self) {
+        self.peek = Some(Tween::new(0.0, 1.0, PEEK_TIME, Easing::Linear));
+    }
+
+    pub fn is_peeking(&self) -> bool {
+        self.peek.is_some()
+    }
+
+    // Move the peek along, returns true once when it is finished
+    pub fn update(&mut self, dt: f32) -> bool {
+        self.card.update(dt);
+        let Some(peek) = self.peek.as_mut() else {
+            return false;
+        };
+        peek.update(dt);
+        if peek.is_finished() {
+            self.peek = None;
+            return true;
+        }
+        false
+    }
+
+    // Queue the card back, or the card turning over, over the hole card's slot
+    pub fn draw(&self, pos: Vec2, size: Vec2) {
+        if size.x <= 0.0 {
+            return;
+        }
+        if !self.hidden {
+            if self.card.is_flipping() {
+                self.card.draw(pos, size, Layer::Table);
+            }
+            return;
+        }
+        // Up and back down again over the length of the peek
+        let lift = self.peek.as_ref().map_or(0.0, |peek| (peek.value() * std::f32::consts::PI).sin());
+        layers::push(Layer::Table, move || {
+            let (x, y) = (pos.x, pos.y - lift * PEEK_LIFT);
+            if lift > 0.0 {
+                draw_rectangle(pos.x + 4.0, pos.y + 4.0, size.x, size.y, Color::new(0.0, 0.0, 0.0, 0.3 * lift));
+            }
+            draw_card_back(x, y, size);
+            if lift > 0.0 {
+                // The bottom corner turned up, showing a bit of the face
+                let corner = vec2(x, y + size.y);
+                let fold = CORNER_SIZE * lift;
+                draw_triangle(corner, corner + vec2(fold, 0.0), corner - vec2(0.0, fold), WHITE);
+                draw_triangle_lines(corner, corner + vec2(fold, 0.0), corner - vec2(0.0, fold), 1.0, GRAY);
+                draw_text("Checking for blackjack", pos.x - 20.0, pos.y - PEEK_LIFT - 8.0, 20.0, Color::new(1.0, 1.0, 1.0, lift));
+            }
+        });
+    }
+}
@@ -0,0 +1,129 @@
+/*
+By: <tyler>
+Date: 2025-11-24
+Program Details: Tiny blocking HTTP client for talking to a sync server
+
+- Native: plain HTTP/1.1 over a TcpStream, so only http:// addresses work.
+  Put an https server behind a local proxy if you need TLS.
+- Web: a synchronous XMLHttpRequest through js/blackjack.js, so http:// and
+  https:// both work (the server has to allow CORS).
+
+Requests block until they finish or time out, so only make them at quiet
+moments (saving after a round, opening a profile).
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod web_bridge;
+    pub mod http;
+
+Then add the following with the use commands:
+use crate::modules::http;
+
+Usage examples:
+1. Get something:
+    match http::request("GET", "http://localhost:8080/blackjack/profiles", None) {
+        Ok(response) if response.status == 200 => println!("{}", response.body),
+        Ok(response) => println!("server said {}", response.status),
+        Err(err) => println!("offline: {}", err),
+    }
+
+2. Send something:
+    http::request("PUT", "http://localhost:8080/blackjack/profiles", Some(&text));
+*/
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+// Send a request with an optional JSON body and wait for the answer
+pub fn request(method: &str, url: &str, body: Option<&str>) -> Result<Response, String> {
+    #[cfg(target_arch = "wasm32")]
+    return crate::modules::web_bridge::http_request(method, url, body)
+        .map(|(status, body)| Response { status, body });
+
+    #[cfg(not(target_arch = "wasm32"))]
+    native::request(method, url, body)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use super::Response;
+    use std::io::{Read, Write};
+    use std::net::{TcpStream, ToSocketAddrs};
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(2);
+
+    pub fn request(method: &str, url: &str, body: Option<&str>) -> Result<Response, String> {
+        let rest = url.strip_prefix("http://").ok_or("only http:// addresses are supported")?;
+        let (host, path) = match rest.find('/') {
+            Some(slash) => (&rest[..slash], &rest[slash..]),
+            None => (rest, "/"),
+        };
+        let address = if host.contains(':') { host.to_string() } else { format!("{}:80", host) };
+        let socket = address
+            .to_socket_addrs()
+            .map_err(|err| err.to_string())?
+            .next()
+            .ok_or("could not find the server")?;
+
+        let mut stream = TcpStream::connect_timeout(&socket, TIMEOUT).map_err(|err| err.to_string())?;
+        stream.set_read_timeout(Some(TIMEOUT)).map_err(|err| err.to_string())?;
+        stream.set_write_timeout(Some(TIMEOUT)).map_err(|err| err.to_string())?;
+
+        let body = body.unwrap_or("");
+        let head = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            method,
+            path,
+            host,
+            body.len()
+        );
+        stream.write_all(head.as_bytes()).map_err(|err| err.to_string())?;
+        stream.write_all(body.as_bytes()).map_err(|err| err.to_string())?;
+
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw).map_err(|err| err.to_string())?;
+        parse_response(&raw)
+    }
+
+    fn parse_response(raw: &[u8]) -> Result<Response, String> {
+        let split = raw.windows(4).position(|window| window == b"\r\n\r\n").ok_or("bad response from server")?;
+        let head = String::from_utf8_lossy(&raw[..split]);
+        let mut body = raw[split + 4..].to_vec();
+
+        let mut lines = head.lines();
+        let status = lines
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|code| code.parse().ok())
+            .ok_or("bad status line from server")?;
+        let chunked = lines.any(|line| {
+            let line = line.to_ascii_lowercase();
+            line.starts_with("transfer-encoding:") && line.contains("chunked")
+        });
+        if chunked {
+            body = unchunk(&body)?;
+        }
+        Ok(Response { status, body: String::from_utf8_lossy(&body).to_string() })
+    }
+
+    // Join the pieces of a "Transfer-Encoding: chunked" body back together
+    fn unchunk(mut data: &[u8]) -> Result<Vec<u8>, String> {
+        let mut out = Vec::new();
+        loop {
+            let line_end = data.windows(2).position(|window| window == b"\r\n").ok_or("bad chunked body")?;
+            let size_text = String::from_utf8_lossy(&data[..line_end]);
+            let size_text = size_text.split(';').next().unwrap_or("").trim();
+            let size = usize::from_str_radix(size_text, 16).map_err(|_| "bad chunk size")?;
+            data = &data[line_end + 2..];
+            if size == 0 || data.len() < size {
+                return Ok(out);
+            }
+            out.extend_from_slice(&data[..size]);
+            data = data.get(size + 2..).unwrap_or(&[]);
+        }
+    }
+}
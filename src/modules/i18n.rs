@@ -0,0 +1,175 @@
+/*
+By: <tyler>
+Date: 2025-12-01
+Program Details: Translatable text
+
+Text shown to the player is looked up by key. English is built in, and a
+language file (assets/lang/<code>.json, a flat object of key: text) can replace
+any of it. Keys missing from the file fall back to English, and a key that is
+missing everywhere shows as itself so it is easy to spot.
+
+Text can have numbered placeholders, {0}, {1}, ... so a translation can put the
+values in whatever order its grammar needs.
+
+Money is written by money() (and signed_money(), short_money()) and nowhere
+else, so every label shows it the same way. The digits are grouped with the
+language's thousands separator (format.thousands, "," in English, a language
+file might use "." or a space) and carry the player's currency, picked in the
+settings: a symbol in front ($, €, £) or the chips unit after (format.chips).
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod i18n;
+
+Then add the following with the use commands:
+use crate::modules::i18n::{self, tr};
+
+Usage examples:
+1. Load a language before the loop (optional, English needs no file):
+    i18n::load("fr").await;
+
+2. Look up text, filling in the placeholders:
+    let text = tr("explain.dealer_beats", &[&19, &18]); // "Dealer 19 beats your 18"
+
+3. Write an amount of money:
+    i18n::set_currency(Currency::Euro);
+    let text = i18n::money(1250);         // "€1,250"
+    let text = i18n::signed_money(-25);   // "-€25"
+    let text = i18n::short_money(1000);   // "€1K", for chip faces
+*/
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt::Display;
+use macroquad::prelude::load_string;
+use nanoserde::DeJson;
+use crate::log_warn;
+
+// The built-in English text
+const ENGLISH: &[(&str, &str)] = &[
+    ("explain.both_bust", "You both busted: your {0} and the dealer's {1}"),
+    ("explain.player_bust", "You busted with {0}"),
+    ("explain.dealer_bust", "Dealer busted with {0}, your {1} wins"),
+    ("explain.player_blackjack", "Your blackjack beats the dealer's {0}"),
+    ("explain.dealer_blackjack", "Dealer's blackjack beats your {0}"),
+    ("explain.player_beats", "Your {0} beats the dealer's {1}"),
+    ("explain.dealer_beats", "Dealer {0} beats your {1}"),
+    ("explain.push", "You both have {0}, it's a push"),
+    ("explain.even_money", "You took even money on your blackjack"),
+    ("explain.hands", "Hands won: {0}, pushed: {1}, lost: {2}"),
+    ("format.thousands", ","),
+    ("format.chips", "{0} chips"),
+];
+
+// What money is counted in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Currency {
+    Dollar,
+    Euro,
+    Pound,
+    Chips,
+}
+
+// In the order the settings button steps through them
+pub const CURRENCIES: [Currency; 4] = [Currency::Dollar, Currency::Euro, Currency::Pound, Currency::Chips];
+
+impl Currency {
+    // The symbol written in front of an amount, None for chips which are written after it
+    pub fn symbol(self) -> Option<&'static str> {
+        match self {
+            Currency::Dollar => Some("$"),
+            Currency::Euro => Some("€"),
+            Currency::Pound => Some("£"),
+            Currency::Chips => None,
+        }
+    }
+
+    // The name on the settings button
+    pub fn name(self) -> &'static str {
+        match self {
+            Currency::Dollar => "Dollars",
+            Currency::Euro => "Euros",
+            Currency::Pound => "Pounds",
+            Currency::Chips => "Chips",
+        }
+    }
+}
+
+thread_local! {
+    static LOADED: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+    static CURRENCY: Cell<Currency> = const { Cell::new(Currency::Dollar) };
+}
+
+// Load a language file over the built-in English. Keeps English if the file is missing or broken.
+#[allow(unused)]
+pub async fn load(code: &str) {
+    let path = format!("assets/lang/{}.json", code);
+    let text = match load_string(&path).await {
+        Ok(text) => text,
+        Err(err) => {
+            log_warn!("could not load {}: {}", path, err);
+            return;
+        }
+    };
+    match HashMap::<String, String>::deserialize_json(&text) {
+        Ok(strings) => LOADED.with(|loaded| *loaded.borrow_mut() = strings),
+        Err(err) => log_warn!("could not parse {}: {}", path, err),
+    }
+}
+
+// The text for `key` in the current language with {0}, {1}, ... replaced by `args`
+pub fn tr(key: &str, args: &[&dyn Display]) -> String {
+    let template = LOADED
+        .with(|loaded| loaded.borrow().get(key).cloned())
+        .or_else(|| ENGLISH.iter().find(|(english_key, _)| *english_key == key).map(|(_, text)| text.to_string()))
+        .unwrap_or_else(|| key.to_string());
+    args.iter()
+        .enumerate()
+        .fold(template, |text, (i, arg)| text.replace(&format!("{{{}}}", i), &arg.to_string()))
+}
+
+// Write money in this currency from now on
+pub fn set_currency(currency: Currency) {
+    CURRENCY.with(|current| current.set(currency));
+}
+
+// "1,250", the digits grouped in threes with the language's separator
+pub fn group_digits(number: u64) -> String {
+    let digits = number.to_string();
+    let separator = tr("format.thousands", &[]);
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push_str(&separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+// "$1,250", "1,250 chips", "-$25" for a loss
+pub fn money(amount: i64) -> String {
+    let sign = if amount < 0 { "-" } else { "" };
+    format!("{}{}", sign, with_currency(group_digits(amount.unsigned_abs())))
+}
+
+// Like money() but a gain gets a "+" too, for changes to the bankroll
+pub fn signed_money(amount: i64) -> String {
+    if amount > 0 { format!("+{}", money(amount)) } else { money(amount) }
+}
+
+// "$5", "$1K", short enough for a chip's face. Chips need no unit on a chip.
+pub fn short_money(amount: i64) -> String {
+    let number = if amount >= 1000 { format!("{}K", amount / 1000) } else { amount.to_string() };
+    match CURRENCY.with(Cell::get).symbol() {
+        Some(symbol) => format!("{}{}", symbol, number),
+        None => number,
+    }
+}
+
+fn with_currency(number: String) -> String {
+    match CURRENCY.with(Cell::get).symbol() {
+        Some(symbol) => format!("{}{}", symbol, number),
+        None => tr("format.chips", &[&number]),
+    }
+}
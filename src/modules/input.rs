@@ -0,0 +1,304 @@
+/*
+By: <tyler>
+Date: 2025-12-02
+Program Details: The frame's mouse, keyboard and touch input, and double-click and long-press gestures
+
+Buttons only need a single click, but some spots on the table take more than one
+kind of press. The detector follows one pointer (the first finger on a touch
+screen, otherwise the left mouse button) and reports:
+    Tap         - pressed and let go without moving much
+    DoubleClick - a second tap close to the first, soon after it
+    LongPress   - held down in one place; reported while still held, and the
+                  release afterwards is not a tap
+The first tap of a double click is still reported as a Tap.
+
+Everything the game reads from the mouse and keyboard goes through this module
+rather than macroquad's own functions. begin_frame() takes a FrameInput snapshot
+once at the top of each frame and the functions here (is_key_pressed,
+mouse_position, get_char_pressed...) answer from it, with the same names and
+meanings as macroquad's so a module only has to import them. That gives one
+place where input can be recorded, and where a recording can be fed back in
+instead of the real devices (see input_recorder). begin_frame() also starts the
+frame's widget registry (see ui).
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod scale;
+    pub mod input;
+
+Then add the following with the use commands:
+use crate::modules::input::{Gesture, GestureDetector, GestureThresholds};
+
+Usage examples:
+1. At the top of every loop that runs frames, before anything reads input:
+    input::begin_frame();
+    if is_key_pressed(KeyCode::Escape) { ... } // from crate::modules::input, not macroquad
+
+2. Create it before the loop, the thresholds can come from the settings:
+    let mut gestures = GestureDetector::new(GestureThresholds::default());
+    gestures.set_thresholds(GestureThresholds { long_press_seconds: 1.0, ..Default::default() });
+
+3. Once a frame, after use_virtual_resolution, see what the pointer did:
+    match gestures.update() {
+        Some(Gesture::DoubleClick(pos)) if spot.contains(pos) => rebet(),
+        Some(Gesture::LongPress(pos)) if spot.contains(pos) => clear_bet(),
+        _ => {}
+    }
+*/
+use macroquad::prelude::*;
+use nanoserde::{DeJson, SerJson};
+use std::cell::{Cell, RefCell};
+use crate::modules::scale::{mouse_position_world, screen_to_world};
+
+const LEFT: u8 = 1;
+const RIGHT: u8 = 2;
+const MIDDLE: u8 = 4;
+
+// Everything read from the mouse, keyboard and touch screen in one frame
+#[derive(SerJson, DeJson, Clone, Debug, Default, PartialEq)]
+#[nserde(default)]
+pub struct FrameInput {
+    pub mouse_x: f32, // Screen pixels
+    pub mouse_y: f32,
+    pub buttons: u8,  // Held down: 1 left, 2 right, 4 middle
+    pub pressed: u8,  // Went down this frame
+    pub released: u8, // Came up this frame
+    pub wheel_x: f32,
+    pub wheel_y: f32,
+    pub keys: Vec<u16>, // Held down, as KeyCode numbers
+    pub keys_pressed: Vec<u16>,
+    pub chars: String, // Typed this frame
+    pub touch: bool,   // A finger is on the screen, at touch_x, touch_y
+    pub touch_down: bool,
+    pub touch_x: f32,
+    pub touch_y: f32,
+}
+
+impl FrameInput {
+    // Read the real devices
+    fn capture() -> Self {
+        let (mouse_x, mouse_y) = ::macroquad::input::mouse_position();
+        let (wheel_x, wheel_y) = ::macroquad::input::mouse_wheel();
+        let buttons = |check: fn(MouseButton) -> bool| {
+            [(MouseButton::Left, LEFT), (MouseButton::Right, RIGHT), (MouseButton::Middle, MIDDLE)]
+                .into_iter()
+                .filter(|(button, _)| check(*button))
+                .fold(0, |bits, (_, bit)| bits | bit)
+        };
+        let mut chars = String::new();
+        while let Some(ch) = ::macroquad::input::get_char_pressed() {
+            chars.push(ch);
+        }
+        let mut input = Self {
+            mouse_x,
+            mouse_y,
+            buttons: buttons(::macroquad::input::is_mouse_button_down),
+            pressed: buttons(::macroquad::input::is_mouse_button_pressed),
+            released: buttons(::macroquad::input::is_mouse_button_released),
+            wheel_x,
+            wheel_y,
+            keys: get_keys_down().into_iter().map(|key| key as u16).collect(),
+            keys_pressed: get_keys_pressed().into_iter().map(|key| key as u16).collect(),
+            chars,
+            ..Self::default()
+        };
+        // Sets come back in any order, sorted they compare equal when nothing changed
+        input.keys.sort_unstable();
+        input.keys_pressed.sort_unstable();
+        if let Some(touch) = touches().first() {
+            input.touch = true;
+            input.touch_down = !matches!(touch.phase, TouchPhase::Ended | TouchPhase::Cancelled);
+            input.touch_x = touch.position.x;
+            input.touch_y = touch.position.y;
+        }
+        input
+    }
+
+    // The same frame without the things that only happen once (presses, typing, the wheel),
+    // what a frame looks like when nothing new is done
+    pub fn held(&self) -> Self {
+        Self { pressed: 0, released: 0, wheel_x: 0.0, wheel_y: 0.0, keys_pressed: Vec::new(), chars: String::new(), ..self.clone() }
+    }
+}
+
+thread_local! {
+    static FRAME: RefCell<FrameInput> = RefCell::new(FrameInput::default());
+    static LAST_FRAME: RefCell<FrameInput> = RefCell::new(FrameInput::default());
+    static CHARS_READ: Cell<usize> = const { Cell::new(0) };
+}
+
+// Take this frame's input, from the devices or from a recording being played back.
+// Call once at the top of every loop that runs frames, before anything reads input.
+pub fn begin_frame() {
+    #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+    let mut input = FrameInput::capture();
+    #[cfg(not(target_arch = "wasm32"))]
+    crate::modules::input_recorder::step(&mut input);
+    let last = FRAME.with(|frame| frame.replace(input));
+    LAST_FRAME.with(|frame| *frame.borrow_mut() = last);
+    CHARS_READ.with(|read| read.set(0));
+    crate::modules::ui::begin_frame();
+}
+
+fn with_frame<T>(read: impl FnOnce(&FrameInput) -> T) -> T {
+    FRAME.with(|frame| read(&frame.borrow()))
+}
+
+fn button_bit(button: MouseButton) -> u8 {
+    match button {
+        MouseButton::Left => LEFT,
+        MouseButton::Right => RIGHT,
+        MouseButton::Middle => MIDDLE,
+        MouseButton::Unknown => 0,
+    }
+}
+
+// The mouse in screen pixels
+pub fn mouse_position() -> (f32, f32) {
+    with_frame(|frame| (frame.mouse_x, frame.mouse_y))
+}
+
+pub fn mouse_wheel() -> (f32, f32) {
+    with_frame(|frame| (frame.wheel_x, frame.wheel_y))
+}
+
+pub fn is_mouse_button_down(button: MouseButton) -> bool {
+    with_frame(|frame| frame.buttons & button_bit(button) != 0)
+}
+
+pub fn is_mouse_button_pressed(button: MouseButton) -> bool {
+    with_frame(|frame| frame.pressed & button_bit(button) != 0)
+}
+
+#[allow(unused)]
+pub fn is_mouse_button_released(button: MouseButton) -> bool {
+    with_frame(|frame| frame.released & button_bit(button) != 0)
+}
+
+pub fn is_key_down(key: KeyCode) -> bool {
+    with_frame(|frame| frame.keys.contains(&(key as u16)))
+}
+
+pub fn is_key_pressed(key: KeyCode) -> bool {
+    with_frame(|frame| frame.keys_pressed.contains(&(key as u16)))
+}
+
+// The next character typed this frame, None once they have all been read
+pub fn get_char_pressed() -> Option<char> {
+    let read = CHARS_READ.with(Cell::get);
+    let ch = with_frame(|frame| frame.chars.chars().nth(read))?;
+    CHARS_READ.with(|count| count.set(read + 1));
+    Some(ch)
+}
+
+// Whether a finger is on the screen (the mouse fields follow it too)
+pub fn is_touching() -> bool {
+    with_frame(|frame| frame.touch)
+}
+
+// Anything done with the mouse, keyboard or a finger this frame, moving the mouse included
+pub fn had_input() -> bool {
+    let changed = LAST_FRAME.with(|last| {
+        let last = last.borrow();
+        with_frame(|frame| {
+            (frame.mouse_x, frame.mouse_y) != (last.mouse_x, last.mouse_y) || last.keys.iter().any(|key| !frame.keys.contains(key))
+        })
+    });
+    changed
+        || with_frame(|frame| {
+            (frame.wheel_x, frame.wheel_y) != (0.0, 0.0) || frame.buttons != 0 || frame.released != 0 || frame.touch || !frame.keys.is_empty()
+        })
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GestureThresholds {
+    pub double_click_seconds: f32, // Most time between the two taps of a double click
+    pub double_click_distance: f32, // Furthest apart the two taps can be, in world units
+    pub long_press_seconds: f32,   // How long to hold for a long press
+    pub move_tolerance: f32,       // How far the pointer can wander before the press no longer counts
+}
+
+impl Default for GestureThresholds {
+    fn default() -> Self {
+        Self { double_click_seconds: 0.35, double_click_distance: 20.0, long_press_seconds: 0.6, move_tolerance: 12.0 }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gesture {
+    Tap(Vec2),
+    DoubleClick(Vec2),
+    LongPress(Vec2),
+}
+
+// The pointer going down and what it has done since
+struct Press {
+    start: Vec2,
+    time: f64,
+    moved: bool,
+    long_press: bool,
+}
+
+pub struct GestureDetector {
+    thresholds: GestureThresholds,
+    press: Option<Press>,
+    last_tap: Option<(Vec2, f64)>,
+}
+
+impl GestureDetector {
+    pub fn new(thresholds: GestureThresholds) -> Self {
+        Self { thresholds, press: None, last_tap: None }
+    }
+
+    pub fn set_thresholds(&mut self, thresholds: GestureThresholds) {
+        self.thresholds = thresholds;
+    }
+
+    // Follow the pointer for this frame, returns the gesture it finished (or the long press it reached)
+    pub fn update(&mut self) -> Option<Gesture> {
+        let (pos, down) = pointer();
+        let now = get_time();
+        let limits = self.thresholds;
+        if down {
+            let press = self.press.get_or_insert(Press { start: pos, time: now, moved: false, long_press: false });
+            if press.start.distance(pos) > limits.move_tolerance {
+                press.moved = true;
+            }
+            if !press.moved && !press.long_press && now - press.time >= limits.long_press_seconds as f64 {
+                press.long_press = true;
+                self.last_tap = None;
+                return Some(Gesture::LongPress(press.start));
+            }
+            return None;
+        }
+
+        let press = self.press.take()?;
+        if press.moved || press.long_press {
+            self.last_tap = None;
+            return None;
+        }
+        // A second tap close enough in time and place makes a double click
+        if let Some((last_pos, last_time)) = self.last_tap
+            && now - last_time <= limits.double_click_seconds as f64
+            && last_pos.distance(press.start) <= limits.double_click_distance
+        {
+            self.last_tap = None;
+            return Some(Gesture::DoubleClick(press.start));
+        }
+        self.last_tap = Some((press.start, now));
+        Some(Gesture::Tap(press.start))
+    }
+}
+
+// Where the pointer is in world coordinates and whether it is held down.
+// A touch screen uses the first finger, anything else the left mouse button.
+pub fn pointer() -> (Vec2, bool) {
+    let touch = with_frame(|frame| frame.touch.then_some((frame.touch_x, frame.touch_y, frame.touch_down)));
+    if let Some((touch_x, touch_y, down)) = touch {
+        let (x, y) = screen_to_world(touch_x, touch_y);
+        return (vec2(x, y), down);
+    }
+    let (x, y) = mouse_position_world();
+    (vec2(x, y), is_mouse_button_down(MouseButton::Left))
+}
@@ -0,0 +1,208 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Records the mouse and keyboard to a file and plays a recording back, for scripted UI runs
+
+Run the game with BLACKJACK_RECORD set to a file name and every frame's input
+(input::FrameInput) is written to it as it is played. Run it with
+BLACKJACK_PLAYBACK set to that file instead and the recording is fed back through
+the input layer in place of the real mouse and keyboard, the same frame by frame,
+and a second after the last change the game quits. That turns a session played
+once by hand into an end-to-end test of the whole UI that can be run again after
+every change.
+
+The file is JSON lines. The first line is the header: the seed the shoe and
+macroquad's rand were started from (playback starts them from the same one, so
+the same cards come out) and the window size (playback asks for the same size,
+mouse positions are in screen pixels). Then one line for each frame whose input
+changed, with the frame number and the time since the start:
+    {"version":1,"seed":1733990400123,"width":1124.0,"height":768.0}
+    {"frame":0,"time":0.0,"input":{"mouse_x":512.0,"mouse_y":300.0,...}}
+    {"frame":41,"time":0.68,"input":{"mouse_x":520.0,...,"pressed":1}}
+A frame that isn't in the file is the one before it held: the same keys and
+buttons down with nothing new pressed or typed.
+
+Playback goes by frame number, not time. Animations follow the frame time, so a
+run only matches the recording on a machine that keeps up the same frame rate
+(vsync on both is the easy way).
+
+Native only, the web build can't read or write files.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod input;
+    pub mod log;
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod input_recorder;
+
+Then add the following with the use commands:
+use crate::modules::input_recorder;
+
+Usage examples:
+1. Once at startup, before anything is shuffled, use the seed it gives back:
+    let seed = input_recorder::start_from_env(entropy::new_seed());
+    if input_recorder::active() {
+        entropy::set_repeatable(seed); // Later shoes come from the seed as well
+    }
+    rand::srand(seed);
+
+2. input::begin_frame() calls step() itself; stop once a recording has been played:
+    if input_recorder::playback_finished() {
+        return;
+    }
+
+3. From a terminal:
+    BLACKJACK_RECORD=session.jsonl cargo run
+    BLACKJACK_PLAYBACK=session.jsonl cargo run
+*/
+use macroquad::prelude::*;
+use nanoserde::{DeJson, SerJson};
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::Write;
+use crate::modules::input::FrameInput;
+use crate::{log_info, log_warn};
+
+const RECORD_VAR: &str = "BLACKJACK_RECORD";
+const PLAYBACK_VAR: &str = "BLACKJACK_PLAYBACK";
+const VERSION: u32 = 1;
+const SETTLE_FRAMES: u64 = 60; // Played after the last change before playback counts as finished
+
+// The first line of a recording
+#[derive(SerJson, DeJson, Default)]
+#[nserde(default)]
+struct Header {
+    version: u32,
+    seed: u64,
+    width: f32,
+    height: f32,
+}
+
+// Every line after it: the input on `frame`
+#[derive(SerJson, DeJson)]
+struct Entry {
+    frame: u64,
+    time: f64,
+    input: FrameInput,
+}
+
+enum Mode {
+    Off,
+    Recording { file: File, last: FrameInput }, // The last frame written
+    Playing { entries: Vec<Entry>, next: usize, last: FrameInput }, // The last frame played
+}
+
+struct Recorder {
+    mode: Mode,
+    frame: u64,
+    start: f64,
+}
+
+thread_local! {
+    static RECORDER: RefCell<Recorder> = const { RefCell::new(Recorder { mode: Mode::Off, frame: 0, start: 0.0 }) };
+}
+
+// Record or play back if BLACKJACK_RECORD or BLACKJACK_PLAYBACK names a file. Returns the seed
+// to start the game from: the recording's when playing one back, otherwise `seed`.
+pub fn start_from_env(seed: u64) -> u64 {
+    if let Ok(path) = std::env::var(PLAYBACK_VAR) {
+        match start_playback(&path) {
+            Ok(recorded_seed) => return recorded_seed,
+            Err(err) => log_warn!("could not play back {}: {}", path, err),
+        }
+    } else if let Ok(path) = std::env::var(RECORD_VAR)
+        && let Err(err) = start_recording(&path, seed)
+    {
+        log_warn!("could not record to {}: {}", path, err);
+    }
+    seed
+}
+
+// Write the input of every frame from now on to `path`, the game having been started from `seed`
+pub fn start_recording(path: &str, seed: u64) -> std::io::Result<()> {
+    let mut file = File::create(path)?;
+    let header = Header { version: VERSION, seed, width: screen_width(), height: screen_height() };
+    writeln!(file, "{}", header.serialize_json())?;
+    RECORDER.with(|recorder| {
+        *recorder.borrow_mut() = Recorder { mode: Mode::Recording { file, last: FrameInput::default() }, frame: 0, start: get_time() };
+    });
+    log_info!("recording input to {}", path);
+    Ok(())
+}
+
+// Play the recording at `path` in place of the mouse and keyboard, returns the seed it was made with
+pub fn start_playback(path: &str) -> Result<u64, String> {
+    let text = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let header = Header::deserialize_json(lines.next().unwrap_or_default()).map_err(|err| format!("bad header: {}", err))?;
+    if header.version != VERSION {
+        return Err(format!("made by a different version of the recorder ({})", header.version));
+    }
+    let entries = lines
+        .enumerate()
+        .map(|(i, line)| Entry::deserialize_json(line).map_err(|err| format!("line {}: {}", i + 2, err)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if header.width > 0.0 && header.height > 0.0 {
+        request_new_screen_size(header.width, header.height);
+    }
+    log_info!("playing back {} ({} input changes over {} frames)", path, entries.len(), entries.last().map_or(0, |entry| entry.frame + 1));
+    RECORDER.with(|recorder| {
+        *recorder.borrow_mut() = Recorder { mode: Mode::Playing { entries, next: 0, last: FrameInput::default() }, frame: 0, start: get_time() };
+    });
+    Ok(header.seed)
+}
+
+// Called by input::begin_frame with the frame's input from the devices. Recording writes it out
+// when it changed, playback swaps in the recorded input.
+pub fn step(input: &mut FrameInput) {
+    RECORDER.with(|recorder| {
+        let recorder = &mut *recorder.borrow_mut();
+        let frame = recorder.frame;
+        let time = get_time() - recorder.start;
+        match &mut recorder.mode {
+            Mode::Off => return,
+            Mode::Recording { file, last } => {
+                if frame == 0 || *input != last.held() {
+                    let entry = Entry { frame, time, input: input.clone() };
+                    if let Err(err) = writeln!(file, "{}", entry.serialize_json()) {
+                        log_warn!("recording stopped, could not write: {}", err);
+                        recorder.mode = Mode::Off;
+                        return;
+                    }
+                }
+                *last = input.clone();
+            }
+            Mode::Playing { entries, next, last } => {
+                *input = match entries.get(*next) {
+                    Some(entry) if entry.frame == frame => {
+                        *next += 1;
+                        entry.input.clone()
+                    }
+                    _ => last.held(),
+                };
+                *last = input.clone();
+            }
+        }
+        recorder.frame += 1;
+    });
+}
+
+// Whether a recording is being made or played back, so the game has to start from its seed
+pub fn active() -> bool {
+    RECORDER.with(|recorder| !matches!(recorder.borrow().mode, Mode::Off))
+}
+
+// Whether a recording is being played back and has run out, a moment after its last change so
+// whatever that started has happened
+pub fn playback_finished() -> bool {
+    RECORDER.with(|recorder| {
+        let recorder = recorder.borrow();
+        match &recorder.mode {
+            Mode::Playing { entries, next, .. } => {
+                *next >= entries.len() && recorder.frame > entries.last().map_or(0, |entry| entry.frame) + SETTLE_FRAMES
+            }
+            _ => false,
+        }
+    })
+}
@@ -0,0 +1,164 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Keyboard shortcuts for the table's buttons, and the overlay that shows them
+
+Each thing that can be done from the keyboard is a KeyAction, and the KeyMap
+says which key does it. The game asks the map (pressed()) instead of checking
+keys itself, so a key can be changed in one place with rebind() and everything,
+the overlay included, follows.
+
+Holding Tab (or ?) shows the overlay: a small key cap on the corner of every
+button that has a shortcut, with the key that presses it. Buttons that are
+hidden get no cap.
+
+While something else is reading the keyboard (the developer console) the map
+can be paused, then nothing counts as pressed.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod layers;
+    pub mod shapes;
+    pub mod text_button;
+    pub mod input;
+    pub mod keys;
+
+Then add the following with the use commands:
+use crate::modules::keys::{KeyAction, KeyMap};
+
+Usage examples:
+1. Create it before the loop (with the default keys):
+    let mut keys = KeyMap::new();
+
+2. A button that can also be pressed from the keyboard:
+    if keys.press(&btn_hit, KeyAction::Hit) {
+        // hit
+    }
+
+3. Show the keys while Tab is held:
+    if keys::hints_held() {
+        keys.draw_hint(&btn_hit, KeyAction::Hit);
+    }
+
+4. Change a key:
+    keys.rebind(KeyAction::Hit, KeyCode::Space);
+*/
+use macroquad::prelude::*;
+use crate::modules::input::{is_key_down, is_key_pressed};
+use crate::modules::layers::{self, Layer};
+use crate::modules::shapes::ShapeBatch;
+use crate::modules::text_button::TextButton;
+use crate::modules::text_cache::measure_text_cached;
+
+const HINT_FONT_SIZE: u16 = 18;
+const HINT_HEIGHT: f32 = 24.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyAction {
+    Deal,
+    Hit,
+    Stand,
+    Replay,
+    Rebet,
+    RebetDeal,
+    Screenshot,
+    SaveGif,
+    Chart,
+}
+
+pub struct KeyMap {
+    bindings: Vec<(KeyAction, KeyCode)>,
+    paused: bool,
+}
+
+impl KeyMap {
+    pub fn new() -> Self {
+        let bindings = vec![
+            (KeyAction::Deal, KeyCode::Space),
+            (KeyAction::Hit, KeyCode::H),
+            (KeyAction::Stand, KeyCode::S),
+            (KeyAction::Replay, KeyCode::R),
+            (KeyAction::Rebet, KeyCode::B),
+            (KeyAction::RebetDeal, KeyCode::N),
+            (KeyAction::Screenshot, KeyCode::F12),
+            (KeyAction::SaveGif, KeyCode::F9),
+            (KeyAction::Chart, KeyCode::C),
+        ];
+        Self { bindings, paused: false }
+    }
+
+    // The key for an action, None if it has none
+    pub fn key(&self, action: KeyAction) -> Option<KeyCode> {
+        self.bindings.iter().find(|(bound, _)| *bound == action).map(|(_, key)| *key)
+    }
+
+    // Use another key for an action. Any other action on that key loses it.
+    #[allow(unused)]
+    pub fn rebind(&mut self, action: KeyAction, key: KeyCode) {
+        self.bindings.retain(|(bound, bound_key)| *bound != action && *bound_key != key);
+        self.bindings.push((action, key));
+    }
+
+    // Stop (or start again) taking keys, while something else is being typed into
+    #[allow(unused)]
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    // True on the frame the action's key goes down
+    pub fn pressed(&self, action: KeyAction) -> bool {
+        !self.paused && self.key(action).is_some_and(is_key_pressed)
+    }
+
+    // Click the button (which draws it) or press its key. The key does nothing while the button
+    // couldn't be clicked.
+    pub fn press(&self, button: &TextButton, action: KeyAction) -> bool {
+        button.click() || (button.enabled && button.visible && self.pressed(action))
+    }
+
+    // Queue the key cap for a button's shortcut onto its top right corner
+    pub fn draw_hint(&self, button: &TextButton, action: KeyAction) {
+        let Some(key) = self.key(action).filter(|_| button.visible) else {
+            return;
+        };
+        let text = key_name(key);
+        let width = measure_text_cached(&text, None, HINT_FONT_SIZE).width + 12.0;
+        let corner = button.get_position() + vec2(button.width - width + 6.0, -HINT_HEIGHT / 2.0);
+        let color = if button.enabled { WHITE } else { LIGHTGRAY };
+        layers::push(Layer::Overlay, move || {
+            let rect = Rect::new(corner.x, corner.y, width, HINT_HEIGHT);
+            let mut shapes = ShapeBatch::new();
+            shapes.fill(rect, 5.0, Color::new(0.1, 0.1, 0.1, 0.9));
+            shapes.outline(rect, 5.0, 1.0, color);
+            shapes.draw();
+            draw_text(&text, corner.x + 6.0, corner.y + HINT_HEIGHT * 0.72, HINT_FONT_SIZE as f32, color);
+        });
+    }
+}
+
+// Whether the shortcut overlay is being asked for: Tab or ? held down
+pub fn hints_held() -> bool {
+    let shift = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+    is_key_down(KeyCode::Tab) || (shift && is_key_down(KeyCode::Slash))
+}
+
+// What a key is called on its cap
+pub fn key_name(key: KeyCode) -> String {
+    match key {
+        KeyCode::Space => "Space".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Escape => "Esc".to_string(),
+        KeyCode::Key0 => "0".to_string(),
+        KeyCode::Key1 => "1".to_string(),
+        KeyCode::Key2 => "2".to_string(),
+        KeyCode::Key3 => "3".to_string(),
+        KeyCode::Key4 => "4".to_string(),
+        KeyCode::Key5 => "5".to_string(),
+        KeyCode::Key6 => "6".to_string(),
+        KeyCode::Key7 => "7".to_string(),
+        KeyCode::Key8 => "8".to_string(),
+        KeyCode::Key9 => "9".to_string(),
+        other => format!("{:?}", other),
+    }
+}
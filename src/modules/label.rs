@@ -29,6 +29,11 @@ You can add a border to the label with:
      lbl_out.with_border(RED, 2.0);
 Where the first value is the border color and the second is the thickness.
 
+New labels start from the style registered in modules::widget_style (colors, font,
+corner radius and border). Anything above overrides it for one label, or use a whole
+other style with:
+     lbl_out.with_style(&my_style);
+
 You can set a fixed size for the label with:
      lbl_out.with_fixed_size(200.0, 100.0);
 Where the values are width and height in pixels.
@@ -49,6 +54,9 @@ This changes the x and y coordinates of the label.
 To change the font size:
      lbl_out.set_font_size(24);
 This changes the font size of the label and recalculates its dimensions.
+The size drawn is the font size times the player's text scale (widget_style::set_text_scale).
+After the scale changes, measure the label again with:
+     lbl_out.rescale_text();
 
 To change the label's text:
      lbl_out.set_text("New text content");
@@ -60,6 +68,8 @@ To control the visibility of a label:
      lbl_out.toggle_visibility(); // Toggle between visible and hidden
 You can also set visibility during creation with:
      lbl_out.with_visibility(false);
+Showing and hiding fades the label in and out, unless reduced motion is on
+(animation::set_reduced_motion).
 
 Example:
      // Load font once at the beginning of your program
@@ -78,14 +88,32 @@ Otherwise the default system font will be used.
 
 Then in the loop you would use:
     lbl_out.draw();
+
+The label is drawn into the Hud layer (needs pub mod layers; and pub mod tooltip; in mod.rs),
+so call layers::flush() at the end of the loop. To use another layer:
+     lbl_out.with_layer(Layer::Overlay);
+
+To let code that doesn't own the label read it (see modules::ui, needs pub mod ui; in mod.rs):
+     lbl_out.with_id("lbl_out");
+     let text = ui::scene().get::<Label>("lbl_out").map(|info| info.text.clone());
 */
 use macroquad::prelude::*;
+use crate::modules::animation::Fade;
+use crate::modules::layers::{self, Layer};
+use crate::modules::shapes::ShapeBatch;
+use crate::modules::text_cache::measure_text_cached;
+use std::rc::Rc;
+use crate::modules::widget_style::{self, WidgetStyle};
+use crate::modules::widget_layout::Placeable;
+use crate::modules::ui::{self, Widget, WidgetInfo};
 
+#[derive(Clone)]
 pub struct Label {
-    text: String,
+    text: Rc<str>,
     x: f32,
     y: f32,
     font_size: u16,
+    drawn_size: u16, // font_size at the text scale, see widget_style::set_text_scale
     foreground: Color,
     background: Option<Color>,
     line_spacing: f32,
@@ -95,6 +123,9 @@ pub struct Label {
     border_color: Color, // Color of the border
     border_thickness: f32, // Thickness of the border
     visible: bool,      // Whether the label should be drawn
+    layer: Layer,       // Draw layer, Hud unless changed
+    shown: Fade,        // Follows visible
+    id: Option<Rc<str>>, // Registered in modules::ui when drawn
     
     // Fixed size properties
     fixed_width: Option<f32>,
@@ -102,14 +133,16 @@ pub struct Label {
     text_align: TextAlign,
     
     // Cached values for performance
-    cached_lines: Vec<String>,
-    cached_line_dimensions: Vec<TextDimensions>,
+    // Shared so the copy queued into the layer every frame doesn't copy the text
+    cached_lines: Rc<Vec<String>>,
+    cached_line_dimensions: Rc<Vec<TextDimensions>>,
     cached_max_width: f32,
     cached_total_height: f32,
 }
 
 // Enum for text alignment within a fixed-size label
 #[allow(unused)]
+#[derive(Clone, Copy)]
 pub enum TextAlign {
     Left,
     Center,
@@ -119,25 +152,30 @@ pub enum TextAlign {
 impl Label {
     // Constructor using x and y separately
     pub fn new<T: Into<String>>(text: T, x: f32, y: f32, font_size: u16) -> Self {
+        let style = widget_style::label_style();
         let mut label = Self {
-            text: text.into(),
+            text: Rc::from(text.into()),
             x,
             y,
             font_size,
-            foreground: BLACK, // Default to black
-            background: None,  // No background by default
+            drawn_size: font_size,
+            foreground: style.text_color,
+            background: style.background,
             line_spacing: 1.2,
-            font: None,        // Default to None (use system font)
-            corner_radius: 0.0, // Default to no rounded corners
-            border: false,      // Default to no border
-            border_color: BLACK, // Default border color
-            border_thickness: 1.0, // Default border thickness
+            font: style.font,
+            corner_radius: style.corner_radius,
+            border: style.border.is_some(),
+            border_color: style.border.map_or(BLACK, |(color, _)| color),
+            border_thickness: style.border.map_or(1.0, |(_, thickness)| thickness),
             visible: true,      // Default to visible
+            layer: Layer::Hud,
+            shown: Fade::new(FADE_SECONDS),
+            id: None,
             fixed_width: None, // No fixed width by default
             fixed_height: None, // No fixed height by default
             text_align: TextAlign::Left, // Default to left alignment
-            cached_lines: Vec::new(),
-            cached_line_dimensions: Vec::new(),
+            cached_lines: Rc::new(Vec::new()),
+            cached_line_dimensions: Rc::new(Vec::new()),
             cached_max_width: 0.0,
             cached_total_height: 0.0,
         };
@@ -151,20 +189,18 @@ impl Label {
     // Calculate and cache text dimensions
     fn calculate_text_dimensions(&mut self) {
         // Split text into lines and store for later use
-        self.cached_lines = self.text.split('\n').map(String::from).collect();
-        let line_height = self.font_size as f32 * self.line_spacing;
+        let lines: Vec<String> = self.text.split('\n').map(String::from).collect();
+        self.drawn_size = widget_style::scaled_font_size(self.font_size);
+        let line_height = self.drawn_size as f32 * self.line_spacing;
         
         // Clear previous cached values
-        self.cached_line_dimensions.clear();
+        let mut line_dimensions = Vec::with_capacity(lines.len());
         self.cached_max_width = 0.0;
         
         // Calculate dimensions for each line
-        for line in &self.cached_lines {
-            let dimensions = match &self.font {
-                Some(font) => measure_text(line, Some(font), self.font_size, 1.0),
-                None => measure_text(line, None, self.font_size, 1.0),
-            };
-            self.cached_line_dimensions.push(dimensions);
+        for line in &lines {
+            let dimensions = measure_text_cached(line, self.font.as_ref(), self.drawn_size);
+            line_dimensions.push(dimensions);
             
             // Only update max_width if we don't have a fixed width
             if self.fixed_width.is_none() {
@@ -174,8 +210,10 @@ impl Label {
         
         // Calculate total height (only if we don't have fixed height)
         if self.fixed_height.is_none() {
-            self.cached_total_height = self.cached_lines.len() as f32 * line_height;
+            self.cached_total_height = lines.len() as f32 * line_height;
         }
+        self.cached_lines = Rc::new(lines);
+        self.cached_line_dimensions = Rc::new(line_dimensions);
     }
 
     // Method to set foreground and background colors
@@ -186,6 +224,22 @@ impl Label {
         self
     }
 
+    // Method to take the colors, font, corner radius and border from a style
+    #[allow(unused)]
+    pub fn with_style(&mut self, style: &WidgetStyle) -> &mut Self {
+        self.foreground = style.text_color;
+        self.background = style.background;
+        self.font = style.font.clone();
+        self.corner_radius = style.corner_radius;
+        self.border = style.border.is_some();
+        if let Some((color, thickness)) = style.border {
+            self.border_color = color;
+            self.border_thickness = thickness;
+        }
+        self.calculate_text_dimensions();
+        self
+    }
+
     // Method to set custom font - taking Font by value since it implements Clone
     #[allow(unused)]
     pub fn with_font(&mut self, font: Font) -> &mut Self {
@@ -236,7 +290,12 @@ impl Label {
     // Method to set text - now accepts both String and &str
     #[allow(unused)]
     pub fn set_text<T: Into<String>>(&mut self, new_text: T) -> &mut Self {
-        self.text = new_text.into();
+        let new_text = new_text.into();
+        // Setting the same text every frame is common, there is nothing to work out again
+        if *self.text == *new_text {
+            return self;
+        }
+        self.text = Rc::from(new_text);
         
         // Only recalculate if we need to (when not using fixed dimensions)
         // Even with fixed dimensions, we still need to recalculate line dimensions
@@ -318,6 +377,12 @@ impl Label {
         self
     }
 
+    // Measure the text again at the current text scale (see widget_style::set_text_scale)
+    pub fn rescale_text(&mut self) -> &mut Self {
+        self.calculate_text_dimensions();
+        self
+    }
+
     // Setter for visibility
     #[allow(unused)]
     pub fn set_visible(&mut self, visible: bool) -> &mut Self {
@@ -332,14 +397,50 @@ impl Label {
         self.visible
     }
     
-    // Method to draw the label
+    // Method to pick the draw layer
+    #[allow(unused)]
+    pub fn with_layer(&mut self, layer: Layer) -> &mut Self {
+        self.layer = layer;
+        self
+    }
+
+    // Method to give the label an ID, so modules::ui can find it
+    #[allow(unused)]
+    pub fn with_id(&mut self, id: &str) -> &mut Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    // Method to draw the label (queued into its layer)
     pub fn draw(&self) {
-        // Only draw if the label is visible
+        if let Some(id) = &self.id {
+            let size = self.size();
+            let rect = Rect::new(self.x - 5.0, self.y - self.drawn_size as f32, size.x, size.y);
+            ui::record(WidgetInfo { id: id.clone(), kind: Self::KIND, rect, text: self.text.to_string(), visible: self.visible, enabled: true });
+        }
+        // Only draw if the label is visible, or still fading out
+        let shown = self.shown.update(self.visible);
+        if shown == 0.0 {
+            return;
+        }
+        let label = self.clone();
+        layers::push(self.layer, move || label.draw_faded(shown));
+    }
+
+    // Method to draw the label straight away, ignoring layers
+    #[allow(unused)]
+    pub fn draw_now(&self) {
         if !self.visible {
             return;
         }
+        self.draw_faded(1.0);
+    }
+
+    // Draw with every color's alpha multiplied by `shown`
+    fn draw_faded(&self, shown: f32) {
+        let fade = |color: Color| Color { a: color.a * shown, ..color };
         
-        let line_height = self.font_size as f32 * self.line_spacing;
+        let line_height = self.drawn_size as f32 * self.line_spacing;
         
         // Determine width and height (using fixed values if set, otherwise use content size)
         let width = self.fixed_width.unwrap_or(self.cached_max_width + 10.0);
@@ -347,48 +448,26 @@ impl Label {
         
         // Calculate positions for all elements
         let bg_x = self.x - 5.0;
-        let bg_y = self.y - self.font_size as f32;
+        let bg_y = self.y - self.drawn_size as f32;
         
-        // Draw background first
+        // Background and border go out together as one mesh
+        let mut shapes = ShapeBatch::new();
+        let rect = Rect::new(bg_x, bg_y, width, height);
         if let Some(bg) = self.background {
-            // Draw a single background for all lines
-            if self.corner_radius > 0.0 {
-                draw_round_rect(
-                    bg_x, bg_y, width, height,
-                    self.corner_radius,
-                    bg,
-                );
-            } else {
-                draw_rectangle(
-                    bg_x, bg_y, width, height,
-                    bg,
-                );
-            }
+            shapes.fill(rect, self.corner_radius, fade(bg));
         }
-        
-        // Draw border if enabled
         if self.border {
-            // Get background color for the inner part of the border
-            let bg_color = self.background.unwrap_or(GRAY);
-            
             if self.corner_radius > 0.0 {
-                // Draw rounded border with the correct background color
-                draw_round_rect_border(
-                    bg_x, bg_y, width, height,
-                    self.corner_radius,
-                    self.border_thickness,
-                    self.border_color,
-                    bg_color,
-                );
+                // A rounded border is the border color with the background (gray if there is none) drawn inside it
+                let inset = self.border_thickness;
+                let inner = Rect::new(rect.x + inset, rect.y + inset, rect.w - inset * 2.0, rect.h - inset * 2.0);
+                shapes.fill(rect, self.corner_radius, fade(self.border_color));
+                shapes.fill(inner, (self.corner_radius - inset).max(0.0), fade(self.background.unwrap_or(GRAY)));
             } else {
-                // Draw regular rectangular border
-                draw_rectangle_border(
-                    bg_x, bg_y, width, height,
-                    self.border_thickness,
-                    self.border_color,
-                );
+                shapes.outline(rect, 0.0, self.border_thickness, fade(self.border_color));
             }
         }
+        shapes.draw();
 
         // Draw each line of text
         for (i, (line, dimensions)) in self.cached_lines.iter().zip(self.cached_line_dimensions.iter()).enumerate() {
@@ -414,76 +493,31 @@ impl Label {
                         y,
                         TextParams {
                             font: Some(font),
-                            font_size: self.font_size,
-                            color: self.foreground,
+                            font_size: self.drawn_size,
+                            color: fade(self.foreground),
                             ..Default::default()
                         },
                     );
                 },
                 None => {
                     // Use the default draw_text function
-                    draw_text(line, x, y, self.font_size as f32, self.foreground);
+                    draw_text(line, x, y, self.drawn_size as f32, fade(self.foreground));
                 }
             }
         }
     }
 }
 
-// Function to draw a rectangle with rounded corners - optimized version
-#[allow(unused)]
-fn draw_round_rect(x: f32, y: f32, w: f32, h: f32, radius: f32, color: Color) {
-    // Precompute corner positions
-    let top_left = Vec2::new(x + radius, y + radius);
-    let top_right = Vec2::new(x + w - radius, y + radius);
-    let bottom_left = Vec2::new(x + radius, y + h - radius);
-    let bottom_right = Vec2::new(x + w - radius, y + h - radius);
-    
-    // Draw center rectangle
-    draw_rectangle(x + radius, y, w - 2.0 * radius, h, color);
-    
-    // Draw the side rectangles
-    draw_rectangle(x, y + radius, radius, h - 2.0 * radius, color);
-    draw_rectangle(x + w - radius, y + radius, radius, h - 2.0 * radius, color);
-    
-    // Draw the four corner circles (could be batched in a real engine)
-    draw_circle(top_left.x, top_left.y, radius, color);     // Top-left
-    draw_circle(top_right.x, top_right.y, radius, color);   // Top-right
-    draw_circle(bottom_left.x, bottom_left.y, radius, color);  // Bottom-left
-    draw_circle(bottom_right.x, bottom_right.y, radius, color); // Bottom-right
-}
-
-// New border drawing method using filled rectangles instead of lines
-// This approach ensures consistent border thickness on all sides
-#[allow(unused)]
-fn draw_rectangle_border(x: f32, y: f32, w: f32, h: f32, thickness: f32, color: Color) {
-    // Top border
-    draw_rectangle(x, y, w, thickness, color);
-    // Bottom border
-    draw_rectangle(x, y + h - thickness, w, thickness, color);
-    // Left border
-    draw_rectangle(x, y + thickness, thickness, h - (thickness * 2.0), color);
-    // Right border
-    draw_rectangle(x + w - thickness, y + thickness, thickness, h - (thickness * 2.0), color);
-}
+impl Placeable for Label {
+    fn size(&self) -> Vec2 {
+        vec2(self.fixed_width.unwrap_or(self.cached_max_width + 10.0), self.fixed_height.unwrap_or(self.cached_total_height))
+    }
 
-// New function to draw rounded rectangle borders with consistent thickness
-#[allow(unused)]
-fn draw_round_rect_border(x: f32, y: f32, w: f32, h: f32, radius: f32, thickness: f32, color: Color, bg_color: Color) {
-    if radius <= 0.0 {
-        // Use our new rectangle border function for non-rounded corners
-        draw_rectangle_border(x, y, w, h, thickness, color);
-        return;
+    // The label's x, y is the start of the first line's baseline, its box starts 5 to the left and a font size up
+    fn place(&mut self, x: f32, y: f32) {
+        self.set_position(x + 5.0, y + self.drawn_size as f32);
     }
-    
-    // Draw outer rounded rectangle
-    draw_round_rect(x, y, w, h, radius, color);
-    
-    // Draw inner rounded rectangle with background color
-    let inner_x = x + thickness;
-    let inner_y = y + thickness;
-    let inner_w = w - (thickness * 2.0);
-    let inner_h = h - (thickness * 2.0);
-    let inner_radius = (radius - thickness).max(0.0);
-    
-    draw_round_rect(inner_x, inner_y, inner_w, inner_h, inner_radius, bg_color);
 }
+
+// How long showing and hiding take
+const FADE_SECONDS: f32 = 0.15;
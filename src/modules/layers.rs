@@ -0,0 +1,120 @@
+/*
+By: <tyler>
+Date: 2025-11-26
+Program Details: Draw layers, so overlays always end up on top
+
+Widgets don't draw straight to the screen any more. Their draw() (or click() for
+buttons) queues the drawing into a layer, and layers::flush() draws the layers in
+order at the end of the frame:
+    Table   - the felt and the cards
+    Hud     - labels and buttons on the table (default for Label and TextButton)
+    Overlay - dialogs, the settings panel, the shuffle animation, then tooltips
+    Debug   - developer information
+and then the game's own mouse cursor (see cursor.rs) over all of them.
+Inside a layer things are drawn by depth, lowest first, and in the order they
+were queued when the depth is the same. Everything is queued at depth 0 unless
+push_at says otherwise; a panel's backdrop and frame go in at BACKGROUND, so the
+buttons on it are drawn over it even when their click() runs before the panel
+is drawn.
+
+Anything drawn directly with macroquad (draw_text, draw_rectangle, ...) happens
+straight away, so it ends up underneath every layer. Use layers::push to put your
+own drawing into a layer.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod tooltip;
+    pub mod cursor;
+    pub mod layers;
+
+Then add the following with the use commands:
+use crate::modules::layers::{self, Layer};
+
+Usage examples:
+1. Put a widget in another layer:
+    btn_close.with_layer(Layer::Overlay);
+
+2. Queue your own drawing (the closure must own what it uses):
+    let title = "Settings".to_string();
+    layers::push(Layer::Overlay, move || draw_text(&title, 20.0, 50.0, 45.0, WHITE));
+
+3. Queue something under everything else in its layer, whenever it is queued:
+    layers::push_at(Layer::Overlay, layers::BACKGROUND, move || draw_rectangle(x, y, w, h, DARKGRAY));
+
+4. Last thing in the loop, just before next_frame() (Frame::end does both, see frame.rs):
+    layers::flush();
+*/
+use std::cell::RefCell;
+use crate::modules::{cursor, tooltip};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layer {
+    Table,
+    Hud,
+    Overlay,
+    Debug,
+}
+
+// Bottom to top
+pub const LAYERS: [Layer; 4] = [Layer::Table, Layer::Hud, Layer::Overlay, Layer::Debug];
+
+// Depth of panel and dialog backgrounds, under anything else queued in their layer
+pub const BACKGROUND: i32 = -1;
+
+type DrawCall = (i32, Box<dyn FnOnce()>);
+
+thread_local! {
+    static QUEUES: RefCell<[Vec<DrawCall>; 4]> = RefCell::new(Default::default());
+    static LAST_COUNTS: RefCell<[usize; 4]> = const { RefCell::new([0; 4]) };
+}
+
+// Where the layer is from the bottom, 0 for Table
+pub fn index(layer: Layer) -> usize {
+    LAYERS.iter().position(|other| *other == layer).unwrap_or(0)
+}
+
+// Queue some drawing into a layer for this frame
+pub fn push(layer: Layer, draw: impl FnOnce() + 'static) {
+    push_at(layer, 0, draw);
+}
+
+// Queue some drawing into a layer at a depth, higher depths are drawn over lower ones
+pub fn push_at(layer: Layer, depth: i32, draw: impl FnOnce() + 'static) {
+    QUEUES.with(|queues| queues.borrow_mut()[index(layer)].push((depth, Box::new(draw))));
+}
+
+// Number of draw calls the last flush() drew in a layer (for the debug overlay)
+#[allow(unused)]
+pub fn drawn_last_frame(layer: Layer) -> usize {
+    LAST_COUNTS.with(|counts| counts.borrow()[index(layer)])
+}
+
+// Throw away everything queued this frame (when switching to another screen mid-frame)
+pub fn clear() {
+    QUEUES.with(|queues| queues.borrow_mut().iter_mut().for_each(Vec::clear));
+}
+
+// Draw every layer in order and empty the queues. Tooltips go on top of the overlay layer,
+// the cursor on top of everything.
+pub fn flush() {
+    draw_queued();
+    cursor::draw();
+}
+
+// flush() without the cursor, for drawing the frame somewhere other than the screen (see scene_fade.rs)
+pub fn draw_queued() {
+    for layer in LAYERS {
+        // Take the queue out first so drawing code can queue more without a double borrow
+        let mut calls = QUEUES.with(|queues| std::mem::take(&mut queues.borrow_mut()[index(layer)]));
+        LAST_COUNTS.with(|counts| counts.borrow_mut()[index(layer)] = calls.len());
+        // Stable, so the same depth keeps the order it was queued in
+        calls.sort_by_key(|(depth, _)| *depth);
+        for (_, draw) in calls {
+            draw();
+        }
+        if layer == Layer::Overlay {
+            tooltip::draw();
+        }
+    }
+}
@@ -0,0 +1,387 @@
+/*
+By: <tyler>
+Date: 2025-11-18
+Program Details: Data-driven table layout presets
+
+Every position on the table (card rows, buttons, labels) lives in
+assets/layouts.json instead of being hard-coded in main.rs. The file holds a
+list of presets, and the settings screen lets the player pick which one to use.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod layout;
+
+Then add the following with the use commands:
+use crate::modules::layout::TableLayouts;
+
+Usage examples:
+1. Load the presets once before the loop:
+    let layouts = TableLayouts::load("assets/layouts.json").await;
+
+2. Get a preset by index (wraps around, so cycling is easy):
+    let layout = layouts.get(settings.layout_preset);
+
+3. Use the preset values when positioning widgets:
+    use_virtual_resolution(layout.virtual_width, layout.virtual_height);
+    btn_deal.update_position(layout.deal.x, layout.deal.y, Some(layout.deal.w), Some(layout.deal.h));
+    first_card.set_position(layout.player_card_pos(0));
+
+4. Lay every preset out for another virtual resolution, keeping its shape (None undoes it):
+    layouts.set_resolution(Some((1920.0, 1080.0)));
+
+5. Every frame, switch to the portrait table while the window is taller than wide:
+    if layouts.set_aspect(screen_width() / screen_height()) {
+        table.apply_layout(layouts.get(settings.layout_preset), true);
+    }
+
+One preset in the file can be marked "portrait": true, a table with the dealer
+on top, the player's hands in the middle and the controls along the bottom, for
+phones held upright. It is taken out of the list the settings step through, and
+while the window is taller than it is wide get() hands it out in place
+of whichever preset is picked, and the picked preset comes back when the window
+is turned again. It keeps its own virtual size whatever the resolution setting.
+
+If the file can't be loaded or parsed the built-in compact preset is used, so the
+game still starts with the original table.
+*/
+use macroquad::prelude::*;
+use nanoserde::DeJson;
+use crate::log_warn;
+
+// A point on the table in virtual-resolution coordinates
+#[derive(DeJson, Clone, Copy, Debug)]
+pub struct Spot {
+    pub x: f32,
+    pub y: f32,
+}
+
+// A rectangle on the table, used for buttons
+#[derive(DeJson, Clone, Copy, Debug)]
+pub struct Area {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+// A circle on the table, for the betting spot and the arc the rules are printed along
+#[derive(DeJson, Clone, Copy, Debug)]
+pub struct Circle {
+    pub x: f32,
+    pub y: f32,
+    pub r: f32,
+}
+
+impl Circle {
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.distance(vec2(self.x, self.y)) <= self.r
+    }
+}
+
+// Uniform scale plus an offset, for moving a layout to another virtual resolution
+#[derive(Clone, Copy)]
+struct Fit {
+    scale: f32,
+    offset: Vec2,
+}
+
+impl Fit {
+    fn spot(&self, spot: Spot) -> Spot {
+        Spot { x: spot.x * self.scale + self.offset.x, y: spot.y * self.scale + self.offset.y }
+    }
+
+    fn area(&self, area: Area) -> Area {
+        let corner = self.spot(Spot { x: area.x, y: area.y });
+        Area { x: corner.x, y: corner.y, w: area.w * self.scale, h: area.h * self.scale }
+    }
+
+    fn circle(&self, circle: Circle) -> Circle {
+        let center = self.spot(Spot { x: circle.x, y: circle.y });
+        Circle { x: center.x, y: center.y, r: circle.r * self.scale }
+    }
+}
+
+// One complete table layout preset
+#[derive(DeJson, Clone, Debug)]
+pub struct TableLayout {
+    pub name: String,
+    pub virtual_width: f32,
+    pub virtual_height: f32,
+    #[nserde(default)]
+    pub portrait: bool, // For windows taller than wide, swapped in on its own instead of picked
+
+    // Card rows: the first card goes at the row position, the rest step right by card_spacing
+    pub card_width: f32,
+    pub card_height: f32,
+    pub card_spacing: f32,
+    pub dealer_row: Spot,
+    pub player_row: Spot,
+
+    // Playing more than one hand: each hand starts at its own row with the cards closer together,
+    // and gets its own bet spot instead of bet_spot
+    pub hand_rows: Vec<Spot>,
+    pub hand_card_spacing: f32,
+    pub hand_spots: Vec<Circle>,
+
+    // Where the shoe and the discard tray sit
+    pub shoe: Area,
+    pub discard_tray: Area,
+
+    // Felt markings: the rules text runs along the bottom of felt_arc, the insurance line just outside it
+    pub felt_arc: Circle,
+    pub bet_spot: Circle,
+
+    // Buttons
+    pub exit: Area,
+    pub settings: Area,
+    pub screenshot: Area,
+    pub fairness: Area,
+    pub deal: Area,
+    pub hit: Area,
+    pub stand: Area,
+    pub replay: Area,
+    pub rebet: Area,      // Rebet and Rebet & Deal take the place of Hit and Stand once a round is settled
+    pub rebet_deal: Area,
+    pub quick_bets: Area, // The recent bet buttons share out this row, in place of Hit and Stand between rounds
+    pub chip_tray: Area,  // The betting chips, in a row or a column, whichever way the area is longer
+    pub percent_bets: Area, // The bets sized to the bankroll, beside the chips and laid out the same way
+    pub quick_settings: Area, // The gear that opens the quick settings popover
+
+    // Labels
+    pub dealer_hand: Spot,
+    pub player_hand: Spot,
+    pub winner: Spot,
+    pub explainer: Spot, // One line under the result saying why
+    pub results: Spot, // Top-left of the last hands strip
+    pub notice: Spot,
+    pub sync_status: Spot,
+    pub streak: Spot, // Top-left of the hot/cold streak icon
+
+    // Panels
+    pub trainer: Area, // Shoe composition and count, when the counting trainer is on
+    pub odds: Area,    // Dealer outcome chances and the chance a hit busts, when the odds panel is on
+    pub advisor: Area, // Suggested bet and risk of ruin, between rounds when the advisor is on
+    pub seats: Vec<Area>, // A nameplate for each seat at the table, the player's first
+}
+
+impl TableLayout {
+    // Position of the player's card at the given index (0 = first card)
+    #[allow(unused)]
+    pub fn player_card_pos(&self, index: usize) -> Vec2 {
+        vec2(self.player_row.x + self.card_spacing * index as f32, self.player_row.y)
+    }
+
+    // Position of a card in one of `hands` player hands. With one hand it is the same as player_card_pos.
+    pub fn hand_card_pos(&self, hand: usize, hands: usize, index: usize) -> Vec2 {
+        match self.hand_rows.get(hand) {
+            Some(row) if hands > 1 => vec2(row.x + self.hand_card_spacing * index as f32, row.y),
+            _ => self.player_card_pos(index),
+        }
+    }
+
+    // The bet spot for one of `hands` player hands
+    pub fn hand_bet_spot(&self, hand: usize, hands: usize) -> Circle {
+        match self.hand_spots.get(hand) {
+            Some(spot) if hands > 1 => *spot,
+            _ => self.bet_spot,
+        }
+    }
+
+    // Position of the dealer's card at the given index (0 = first card)
+    #[allow(unused)]
+    pub fn dealer_card_pos(&self, index: usize) -> Vec2 {
+        vec2(self.dealer_row.x + self.card_spacing * index as f32, self.dealer_row.y)
+    }
+
+    // The same table for another virtual resolution: everything grows or shrinks by the same
+    // amount so nothing gets stretched, and the table is centered in any room left over.
+    // Text sizes are not part of the layout, so a bigger resolution makes the text look smaller.
+    pub fn scaled_to(&self, width: f32, height: f32) -> Self {
+        let scale = (width / self.virtual_width).min(height / self.virtual_height);
+        let offset = vec2((width - self.virtual_width * scale) / 2.0, (height - self.virtual_height * scale) / 2.0);
+        let fit = Fit { scale, offset };
+        Self {
+            name: self.name.clone(),
+            virtual_width: width,
+            virtual_height: height,
+            portrait: self.portrait,
+            card_width: self.card_width * scale,
+            card_height: self.card_height * scale,
+            card_spacing: self.card_spacing * scale,
+            dealer_row: fit.spot(self.dealer_row),
+            player_row: fit.spot(self.player_row),
+            hand_rows: self.hand_rows.iter().map(|row| fit.spot(*row)).collect(),
+            hand_card_spacing: self.hand_card_spacing * scale,
+            hand_spots: self.hand_spots.iter().map(|spot| fit.circle(*spot)).collect(),
+            shoe: fit.area(self.shoe),
+            discard_tray: fit.area(self.discard_tray),
+            felt_arc: fit.circle(self.felt_arc),
+            bet_spot: fit.circle(self.bet_spot),
+            exit: fit.area(self.exit),
+            settings: fit.area(self.settings),
+            screenshot: fit.area(self.screenshot),
+            fairness: fit.area(self.fairness),
+            deal: fit.area(self.deal),
+            hit: fit.area(self.hit),
+            stand: fit.area(self.stand),
+            replay: fit.area(self.replay),
+            rebet: fit.area(self.rebet),
+            rebet_deal: fit.area(self.rebet_deal),
+            quick_bets: fit.area(self.quick_bets),
+            chip_tray: fit.area(self.chip_tray),
+            percent_bets: fit.area(self.percent_bets),
+            quick_settings: fit.area(self.quick_settings),
+            dealer_hand: fit.spot(self.dealer_hand),
+            player_hand: fit.spot(self.player_hand),
+            winner: fit.spot(self.winner),
+            explainer: fit.spot(self.explainer),
+            results: fit.spot(self.results),
+            notice: fit.spot(self.notice),
+            sync_status: fit.spot(self.sync_status),
+            streak: fit.spot(self.streak),
+            trainer: fit.area(self.trainer),
+            odds: fit.area(self.odds),
+            advisor: fit.area(self.advisor),
+            seats: self.seats.iter().map(|seat| fit.area(*seat)).collect(),
+        }
+    }
+
+    // The original hard-coded table, used when layouts.json is missing or broken
+    pub fn compact() -> Self {
+        let spot = |x, y| Spot { x, y };
+        let area = |x, y, w, h| Area { x, y, w, h };
+        let circle = |x, y, r| Circle { x, y, r };
+        Self {
+            name: "Compact".to_string(),
+            virtual_width: 1000.0,
+            virtual_height: 700.0,
+            portrait: false,
+            card_width: 110.0,
+            card_height: 160.0,
+            card_spacing: 125.0,
+            dealer_row: spot(100.0, 100.0),
+            player_row: spot(100.0, 500.0),
+            hand_rows: vec![spot(100.0, 500.0), spot(313.0, 500.0), spot(527.0, 500.0)],
+            hand_card_spacing: 22.0,
+            hand_spots: vec![circle(250.0, 462.0, 26.0), circle(463.0, 462.0, 26.0), circle(677.0, 462.0, 26.0)],
+            shoe: area(790.0, 440.0, 170.0, 100.0),
+            discard_tray: area(790.0, 570.0, 170.0, 100.0),
+            felt_arc: circle(500.0, -700.0, 985.0),
+            bet_spot: circle(50.0, 580.0, 38.0),
+            exit: area(780.0, 0.0, 200.0, 65.0),
+            settings: area(560.0, 0.0, 200.0, 65.0),
+            screenshot: area(340.0, 0.0, 200.0, 65.0),
+            fairness: area(120.0, 0.0, 200.0, 65.0),
+            deal: area(100.0, 350.0, 200.0, 65.0),
+            hit: area(330.0, 350.0, 170.0, 65.0),
+            stand: area(530.0, 350.0, 170.0, 65.0),
+            replay: area(750.0, 350.0, 200.0, 65.0),
+            rebet: area(330.0, 350.0, 170.0, 65.0),
+            rebet_deal: area(530.0, 350.0, 170.0, 65.0),
+            quick_bets: area(330.0, 350.0, 370.0, 65.0),
+            chip_tray: area(22.0, 160.0, 56.0, 190.0),
+            percent_bets: area(22.0, 360.0, 56.0, 100.0),
+            quick_settings: area(62.0, 8.0, 50.0, 50.0),
+            dealer_hand: spot(70.0, 80.0),
+            player_hand: spot(70.0, 475.0),
+            winner: spot(485.0, 60.0),
+            explainer: spot(485.0, 92.0),
+            results: spot(735.0, 110.0),
+            notice: spot(20.0, 690.0),
+            sync_status: spot(750.0, 240.0),
+            streak: spot(750.0, 265.0),
+            trainer: area(100.0, 268.0, 560.0, 58.0),
+            odds: area(700.0, 292.0, 290.0, 52.0),
+            advisor: area(700.0, 282.0, 290.0, 64.0),
+            seats: vec![area(740.0, 150.0, 250.0, 56.0)],
+        }
+    }
+}
+
+// The file format: a list of presets
+#[derive(DeJson, Clone, Debug)]
+pub struct TableLayouts {
+    pub presets: Vec<TableLayout>,
+    #[nserde(skip)]
+    portrait: Option<TableLayout>, // Taken out of the presets, used in place of any of them while the window is upright
+    #[nserde(skip)]
+    originals: Vec<TableLayout>, // The presets as loaded, before set_resolution
+    #[nserde(skip)]
+    portrait_active: bool,
+}
+
+impl Default for TableLayouts {
+    fn default() -> Self {
+        Self { presets: vec![TableLayout::compact()], portrait: None, originals: vec![TableLayout::compact()], portrait_active: false }
+    }
+}
+
+impl TableLayouts {
+    // Load the presets from a JSON file, falling back to the built-in compact table
+    pub async fn load(path: &str) -> Self {
+        let text = match load_string(path).await {
+            Ok(text) => text,
+            Err(err) => {
+                log_warn!("could not load {}: {}", path, err);
+                return Self::default();
+            }
+        };
+
+        match TableLayouts::deserialize_json(&text) {
+            Ok(mut layouts) if layouts.presets.iter().any(|layout| !layout.portrait) => {
+                layouts.portrait = layouts.presets.iter().find(|layout| layout.portrait).cloned();
+                layouts.presets.retain(|layout| !layout.portrait);
+                layouts.originals = layouts.presets.clone();
+                layouts
+            }
+            Ok(_) => {
+                log_warn!("{} has no presets", path);
+                Self::default()
+            }
+            Err(err) => {
+                log_warn!("could not parse {}: {}", path, err);
+                Self::default()
+            }
+        }
+    }
+
+    // Get a preset by index, wrapping around so settings can just keep counting up.
+    // While the window is upright this is the portrait table whatever the index.
+    pub fn get(&self, index: usize) -> &TableLayout {
+        match &self.portrait {
+            Some(portrait) if self.portrait_active => portrait,
+            _ => &self.presets[index % self.presets.len()],
+        }
+    }
+
+    // Follow the window's width / height, returns true when that switches the portrait table in or out
+    pub fn set_aspect(&mut self, aspect: f32) -> bool {
+        let portrait = aspect < 1.0 && self.portrait.is_some();
+        let changed = portrait != self.portrait_active;
+        self.portrait_active = portrait;
+        changed
+    }
+
+    // Whether the portrait table is in use, the presets can't be picked then
+    pub fn is_portrait(&self) -> bool {
+        self.portrait_active
+    }
+
+    // Lay every preset out again for a virtual resolution, None puts each back to its own size
+    pub fn set_resolution(&mut self, resolution: Option<(f32, f32)>) {
+        self.presets = self
+            .originals
+            .iter()
+            .map(|layout| match resolution {
+                Some((width, height)) => layout.scaled_to(width, height),
+                None => layout.clone(),
+            })
+            .collect();
+    }
+
+    // Number of presets available
+    pub fn count(&self) -> usize {
+        self.presets.len()
+    }
+}
@@ -0,0 +1,152 @@
+/*
+By: <tyler>
+Date: 2025-11-27
+Program Details: Logging with levels, instead of println! everywhere
+
+Messages go to the terminal (stderr) on native and to the browser console on the
+web (console.error / console.warn / ...), tagged with the level and the module
+they came from:
+    [WARN  storage] could not save profiles: disk full
+
+Only messages at or above the current level are shown. The level comes from the
+BLACKJACK_LOG environment variable if it is set (error, warn, info or debug),
+otherwise from the player's settings, otherwise it is info.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod log;
+
+Then add the following with the use commands (only the macros you use):
+use crate::{log_debug, log_error, log_info, log_warn};
+
+Usage examples:
+1. Log something, with format! style arguments:
+    log_info!("Loaded {} textures", tm.texture_count());
+    log_warn!("could not load {}: {}", key, err);
+
+2. Pick the level once the settings are loaded (BLACKJACK_LOG still wins):
+    log::configure(profile.settings.log_level());
+
+3. Run with more detail from a terminal:
+    BLACKJACK_LOG=debug cargo run
+*/
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const ENV_VAR: &str = "BLACKJACK_LOG";
+
+// Most important first
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+pub const LEVELS: [Level; 4] = [Level::Error, Level::Warn, Level::Info, Level::Debug];
+
+impl Level {
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+        }
+    }
+
+    // Read a level name, ignoring case ("warning" is accepted too)
+    pub fn parse(text: &str) -> Option<Level> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "error" => Some(Level::Error),
+            "warn" | "warning" => Some(Level::Warn),
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            _ => None,
+        }
+    }
+
+    // The next more detailed level, wrapping back to error after debug
+    pub fn next(self) -> Level {
+        LEVELS[(self as usize + 1) % LEVELS.len()]
+    }
+}
+
+static MAX_LEVEL: AtomicUsize = AtomicUsize::new(Level::Info as usize);
+
+// Show messages at this level and above from now on
+pub fn set_level(level: Level) {
+    MAX_LEVEL.store(level as usize, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    LEVELS[MAX_LEVEL.load(Ordering::Relaxed).min(LEVELS.len() - 1)]
+}
+
+pub fn enabled(level: Level) -> bool {
+    level <= self::level()
+}
+
+// The level asked for by BLACKJACK_LOG, if it is set to something valid (never on the web)
+pub fn env_level() -> Option<Level> {
+    #[cfg(target_arch = "wasm32")]
+    return None;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    std::env::var(ENV_VAR).ok().and_then(|value| Level::parse(&value))
+}
+
+// Use BLACKJACK_LOG if it is set, otherwise the given level (usually from the settings)
+pub fn configure(fallback: Level) {
+    set_level(env_level().unwrap_or(fallback));
+}
+
+// Used by the log_* macros; call those instead
+pub fn write(level: Level, module: &str, message: fmt::Arguments) {
+    if !enabled(level) {
+        return;
+    }
+    // "blackjack::modules::storage" -> "storage"
+    let module = module.rsplit("::").next().unwrap_or(module);
+    let line = format!("[{:<5} {}] {}", level.name().to_ascii_uppercase(), module, message);
+
+    #[cfg(target_arch = "wasm32")]
+    match level {
+        Level::Error => macroquad::logging::error!("{}", line),
+        Level::Warn => macroquad::logging::warn!("{}", line),
+        Level::Info => macroquad::logging::info!("{}", line),
+        Level::Debug => macroquad::logging::debug!("{}", line),
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    eprintln!("{}", line);
+}
+
+#[macro_export]
+macro_rules! log_error {
+    ($($arg:tt)*) => {
+        $crate::modules::log::write($crate::modules::log::Level::Error, module_path!(), format_args!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! log_warn {
+    ($($arg:tt)*) => {
+        $crate::modules::log::write($crate::modules::log::Level::Warn, module_path!(), format_args!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! log_info {
+    ($($arg:tt)*) => {
+        $crate::modules::log::write($crate::modules::log::Level::Info, module_path!(), format_args!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! log_debug {
+    ($($arg:tt)*) => {
+        $crate::modules::log::write($crate::modules::log::Level::Debug, module_path!(), format_args!($($arg)*))
+    };
+}
@@ -0,0 +1,138 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: A warning when the bankroll runs low, with a cheaper table to move to
+
+Once the bankroll is down to a few of the table's smallest bets (how many is the
+player's pick, 20 to begin with, see Settings::low_bankroll_bets) a banner comes
+up over the table saying so. When one of the stakes in rules::STAKES is cheaper
+than the table being played it is offered as well: the dearest one the bankroll
+still has that many bets for, or the cheapest when there isn't one.
+
+check() says when the bankroll has just gone under the line, once each time, so
+the game can log it (GameEvent::LowBankroll, counted in the stats). The banner
+stays up until it is dismissed, a cheaper table is picked, or the bankroll gets
+back over the line.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod layers;
+    pub mod layout;
+    pub mod rules;
+    pub mod settings;
+    pub mod text_button;
+    pub mod ui;
+    pub mod low_bankroll;
+
+Then add the following with the use commands:
+use crate::modules::low_bankroll::{LowBankroll, LowBankrollAction};
+
+Usage examples:
+1. Create it before the loop:
+    let mut low_bankroll = LowBankroll::new();
+
+2. Between rounds, check the bankroll and log a new warning:
+    if low_bankroll.check(profile.info.bankroll, &profile.settings) {
+        bus.emit(GameEvent::LowBankroll { bankroll: profile.info.bankroll, minimum: profile.settings.rules.limits.min });
+    }
+
+3. Then show the banner, and move to the cheaper table if the player wants to:
+    match low_bankroll.update(layout, profile.info.bankroll, &profile.settings) {
+        Some(LowBankrollAction::Switch(limits)) => profile.settings.rules.limits = limits,
+        Some(LowBankrollAction::Dismiss) | None => {}
+    }
+*/
+use macroquad::prelude::*;
+use crate::modules::layers::{self, Layer};
+use crate::modules::layout::TableLayout;
+use crate::modules::rules::{Limits, STAKES};
+use crate::modules::settings::Settings;
+use crate::modules::text_button::TextButton;
+use crate::modules::ui;
+
+const HEIGHT: f32 = 60.0;
+const GAP: f32 = 10.0; // Between the banner and the Deal row under it
+const TEXT_SIZE: f32 = 22.0;
+
+pub enum LowBankrollAction {
+    Switch(Limits), // Play the same table at these stakes
+    Dismiss,
+}
+
+pub struct LowBankroll {
+    low: bool,     // The bankroll was under the line at the last check
+    showing: bool, // The banner is up
+    btn_switch: TextButton,
+    btn_dismiss: TextButton,
+}
+
+impl LowBankroll {
+    pub fn new() -> Self {
+        Self {
+            low: false,
+            showing: false,
+            btn_switch: TextButton::new(0.0, 0.0, 170.0, 44.0, "Move down", BLACK, DARKGREEN, 26),
+            btn_dismiss: TextButton::new(0.0, 0.0, 130.0, 44.0, "Dismiss", BLACK, DARKGREEN, 26),
+        }
+    }
+
+    // Look at the bankroll, true when it has just gone under the line (the banner comes up)
+    pub fn check(&mut self, bankroll: i64, settings: &Settings) -> bool {
+        let bets = settings.low_bankroll_bets();
+        let low = bets > 0 && bankroll < bets * settings.rules.limits.min;
+        let dropped = low && !self.low;
+        self.low = low;
+        self.showing = dropped || (self.showing && low);
+        dropped
+    }
+
+    // Place, handle and draw the banner, above the layout's Deal row
+    pub fn update(&mut self, layout: &TableLayout, bankroll: i64, settings: &Settings) -> Option<LowBankrollAction> {
+        if !self.showing {
+            return None;
+        }
+        let limits = settings.rules.limits;
+        let bets = settings.low_bankroll_bets();
+        let cheaper = cheaper_stakes(bankroll, bets, limits);
+        let right = layout.replay.x + layout.replay.w;
+        let banner = Rect::new(layout.deal.x, layout.deal.y - HEIGHT - GAP, right - layout.deal.x, HEIGHT);
+        let button_y = banner.y + (HEIGHT - 44.0) / 2.0;
+        self.btn_dismiss.update_position(right - 140.0, button_y, None, None);
+        self.btn_switch.update_position(right - 320.0, button_y, None, None);
+        self.btn_switch.enabled = cheaper.is_some();
+        self.btn_switch.set_disabled_reason("This is already the cheapest table");
+
+        // Under its own buttons, and the table under it doesn't take the pointer
+        ui::block(Layer::Hud, layers::BACKGROUND, banner);
+        let first = format!("Bankroll low: under {} of this table's smallest bets", bets);
+        let second = match cheaper {
+            Some(cheaper) => format!("The {} table would give you more hands", cheaper.text()),
+            None => "Bet smaller, or take a break".to_string(),
+        };
+        layers::push_at(Layer::Hud, layers::BACKGROUND, move || {
+            draw_rectangle(banner.x, banner.y, banner.w, banner.h, Color::new(0.2, 0.05, 0.0, 0.9));
+            draw_rectangle_lines(banner.x, banner.y, banner.w, banner.h, 2.0, ORANGE);
+            draw_text(&first, banner.x + 12.0, banner.y + 25.0, TEXT_SIZE, ORANGE);
+            draw_text(&second, banner.x + 12.0, banner.y + 49.0, TEXT_SIZE, WHITE);
+        });
+
+        let (switch, dismiss) = (self.btn_switch.click(), self.btn_dismiss.click());
+        if switch && let Some(cheaper) = cheaper {
+            self.showing = false;
+            return Some(LowBankrollAction::Switch(cheaper));
+        }
+        if dismiss {
+            self.showing = false;
+            return Some(LowBankrollAction::Dismiss);
+        }
+        None
+    }
+}
+
+// The stakes to suggest: of those cheaper than `limits`, the dearest the bankroll still has
+// `bets` smallest bets for, otherwise the cheapest. None at the cheapest stakes.
+fn cheaper_stakes(bankroll: i64, bets: i64, limits: Limits) -> Option<Limits> {
+    let cheaper: Vec<Limits> = STAKES.iter().copied().filter(|stakes| stakes.min < limits.min).collect();
+    cheaper.iter().rev().find(|stakes| bankroll >= bets * stakes.min).or(cheaper.first()).copied()
+}
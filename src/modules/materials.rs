@@ -0,0 +1,170 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Shader effects for the table, with plain drawing when they aren't available
+
+Two small shaders, compiled once at startup by load():
+    Vignette - the felt, lit in the middle and darker towards the edges in one smooth fade
+    Glow     - a soft halo around a rectangle, put behind the cards of the hand being played
+
+Shaders can fail to compile on some graphics drivers (older GPUs, some
+browsers' WebGL). load() logs the error and leaves that effect out, and the
+draw functions return false so the caller can draw its plain version instead.
+They also return false in low quality (see quality.rs), where effects are left
+out anyway.
+
+The draw functions draw straight away, so they go inside a layers::push closure
+like any other drawing. They put the default material back when they are done.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod quality;
+    pub mod materials;
+
+Then add the following with the use commands:
+use crate::modules::materials;
+
+Usage examples:
+1. Compile the shaders once the window is open:
+    materials::load();
+
+2. Draw the felt, with the old banded shading when the shader isn't there:
+    if !materials::draw_vignette(0.0, 0.0, width, height, felt_color) {
+        draw_rectangle(0.0, 0.0, width, height, felt_color);
+        draw_shading_bands();
+    }
+
+3. A glow 16 pixels wide around a hand's cards, before the cards are drawn:
+    if !materials::draw_glow(Rect::new(x, y, w, h), 16.0, GOLD) {
+        draw_rectangle(x, y + h + 6.0, w, 5.0, GOLD);
+    }
+*/
+use macroquad::prelude::*;
+use macroquad::miniquad::{BlendFactor, BlendState, BlendValue, Equation};
+use std::cell::RefCell;
+use crate::modules::quality;
+use crate::{log_info, log_warn};
+
+// Shared by both shaders: the vertex colour and where in the rectangle the pixel is (0 to 1 across)
+const VERTEX: &str = r#"#version 100
+attribute vec3 position;
+attribute vec2 texcoord;
+attribute vec4 color0;
+
+varying lowp vec2 uv;
+varying lowp vec4 color;
+
+uniform mat4 Model;
+uniform mat4 Projection;
+
+void main() {
+    gl_Position = Projection * Model * vec4(position, 1);
+    color = color0 / 255.0;
+    uv = texcoord;
+}"#;
+
+// Full colour in the middle, down to 1 - Strength in the corners
+const VIGNETTE_FRAGMENT: &str = r#"#version 100
+varying lowp vec2 uv;
+varying lowp vec4 color;
+
+uniform mediump float Strength;
+
+void main() {
+    mediump float edge = length((uv - 0.5) * 1.4);
+    mediump float dark = smoothstep(0.35, 1.0, edge) * Strength;
+    gl_FragColor = vec4(color.rgb * (1.0 - dark), color.a);
+}"#;
+
+// Solid over the inner rectangle, fading out over Radius pixels around it
+const GLOW_FRAGMENT: &str = r#"#version 100
+varying lowp vec2 uv;
+varying lowp vec4 color;
+
+uniform mediump vec2 Size;
+uniform mediump float Radius;
+
+void main() {
+    mediump vec2 from_middle = abs(uv - 0.5) * Size;
+    mediump vec2 outside = max(from_middle - (Size * 0.5 - Radius), 0.0);
+    mediump float fade = 1.0 - smoothstep(0.0, Radius, length(outside));
+    gl_FragColor = vec4(color.rgb, color.a * fade * fade);
+}"#;
+
+const VIGNETTE_STRENGTH: f32 = 0.35;
+
+struct Materials {
+    vignette: Option<Material>,
+    glow: Option<Material>,
+}
+
+thread_local! {
+    static MATERIALS: RefCell<Materials> = const { RefCell::new(Materials { vignette: None, glow: None }) };
+}
+
+// Compile one shader, None (and a warning in the log) if the driver won't take it
+fn compile(name: &str, fragment: &str, uniforms: Vec<UniformDesc>) -> Option<Material> {
+    let pipeline_params = PipelineParams {
+        color_blend: Some(BlendState::new(
+            Equation::Add,
+            BlendFactor::Value(BlendValue::SourceAlpha),
+            BlendFactor::OneMinusValue(BlendValue::SourceAlpha),
+        )),
+        ..Default::default()
+    };
+    let params = MaterialParams { pipeline_params, uniforms, ..Default::default() };
+    match load_material(ShaderSource::Glsl { vertex: VERTEX, fragment }, params) {
+        Ok(material) => Some(material),
+        Err(err) => {
+            log_warn!("the {} shader did not compile, drawing without it: {:?}", name, err);
+            None
+        }
+    }
+}
+
+// Compile the shaders. Needs the window, call it at the start of main.
+pub fn load() {
+    let vignette = compile("vignette", VIGNETTE_FRAGMENT, vec![UniformDesc::new("Strength", UniformType::Float1)]);
+    let glow = compile(
+        "glow",
+        GLOW_FRAGMENT,
+        vec![UniformDesc::new("Size", UniformType::Float2), UniformDesc::new("Radius", UniformType::Float1)],
+    );
+    log_info!("shaders: vignette {}, glow {}", if vignette.is_some() { "on" } else { "off" }, if glow.is_some() { "on" } else { "off" });
+    MATERIALS.with(|materials| *materials.borrow_mut() = Materials { vignette, glow });
+}
+
+// A shader to draw with, None when it didn't compile or effects are low
+fn material(pick: impl Fn(&Materials) -> Option<Material>) -> Option<Material> {
+    if quality::low() {
+        return None;
+    }
+    MATERIALS.with(|materials| pick(&materials.borrow()))
+}
+
+// Fill a rectangle with `color`, darker towards its edges. False (and nothing drawn) without the shader.
+pub fn draw_vignette(x: f32, y: f32, w: f32, h: f32, color: Color) -> bool {
+    let Some(vignette) = material(|materials| materials.vignette.clone()) else {
+        return false;
+    };
+    gl_use_material(&vignette);
+    vignette.set_uniform("Strength", VIGNETTE_STRENGTH);
+    draw_rectangle(x, y, w, h, color);
+    gl_use_default_material();
+    true
+}
+
+// A soft glow `radius` wide around `rect`, filled inside. False (and nothing drawn) without the shader.
+pub fn draw_glow(rect: Rect, radius: f32, color: Color) -> bool {
+    let Some(glow) = material(|materials| materials.glow.clone()) else {
+        return false;
+    };
+    let size = vec2(rect.w + radius * 2.0, rect.h + radius * 2.0);
+    gl_use_material(&glow);
+    glow.set_uniform("Size", size);
+    glow.set_uniform("Radius", radius);
+    draw_rectangle(rect.x - radius, rect.y - radius, size.x, size.y, color);
+    gl_use_default_material();
+    true
+}
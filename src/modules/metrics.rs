@@ -0,0 +1,183 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Counters and timing histograms for the game engine, read out as one report
+
+The engine counts what it does as it does it, whoever is driving it (the table,
+the scenario simulator or its benchmark):
+    hands_played              every player hand settled
+    rounds_played             every round settled
+    shoe_shuffles             every shuffle of a shoe
+    actions                   hits and stands played by the simulator
+and times the work that matters at scale, in seconds:
+    round_seconds             the simulator playing one round, deal to settle
+    action_seconds            the simulator playing one hit or stand
+
+Only the simulator is timed, and it is only in debug builds and tests, so a
+release build counts but keeps its histograms empty.
+
+Everything is global and safe to touch from any thread (the tests play hands on
+several at once), counters are atomics and the histograms sit behind a mutex.
+Histograms keep a count per bucket, cumulative like Prometheus does it, so the
+report is already in the Prometheus text format and can be served as it is by
+anything that wants to expose a /metrics endpoint.
+
+The developer console prints a short summary with `metrics`, the simulator
+benchmark prints the whole report when it is done.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod metrics;
+
+Then add the following with the use commands:
+use crate::modules::metrics::{self, Counter, Histogram};
+
+Usage examples:
+1. Count something:
+    metrics::count(Counter::ShoeShuffles, 1);
+
+2. Time something (returns what the closure returns):
+    let played = metrics::time(Histogram::RoundSeconds, || play_round(&mut shoe));
+
+3. Print everything, or the short version:
+    print!("{}", metrics::report());
+    for line in metrics::summary() {
+        console.print(line);
+    }
+*/
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Counter {
+    HandsPlayed,
+    RoundsPlayed,
+    ShoeShuffles,
+    Actions,
+}
+
+impl Counter {
+    const ALL: [Counter; 4] = [Counter::HandsPlayed, Counter::RoundsPlayed, Counter::ShoeShuffles, Counter::Actions];
+
+    fn name(self) -> &'static str {
+        match self {
+            Counter::HandsPlayed => "hands_played",
+            Counter::RoundsPlayed => "rounds_played",
+            Counter::ShoeShuffles => "shoe_shuffles",
+            Counter::Actions => "actions",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Histogram {
+    RoundSeconds,
+    ActionSeconds,
+}
+
+impl Histogram {
+    const ALL: [Histogram; 2] = [Histogram::RoundSeconds, Histogram::ActionSeconds];
+
+    fn name(self) -> &'static str {
+        match self {
+            Histogram::RoundSeconds => "round_seconds",
+            Histogram::ActionSeconds => "action_seconds",
+        }
+    }
+}
+
+// Upper bounds of the buckets, a microsecond to a tenth of a second. Anything slower lands in +Inf.
+const BUCKETS: [f64; 6] = [0.000_001, 0.000_01, 0.000_1, 0.001, 0.01, 0.1];
+
+// One histogram's numbers
+#[derive(Clone, Copy)]
+struct Buckets {
+    counts: [u64; BUCKETS.len() + 1], // The last is +Inf
+    sum: f64,
+}
+
+impl Buckets {
+    const EMPTY: Buckets = Buckets { counts: [0; BUCKETS.len() + 1], sum: 0.0 };
+}
+
+static COUNTERS: [AtomicU64; Counter::ALL.len()] = [const { AtomicU64::new(0) }; Counter::ALL.len()];
+static HISTOGRAMS: Mutex<[Buckets; Histogram::ALL.len()]> = Mutex::new([Buckets::EMPTY; Histogram::ALL.len()]);
+
+// Add to a counter
+pub fn count(counter: Counter, amount: u64) {
+    COUNTERS[counter as usize].fetch_add(amount, Ordering::Relaxed);
+}
+
+// A counter's value so far
+pub fn counter(counter: Counter) -> u64 {
+    COUNTERS[counter as usize].load(Ordering::Relaxed)
+}
+
+// Put one measurement in a histogram
+#[allow(unused)]
+pub fn observe(histogram: Histogram, value: f64) {
+    let bucket = BUCKETS.iter().position(|bound| value <= *bound).unwrap_or(BUCKETS.len());
+    let mut histograms = HISTOGRAMS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let buckets = &mut histograms[histogram as usize];
+    buckets.counts[bucket] += 1;
+    buckets.sum += value;
+}
+
+// Run `work` and put how long it took in a histogram
+#[allow(unused)]
+pub fn time<T>(histogram: Histogram, work: impl FnOnce() -> T) -> T {
+    let start = now();
+    let result = work();
+    observe(histogram, now() - start);
+    result
+}
+
+// Seconds from a clock that works everywhere, the web included, without a window
+#[allow(unused)]
+fn now() -> f64 {
+    macroquad::miniquad::date::now()
+}
+
+// A few short lines for a small screen: the counters, then each histogram's count and average
+#[allow(unused)]
+pub fn summary() -> Vec<String> {
+    let counters: Vec<String> = Counter::ALL.iter().map(|counter| format!("{} {}", counter.name(), self::counter(*counter))).collect();
+    let mut lines = vec![counters.join(", ")];
+    let histograms = *HISTOGRAMS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    for histogram in Histogram::ALL {
+        let buckets = histograms[histogram as usize];
+        let total: u64 = buckets.counts.iter().sum();
+        let average = if total > 0 { buckets.sum / total as f64 } else { 0.0 };
+        lines.push(format!("{}: {} timed, {:.2} us on average", histogram.name(), total, average * 1_000_000.0));
+    }
+    lines
+}
+
+// Everything so far in the Prometheus text format
+#[allow(unused)]
+pub fn report() -> String {
+    const PREFIX: &str = "blackjack_";
+    let mut text = String::new();
+    for counter in Counter::ALL {
+        let name = format!("{}{}_total", PREFIX, counter.name());
+        let _ = writeln!(text, "# TYPE {} counter", name);
+        let _ = writeln!(text, "{} {}", name, self::counter(counter));
+    }
+    let histograms = *HISTOGRAMS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    for histogram in Histogram::ALL {
+        let name = format!("{}{}", PREFIX, histogram.name());
+        let buckets = histograms[histogram as usize];
+        let _ = writeln!(text, "# TYPE {} histogram", name);
+        let mut total = 0;
+        for (i, count) in buckets.counts.iter().enumerate() {
+            total += count;
+            let bound = BUCKETS.get(i).map_or("+Inf".to_string(), |bound| bound.to_string());
+            let _ = writeln!(text, "{}_bucket{{le=\"{}\"}} {}", name, bound, total);
+        }
+        let _ = writeln!(text, "{}_sum {}", name, buckets.sum);
+        let _ = writeln!(text, "{}_count {}", name, total);
+    }
+    text
+}
@@ -17,4 +17,100 @@ use crate::modules::grid::draw_grid;
     pub mod still_image;
     pub mod label;
     pub mod preload_image;
-    pub mod scale;
\ No newline at end of file
+    pub mod scale;
+    pub mod layout;
+    pub mod settings;
+    pub mod web_bridge;
+    pub mod screenshot;
+    pub mod cards;
+    pub mod shoe;
+    pub mod animation;
+    pub mod shuffle_animation;
+    pub mod events;
+    pub mod stats;
+    pub mod hand;
+    pub mod entropy;
+    pub mod audit;
+    pub mod storage;
+    pub mod achievements;
+    pub mod profile;
+    pub mod profile_menu;
+    pub mod http;
+    pub mod tooltip;
+    pub mod layers;
+    #[cfg(debug_assertions)]
+    pub mod debug_overlay;
+    pub mod log;
+    pub mod crash;
+    pub mod shoe_view;
+    pub mod hand_badge;
+    pub mod streak_indicator;
+    pub mod dialog;
+    pub mod history;
+    pub mod strategy;
+    pub mod decision_timer;
+    pub mod rules;
+    pub mod hole_card;
+    pub mod felt;
+    pub mod i18n;
+    pub mod results_strip;
+    pub mod slider;
+    pub mod audio;
+    pub mod input;
+    pub mod bet;
+    pub mod chart;
+    pub mod count_trainer;
+    pub mod profile_transfer;
+    pub mod widget_style;
+    pub mod frame_pacer;
+    pub mod text_cache;
+    pub mod shapes;
+    pub mod scroll_panel;
+    pub mod widget_layout;
+    pub mod card_view;
+    pub mod card_pool;
+    pub mod timer;
+    pub mod odds;
+    pub mod ev;
+    pub mod goals;
+    pub mod cursor;
+    pub mod bankroll;
+    pub mod draw;
+    pub mod countdown;
+    pub mod version;
+    pub mod card_face;
+    pub mod asset_check;
+    pub mod chip;
+    pub mod hand_snapshot;
+    pub mod drills;
+    pub mod keys;
+    pub mod speech;
+    pub mod squeeze;
+    pub mod advisor;
+    pub mod strategy_chart;
+    pub mod nameplate;
+    pub mod metrics;
+    pub mod quality;
+    pub mod theme;
+    pub mod ui;
+    pub mod famous_hands;
+    pub mod rules_editor;
+    pub mod autosave;
+    pub mod shoe_meter;
+    pub mod scene_fade;
+    pub mod materials;
+    pub mod analytics;
+    pub mod frame;
+    pub mod low_bankroll;
+    pub mod quick_settings;
+    pub mod pacing;
+    #[cfg(all(feature = "gif_recorder", not(target_arch = "wasm32")))]
+    pub mod gif_recorder;
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod input_recorder;
+    #[cfg(debug_assertions)]
+    pub mod dev_console;
+    #[cfg(any(test, debug_assertions))]
+    pub mod scenario;
+    #[cfg(test)]
+    pub mod bench;
@@ -0,0 +1,146 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: A seat's nameplate: avatar, name, bankroll and bet, lit up while it is the seat's turn
+
+The nameplate is a small rounded panel over the table:
+    [avatar]  Tyler
+              $1,250   Bet $25
+The layout puts one at each of its seats (TableLayout::seats), the player's is
+the first. While it is the seat's turn a gold glow pulses around the edge and
+fades away again when the turn is over.
+
+The avatar is looked up in the TextureManager when it is drawn, so it can be set
+before the picture has loaded.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod animation;
+    pub mod i18n;
+    pub mod layers;
+    pub mod preload_image;
+    pub mod shapes;
+    pub mod text_cache;
+    pub mod nameplate;
+
+Then add the following with the use commands:
+use crate::modules::nameplate::Nameplate;
+
+Usage examples:
+1. Create it before the loop and put it on its seat:
+    let mut nameplate = Nameplate::new(&tm);
+    let seat = layout.seats[0];
+    nameplate.set_area(Rect::new(seat.x, seat.y, seat.w, seat.h));
+
+2. Say who sits there and what they have:
+    nameplate.set_player(&info.name, info.avatar_path());
+    nameplate.set_bankroll(bankroll);
+    nameplate.set_bet(bet.total());
+    nameplate.set_turn(phase == GamePhase::PlayerTurn);
+
+3. Every frame:
+    nameplate.draw();
+*/
+use macroquad::prelude::*;
+use crate::modules::animation::{animation_time, Fade};
+use crate::modules::i18n;
+use crate::modules::layers::{self, Layer};
+use crate::modules::preload_image::TextureManager;
+use crate::modules::shapes::ShapeBatch;
+use crate::modules::text_cache::measure_text_cached;
+
+const PADDING: f32 = 6.0;
+const CORNER: f32 = 8.0;
+const NAME_SIZE: f32 = 24.0;
+const MONEY_SIZE: f32 = 20.0;
+const GLOW_TIME: f32 = 0.3; // Fading in and out as the turn starts and ends
+const PULSE_SPEED: f32 = 4.0;
+
+pub struct Nameplate {
+    tm: TextureManager,
+    area: Rect,
+    name: String,
+    avatar: String, // Path of the avatar picture
+    bankroll: i64,
+    bet: i64,
+    turn: bool,
+    glow: Fade,
+}
+
+impl Nameplate {
+    pub fn new(tm: &TextureManager) -> Self {
+        Self {
+            tm: tm.clone(),
+            area: Rect::default(),
+            name: String::new(),
+            avatar: String::new(),
+            bankroll: 0,
+            bet: 0,
+            turn: false,
+            glow: Fade::new(GLOW_TIME),
+        }
+    }
+
+    pub fn set_area(&mut self, area: Rect) {
+        self.area = area;
+    }
+
+    pub fn set_player(&mut self, name: &str, avatar: &str) {
+        self.name = name.to_string();
+        self.avatar = avatar.to_string();
+    }
+
+    pub fn set_bankroll(&mut self, bankroll: i64) {
+        self.bankroll = bankroll;
+    }
+
+    // The bet on the table, 0 for none
+    pub fn set_bet(&mut self, bet: i64) {
+        self.bet = bet;
+    }
+
+    // Whether the seat is the one being played
+    pub fn set_turn(&mut self, turn: bool) {
+        self.turn = turn;
+    }
+
+    // Queue the nameplate into the HUD layer
+    pub fn draw(&self) {
+        let glow = self.glow.update(self.turn);
+        if self.area.w <= 0.0 {
+            return;
+        }
+        let area = self.area;
+        let avatar = self.tm.get_texture(&self.avatar);
+        let avatar_size = area.h - PADDING * 2.0;
+        let text_x = area.x + PADDING * 2.0 + avatar_size;
+        let name = self.name.clone();
+        let bankroll = i18n::money(self.bankroll);
+        let bet = if self.bet > 0 { format!("Bet {}", i18n::money(self.bet)) } else { String::new() };
+        // A slow pulse on top of the fade, so the seat being played catches the eye
+        let pulse = 0.65 + 0.35 * (animation_time() * PULSE_SPEED).sin();
+        layers::push(Layer::Hud, move || {
+            let mut shapes = ShapeBatch::new();
+            if glow > 0.0 {
+                let halo = Rect::new(area.x - 3.0, area.y - 3.0, area.w + 6.0, area.h + 6.0);
+                shapes.outline(halo, CORNER + 3.0, 4.0, Color::new(GOLD.r, GOLD.g, GOLD.b, glow * pulse));
+            }
+            shapes.fill(area, CORNER, Color::new(0.0, 0.0, 0.0, 0.55));
+            shapes.outline(area, CORNER, 1.0, Color::new(1.0, 1.0, 1.0, 0.3));
+            shapes.draw();
+            if let Some(texture) = &avatar {
+                let params = DrawTextureParams { dest_size: Some(vec2(avatar_size, avatar_size)), ..Default::default() };
+                draw_texture_ex(texture, area.x + PADDING, area.y + PADDING, WHITE, params);
+            }
+            let line = (area.h - PADDING * 2.0) / 2.0;
+            draw_text(&name, text_x, area.y + PADDING + line * 0.8, NAME_SIZE, WHITE);
+            let money_y = area.y + PADDING + line * 1.8;
+            draw_text(&bankroll, text_x, money_y, MONEY_SIZE, GOLD);
+            if !bet.is_empty() {
+                let width = measure_text_cached(&bet, None, MONEY_SIZE as u16).width;
+                draw_text(&bet, area.x + area.w - PADDING - width, money_y, MONEY_SIZE, WHITE);
+            }
+        });
+    }
+}
@@ -0,0 +1,264 @@
+/*
+By: <tyler>
+Date: 2025-12-09
+Program Details: Live odds for the hand being played
+
+Two numbers worked out exactly, not by dealing thousands of pretend hands:
+- the chance that one more card busts the player's hand
+- how the dealer's hand will end up from the up card: each total the dealer can
+  stand on (16 - 21), a blackjack, or a bust
+The dealer follows the same rule as the game (rules::DEALER_STANDS_ON, soft
+totals count). When the dealer has already peeked under an ace or a ten and
+play went on, the hole card can't be the one that makes blackjack, and the
+odds leave it out.
+
+The cards that can come next are a Composition: either a full deck that never
+runs out (the odds any table starts with), or exactly the cards the player
+hasn't seen yet, from the counting trainer's view of the shoe.
+
+The dealer's odds are worked out by following every card the dealer could draw,
+and every hand state the dealer goes through is remembered along with the cards
+that were left, so a state that comes up again (the same totals reached in a
+different order) is only worked out once. The memory is emptied when it gets big.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod cards;
+    pub mod chart;
+    pub mod hand;
+    pub mod rules;
+    pub mod odds;
+
+Then add the following with the use commands:
+use crate::modules::odds::{Composition, OddsPanel};
+
+Usage examples:
+1. Create the panel before the loop:
+    let mut odds = OddsPanel::new();
+
+2. Every frame, draw it with the hand being played (None hides the bust line),
+   the dealer's up card and the cards that could come next:
+    let unseen = Composition::from_ranks(trainer.unseen(hole_card));   // or Composition::full_deck()
+    odds.draw(area, Some(&player_hand), up_card, &unseen, rules.dealer_checks(up_card));
+
+3. Or just the numbers:
+    let bust = bust_chance(&player_hand, &Composition::full_deck());
+    let dealer = OddsCalculator::new().dealer_odds(up_card, &Composition::full_deck(), false);
+    println!("{:.0}% to bust, dealer busts {:.0}%", bust * 100.0, dealer.bust * 100.0);
+*/
+use std::collections::HashMap;
+use macroquad::prelude::*;
+use crate::modules::cards::{Card, Rank};
+use crate::modules::chart::{bar_chart, Bar};
+use crate::modules::hand::Hand;
+use crate::modules::layers::{self, Layer};
+use crate::modules::rules::DEALER_STANDS_ON;
+
+const MEMO_LIMIT: usize = 200_000; // Remembered dealer states before starting over
+const TEXT_WIDTH: f32 = 110.0; // Room on the right for the bust chance
+
+// Cards that can come next, counted by value: aces first (index 0), then 2 - 9, then every ten-value card
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Composition {
+    left: [u32; 10],
+    runs_out: bool, // False for the full deck that never runs out
+}
+
+impl Composition {
+    // A fresh deck's chances for every card
+    pub fn full_deck() -> Self {
+        Self { left: [4, 4, 4, 4, 4, 4, 4, 4, 4, 16], runs_out: false }
+    }
+
+    // From cards left of each rank, in RANKS order (2 - A)
+    pub fn from_ranks(left: [u32; 13]) -> Self {
+        let mut by_value = [0; 10];
+        for (index, count) in left.into_iter().enumerate() {
+            // RANKS runs 2 to 10, J, Q, K, then A
+            let value = match index {
+                12 => 1,
+                0..=8 => index + 2,
+                _ => 10,
+            };
+            by_value[value - 1] += count;
+        }
+        Self { left: by_value, runs_out: true }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.left.iter().sum()
+    }
+
+    // Cards left of a value, 1 (ace) to 10
+    pub fn count(&self, value: u32) -> u32 {
+        self.left[value as usize - 1]
+    }
+
+    // False for the full deck, whose chances never change as cards come out
+    pub fn runs_out(&self) -> bool {
+        self.runs_out
+    }
+
+    // The same cards after one of `value` has been drawn
+    fn without(&self, value: u32) -> Self {
+        let mut next = *self;
+        if next.runs_out {
+            next.left[value as usize - 1] -= 1;
+        }
+        next
+    }
+}
+
+// Hard total (aces as 1) and whether there is an ace that can count as 11
+pub fn best_total(hard: u32, has_ace: bool) -> u32 {
+    if has_ace && hard + 10 <= 21 { hard + 10 } else { hard }
+}
+
+// What a card adds to the hard total, aces as 1
+pub fn card_value(card: Card) -> u32 {
+    if card.rank == Rank::Ace { 1 } else { card.value().min(10) }
+}
+
+// The chance that one more card takes the hand over 21
+pub fn bust_chance(hand: &Hand, unseen: &Composition) -> f32 {
+    let hard: u32 = hand.cards().iter().map(|card| card_value(*card)).sum();
+    let unseen = if unseen.total() == 0 { Composition::full_deck() } else { *unseen };
+    // An ace counting as 11 can always drop to 1, so only the hard total can bust
+    let busting: u32 = (1..=10).filter(|value| hard + value > 21).map(|value| unseen.count(value)).sum();
+    busting as f32 / unseen.total() as f32
+}
+
+// How the dealer's hand ends, each a chance from 0 to 1 and adding up to 1
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DealerOdds {
+    pub totals: [f32; 6], // Standing on DEALER_STANDS_ON (16) up to 21
+    pub blackjack: f32,
+    pub bust: f32,
+}
+
+impl DealerOdds {
+    fn add(&mut self, other: &DealerOdds, weight: f32) {
+        for (total, other_total) in self.totals.iter_mut().zip(other.totals) {
+            *total += other_total * weight;
+        }
+        self.blackjack += other.blackjack * weight;
+        self.bust += other.bust * weight;
+    }
+}
+
+// A dealer hand part way through, with the cards that are left
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct DealerState {
+    hard: u32,
+    has_ace: bool,
+    cards: u32,   // 1, 2, or 3 for three or more (nothing changes after the third card)
+    peeked: bool, // Only kept while the hole card is still to come
+    unseen: Composition,
+}
+
+pub struct OddsCalculator {
+    memo: HashMap<DealerState, DealerOdds>,
+}
+
+impl OddsCalculator {
+    pub fn new() -> Self {
+        Self { memo: HashMap::new() }
+    }
+
+    // How the dealer ends up from `up_card`. `peeked` is true when the dealer has
+    // already checked the hole card for blackjack and play went on.
+    pub fn dealer_odds(&mut self, up_card: Card, unseen: &Composition, peeked: bool) -> DealerOdds {
+        if self.memo.len() > MEMO_LIMIT {
+            self.memo.clear();
+        }
+        let unseen = if unseen.total() == 0 { Composition::full_deck() } else { *unseen };
+        let value = card_value(up_card);
+        self.play(DealerState { hard: value, has_ace: value == 1, cards: 1, peeked, unseen })
+    }
+
+    fn play(&mut self, state: DealerState) -> DealerOdds {
+        let total = best_total(state.hard, state.has_ace);
+        // The same drawing rule as rules::dealer_draws
+        if state.cards >= 2 && total >= DEALER_STANDS_ON {
+            let mut odds = DealerOdds::default();
+            if total > 21 {
+                odds.bust = 1.0;
+            } else if state.cards == 2 && total == 21 {
+                odds.blackjack = 1.0;
+            } else {
+                odds.totals[(total - DEALER_STANDS_ON) as usize] = 1.0;
+            }
+            return odds;
+        }
+        if let Some(odds) = self.memo.get(&state) {
+            return *odds;
+        }
+        let hole_card = state.cards == 1;
+        // The hole card can't make a blackjack the peek would have found
+        let allowed = |value: u32| !(hole_card && state.peeked && best_total(state.hard + value, state.has_ace || value == 1) == 21);
+        let draws: u32 = (1..=10).filter(|value| allowed(*value)).map(|value| state.unseen.count(value)).sum();
+        let mut odds = DealerOdds::default();
+        if draws == 0 {
+            // Nothing left that could come, the dealer stands where they are
+            odds.totals[(total.clamp(DEALER_STANDS_ON, 21) - DEALER_STANDS_ON) as usize] = 1.0;
+            return odds;
+        }
+        for value in (1..=10).filter(|value| allowed(*value)) {
+            let count = state.unseen.count(value);
+            if count == 0 {
+                continue;
+            }
+            let next = DealerState {
+                hard: state.hard + value,
+                has_ace: state.has_ace || value == 1,
+                cards: (state.cards + 1).min(3),
+                peeked: false,
+                unseen: state.unseen.without(value),
+            };
+            let outcome = self.play(next);
+            odds.add(&outcome, count as f32 / draws as f32);
+        }
+        self.memo.insert(state, odds);
+        odds
+    }
+}
+
+// A HUD strip with the dealer's odds as bars and the chance the next hit busts
+pub struct OddsPanel {
+    calculator: OddsCalculator,
+}
+
+impl OddsPanel {
+    pub fn new() -> Self {
+        Self { calculator: OddsCalculator::new() }
+    }
+
+    // Queue the panel into the HUD layer. Nothing is drawn before the dealer has an up card.
+    pub fn draw(&mut self, area: Rect, player_hand: Option<&Hand>, up_card: Option<Card>, unseen: &Composition, peeked: bool) {
+        let Some(up_card) = up_card else {
+            return;
+        };
+        let dealer = self.calculator.dealer_odds(up_card, unseen, peeked);
+        let bust = player_hand.map(|hand| bust_chance(hand, unseen));
+        let mut bars: Vec<Bar> = dealer
+            .totals
+            .iter()
+            .enumerate()
+            .map(|(i, chance)| Bar::new((DEALER_STANDS_ON + i as u32).to_string(), *chance, WHITE))
+            .collect();
+        bars.push(Bar::new("BJ", dealer.blackjack, GOLD));
+        bars.push(Bar::new("Bust", dealer.bust, RED));
+        let dealer_bust = dealer.bust;
+        layers::push(Layer::Hud, move || {
+            draw_rectangle(area.x, area.y, area.w, area.h, Color::new(0.0, 0.0, 0.0, 0.45));
+            let chart_area = Rect::new(area.x + 4.0, area.y + 4.0, area.w - TEXT_WIDTH - 8.0, area.h - 8.0);
+            bar_chart(chart_area, &bars, 1.0);
+            let text_x = area.x + area.w - TEXT_WIDTH;
+            draw_text(&format!("Dealer {:.0}%", dealer_bust * 100.0), text_x, area.y + area.h * 0.4, 20.0, RED);
+            if let Some(bust) = bust {
+                draw_text(&format!("Hit {:.0}%", bust * 100.0), text_x, area.y + area.h * 0.85, 20.0, GOLD);
+            }
+        });
+    }
+}
@@ -0,0 +1,212 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: How fast the player is playing this session
+
+Pacing listens to the event bus and times the session from the events' own
+timestamps (see events.rs), so the game code never starts or stops a clock:
+    Hands an hour  - hands settled over the time spent playing. A gap of more
+                     than IDLE_GAP between two events (the player was away, or
+                     sat in the menus) only counts as IDLE_GAP.
+    Decision time  - from the last thing the player was shown (a card dealt, an
+                     even money answer, the hand before standing) to their hit
+                     or stand, on average and by the kind of hand (SITUATIONS).
+                     Decisions over AWAY seconds are left out, the player wasn't
+                     really deciding all that time.
+A move played by the decision timer running out isn't the player's, so it isn't
+timed. Like the session goals, none of it is saved.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod events;
+    pub mod pacing;
+
+Then add the following with the use commands:
+use crate::modules::pacing::Pacing;
+
+Usage examples:
+1. Create it before the loop and pass it to the event bus every frame:
+    let mut pacing = Pacing::new();
+    bus.dispatch(&mut [&mut pacing]);
+
+2. Show it:
+    println!("{}", pacing.summary_text());   // "42 hands in 18 min, 140 an hour, 3.2s a decision"
+    println!("{}", pacing.decisions_text()); // "Hard 4-11 1.1s, Hard 12-16 4.0s, ..."
+
+3. Or read the numbers:
+    if let Some(seconds) = pacing.average_decision(1) {
+        println!("Hard 12-16 takes you {:.1}s", seconds);
+    }
+*/
+use crate::modules::events::{self, EventListener, GameEvent};
+
+// Longest gap between two events counted as playing, in seconds
+const IDLE_GAP: f64 = 120.0;
+
+// Longest decision that is timed, in seconds
+const AWAY: f64 = 60.0;
+
+// Playing time before hands an hour is worth working out, in seconds
+const SETTLE_TIME: f64 = 60.0;
+
+// The kinds of hand decisions are timed by
+pub const SITUATIONS: [&str; 5] = ["Hard 4-11", "Hard 12-16", "Hard 17+", "Soft 13-17", "Soft 18+"];
+
+// Decisions made in one situation and the seconds they took together
+#[derive(Clone, Copy, Debug, Default)]
+struct Timing {
+    decisions: u32,
+    seconds: f64,
+}
+
+pub struct Pacing {
+    hands: u32,
+    playing: f64,            // Seconds spent playing, long gaps cut down to IDLE_GAP
+    last_event: Option<f64>,
+    asked: Option<f64>,      // When the player was last shown something to decide on
+    timings: [Timing; SITUATIONS.len()],
+}
+
+impl Pacing {
+    pub fn new() -> Self {
+        Self { hands: 0, playing: 0.0, last_event: None, asked: None, timings: [Timing::default(); SITUATIONS.len()] }
+    }
+
+    // Hands settled this session
+    #[allow(unused)]
+    pub fn hands(&self) -> u32 {
+        self.hands
+    }
+
+    // Minutes spent playing this session
+    pub fn minutes(&self) -> f64 {
+        self.playing / 60.0
+    }
+
+    // Hands settled an hour, None until there has been a minute of play
+    pub fn hands_per_hour(&self) -> Option<f64> {
+        (self.playing >= SETTLE_TIME).then(|| self.hands as f64 * 3600.0 / self.playing)
+    }
+
+    // Average seconds a decision in one of SITUATIONS took, None before the first one
+    pub fn average_decision(&self, situation: usize) -> Option<f64> {
+        self.timings.get(situation).and_then(average)
+    }
+
+    // Average seconds a decision took, every situation together
+    pub fn average_all(&self) -> Option<f64> {
+        let all = self.timings.iter().fold(Timing::default(), |all, timing| Timing {
+            decisions: all.decisions + timing.decisions,
+            seconds: all.seconds + timing.seconds,
+        });
+        average(&all)
+    }
+
+    // "42 hands in 18 min, 140 an hour, 3.2s a decision"
+    pub fn summary_text(&self) -> String {
+        let rate = self.hands_per_hour().map_or("- an hour".to_string(), |rate| format!("{:.0} an hour", rate));
+        let decision = self.average_all().map_or("-".to_string(), |seconds| format!("{:.1}s", seconds));
+        format!("{} hands in {:.0} min, {}, {} a decision", self.hands, self.minutes(), rate, decision)
+    }
+
+    // "Hard 4-11 1.1s, Hard 12-16 4.0s, ..." with - for situations not played yet
+    pub fn decisions_text(&self) -> String {
+        SITUATIONS
+            .iter()
+            .enumerate()
+            .map(|(situation, name)| {
+                let seconds = self.average_decision(situation).map_or("-".to_string(), |seconds| format!("{:.1}s", seconds));
+                format!("{} {}", name, seconds)
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+impl EventListener for Pacing {
+    fn on_event(&mut self, event: &GameEvent) {
+        self.on_timed_event(event, events::now());
+    }
+
+    fn on_timed_event(&mut self, event: &GameEvent, time: f64) {
+        if let Some(last) = self.last_event {
+            self.playing += (time - last).clamp(0.0, IDLE_GAP);
+        }
+        self.last_event = Some(time);
+        match event {
+            GameEvent::PlayerDecision { total, soft, .. } => {
+                if let Some(asked) = self.asked
+                    && time - asked <= AWAY
+                {
+                    let timing = &mut self.timings[situation(*total, *soft)];
+                    timing.decisions += 1;
+                    timing.seconds += (time - asked).max(0.0);
+                }
+                self.asked = Some(time);
+            }
+            GameEvent::CardDealt { .. } | GameEvent::EvenMoney { .. } => self.asked = Some(time),
+            GameEvent::RoundSettled { .. } => self.hands += 1,
+            GameEvent::RoundFinished => self.asked = None,
+            _ => {}
+        }
+    }
+}
+
+fn average(timing: &Timing) -> Option<f64> {
+    (timing.decisions > 0).then(|| timing.seconds / timing.decisions as f64)
+}
+
+// Which of SITUATIONS a hand's total is
+fn situation(total: u32, soft: bool) -> usize {
+    match (soft, total) {
+        (false, ..=11) => 0,
+        (false, 12..=16) => 1,
+        (false, _) => 2,
+        (true, ..=17) => 3,
+        (true, _) => 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::cards::Card;
+    use crate::modules::events::Outcome;
+
+    fn decision(total: u32, soft: bool) -> GameEvent {
+        GameEvent::PlayerDecision { hand: 0, total, soft, dealer_up: Card::deck()[0], hit: false }
+    }
+
+    fn settled() -> GameEvent {
+        GameEvent::RoundSettled { hand: 0, outcome: Outcome::Push, blackjack: false, player_total: 18, dealer_total: 18, change: 0 }
+    }
+
+    #[test]
+    fn decisions_are_timed_from_the_last_card() {
+        let mut pacing = Pacing::new();
+        let card = GameEvent::CardDealt { card: Card::deck()[0], seat: events::Seat::Player(0) };
+        pacing.on_timed_event(&card, 10.0);
+        pacing.on_timed_event(&decision(16, false), 14.0);
+        pacing.on_timed_event(&card, 20.0);
+        pacing.on_timed_event(&decision(14, false), 22.0);
+        // Away from the table, not timed
+        pacing.on_timed_event(&card, 30.0);
+        pacing.on_timed_event(&decision(19, true), 200.0);
+        assert_eq!(pacing.average_decision(1), Some(3.0));
+        assert_eq!(pacing.average_decision(4), None);
+        assert_eq!(pacing.average_all(), Some(3.0));
+    }
+
+    #[test]
+    fn long_breaks_dont_count_as_playing() {
+        let mut pacing = Pacing::new();
+        pacing.on_timed_event(&settled(), 0.0);
+        assert_eq!(pacing.hands_per_hour(), None);
+        pacing.on_timed_event(&settled(), 60.0);
+        pacing.on_timed_event(&GameEvent::RoundFinished, 3600.0);
+        // 60 seconds, then a break cut down to IDLE_GAP
+        assert_eq!(pacing.minutes(), 3.0);
+        assert_eq!(pacing.hands_per_hour(), Some(40.0));
+    }
+}
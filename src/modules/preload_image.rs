@@ -10,6 +10,9 @@ To use this:
 2. Add the following use commands:
     use crate::modules::preload_image::TextureManager;
     use crate::modules::preload_image::LoadingScreenOptions; // If you want to customize the loading screen
+    use crate::modules::preload_image::{LoadingScreen, ProgressEvent}; // If you want to watch the loading yourself
+    use crate::modules::preload_image::CancelToken; // If you want to stop the loading part way
+    use crate::modules::preload_image::TextureOptions; // If you want to set filtering per asset in code
 
 3. Create and initialize a TextureManager:
     let tm = TextureManager::new();
@@ -25,7 +28,7 @@ To use this:
    
    // Option 2: Preload with a built-in loading screen (best for web)
    // Using default loading screen appearance
-   tm.preload_with_loading_screen(&all_assets, None).await;
+   tm.preload_with_loading_screen(&all_assets, None, &CancelToken::new()).await;
    
    // Using custom loading screen appearance
    let loading_options = LoadingScreenOptions {
@@ -35,7 +38,34 @@ To use this:
        // Use default values for other options
        ..Default::default()
    };
-   tm.preload_with_loading_screen(&all_assets, Some(loading_options)).await;
+   tm.preload_with_loading_screen(&all_assets, Some(loading_options), &CancelToken::new()).await;
+
+   // Option 3: Watch the loading yourself, for a custom loading scene or the debug overlay.
+   // The callback gets a ProgressEvent for every asset that starts, finishes or fails,
+   // a Frame event once per frame (draw your scene there) and Done at the end.
+   // Assets that fail are skipped instead of stopping the game.
+   tm.preload_with_progress(&all_assets, &CancelToken::new(), |event| match event {
+       ProgressEvent::Frame { completed, total } => draw_my_loading_scene(*completed, *total),
+       ProgressEvent::Failed { path, error, .. } => println!("{} failed: {}", path, error),
+       _ => {}
+   }).await;
+
+   // The built-in screen can be driven the same way, next to your own callback:
+   let mut loading_screen = LoadingScreen::new(loading_options);
+   tm.preload_with_progress(&all_assets, &CancelToken::new(), |event| loading_screen.on_event(event)).await;
+   loading_screen.finish().await;
+
+   // Stopping part way (the player quit, or the scene changed): cancel the token from
+   // the callback or anywhere else that holds a clone of it. The asset being loaded
+   // finishes, the rest are skipped, and Done says how many were. The wait returns on
+   // the next frame without waiting for the asset still loading.
+   let cancel = CancelToken::new();
+   tm.preload_with_progress(&all_assets, &cancel, |event| {
+       if is_key_pressed(KeyCode::Escape) {
+           cancel.cancel();
+       }
+   }).await;
+   if cancel.is_cancelled() { return; }
     
 5. Get preloaded textures for use with StillImage - two approaches:
 
@@ -50,8 +80,30 @@ To use this:
        println!("Warning: Image not found in texture manager");
        // Handle the error case (e.g., try to load it or use a placeholder)
    }
+
+   get_preload() hands back an Arc<TextureEntry> (the texture, its mask and its path), shared
+   by every image showing it, so taking it for each sprite costs nothing. To draw a texture
+   yourself, get_texture() gives just the Texture2D.
     
-6. Access textures by index:
+6. Choose how textures are filtered (before preloading, or later to change the ones already loaded):
+    // From the asset manifest: a default plus overrides for files or folders (ending in /),
+    // the longest matching path wins. Missing manifest = linear, no mipmaps.
+    tm.load_manifest("assets/manifest.json").await;
+
+    // Or in code:
+    tm.set_default_filter(FilterMode::Nearest);             // Pixel-art look for everything
+    tm.set_asset_options("assets/cards/", TextureOptions { filter: FilterMode::Linear, mipmaps: true, mask: false });
+
+   Mipmaps keep textures that are drawn much smaller than they are (like the card faces)
+   from looking grainy. WebGL 1 can only build them for power-of-two sized textures,
+   so on the web other sizes just skip them.
+
+   Loading doesn't work out transparency masks any more. A mask is made the first time
+   StillImage::contains_point needs it and kept with the texture, so every image sharing
+   the texture shares it too. Assets that are never hit tested (the cards) can turn it off
+   with "mask": false in the manifest, or mask: false in code, and then hit test as a rectangle.
+
+7. Access textures by index:
     // Using unwrap() approach:
     img.set_preload(tm.get_preload_by_index(0).unwrap());
     
@@ -60,10 +112,22 @@ To use this:
         img.set_preload(preloaded);
     }
     
-7. Getting the number of preloaded textures:
+8. Preload groups: name a set of files once (in the manifest, or in code) and load or
+   unload them together when the game moves between scenes:
+    // "groups": [ { "name": "menu", "paths": ["assets/avatars/avatar-1.png", ...] } ]
+    tm.set_group("menu", &["assets/avatars/avatar-1.png", "assets/avatars/avatar-2.png"]);
+    tm.preload_group("menu", &CancelToken::new(), |event| loading_screen.on_event(event)).await;
+    tm.preload_groups(&["cards", "menu"], &cancel, |event| ...).await; // One progress bar for both
+    let (loaded, total) = tm.group_progress("cards");
+    tm.unload_group("menu"); // Drops its textures, except ones another loaded group still uses
+   Images still showing an unloaded texture keep it until they are given another one. Loading
+   a file that is already being loaded waits for that load instead of starting another.
+
+9. Getting the number of preloaded textures and roughly how much memory they use:
     let count = tm.texture_count();
+    let bytes = tm.memory_bytes();
     
-8. Customizing the loading screen appearance:
+10. Customizing the loading screen appearance:
    // LoadingScreenOptions provides many customization options:
    let custom_options = LoadingScreenOptions {
        // Game title (optional)
@@ -93,10 +157,14 @@ uses coroutines to load assets in the background, avoiding black flashing on web
 use macroquad::texture::Texture2D;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use macroquad::prelude::*;
 use macroquad::experimental::coroutines::start_coroutine;
-use crate::modules::still_image::set_texture_main;
+use macroquad::miniquad::MipmapFilterMode;
+use nanoserde::DeJson;
+use crate::modules::still_image::{try_set_texture_main, TransparencyMask};
+use crate::modules::timer::wait_seconds;
+use crate::{log_debug, log_error, log_info, log_warn};
 
 /// Options for customizing the loading screen appearance
 pub struct LoadingScreenOptions {
@@ -145,12 +213,153 @@ impl Default for LoadingScreenOptions {
     }
 }
 
+/// Something that happened while preloading, passed to the preload_with_progress callback
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProgressEvent {
+    /// An asset started loading (index counts from 0)
+    Started { path: String, index: usize, total: usize },
+    /// An asset finished loading
+    Finished { path: String, index: usize, total: usize },
+    /// An asset could not be loaded and was skipped
+    Failed { path: String, index: usize, total: usize, error: String },
+    /// Sent once a frame after that frame's other events, draw the loading scene here
+    Frame { completed: usize, total: usize },
+    /// Every asset has been tried or the loading was cancelled, always the last event.
+    /// `skipped` counts the assets that weren't tried because of the cancel.
+    Done { loaded: usize, failed: usize, skipped: usize, seconds: f32 },
+}
+
+/// Shared flag for stopping a preload part way, clones all see the same cancel
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// How a texture is sampled when it is drawn at a different size
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextureOptions {
+    /// Linear for smooth scaling, Nearest for crisp pixel art
+    pub filter: FilterMode,
+    /// Build mipmaps, for textures drawn a lot smaller than they are
+    pub mipmaps: bool,
+    /// Keep a transparency mask for hit testing, made the first time it is needed
+    pub mask: bool,
+}
+
+impl Default for TextureOptions {
+    fn default() -> Self {
+        Self { filter: FilterMode::Linear, mipmaps: false, mask: true }
+    }
+}
+
+impl TextureOptions {
+    /// Set the texture's filtering, building mipmaps first if they are wanted
+    pub fn apply(&self, texture: &Texture2D) {
+        let (width, height) = (texture.width() as u32, texture.height() as u32);
+        // WebGL 1 can't build mipmaps for textures that aren't a power of two
+        let can_mipmap = !cfg!(target_arch = "wasm32") || (width.is_power_of_two() && height.is_power_of_two());
+        if !self.mipmaps || !can_mipmap {
+            texture.set_filter(self.filter);
+            return;
+        }
+        let mipmap_filter = match self.filter {
+            FilterMode::Linear => MipmapFilterMode::Linear,
+            FilterMode::Nearest => MipmapFilterMode::Nearest,
+        };
+        let id = texture.raw_miniquad_id();
+        // Texture2D has no mipmap setting of its own, so this goes to miniquad directly
+        let context = unsafe { get_internal_gl() }.quad_context;
+        context.texture_generate_mipmaps(id);
+        context.texture_set_min_filter(id, self.filter, mipmap_filter);
+        context.texture_set_mag_filter(id, self.filter);
+    }
+}
+
+// The asset manifest file: filtering for all textures plus overrides for some of them,
+// and the preload groups
+#[derive(DeJson)]
+struct Manifest {
+    #[nserde(default)]
+    default_filter: String,
+    #[nserde(default)]
+    mipmaps: bool,
+    #[nserde(default = "true")]
+    mask: bool,
+    #[nserde(default)]
+    assets: Vec<ManifestEntry>,
+    #[nserde(default)]
+    groups: Vec<ManifestGroup>,
+}
+
+#[derive(DeJson)]
+struct ManifestEntry {
+    path: String, // A file, or a folder ending in /
+    #[nserde(default)]
+    filter: String,
+    #[nserde(default)]
+    mipmaps: bool,
+    #[nserde(default = "true")]
+    mask: bool,
+}
+
+#[derive(DeJson)]
+struct ManifestGroup {
+    name: String,
+    paths: Vec<String>, // Files only, folders can't be listed on the web
+}
+
+fn parse_filter(name: &str) -> FilterMode {
+    match name {
+        "nearest" => FilterMode::Nearest,
+        "linear" | "" => FilterMode::Linear,
+        other => {
+            log_warn!("unknown texture filter '{}', using linear", other);
+            FilterMode::Linear
+        }
+    }
+}
+
+/// A loaded texture with its transparency mask (made when first used) and the path it came from.
+/// The TextureManager hands out Arc<TextureEntry>, so every StillImage showing the texture shares
+/// this one: taking it is a count going up, not a copy, and unloading the texture from the manager
+/// doesn't pull it out from under an image still showing it, it goes when the last one lets go.
+pub struct TextureEntry {
+    pub texture: Texture2D,
+    pub mask: TransparencyMask,
+    pub path: String,
+}
+
+impl TextureEntry {
+    pub fn new(texture: Texture2D, mask: TransparencyMask, path: &str) -> Arc<Self> {
+        Arc::new(Self { texture, mask, path: path.to_string() })
+    }
+}
+
+// A preload group's name and its files
+type PreloadGroup = (String, Vec<String>);
+
 /// A central texture manager to preload and share textures
 /// This reduces memory usage and prevents flickering when switching images
 #[derive(Clone)]
 pub struct TextureManager {
-    textures: Arc<Mutex<HashMap<String, (Texture2D, Option<Vec<u8>>)>>>,
+    textures: Arc<Mutex<HashMap<String, Arc<TextureEntry>>>>,
+    loading: Arc<Mutex<HashMap<String, bool>>>, // Paths being loaded right now, and whether they are still wanted
     load_order: Arc<Mutex<Vec<String>>>, // Store just the order textures were loaded in
+    default_options: Arc<Mutex<TextureOptions>>,
+    asset_options: Arc<Mutex<Vec<(String, TextureOptions)>>>, // Path (file or folder) and its options
+    groups: Arc<Mutex<Vec<PreloadGroup>>>,
 }
 
 impl TextureManager {
@@ -158,33 +367,245 @@ impl TextureManager {
     pub fn new() -> Self {
         Self {
             textures: Arc::new(Mutex::new(HashMap::new())),
+            loading: Arc::new(Mutex::new(HashMap::new())),
             load_order: Arc::new(Mutex::new(Vec::new())),
+            default_options: Arc::new(Mutex::new(TextureOptions::default())),
+            asset_options: Arc::new(Mutex::new(Vec::new())),
+            groups: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Set the filter for every texture without its own options (also changes ones already loaded)
+    #[allow(unused)]
+    pub fn set_default_filter(&self, filter: FilterMode) {
+        self.default_options.lock().unwrap().filter = filter;
+        self.reapply_options();
+    }
+
+    /// Set the options for one file, or every file in a folder if the path ends in /
+    #[allow(unused)]
+    pub fn set_asset_options(&self, path: &str, options: TextureOptions) {
+        {
+            let mut asset_options = self.asset_options.lock().unwrap();
+            asset_options.retain(|(existing, _)| existing != path);
+            asset_options.push((path.to_string(), options));
+        }
+        self.reapply_options();
+    }
+
+    /// The options a texture gets: the longest matching path, or the default
+    pub fn options_for(&self, path: &str) -> TextureOptions {
+        let asset_options = self.asset_options.lock().unwrap();
+        asset_options
+            .iter()
+            .filter(|(prefix, _)| path == prefix || (prefix.ends_with('/') && path.starts_with(prefix.as_str())))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, options)| *options)
+            .unwrap_or(*self.default_options.lock().unwrap())
+    }
+
+    /// Read the default and per-asset texture options from a manifest file
+    /// Keeps the current options if the file is missing or broken
+    pub async fn load_manifest(&self, path: &str) {
+        let text = match load_string(path).await {
+            Ok(text) => text,
+            Err(err) => {
+                log_warn!("could not load {}: {}", path, err);
+                return;
+            }
+        };
+        let manifest = match Manifest::deserialize_json(&text) {
+            Ok(manifest) => manifest,
+            Err(err) => {
+                log_warn!("could not parse {}: {}", path, err);
+                return;
+            }
+        };
+        *self.default_options.lock().unwrap() =
+            TextureOptions { filter: parse_filter(&manifest.default_filter), mipmaps: manifest.mipmaps, mask: manifest.mask };
+        *self.asset_options.lock().unwrap() = manifest
+            .assets
+            .iter()
+            .map(|entry| (entry.path.clone(), TextureOptions { filter: parse_filter(&entry.filter), mipmaps: entry.mipmaps, mask: entry.mask }))
+            .collect();
+        for group in manifest.groups {
+            self.set_group_paths(&group.name, group.paths);
+        }
+        self.reapply_options();
+    }
+
+    /// Name a set of files to preload and unload together, replacing a group with the same name
+    #[allow(unused)]
+    pub fn set_group(&self, name: &str, paths: &[&str]) {
+        self.set_group_paths(name, paths.iter().map(|path| path.to_string()).collect());
+    }
+
+    fn set_group_paths(&self, name: &str, paths: Vec<String>) {
+        let mut groups = self.groups.lock().unwrap();
+        groups.retain(|(existing, _)| existing != name);
+        groups.push((name.to_string(), paths));
+    }
+
+    /// The files in a group, empty if there is no group by that name
+    pub fn group_paths(&self, name: &str) -> Vec<String> {
+        let groups = self.groups.lock().unwrap();
+        groups.iter().find(|(group, _)| group == name).map(|(_, paths)| paths.clone()).unwrap_or_default()
+    }
+
+    /// How many of a group's files are loaded, out of how many
+    #[allow(unused)]
+    pub fn group_progress(&self, name: &str) -> (usize, usize) {
+        let paths = self.group_paths(name);
+        let textures = self.textures.lock().unwrap();
+        (paths.iter().filter(|path| textures.contains_key(path.as_str())).count(), paths.len())
+    }
+
+    /// Preload every file in a group, see preload_with_progress
+    #[allow(unused)]
+    pub async fn preload_group(&self, name: &str, cancel: &CancelToken, on_event: impl FnMut(&ProgressEvent)) {
+        self.preload_groups(&[name], cancel, on_event).await;
+    }
+
+    /// Preload several groups as one lot, so a loading screen shows one bar for all of them.
+    /// Files that are already loaded, or in more than one of the groups, aren't loaded twice.
+    pub async fn preload_groups(&self, names: &[&str], cancel: &CancelToken, on_event: impl FnMut(&ProgressEvent)) {
+        let mut paths: Vec<String> = Vec::new();
+        for name in names {
+            let group = self.group_paths(name);
+            if group.is_empty() {
+                log_warn!("preload group '{}' is empty or missing", name);
+            }
+            for path in group {
+                if !paths.contains(&path) && self.cached(&path).is_none() {
+                    paths.push(path);
+                }
+            }
+        }
+        let paths: Vec<&str> = paths.iter().map(|path| path.as_str()).collect();
+        self.preload_with_progress(&paths, cancel, on_event).await;
+    }
+
+    /// Drop a group's textures to free their memory. Files another group with textures
+    /// still loaded also lists are kept, so shared pictures don't disappear from under it.
+    /// Images still showing a dropped texture keep it until they show something else,
+    /// and files of the group still loading are thrown away when they arrive.
+    #[allow(unused)]
+    pub fn unload_group(&self, name: &str) {
+        let others: Vec<Vec<String>> = {
+            let groups = self.groups.lock().unwrap();
+            groups.iter().filter(|(group, _)| group != name).map(|(_, paths)| paths.clone()).collect()
+        };
+        let mut textures = self.textures.lock().unwrap();
+        let in_use = |path: &String| others.iter().any(|other| other.contains(path) && other.iter().any(|file| file != path && textures.contains_key(file)));
+        let unload: Vec<String> = self.group_paths(name).into_iter().filter(|path| !in_use(path)).collect();
+        let mut showing = 0;
+        for path in &unload {
+            if textures.remove(path).is_some_and(|entry| Arc::strong_count(&entry) > 1) {
+                showing += 1;
+            }
+        }
+        drop(textures);
+        {
+            let mut loading = self.loading.lock().unwrap();
+            for path in &unload {
+                if let Some(wanted) = loading.get_mut(path) {
+                    *wanted = false;
+                }
+            }
+        }
+        self.load_order.lock().unwrap().retain(|path| !unload.contains(path));
+        log_debug!("unloaded group '{}' ({} textures, {} still on show)", name, unload.len(), showing);
+    }
+
+    // Apply the current options to the textures that are already loaded
+    fn reapply_options(&self) {
+        let textures: Vec<(String, Texture2D)> = {
+            let textures = self.textures.lock().unwrap();
+            textures.iter().map(|(path, entry)| (path.clone(), entry.texture.clone())).collect()
+        };
+        for (path, texture) in textures {
+            self.options_for(&path).apply(&texture);
         }
     }
     
     /// Preload a texture by its file path
     pub async fn preload(&self, path: &str) {
-        // First, check if the texture already exists
-        let texture_exists = {
-            let textures = self.textures.lock().unwrap();
-            textures.contains_key(path)
-        };
-        
-        // If it doesn't exist, load it
-        if !texture_exists {
-            // Load the texture outside of any locks
-            let (texture, mask) = set_texture_main(path).await;
-            
-            // Now update the maps with short-lived locks
-            {
-                let mut textures = self.textures.lock().unwrap();
-                textures.insert(path.to_string(), (texture, mask));
+        if let Err(err) = self.try_preload(path).await {
+            log_error!("could not load {}: {}", path, err);
+            panic!("could not load {}: {}", path, err)
+        }
+    }
+
+    /// Preload a texture, handing back the error instead of panicking if it can't be loaded.
+    /// A texture already being loaded (by a loading screen, say) isn't loaded a second time,
+    /// this waits for that load to finish instead.
+    pub async fn try_preload(&self, path: &str) -> Result<(), String> {
+        loop {
+            if self.cached(path).is_some() {
+                return Ok(());
+            }
+            let claimed = {
+                let mut loading = self.loading.lock().unwrap();
+                match loading.get_mut(path) {
+                    // Wanted again, even if it was unloaded while it loaded
+                    Some(wanted) => {
+                        *wanted = true;
+                        false
+                    }
+                    None => {
+                        loading.insert(path.to_string(), true);
+                        true
+                    }
+                }
+            };
+            if claimed {
+                break;
             }
-            
-            {
-                let mut load_order = self.load_order.lock().unwrap();
-                load_order.push(path.to_string());
+            while self.loading.lock().unwrap().contains_key(path) {
+                next_frame().await;
             }
+            // Loaded by the other load now, or it failed and this one tries itself
+        }
+        // Load the texture outside of any locks
+        let loaded = try_set_texture_main(path).await;
+        let wanted = self.loading.lock().unwrap().remove(path).unwrap_or(false);
+        let texture = loaded.map_err(|err| err.to_string())?;
+        if wanted {
+            self.insert(path, texture);
+        } else {
+            log_debug!("{} was unloaded while it loaded, not keeping it", path);
+        }
+        Ok(())
+    }
+
+    /// Keep a texture made in code under a path, as if it had been loaded from there
+    pub fn insert_texture(&self, path: &str, texture: Texture2D) {
+        // A file of the same name still loading doesn't replace it when it arrives
+        if let Some(wanted) = self.loading.lock().unwrap().get_mut(path) {
+            *wanted = false;
+        }
+        self.insert(path, texture);
+    }
+
+    fn cached(&self, path: &str) -> Option<Arc<TextureEntry>> {
+        let textures = self.textures.lock().unwrap();
+        textures.get(path).cloned()
+    }
+
+    fn insert(&self, path: &str, texture: Texture2D) {
+        let options = self.options_for(path);
+        options.apply(&texture);
+        let mask = if options.mask { TransparencyMask::lazy() } else { TransparencyMask::none() };
+        log_debug!("loaded {} ({}x{}, {:?}{})", path, texture.width(), texture.height(), options.filter, if options.mipmaps { ", mipmaps" } else { "" });
+        // Update the maps with short-lived locks. A texture put in again (insert_texture) replaces
+        // the old one for images taking it from now on, ones already showing the old one keep it.
+        let replaced = {
+            let mut textures = self.textures.lock().unwrap();
+            textures.insert(path.to_string(), TextureEntry::new(texture, mask, path)).is_some()
+        };
+        if !replaced {
+            let mut load_order = self.load_order.lock().unwrap();
+            load_order.push(path.to_string());
         }
     }
     
@@ -196,18 +617,22 @@ impl TextureManager {
         }
     }
     
-    /// Get a preloaded texture for use in an ImageObject
+    /// Get a preloaded texture for use in a StillImage, shared rather than copied
+    #[allow(unused)]
+    pub fn get_preload(&self, path: &str) -> Option<Arc<TextureEntry>> {
+        self.cached(path)
+    }
+    
+    /// Get just a preloaded texture, for drawing it straight away
     #[allow(unused)]
-    pub fn get_preload(&self, path: &str) -> Option<(Texture2D, Option<Vec<u8>>, String)> {
+    pub fn get_texture(&self, path: &str) -> Option<Texture2D> {
         let textures = self.textures.lock().unwrap();
-        textures.get(path).map(|(texture, mask)| 
-            (texture.clone(), mask.clone(), path.to_string())
-        )
+        textures.get(path).map(|entry| entry.texture.clone())
     }
     
     /// Get a preloaded texture by its index in the preload order
     #[allow(unused)]
-    pub fn get_preload_by_index(&self, index: usize) -> Option<(Texture2D, Option<Vec<u8>>, String)> {
+    pub fn get_preload_by_index(&self, index: usize) -> Option<Arc<TextureEntry>> {
         let load_order = self.load_order.lock().unwrap();
         if index < load_order.len() {
             let path = &load_order[index];
@@ -224,6 +649,20 @@ impl TextureManager {
         load_order.len()
     }
     
+    /// Estimate the GPU memory used by the preloaded textures (RGBA, 4 bytes a pixel)
+    /// plus the CPU-side transparency masks that have been made so far
+    #[allow(unused)]
+    pub fn memory_bytes(&self) -> usize {
+        let textures = self.textures.lock().unwrap();
+        textures
+            .values()
+            .map(|entry| {
+                let pixels = entry.texture.width() as usize * entry.texture.height() as usize;
+                pixels * 4 + entry.mask.memory_bytes()
+            })
+            .sum()
+    }
+    
     /// Get a list of all preloaded texture paths in load order
     #[allow(unused)]
     pub fn get_texture_paths(&self) -> Vec<String> {
@@ -233,128 +672,215 @@ impl TextureManager {
     
     /// Load assets with a built-in loading screen that works well for web
     /// This method handles all the complexities of asset loading and progress display
-    pub async fn preload_with_loading_screen(&self, assets: &[&str], options: Option<LoadingScreenOptions>) {
-        // Use default options if none provided
-        let options = options.unwrap_or_default();
-        
-        // Thread-safe progress counters that can be shared between coroutines
-        let loaded_counter = Arc::new(AtomicUsize::new(0));
-        let total_assets = assets.len();
-        
+    #[allow(unused)]
+    /// Cancelling skips the completion message
+    #[allow(unused)]
+    pub async fn preload_with_loading_screen(&self, assets: &[&str], options: Option<LoadingScreenOptions>, cancel: &CancelToken) {
+        let mut loading_screen = LoadingScreen::new(options.unwrap_or_default());
+        self.preload_with_progress(assets, cancel, |event| loading_screen.on_event(event)).await;
+        if !cancel.is_cancelled() {
+            loading_screen.finish().await;
+        }
+    }
+
+    /// Load assets in the background and report every step to `on_event`, see ProgressEvent
+    /// Assets that fail to load are reported and skipped, the rest still load.
+    /// Cancelling `cancel` stops the loading after the asset being loaded and returns straight away.
+    pub async fn preload_with_progress(&self, assets: &[&str], cancel: &CancelToken, mut on_event: impl FnMut(&ProgressEvent)) {
+        // Events from the loading coroutine, handed to the callback on the main thread
+        let queue: Arc<Mutex<Vec<ProgressEvent>>> = Arc::new(Mutex::new(Vec::new()));
+        let completed = Arc::new(AtomicUsize::new(0));
+        let total = assets.len();
+
         // Start a background coroutine for loading assets WITHOUT awaiting it
         // This is the key to avoiding black flashes on web
         {
             // Convert &[&str] to Vec<String> for the coroutine to own its data
             let assets_to_load: Vec<String> = assets.iter().map(|&s| s.to_string()).collect();
-            let counter = loaded_counter.clone();
+            let (queue, completed, cancel) = (queue.clone(), completed.clone(), cancel.clone());
             let loading_tm = self.clone(); // Clone the TextureManager for the coroutine
-            
+
             // Important: We start the coroutine but DON'T await it
             start_coroutine(async move {
-                for asset_path in assets_to_load {
-                    // Load asset into the shared texture manager
-                    loading_tm.preload(&asset_path).await;
-                    
-                    // Update the counter atomically
-                    counter.fetch_add(1, Ordering::SeqCst);
-                    
+                let start_time = get_time();
+                let mut failed = 0;
+                let mut skipped = 0;
+                for (index, path) in assets_to_load.into_iter().enumerate() {
+                    // Nobody is waiting for the rest any more
+                    if cancel.is_cancelled() {
+                        skipped = total - index;
+                        break;
+                    }
+                    queue.lock().unwrap().push(ProgressEvent::Started { path: path.clone(), index, total });
+                    let event = match loading_tm.try_preload(&path).await {
+                        Ok(()) => ProgressEvent::Finished { path, index, total },
+                        Err(error) => {
+                            failed += 1;
+                            ProgressEvent::Failed { path, index, total, error }
+                        }
+                    };
+                    queue.lock().unwrap().push(event);
+                    completed.fetch_add(1, Ordering::SeqCst);
+
                     // Yielding control back to the main thread
                     next_frame().await;
                 }
+                let seconds = (get_time() - start_time) as f32;
+                let loaded = total - failed - skipped;
+                if skipped > 0 {
+                    log_info!("loading cancelled after {} textures, {} skipped", loaded + failed, skipped);
+                }
+                queue.lock().unwrap().push(ProgressEvent::Done { loaded, failed, skipped, seconds });
             });
         }
-        
-        // Main rendering loop for the loading screen
+
+        // Main loop: pass the events on and give the callback a frame to draw in
         // This runs in the main thread and never awaits the asset loading
+        let mut failed = 0;
         loop {
-            // Read the current progress atomically
-            let loaded_assets = loaded_counter.load(Ordering::SeqCst);
-            let progress = loaded_assets as f32 / total_assets as f32;
-            
-            // Clear the screen with custom background color
-            clear_background(options.background_color);
-            
-            // Draw title if one is provided
-            if let Some(title) = &options.title {
-                let title_size = options.title_font_size;
-                let title_dim = measure_text(title, None, title_size, 1.0);
-                draw_text(
-                    title,
-                    screen_width() / 2.0 - title_dim.width / 2.0,
-                    screen_height() / 3.0,
-                    title_size as f32,
-                    options.text_color
-                );
-            }
-            
-            // Draw progress text
-            let progress_text = format!("Loading: {:.0}%", progress * 100.0);
-            draw_text(
-                &progress_text,
-                screen_width() / 2.0 - measure_text(&progress_text, None, options.progress_font_size, 1.0).width / 2.0,
-                screen_height() / 2.0,
-                options.progress_font_size as f32,
-                options.text_color
-            );
-            
-            // Draw loading bar
-            let bar_width = screen_width() * 0.6;
-            let bar_height = 30.0;
-            let bar_x = screen_width() / 2.0 - bar_width / 2.0;
-            let bar_y = screen_height() / 2.0 + 40.0;
-            
-            // Background bar
-            draw_rectangle(bar_x, bar_y, bar_width, bar_height, options.bar_background_color);
-            
-            // Progress bar
-            if progress > 0.0 {
-                draw_rectangle(bar_x, bar_y, bar_width * progress, bar_height, options.bar_fill_color);
-            }
-            
-            // Border
-            draw_rectangle_lines(bar_x, bar_y, bar_width, bar_height, 2.0, options.text_color);
-            
-            // Display current file if available
-            if loaded_assets > 0 && loaded_assets < total_assets {
-                let file_name = assets[loaded_assets].split('/').last().unwrap_or("");
-                let file_text = format!("Loading: {}", file_name);
-                draw_text(
-                    &file_text,
-                    screen_width() / 2.0 - measure_text(&file_text, None, options.filename_font_size, 1.0).width / 2.0,
-                    bar_y + bar_height + 30.0,
-                    options.filename_font_size as f32,
-                    options.filename_color
-                );
-            }
-            
-            // Check if loading is complete
-            if loaded_assets >= total_assets {
-                // Show completion message if enabled
-                if options.show_completion_message {
-                    clear_background(options.background_color);
-                    let text_size = options.progress_font_size + 20; // Slightly larger than progress font
-                    let text_dimensions = measure_text(&options.completion_message, None, text_size, 1.0);
-                    let text_x = screen_width() / 2.0 - text_dimensions.width / 2.0;
-                    let text_y = screen_height() / 2.0;
-                    
-                    draw_text(&options.completion_message, text_x, text_y, text_size as f32, options.text_color);
-                    next_frame().await;
-                    
-                    // Apply completion delay if specified
-                    if options.completion_delay > 0.0 {
-                        let start_time = get_time();
-                        while get_time() - start_time < options.completion_delay as f64 {
-                            next_frame().await;
-                        }
+            let events: Vec<ProgressEvent> = std::mem::take(&mut *queue.lock().unwrap());
+            let mut done = None;
+            for event in events {
+                match &event {
+                    ProgressEvent::Failed { path, error, .. } => {
+                        log_warn!("could not load {}: {}", path, error);
+                        failed += 1;
+                    }
+                    ProgressEvent::Done { failed, seconds, .. } => {
+                        log_info!(
+                            "loaded {} textures ({:.1} MB) in {:.2}s, {} failed",
+                            self.texture_count(),
+                            self.memory_bytes() as f32 / 1_048_576.0,
+                            seconds,
+                            failed
+                        );
+                        done = Some(event);
+                        continue;
                     }
+                    _ => {}
                 }
-                
-                // Break the loading loop and proceed with the game
+                on_event(&event);
+            }
+            on_event(&ProgressEvent::Frame { completed: completed.load(Ordering::SeqCst), total });
+            if let Some(done) = done {
+                on_event(&done);
+                break;
+            }
+            // The coroutine stops on its own once the asset it is on has loaded
+            if cancel.is_cancelled() {
+                let tried = completed.load(Ordering::SeqCst);
+                on_event(&ProgressEvent::Done { loaded: tried - failed, failed, skipped: total - tried, seconds: 0.0 });
                 break;
             }
-            
+
             // Update the screen WITHOUT awaiting asset loading
             next_frame().await;
         }
     }
 }
+
+/// The built-in loading screen: a title, a progress bar and the file being loaded
+/// Feed it the events from preload_with_progress, then call finish()
+pub struct LoadingScreen {
+    options: LoadingScreenOptions,
+    current_file: Option<String>,
+}
+
+impl LoadingScreen {
+    pub fn new(options: LoadingScreenOptions) -> Self {
+        Self { options, current_file: None }
+    }
+
+    /// Keep track of the file being loaded and draw on every Frame event
+    pub fn on_event(&mut self, event: &ProgressEvent) {
+        match event {
+            // The first file isn't named, like before (it usually loads in a frame)
+            ProgressEvent::Started { path, index, .. } if *index > 0 => {
+                self.current_file = Some(path.split('/').next_back().unwrap_or("").to_string());
+            }
+            ProgressEvent::Frame { completed, total } => self.draw(*completed, *total),
+            _ => {}
+        }
+    }
+
+    fn draw(&self, loaded_assets: usize, total_assets: usize) {
+        let options = &self.options;
+        let progress = if total_assets == 0 { 1.0 } else { loaded_assets as f32 / total_assets as f32 };
+
+        // Clear the screen with custom background color
+        clear_background(options.background_color);
+
+        // Draw title if one is provided
+        if let Some(title) = &options.title {
+            let title_size = options.title_font_size;
+            let title_dim = measure_text(title, None, title_size, 1.0);
+            draw_text(
+                title,
+                screen_width() / 2.0 - title_dim.width / 2.0,
+                screen_height() / 3.0,
+                title_size as f32,
+                options.text_color
+            );
+        }
+
+        // Draw progress text
+        let progress_text = format!("Loading: {:.0}%", progress * 100.0);
+        draw_text(
+            &progress_text,
+            screen_width() / 2.0 - measure_text(&progress_text, None, options.progress_font_size, 1.0).width / 2.0,
+            screen_height() / 2.0,
+            options.progress_font_size as f32,
+            options.text_color
+        );
+
+        // Draw loading bar
+        let bar_width = screen_width() * 0.6;
+        let bar_height = 30.0;
+        let bar_x = screen_width() / 2.0 - bar_width / 2.0;
+        let bar_y = screen_height() / 2.0 + 40.0;
+
+        // Background bar
+        draw_rectangle(bar_x, bar_y, bar_width, bar_height, options.bar_background_color);
+
+        // Progress bar
+        if progress > 0.0 {
+            draw_rectangle(bar_x, bar_y, bar_width * progress, bar_height, options.bar_fill_color);
+        }
+
+        // Border
+        draw_rectangle_lines(bar_x, bar_y, bar_width, bar_height, 2.0, options.text_color);
+
+        // Display current file if available
+        if let Some(file_name) = &self.current_file
+            && loaded_assets < total_assets
+        {
+            let file_text = format!("Loading: {}", file_name);
+            draw_text(
+                &file_text,
+                screen_width() / 2.0 - measure_text(&file_text, None, options.filename_font_size, 1.0).width / 2.0,
+                bar_y + bar_height + 30.0,
+                options.filename_font_size as f32,
+                options.filename_color
+            );
+        }
+    }
+
+    /// Show the completion message (if enabled) for the completion delay
+    pub async fn finish(&self) {
+        let options = &self.options;
+        if !options.show_completion_message {
+            return;
+        }
+        clear_background(options.background_color);
+        let text_size = options.progress_font_size + 20; // Slightly larger than progress font
+        let text_dimensions = measure_text(&options.completion_message, None, text_size, 1.0);
+        let text_x = screen_width() / 2.0 - text_dimensions.width / 2.0;
+        let text_y = screen_height() / 2.0;
+
+        draw_text(&options.completion_message, text_x, text_y, text_size as f32, options.text_color);
+        next_frame().await;
+
+        // Apply completion delay if specified
+        wait_seconds(options.completion_delay).await;
+    }
+}
@@ -0,0 +1,458 @@
+/*
+By: <tyler>
+Date: 2025-11-23
+Program Details: Player profiles
+
+Each profile has a name, an avatar picked from assets/avatars, its own bankroll,
+settings, stats and achievements. Everything is saved through modules::storage,
+with each part under its own key:
+    profiles                     - list of profile ids, the last one used and whether
+                                   the first-run welcome has been through
+    profile_<id>                 - name, avatar, bankroll and recent bets
+    profile_<id>_settings        - Settings
+    profile_<id>_stats           - Stats
+    profile_<id>_achievements    - Achievements
+    profile_<id>_slots           - the save slots: which is being played and what each holds
+    profile_<id>_slot_<n>        - bankroll, recent bets and stats of a slot not being played
+    profile_<id>_round_<n>       - the round a slot was left in, if the game was closed part way
+                                   through one (written by modules::autosave)
+
+A profile has MAX_SLOTS save slots, separate sessions each with its own
+bankroll, recent bets and stats (a casual game and a challenge run, say). The
+settings and achievements belong to the whole profile. The slot being played
+lives in the profile's own keys, the others are put aside in their slot keys
+until they are picked again, so a profile saved before there were slots is
+simply playing slot 1.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod storage;
+    pub mod achievements;
+    pub mod profile;
+
+Then add the following with the use commands:
+use crate::modules::profile::{Profile, ProfileList, STARTING_BANKROLL};
+
+Usage examples:
+1. List the saved profiles:
+    let list = ProfileList::load();
+    for (id, info) in list.entries() {
+        println!("{} {}", id, info.name);
+    }
+
+2. Make a new one or open an old one:
+    let profile = list.create("Tyler", 0, STARTING_BANKROLL);
+    let profile = list.open(id);
+
+3. Give it every game event and save when something changed:
+    bus.dispatch(&mut [&mut profile]);
+    profile.save_if_changed();
+
+4. Play another save slot (an empty one starts a new session with that name):
+    for (i, slot) in profile.slots().iter().enumerate() {
+        println!("{}: {} ${} {} hands", i, slot.name, slot.bankroll, slot.hands);
+    }
+    profile.use_slot(2, "Challenge");
+
+5. Move a profile to another device as one JSON blob (modules::profile_transfer
+   writes it to a file or shows it as text):
+    let text = profile.export_json();
+    other_profile.import_json(&text)?;
+*/
+use nanoserde::{DeJson, SerJson};
+use crate::modules::achievements::Achievements;
+use crate::modules::events::{EventListener, GameEvent};
+use crate::modules::settings::Settings;
+use crate::modules::stats::Stats;
+use crate::modules::autosave;
+use crate::modules::storage;
+use crate::{log_info, log_warn};
+
+pub const AVATAR_PATHS: [&str; 6] = [
+    "assets/avatars/avatar-1.png",
+    "assets/avatars/avatar-2.png",
+    "assets/avatars/avatar-3.png",
+    "assets/avatars/avatar-4.png",
+    "assets/avatars/avatar-5.png",
+    "assets/avatars/avatar-6.png",
+];
+
+pub const STARTING_BANKROLL: i64 = 1000;
+pub const MAX_NAME_LENGTH: usize = 16;
+
+pub const MAX_SLOTS: usize = 3;
+
+const INDEX_KEY: &str = "profiles";
+const EXPORT_VERSION: u32 = 1;
+
+// The "profiles" key. Ids start at 1, so a last_used of 0 means none.
+#[derive(SerJson, DeJson, Default, Debug)]
+#[nserde(default)]
+struct ProfileIndex {
+    ids: Vec<u32>,
+    next_id: u32,
+    last_used: u32,
+    onboarded: bool, // The first-run welcome has been through, it is only shown once
+}
+
+// The "profile_<id>" key
+#[derive(SerJson, DeJson, Clone, Debug)]
+#[nserde(default)]
+pub struct ProfileInfo {
+    pub name: String,
+    pub avatar: usize, // Index into AVATAR_PATHS
+    pub bankroll: i64,
+    pub recent_bets: Vec<i64>, // Newest first, see Bet::recent
+}
+
+impl Default for ProfileInfo {
+    fn default() -> Self {
+        Self { name: "Player".to_string(), avatar: 0, bankroll: STARTING_BANKROLL, recent_bets: Vec::new() }
+    }
+}
+
+impl ProfileInfo {
+    pub fn avatar_path(&self) -> &'static str {
+        AVATAR_PATHS[self.avatar % AVATAR_PATHS.len()]
+    }
+}
+
+// What the slot picker shows about a save slot, kept up to date as it is played
+#[derive(SerJson, DeJson, Clone, Debug, Default)]
+#[nserde(default)]
+pub struct SlotInfo {
+    pub used: bool, // False for a slot nothing has been played in yet
+    pub name: String,
+    pub bankroll: i64,
+    pub hands: u32,
+    pub last_played: f64, // Seconds since 1970, 0 for never
+}
+
+// The "profile_<id>_slots" key
+#[derive(SerJson, DeJson, Clone, Debug, Default)]
+#[nserde(default)]
+struct SlotIndex {
+    active: usize,
+    slots: Vec<SlotInfo>,
+}
+
+// The "profile_<id>_slot_<n>" key, a session put aside while another slot is played
+#[derive(SerJson, DeJson, Default)]
+#[nserde(default)]
+struct SlotSession {
+    bankroll: i64,
+    recent_bets: Vec<i64>,
+    stats: Stats,
+}
+
+// Every part of a profile in one blob, for export_json/import_json.
+// A version of 0 means the field was missing, so the text wasn't an export.
+#[derive(SerJson, DeJson, Default)]
+#[nserde(default)]
+struct ProfileExport {
+    version: u32,
+    info: ProfileInfo,
+    settings: Settings,
+    stats: Stats,
+    achievements: Achievements,
+}
+
+pub struct Profile {
+    pub id: u32,
+    pub info: ProfileInfo,
+    pub settings: Settings,
+    pub stats: Stats,
+    pub achievements: Achievements,
+    slots: SlotIndex,
+    unsaved: bool,
+}
+
+impl Profile {
+    // Write every part of the profile to storage
+    pub fn save(&mut self) {
+        for (key, value) in self.parts() {
+            storage::save(&key, &value);
+        }
+        // What was staged for closing the game is older than this
+        autosave::clear();
+        self.unsaved = false;
+    }
+
+    // Every part of the profile as the keys and values save() writes
+    pub fn parts(&mut self) -> Vec<(String, String)> {
+        self.note_active_slot();
+        vec![
+            (info_key(self.id), self.info.serialize_json()),
+            (part_key(self.id, "settings"), self.settings.serialize_json()),
+            (part_key(self.id, "stats"), self.stats.serialize_json()),
+            (part_key(self.id, "achievements"), self.achievements.serialize_json()),
+            (part_key(self.id, "slots"), self.slots.serialize_json()),
+        ]
+    }
+
+    // Whether something changed since the last save
+    pub fn has_changes(&self) -> bool {
+        self.unsaved
+    }
+
+    // The save slots, MAX_SLOTS of them
+    pub fn slots(&self) -> &[SlotInfo] {
+        &self.slots.slots
+    }
+
+    // The slot being played
+    pub fn active_slot(&self) -> usize {
+        self.slots.active
+    }
+
+    // Where the slot being played keeps a round the game was closed in the middle of
+    pub fn round_key(&self) -> String {
+        round_key(self.id, self.slots.active)
+    }
+
+    // Put the session being played aside and play `slot`. An empty slot starts a new session called `name`.
+    pub fn use_slot(&mut self, slot: usize, name: &str) {
+        if slot >= MAX_SLOTS {
+            return;
+        }
+        if slot != self.slots.active {
+            let set_aside = SlotSession { bankroll: self.info.bankroll, recent_bets: self.info.recent_bets.clone(), stats: self.stats.clone() };
+            self.note_active_slot();
+            storage::save(&slot_key(self.id, self.slots.active), &set_aside.serialize_json());
+            let session = if self.slots.slots[slot].used { load_json::<SlotSession>(&slot_key(self.id, slot)) } else { None };
+            let session = session.unwrap_or(SlotSession { bankroll: STARTING_BANKROLL, ..Default::default() });
+            self.info.bankroll = session.bankroll;
+            self.info.recent_bets = session.recent_bets;
+            self.stats = session.stats;
+            storage::remove(&slot_key(self.id, slot));
+            self.slots.active = slot;
+        }
+        let info = &mut self.slots.slots[slot];
+        if !info.used {
+            info.used = true;
+            info.name = name.chars().take(MAX_NAME_LENGTH).collect();
+        }
+        log_info!("profile {} playing slot {} ({})", self.id, slot + 1, self.slots.slots[slot].name);
+        self.save();
+    }
+
+    // Empty a slot that isn't being played
+    pub fn clear_slot(&mut self, slot: usize) {
+        if slot >= MAX_SLOTS || slot == self.slots.active {
+            return;
+        }
+        self.slots.slots[slot] = SlotInfo::default();
+        storage::remove(&slot_key(self.id, slot));
+        storage::remove(&round_key(self.id, slot));
+        self.save();
+    }
+
+    // Bring the picker's view of the slot being played up to date
+    fn note_active_slot(&mut self) {
+        let info = &mut self.slots.slots[self.slots.active];
+        info.used = true;
+        info.bankroll = self.info.bankroll;
+        info.hands = self.stats.hands_played;
+        info.last_played = macroquad::miniquad::date::now();
+    }
+
+    // Call this after changing settings or the bankroll directly
+    pub fn mark_changed(&mut self) {
+        self.unsaved = true;
+    }
+
+    // Save only if something changed since the last save
+    pub fn save_if_changed(&mut self) {
+        if self.unsaved {
+            self.save();
+        }
+    }
+
+    // The name, bankroll, settings, stats and achievements as one JSON blob
+    pub fn export_json(&self) -> String {
+        let export = ProfileExport {
+            version: EXPORT_VERSION,
+            info: self.info.clone(),
+            settings: self.settings.clone(),
+            stats: self.stats.clone(),
+            achievements: self.achievements.clone(),
+        };
+        export.serialize_json()
+    }
+
+    // Replace everything in this profile with an exported blob and save it.
+    // The id stays the same, so the import takes over this profile's save.
+    pub fn import_json(&mut self, text: &str) -> Result<(), String> {
+        let export = ProfileExport::deserialize_json(text.trim()).map_err(|err| err.to_string())?;
+        if export.version == 0 || export.version > EXPORT_VERSION {
+            return Err("not a profile export from this game".to_string());
+        }
+        self.info = export.info;
+        // Hand edited exports could have a name the profile menu has no room for
+        self.info.name = self.info.name.chars().take(MAX_NAME_LENGTH).collect();
+        self.settings = export.settings;
+        self.stats = export.stats;
+        self.achievements = export.achievements;
+        self.save();
+        log_info!("imported {} into profile {}", self.info.name, self.id);
+        Ok(())
+    }
+}
+
+impl EventListener for Profile {
+    fn on_event(&mut self, event: &GameEvent) {
+        self.stats.on_event(event);
+        self.achievements.check(&self.stats, event);
+        if matches!(event, GameEvent::RoundSettled { .. } | GameEvent::PlayerBusted { .. } | GameEvent::LowBankroll { .. }) {
+            self.unsaved = true;
+        }
+    }
+}
+
+// Every saved profile, for the profile menu
+pub struct ProfileList {
+    index: ProfileIndex,
+    entries: Vec<(u32, ProfileInfo)>,
+}
+
+impl ProfileList {
+    pub fn load() -> Self {
+        let index = load_json::<ProfileIndex>(INDEX_KEY).unwrap_or_default();
+        let entries = index
+            .ids
+            .iter()
+            .map(|id| (*id, load_json::<ProfileInfo>(&info_key(*id)).unwrap_or_default()))
+            .collect();
+        Self { index, entries }
+    }
+
+    pub fn entries(&self) -> &[(u32, ProfileInfo)] {
+        &self.entries
+    }
+
+    // The profile that was played last, if it still exists
+    pub fn last_used(&self) -> Option<u32> {
+        Some(self.index.last_used).filter(|id| self.index.ids.contains(id))
+    }
+
+    // Settings of the profile played last without opening it, for the window options
+    // that have to be picked before the game starts
+    pub fn last_used_settings(&self) -> Option<Settings> {
+        load_json(&part_key(self.last_used()?, "settings"))
+    }
+
+    // Whether this is the game's first run: no profiles and the welcome never finished
+    pub fn first_run(&self) -> bool {
+        self.index.ids.is_empty() && !self.index.onboarded
+    }
+
+    // Remember the welcome has been through, so it is skipped from now on
+    pub fn finish_onboarding(&mut self) {
+        self.index.onboarded = true;
+        self.save_index();
+    }
+
+    // Make and save a brand new profile, starting with `bankroll`
+    pub fn create(&mut self, name: &str, avatar: usize, bankroll: i64) -> Profile {
+        let id = self.index.next_id.max(1);
+        self.index.next_id = id + 1;
+        self.index.ids.push(id);
+        let info = ProfileInfo { name: name.to_string(), avatar, bankroll, ..Default::default() };
+        self.entries.push((id, info.clone()));
+        let mut profile = Profile {
+            id,
+            info,
+            settings: Settings::default(),
+            stats: Stats::default(),
+            achievements: Achievements::default(),
+            slots: new_slots(),
+            unsaved: false,
+        };
+        profile.save();
+        log_info!("created profile {} ({})", id, name);
+        self.index.last_used = id;
+        self.save_index();
+        profile
+    }
+
+    // Load a saved profile, anything missing or unreadable starts from its default
+    pub fn open(&mut self, id: u32) -> Profile {
+        log_info!("opening profile {}", id);
+        self.index.last_used = id;
+        self.save_index();
+        Profile {
+            id,
+            info: load_json(&info_key(id)).unwrap_or_default(),
+            settings: load_json(&part_key(id, "settings")).unwrap_or_default(),
+            stats: load_json(&part_key(id, "stats")).unwrap_or_default(),
+            achievements: load_json(&part_key(id, "achievements")).unwrap_or_default(),
+            slots: load_slots(id),
+            unsaved: false,
+        }
+    }
+
+    // Remove a profile and everything saved for it
+    pub fn delete(&mut self, id: u32) {
+        log_info!("deleting profile {}", id);
+        self.index.ids.retain(|other| *other != id);
+        self.entries.retain(|(other, _)| *other != id);
+        storage::remove(&info_key(id));
+        for part in ["settings", "stats", "achievements", "slots"] {
+            storage::remove(&part_key(id, part));
+        }
+        for slot in 0..MAX_SLOTS {
+            storage::remove(&slot_key(id, slot));
+            storage::remove(&round_key(id, slot));
+        }
+        self.save_index();
+    }
+
+    fn save_index(&self) {
+        storage::save(INDEX_KEY, &self.index.serialize_json());
+    }
+}
+
+fn info_key(id: u32) -> String {
+    format!("profile_{}", id)
+}
+
+fn part_key(id: u32, part: &str) -> String {
+    format!("profile_{}_{}", id, part)
+}
+
+fn slot_key(id: u32, slot: usize) -> String {
+    format!("profile_{}_slot_{}", id, slot + 1)
+}
+
+fn round_key(id: u32, slot: usize) -> String {
+    format!("profile_{}_round_{}", id, slot + 1)
+}
+
+// Slot 1 being played, the rest empty
+fn new_slots() -> SlotIndex {
+    let mut slots = vec![SlotInfo::default(); MAX_SLOTS];
+    slots[0] = SlotInfo { used: true, name: "Slot 1".to_string(), ..Default::default() };
+    SlotIndex { active: 0, slots }
+}
+
+// The saved slots, a profile from before there were slots is playing slot 1
+fn load_slots(id: u32) -> SlotIndex {
+    let mut index = load_json::<SlotIndex>(&part_key(id, "slots")).unwrap_or_else(new_slots);
+    index.slots.resize(MAX_SLOTS, SlotInfo::default());
+    if index.active >= MAX_SLOTS {
+        index.active = 0;
+    }
+    index
+}
+
+fn load_json<T: DeJson>(key: &str) -> Option<T> {
+    let text = storage::load(key)?;
+    match T::deserialize_json(&text) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            log_warn!("could not read saved {}: {}", key, err);
+            None
+        }
+    }
+}
@@ -0,0 +1,512 @@
+/*
+By: <tyler>
+Date: 2025-11-23
+Program Details: Main menu for choosing or creating a player profile
+
+Shows every saved profile with its avatar and bankroll. The player can pick one
+and press Play, delete one, or make a new one (type a name and click an avatar).
+If there are no profiles yet it goes straight to the new profile screen.
+
+The very first time the game is started it welcomes the player: after the name
+and avatar they pick the felt's colour (modules::theme, shown on the menu as
+it is picked) and how much to start with, then a dialog offers a look at the
+rules before the first hand. ProfileList remembers the welcome has been
+through, so it is only ever shown once, later profiles are made the plain way.
+
+Play opens the profile's save slots (see profile.rs): each one's name, bankroll,
+hands played and when it was last played. Continue plays the picked slot, an
+empty one is given a name first, and Clear empties a slot that isn't the one
+being played.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod profile;
+    pub mod frame_pacer;
+    pub mod draw;
+    pub mod input;
+    pub mod dialog;
+    pub mod theme;
+    pub mod profile_menu;
+
+Then add the following with the use commands:
+use crate::modules::profile_menu::ProfileMenu;
+
+Usage examples:
+1. Make sure the avatars (the "menu" preload group) are preloaded, then create the menu:
+    tm.preload_group("menu", &CancelToken::new(), |_| {}).await;
+    let mut menu = ProfileMenu::new();
+
+2. Run it until the player picks a profile (None means they pressed Exit):
+    let Some(profile) = menu.run(&mut profiles, &tm, layout).await else {
+        return;
+    };
+
+3. Open the rules when a new player asked to see them first:
+    if menu.wants_tour() {
+        rules_open = true;
+    }
+*/
+use macroquad::prelude::*;
+use crate::modules::draw::draw_smooth_ring;
+use crate::modules::text_cache::measure_text_cached;
+use crate::modules::layout::TableLayout;
+use crate::modules::preload_image::TextureManager;
+use crate::modules::profile::{Profile, ProfileList, SlotInfo, AVATAR_PATHS, MAX_NAME_LENGTH, MAX_SLOTS, STARTING_BANKROLL};
+use crate::modules::scale::mouse_position_world;
+use crate::modules::text_button::TextButton;
+use crate::modules::widget_layout::{Align, Placeable, Row};
+use crate::modules::layers::{self, Layer};
+use crate::modules::scene_fade;
+use crate::modules::frame_pacer::{self, FramePacer};
+use crate::modules::i18n;
+use crate::modules::input::{get_char_pressed, is_key_pressed, is_mouse_button_pressed};
+use crate::modules::frame::Frame;
+use crate::modules::dialog::Dialog;
+use crate::modules::theme::{self, THEMES};
+
+// Only this many rows fit on the smallest layout
+pub const MAX_PROFILES: usize = 6;
+
+const ROW_WIDTH: f32 = 420.0;
+const ROW_HEIGHT: f32 = 60.0;
+const ROW_GAP: f32 = 12.0;
+const AVATAR_SIZE: f32 = 60.0;
+const SLOT_ROW_WIDTH: f32 = 640.0;
+const SECONDS_PER_DAY: f64 = 86_400.0;
+const SWATCH_SIZE: f32 = 80.0;
+// What a new player can start with on the welcome
+const START_BANKROLLS: [i64; 4] = [500, STARTING_BANKROLL, 5_000, 10_000];
+
+const PICK_PROFILE_FIRST: &str = "Click a profile first";
+
+// The first-run steps after the name and avatar
+enum Welcome {
+    Table,        // Pick the felt and the starting bankroll
+    Tour(Dialog), // Offer the rules before the first hand
+}
+
+pub struct ProfileMenu {
+    rows: Vec<(u32, TextButton)>,
+    selected: Option<u32>,
+    creating: bool,
+    new_name: String,
+    new_avatar: usize,
+    btn_play: TextButton,
+    btn_new: TextButton,
+    btn_delete: TextButton,
+    btn_exit: TextButton,
+    btn_create: TextButton,
+    btn_back: TextButton,
+    slot_profile: Option<Profile>, // The profile whose slots are being picked from
+    slot_rows: Vec<TextButton>,
+    slot_selected: usize,
+    slot_name: String, // Typed for an empty slot
+    btn_slot_back: TextButton,
+    btn_slot_clear: TextButton,
+    btn_slot_continue: TextButton,
+    first_run: bool,
+    welcome: Option<Welcome>,
+    new_theme: usize,
+    new_bankroll: i64,
+    bankroll_rows: Vec<TextButton>,
+    btn_welcome_back: TextButton,
+    btn_welcome_next: TextButton,
+    tour: bool, // The new player asked to see the rules first
+}
+
+impl ProfileMenu {
+    pub fn new() -> Self {
+        let button = |text: &str| TextButton::new(0.0, 0.0, 200.0, 60.0, text, BLACK, DARKGRAY, 35);
+        let mut btn_play = button("Play");
+        btn_play.set_disabled_reason(PICK_PROFILE_FIRST);
+        let mut btn_delete = button("Delete");
+        btn_delete.set_disabled_reason(PICK_PROFILE_FIRST);
+        let mut btn_new = button("New Profile");
+        btn_new.set_disabled_reason("No room for more profiles, delete one first");
+        let mut btn_create = button("Create");
+        btn_create.set_disabled_reason("Type a name first");
+        let mut btn_slot_clear = button("Clear");
+        btn_slot_clear.set_disabled_reason("Only a saved slot you aren't playing can be cleared");
+        let mut btn_slot_continue = button("Continue");
+        btn_slot_continue.set_disabled_reason("Type a name for the new slot");
+        Self {
+            rows: Vec::new(),
+            selected: None,
+            creating: false,
+            new_name: String::new(),
+            new_avatar: 0,
+            btn_play,
+            btn_new,
+            btn_delete,
+            btn_exit: button("Exit"),
+            btn_create,
+            btn_back: button("Back"),
+            slot_profile: None,
+            slot_rows: Vec::new(),
+            slot_selected: 0,
+            slot_name: String::new(),
+            btn_slot_back: button("Back"),
+            btn_slot_clear,
+            btn_slot_continue,
+            first_run: false,
+            welcome: None,
+            new_theme: 0,
+            new_bankroll: STARTING_BANKROLL,
+            bankroll_rows: START_BANKROLLS
+                .iter()
+                .map(|amount| TextButton::new(0.0, 0.0, 160.0, 60.0, i18n::money(*amount), BLACK, DARKGRAY, 30))
+                .collect(),
+            btn_welcome_back: button("Back"),
+            btn_welcome_next: button("Next"),
+            tour: false,
+        }
+    }
+
+    // Whether the player just made on the welcome wants to see the rules, once
+    pub fn wants_tour(&mut self) -> bool {
+        std::mem::take(&mut self.tour)
+    }
+
+    // Show the menu until a profile is chosen. Returns None if the player exits or closes the window.
+    pub async fn run(&mut self, list: &mut ProfileList, tm: &TextureManager, layout: &TableLayout) -> Option<Profile> {
+        self.selected = list.last_used();
+        self.creating = list.entries().is_empty();
+        self.first_run = list.first_run();
+        self.rebuild_rows(list, layout);
+        // Drop anything the previous screen queued this frame
+        layers::clear();
+        let mut pacer = FramePacer::new();
+        loop {
+            pacer.wait(0.0);
+            let frame = Frame::begin();
+            if is_quit_requested() {
+                return None;
+            }
+            frame_pacer::declare_static();
+            frame.layout(layout.virtual_width, layout.virtual_height, theme::felt_color());
+            scene_fade::draw();
+            // Queued so it is in the picture the table fades in from
+            let title = "Black Jack";
+            let title_width = measure_text_cached(title, None, 70).width;
+            let title_x = layout.virtual_width / 2.0 - title_width / 2.0;
+            layers::push(Layer::Table, move || {
+                draw_text(title, title_x, 90.0, 70.0, GOLD);
+            });
+
+            let chosen = if self.slot_profile.is_some() {
+                self.update_slots(layout)
+            } else if self.welcome.is_some() {
+                self.update_welcome(list, layout)
+            } else if self.creating {
+                self.update_create(list, tm, layout)
+            } else {
+                match self.update_list(list, tm, layout) {
+                    Some(None) => return None,
+                    Some(profile) => profile,
+                    None => None,
+                }
+            };
+            if chosen.is_some() {
+                scene_fade::capture(theme::felt_color());
+                return chosen;
+            }
+            frame.end().await;
+        }
+    }
+
+    // One button per saved profile, stacked down the middle of the screen
+    fn rebuild_rows(&mut self, list: &ProfileList, layout: &TableLayout) {
+        let x = layout.virtual_width / 2.0 - (ROW_WIDTH - AVATAR_SIZE) / 2.0;
+        self.rows = list
+            .entries()
+            .iter()
+            .enumerate()
+            .map(|(i, (id, info))| {
+                let y = 130.0 + i as f32 * (ROW_HEIGHT + ROW_GAP);
+                let text = format!("{}   {}", info.name, i18n::money(info.bankroll));
+                let mut button = TextButton::new(x, y, ROW_WIDTH - AVATAR_SIZE - 10.0, ROW_HEIGHT, text, BLACK, DARKGRAY, 30);
+                button.with_padding(10.0, 10.0, 0.0, 0.0);
+                (*id, button)
+            })
+            .collect();
+    }
+
+    // The list of profiles. Some(None) means Exit was pressed.
+    fn update_list(&mut self, list: &mut ProfileList, tm: &TextureManager, layout: &TableLayout) -> Option<Option<Profile>> {
+        let center_x = layout.virtual_width / 2.0;
+        for (i, (id, button)) in self.rows.iter().enumerate() {
+            let info = &list.entries()[i].1;
+            let avatar_x = button.get_x() - AVATAR_SIZE - 10.0;
+            draw_avatar(tm, info.avatar_path(), avatar_x, button.get_y(), AVATAR_SIZE);
+            if button.click() {
+                self.selected = Some(*id);
+            }
+            if self.selected == Some(*id) {
+                draw_rectangle_lines(avatar_x - 5.0, button.get_y() - 5.0, ROW_WIDTH + 10.0, ROW_HEIGHT + 10.0, 3.0, GOLD);
+            }
+        }
+
+        let buttons_y = layout.virtual_height - 100.0;
+        Row::new(center_x, buttons_y).spacing(20.0).anchor(Align::Center).place(&mut [
+            &mut self.btn_new,
+            &mut self.btn_delete,
+            &mut self.btn_play,
+            &mut self.btn_exit,
+        ]);
+        self.btn_new.enabled = list.entries().len() < MAX_PROFILES;
+        self.btn_delete.enabled = self.selected.is_some();
+        self.btn_play.enabled = self.selected.is_some();
+
+        if self.btn_new.click() {
+            self.creating = true;
+            self.new_name.clear();
+        }
+        if self.btn_delete.click() && let Some(id) = self.selected.take() {
+            list.delete(id);
+            self.rebuild_rows(list, layout);
+        }
+        let play = self.btn_play.click() || is_key_pressed(KeyCode::Enter);
+        if let (true, Some(id)) = (play, self.selected) {
+            let profile = list.open(id);
+            self.slot_selected = profile.active_slot();
+            self.slot_name = format!("Slot {}", self.slot_selected + 1);
+            self.rebuild_slot_rows(profile.slots(), layout);
+            self.slot_profile = Some(profile);
+        }
+        if self.btn_exit.click() {
+            return Some(None);
+        }
+        None
+    }
+
+    // The new profile screen: type a name and click an avatar
+    fn update_create(&mut self, list: &mut ProfileList, tm: &TextureManager, layout: &TableLayout) -> Option<Profile> {
+        let center_x = layout.virtual_width / 2.0;
+        if self.first_run {
+            let welcome = "Welcome! Make a profile to keep your bankroll and stats";
+            let welcome_width = measure_text_cached(welcome, None, 28).width;
+            draw_text(welcome, center_x - welcome_width / 2.0, 132.0, 28.0, WHITE);
+        }
+        draw_text("Name:", center_x - 250.0, 185.0, 35.0, WHITE);
+        draw_rectangle(center_x - 150.0, 150.0, 400.0, 50.0, Color::new(0.0, 0.0, 0.0, 0.4));
+        draw_rectangle_lines(center_x - 150.0, 150.0, 400.0, 50.0, 2.0, GOLD);
+        // Blinking cursor after the name
+        let cursor = if get_time() % 1.0 < 0.5 { "|" } else { "" };
+        draw_text(&format!("{}{}", self.new_name, cursor), center_x - 140.0, 185.0, 35.0, WHITE);
+
+        while let Some(ch) = get_char_pressed() {
+            let allowed = ch.is_alphanumeric() || ch == ' ' || ch == '-' || ch == '_';
+            if allowed && self.new_name.chars().count() < MAX_NAME_LENGTH {
+                self.new_name.push(ch);
+            }
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            self.new_name.pop();
+        }
+
+        draw_text("Avatar:", center_x - 250.0, 285.0, 35.0, WHITE);
+        let (mouse_x, mouse_y) = mouse_position_world();
+        for (i, path) in AVATAR_PATHS.iter().enumerate() {
+            let size = 80.0;
+            let x = center_x - 250.0 + (i % 3) as f32 * (size + 20.0) + 130.0;
+            let y = 240.0 + (i / 3) as f32 * (size + 20.0);
+            draw_avatar(tm, path, x, y, size);
+            let hovered = Rect::new(x, y, size, size).contains(vec2(mouse_x, mouse_y));
+            if hovered && is_mouse_button_pressed(MouseButton::Left) {
+                self.new_avatar = i;
+            }
+            if self.new_avatar == i {
+                draw_smooth_ring(x + size / 2.0, y + size / 2.0, size / 2.0 + 5.0, 4.0, GOLD);
+            }
+        }
+
+        let buttons_y = layout.virtual_height - 100.0;
+        Row::new(center_x, buttons_y).spacing(20.0).anchor(Align::Center).place(&mut [&mut self.btn_back, &mut self.btn_create]);
+        let name = self.new_name.trim().to_string();
+        self.btn_create.enabled = !name.is_empty();
+        self.btn_back.visible = !list.entries().is_empty();
+
+        let create = self.btn_create.click() || is_key_pressed(KeyCode::Enter);
+        if create && !name.is_empty() && self.first_run {
+            self.welcome = Some(Welcome::Table);
+            return None;
+        }
+        if create && !name.is_empty() {
+            return Some(list.create(&name, self.new_avatar, STARTING_BANKROLL));
+        }
+        if self.btn_back.click() || (is_key_pressed(KeyCode::Escape) && self.btn_back.visible) {
+            self.creating = false;
+        }
+        None
+    }
+
+    // The welcome after the name and avatar: the felt, the starting bankroll, then the offer of the rules
+    fn update_welcome(&mut self, list: &mut ProfileList, layout: &TableLayout) -> Option<Profile> {
+        let center_x = layout.virtual_width / 2.0;
+        let heading = format!("Welcome, {}! Pick your table", self.new_name.trim());
+        let heading_width = measure_text_cached(&heading, None, 35).width;
+        draw_text(&heading, center_x - heading_width / 2.0, 165.0, 35.0, WHITE);
+
+        if let Some(Welcome::Tour(dialog)) = self.welcome.as_mut() {
+            let answer = dialog.update(layout.virtual_width, layout.virtual_height)?;
+            self.tour = answer == 0;
+            self.welcome = None;
+            let mut profile = list.create(self.new_name.trim(), self.new_avatar, self.new_bankroll);
+            profile.settings.theme = self.new_theme;
+            profile.save();
+            list.finish_onboarding();
+            self.first_run = false;
+            return Some(profile);
+        }
+
+        // The felt: a swatch per theme, picking one repaints the menu behind it
+        draw_text("Felt:", center_x - 250.0, 265.0, 35.0, WHITE);
+        let (mouse_x, mouse_y) = mouse_position_world();
+        for (i, theme) in THEMES.iter().enumerate() {
+            let x = center_x - 120.0 + i as f32 * (SWATCH_SIZE + 20.0);
+            let y = 220.0;
+            draw_rectangle(x, y, SWATCH_SIZE, SWATCH_SIZE, theme.felt);
+            draw_rectangle_lines(x, y, SWATCH_SIZE, SWATCH_SIZE, 2.0, WHITE);
+            let name_width = measure_text_cached(theme.name, None, 22).width;
+            draw_text(theme.name, x + SWATCH_SIZE / 2.0 - name_width / 2.0, y + SWATCH_SIZE + 25.0, 22.0, WHITE);
+            let hovered = Rect::new(x, y, SWATCH_SIZE, SWATCH_SIZE).contains(vec2(mouse_x, mouse_y));
+            if hovered && is_mouse_button_pressed(MouseButton::Left) {
+                self.new_theme = i;
+                theme::set_theme(i);
+            }
+            if self.new_theme == i {
+                draw_rectangle_lines(x - 5.0, y - 5.0, SWATCH_SIZE + 10.0, SWATCH_SIZE + 10.0, 4.0, GOLD);
+            }
+        }
+
+        draw_text("Start with:", center_x - 250.0, 385.0, 35.0, WHITE);
+        let mut rows: Vec<&mut dyn Placeable> = self.bankroll_rows.iter_mut().map(|button| button as &mut dyn Placeable).collect();
+        Row::new(center_x, 410.0).spacing(15.0).anchor(Align::Center).place(&mut rows);
+        for (button, amount) in self.bankroll_rows.iter().zip(START_BANKROLLS) {
+            if button.click() {
+                self.new_bankroll = amount;
+            }
+            if self.new_bankroll == amount {
+                draw_rectangle_lines(button.get_x() - 5.0, button.get_y() - 5.0, 170.0, 70.0, 3.0, GOLD);
+            }
+        }
+
+        let buttons_y = layout.virtual_height - 100.0;
+        Row::new(center_x, buttons_y).spacing(20.0).anchor(Align::Center).place(&mut [&mut self.btn_welcome_back, &mut self.btn_welcome_next]);
+        if self.btn_welcome_next.click() || is_key_pressed(KeyCode::Enter) {
+            let message = "New to the game? The rules screen shows how this table plays.";
+            self.welcome = Some(Welcome::Tour(Dialog::new("Before you play", message, &["Show me", "Just play"])));
+        }
+        if self.btn_welcome_back.click() || is_key_pressed(KeyCode::Escape) {
+            self.welcome = None;
+        }
+        None
+    }
+
+    // One button per save slot, stacked down the middle of the screen
+    fn rebuild_slot_rows(&mut self, slots: &[SlotInfo], layout: &TableLayout) {
+        let x = layout.virtual_width / 2.0 - SLOT_ROW_WIDTH / 2.0;
+        self.slot_rows = slots
+            .iter()
+            .enumerate()
+            .map(|(i, slot)| {
+                let y = 190.0 + i as f32 * (ROW_HEIGHT + ROW_GAP);
+                let text = if slot.used {
+                    format!("{}   {}   {} hands   {}", slot.name, i18n::money(slot.bankroll), slot.hands, played_text(slot.last_played))
+                } else {
+                    format!("Slot {}: empty", i + 1)
+                };
+                let mut button = TextButton::new(x, y, SLOT_ROW_WIDTH, ROW_HEIGHT, text, BLACK, DARKGRAY, 28);
+                button.with_padding(10.0, 10.0, 0.0, 0.0);
+                button
+            })
+            .collect();
+    }
+
+    // The save slots of the profile being opened
+    fn update_slots(&mut self, layout: &TableLayout) -> Option<Profile> {
+        let profile = self.slot_profile.as_mut()?;
+        let center_x = layout.virtual_width / 2.0;
+        let heading = format!("{}: pick a save slot", profile.info.name);
+        let heading_width = measure_text_cached(&heading, None, 35).width;
+        draw_text(&heading, center_x - heading_width / 2.0, 165.0, 35.0, WHITE);
+        for (i, button) in self.slot_rows.iter().enumerate() {
+            if button.click() && self.slot_selected != i {
+                self.slot_selected = i;
+                self.slot_name = format!("Slot {}", i + 1);
+            }
+            if self.slot_selected == i {
+                draw_rectangle_lines(button.get_x() - 5.0, button.get_y() - 5.0, SLOT_ROW_WIDTH + 10.0, ROW_HEIGHT + 10.0, 3.0, GOLD);
+            }
+        }
+        let slot_used = profile.slots()[self.slot_selected].used;
+
+        // A new slot is named before it is played
+        let name_y = 190.0 + MAX_SLOTS as f32 * (ROW_HEIGHT + ROW_GAP) + 30.0;
+        if !slot_used {
+            draw_text("Name:", center_x - 250.0, name_y + 35.0, 35.0, WHITE);
+            draw_rectangle(center_x - 150.0, name_y, 400.0, 50.0, Color::new(0.0, 0.0, 0.0, 0.4));
+            draw_rectangle_lines(center_x - 150.0, name_y, 400.0, 50.0, 2.0, GOLD);
+            let cursor = if get_time() % 1.0 < 0.5 { "|" } else { "" };
+            draw_text(&format!("{}{}", self.slot_name, cursor), center_x - 140.0, name_y + 35.0, 35.0, WHITE);
+            while let Some(ch) = get_char_pressed() {
+                let allowed = ch.is_alphanumeric() || ch == ' ' || ch == '-' || ch == '_';
+                if allowed && self.slot_name.chars().count() < MAX_NAME_LENGTH {
+                    self.slot_name.push(ch);
+                }
+            }
+            if is_key_pressed(KeyCode::Backspace) {
+                self.slot_name.pop();
+            }
+        }
+
+        let buttons_y = layout.virtual_height - 100.0;
+        Row::new(center_x, buttons_y).spacing(20.0).anchor(Align::Center).place(&mut [
+            &mut self.btn_slot_back,
+            &mut self.btn_slot_clear,
+            &mut self.btn_slot_continue,
+        ]);
+        let name = self.slot_name.trim().to_string();
+        self.btn_slot_clear.enabled = slot_used && self.slot_selected != profile.active_slot();
+        self.btn_slot_continue.enabled = slot_used || !name.is_empty();
+
+        if self.btn_slot_clear.click() {
+            profile.clear_slot(self.slot_selected);
+            self.slot_name = format!("Slot {}", self.slot_selected + 1);
+            let slots = profile.slots().to_vec();
+            self.rebuild_slot_rows(&slots, layout);
+            return None;
+        }
+        let go = self.btn_slot_continue.click() || is_key_pressed(KeyCode::Enter);
+        if go && self.btn_slot_continue.enabled {
+            let mut profile = self.slot_profile.take()?;
+            profile.use_slot(self.slot_selected, &name);
+            return Some(profile);
+        }
+        if self.btn_slot_back.click() || is_key_pressed(KeyCode::Escape) {
+            self.slot_profile = None;
+        }
+        None
+    }
+}
+
+// "today", "yesterday", "3 days ago", nothing if it has never been played
+fn played_text(last_played: f64) -> String {
+    if last_played <= 0.0 {
+        return String::new();
+    }
+    let days = ((macroquad::miniquad::date::now() - last_played) / SECONDS_PER_DAY).max(0.0) as u64;
+    match days {
+        0 => "today".to_string(),
+        1 => "yesterday".to_string(),
+        days => format!("{} days ago", days),
+    }
+}
+
+fn draw_avatar(tm: &TextureManager, path: &str, x: f32, y: f32, size: f32) {
+    if let Some(texture) = tm.get_texture(path) {
+        let params = DrawTextureParams { dest_size: Some(vec2(size, size)), ..Default::default() };
+        draw_texture_ex(&texture, x, y, WHITE, params);
+    }
+}
@@ -0,0 +1,77 @@
+/*
+By: <tyler>
+Date: 2025-12-04
+Program Details: Export and import a profile without the cloud backend
+
+Moves the name, bankroll, settings, stats and achievements between devices as
+one JSON blob (see Profile::export_json).
+On native the blob is a file next to the game (blackjack-profile.json): export
+writes it, copy it to the other computer and import reads it back.
+On the web the blob is text: export shows it in a browser dialog to copy (and
+puts it on the clipboard), import asks for it to be pasted in.
+An import replaces everything in the profile it is loaded into.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod web_bridge;
+    pub mod profile;
+    pub mod profile_transfer;
+
+Then add the following with the use commands:
+use crate::modules::profile_transfer::{export_profile, import_profile};
+
+Usage examples:
+1. Export, the message says where it went:
+    match export_profile(&profile) {
+        Ok(message) => println!("{}", message),
+        Err(err) => println!("Export failed: {}", err),
+    }
+
+2. Import into the open profile, Ok(false) means the player cancelled:
+    if let Ok(true) = import_profile(&mut profile) {
+        table.show_profile(&profile.info);
+    }
+*/
+use crate::modules::profile::Profile;
+#[cfg(target_arch = "wasm32")]
+use crate::modules::web_bridge::{ask_text, show_text};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub const EXPORT_FILE: &str = "blackjack-profile.json";
+
+// Hand the profile to the player, returns a message saying where to find it
+pub fn export_profile(profile: &Profile) -> Result<String, String> {
+    let text = profile.export_json();
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        show_text("Copy this text and paste it into Import Profile on the other device:", &text);
+        Ok("Profile copied".to_string())
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::fs::write(EXPORT_FILE, text).map_err(|err| err.to_string())?;
+        Ok(format!("Saved {}", EXPORT_FILE))
+    }
+}
+
+// Load an exported profile over `profile`. Returns false if there was nothing to import.
+pub fn import_profile(profile: &mut Profile) -> Result<bool, String> {
+    #[cfg(target_arch = "wasm32")]
+    let text = match ask_text("Paste the text from Export Profile:") {
+        Some(text) if !text.trim().is_empty() => text,
+        _ => return Ok(false),
+    };
+    #[cfg(not(target_arch = "wasm32"))]
+    let text = match std::fs::read_to_string(EXPORT_FILE) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(format!("put {} next to the game first", EXPORT_FILE));
+        }
+        Err(err) => return Err(err.to_string()),
+    };
+
+    profile.import_json(&text)?;
+    Ok(true)
+}
@@ -0,0 +1,99 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Effect quality, and a frame time monitor that lowers it when the game can't keep up
+
+On an older Chromebook running the web build the table can drop well below a
+smooth frame rate. With the quality setting on Auto a QualityMonitor watches how
+long frames take, and when the frame rate has stayed under MIN_FPS for
+SLOW_SECONDS it says so once; the game then lowers the quality and tells the
+player. The setting can also be held at High or Low by hand.
+
+Low quality:
+    - the felt is drawn flat, without its shading towards the edges
+    - looping effects (pulses, flickers) stand still, animation_time() is 0
+    - no MSAA, from the next start (it can only be picked when the window is made)
+
+Frames the pacer stretched on purpose (the frame rate cap, power saving) don't
+count, the monitor is fed FramePacer::busy_seconds(). Single slow frames (a
+texture loading, the browser tab coming back into view) are left out too.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod frame_pacer;
+    pub mod quality;
+
+Then add the following with the use commands:
+use crate::modules::quality::{self, QualityMonitor};
+
+Usage examples:
+1. Apply the setting:
+    quality::set_low(settings.low_quality());
+
+2. Watch the frames, on Auto only:
+    let mut monitor = QualityMonitor::new();
+    loop {
+        pacer.wait(settings.min_frame_seconds());
+        if settings.quality == 0 && !quality::low() && monitor.update(pacer.busy_seconds()) {
+            settings.slow_device = true; // Remembered, so the next start is low from the first frame
+            quality::set_low(true);
+        }
+    }
+
+3. Drop an effect in low quality:
+    if !quality::low() {
+        draw_shading();
+    }
+*/
+use std::cell::Cell;
+
+pub const MIN_FPS: f64 = 24.0;
+const SLOW_SECONDS: f64 = 5.0; // How long the frame rate has to stay low
+const SMOOTHING: f64 = 0.1;    // Weight of each new frame in the running average
+const HITCH_SECONDS: f64 = 0.25; // A frame longer than this is a one-off, not a slow device
+
+thread_local! {
+    static LOW: Cell<bool> = const { Cell::new(false) };
+}
+
+pub fn set_low(on: bool) {
+    LOW.with(|low| low.set(on));
+}
+
+pub fn low() -> bool {
+    LOW.with(Cell::get)
+}
+
+pub struct QualityMonitor {
+    average: f64,   // Running average of the frame time, 0 before the first frame
+    slow_for: f64,  // Seconds the average has been over the limit
+    tripped: bool,
+}
+
+impl QualityMonitor {
+    pub fn new() -> Self {
+        Self { average: 0.0, slow_for: 0.0, tripped: false }
+    }
+
+    // Count a frame that took `frame_seconds`. True once, the first time the frame rate has
+    // stayed too low for long enough.
+    pub fn update(&mut self, frame_seconds: f64) -> bool {
+        if self.tripped || frame_seconds <= 0.0 || frame_seconds > HITCH_SECONDS {
+            return false;
+        }
+        self.average = if self.average == 0.0 { frame_seconds } else { self.average + (frame_seconds - self.average) * SMOOTHING };
+        if self.average > 1.0 / MIN_FPS {
+            self.slow_for += frame_seconds;
+        } else {
+            self.slow_for = 0.0;
+        }
+        self.tripped = self.slow_for >= SLOW_SECONDS;
+        self.tripped
+    }
+
+    // Start watching again from nothing, after the quality was put back up
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
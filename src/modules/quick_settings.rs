@@ -0,0 +1,202 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: A small popover on the table for the settings changed mid-session
+
+A gear button sits on the table (the layout's quick_settings area). Pressing it
+opens a popover next to it with the few settings players change while they
+play, so they don't have to leave the table for the Settings panel:
+    Speed    - how fast the cards move and the dealer plays
+    Music    - music volume
+    Effects  - sound effects volume
+    Odds     - the live odds panel (off, full deck, this shoe)
+    Advisor  - the suggested bet between rounds
+
+The table keeps running behind it. Only the popover itself is blocked off from
+the table (see ui.rs), and a click anywhere else, Escape or the gear again
+closes it. It is drawn in the Overlay layer, the gear in the Hud.
+
+update() changes the settings in place and says when it did, the caller saves
+and applies them the same as the Settings panel does.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod draw;
+    pub mod input;
+    pub mod layers;
+    pub mod layout;
+    pub mod settings;
+    pub mod slider;
+    pub mod text_button;
+    pub mod ui;
+    pub mod widget_layout;
+    pub mod quick_settings;
+
+Then add the following with the use commands:
+use crate::modules::quick_settings::QuickSettings;
+
+Usage examples:
+1. Create it before the loop:
+    let mut quick_settings = QuickSettings::new();
+
+2. Every frame the table is showing, apply whatever was changed:
+    if quick_settings.update(layout, &mut profile.settings) {
+        profile.mark_changed();
+        animation::set_animation_speed(profile.settings.animation_speed);
+        audio.set_levels(profile.settings.music_volume, profile.settings.sfx_volume, profile.settings.duck_level);
+    }
+
+3. Close it, when something else takes the screen over:
+    quick_settings.close();
+*/
+use macroquad::prelude::*;
+use std::f32::consts::TAU;
+use crate::modules::draw::draw_smooth_ring;
+use crate::modules::input::{is_key_pressed, is_mouse_button_pressed, pointer};
+use crate::modules::layers::{self, Layer};
+use crate::modules::layout::TableLayout;
+use crate::modules::settings::Settings;
+use crate::modules::slider::Slider;
+use crate::modules::text_button::TextButton;
+use crate::modules::ui;
+use crate::modules::widget_layout::Column;
+
+const SIZE: Vec2 = vec2(400.0, 300.0);
+const GAP: f32 = 8.0;     // Between the gear and the popover, and the popover and the screen's edge
+const LABEL_SIZE: f32 = 26.0;
+const CONTROLS_X: f32 = 140.0; // From the popover's left
+
+pub struct QuickSettings {
+    open: bool,
+    btn_gear: TextButton,
+    btn_speed: TextButton,
+    sld_music: Slider,
+    sld_sfx: Slider,
+    btn_odds: TextButton,
+    btn_advisor: TextButton,
+}
+
+impl QuickSettings {
+    pub fn new() -> Self {
+        let button = |text: &str| {
+            let mut button = TextButton::new(0.0, 0.0, 220.0, 40.0, text, BLACK, DARKGREEN, 26);
+            button.with_layer(Layer::Overlay);
+            button
+        };
+        let slider = || {
+            let mut slider = Slider::new(0.0, 0.0, 160.0, 0.5);
+            slider.with_layer(Layer::Overlay);
+            slider
+        };
+        Self {
+            open: false,
+            btn_gear: TextButton::new(0.0, 0.0, 50.0, 50.0, "", BLACK, DARKGREEN, 20),
+            btn_speed: button(""),
+            sld_music: slider(),
+            sld_sfx: slider(),
+            btn_odds: button(""),
+            btn_advisor: button(""),
+        }
+    }
+
+    #[allow(unused)]
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    // Draw the gear, and the popover while it is open. True when a setting was changed.
+    pub fn update(&mut self, layout: &TableLayout, settings: &mut Settings) -> bool {
+        let area = layout.quick_settings;
+        let gear = Rect::new(area.x, area.y, area.w, area.h);
+        self.btn_gear.update_position(gear.x, gear.y, Some(gear.w), Some(gear.h));
+        let toggled = self.btn_gear.click();
+        draw_gear(gear);
+        if toggled {
+            self.open = !self.open;
+            return false;
+        }
+        if !self.open {
+            return false;
+        }
+
+        // Under the gear, or above it when there is no room, and kept on the screen
+        let x = gear.x.min(layout.virtual_width - SIZE.x - GAP).max(GAP);
+        let below = gear.y + gear.h + GAP;
+        let y = if below + SIZE.y <= layout.virtual_height { below } else { (gear.y - SIZE.y - GAP).max(GAP) };
+        let panel = Rect::new(x, y, SIZE.x, SIZE.y);
+
+        // A click anywhere else puts it away, the table gets the click as well
+        let (point, _) = pointer();
+        if is_key_pressed(KeyCode::Escape) || (is_mouse_button_pressed(MouseButton::Left) && !panel.contains(point)) {
+            self.open = false;
+            return false;
+        }
+
+        ui::block(Layer::Overlay, layers::BACKGROUND, panel);
+        layers::push_at(Layer::Overlay, layers::BACKGROUND, move || {
+            draw_rectangle(panel.x, panel.y, panel.w, panel.h, Color::new(0.12, 0.12, 0.12, 0.95));
+            draw_rectangle_lines(panel.x, panel.y, panel.w, panel.h, 2.0, GOLD);
+            draw_text("Quick settings", panel.x + 15.0, panel.y + 32.0, 30.0, GOLD);
+        });
+        self.btn_speed.set_text(settings.animation_speed_text());
+        self.btn_odds.set_text(settings.odds_text());
+        self.btn_advisor.set_text(settings.advisor_text());
+        self.sld_music.set_value(settings.music_volume);
+        self.sld_sfx.set_value(settings.sfx_volume);
+        let rows = Column::new(x + CONTROLS_X, y + 50.0).spacing(14.0).place(&mut [
+            &mut self.btn_speed,
+            &mut self.sld_music,
+            &mut self.sld_sfx,
+            &mut self.btn_odds,
+            &mut self.btn_advisor,
+        ]);
+        for (name, row) in ["Speed:", "Music:", "Effects:", "Odds:", "Advisor:"].into_iter().zip(rows) {
+            layers::push(Layer::Overlay, move || {
+                draw_text(name, x + 15.0, row.y + row.h / 2.0 + 8.0, LABEL_SIZE, WHITE);
+            });
+        }
+
+        let mut changed = false;
+        if self.btn_speed.click() {
+            settings.next_animation_speed();
+            changed = true;
+        }
+        if let Some(volume) = self.sld_music.update() {
+            settings.music_volume = volume;
+            changed = true;
+        }
+        if let Some(volume) = self.sld_sfx.update() {
+            settings.sfx_volume = volume;
+            changed = true;
+        }
+        if self.btn_odds.click() {
+            settings.next_odds();
+            changed = true;
+        }
+        if self.btn_advisor.click() {
+            settings.advisor = !settings.advisor;
+            changed = true;
+        }
+        changed
+    }
+}
+
+// A cog on the gear button: a ring with teeth round it and a hole in the middle
+fn draw_gear(gear: Rect) {
+    let center = gear.center();
+    let radius = gear.w.min(gear.h) * 0.26;
+    layers::push_at(Layer::Hud, 1, move || {
+        for tooth in 0..8 {
+            let angle = tooth as f32 / 8.0 * TAU;
+            let at = center + Vec2::from_angle(angle) * radius * 1.15;
+            let params = DrawRectangleParams { offset: vec2(0.5, 0.5), rotation: angle, color: WHITE };
+            draw_rectangle_ex(at.x, at.y, radius * 0.6, radius * 0.55, params);
+        }
+        draw_smooth_ring(center.x, center.y, radius * 0.75, radius * 0.5, WHITE);
+    });
+}
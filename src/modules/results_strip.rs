@@ -0,0 +1,167 @@
+/*
+By: <tyler>
+Date: 2025-12-01
+Program Details: Strip of the last few hand results for the HUD
+
+Each of the last STRIP_LENGTH hands gets a small colored tile, newest on the
+right: W (green) for a win, BJ (gold) for a blackjack win, P (grey) for a push
+and L (red) for a loss. Hovering a tile shows which hand it was, clicking it
+hands back the hand number so the game can open that hand's history.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod history;
+    pub mod layers;
+    pub mod tooltip;
+    pub mod input;
+    pub mod results_strip;
+
+Then add the following with the use commands:
+use crate::modules::results_strip::ResultsStrip;
+
+Usage examples:
+1. Create it before the loop and put it somewhere:
+    let mut results = ResultsStrip::new();
+    results.set_position(735.0, 90.0);
+
+2. Keep it in step with the hand history and draw it:
+    results.show(&history);
+    results.draw();
+
+3. Find out if a tile was clicked, or is being hovered:
+    if let Some(number) = results.clicked() {
+        // open hand #number
+    }
+    if let Some(number) = results.hovered_hand() {
+        // show a picture of hand #number
+    }
+*/
+use macroquad::prelude::*;
+use crate::modules::text_cache::measure_text_cached;
+use crate::modules::events::Outcome;
+use crate::modules::hand::Hand;
+use crate::modules::history::{HandHistory, HandRecord};
+use crate::modules::layers::{self, Layer};
+use crate::modules::input::is_mouse_button_pressed;
+use crate::modules::scale::mouse_position_world;
+use crate::modules::tooltip;
+use crate::modules::cursor::{self, CursorKind};
+
+const STRIP_LENGTH: usize = 10;
+const TILE_SIZE: f32 = 22.0;
+const TILE_GAP: f32 = 3.0;
+const CAPTION_HEIGHT: f32 = 22.0;
+
+#[derive(Clone, Copy, PartialEq)]
+enum ResultIcon {
+    Win,
+    Blackjack,
+    Push,
+    Loss,
+}
+
+impl ResultIcon {
+    fn from_record(record: &HandRecord) -> Self {
+        match record.outcome {
+            Some(Outcome::PlayerWin) if Hand::from_cards(&record.player_cards).is_blackjack() => ResultIcon::Blackjack,
+            Some(Outcome::PlayerWin) => ResultIcon::Win,
+            Some(Outcome::Push) => ResultIcon::Push,
+            _ => ResultIcon::Loss,
+        }
+    }
+
+    fn text(&self) -> &'static str {
+        match self {
+            ResultIcon::Win => "W",
+            ResultIcon::Blackjack => "BJ",
+            ResultIcon::Push => "P",
+            ResultIcon::Loss => "L",
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            ResultIcon::Win => GREEN,
+            ResultIcon::Blackjack => GOLD,
+            ResultIcon::Push => GRAY,
+            ResultIcon::Loss => RED,
+        }
+    }
+}
+
+pub struct ResultsStrip {
+    x: f32,
+    y: f32,
+    tiles: Vec<(u32, ResultIcon)>, // Hand number and result, oldest first
+}
+
+impl ResultsStrip {
+    pub fn new() -> Self {
+        Self { x: 0.0, y: 0.0, tiles: Vec::new() }
+    }
+
+    // Top-left corner of the caption
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        self.x = x;
+        self.y = y;
+    }
+
+    // Rebuild the tiles from the most recent finished hands
+    pub fn show(&mut self, history: &HandHistory) {
+        let hands: Vec<&HandRecord> = history.hands().collect();
+        let recent = &hands[hands.len().saturating_sub(STRIP_LENGTH)..];
+        self.tiles = recent.iter().map(|record| (record.number, ResultIcon::from_record(record))).collect();
+    }
+
+    fn tile_rect(&self, index: usize) -> Rect {
+        Rect::new(self.x + index as f32 * (TILE_SIZE + TILE_GAP), self.y + CAPTION_HEIGHT - 12.0, TILE_SIZE, TILE_SIZE)
+    }
+
+    fn hovered(&self) -> Option<usize> {
+        let (mouse_x, mouse_y) = mouse_position_world();
+        (0..self.tiles.len()).find(|index| self.tile_rect(*index).contains(vec2(mouse_x, mouse_y)))
+    }
+
+    // The hand number of the tile under the pointer, if any
+    pub fn hovered_hand(&self) -> Option<u32> {
+        self.hovered().map(|index| self.tiles[index].0)
+    }
+
+    // The hand number of the tile clicked this frame, if any
+    pub fn clicked(&self) -> Option<u32> {
+        if !is_mouse_button_pressed(MouseButton::Left) {
+            return None;
+        }
+        self.hovered_hand()
+    }
+
+    pub fn draw(&self) {
+        let hovered = self.hovered();
+        if let Some(index) = hovered {
+            let (mouse_x, mouse_y) = mouse_position_world();
+            tooltip::show(&format!("Hand #{} - click for details", self.tiles[index].0), mouse_x, mouse_y);
+            cursor::want(CursorKind::Pointer);
+        }
+        let (x, y) = (self.x, self.y);
+        let tiles: Vec<(Rect, ResultIcon, bool)> = self
+            .tiles
+            .iter()
+            .enumerate()
+            .map(|(index, (_, icon))| (self.tile_rect(index), *icon, hovered == Some(index)))
+            .collect();
+        layers::push(Layer::Hud, move || {
+            draw_text("Last hands:", x, y, 22.0, WHITE);
+            if tiles.is_empty() {
+                draw_text("none yet", x + 110.0, y, 22.0, LIGHTGRAY);
+            }
+            for (rect, icon, hovered) in tiles {
+                draw_rectangle(rect.x, rect.y, rect.w, rect.h, icon.color());
+                draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, if hovered { 3.0 } else { 1.0 }, if hovered { WHITE } else { BLACK });
+                let font_size = if icon.text().len() > 1 { 14.0 } else { 18.0 };
+                let text_width = measure_text_cached(icon.text(), None, font_size as u16).width;
+                draw_text(icon.text(), rect.x + (rect.w - text_width) / 2.0, rect.y + rect.h * 0.72, font_size, BLACK);
+            }
+        });
+    }
+}
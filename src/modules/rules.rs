@@ -0,0 +1,257 @@
+/*
+By: <tyler>
+Date: 2025-11-30
+Program Details: Table rules that change how a round plays out
+
+dealer_peeks (US rules, on by default):
+    The dealer takes a second card face down (the hole card). With an ace or a
+    ten-value card showing, the dealer peeks at it before the player acts and
+    the round ends right away on a dealer blackjack. Even money is offered
+    before the peek, so the hole card only comes out once it is turned down.
+    Turned off the dealer plays European style: no hole card, the second card
+    is drawn after the player stands.
+
+blackjack_pays (3 to 2 by default):
+    What a winning blackjack pays, as a fraction of the bet rounded down. Plenty
+    of casinos pay 6 to 5 instead. check() keeps it between 1 to 1 and 2 to 1,
+    the range real tables use, with each side of the fraction 1 to MAX_TERM.
+
+limits ($5 to $2,500 by default):
+    The smallest and biggest bet on one hand. A hand with nothing on it is still
+    dealt, for fun. The stakes (STAKES) are picked apart from the rest of the
+    table, so a Standard table is Standard at any stakes.
+
+A few well known tables come built in (VARIANTS). The player can save their own
+as a NamedRules in the settings, and pick from both the same way.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod cards;
+    pub mod rules;
+
+Then add the following with the use commands:
+use crate::modules::rules::{Limits, NamedRules, Payout, Ruleset, STAKES, VARIANTS};
+
+Usage examples:
+1. The rules live in the player's settings:
+    let rules = &profile.settings.rules;
+
+2. Print them on the felt:
+    felt.set_rules_text(rules.felt_text());
+
+3. Ask whether the dealer checks for blackjack with this card showing:
+    if rules.dealer_checks(dealer_up_card) {
+        // peek at the hole card
+    }
+
+4. The rules screen, one (heading, line) pair per line, the heading only on a section's first line:
+    for (heading, line) in rules.reference() {
+        draw_text(heading, x, y, 24.0, GOLD);
+        draw_text(&line, x + 180.0, y, 24.0, WHITE);
+        y += 26.0;
+    }
+   It is built from the Ruleset fields themselves, so whatever the table is set to is what it says.
+
+5. Pay a blackjack, and make sure hand-made rules make sense before they are used:
+    let won = rules.blackjack_pays.pay(bet);
+    if let Err(reason) = rules.check() {
+        println!("{}", reason);
+    }
+
+6. The built-in tables:
+    for (name, rules) in VARIANTS.iter() {
+        println!("{}: {}", name, rules.felt_text());
+    }
+
+7. Check every bet against the table's limits before dealing:
+    if !(0..hands).all(|hand| rules.limits.allows(bet.amount(hand))) {
+        println!("This table takes bets of {} a hand", rules.limits.text());
+    }
+
+8. The parts of a round every table plays the same:
+    if rules::offers_even_money(&player_hands, up_card) { ... }
+    while rules::dealer_draws(&dealer_hand) {
+        dealer_hand.add(shoe.deal());
+    }
+*/
+use nanoserde::{DeJson, SerJson};
+use crate::modules::cards::{Card, Rank};
+use crate::modules::hand::{Hand, MAX_HANDS};
+use crate::modules::i18n;
+
+// The dealer keeps drawing below this total
+pub const DEALER_STANDS_ON: u32 = 16;
+
+// The dealer takes a second card, then keeps drawing until reaching DEALER_STANDS_ON (soft totals count)
+pub fn dealer_draws(dealer: &Hand) -> bool {
+    dealer.len() < 2 || dealer.value() < DEALER_STANDS_ON
+}
+
+// Even money is offered on a blackjack against an ace, only when a single hand is played
+pub fn offers_even_money(player_hands: &[Hand], up_card: Card) -> bool {
+    matches!(player_hands, [hand] if hand.is_blackjack()) && up_card.rank == Rank::Ace
+}
+
+// The biggest number either side of a payout can be
+pub const MAX_TERM: u32 = 20;
+
+// A payout as a fraction of the bet: `win` for every `bet`, 3 to 2 is win 3 bet 2
+#[derive(SerJson, DeJson, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Payout {
+    pub win: u32,
+    pub bet: u32,
+}
+
+impl Payout {
+    pub const fn new(win: u32, bet: u32) -> Self {
+        Self { win, bet }
+    }
+
+    // What a bet of `amount` wins, rounded down
+    pub fn pay(self, amount: i64) -> i64 {
+        amount * self.win as i64 / self.bet.max(1) as i64
+    }
+
+    // How many bets it wins, 1.5 for 3 to 2
+    pub fn ratio(self) -> f32 {
+        self.win as f32 / self.bet.max(1) as f32
+    }
+
+    pub fn text(self) -> String {
+        format!("{} to {}", self.win, self.bet)
+    }
+}
+
+// The smallest and biggest bet on one hand
+#[derive(SerJson, DeJson, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+    pub min: i64,
+    pub max: i64,
+}
+
+impl Limits {
+    pub const fn new(min: i64, max: i64) -> Self {
+        Self { min, max }
+    }
+
+    // Whether one hand can be dealt with `amount` on it, nothing is always fine
+    pub fn allows(self, amount: i64) -> bool {
+        amount == 0 || (self.min..=self.max).contains(&amount)
+    }
+
+    pub fn text(self) -> String {
+        format!("{} to {}", i18n::money(self.min), i18n::money(self.max))
+    }
+}
+
+// The stakes a table can be played at, cheapest first
+pub const STAKES: [Limits; 4] = [Limits::new(1, 500), Limits::new(5, 2_500), Limits::new(25, 10_000), Limits::new(100, 50_000)];
+pub const DEFAULT_LIMITS: Limits = STAKES[1];
+
+#[derive(SerJson, DeJson, Clone, Debug, PartialEq)]
+#[nserde(default)]
+pub struct Ruleset {
+    pub dealer_peeks: bool, // Hole card and peek for blackjack (US), or no hole card (European)
+    #[nserde(default = "Payout::new(3, 2)")] // Rules saved before payouts could be changed paid 3 to 2
+    pub blackjack_pays: Payout,
+    #[nserde(default = "DEFAULT_LIMITS")] // Rules saved before tables had limits get the default stakes
+    pub limits: Limits,
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self { dealer_peeks: true, blackjack_pays: Payout::new(3, 2), limits: DEFAULT_LIMITS }
+    }
+}
+
+// The tables that come with the game, the first is the default
+pub const VARIANTS: [(&str, Ruleset); 3] = [
+    ("Standard", Ruleset { dealer_peeks: true, blackjack_pays: Payout::new(3, 2), limits: DEFAULT_LIMITS }),
+    ("6 to 5", Ruleset { dealer_peeks: true, blackjack_pays: Payout::new(6, 5), limits: DEFAULT_LIMITS }),
+    ("European", Ruleset { dealer_peeks: false, blackjack_pays: Payout::new(3, 2), limits: DEFAULT_LIMITS }),
+];
+
+// A table the player made and named, saved with their settings
+#[derive(SerJson, DeJson, Clone, Debug, Default, PartialEq)]
+#[nserde(default)]
+pub struct NamedRules {
+    pub name: String,
+    pub rules: Ruleset,
+}
+
+impl Ruleset {
+    // The dealer only peeks under an ace or a ten-value card
+    pub fn dealer_checks(&self, up_card: Card) -> bool {
+        self.dealer_peeks && up_card.value() >= 10
+    }
+
+    // Whether the rules make sense for a real table, and why not
+    pub fn check(&self) -> Result<(), String> {
+        let Payout { win, bet } = self.blackjack_pays;
+        if !(1..=MAX_TERM).contains(&win) || !(1..=MAX_TERM).contains(&bet) {
+            return Err(format!("Both sides of a payout have to be 1 to {}", MAX_TERM));
+        }
+        if win < bet {
+            return Err("A blackjack has to pay at least 1 to 1".to_string());
+        }
+        if win > bet * 2 {
+            return Err("A blackjack can't pay more than 2 to 1".to_string());
+        }
+        if self.limits.min < 1 || self.limits.max < self.limits.min {
+            return Err("The table's biggest bet has to be at least its smallest".to_string());
+        }
+        Ok(())
+    }
+
+    // The same game, whatever the stakes
+    pub fn plays_like(&self, other: &Ruleset) -> bool {
+        Ruleset { limits: other.limits, ..self.clone() } == *other
+    }
+
+    // The rules as printed on the felt
+    pub fn felt_text(&self) -> String {
+        format!("BLACKJACK PAYS {}  -  DEALER MUST STAND ON {}", self.blackjack_pays.text().to_uppercase(), DEALER_STANDS_ON)
+    }
+
+    // Every rule the table plays by in plain words, for the rules screen
+    pub fn reference(&self) -> Vec<(&'static str, String)> {
+        // Taken apart so a new rule can't be added without saying here what it does
+        let Ruleset { dealer_peeks, blackjack_pays, limits } = self;
+        let mut sections: Vec<(&'static str, Vec<String>)> = vec![
+            ("Payouts", vec![
+                format!("Blackjack pays {}, rounded down", blackjack_pays.text()),
+                "A blackjack beats a 21 of three or more cards".to_string(),
+                "Any other win pays 1 to 1".to_string(),
+                "A push gives the bet back".to_string(),
+            ]),
+            ("Busting", vec!["A bust loses, even when the dealer busts too".to_string()]),
+            ("Dealer", vec![
+                format!("Draws below {}, stands on {} or more", DEALER_STANDS_ON, DEALER_STANDS_ON),
+                "Soft totals count the same as hard ones".to_string(),
+            ]),
+        ];
+        let hole_card = if *dealer_peeks {
+            vec![
+                "Second card face down (US)".to_string(),
+                "Peeks under an ace or a ten, a dealer".to_string(),
+                "blackjack ends the round before you play".to_string(),
+            ]
+        } else {
+            vec!["No hole card (European)".to_string(), "The second card is drawn after you stand".to_string()]
+        };
+        sections.push(("Hole card", hole_card));
+        sections.push(("Even money", vec!["Offered on a blackjack against an ace,".to_string(), "when a single hand is played".to_string()]));
+        sections.push(("Your moves", vec!["Hit or stand".to_string(), "No doubling, splitting or surrender".to_string()]));
+        sections.push(("Hands", vec![format!("1 to {} at once, each with its own bet", MAX_HANDS)]));
+        sections.push(("Bets", vec![format!("{} a hand, or nothing to play for fun", limits.text())]));
+        sections
+            .into_iter()
+            .flat_map(|(heading, lines)| lines.into_iter().enumerate().map(move |(i, line)| (if i == 0 { heading } else { "" }, line)))
+            .collect()
+    }
+
+    pub fn dealer_peeks_text(&self) -> &'static str {
+        if self.dealer_peeks { "Yes (US)" } else { "No (European)" }
+    }
+}
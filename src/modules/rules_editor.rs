@@ -0,0 +1,212 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: A screen for making a table's rules by hand and saving them under a name
+
+Opened from the Rules screen, it starts from the rules being played. The
+blackjack payout is edited as a fraction, each side with its own - and +
+buttons, and the dealer's peek can be turned on or off. Typing names the table.
+Everything is checked as it is changed (Ruleset::check and
+Settings::check_rules_name) and Save stays greyed out, with the reason under
+it, until the table makes sense.
+
+Saved tables go in the player's settings next to the built-in ones
+(rules::VARIANTS) and are picked the same way. Saving under the name of one
+of the player's own tables replaces it, Delete forgets it.
+
+The editor only keeps the table being made; main.rs does the saving, so it can
+refuse it part way through a round.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod input;
+    pub mod layers;
+    pub mod rules;
+    pub mod settings;
+    pub mod text_button;
+    pub mod rules_editor;
+
+Then add the following with the use commands:
+use crate::modules::rules_editor::{EditorAction, RulesEditor};
+
+Usage examples:
+1. Create it before the loop, and start it from the rules being played when it is opened:
+    let mut editor = RulesEditor::new();
+    editor.open(&profile.settings);
+
+2. Every frame it is open, with the panel's top left corner at x, y (RulesEditor::SIZE big):
+    match editor.update(x, y, &profile.settings, phase == GamePhase::WaitingToDeal) {
+        Some(EditorAction::Save(name, rules)) => {
+            if let Err(reason) = profile.settings.save_rules(&name, rules) {
+                editor.set_error(reason);
+            }
+        }
+        Some(EditorAction::Delete(name)) => {
+            profile.settings.delete_rules(&name);
+        }
+        Some(EditorAction::Close) => editor_open = false,
+        None => {}
+    }
+*/
+use macroquad::prelude::*;
+use crate::modules::input::{get_char_pressed, is_key_pressed};
+use crate::modules::layers::{self, Layer};
+use crate::modules::rules::{Payout, Ruleset, MAX_TERM};
+use crate::modules::settings::{Settings, MAX_RULES_NAME};
+use crate::modules::text_button::TextButton;
+use crate::modules::text_cache::measure_text_cached;
+
+const TEXT_SIZE: f32 = 30.0;
+const NOTE_SIZE: f32 = 22.0;
+const BETWEEN_ROUNDS: &str = "Finish the hand first";
+
+pub enum EditorAction {
+    Save(String, Ruleset), // Save the rules under the name and play them
+    Delete(String),        // Forget the player's table with this name
+    Close,
+}
+
+pub struct RulesEditor {
+    name: String,
+    draft: Ruleset,
+    error: String, // Why the last save was refused, until something changes
+    btn_win_down: TextButton,
+    btn_win_up: TextButton,
+    btn_bet_down: TextButton,
+    btn_bet_up: TextButton,
+    btn_peeks: TextButton,
+    btn_save: TextButton,
+    btn_delete: TextButton,
+    btn_cancel: TextButton,
+}
+
+impl RulesEditor {
+    // Width and height of the panel it is drawn on
+    pub const SIZE: Vec2 = vec2(700.0, 480.0);
+
+    pub fn new() -> Self {
+        let button = |width, height, text: &str, font_size| {
+            let mut button = TextButton::new(0.0, 0.0, width, height, text, BLACK, DARKGREEN, font_size);
+            button.with_layer(Layer::Overlay);
+            button
+        };
+        Self {
+            name: String::new(),
+            draft: Ruleset::default(),
+            error: String::new(),
+            btn_win_down: button(45.0, 45.0, "-", 35),
+            btn_win_up: button(45.0, 45.0, "+", 35),
+            btn_bet_down: button(45.0, 45.0, "-", 35),
+            btn_bet_up: button(45.0, 45.0, "+", 35),
+            btn_peeks: button(270.0, 45.0, "", 30),
+            btn_save: button(200.0, 60.0, "Save", 35),
+            btn_delete: button(200.0, 60.0, "Delete", 35),
+            btn_cancel: button(200.0, 60.0, "Cancel", 35),
+        }
+    }
+
+    // Start from the rules being played, named after them when they are one of the player's tables
+    pub fn open(&mut self, settings: &Settings) {
+        self.draft = settings.rules.clone();
+        let name = settings.rules_name();
+        self.name = if settings.has_custom_rules(&name) { name } else { String::new() };
+        self.error.clear();
+    }
+
+    // Say why the table couldn't be saved
+    pub fn set_error(&mut self, reason: impl Into<String>) {
+        self.error = reason.into();
+    }
+
+    // Handle and draw the editor at x, y. Saving and deleting are only offered while `can_save`.
+    pub fn update(&mut self, x: f32, y: f32, settings: &Settings, can_save: bool) -> Option<EditorAction> {
+        let before = (self.name.clone(), self.draft.clone());
+        while let Some(ch) = get_char_pressed() {
+            let allowed = ch.is_alphanumeric() || ch == ' ' || ch == '-' || ch == '_';
+            if allowed && self.name.chars().count() < MAX_RULES_NAME {
+                self.name.push(ch);
+            }
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            self.name.pop();
+        }
+
+        // Every button has to be updated to be drawn, so all of them are placed and clicked first
+        let Payout { win, bet } = self.draft.blackjack_pays;
+        self.btn_win_down.update_position(x + 240.0, y + 135.0, None, None);
+        self.btn_win_up.update_position(x + 345.0, y + 135.0, None, None);
+        self.btn_bet_down.update_position(x + 455.0, y + 135.0, None, None);
+        self.btn_bet_up.update_position(x + 560.0, y + 135.0, None, None);
+        self.btn_peeks.update_position(x + 240.0, y + 205.0, None, None);
+        self.btn_peeks.set_text(self.draft.dealer_peeks_text());
+        self.btn_save.update_position(x + 20.0, y + 400.0, None, None);
+        self.btn_delete.update_position(x + 250.0, y + 400.0, None, None);
+        self.btn_cancel.update_position(x + 480.0, y + 400.0, None, None);
+        self.btn_win_down.enabled = win > 1;
+        self.btn_win_up.enabled = win < MAX_TERM;
+        self.btn_bet_down.enabled = bet > 1;
+        self.btn_bet_up.enabled = bet < MAX_TERM;
+        let problem = self.draft.check().and_then(|_| settings.check_rules_name(&self.name)).err();
+        self.btn_save.enabled = can_save && problem.is_none();
+        self.btn_delete.enabled = can_save && settings.has_custom_rules(&self.name);
+        for button in [&mut self.btn_save, &mut self.btn_delete] {
+            if can_save {
+                button.clear_disabled_reason();
+            } else {
+                button.set_disabled_reason(BETWEEN_ROUNDS);
+            }
+        }
+        let clicks = [
+            self.btn_win_down.click(),
+            self.btn_win_up.click(),
+            self.btn_bet_down.click(),
+            self.btn_bet_up.click(),
+            self.btn_peeks.click(),
+        ];
+        let (save, delete, cancel) = (self.btn_save.click(), self.btn_delete.click(), self.btn_cancel.click());
+        let payout = &mut self.draft.blackjack_pays;
+        match clicks {
+            [true, ..] => payout.win -= 1,
+            [_, true, ..] => payout.win += 1,
+            [_, _, true, ..] => payout.bet -= 1,
+            [_, _, _, true, _] => payout.bet += 1,
+            [.., true] => self.draft.dealer_peeks = !self.draft.dealer_peeks,
+            _ => {}
+        }
+        if (self.name.clone(), self.draft.clone()) != before {
+            self.error.clear();
+        }
+
+        let cursor = if get_time() % 1.0 < 0.5 { "|" } else { "" };
+        let name = format!("{}{}", self.name, cursor);
+        let (win, bet) = (self.draft.blackjack_pays.win.to_string(), self.draft.blackjack_pays.bet.to_string());
+        let note = match (self.error.is_empty(), problem) {
+            (false, _) => (self.error.clone(), RED),
+            (true, Some(problem)) => (problem, ORANGE),
+            (true, None) => ("Saved tables are picked on the Rules screen".to_string(), LIGHTGRAY),
+        };
+        layers::push(Layer::Overlay, move || {
+            draw_text("Name:", x + 20.0, y + 100.0, TEXT_SIZE, WHITE);
+            draw_rectangle(x + 240.0, y + 70.0, 365.0, 45.0, Color::new(0.0, 0.0, 0.0, 0.4));
+            draw_rectangle_lines(x + 240.0, y + 70.0, 365.0, 45.0, 2.0, GOLD);
+            draw_text(&name, x + 250.0, y + 100.0, TEXT_SIZE, WHITE);
+            draw_text("Blackjack pays:", x + 20.0, y + 167.0, TEXT_SIZE, WHITE);
+            for (value, center) in [(&win, x + 315.0), (&bet, x + 530.0)] {
+                let width = measure_text_cached(value, None, TEXT_SIZE as u16).width;
+                draw_text(value, center - width / 2.0, y + 167.0, TEXT_SIZE, GOLD);
+            }
+            draw_text("to", x + 407.0, y + 167.0, TEXT_SIZE, WHITE);
+            draw_text("Dealer peeks:", x + 20.0, y + 237.0, TEXT_SIZE, WHITE);
+            draw_text(&note.0, x + 20.0, y + 300.0, NOTE_SIZE, note.1);
+        });
+
+        if save {
+            return Some(EditorAction::Save(self.name.trim().to_string(), self.draft.clone()));
+        }
+        if delete {
+            return Some(EditorAction::Delete(self.name.trim().to_string()));
+        }
+        cancel.then_some(EditorAction::Close)
+    }
+}
@@ -46,7 +46,7 @@ thread_local! {
     });
     
     // We'll store the current virtual resolution here - made pub so other modules can access it
-    pub static VIRTUAL_RESOLUTION: RefCell<(f32, f32)> = RefCell::new((1024.0, 768.0));
+    pub static VIRTUAL_RESOLUTION: RefCell<(f32, f32)> = const { RefCell::new((1024.0, 768.0)) };
 }
 
 /// Sets the camera to the virtual resolution and adjusts the scale
@@ -87,8 +87,12 @@ pub fn use_virtual_resolution(virtual_width: f32, virtual_height: f32) {
 
 /// Function to get the mouse position in world coordinates based on the current camera state
 pub fn mouse_position_world() -> (f32, f32) {
-    let (mouse_x, mouse_y) = ::macroquad::input::mouse_position();  // Get the raw mouse position
+    let (mouse_x, mouse_y) = crate::modules::input::mouse_position();  // Get the raw mouse position
+    screen_to_world(mouse_x, mouse_y)
+}
 
+/// Convert a point in screen pixels (a mouse or touch position) to world coordinates
+pub fn screen_to_world(mouse_x: f32, mouse_y: f32) -> (f32, f32) {
     VIRTUAL_RESOLUTION.with(|res| {
         let (virtual_width, virtual_height) = *res.borrow();
         
@@ -123,3 +127,24 @@ pub fn mouse_position_world() -> (f32, f32) {
         (virtual_x, virtual_y)
     })
 }
+
+/// Convert a point in world coordinates to screen pixels (the opposite of screen_to_world)
+pub fn world_to_screen(world_x: f32, world_y: f32) -> (f32, f32) {
+    VIRTUAL_RESOLUTION.with(|res| {
+        let (virtual_width, virtual_height) = *res.borrow();
+
+        let screen_width = screen_width();
+        let screen_height = screen_height();
+
+        // Same scale factor and centering offset as screen_to_world
+        let scale_factor = if screen_width / screen_height > virtual_width / virtual_height {
+            screen_height / virtual_height
+        } else {
+            screen_width / virtual_width
+        };
+        let offset_x = (screen_width - virtual_width * scale_factor) / 2.0;
+        let offset_y = (screen_height - virtual_height * scale_factor) / 2.0;
+
+        (world_x * scale_factor + offset_x, world_y * scale_factor + offset_y)
+    })
+}
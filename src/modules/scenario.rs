@@ -0,0 +1,459 @@
+/*
+By: <tyler>
+Date: 2025-12-08
+Program Details: Golden-hand scenarios, stacked rounds with the result they must give
+
+A scenario lists the cards in the order they come out of the shoe, what the
+player does, and what the round has to end with. run() plays it with the same
+pieces the table uses (Shoe, Hand, the rules in modules::rules and Bet for the
+payout) and lists everything that came out different. They live in
+assets/scenarios.json and are checked two ways:
+    - `cargo test` plays every one and fails on any difference
+    - in a debug build, the console command `scenarios` plays them in game and
+      reads the file again first, so a new scenario can be tried without a rebuild
+The tests also play a few thousand random rounds (random shoes, hands, bets
+and hits) and check what has to hold for every round: no hand goes past 31,
+the money moved matches the hands and never takes the bankroll below 0, and no
+card is in play more times than the shoe holds it. A failing round prints its
+seed, random_case(seed) rebuilds it.
+
+The format, hands, bets, dealer_peeks, even_money and actions can be left out:
+    {
+        "name": "Soft 18 pushes against 18",
+        "hands": 1,                    // 1 - MAX_HANDS, default 1
+        "bets": [10],                  // Per hand, default nothing bet
+        "dealer_peeks": true,          // Table rule, default true (US)
+        "even_money": false,           // Take even money when it is offered
+        "cards": ["AS", "7H", "TD", "8C"],
+        "actions": ["stand"],          // "hit" or "stand", hand by hand
+        "expect": {
+            "outcomes": ["push"],      // win, lose, push or both_bust, per hand
+            "player_totals": [18],
+            "dealer_total": 18,
+            "bankroll_change": 0
+        }
+    }
+All four expectations have to be given. The cards come out in table
+order: two to each hand, left to right one card at a time, the dealer's up
+card, the hole card (with dealer peeks on), then the hits and the dealer's
+draws. When the actions run out the hands left all stand. Using more cards than
+the scenario lists is a failure, so a scenario can't quietly depend on the
+rest of the shoe. The table has no splitting or doubling yet, so neither can a
+scenario.
+
+Only debug builds and tests have it: the module is behind
+#[cfg(any(test, debug_assertions))] in mod.rs, so `cargo build --release`
+leaves it out while the benchmarks in its tests can still run optimized.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod cards;
+    pub mod shoe;
+    pub mod hand;
+    pub mod rules;
+    pub mod bet;
+    pub mod metrics;
+    #[cfg(any(test, debug_assertions))]
+    pub mod scenario;
+
+Then add the following with the use commands:
+use crate::modules::scenario::{self, Scenario};
+
+Usage examples:
+1. Load the scenarios and play them:
+    let scenarios = scenario::load(scenario::SCENARIOS_PATH).await?;
+    for scenario in scenarios.iter() {
+        let failures = scenario.run();
+        if !failures.is_empty() {
+            log_warn!("{} failed: {}", scenario.name, failures.join(", "));
+        }
+    }
+
+2. Play one straight from JSON:
+    let scenarios = scenario::parse(r#"{ "scenarios": [ ... ] }"#)?;
+*/
+use macroquad::file::load_string;
+use nanoserde::DeJson;
+use crate::modules::bet::Bet;
+use crate::modules::cards::Card;
+use crate::modules::hand::{settle_hands, Hand, Outcome, MAX_HANDS};
+use crate::modules::metrics::{self, Counter, Histogram};
+use crate::modules::rules::{self, Ruleset};
+use crate::modules::shoe::Shoe;
+
+#[allow(unused)]
+pub const SCENARIOS_PATH: &str = "assets/scenarios.json";
+const SHOE_DECKS: usize = 8; // Enough copies of every card for any scenario
+
+// Every field has to be given
+#[derive(DeJson, Clone, Debug, Default)]
+pub struct Expected {
+    pub outcomes: Vec<String>,
+    pub player_totals: Vec<u32>,
+    pub dealer_total: u32,
+    pub bankroll_change: i64,
+}
+
+#[derive(DeJson, Clone, Debug)]
+#[nserde(default)]
+pub struct Scenario {
+    pub name: String,
+    #[nserde(default = "1")]
+    pub hands: usize,
+    pub bets: Vec<i64>,
+    #[nserde(default = "true")]
+    pub dealer_peeks: bool,
+    pub even_money: bool,
+    pub cards: Vec<String>,
+    pub actions: Vec<String>,
+    pub expect: Expected,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            hands: 1,
+            bets: Vec::new(),
+            dealer_peeks: true,
+            even_money: false,
+            cards: Vec::new(),
+            actions: Vec::new(),
+            expect: Expected::default(),
+        }
+    }
+}
+
+// The file format
+#[derive(DeJson, Clone, Debug, Default)]
+#[nserde(default)]
+struct ScenarioFile {
+    scenarios: Vec<Scenario>,
+}
+
+// How a played scenario came out
+struct Played {
+    results: Vec<(Outcome, bool)>,
+    player_hands: Vec<Hand>,
+    dealer_hand: Hand,
+    bankroll_change: i64,
+}
+
+impl Scenario {
+    // Play the round, returns what didn't match (empty when it passed)
+    pub fn run(&self) -> Vec<String> {
+        let played = match self.play() {
+            Ok(played) => played,
+            Err(err) => return vec![err],
+        };
+        let mut failures = Vec::new();
+        let expect = &self.expect;
+        let outcomes: Vec<&str> = played.results.iter().map(|(outcome, _)| outcome_name(*outcome)).collect();
+        if outcomes != expect.outcomes {
+            failures.push(format!("outcomes {:?}, expected {:?}", outcomes, expect.outcomes));
+        }
+        let totals: Vec<u32> = played.player_hands.iter().map(|hand| hand.value()).collect();
+        if totals != expect.player_totals {
+            failures.push(format!("player totals {:?}, expected {:?}", totals, expect.player_totals));
+        }
+        if played.dealer_hand.value() != expect.dealer_total {
+            failures.push(format!("dealer total {}, expected {}", played.dealer_hand.value(), expect.dealer_total));
+        }
+        if played.bankroll_change != expect.bankroll_change {
+            failures.push(format!("bankroll change {:+}, expected {:+}", played.bankroll_change, expect.bankroll_change));
+        }
+        failures
+    }
+
+    // The round as the table plays it, minus the pauses and the drawing
+    fn play(&self) -> Result<Played, String> {
+        let cards: Vec<Card> = self.cards.iter().map(|code| Card::parse(code).ok_or(format!("{} isn't a card", code))).collect::<Result<_, _>>()?;
+        let mut shoe = Shoe::new(SHOE_DECKS, 0);
+        if !shoe.stack(0, &cards) {
+            return Err("more copies of a card than the shoe holds".to_string());
+        }
+        let played = self.play_shoe(&mut shoe)?;
+        if shoe.dealt() > cards.len() {
+            return Err(format!("used {} cards but only {} are listed", shoe.dealt(), cards.len()));
+        }
+        Ok(played)
+    }
+
+    // Play the round from whatever is in the shoe, the listed cards are left out
+    fn play_shoe(&self, shoe: &mut Shoe) -> Result<Played, String> {
+        metrics::time(Histogram::RoundSeconds, || self.play_round(shoe))
+    }
+
+    fn play_round(&self, shoe: &mut Shoe) -> Result<Played, String> {
+        if !(1..=MAX_HANDS).contains(&self.hands) {
+            return Err(format!("hands has to be from 1 to {}", MAX_HANDS));
+        }
+        let rules = Ruleset { dealer_peeks: self.dealer_peeks, ..Ruleset::default() };
+        let mut player_hands = vec![Hand::new(); self.hands];
+        let mut dealer_hand = Hand::new();
+        for _ in 0..2 {
+            for hand in player_hands.iter_mut() {
+                hand.add(shoe.deal());
+            }
+        }
+        dealer_hand.add(shoe.deal());
+        let up_card = dealer_hand.cards()[0];
+
+        let mut results = None;
+        let mut hole_card = None;
+        if rules::offers_even_money(&player_hands, up_card) && self.even_money {
+            results = Some(vec![(Outcome::PlayerWin, false)]);
+        } else if rules.dealer_peeks {
+            let card = shoe.deal();
+            let mut peeked = dealer_hand.clone();
+            peeked.add(card);
+            if rules.dealer_checks(up_card) && peeked.is_blackjack() {
+                dealer_hand = peeked;
+                results = Some(settle_hands(&player_hands, &dealer_hand));
+            } else {
+                hole_card = Some(card);
+            }
+        }
+
+        let results = match results {
+            Some(results) => results,
+            None => {
+                self.play_hands(shoe, &mut player_hands)?;
+                if let Some(card) = hole_card {
+                    dealer_hand.add(card);
+                }
+                while rules::dealer_draws(&dealer_hand) {
+                    dealer_hand.add(shoe.deal());
+                }
+                settle_hands(&player_hands, &dealer_hand)
+            }
+        };
+
+        let mut bet = Bet::new();
+        for (hand, amount) in self.bets.iter().enumerate() {
+            bet.add(hand, *amount, i64::MAX);
+        }
+        let bankroll_change = bet.settle(&results, rules.blackjack_pays);
+        Ok(Played { results, player_hands, dealer_hand, bankroll_change })
+    }
+
+    // The player's actions, hand by hand. Hit is only allowed under 21, like the Hit button.
+    fn play_hands(&self, shoe: &mut Shoe, player_hands: &mut [Hand]) -> Result<(), String> {
+        let mut actions = self.actions.iter();
+        for (i, hand) in player_hands.iter_mut().enumerate() {
+            for action in actions.by_ref() {
+                metrics::count(Counter::Actions, 1);
+                let stand = metrics::time(Histogram::ActionSeconds, || match action.as_str() {
+                    "hit" if hand.value() < 21 => {
+                        hand.add(shoe.deal());
+                        Ok(false)
+                    }
+                    "hit" => Err(format!("hand {} can't hit on {}", i + 1, hand.value())),
+                    "stand" => Ok(true),
+                    other => Err(format!("unknown action {}", other)),
+                })?;
+                if stand {
+                    break;
+                }
+            }
+        }
+        match actions.len() {
+            0 => Ok(()),
+            left => Err(format!("{} actions left after every hand stood", left)),
+        }
+    }
+}
+
+// The name an outcome has in the scenario file
+fn outcome_name(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::PlayerWin => "win",
+        Outcome::DealerWin => "lose",
+        Outcome::Push => "push",
+        Outcome::NoWinner => "both_bust",
+    }
+}
+
+pub fn parse(text: &str) -> Result<Vec<Scenario>, String> {
+    ScenarioFile::deserialize_json(text).map(|file| file.scenarios).map_err(|err| err.to_string())
+}
+
+#[allow(unused)]
+pub async fn load(path: &str) -> Result<Vec<Scenario>, String> {
+    let text = load_string(path).await.map_err(|err| format!("could not load {}: {}", path, err))?;
+    parse(&text).map_err(|err| format!("could not parse {}: {}", path, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macroquad::rand::RandGenerator;
+    use crate::modules::bench;
+
+    // Random rounds checked by the property tests below, a failure names the case's seed
+    const CASES: u64 = 2000;
+
+    #[test]
+    fn scenarios_in_assets_pass() {
+        let scenarios = parse(include_str!("../../assets/scenarios.json")).unwrap();
+        assert!(!scenarios.is_empty());
+        let failed: Vec<String> = scenarios
+            .iter()
+            .filter_map(|scenario| {
+                let failures = scenario.run();
+                (!failures.is_empty()).then(|| format!("{}: {}", scenario.name, failures.join(", ")))
+            })
+            .collect();
+        assert!(failed.is_empty(), "scenarios failed:\n{}", failed.join("\n"));
+    }
+
+    #[test]
+    fn wrong_expectation_is_reported() {
+        let scenario = Scenario {
+            cards: ["TH", "9S", "TD", "7C"].map(String::from).to_vec(),
+            actions: vec!["stand".to_string()],
+            expect: Expected { outcomes: vec!["lose".to_string()], player_totals: vec![19], dealer_total: 17, bankroll_change: 0 },
+            ..Default::default()
+        };
+        assert_eq!(scenario.run(), vec!["outcomes [\"win\"], expected [\"lose\"]".to_string()]);
+    }
+
+    #[test]
+    fn running_past_the_listed_cards_fails() {
+        // The dealer's 12 has to draw, and no card is listed for it
+        let scenario = Scenario { cards: ["TH", "9S", "TD", "2C"].map(String::from).to_vec(), ..Default::default() };
+        assert_eq!(scenario.run(), vec!["used 5 cards but only 4 are listed".to_string()]);
+    }
+
+    #[test]
+    fn hitting_on_21_is_refused() {
+        let scenario = Scenario { cards: ["AH", "KS", "TD", "7C"].map(String::from).to_vec(), actions: vec!["hit".to_string()], ..Default::default() };
+        assert_eq!(scenario.run(), vec!["hand 1 can't hit on 21".to_string()]);
+    }
+
+    // A random round: shoe, hands, rules, bets the bankroll covers and 0 - 2 hits per hand
+    fn random_case(seed: u64) -> (Scenario, Shoe, i64) {
+        let rng = RandGenerator::new();
+        rng.srand(seed);
+        let hands = rng.gen_range(1, MAX_HANDS + 1);
+        let bankroll = rng.gen_range(0, 500);
+        // Through Bet::add like the bet spots, so together they never pass the bankroll
+        let mut bet = Bet::new();
+        for hand in 0..hands {
+            bet.add(hand, rng.gen_range(0, 200), bankroll);
+        }
+        let mut actions = Vec::new();
+        for _ in 0..hands {
+            for _ in 0..rng.gen_range(0, 3) {
+                actions.push("hit".to_string());
+            }
+            actions.push("stand".to_string());
+        }
+        let scenario = Scenario {
+            hands,
+            bets: (0..hands).map(|hand| bet.amount(hand)).collect(),
+            dealer_peeks: rng.gen_range(0, 2) == 0,
+            even_money: rng.gen_range(0, 2) == 0,
+            actions,
+            ..Default::default()
+        };
+        (scenario, Shoe::new(rng.gen_range(1, 9), seed), bankroll)
+    }
+
+    // Every case that could be played. Hits on a hand already at 21 or bust are refused
+    // like the Hit button refuses them, so those cases are thrown away.
+    fn played_cases() -> Vec<(u64, Scenario, Shoe, Played, i64)> {
+        let cases: Vec<_> = (0..CASES)
+            .filter_map(|seed| {
+                let (scenario, mut shoe, bankroll) = random_case(seed);
+                let played = scenario.play_shoe(&mut shoe).ok()?;
+                Some((seed, scenario, shoe, played, bankroll))
+            })
+            .collect();
+        assert!(cases.len() as u64 > CASES / 4, "only {} of {} cases could be played", cases.len(), CASES);
+        cases
+    }
+
+    #[test]
+    fn hands_are_at_most_31_before_settling() {
+        // Nobody draws on 21 or more, so the worst is 20 plus a ten
+        for (seed, _, _, played, _) in played_cases() {
+            for hand in played.player_hands.iter().chain([&played.dealer_hand]) {
+                assert!(hand.value() <= 31, "seed {}: a hand reached {}", seed, hand.value());
+            }
+        }
+    }
+
+    #[test]
+    fn bankroll_is_conserved_across_a_round() {
+        for (seed, scenario, _, played, bankroll) in played_cases() {
+            let staked: i64 = scenario.bets.iter().sum();
+            assert!(staked <= bankroll, "seed {}: ${} bet from a ${} bankroll", seed, staked, bankroll);
+            // What each hand wins or loses, worked out from its own cards
+            let expected: i64 = scenario
+                .bets
+                .iter()
+                .zip(played.results.iter().zip(played.player_hands.iter()))
+                .map(|(amount, ((outcome, _), hand))| match outcome {
+                    Outcome::PlayerWin if hand.is_blackjack() => amount * 3 / 2,
+                    Outcome::PlayerWin => *amount,
+                    Outcome::Push => 0,
+                    Outcome::DealerWin | Outcome::NoWinner => -amount,
+                })
+                .sum();
+            // Taking even money pays a blackjack 1 to 1
+            let even_money = scenario.even_money && rules::offers_even_money(&played.player_hands, played.dealer_hand.cards()[0]);
+            let expected = if even_money { staked } else { expected };
+            assert_eq!(played.bankroll_change, expected, "seed {}: the money moved doesn't add up", seed);
+            assert!(bankroll + played.bankroll_change >= 0, "seed {}: the bankroll went below 0", seed);
+        }
+    }
+
+    #[test]
+    fn no_card_is_in_play_more_often_than_the_shoe_holds_it() {
+        for (seed, _, shoe, played, _) in played_cases() {
+            let in_play: Vec<Card> = played.player_hands.iter().chain([&played.dealer_hand]).flat_map(|hand| hand.cards().to_vec()).collect();
+            assert_eq!(in_play.len(), shoe.dealt(), "seed {}: cards in play that didn't come out of the shoe", seed);
+            for card in in_play.iter() {
+                let copies = in_play.iter().filter(|other| *other == card).count();
+                assert!(copies <= shoe.decks(), "seed {}: {} is in play {} times from {} decks", seed, card.code(), copies, shoe.decks());
+            }
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_simulator_hands_per_second() {
+        // Three hands a round standing on their first two cards, from one six deck shoe like the table's
+        const ROUNDS: usize = 1000;
+        let scenario = Scenario { hands: 3, bets: vec![10, 10, 10], ..Default::default() };
+        let mut shoe = Shoe::new(6, 42);
+        bench::run("simulator hands", ROUNDS * scenario.hands, || {
+            for _ in 0..ROUNDS {
+                if shoe.needs_reshuffle() {
+                    shoe.reshuffle();
+                }
+                scenario.play_shoe(&mut shoe).unwrap();
+            }
+        });
+        // Every test in this run counted into the same totals, so the numbers are only worth
+        // reading when the benchmark runs on its own
+        print!("{}", metrics::report());
+    }
+
+    #[test]
+    fn every_shuffle_holds_each_card_once_per_deck() {
+        for seed in 0..50 {
+            let mut shoe = Shoe::new(seed as usize % 8 + 1, seed);
+            for _ in 0..2 {
+                let dealt: Vec<Card> = (0..shoe.remaining()).map(|_| shoe.deal()).collect();
+                for card in Card::deck() {
+                    let copies = dealt.iter().filter(|other| **other == card).count();
+                    assert_eq!(copies, shoe.decks(), "seed {}: {} came out {} times", seed, card.code(), copies);
+                }
+                shoe.reshuffle();
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Fading from one screen of the game to the next
+
+The game's screens (the profile menu and the table) each run their own loop,
+so the screen being left is gone by the time the next one draws. To fade
+between them, the screen being left draws its last frame into a render target
+(capture) instead of onto the window, and the next screen draws that picture
+over itself for a moment (draw), letting it go:
+    Crossfade      - the old picture fades straight into the new screen
+    Fade to black  - the old picture goes dark, then the new screen comes up out of black
+    Off            - the new screen is just there
+
+The picture is everything the frame queued into the layers (see layers.rs),
+over the background colour the screen cleared to; anything drawn straight to
+the screen isn't in it. It is drawn in the Debug layer, above every panel,
+stretched over the virtual screen.
+
+How long it takes is set with set_duration. With reduced motion (or instant
+animations) capture does nothing, so screens change at once.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod animation;
+    pub mod frame_pacer;
+    pub mod layers;
+    pub mod scale;
+    pub mod scene_fade;
+
+Then add the following with the use commands:
+use crate::modules::scene_fade::{self, FadeStyle};
+
+Usage examples:
+1. Pick the look once the player's settings are known:
+    scene_fade::set_style(FadeStyle::FadeToBlack);
+    scene_fade::set_duration(0.5);
+
+2. Leaving a screen, after its frame is queued and before switching (what was queued is used up):
+    scene_fade::capture(theme::felt_color());
+    let chosen = profile_menu.run(&mut profiles, &tm, layout).await;
+
+3. Every frame of any screen, before layers::flush():
+    scene_fade::draw();
+*/
+use macroquad::prelude::*;
+use std::cell::{Cell, RefCell};
+use crate::modules::animation::motion_skipped;
+use crate::modules::frame_pacer;
+use crate::modules::layers::{self, Layer};
+use crate::modules::scale::VIRTUAL_RESOLUTION;
+
+pub const DEFAULT_DURATION: f32 = 0.4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FadeStyle {
+    Crossfade,
+    FadeToBlack,
+    Off,
+}
+
+// The last frame of the screen that was left, and when the fade started
+struct Transition {
+    picture: Texture2D,
+    started: f64,
+}
+
+thread_local! {
+    static STYLE: Cell<FadeStyle> = const { Cell::new(FadeStyle::Crossfade) };
+    static DURATION: Cell<f32> = const { Cell::new(DEFAULT_DURATION) };
+    static CURRENT: RefCell<Option<Transition>> = const { RefCell::new(None) };
+}
+
+pub fn set_style(style: FadeStyle) {
+    STYLE.with(|current| current.set(style));
+}
+
+// Seconds from the old screen to the new one, at least a frame's worth
+#[allow(unused)]
+pub fn set_duration(seconds: f32) {
+    DURATION.with(|duration| duration.set(seconds.max(0.05)));
+}
+
+// Draw what this frame queued into a picture, to fade out of on the next screen. The queue is emptied.
+pub fn capture(background: Color) {
+    if STYLE.with(Cell::get) == FadeStyle::Off || motion_skipped() {
+        return;
+    }
+    let (width, height) = VIRTUAL_RESOLUTION.with(|resolution| *resolution.borrow());
+    // As sharp as the window shows it
+    let scale = (screen_width() / width).min(screen_height() / height).clamp(0.5, 2.0);
+    let target = render_target((width * scale) as u32, (height * scale) as u32);
+    target.texture.set_filter(FilterMode::Linear);
+    push_camera_state();
+    // y grows downwards like the screen, a render target is stored bottom row first
+    set_camera(&Camera2D {
+        zoom: vec2(2.0 / width, 2.0 / height),
+        target: vec2(width / 2.0, height / 2.0),
+        render_target: Some(target.clone()),
+        ..Default::default()
+    });
+    clear_background(background);
+    layers::draw_queued();
+    pop_camera_state();
+    CURRENT.with(|current| *current.borrow_mut() = Some(Transition { picture: target.texture, started: get_time() }));
+}
+
+// Whether a fade is still going
+#[allow(unused)]
+pub fn is_fading() -> bool {
+    CURRENT.with(|current| current.borrow().is_some())
+}
+
+// Queue the old screen's picture over this frame, while the fade lasts
+pub fn draw() {
+    let duration = DURATION.with(Cell::get) as f64;
+    let faded = CURRENT.with(|current| {
+        let mut current = current.borrow_mut();
+        let transition = current.as_ref()?;
+        let t = ((get_time() - transition.started) / duration) as f32;
+        if t >= 1.0 {
+            *current = None;
+            return None;
+        }
+        Some((transition.picture.clone(), t))
+    });
+    let Some((picture, t)) = faded else {
+        return;
+    };
+    frame_pacer::mark_dirty();
+    let (width, height) = VIRTUAL_RESOLUTION.with(|resolution| *resolution.borrow());
+    let style = STYLE.with(Cell::get);
+    layers::push(Layer::Debug, move || {
+        let params = DrawTextureParams { dest_size: Some(vec2(width, height)), ..Default::default() };
+        match style {
+            FadeStyle::Crossfade => draw_texture_ex(&picture, 0.0, 0.0, Color::new(1.0, 1.0, 1.0, 1.0 - t), params),
+            // First half the old screen darkens, second half the black lifts off the new one
+            _ if t < 0.5 => {
+                draw_texture_ex(&picture, 0.0, 0.0, WHITE, params);
+                draw_rectangle(0.0, 0.0, width, height, Color::new(0.0, 0.0, 0.0, t * 2.0));
+            }
+            _ => draw_rectangle(0.0, 0.0, width, height, Color::new(0.0, 0.0, 0.0, 2.0 - t * 2.0)),
+        }
+    });
+}
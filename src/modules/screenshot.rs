@@ -0,0 +1,92 @@
+/*
+By: <tyler>
+Date: 2025-11-19
+Program Details: Save the current frame as a PNG
+
+On native the picture is written next to the game as a timestamped file
+(blackjack-2025-11-19_18-04-33.png). On the web the browser downloads it instead.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod screenshot;
+    pub mod web_bridge;
+In the Cargo.toml file add the following:
+    image = { version = "0.24", default-features = false, features = ["png"] }
+
+Then add the following with the use commands:
+use crate::modules::screenshot::capture_screenshot;
+
+Usage examples:
+1. Capture after everything has been drawn, right before next_frame():
+    if screenshot_requested {
+        match capture_screenshot() {
+            Ok(name) => println!("Saved {}", name),
+            Err(err) => println!("Screenshot failed: {}", err),
+        }
+    }
+*/
+use macroquad::prelude::*;
+#[cfg(target_arch = "wasm32")]
+use crate::modules::web_bridge::download_file;
+use image::ImageEncoder;
+use image::codecs::png::PngEncoder;
+
+// Grab the screen, encode it and save/download it. Returns the file name on success.
+pub fn capture_screenshot() -> Result<String, String> {
+    let screen = get_screen_data();
+    let png = encode_png(&screen)?;
+    let name = format!("blackjack-{}.png", timestamp());
+
+    #[cfg(target_arch = "wasm32")]
+    download_file(&name, &png, "image/png");
+    #[cfg(not(target_arch = "wasm32"))]
+    std::fs::write(&name, &png).map_err(|err| err.to_string())?;
+
+    Ok(name)
+}
+
+// Encode screen data as PNG bytes. The GPU hands rows back bottom-up so they get flipped here.
+fn encode_png(screen: &Image) -> Result<Vec<u8>, String> {
+    let width = screen.width as usize;
+    let height = screen.height as usize;
+    let row = width * 4;
+    let mut flipped = Vec::with_capacity(screen.bytes.len());
+    for y in (0..height).rev() {
+        flipped.extend_from_slice(&screen.bytes[y * row..(y + 1) * row]);
+    }
+
+    let mut png = Vec::new();
+    PngEncoder::new(&mut png)
+        .write_image(&flipped, width as u32, height as u32, image::ColorType::Rgba8)
+        .map_err(|err| err.to_string())?;
+    Ok(png)
+}
+
+// Current UTC time as YYYY-MM-DD_HH-MM-SS, safe to use in a file name
+pub fn timestamp() -> String {
+    let secs = miniquad::date::now() as i64;
+    let days = secs.div_euclid(86_400);
+    let time = secs.rem_euclid(86_400);
+
+    // Convert days since 1970-01-01 to a calendar date (Howard Hinnant's civil_from_days)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}_{:02}-{:02}-{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        (time / 60) % 60,
+        time % 60
+    )
+}
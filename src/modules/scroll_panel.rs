@@ -0,0 +1,293 @@
+/*
+By: <tyler>
+Date: 2025-12-07
+Program Details: A box that scrolls whatever is drawn inside it
+
+Lists that can grow longer than their panel (the fairness log now, the hand
+history, leaderboard, chat and dropdown options later) go inside a ScrollPanel.
+Anything drawn into the panel's layer between begin() and end() is clipped to
+the panel's area, so rows that are scrolled away are cut off at the edge.
+Children are laid out in content coordinates, 0,0 is the top left of the
+content, and content_pos() turns that into the world position to draw at for
+the current scroll.
+
+The list scrolls with:
+    - the mouse wheel while the pointer is over the panel
+    - dragging the content up and down (mouse or finger)
+    - dragging the scrollbar thumb, or pressing the bar to jump there
+A flick on a touch screen keeps scrolling after the finger lifts and slows to a
+stop (not with reduced motion on). The scrollbar only shows when the content is
+taller than the panel.
+
+Buttons inside the panel should check is_dragging() so that letting go at the
+end of a drag doesn't click them, and be hidden when shows() says they are
+scrolled out of sight. Panels can't be nested, the clip of the inner one would
+end the clip of the outer one.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod scale;
+    pub mod input;
+    pub mod layers;
+    pub mod scroll_panel;
+
+Then add the following with the use commands:
+use crate::modules::scroll_panel::ScrollPanel;
+
+Usage examples:
+1. Create it before the loop, in the layer its children draw in:
+    let mut scroll = ScrollPanel::new(100.0, 150.0, 600.0, 300.0);
+    scroll.with_layer(Layer::Overlay);
+
+2. Every frame, size the content, scroll, then draw the rows between begin() and end():
+    scroll.set_content_height(rows.len() as f32 * 32.0);
+    scroll.update();
+    scroll.begin();
+    for (i, row) in rows.iter().enumerate() {
+        let pos = scroll.content_pos(10.0, 24.0 + i as f32 * 32.0);
+        overlay_text(row, pos.x, pos.y, 24.0, WHITE);
+    }
+    scroll.end();
+
+3. A button in the list:
+    let pos = scroll.content_pos(10.0, 200.0);
+    btn_row.update_position(pos.x, pos.y, None, None);
+    btn_row.visible = scroll.shows(200.0, btn_row.height);
+    if btn_row.click() && !scroll.is_dragging() { ... }
+
+4. Jump to the newest line of a chat:
+    scroll.scroll_to_bottom();
+*/
+use macroquad::prelude::*;
+use crate::modules::animation;
+use crate::modules::cursor::{self, CursorKind};
+use crate::modules::frame_pacer;
+use crate::modules::input::{is_touching, mouse_wheel, pointer};
+use crate::modules::layers::{self, Layer};
+use crate::modules::scale::world_to_screen;
+
+const BAR_WIDTH: f32 = 8.0;
+const MIN_THUMB_HEIGHT: f32 = 30.0;
+const WHEEL_STEP: f32 = 40.0;     // World units per notch of the wheel
+const DRAG_THRESHOLD: f32 = 8.0;  // How far a press moves before it is a drag and not a click
+const FRICTION: f32 = 4.0;        // A flick loses this share of its speed per second (as a rate)
+const MIN_SPEED: f32 = 10.0;      // A flick slower than this stops
+
+// The pointer held down on the panel
+struct Drag {
+    start_y: f32,
+    last_y: f32,
+    on_thumb: bool,
+    moved: bool,
+    touch: bool,
+}
+
+pub struct ScrollPanel {
+    area: Rect,
+    content_height: f32,
+    offset: f32,      // How far the content is scrolled up
+    velocity: f32,    // Flick speed after a touch drag, world units a second
+    drag: Option<Drag>,
+    was_down: bool,
+    drag_released: bool, // A drag ended this frame
+    pub layer: Layer,
+    pub bar_color: Color,
+    pub thumb_color: Color,
+}
+
+impl ScrollPanel {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            area: Rect::new(x, y, width, height),
+            content_height: 0.0,
+            offset: 0.0,
+            velocity: 0.0,
+            drag: None,
+            was_down: false,
+            drag_released: false,
+            layer: Layer::Hud,
+            bar_color: Color::new(0.0, 0.0, 0.0, 0.3),
+            thumb_color: Color::new(1.0, 1.0, 1.0, 0.6),
+        }
+    }
+
+    pub fn with_layer(&mut self, layer: Layer) -> &mut Self {
+        self.layer = layer;
+        self
+    }
+
+    // Move or resize the panel (when the panel it sits in moves)
+    pub fn set_area(&mut self, x: f32, y: f32, width: f32, height: f32) -> &mut Self {
+        self.area = Rect::new(x, y, width, height);
+        self.offset = self.offset.min(self.max_offset());
+        self
+    }
+
+    // How tall everything inside is, call when the content changes
+    pub fn set_content_height(&mut self, height: f32) -> &mut Self {
+        self.content_height = height.max(0.0);
+        self.offset = self.offset.min(self.max_offset());
+        self
+    }
+
+    #[allow(unused)]
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    // Furthest the content can scroll, 0 when it all fits
+    pub fn max_offset(&self) -> f32 {
+        (self.content_height - self.area.h).max(0.0)
+    }
+
+    pub fn scroll_to(&mut self, offset: f32) {
+        self.offset = offset.clamp(0.0, self.max_offset());
+        self.velocity = 0.0;
+    }
+
+    #[allow(unused)]
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_to(self.max_offset());
+    }
+
+    // True while the content is being dragged and on the frame the drag ends
+    #[allow(unused)]
+    pub fn is_dragging(&self) -> bool {
+        self.drag.as_ref().is_some_and(|drag| drag.moved) || self.drag_released
+    }
+
+    // World position of a point given in content coordinates
+    pub fn content_pos(&self, x: f32, y: f32) -> Vec2 {
+        vec2(self.area.x + x, self.area.y + y - self.offset)
+    }
+
+    // Whether any of the content from `y` down `height` is in sight
+    #[allow(unused)]
+    pub fn shows(&self, y: f32, height: f32) -> bool {
+        y + height > self.offset && y < self.offset + self.area.h
+    }
+
+    // Scroll for the wheel, drags and flicks. Once a frame, after use_virtual_resolution.
+    pub fn update(&mut self) {
+        let dt = get_frame_time();
+        let (pos, down) = pointer();
+        let pressed = down && !self.was_down;
+        self.was_down = down;
+        self.drag_released = false;
+
+        let (_, wheel) = mouse_wheel();
+        if wheel != 0.0 && self.area.contains(pos) {
+            // Wheel units differ between platforms and browsers, only the direction is used
+            self.scroll_to(self.offset - wheel.signum() * WHEEL_STEP);
+        }
+
+        if pressed && self.area.contains(pos) {
+            let on_bar = self.max_offset() > 0.0 && self.bar_rect().contains(pos);
+            if on_bar && !self.thumb_rect().contains(pos) {
+                // Pressing the bar puts the middle of the thumb there
+                let thumb = self.thumb_rect();
+                self.scroll_by_thumb(pos.y - (thumb.y + thumb.h / 2.0));
+            }
+            self.velocity = 0.0;
+            self.drag = Some(Drag { start_y: pos.y, last_y: pos.y, on_thumb: on_bar, moved: on_bar, touch: is_touching() });
+        }
+
+        if let Some(mut drag) = self.drag.take() {
+            if down {
+                let moved_by = pos.y - drag.last_y;
+                drag.last_y = pos.y;
+                if !drag.moved && (pos.y - drag.start_y).abs() > DRAG_THRESHOLD {
+                    drag.moved = true;
+                }
+                if drag.on_thumb {
+                    self.scroll_by_thumb(moved_by);
+                } else if drag.moved {
+                    self.offset = (self.offset - moved_by).clamp(0.0, self.max_offset());
+                    if dt > 0.0 {
+                        // Average out the last few frames so one jittery frame doesn't decide the flick
+                        self.velocity = self.velocity * 0.5 - moved_by / dt * 0.5;
+                    }
+                }
+                if drag.moved {
+                    cursor::want(CursorKind::Grab);
+                }
+                self.drag = Some(drag);
+            } else {
+                self.drag_released = drag.moved;
+                if !(drag.touch && drag.moved && !drag.on_thumb) || animation::reduced_motion() {
+                    self.velocity = 0.0;
+                }
+            }
+        }
+
+        // Coast after a flick
+        if self.drag.is_none() && self.velocity != 0.0 {
+            self.offset += self.velocity * dt;
+            self.velocity *= (-FRICTION * dt).exp();
+            if self.velocity.abs() < MIN_SPEED || self.offset <= 0.0 || self.offset >= self.max_offset() {
+                self.velocity = 0.0;
+            }
+            self.offset = self.offset.clamp(0.0, self.max_offset());
+            frame_pacer::mark_dirty();
+        }
+    }
+
+    // Start clipping the panel's layer to the panel
+    pub fn begin(&self) {
+        let area = self.area;
+        layers::push(self.layer, move || set_clip(Some(area)));
+    }
+
+    // Stop clipping and draw the scrollbar over the content
+    pub fn end(&self) {
+        let bar = (self.max_offset() > 0.0).then(|| (self.bar_rect(), self.thumb_rect()));
+        let (bar_color, thumb_color) = (self.bar_color, self.thumb_color);
+        layers::push(self.layer, move || {
+            set_clip(None);
+            if let Some((bar, thumb)) = bar {
+                draw_rectangle(bar.x, bar.y, bar.w, bar.h, bar_color);
+                draw_rectangle(thumb.x, thumb.y, thumb.w, thumb.h, thumb_color);
+            }
+        });
+    }
+
+    fn bar_rect(&self) -> Rect {
+        Rect::new(self.area.x + self.area.w - BAR_WIDTH, self.area.y, BAR_WIDTH, self.area.h)
+    }
+
+    // The thumb is as much of the bar as the panel shows of the content
+    fn thumb_rect(&self) -> Rect {
+        let bar = self.bar_rect();
+        let height = (bar.h * self.area.h / self.content_height.max(self.area.h)).max(MIN_THUMB_HEIGHT).min(bar.h);
+        let max_offset = self.max_offset();
+        let travel = if max_offset > 0.0 { self.offset / max_offset } else { 0.0 };
+        Rect::new(bar.x, bar.y + (bar.h - height) * travel, bar.w, height)
+    }
+
+    // Move the thumb down by `distance` and scroll the content to match
+    fn scroll_by_thumb(&mut self, distance: f32) {
+        let track = self.area.h - self.thumb_rect().h;
+        if track > 0.0 {
+            self.scroll_to(self.offset + distance * self.max_offset() / track);
+        }
+    }
+}
+
+// Limit drawing to a world rect, None draws everywhere again
+fn set_clip(area: Option<Rect>) {
+    let clip = area.map(|area| {
+        // The scissor works in framebuffer pixels, screen pixels times the DPI scale
+        let dpi = miniquad::window::dpi_scale();
+        let (left, top) = world_to_screen(area.x, area.y);
+        let (right, bottom) = world_to_screen(area.x + area.w, area.y + area.h);
+        (
+            (left * dpi) as i32,
+            (top * dpi) as i32,
+            ((right - left) * dpi).ceil() as i32,
+            ((bottom - top) * dpi).ceil() as i32,
+        )
+    });
+    let gl = unsafe { get_internal_gl() };
+    gl.quad_gl.scissor(clip);
+}
@@ -0,0 +1,609 @@
+/*
+By: <tyler>
+Date: 2025-11-18
+Program Details: Player settings for the black jack table
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod settings;
+
+Then add the following with the use commands:
+use crate::modules::settings::Settings;
+
+Usage examples:
+1. Create the settings with their default values before the loop:
+    let mut settings = Settings::default();
+
+2. Read a setting:
+    let layout = layouts.get(settings.layout_preset);
+
+3. Change a setting from the settings screen:
+    settings.next_layout(layouts.count());
+    settings.next_log_level();
+    settings.next_decision_time();
+    settings.next_hand_count();
+    settings.next_trainer();
+    settings.next_odds();
+    settings.squeeze = !settings.squeeze;
+    settings.advisor = !settings.advisor;
+    settings.fullscreen = !settings.fullscreen;
+    settings.next_resolution();
+    settings.next_target_fps();
+    settings.reduced_motion = !settings.reduced_motion;
+    settings.next_animation_speed();
+    settings.next_currency();
+    settings.power_saving = !settings.power_saving;
+    settings.system_cursor = !settings.system_cursor;
+    settings.next_stream_mode();
+    settings.next_no_contest();
+    settings.next_scene_fade();
+    settings.next_hand_totals();
+    settings.next_text_scale();
+    settings.classic_cards = !settings.classic_cards;
+    settings.announce = !settings.announce;
+    settings.analytics = !settings.analytics;
+    settings.next_quality();
+    settings.theme = 2; // An index into theme::THEMES, picked on the Theme screen
+    settings.rules.dealer_peeks = !settings.rules.dealer_peeks;
+    settings.next_rules();
+    settings.next_stakes();
+    settings.next_low_bankroll();
+
+4. Keep a table the player made, under a name, and play it (or drop it again):
+    settings.save_rules("Downtown", rules)?;
+    settings.delete_rules("Downtown");
+    let name = settings.rules_name(); // "Standard", "Downtown" or "Custom"
+
+5. Apply the log level (the BLACKJACK_LOG environment variable still wins):
+    log::configure(settings.log_level());
+
+6. Apply the sound levels:
+    audio.set_levels(settings.music_volume, settings.sfx_volume, settings.duck_level);
+
+7. Apply the double-click and long-press timing:
+    gestures.set_thresholds(settings.gesture_thresholds());
+
+8. Apply the display settings (vsync can only be picked when the window is made, see window_conf):
+    set_fullscreen(settings.fullscreen);
+    layouts.set_resolution(settings.resolution_size());
+    let frame_time = settings.min_frame_seconds();
+    animation::set_reduced_motion(settings.reduced_motion);
+    animation::set_animation_speed(settings.animation_speed);
+    i18n::set_currency(settings.currency());
+    frame_pacer::set_power_saving(settings.power_saving);
+    cursor::set_enabled(!settings.system_cursor);
+    felt.set_green_screen(settings.green_screen());
+    cards::set_classic_faces(settings.classic_cards);
+    announcer.set_enabled(settings.announce);
+    quality::set_low(settings.low_quality());
+    theme::set_theme(settings.theme);
+    scene_fade::set_style(settings.scene_fade_style());
+    widget_style::set_text_scale(settings.text_scale_factor());
+
+Settings are saved as part of the player's profile (see profile.rs).
+*/
+use nanoserde::{DeJson, SerJson};
+use crate::modules::log::Level;
+use crate::modules::rules::{NamedRules, Ruleset, STAKES, VARIANTS};
+use crate::modules::input::GestureThresholds;
+use crate::modules::hand::MAX_HANDS;
+use crate::modules::i18n::{Currency, CURRENCIES};
+use crate::modules::theme::THEMES;
+use crate::modules::scene_fade::FadeStyle;
+use crate::modules::hand_badge::TotalStyle;
+
+// Choices for the decision timer in seconds, 0 is off
+const DECISION_TIMES: [u32; 5] = [0, 5, 10, 15, 30];
+
+// Virtual resolutions the table can be laid out for, on top of each layout's own size
+pub const RESOLUTIONS: [(f32, f32); 3] = [(1024.0, 768.0), (1280.0, 720.0), (1920.0, 1080.0)];
+
+// Choices for the frame rate cap, 0 is no cap
+const TARGET_FPS: [u32; 5] = [0, 30, 60, 120, 144];
+
+// Choices for how fast cards move and the dealer plays, 0 is instant
+const ANIMATION_SPEEDS: [f32; 7] = [0.5, 0.75, 1.0, 1.5, 2.0, 3.0, 0.0];
+
+// Choices for the low bankroll warning, in the table's smallest bets, 0 is off
+const LOW_BANKROLL_BETS: [i64; 4] = [20, 10, 50, 0];
+
+// Choices for the size of the buttons' and labels' text, in percent
+const TEXT_SCALES: [u32; 5] = [100, 115, 130, 150, 85];
+
+// Tables the player can save on top of the built-in ones, and how long their names can be
+pub const MAX_CUSTOM_RULES: usize = 8;
+pub const MAX_RULES_NAME: usize = 16;
+
+#[derive(SerJson, DeJson, Clone, Debug)]
+#[nserde(default)]
+pub struct Settings {
+    pub layout_preset: usize, // Index into the presets in assets/layouts.json
+    pub log_level: String,    // error, warn, info or debug (empty means info)
+    pub decision_seconds: u32, // Time for each hit/stand decision, 0 for no timer
+    pub timeout_plays_strategy: bool, // On timeout play basic strategy instead of standing
+    pub rules: Ruleset,       // Table rules, see rules.rs
+    pub custom_rules: Vec<NamedRules>, // Tables the player made, offered after rules::VARIANTS
+    pub music_volume: f32,    // 0.0 - 1.0
+    pub sfx_volume: f32,      // 0.0 - 1.0
+    pub duck_level: f32,      // Music volume while a win/lose stinger plays, as a fraction of music_volume
+    pub double_click_seconds: f32, // Most time between the taps of a double click
+    pub long_press_seconds: f32, // How long to hold for a long press
+    pub hand_count: usize,    // Hands played each round, 1 to MAX_HANDS
+    pub counting_trainer: bool, // Show the shoe composition and count panel
+    pub practice_drills: bool, // With the trainer on, deal the hands the player gets wrong most (see drills.rs)
+    pub odds: u32,            // Live odds panel: 0 off, 1 from a full deck, 2 from the cards left in this shoe
+    pub squeeze: bool,        // Hit cards come face down and are dragged over (see squeeze.rs)
+    pub advisor: bool,        // Suggested bet and risk of ruin between rounds (see advisor.rs)
+    pub fullscreen: bool,
+    pub resolution: usize,    // 0 for the layout's own size, otherwise 1 + an index into RESOLUTIONS
+    pub vsync: bool,          // Only read when the window is created
+    pub target_fps: u32,      // Frame rate cap, 0 for none (native only)
+    pub reduced_motion: bool, // Cards and panels jump into place instead of moving
+    pub animation_speed: f32, // How fast cards move and the dealer plays, 1.0 normal, 0.0 instant
+    pub currency: usize,      // Index into i18n::CURRENCIES, what money is shown in
+    pub power_saving: bool,   // Draw fewer frames while nothing on screen is moving
+    pub system_cursor: bool,  // Keep the system's mouse cursor instead of the game's own
+    pub stream_mode: u32,     // For streaming: 0 off, 1 clean HUD, 2 clean HUD on a green screen
+    pub classic_cards: bool,  // Card faces drawn in code instead of the card pictures
+    pub announce: bool,       // Read cards and results out loud (see speech.rs)
+    pub quality: u32,         // Effects: 0 auto, 1 high, 2 low (see quality.rs)
+    pub slow_device: bool,    // On auto, the frame rate was too low and the effects were lowered
+    pub theme: usize,         // Index into theme::THEMES
+    pub no_contest: u32,      // When every hand is bust or a blackjack: 0 settle at once, 1 the dealer plays fast, 2 plays it out
+    pub scene_fade: u32,      // Changing screens: 0 crossfade, 1 fade to black, 2 off (see scene_fade.rs)
+    pub analytics: bool,      // Keep a play log on this device (see analytics.rs), off unless the player turns it on
+    pub hand_totals: u32,     // Soft totals on the badges: 0 "7/17", 1 "Soft 17 (7/17)", 2 just "17"
+    pub low_bankroll: u32,    // Index into LOW_BANKROLL_BETS, when to warn that the bankroll is running low
+    pub text_scale: u32,      // Size of the buttons' and labels' text in percent, 85 to 150 (see widget_style.rs)
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            layout_preset: 0,
+            log_level: String::new(),
+            decision_seconds: 0,
+            timeout_plays_strategy: false,
+            rules: Ruleset::default(),
+            custom_rules: Vec::new(),
+            music_volume: 0.5,
+            sfx_volume: 0.8,
+            duck_level: 0.3,
+            double_click_seconds: 0.35,
+            long_press_seconds: 0.6,
+            hand_count: 1,
+            counting_trainer: false,
+            practice_drills: false,
+            odds: 0,
+            squeeze: false,
+            advisor: false,
+            fullscreen: false,
+            resolution: 0,
+            vsync: true,
+            target_fps: 0,
+            reduced_motion: false,
+            animation_speed: 1.0,
+            currency: 0,
+            power_saving: true,
+            system_cursor: false,
+            stream_mode: 0,
+            classic_cards: false,
+            announce: false,
+            quality: 0,
+            slow_device: false,
+            theme: 0,
+            no_contest: 0,
+            scene_fade: 0,
+            analytics: false,
+            hand_totals: 0,
+            low_bankroll: 0,
+            text_scale: 100,
+        }
+    }
+}
+
+impl Settings {
+    // Step to the next table layout preset, wrapping around at the end
+    pub fn next_layout(&mut self, preset_count: usize) {
+        self.layout_preset = (self.layout_preset + 1) % preset_count.max(1);
+    }
+
+    // Step to the previous table layout preset, wrapping around at the start
+    pub fn previous_layout(&mut self, preset_count: usize) {
+        let count = preset_count.max(1);
+        self.layout_preset = (self.layout_preset + count - 1) % count;
+    }
+
+    // How much the game should log, info unless something else was picked
+    pub fn log_level(&self) -> Level {
+        Level::parse(&self.log_level).unwrap_or(Level::Info)
+    }
+
+    // Step to the next log level: error, warn, info, debug, then back to error
+    pub fn next_log_level(&mut self) {
+        self.log_level = self.log_level().next().name().to_string();
+    }
+
+    // Step to the next decision time: off, 5, 10, 15, 30 seconds, then back to off
+    pub fn next_decision_time(&mut self) {
+        let current = DECISION_TIMES.iter().position(|seconds| *seconds == self.decision_seconds).unwrap_or(0);
+        self.decision_seconds = DECISION_TIMES[(current + 1) % DECISION_TIMES.len()];
+    }
+
+    pub fn decision_time_text(&self) -> String {
+        if self.decision_seconds == 0 { "Off".to_string() } else { format!("{} seconds", self.decision_seconds) }
+    }
+
+    // How many hands to play, kept in range in case the saved value isn't
+    pub fn hands(&self) -> usize {
+        self.hand_count.clamp(1, MAX_HANDS)
+    }
+
+    // Step to the next hand count: 1, 2, 3, then back to 1
+    pub fn next_hand_count(&mut self) {
+        self.hand_count = self.hands() % MAX_HANDS + 1;
+    }
+
+    pub fn hand_count_text(&self) -> String {
+        if self.hands() == 1 { "1 hand".to_string() } else { format!("{} hands", self.hands()) }
+    }
+
+    // The gesture timing from these settings, distances stay at their defaults
+    pub fn gesture_thresholds(&self) -> GestureThresholds {
+        GestureThresholds {
+            double_click_seconds: self.double_click_seconds,
+            long_press_seconds: self.long_press_seconds,
+            ..Default::default()
+        }
+    }
+
+    // Step to the next trainer choice: off, counting, counting and drills, then back to off
+    pub fn next_trainer(&mut self) {
+        (self.counting_trainer, self.practice_drills) = match (self.counting_trainer, self.practice_drills) {
+            (false, _) => (true, false),
+            (true, false) => (true, true),
+            (true, true) => (false, false),
+        };
+    }
+
+    // Drills are part of the trainer, they only run while it is on
+    pub fn drills(&self) -> bool {
+        self.counting_trainer && self.practice_drills
+    }
+
+    pub fn counting_trainer_text(&self) -> &'static str {
+        match (self.counting_trainer, self.drills()) {
+            (false, _) => "Off",
+            (true, false) => "Count",
+            (true, true) => "Count + drills",
+        }
+    }
+
+    // Step to the next odds panel choice: off, full deck, this shoe, then back to off
+    pub fn next_odds(&mut self) {
+        self.odds = (self.odds.min(2) + 1) % 3;
+    }
+
+    pub fn odds_text(&self) -> &'static str {
+        match self.odds {
+            0 => "Off",
+            1 => "Full deck",
+            _ => "This shoe",
+        }
+    }
+
+    // The virtual resolution picked, None to use the layout's own
+    pub fn resolution_size(&self) -> Option<(f32, f32)> {
+        self.resolution.checked_sub(1).and_then(|index| RESOLUTIONS.get(index)).copied()
+    }
+
+    // Step to the next resolution: the layout's own, then each of RESOLUTIONS, then back
+    pub fn next_resolution(&mut self) {
+        self.resolution = (self.resolution.min(RESOLUTIONS.len()) + 1) % (RESOLUTIONS.len() + 1);
+    }
+
+    pub fn resolution_text(&self) -> String {
+        match self.resolution_size() {
+            Some((width, height)) => format!("{}x{}", width, height),
+            None => "Layout's own".to_string(),
+        }
+    }
+
+    // Step to the next frame rate cap: none, 30, 60, 120, 144, then back to none
+    pub fn next_target_fps(&mut self) {
+        let current = TARGET_FPS.iter().position(|fps| *fps == self.target_fps).unwrap_or(0);
+        self.target_fps = TARGET_FPS[(current + 1) % TARGET_FPS.len()];
+    }
+
+    pub fn target_fps_text(&self) -> String {
+        if self.target_fps == 0 { "No cap".to_string() } else { format!("{} FPS", self.target_fps) }
+    }
+
+    // Shortest time a frame should take for the cap, 0 when there isn't one
+    pub fn min_frame_seconds(&self) -> f64 {
+        if self.target_fps == 0 { 0.0 } else { 1.0 / self.target_fps as f64 }
+    }
+
+    pub fn fullscreen_text(&self) -> &'static str {
+        if self.fullscreen { "On" } else { "Off" }
+    }
+
+    pub fn next_animation_speed(&mut self) {
+        let current = ANIMATION_SPEEDS.iter().position(|speed| *speed == self.animation_speed).unwrap_or(1);
+        self.animation_speed = ANIMATION_SPEEDS[(current + 1) % ANIMATION_SPEEDS.len()];
+    }
+
+    pub fn next_currency(&mut self) {
+        self.currency = (self.currency + 1) % CURRENCIES.len();
+    }
+
+    // The picked currency, dollars if the saved index is out of range
+    pub fn currency(&self) -> Currency {
+        CURRENCIES.get(self.currency).copied().unwrap_or(Currency::Dollar)
+    }
+
+    pub fn currency_text(&self) -> &'static str {
+        self.currency().name()
+    }
+
+    pub fn animation_speed_text(&self) -> String {
+        if self.animation_speed == 0.0 { "Instant".to_string() } else { format!("{}x", self.animation_speed) }
+    }
+
+    pub fn reduced_motion_text(&self) -> &'static str {
+        if self.reduced_motion { "On" } else { "Off" }
+    }
+
+    pub fn power_saving_text(&self) -> &'static str {
+        if self.power_saving { "On" } else { "Off" }
+    }
+
+    pub fn cursor_text(&self) -> &'static str {
+        if self.system_cursor { "System" } else { "Game" }
+    }
+
+    // Off, a clean HUD, then a clean HUD with the felt keyed out
+    pub fn next_stream_mode(&mut self) {
+        self.stream_mode = (self.stream_mode + 1) % 3;
+    }
+
+    // Only the cards, hands, bets and buttons, no counters, banners or streak
+    pub fn clean_hud(&self) -> bool {
+        self.stream_mode > 0
+    }
+
+    // The felt is drawn flat chroma green so a streaming program can key it out
+    pub fn green_screen(&self) -> bool {
+        self.stream_mode == 2
+    }
+
+    pub fn stream_mode_text(&self) -> &'static str {
+        match self.stream_mode {
+            0 => "Off",
+            1 => "Clean",
+            _ => "Green screen",
+        }
+    }
+
+    // What the dealer does once the draw can't change the results (see hand::dealer_can_change_results)
+    pub fn next_no_contest(&mut self) {
+        self.no_contest = (self.no_contest + 1) % 3;
+    }
+
+    // Settle without the dealer drawing
+    pub fn skips_no_contest(&self) -> bool {
+        self.no_contest == 0
+    }
+
+    // Don't wait the usual time between the dealer's moves (there may be none left to make)
+    pub fn hurries_no_contest(&self) -> bool {
+        self.no_contest < 2
+    }
+
+    pub fn no_contest_text(&self) -> &'static str {
+        match self.no_contest {
+            0 => "Settle at once",
+            1 => "Dealer plays fast",
+            _ => "Play it out",
+        }
+    }
+
+    pub fn next_hand_totals(&mut self) {
+        self.hand_totals = (self.hand_totals + 1) % 3;
+    }
+
+    pub fn hand_totals_style(&self) -> TotalStyle {
+        match self.hand_totals {
+            0 => TotalStyle::Both,
+            1 => TotalStyle::Spelled,
+            _ => TotalStyle::Best,
+        }
+    }
+
+    // An example of the style, for its button
+    pub fn hand_totals_text(&self) -> &'static str {
+        match self.hand_totals_style() {
+            TotalStyle::Both => "7/17",
+            TotalStyle::Spelled => "Soft 17 (7/17)",
+            TotalStyle::Best => "17",
+        }
+    }
+
+    pub fn next_text_scale(&mut self) {
+        let current = TEXT_SCALES.iter().position(|percent| *percent == self.text_scale).unwrap_or(0);
+        self.text_scale = TEXT_SCALES[(current + 1) % TEXT_SCALES.len()];
+    }
+
+    // The text scale for widget_style, 1.0 for normal sized text
+    pub fn text_scale_factor(&self) -> f32 {
+        self.text_scale as f32 / 100.0
+    }
+
+    pub fn text_scale_text(&self) -> String {
+        format!("{}%", self.text_scale)
+    }
+
+    pub fn next_scene_fade(&mut self) {
+        self.scene_fade = (self.scene_fade + 1) % 3;
+    }
+
+    pub fn scene_fade_style(&self) -> FadeStyle {
+        match self.scene_fade {
+            0 => FadeStyle::Crossfade,
+            1 => FadeStyle::FadeToBlack,
+            _ => FadeStyle::Off,
+        }
+    }
+
+    pub fn scene_fade_text(&self) -> &'static str {
+        match self.scene_fade_style() {
+            FadeStyle::Crossfade => "Crossfade",
+            FadeStyle::FadeToBlack => "Through black",
+            FadeStyle::Off => "Off",
+        }
+    }
+
+    pub fn card_style_text(&self) -> &'static str {
+        if self.classic_cards { "Classic" } else { "Pictures" }
+    }
+
+    pub fn squeeze_text(&self) -> &'static str {
+        if self.squeeze { "On" } else { "Off" }
+    }
+
+    pub fn advisor_text(&self) -> &'static str {
+        if self.advisor { "On" } else { "Off" }
+    }
+
+    pub fn analytics_text(&self) -> &'static str {
+        if self.analytics { "On" } else { "Off" }
+    }
+
+    pub fn announce_text(&self) -> &'static str {
+        if self.announce { "On" } else { "Off" }
+    }
+
+    // Auto, high, low, then back to auto. Picking again forgets that auto found the device slow.
+    pub fn next_quality(&mut self) {
+        self.quality = (self.quality.min(2) + 1) % 3;
+        self.slow_device = false;
+    }
+
+    // Whether to draw the cheaper effects
+    pub fn low_quality(&self) -> bool {
+        match self.quality {
+            0 => self.slow_device,
+            1 => false,
+            _ => true,
+        }
+    }
+
+    pub fn quality_text(&self) -> &'static str {
+        match (self.quality, self.slow_device) {
+            (0, false) => "Auto",
+            (0, true) => "Auto (low)",
+            (1, _) => "High",
+            _ => "Low",
+        }
+    }
+
+    pub fn theme_text(&self) -> &'static str {
+        THEMES.get(self.theme).unwrap_or(&THEMES[0]).name
+    }
+
+    // Every table to pick from: the built-in ones, then the player's
+    fn rules_choices(&self) -> Vec<(&str, &Ruleset)> {
+        let built_in = VARIANTS.iter().map(|(name, rules)| (*name, rules));
+        built_in.chain(self.custom_rules.iter().map(|custom| (custom.name.as_str(), &custom.rules))).collect()
+    }
+
+    // The name of the table being played, "Custom" when the rules were changed by hand
+    pub fn rules_name(&self) -> String {
+        let choices = self.rules_choices();
+        choices.iter().find(|(_, rules)| rules.plays_like(&self.rules)).map_or("Custom".to_string(), |(name, _)| name.to_string())
+    }
+
+    // Play the next table along, back to the first after the last. The stakes stay as they are.
+    pub fn next_rules(&mut self) {
+        let choices = self.rules_choices();
+        let current = choices.iter().position(|(_, rules)| rules.plays_like(&self.rules));
+        let next = current.map_or(0, |i| (i + 1) % choices.len());
+        self.rules = Ruleset { limits: self.rules.limits, ..choices[next].1.clone() };
+    }
+
+    // Play the same table at the next stakes up, back to the cheapest after the dearest
+    pub fn next_stakes(&mut self) {
+        let current = STAKES.iter().position(|limits| *limits == self.rules.limits);
+        self.rules.limits = STAKES[current.map_or(0, |i| (i + 1) % STAKES.len())];
+    }
+
+    // Warn when the bankroll is down to this many of the table's smallest bets, 0 never
+    pub fn low_bankroll_bets(&self) -> i64 {
+        LOW_BANKROLL_BETS[self.low_bankroll as usize % LOW_BANKROLL_BETS.len()]
+    }
+
+    pub fn next_low_bankroll(&mut self) {
+        self.low_bankroll = (self.low_bankroll + 1) % LOW_BANKROLL_BETS.len() as u32;
+    }
+
+    pub fn low_bankroll_text(&self) -> String {
+        match self.low_bankroll_bets() {
+            0 => "Warn: off".to_string(),
+            bets => format!("Warn: {} bets", bets),
+        }
+    }
+
+    // Save `rules` as the player's own table called `name` (replacing one with the same name)
+    // and play it, or say why not
+    pub fn save_rules(&mut self, name: &str, rules: Ruleset) -> Result<(), String> {
+        rules.check()?;
+        self.check_rules_name(name)?;
+        let name = name.trim();
+        match self.custom_rules.iter_mut().find(|custom| custom.name == name) {
+            Some(custom) => custom.rules = rules.clone(),
+            None => self.custom_rules.push(NamedRules { name: name.to_string(), rules: rules.clone() }),
+        }
+        self.rules = rules;
+        Ok(())
+    }
+
+    // Whether a table can be saved under `name`, and why not
+    pub fn check_rules_name(&self, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Give the table a name".to_string());
+        }
+        if name.chars().count() > MAX_RULES_NAME {
+            return Err(format!("Names can be up to {} letters", MAX_RULES_NAME));
+        }
+        if VARIANTS.iter().any(|(built_in, _)| built_in.eq_ignore_ascii_case(name)) || name.eq_ignore_ascii_case("Custom") {
+            return Err(format!("{} is taken by a built-in table", name));
+        }
+        if self.custom_rules.len() >= MAX_CUSTOM_RULES && !self.has_custom_rules(name) {
+            return Err(format!("There is room for {} tables, delete one first", MAX_CUSTOM_RULES));
+        }
+        Ok(())
+    }
+
+    // Whether the player has a table saved as `name`
+    pub fn has_custom_rules(&self, name: &str) -> bool {
+        self.custom_rules.iter().any(|custom| custom.name == name.trim())
+    }
+
+    // Forget one of the player's tables, returns false if there is none by that name.
+    // The rules being played stay as they are.
+    pub fn delete_rules(&mut self, name: &str) -> bool {
+        let before = self.custom_rules.len();
+        self.custom_rules.retain(|custom| custom.name != name.trim());
+        self.custom_rules.len() != before
+    }
+
+    pub fn vsync_text(&self) -> &'static str {
+        if self.vsync { "On" } else { "Off" }
+    }
+
+    pub fn timeout_action_text(&self) -> &'static str {
+        if self.timeout_plays_strategy { "Basic strategy" } else { "Stand" }
+    }
+}
@@ -0,0 +1,224 @@
+/*
+By: <tyler>
+Date: 2025-12-06
+Program Details: Rounded rectangles built as one mesh
+
+A rounded rectangle made from macroquad shapes is three rectangles and four
+circles, and its border is four lines plus one line per step of every corner
+arc. With a panel full of buttons that is hundreds of shapes a frame. Here each
+outline is worked out once and everything added to a ShapeBatch goes out in a
+single draw_mesh call: a fill is a fan of triangles from the middle, a border is
+a strip between the outer edge and the edge `thickness` further in.
+A radius of 0 gives square corners, so plain panels can use the same batch.
+Shapes are drawn in the order they were added.
+
+Every edge fades out over one screen pixel (half a pixel each side of where the
+edge really is), so curves and slopes don't stair-step whatever the window
+size. Circles, rings and arcs go in the same batch, see draw.rs.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod shapes;
+
+Then add the following with the use commands:
+use crate::modules::shapes::{draw_round_rect, draw_round_rect_lines, ShapeBatch};
+
+Usage examples:
+1. One shape on its own:
+    draw_round_rect(100.0, 100.0, 200.0, 60.0, 5.0, DARKGREEN);
+    draw_round_rect_lines(100.0, 100.0, 200.0, 60.0, 5.0, 2.0, GOLD);
+
+2. Several shapes in one go, for a panel with a frame:
+    let mut batch = ShapeBatch::new();
+    batch.fill(Rect::new(0.0, 0.0, 1000.0, 700.0), 0.0, Color::new(0.0, 0.0, 0.0, 0.6));
+    batch.fill(panel, 8.0, DARKGRAY);
+    batch.outline(panel, 8.0, 3.0, GOLD);
+    batch.draw();
+*/
+use macroquad::prelude::*;
+use macroquad::models::{draw_mesh, Mesh, Vertex};
+use crate::modules::draw::{arc_segments, pixel_size};
+
+// Steps along each quarter circle corner
+const CORNER_SEGMENTS: usize = 8;
+
+// Triangles waiting to be drawn together
+pub struct ShapeBatch {
+    vertices: Vec<Vertex>,
+    indices: Vec<u16>,
+}
+
+impl ShapeBatch {
+    pub fn new() -> Self {
+        Self { vertices: Vec::new(), indices: Vec::new() }
+    }
+
+    // A filled rectangle with corners rounded by `radius`
+    pub fn fill(&mut self, rect: Rect, radius: f32, color: Color) {
+        let half = pixel_size() / 2.0;
+        let solid = outline_points(grow(rect, -half), radius - half);
+        let soft = outline_points(grow(rect, half), radius + half);
+        self.fan(rect.center(), &solid, color);
+        self.band(&soft, &solid, clear(color), color, true);
+    }
+
+    // A border `thickness` wide drawn inside the edge of `rect`
+    pub fn outline(&mut self, rect: Rect, radius: f32, thickness: f32, color: Color) {
+        let half = pixel_size() / 2.0;
+        let inset = thickness.min(rect.w / 2.0).min(rect.h / 2.0);
+        let inner_rect = grow(rect, -inset);
+        let inner_radius = (radius - inset).max(0.0);
+        let outer = [outline_points(grow(rect, half), radius + half), outline_points(grow(rect, -half), radius - half)];
+        let inner = [outline_points(grow(inner_rect, half), inner_radius + half), outline_points(grow(inner_rect, -half), inner_radius - half)];
+        self.soft_band(&outer, &inner, color, true);
+    }
+
+    // A filled circle
+    pub fn circle(&mut self, center: Vec2, radius: f32, color: Color) {
+        let half = pixel_size() / 2.0;
+        let segments = arc_segments(radius, std::f32::consts::TAU);
+        let solid = circle_points(center, radius - half, 0.0, std::f32::consts::TAU, segments, false);
+        let soft = circle_points(center, radius + half, 0.0, std::f32::consts::TAU, segments, false);
+        self.fan(center, &solid, color);
+        self.band(&soft, &solid, clear(color), color, true);
+    }
+
+    // A circle's outline `thickness` wide, centered on `radius`
+    pub fn ring(&mut self, center: Vec2, radius: f32, thickness: f32, color: Color) {
+        self.ring_part(center, radius, thickness, 0.0, std::f32::consts::TAU, color, true);
+    }
+
+    // Part of a ring, from `start` radians round by `sweep` radians
+    #[allow(unused)]
+    pub fn arc(&mut self, center: Vec2, radius: f32, thickness: f32, start: f32, sweep: f32, color: Color) {
+        if sweep.abs() >= std::f32::consts::TAU {
+            return self.ring(center, radius, thickness, color);
+        }
+        self.ring_part(center, radius, thickness, start, sweep, color, false);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn ring_part(&mut self, center: Vec2, radius: f32, thickness: f32, start: f32, sweep: f32, color: Color, closed: bool) {
+        let half = pixel_size() / 2.0;
+        let (outer_radius, inner_radius) = (radius + thickness / 2.0, (radius - thickness / 2.0).max(0.0));
+        let segments = arc_segments(outer_radius, sweep);
+        let edge = |radius: f32| circle_points(center, radius.max(0.0), start, sweep, segments, !closed);
+        let outer = [edge(outer_radius + half), edge(outer_radius - half)];
+        let inner = [edge(inner_radius + half), edge(inner_radius - half)];
+        self.soft_band(&outer, &inner, color, closed);
+    }
+
+    // A band from the outer edge to the inner one, each given as its soft side then its solid side
+    fn soft_band(&mut self, outer: &[Vec<Vec2>; 2], inner: &[Vec<Vec2>; 2], color: Color, closed: bool) {
+        self.band(&outer[0], &outer[1], clear(color), color, closed);
+        self.band(&outer[1], &inner[0], color, color, closed);
+        self.band(&inner[0], &inner[1], color, clear(color), closed);
+    }
+
+    // Triangles from `center` to every pair of neighbouring edge points
+    fn fan(&mut self, center: Vec2, edge: &[Vec2], color: Color) {
+        self.make_room(edge.len() + 1);
+        let middle = self.vertices.len() as u16;
+        self.vertices.push(Vertex::new(center.x, center.y, 0.0, 0.0, 0.0, color));
+        let first = self.vertices.len() as u16;
+        let count = edge.len() as u16;
+        for point in edge {
+            self.vertices.push(Vertex::new(point.x, point.y, 0.0, 0.0, 0.0, color));
+        }
+        for i in 0..count {
+            self.indices.extend_from_slice(&[middle, first + i, first + (i + 1) % count]);
+        }
+    }
+
+    // A strip between two edges with the same number of points, each edge with its own color.
+    // A closed band joins the last points back to the first.
+    fn band(&mut self, outer: &[Vec2], inner: &[Vec2], outer_color: Color, inner_color: Color, closed: bool) {
+        self.make_room(outer.len() * 2);
+        let first = self.vertices.len() as u16;
+        let count = outer.len() as u16;
+        for (outer, inner) in outer.iter().zip(inner.iter()) {
+            self.vertices.push(Vertex::new(outer.x, outer.y, 0.0, 0.0, 0.0, outer_color));
+            self.vertices.push(Vertex::new(inner.x, inner.y, 0.0, 0.0, 0.0, inner_color));
+        }
+        let quads = if closed { count } else { count.saturating_sub(1) };
+        for i in 0..quads {
+            let (outer_a, inner_a) = (first + i * 2, first + i * 2 + 1);
+            let next = (i + 1) % count;
+            let (outer_b, inner_b) = (first + next * 2, first + next * 2 + 1);
+            self.indices.extend_from_slice(&[outer_a, outer_b, inner_a, inner_a, outer_b, inner_b]);
+        }
+    }
+
+    // Send everything to the GPU as one mesh and start again
+    pub fn draw(&mut self) {
+        if self.indices.is_empty() {
+            return;
+        }
+        let mesh = Mesh {
+            vertices: std::mem::take(&mut self.vertices),
+            indices: std::mem::take(&mut self.indices),
+            texture: None,
+        };
+        draw_mesh(&mesh);
+    }
+
+    // Mesh indices are 16 bit, draw what is queued if the next shape wouldn't fit
+    fn make_room(&mut self, vertices: usize) {
+        if self.vertices.len() + vertices > u16::MAX as usize {
+            self.draw();
+        }
+    }
+}
+
+#[allow(unused)]
+pub fn draw_round_rect(x: f32, y: f32, w: f32, h: f32, radius: f32, color: Color) {
+    let mut batch = ShapeBatch::new();
+    batch.fill(Rect::new(x, y, w, h), radius, color);
+    batch.draw();
+}
+
+#[allow(unused)]
+pub fn draw_round_rect_lines(x: f32, y: f32, w: f32, h: f32, radius: f32, thickness: f32, color: Color) {
+    let mut batch = ShapeBatch::new();
+    batch.outline(Rect::new(x, y, w, h), radius, thickness, color);
+    batch.draw();
+}
+
+// The same rectangle `by` bigger on every side (smaller when negative), never below nothing
+fn grow(rect: Rect, by: f32) -> Rect {
+    let (w, h) = ((rect.w + by * 2.0).max(0.0), (rect.h + by * 2.0).max(0.0));
+    Rect::new(rect.center().x - w / 2.0, rect.center().y - h / 2.0, w, h)
+}
+
+// The same color, fully see-through, for the outside of a soft edge
+fn clear(color: Color) -> Color {
+    Color { a: 0.0, ..color }
+}
+
+// Points clockwise around a circle from `start` round by `sweep`, `segments` sides.
+// An open arc gets a point at both ends, a full circle doesn't repeat its first point.
+fn circle_points(center: Vec2, radius: f32, start: f32, sweep: f32, segments: usize, open: bool) -> Vec<Vec2> {
+    let points = if open { segments + 1 } else { segments };
+    let step = sweep / segments as f32;
+    (0..points).map(|i| center + Vec2::from_angle(start + step * i as f32) * radius).collect()
+}
+
+// Points clockwise around the edge, the same number for any radius so outlines can pair them up
+fn outline_points(rect: Rect, radius: f32) -> Vec<Vec2> {
+    let radius = radius.min(rect.w.min(rect.h) / 2.0).max(0.0);
+    let corners = [
+        (vec2(rect.x + rect.w - radius, rect.y + radius), -std::f32::consts::FRAC_PI_2), // Top right
+        (vec2(rect.x + rect.w - radius, rect.y + rect.h - radius), 0.0),                 // Bottom right
+        (vec2(rect.x + radius, rect.y + rect.h - radius), std::f32::consts::FRAC_PI_2),  // Bottom left
+        (vec2(rect.x + radius, rect.y + radius), std::f32::consts::PI),                  // Top left
+    ];
+    let step = std::f32::consts::FRAC_PI_2 / CORNER_SEGMENTS as f32;
+    let mut points = Vec::with_capacity(corners.len() * (CORNER_SEGMENTS + 1));
+    for (center, start) in corners {
+        for i in 0..=CORNER_SEGMENTS {
+            points.push(center + Vec2::from_angle(start + step * i as f32) * radius);
+        }
+    }
+    points
+}
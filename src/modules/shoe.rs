@@ -0,0 +1,279 @@
+/*
+By: <tyler>
+Date: 2025-11-20
+Program Details: The dealing shoe - several decks shuffled together with a cut card
+
+Cards come out of the shoe in order instead of being picked at random, so the
+same card can't show up more times than there are decks. Once the cut card is
+reached the shoe asks to be reshuffled between rounds.
+
+The shoe has its own small random number generator so a seed always produces the
+same order of cards. Each reshuffle picks its new seed from modules::entropy,
+never from the seed before it: the fairness log reveals a shoe's seed once it
+is retired, and that mustn't give away the shoe being dealt now. A seed and how
+many cards had been dealt from it (ShoePosition) is enough to deal the same
+cards again, the game can be started from one with `--seed <seed> --skip <cards>`
+(see ShoePosition::from_args).
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod cards;
+    pub mod metrics;
+    pub mod entropy;
+    pub mod shoe;
+
+Then add the following with the use commands:
+use crate::modules::shoe::{Shoe, ShoePosition};
+
+Usage examples:
+1. Create a six deck shoe (it starts out shuffled):
+    let mut shoe = Shoe::new(6, entropy::new_seed());
+
+2. Deal a card:
+    let card = shoe.deal();
+
+3. Between rounds, reshuffle once the cut card has come out:
+    if shoe.needs_reshuffle() {
+        shoe.reshuffle();
+    }
+
+4. Rig the next deal for testing, the first two cards dealt go as they are and
+   the next two are a ten and a six (fails if the shoe has none of them left):
+    shoe.stack(2, &[Card::new(Rank::Ten, Suit::Spades), Card::new(Rank::Six, Suit::Hearts)]);
+
+5. Carry on with a saved shoe, 40 cards in:
+    let mut shoe = Shoe::new(6, saved_seed).with_dealt(40);
+
+6. React to a reshuffle (each reshuffle is reported once):
+    if shoe.take_reshuffle_event() {
+        shuffle_animation.start();
+    }
+
+7. Start from the command line's --seed if there is one, and get the arguments to come back here:
+    let mut shoe = match ShoePosition::from_args(std::env::args()) {
+        Some(position) => Shoe::new(6, position.seed).with_dealt(position.dealt),
+        None => Shoe::new(6, seed),
+    };
+    let args = shoe.position().args(); // "--seed 1733990400 --skip 41"
+*/
+use crate::modules::cards::Card;
+use crate::modules::entropy;
+use crate::modules::metrics::{self, Counter};
+
+// How much of the shoe is dealt before the cut card comes out (default)
+const DEFAULT_PENETRATION: f32 = 0.75;
+
+// Where in which shuffle the shoe is: the same seed with `dealt` cards gone deals the same cards next
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShoePosition {
+    pub seed: u64,
+    pub dealt: usize,
+}
+
+impl ShoePosition {
+    // The command line arguments that start the game here
+    pub fn args(&self) -> String {
+        format!("--seed {} --skip {}", self.seed, self.dealt)
+    }
+
+    // Read `--seed <seed>` and the optional `--skip <cards>` from the command line, None without a usable seed
+    pub fn from_args(args: impl Iterator<Item = String>) -> Option<Self> {
+        let args: Vec<String> = args.collect();
+        let value = |name: &str| args.iter().position(|arg| arg == name).and_then(|i| args.get(i + 1));
+        let seed = value("--seed")?.parse().ok()?;
+        let dealt = value("--skip").and_then(|cards| cards.parse().ok()).unwrap_or(0);
+        Some(Self { seed, dealt })
+    }
+}
+
+pub struct Shoe {
+    cards: Vec<Card>,
+    next: usize,        // Index of the next card to deal
+    decks: usize,
+    penetration: f32,   // Fraction of the shoe dealt before reshuffling
+    reshuffled: bool,   // Set on every reshuffle, cleared by take_reshuffle_event()
+    seed: u64,          // Seed used for the current shuffle
+}
+
+impl Shoe {
+    // Create a shoe holding the given number of decks, shuffled with the seed
+    pub fn new(decks: usize, seed: u64) -> Self {
+        let decks = decks.max(1);
+        let mut shoe = Self {
+            cards: Vec::with_capacity(decks * 52),
+            next: 0,
+            decks,
+            penetration: DEFAULT_PENETRATION,
+            reshuffled: false,
+            seed,
+        };
+        shoe.shuffle_with_seed(seed);
+        shoe
+    }
+
+    // Change how deep the cut card sits (0.1 - 0.95 of the shoe)
+    #[allow(unused)]
+    pub fn with_penetration(mut self, penetration: f32) -> Self {
+        self.penetration = penetration.clamp(0.1, 0.95);
+        self
+    }
+
+    // Pick up a shoe part way through: the first `dealt` cards are already gone
+    pub fn with_dealt(mut self, dealt: usize) -> Self {
+        self.next = dealt.min(self.cards.len());
+        self
+    }
+
+    // Deal the next card. If the shoe runs completely dry it reshuffles on the spot.
+    pub fn deal(&mut self) -> Card {
+        if self.next >= self.cards.len() {
+            self.reshuffle();
+        }
+        let card = self.cards[self.next];
+        self.next += 1;
+        card
+    }
+
+    // Put `cards` next in line, after the next `skip` cards, by swapping them forward from
+    // further down the shoe. Each one has to be left in the undealt part. The shoe's order
+    // no longer matches its seed afterwards, so the fairness log will flag the round.
+    #[allow(unused)]
+    pub fn stack(&mut self, skip: usize, cards: &[Card]) -> bool {
+        let start = self.next + skip;
+        if start + cards.len() > self.cards.len() {
+            return false;
+        }
+        // Worked on a copy so a missing card leaves the shoe as it was
+        let mut stacked = self.cards.clone();
+        for (i, card) in cards.iter().enumerate() {
+            let target = start + i;
+            let Some(found) = stacked[target..].iter().position(|other| other == card) else {
+                return false;
+            };
+            stacked.swap(target, target + found);
+        }
+        self.cards = stacked;
+        true
+    }
+
+    // True once the cut card has been reached
+    pub fn needs_reshuffle(&self) -> bool {
+        self.next >= self.cut_card_index()
+    }
+
+    // Gather every card back and shuffle with a new seed, one the old seed can't give away
+    pub fn reshuffle(&mut self) {
+        self.shuffle_with_seed(entropy::new_seed());
+    }
+
+    // Gather every card back and shuffle with a specific seed
+    pub fn shuffle_with_seed(&mut self, seed: u64) {
+        metrics::count(Counter::ShoeShuffles, 1);
+        self.seed = seed;
+        let mut rng = ShoeRng::new(seed);
+        // Start from a fresh ordered shoe so the seed alone decides the order
+        self.cards.clear();
+        for _ in 0..self.decks {
+            self.cards.extend(Card::deck());
+        }
+        // Fisher-Yates shuffle
+        for i in (1..self.cards.len()).rev() {
+            let j = rng.below(i + 1);
+            self.cards.swap(i, j);
+        }
+        self.next = 0;
+        self.reshuffled = true;
+    }
+
+    // Returns true once after each reshuffle, so listeners only react one time
+    pub fn take_reshuffle_event(&mut self) -> bool {
+        std::mem::take(&mut self.reshuffled)
+    }
+
+    // Index where the cut card sits
+    #[allow(unused)]
+    pub fn cut_card_index(&self) -> usize {
+        (self.cards.len() as f32 * self.penetration) as usize
+    }
+
+    // Number of cards dealt since the last shuffle
+    #[allow(unused)]
+    pub fn dealt(&self) -> usize {
+        self.next
+    }
+
+    // Number of cards left to deal
+    #[allow(unused)]
+    pub fn remaining(&self) -> usize {
+        self.cards.len() - self.next
+    }
+
+    // Fraction of the shoe dealt so far (0.0 - 1.0)
+    #[allow(unused)]
+    pub fn dealt_fraction(&self) -> f32 {
+        self.next as f32 / self.cards.len() as f32
+    }
+
+    #[allow(unused)]
+    pub fn decks(&self) -> usize {
+        self.decks
+    }
+
+    #[allow(unused)]
+    pub fn penetration(&self) -> f32 {
+        self.penetration
+    }
+
+    // Seed used for the current shuffle, enough to recreate the card order
+    #[allow(unused)]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    // The seed and how far into it the shoe is, to deal the next cards again later
+    pub fn position(&self) -> ShoePosition {
+        ShoePosition { seed: self.seed, dealt: self.next }
+    }
+}
+
+// Small SplitMix64 generator, good enough for shuffling cards and fully repeatable
+struct ShoeRng {
+    state: u64,
+}
+
+impl ShoeRng {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Random number from 0 up to (but not including) bound
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_revealed_seed_does_not_give_away_the_next_shoe() {
+        // Two shoes shuffled from the same (revealed) seed go on to different shoes
+        let mut first = Shoe::new(6, 1733990400);
+        let mut second = Shoe::new(6, 1733990400);
+        first.reshuffle();
+        second.reshuffle();
+        assert_ne!(first.seed(), second.seed());
+        let cards = |shoe: &mut Shoe| (0..20).map(|_| shoe.deal()).collect::<Vec<Card>>();
+        assert_ne!(cards(&mut first), cards(&mut second));
+    }
+}
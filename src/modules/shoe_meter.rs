@@ -0,0 +1,85 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: How far through the shoe the dealer is, and whether it is about to be reshuffled
+
+A thin bar under the shoe fills up as the shoe is dealt, with the share dealt
+written on it and a red tick where the cut card sits. Once the cut card has
+come out the shoe will be reshuffled before the next deal (Shoe::needs_reshuffle),
+and a little red cut card next to the bar pulses until then. Card counters can
+see how deep the count goes and when it is about to start again from zero.
+
+Everything comes from the Shoe each time it is drawn, nothing is kept. With
+reduced motion or low effects the cut card stays lit instead of pulsing.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod animation;
+    pub mod layers;
+    pub mod shoe;
+    pub mod shoe_meter;
+
+Then add the following with the use commands:
+use crate::modules::shoe_meter::ShoeMeter;
+
+Usage examples:
+1. Create it with the layout, it sits under the layout's shoe (again whenever the layout changes):
+    let mut meter = ShoeMeter::new(layout);
+    meter.set_layout(layout);
+
+2. Draw it every frame:
+    meter.draw(&shoe);
+*/
+use macroquad::prelude::*;
+use crate::modules::animation::animation_time;
+use crate::modules::layers::{self, Layer};
+use crate::modules::layout::{Area, TableLayout};
+use crate::modules::shoe::Shoe;
+use crate::modules::text_cache::measure_text_cached;
+
+const GAP: f32 = 5.0;        // Between the shoe and the bar
+const BAR_HEIGHT: f32 = 18.0;
+const ICON_WIDTH: f32 = 14.0; // The cut card, as tall as the bar
+const TEXT_SIZE: u16 = 16;
+const CUT_CARD_RED: Color = Color::new(0.86, 0.1, 0.12, 1.0);
+
+pub struct ShoeMeter {
+    shoe: Area,
+}
+
+impl ShoeMeter {
+    pub fn new(layout: &TableLayout) -> Self {
+        Self { shoe: layout.shoe }
+    }
+
+    pub fn set_layout(&mut self, layout: &TableLayout) {
+        self.shoe = layout.shoe;
+    }
+
+    pub fn draw(&self, shoe: &Shoe) {
+        let dealt = shoe.dealt_fraction().clamp(0.0, 1.0);
+        let cut = shoe.penetration();
+        // The cut card is out, the shoe goes back together after this round
+        let reshuffle_due = shoe.needs_reshuffle();
+        let glow = if reshuffle_due { 0.65 + 0.35 * (animation_time() * 5.0).cos() } else { 0.0 };
+        let area = self.shoe;
+        let bar = Rect::new(area.x, area.y + area.h + GAP, area.w - ICON_WIDTH - GAP, BAR_HEIGHT);
+        let text = format!("{:.0}% dealt", dealt * 100.0);
+        layers::push(Layer::Hud, move || {
+            draw_rectangle(bar.x, bar.y, bar.w, bar.h, Color::new(0.0, 0.0, 0.0, 0.45));
+            draw_rectangle(bar.x, bar.y, bar.w * dealt, bar.h, Color::new(0.85, 0.68, 0.2, 0.85));
+            let cut_x = bar.x + bar.w * cut;
+            draw_line(cut_x, bar.y - 2.0, cut_x, bar.y + bar.h + 2.0, 2.0, CUT_CARD_RED);
+            draw_rectangle_lines(bar.x, bar.y, bar.w, bar.h, 1.0, GOLD);
+            let width = measure_text_cached(&text, None, TEXT_SIZE).width;
+            draw_text(&text, bar.x + (bar.w - width) / 2.0, bar.y + bar.h - 4.0, TEXT_SIZE as f32, WHITE);
+
+            // The cut card, faint until it has come out
+            let icon = Rect::new(bar.x + bar.w + GAP, bar.y, ICON_WIDTH, BAR_HEIGHT);
+            let color = if reshuffle_due { Color::new(CUT_CARD_RED.r, CUT_CARD_RED.g, CUT_CARD_RED.b, glow) } else { Color::new(1.0, 1.0, 1.0, 0.2) };
+            draw_rectangle(icon.x, icon.y, icon.w, icon.h, color);
+            draw_rectangle_lines(icon.x, icon.y, icon.w, icon.h, 1.0, if reshuffle_due { WHITE } else { Color::new(1.0, 1.0, 1.0, 0.4) });
+        });
+    }
+}
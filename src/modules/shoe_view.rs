@@ -0,0 +1,250 @@
+/*
+By: <tyler>
+Date: 2025-11-28
+Program Details: The shoe and discard tray on the table, and cards moving between them
+
+- The shoe sits at the layout's shoe area. Its stack of card backs gets shorter
+  as the shoe is dealt and fills back up when it is reshuffled.
+- Every dealt card flies out of the shoe to its slot. Cards dealt together leave
+  one after the other.
+- When the table is cleared the cards slide into the discard tray, whose stack
+  grows with the number of used cards until the next shuffle.
+Everything is drawn from the Shoe's own counts, so it can't drift out of step.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod animation;
+    pub mod layers;
+    pub mod shoe_view;
+
+Then add the following with the use commands:
+use crate::modules::shoe_view::ShoeView;
+
+Usage examples:
+1. Create it and give it the layout (again whenever the layout or the number of player hands changes):
+    let mut shoe_view = ShoeView::new(layout);
+    shoe_view.set_layout(layout);
+    shoe_view.set_hands(2);
+
+2. Dealt cards fly out on their own, it listens for CardDealt:
+    bus.dispatch(&mut [&mut shoe_view, &mut stats]);
+
+3. Every frame, move the cards that are flying, then draw:
+    shoe_view.update(get_frame_time());
+    shoe_view.apply(&mut player_hands_cards, &mut dealer_cards);
+    shoe_view.draw_shoe(&shoe);
+    // ... draw the cards ...
+    shoe_view.draw_discards();
+
+4. Clearing the table, slide each card into the tray and start the next hands at slot 0:
+    shoe_view.discard(texture, card.pos());
+    shoe_view.clear_table(&mut player_hands_cards, &mut dealer_cards);
+*/
+use macroquad::prelude::*;
+use crate::modules::text_cache::measure_text_cached;
+use crate::modules::animation::{motion_skipped, scaled_time, Easing, Tween};
+use crate::modules::events::{EventListener, GameEvent, Seat};
+use crate::modules::hand::MAX_HANDS;
+use crate::modules::layers::{self, Layer};
+use crate::modules::layout::{Area, TableLayout};
+use crate::modules::shoe::Shoe;
+use crate::modules::still_image::StillImage;
+
+const DEAL_TIME: f32 = 0.35;
+const DEAL_STAGGER: f32 = 0.15; // Gap between cards dealt at the same time
+const DISCARD_TIME: f32 = 0.4;
+const CARD_EDGE: f32 = 0.6; // How thick one card looks from the side
+
+// A dealt card on its way from the shoe to its slot
+struct Flight {
+    seat: Seat,
+    slot: usize,
+    delay: f32,
+    tween: Tween,
+}
+
+// A used card on its way to the discard tray
+struct Discard {
+    texture: Texture2D,
+    from: Vec2,
+    tween: Tween,
+}
+
+pub struct ShoeView {
+    layout: TableLayout,
+    flights: Vec<Flight>,
+    discards: Vec<Discard>,
+    player_slots: [usize; MAX_HANDS], // Cards dealt to each seat since the table was cleared
+    dealer_slots: usize,
+    hands: usize, // How many player hands are on the table
+}
+
+impl ShoeView {
+    pub fn new(layout: &TableLayout) -> Self {
+        Self {
+            layout: layout.clone(),
+            flights: Vec::new(),
+            discards: Vec::new(),
+            player_slots: [0; MAX_HANDS],
+            dealer_slots: 0,
+            hands: 1,
+        }
+    }
+
+    pub fn set_layout(&mut self, layout: &TableLayout) {
+        self.layout = layout.clone();
+    }
+
+    pub fn set_hands(&mut self, hands: usize) {
+        self.hands = hands;
+    }
+
+    // Send a card from the shoe to the seat's next slot, a little after the cards still waiting to leave.
+    // A card that has already started moving doesn't hold the next one back.
+    pub fn deal(&mut self, seat: Seat) {
+        let slot = match seat {
+            Seat::Player(hand) => &mut self.player_slots[hand.min(MAX_HANDS - 1)],
+            Seat::Dealer => &mut self.dealer_slots,
+        };
+        let slot = std::mem::replace(slot, *slot + 1);
+        let waiting = self.flights.iter().filter(|flight| flight.delay > 0.0 || flight.tween.progress() == 0.0);
+        let delay = waiting.map(|flight| flight.delay.max(0.0) + DEAL_STAGGER).fold(0.0, f32::max);
+        self.flights.push(Flight { seat, slot, delay, tween: Tween::new(0.0, 1.0, DEAL_TIME, Easing::EaseOutCubic) });
+    }
+
+    // Slide a card picture from where it is now into the discard tray
+    pub fn discard(&mut self, texture: Texture2D, from: Vec2) {
+        self.discards.push(Discard { texture, from, tween: Tween::new(0.0, 1.0, DISCARD_TIME, Easing::EaseInOutQuad) });
+    }
+
+    // The cards have been picked up: land any flights still going and start again at slot 0
+    pub fn clear_table(&mut self, player_cards: &mut [Vec<StillImage>], dealer_cards: &mut [StillImage]) {
+        for flight in self.flights.iter_mut() {
+            flight.delay = 0.0;
+            flight.tween.finish();
+        }
+        self.apply(player_cards, dealer_cards);
+        self.player_slots = [0; MAX_HANDS];
+        self.dealer_slots = 0;
+    }
+
+    pub fn is_busy(&self) -> bool {
+        !self.flights.is_empty() || !self.discards.is_empty()
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        for flight in self.flights.iter_mut() {
+            // With reduced motion (or instant animations) every card lands at once instead of one after another
+            if flight.delay > 0.0 && !motion_skipped() {
+                flight.delay -= scaled_time(dt);
+            } else {
+                flight.delay = 0.0;
+                flight.tween.update(dt);
+            }
+        }
+        for discard in self.discards.iter_mut() {
+            discard.tween.update(dt);
+        }
+        self.discards.retain(|discard| !discard.tween.is_finished());
+    }
+
+    // Move the flying card images. Cards still waiting in the shoe are hidden.
+    pub fn apply(&mut self, player_cards: &mut [Vec<StillImage>], dealer_cards: &mut [StillImage]) {
+        let layout = &self.layout;
+        let start = area_center(layout.shoe) - vec2(layout.card_width, layout.card_height) / 2.0;
+        for flight in self.flights.iter() {
+            let (card, target) = match flight.seat {
+                Seat::Player(hand) => (
+                    player_cards.get_mut(hand).and_then(|cards| cards.get_mut(flight.slot)),
+                    layout.hand_card_pos(hand, self.hands, flight.slot),
+                ),
+                Seat::Dealer => (dealer_cards.get_mut(flight.slot), layout.dealer_card_pos(flight.slot)),
+            };
+            let Some(card) = card else {
+                continue;
+            };
+            if flight.delay > 0.0 {
+                card.set_size(0.0, 0.0);
+            } else {
+                card.set_size(layout.card_width, layout.card_height);
+                card.set_position(start.lerp(target, flight.tween.value()));
+            }
+        }
+        self.flights.retain(|flight| !flight.tween.is_finished());
+    }
+
+    // Queue the shoe and the discard tray, sized from the shoe's counts. Draw before the cards.
+    pub fn draw_shoe(&self, shoe: &Shoe) {
+        let cards_on_table = self.player_slots.iter().sum::<usize>() + self.dealer_slots;
+        let total = (shoe.decks() * 52) as f32;
+        let in_shoe = shoe.remaining() as f32 / total;
+        // Cards still sliding over are drawn on their own until they land
+        let in_tray = shoe.dealt().saturating_sub(cards_on_table + self.discards.len()) as f32 / total;
+        let (shoe_area, tray_area) = (self.layout.shoe, self.layout.discard_tray);
+        layers::push(Layer::Table, move || {
+            draw_holder(shoe_area, "Shoe", in_shoe, MAROON);
+            draw_holder(tray_area, "Discards", in_tray, GRAY);
+        });
+    }
+
+    // Queue the cards sliding into the tray. Draw after the cards so they pass over them.
+    pub fn draw_discards(&self) {
+        let (width, height) = (self.layout.card_width, self.layout.card_height);
+        let target = area_center(self.layout.discard_tray) - vec2(width, height) / 2.0;
+        for discard in self.discards.iter() {
+            let t = discard.tween.value();
+            let pos = discard.from.lerp(target, t);
+            let texture = discard.texture.clone();
+            // Shrink a little and turn sideways as it drops into the tray
+            let scale = 1.0 - 0.3 * t;
+            let rotation = t * std::f32::consts::FRAC_PI_2;
+            layers::push(Layer::Table, move || {
+                draw_texture_ex(
+                    &texture,
+                    pos.x + width * (1.0 - scale) / 2.0,
+                    pos.y + height * (1.0 - scale) / 2.0,
+                    WHITE,
+                    DrawTextureParams {
+                        dest_size: Some(vec2(width * scale, height * scale)),
+                        rotation,
+                        ..Default::default()
+                    },
+                );
+            });
+        }
+    }
+}
+
+impl EventListener for ShoeView {
+    fn on_event(&mut self, event: &GameEvent) {
+        if let GameEvent::CardDealt { seat, .. } = event {
+            self.deal(*seat);
+        }
+    }
+}
+
+fn area_center(area: Area) -> Vec2 {
+    vec2(area.x + area.w / 2.0, area.y + area.h / 2.0)
+}
+
+// A box holding a stack of cards seen from the side, `fill` (0 - 1) of its full height
+fn draw_holder(area: Area, caption: &str, fill: f32, back_color: Color) {
+    draw_rectangle(area.x, area.y, area.w, area.h, Color::new(0.25, 0.15, 0.08, 1.0));
+    let inner = Area { x: area.x + 8.0, y: area.y + 8.0, w: area.w - 16.0, h: area.h - 30.0 };
+    let stack_height = inner.h * fill.clamp(0.0, 1.0);
+    if stack_height > 0.0 {
+        let top = inner.y + inner.h - stack_height;
+        draw_rectangle(inner.x, top, inner.w, stack_height, back_color);
+        // Card edges every few cards so it reads as a pile
+        let mut y = top + CARD_EDGE * 4.0;
+        while y < inner.y + inner.h {
+            draw_line(inner.x, y, inner.x + inner.w, y, 1.0, Color::new(1.0, 1.0, 1.0, 0.35));
+            y += CARD_EDGE * 4.0 + 2.0;
+        }
+        draw_rectangle_lines(inner.x, top, inner.w, stack_height, 2.0, WHITE);
+    }
+    draw_rectangle_lines(area.x, area.y, area.w, area.h, 3.0, GOLD);
+    let caption_width = measure_text_cached(caption, None, 20).width;
+    draw_text(caption, area.x + (area.w - caption_width) / 2.0, area.y + area.h - 6.0, 20.0, WHITE);
+}
@@ -0,0 +1,150 @@
+/*
+By: <tyler>
+Date: 2025-11-20
+Program Details: Riffle/swirl animation shown while the shoe is reshuffled
+
+Card backs fly out of the shoe, swirl around and land back in a neat stack,
+with a progress bar underneath. It starts when the shoe reports a reshuffle.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod animation;
+    pub mod shuffle_animation;
+
+Then add the following with the use commands:
+use crate::modules::shuffle_animation::ShuffleAnimation;
+
+Usage examples:
+1. Create it before the loop:
+    let mut shuffle_anim = ShuffleAnimation::new();
+
+2. Start it when the shoe is reshuffled, either by hand or through the event bus:
+    shuffle_anim.start();
+    bus.dispatch(&mut [&mut shuffle_anim]); // Starts on GameEvent::ShoeShuffled
+
+3. In the loop, update and draw it (centered on a point, drawn in the Overlay layer):
+    if shuffle_anim.is_running() {
+        shuffle_anim.update(get_frame_time());
+        shuffle_anim.draw(500.0, 350.0);
+    }
+*/
+use macroquad::prelude::*;
+use crate::modules::text_cache::measure_text_cached;
+use crate::modules::animation::{Easing, Tween};
+use crate::modules::events::{EventListener, GameEvent};
+use crate::modules::layers::{self, Layer};
+
+const CARD_COUNT: usize = 16;
+const CARD_WIDTH: f32 = 70.0;
+const CARD_HEIGHT: f32 = 100.0;
+const DURATION: f32 = 1.8;
+const STAGGER: f32 = 0.04; // Delay between each card starting to move
+
+pub struct ShuffleAnimation {
+    timer: Tween,
+    running: bool,
+}
+
+impl ShuffleAnimation {
+    pub fn new() -> Self {
+        let mut timer = Tween::new(0.0, DURATION, DURATION, Easing::Linear);
+        timer.finish();
+        Self { timer, running: false }
+    }
+
+    // Play the animation from the beginning
+    pub fn start(&mut self) {
+        self.timer.restart();
+        self.running = true;
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        if !self.running {
+            return;
+        }
+        self.timer.update(dt);
+        if self.timer.is_finished() {
+            self.running = false;
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    // Draw the swirling cards and the progress bar centered on (center_x, center_y), in the overlay layer
+    pub fn draw(&self, center_x: f32, center_y: f32) {
+        if !self.running {
+            return;
+        }
+        let elapsed = self.timer.value();
+        let progress = self.timer.progress();
+        layers::push(Layer::Overlay, move || draw_frame(elapsed, progress, center_x, center_y));
+    }
+}
+
+// One frame of the animation, elapsed seconds in
+fn draw_frame(elapsed: f32, progress: f32, center_x: f32, center_y: f32) {
+    let travel = DURATION - STAGGER * CARD_COUNT as f32;
+    let radius = 220.0;
+
+    for i in 0..CARD_COUNT {
+        // Each card leaves a little after the one before it
+        let t = ((elapsed - i as f32 * STAGGER) / travel).clamp(0.0, 1.0);
+        let start_angle = i as f32 / CARD_COUNT as f32 * std::f32::consts::TAU;
+
+        // First half: fly out of the stack, second half: spiral back in
+        let spread = if t < 0.5 {
+            Easing::EaseOutQuad.apply(t * 2.0)
+        } else {
+            1.0 - Easing::EaseInOutQuad.apply((t - 0.5) * 2.0)
+        };
+        let angle = start_angle + Easing::EaseInOutQuad.apply(t) * std::f32::consts::TAU * 1.5;
+        // Cards from the two halves of the riffle alternate sides
+        let side = if i % 2 == 0 { 1.0 } else { -1.0 };
+        let x = center_x + angle.cos() * radius * spread * side;
+        let y = center_y + angle.sin() * radius * 0.45 * spread;
+        // Settled cards stack slightly offset so the pile looks thick
+        let stack_offset = (1.0 - spread) * i as f32 * 0.8;
+        draw_card_back(x, y - stack_offset, angle * spread);
+    }
+
+    // Progress bar and caption
+    let bar_width = 300.0;
+    let bar_x = center_x - bar_width / 2.0;
+    let bar_y = center_y + CARD_HEIGHT / 2.0 + 40.0;
+    draw_rectangle(bar_x, bar_y, bar_width, 16.0, DARKGRAY);
+    draw_rectangle(bar_x, bar_y, bar_width * progress, 16.0, GOLD);
+    draw_rectangle_lines(bar_x, bar_y, bar_width, 16.0, 2.0, WHITE);
+    let caption = "Shuffling...";
+    let caption_width = measure_text_cached(caption, None, 30).width;
+    draw_text(caption, center_x - caption_width / 2.0, bar_y + 45.0, 30.0, WHITE);
+}
+
+impl EventListener for ShuffleAnimation {
+    fn on_event(&mut self, event: &GameEvent) {
+        if let GameEvent::ShoeShuffled { .. } = event {
+            self.start();
+        }
+    }
+}
+
+// Draw a simple card back centered on (x, y), rotated by `rotation` radians
+fn draw_card_back(x: f32, y: f32, rotation: f32) {
+    // White edge, maroon back, red inner panel
+    let layers = [(0.0, WHITE), (8.0, MAROON), (24.0, RED)];
+    for (inset, color) in layers {
+        draw_rectangle_ex(
+            x,
+            y,
+            CARD_WIDTH - inset,
+            CARD_HEIGHT - inset,
+            DrawRectangleParams {
+                offset: vec2(0.5, 0.5),
+                rotation,
+                color,
+            },
+        );
+    }
+}
@@ -0,0 +1,127 @@
+/*
+By: <tyler>
+Date: 2025-12-01
+Program Details: A horizontal slider for picking a value from 0.0 to 1.0
+
+Drag the knob (or click anywhere on the track) to change the value. The
+percentage is shown to the right of the track. Like TextButton it draws itself
+when it is updated, into the Hud layer unless told otherwise.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod layers;
+    pub mod draw;
+    pub mod input;
+    pub mod slider;
+
+Then add the following with the use commands:
+use crate::modules::slider::Slider;
+
+Usage examples:
+1. Create it above the loop (x, y, width, starting value):
+    let mut sld_music = Slider::new(100.0, 200.0, 250.0, 0.5);
+    sld_music.with_layer(Layer::Overlay);
+
+2. Move it, or set its value from somewhere else:
+    sld_music.update_position(150.0, 250.0);
+    sld_music.set_value(settings.music_volume);
+
+3. In the loop, draw it and react to changes:
+    if let Some(volume) = sld_music.update() {
+        settings.music_volume = volume;
+    }
+*/
+use macroquad::prelude::*;
+use crate::modules::draw::{draw_smooth_circle, draw_smooth_ring};
+use crate::modules::layers::{self, Layer};
+use crate::modules::cursor::{self, CursorKind};
+use crate::modules::input::{is_mouse_button_down, is_mouse_button_pressed};
+use crate::modules::scale::mouse_position_world;
+use crate::modules::widget_layout::Placeable;
+
+const TRACK_HEIGHT: f32 = 8.0;
+const KNOB_RADIUS: f32 = 12.0;
+
+pub struct Slider {
+    x: f32,
+    y: f32, // Middle of the track
+    width: f32,
+    value: f32,
+    dragging: bool,
+    layer: Layer,
+}
+
+impl Slider {
+    pub fn new(x: f32, y: f32, width: f32, value: f32) -> Self {
+        Self { x, y, width, value: value.clamp(0.0, 1.0), dragging: false, layer: Layer::Hud }
+    }
+
+    pub fn with_layer(&mut self, layer: Layer) -> &mut Self {
+        self.layer = layer;
+        self
+    }
+
+    pub fn update_position(&mut self, x: f32, y: f32) {
+        self.x = x;
+        self.y = y;
+    }
+
+    pub fn set_value(&mut self, value: f32) {
+        self.value = value.clamp(0.0, 1.0);
+    }
+
+    #[allow(unused)]
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    // Handle the mouse and draw, returns the new value when it changed this frame
+    pub fn update(&mut self) -> Option<f32> {
+        let (mouse_x, mouse_y) = mouse_position_world();
+        let grab_area = Rect::new(self.x - KNOB_RADIUS, self.y - KNOB_RADIUS, self.width + KNOB_RADIUS * 2.0, KNOB_RADIUS * 2.0);
+        let hovered = grab_area.contains(vec2(mouse_x, mouse_y));
+        if hovered && is_mouse_button_pressed(MouseButton::Left) {
+            self.dragging = true;
+        }
+        if !is_mouse_button_down(MouseButton::Left) {
+            self.dragging = false;
+        }
+        let mut changed = None;
+        if self.dragging {
+            let value = ((mouse_x - self.x) / self.width).clamp(0.0, 1.0);
+            if value != self.value {
+                self.value = value;
+                changed = Some(value);
+            }
+        }
+
+        let (x, y, width, value) = (self.x, self.y, self.width, self.value);
+        let knob_color = if hovered || self.dragging { GOLD } else { WHITE };
+        if self.dragging {
+            cursor::want(CursorKind::Grab);
+        } else if hovered {
+            cursor::want(CursorKind::Pointer);
+        }
+        layers::push(self.layer, move || {
+            draw_rectangle(x, y - TRACK_HEIGHT / 2.0, width, TRACK_HEIGHT, BLACK);
+            draw_rectangle(x, y - TRACK_HEIGHT / 2.0, width * value, TRACK_HEIGHT, DARKGREEN);
+            draw_rectangle_lines(x, y - TRACK_HEIGHT / 2.0, width, TRACK_HEIGHT, 1.0, GRAY);
+            draw_smooth_circle(x + width * value, y, KNOB_RADIUS, knob_color);
+            draw_smooth_ring(x + width * value, y, KNOB_RADIUS, 2.0, BLACK);
+            draw_text(&format!("{}%", (value * 100.0).round()), x + width + KNOB_RADIUS + 8.0, y + 8.0, 24.0, WHITE);
+        });
+        changed
+    }
+}
+
+// Only the track and knob count, the percentage hangs off the right end
+impl Placeable for Slider {
+    fn size(&self) -> Vec2 {
+        vec2(self.width, KNOB_RADIUS * 2.0)
+    }
+
+    fn place(&mut self, x: f32, y: f32) {
+        self.update_position(x, y + KNOB_RADIUS);
+    }
+}
@@ -0,0 +1,231 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Reads the important moments of a round out loud, for players who can't see the table well
+
+The Announcer listens to the game events and turns them into short sentences:
+    "You drew the Seven of Hearts, total fifteen"
+    "Dealer shows an Ace"
+    "Bust"
+    "You win. Dealer has nineteen"
+When more than one hand is played each hand's cards start with "Hand two:".
+Only the dealer's first card is read out, the second one is the hole card and
+saying it would give it away. Later dealer cards are covered by the total read
+out with the result.
+
+What is said goes to a SpeechBackend, which does the speaking:
+    web    - the browser's SpeechSynthesis (js/blackjack.js)
+    native - the first of `say` (macOS), `espeak-ng` or `espeak` that runs,
+             one sentence after another on a thread so the game never waits
+Any other backend can be plugged in with Announcer::with_backend. If nothing can
+speak the sentences are dropped.
+
+It is off unless the player turns on Settings::announce.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod cards;
+    pub mod events;
+    pub mod hand;
+    pub mod web_bridge;
+    pub mod speech;
+
+Then add the following with the use commands:
+use crate::modules::speech::Announcer;
+
+Usage examples:
+1. Create it before the loop and give it the game events:
+    let mut announcer = Announcer::new();
+    announcer.set_enabled(settings.announce);
+    bus.dispatch(&mut [&mut announcer]);
+
+2. Speak through something else:
+    struct Captions;
+    impl SpeechBackend for Captions {
+        fn speak(&mut self, text: &str) { println!("{}", text); }
+    }
+    let mut announcer = Announcer::new().with_backend(Box::new(Captions));
+
+3. Say something that isn't a game event:
+    announcer.say("Shuffling");
+*/
+use crate::modules::cards::{Card, Rank};
+use crate::modules::events::{EventListener, GameEvent, Outcome, Seat};
+use crate::modules::hand::{Hand, MAX_HANDS};
+
+// Something that can say a sentence out loud. It must not wait for the sentence to finish.
+pub trait SpeechBackend {
+    fn speak(&mut self, text: &str);
+}
+
+// The browser's speech synthesis
+#[cfg(target_arch = "wasm32")]
+pub struct WebSpeech;
+
+#[cfg(target_arch = "wasm32")]
+impl SpeechBackend for WebSpeech {
+    fn speak(&mut self, text: &str) {
+        crate::modules::web_bridge::speak(text);
+    }
+}
+
+// A command line speech program, run on its own thread
+#[cfg(not(target_arch = "wasm32"))]
+pub struct CommandSpeech {
+    sender: std::sync::mpsc::Sender<String>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl CommandSpeech {
+    // Programs tried in order, the first that runs is used from then on
+    const PROGRAMS: [&'static str; 3] = ["say", "espeak-ng", "espeak"];
+
+    pub fn new() -> Self {
+        let (sender, receiver) = std::sync::mpsc::channel::<String>();
+        std::thread::spawn(move || {
+            let mut program: Option<&str> = None;
+            let mut missing = false;
+            for text in receiver {
+                if missing {
+                    continue;
+                }
+                let run = |name: &str| std::process::Command::new(name).arg(&text).status().is_ok();
+                match program {
+                    Some(name) => {
+                        run(name);
+                    }
+                    None => {
+                        program = Self::PROGRAMS.into_iter().find(|name| run(name));
+                        if program.is_none() {
+                            crate::log_warn!("no speech program found (tried {}), announcements are off", Self::PROGRAMS.join(", "));
+                            missing = true;
+                        }
+                    }
+                }
+            }
+        });
+        Self { sender }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SpeechBackend for CommandSpeech {
+    fn speak(&mut self, text: &str) {
+        let _ = self.sender.send(text.to_string());
+    }
+}
+
+// The backend for this platform
+fn default_backend() -> Box<dyn SpeechBackend> {
+    #[cfg(target_arch = "wasm32")]
+    return Box::new(WebSpeech);
+    #[cfg(not(target_arch = "wasm32"))]
+    return Box::new(CommandSpeech::new());
+}
+
+pub struct Announcer {
+    enabled: bool,
+    backend: Option<Box<dyn SpeechBackend>>, // Made the first time something is said
+    hands: Vec<Hand>,                        // The player's cards this round, to say the totals
+    dealer_shown: bool,                      // The up card has been read out this round
+}
+
+impl Announcer {
+    pub fn new() -> Self {
+        Self { enabled: false, backend: None, hands: vec![Hand::new(); MAX_HANDS], dealer_shown: false }
+    }
+
+    // Speak through `backend` instead of the platform's own
+    #[allow(unused)]
+    pub fn with_backend(mut self, backend: Box<dyn SpeechBackend>) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    // Say something now, if announcements are on
+    pub fn say(&mut self, text: &str) {
+        if !self.enabled {
+            return;
+        }
+        self.backend.get_or_insert_with(default_backend).speak(text);
+    }
+
+    fn player_card(&mut self, hand: usize, card: Card) {
+        let Some(cards) = self.hands.get_mut(hand) else {
+            return;
+        };
+        cards.add(card);
+        let total = cards.value();
+        let soft = if cards.is_soft() { "soft " } else { "" };
+        // A hand number only helps when there is more than one hand
+        let which = if self.hands.iter().skip(1).any(|other| !other.is_empty()) {
+            format!("Hand {}: ", number_words(hand as u32 + 1))
+        } else {
+            String::new()
+        };
+        let text = format!("{}You drew {}, total {}{}", which, card_words(card), soft, number_words(total));
+        self.say(&text);
+    }
+}
+
+impl EventListener for Announcer {
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::CardDealt { card, seat: Seat::Player(hand) } => self.player_card(*hand, *card),
+            GameEvent::CardDealt { card, seat: Seat::Dealer } if !self.dealer_shown => {
+                self.dealer_shown = true;
+                self.say(&format!("Dealer shows {}", with_article(card.rank)));
+            }
+            GameEvent::PlayerBusted { .. } => self.say("Bust"),
+            GameEvent::RoundSettled { outcome, blackjack, dealer_total, .. } => {
+                let result = match outcome {
+                    Outcome::PlayerWin if *blackjack => "Blackjack, you win",
+                    Outcome::PlayerWin => "You win",
+                    Outcome::Push => "Push",
+                    Outcome::DealerWin | Outcome::NoWinner => "Dealer wins",
+                };
+                let dealer = if *dealer_total > 21 { "Dealer busts".to_string() } else { format!("Dealer has {}", number_words(*dealer_total)) };
+                self.say(&format!("{}. {}", result, dealer));
+            }
+            GameEvent::RoundFinished => {
+                self.hands.iter_mut().for_each(Hand::clear);
+                self.dealer_shown = false;
+            }
+            _ => {}
+        }
+    }
+}
+
+// "the Seven of Hearts"
+fn card_words(card: Card) -> String {
+    let suit = card.suit.name();
+    let mut suit_title = suit[..1].to_uppercase();
+    suit_title.push_str(&suit[1..]);
+    format!("the {} of {}", card.rank.name(), suit_title)
+}
+
+// "an Ace", "an Eight", "a Seven"
+fn with_article(rank: Rank) -> String {
+    let article = if matches!(rank, Rank::Ace | Rank::Eight) { "an" } else { "a" };
+    format!("{} {}", article, rank.name())
+}
+
+// Card totals as words, speech programs read "15" well enough but some say "one five"
+fn number_words(number: u32) -> String {
+    const ONES: [&str; 20] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven", "twelve", "thirteen",
+        "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+    ];
+    match number {
+        0..=19 => ONES[number as usize].to_string(),
+        20 => "twenty".to_string(),
+        21..=29 => format!("twenty {}", ONES[number as usize - 20]),
+        30 => "thirty".to_string(),
+        _ => number.to_string(),
+    }
+}
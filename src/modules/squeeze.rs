@@ -0,0 +1,154 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: The squeeze, a hit card that comes face down and is peeled over by dragging
+
+With the squeeze on, a hit card lands face down and waits. Pressing on it and
+dragging upward lifts its bottom edge: the further the drag, the more of the face
+shows (a whole card's height turns it over, see card_view::draw_peeled). Letting
+go past REVEAL_AT turns the rest of it over, letting go before that lays it back
+down to try again. A plain click on the card turns it straight over, so the
+squeeze never holds up a player who only wanted to hit.
+
+It only draws and reports when the card has been turned over, the game decides
+what that means (the card joins the hand then).
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod animation;
+    pub mod layers;
+    pub mod input;
+    pub mod cursor;
+    pub mod card_view;
+    pub mod squeeze;
+
+Then add the following with the use commands:
+use crate::modules::squeeze::Squeeze;
+
+Usage examples:
+1. Create it before the loop:
+    let mut squeeze = Squeeze::new();
+
+2. A card to squeeze, face down over its slot:
+    squeeze.start(tm.get_texture(&card.texture_path()));
+
+3. Every frame, follow the pointer and draw (true once the card is over):
+    if squeeze.update(slot.pos(), slot.size()) {
+        // the player has seen the card
+    }
+    squeeze.draw(slot.pos(), slot.size());
+*/
+use macroquad::prelude::*;
+use crate::modules::animation::{Easing, Tween};
+use crate::modules::card_view::draw_peeled;
+use crate::modules::cursor::{self, CursorKind};
+use crate::modules::input::pointer;
+use crate::modules::layers::{self, Layer};
+
+const REVEAL_AT: f32 = 0.6; // How far peeled the card must be to turn over on its own when let go
+const SETTLE_TIME: f32 = 0.25; // Turning the rest of the way over, or laying back down
+const CLICK_DISTANCE: f32 = 8.0; // Further than this and a press is a drag, not a click
+const GRAB_MARGIN: f32 = 16.0; // The card can be grabbed a little outside its edges
+
+// A press on the card that hasn't been let go yet
+struct Drag {
+    start: Vec2,
+    peel_at_start: f32,
+    moved: bool,
+}
+
+pub struct Squeeze {
+    front: Option<Texture2D>,
+    active: bool,
+    peel: f32, // 0.0 face down to 1.0 turned over
+    drag: Option<Drag>,
+    settle: Option<Tween>, // Carrying on by itself once let go
+    was_down: bool,
+}
+
+impl Squeeze {
+    pub fn new() -> Self {
+        Self { front: None, active: false, peel: 0.0, drag: None, settle: None, was_down: true }
+    }
+
+    // Lay a card face down to be squeezed
+    pub fn start(&mut self, front: Option<Texture2D>) {
+        *self = Self { front, active: true, ..Self::new() };
+    }
+
+    // A card is waiting to be turned over
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    // Drop the card without turning it, for when the table is cleared
+    pub fn cancel(&mut self) {
+        self.active = false;
+    }
+
+    // Follow the pointer over the card at `pos`, returns true once when it has been turned over
+    pub fn update(&mut self, pos: Vec2, size: Vec2) -> bool {
+        let (pointer_pos, down) = pointer();
+        let pressed = down && !self.was_down;
+        self.was_down = down;
+        if !self.active {
+            return false;
+        }
+        let area = Rect::new(pos.x - GRAB_MARGIN, pos.y - GRAB_MARGIN, size.x + GRAB_MARGIN * 2.0, size.y + GRAB_MARGIN * 2.0);
+        let hovered = area.contains(pointer_pos);
+        match &mut self.drag {
+            None if pressed && hovered && self.settle.is_none() => {
+                self.drag = Some(Drag { start: pointer_pos, peel_at_start: self.peel, moved: false });
+            }
+            Some(drag) if down => {
+                if drag.start.distance(pointer_pos) > CLICK_DISTANCE {
+                    drag.moved = true;
+                }
+                // Up is peeling, a whole card's height turns it over
+                let lifted = (drag.start.y - pointer_pos.y) / size.y.max(1.0);
+                self.peel = (drag.peel_at_start + lifted).clamp(0.0, 1.0);
+            }
+            Some(drag) => {
+                let target = if !drag.moved || self.peel >= REVEAL_AT { 1.0 } else { 0.0 };
+                self.settle = Some(Tween::new(self.peel, target, SETTLE_TIME * (target - self.peel).abs(), Easing::EaseOutQuad));
+                self.drag = None;
+            }
+            None => {}
+        }
+        if self.drag.is_some() {
+            cursor::want(CursorKind::Grab);
+        } else if hovered {
+            cursor::want(CursorKind::Pointer);
+        }
+
+        if let Some(settle) = self.settle.as_mut() {
+            settle.update(get_frame_time());
+            self.peel = settle.value();
+            if settle.is_finished() {
+                self.settle = None;
+            }
+        }
+        if self.peel >= 1.0 && self.drag.is_none() && self.settle.is_none() {
+            self.active = false;
+            return true;
+        }
+        false
+    }
+
+    // Queue the card as far peeled as it is, over its slot
+    pub fn draw(&self, pos: Vec2, size: Vec2) {
+        if !self.active || size.x <= 0.0 {
+            return;
+        }
+        let front = self.front.clone();
+        let peel = self.peel;
+        let hint = self.drag.is_none() && self.settle.is_none() && peel == 0.0;
+        layers::push(Layer::Table, move || {
+            draw_peeled(front.as_ref(), peel, pos, size);
+            if hint {
+                draw_text("Drag up to squeeze", pos.x - 10.0, pos.y - 8.0, 20.0, WHITE);
+            }
+        });
+    }
+}
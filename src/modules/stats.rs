@@ -0,0 +1,215 @@
+/*
+By: <tyler>
+Date: 2025-11-21
+Program Details: Win/loss statistics
+
+Stats listens to the event bus, so the game code never has to update the
+counters itself.
+
+Every settled hand is also added to a breakdown grid: one row for each kind of
+starting hand (hard totals, soft totals and pairs, see START_ROWS) and one
+column for each dealer up card (UP_CARDS). Each cell keeps how many hands
+were played there and what they won or lost, so the player can see where the
+money actually goes.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod events;
+    pub mod stats;
+
+Then add the following with the use commands:
+use crate::modules::stats::Stats;
+
+Usage examples:
+1. Create it before the loop:
+    let mut stats = Stats::default();
+
+2. Pass it to the event bus every frame:
+    bus.dispatch(&mut [&mut stats]);
+
+3. Read the numbers:
+    lbl_wins.set_text(format!("{}", stats.player_wins));
+
+4. Streaks (a push doesn't end one):
+    if stats.is_hot() {
+        lbl_streak.set_text(format!("{} wins in a row", stats.win_streak()));
+    }
+
+5. The breakdown: a hard 16 against a dealer's 10, and everything against a 10:
+    let cell = stats.cell(7, 8);
+    let column = stats.column_total(8);
+    println!("{} hands, {:+}", cell.hands, cell.net);
+*/
+use nanoserde::{DeJson, SerJson};
+use crate::modules::cards::Card;
+use crate::modules::events::{EventListener, GameEvent, Outcome, Seat};
+
+// Wins or losses in a row before the table calls it a hot or cold streak
+pub const STREAK_THRESHOLD: u32 = 3;
+
+// The breakdown's rows: the player's first two cards. Hard totals first, then an ace
+// and another card, then pairs (any two ten-cards count as a pair of tens).
+pub const START_ROWS: [&str; 29] = [
+    "Hard 5-8", "Hard 9", "Hard 10", "Hard 11", "Hard 12", "Hard 13", "Hard 14", "Hard 15", "Hard 16", "Hard 17-19",
+    "A,2", "A,3", "A,4", "A,5", "A,6", "A,7", "A,8", "A,9", "Blackjack",
+    "2,2", "3,3", "4,4", "5,5", "6,6", "7,7", "8,8", "9,9", "10,10", "A,A",
+];
+
+// The breakdown's columns: the dealer's up card
+pub const UP_CARDS: [&str; 10] = ["2", "3", "4", "5", "6", "7", "8", "9", "10", "A"];
+
+// Hands played in one cell of the breakdown and what they came to
+#[derive(SerJson, DeJson, Default, Clone, Copy, Debug, PartialEq)]
+#[nserde(default)]
+pub struct Tally {
+    pub hands: u32,
+    pub net: i64,
+}
+
+impl Tally {
+    fn add(&mut self, other: Tally) {
+        self.hands += other.hands;
+        self.net += other.net;
+    }
+}
+
+#[derive(SerJson, DeJson, Default, Clone, Debug)]
+#[nserde(default)]
+pub struct Stats {
+    pub hands_played: u32,
+    pub player_wins: u32,
+    pub dealer_wins: u32,
+    pub pushes: u32,
+    pub player_busts: u32,
+    pub streak: i32,          // Wins in a row if positive, losses in a row if negative
+    pub best_win_streak: u32, // Longest ever
+    pub low_bankroll_warnings: u32, // Times the bankroll ran low (see low_bankroll.rs)
+    #[nserde(skip)]
+    pub session_best_streak: u32, // Longest since the game was started
+    pub breakdown: Vec<Tally>,    // START_ROWS by UP_CARDS, a row at a time. Empty until a hand is added.
+    #[nserde(skip)]
+    starting: Vec<Vec<Card>>, // Each hand's first two cards this round
+    #[nserde(skip)]
+    up_card: Option<Card>,    // The dealer's first card this round
+}
+
+impl Stats {
+    pub fn win_streak(&self) -> u32 {
+        self.streak.max(0) as u32
+    }
+
+    pub fn loss_streak(&self) -> u32 {
+        (-self.streak).max(0) as u32
+    }
+
+    pub fn is_hot(&self) -> bool {
+        self.win_streak() >= STREAK_THRESHOLD
+    }
+
+    pub fn is_cold(&self) -> bool {
+        self.loss_streak() >= STREAK_THRESHOLD
+    }
+
+    // One cell of the breakdown
+    pub fn cell(&self, row: usize, column: usize) -> Tally {
+        self.breakdown.get(row * UP_CARDS.len() + column).copied().unwrap_or_default()
+    }
+
+    // A starting hand against every up card
+    pub fn row_total(&self, row: usize) -> Tally {
+        let mut total = Tally::default();
+        (0..UP_CARDS.len()).for_each(|column| total.add(self.cell(row, column)));
+        total
+    }
+
+    // Every starting hand against one up card
+    pub fn column_total(&self, column: usize) -> Tally {
+        let mut total = Tally::default();
+        (0..START_ROWS.len()).for_each(|row| total.add(self.cell(row, column)));
+        total
+    }
+
+    // Add a settled hand to the breakdown, if its first cards and the up card were seen
+    fn record_breakdown(&mut self, hand: usize, change: i64) {
+        let (Some([first, second]), Some(up)) = (self.starting.get(hand).and_then(|cards| cards.first_chunk()), self.up_card) else {
+            return;
+        };
+        let index = start_row(*first, *second) * UP_CARDS.len() + up_column(up);
+        self.breakdown.resize(START_ROWS.len() * UP_CARDS.len(), Tally::default());
+        self.breakdown[index].add(Tally { hands: 1, net: change });
+    }
+
+    fn record_win(&mut self) {
+        self.streak = self.streak.max(0) + 1;
+        self.best_win_streak = self.best_win_streak.max(self.win_streak());
+        self.session_best_streak = self.session_best_streak.max(self.win_streak());
+    }
+
+    fn record_loss(&mut self) {
+        self.streak = self.streak.min(0) - 1;
+    }
+}
+
+impl EventListener for Stats {
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::CardDealt { card, seat: Seat::Player(hand) } => {
+                if self.starting.len() <= *hand {
+                    self.starting.resize(hand + 1, Vec::new());
+                }
+                if self.starting[*hand].len() < 2 {
+                    self.starting[*hand].push(*card);
+                }
+            }
+            GameEvent::CardDealt { card, seat: Seat::Dealer } => {
+                self.up_card.get_or_insert(*card);
+            }
+            GameEvent::RoundSettled { hand, outcome, change, .. } => {
+                self.hands_played += 1;
+                self.record_breakdown(*hand, *change);
+                match outcome {
+                    Outcome::PlayerWin => {
+                        self.player_wins += 1;
+                        self.record_win();
+                    }
+                    Outcome::DealerWin => {
+                        self.dealer_wins += 1;
+                        self.record_loss();
+                    }
+                    Outcome::Push => self.pushes += 1,
+                    Outcome::NoWinner => {}
+                }
+            }
+            GameEvent::PlayerBusted { .. } => self.player_busts += 1,
+            GameEvent::LowBankroll { .. } => self.low_bankroll_warnings += 1,
+            GameEvent::RoundFinished => {
+                self.starting.clear();
+                self.up_card = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+// Which of START_ROWS two cards are
+fn start_row(first: Card, second: Card) -> usize {
+    let (a, b) = (first.value(), second.value());
+    if a == b {
+        return 19 + (a - 2) as usize;
+    }
+    if a == 11 || b == 11 {
+        let other = a + b - 11;
+        return 10 + (other - 2) as usize;
+    }
+    match a + b {
+        ..=8 => 0,
+        total @ 9..=16 => (total - 8) as usize,
+        _ => 9,
+    }
+}
+
+// Which of UP_CARDS a card is, aces last
+fn up_column(card: Card) -> usize {
+    (card.value().clamp(2, 11) - 2) as usize
+}
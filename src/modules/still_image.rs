@@ -61,31 +61,56 @@ Usage examples:
     
 6. Draw the image in your game loop:
     img.draw();
+   The image is drawn into the Table layer (needs pub mod layers; and pub mod tooltip; in mod.rs),
+   so call layers::flush() at the end of the loop. To use another layer:
+    img.set_layer(Layer::Overlay);
 
 Additional functionality:
 - Zoom controls: set_zoom(), zoom_in(), zoom_out(), reset_zoom()
 - Stretch controls: enable_stretch(), disable_stretch(), toggle_stretch()
-- Position control: set_position()
+- Position and size control: set_position(), set_size()
+- Smooth movement: move_to() glides to a spot using an easing curve (needs pub mod animation;),
+  call update() every frame to move it along:
+    img.move_to(vec2(300.0, 400.0), 0.4, Easing::EaseInOutQuad);
+    img.update(get_frame_time());
+  set_position(), set_x() and set_y() snap straight there and cancel any move in progress.
 - Check if empty: is_empty()
+- Snapshots: compose() draws several images, each at its own position, into a new texture
+  once, so a group of them can be shown small (or often) without laying them out again:
+    let thumbnail = compose(&[first, second], 300.0, 200.0, DARKGREEN);
+  draw_now() draws one image straight away instead of queuing it, for drawing into a texture.
+- Hit testing: contains_point() is true over the parts of the picture that can be seen,
+  so clicks on the transparent corners of a rounded picture go through:
+    if img.contains_point(mouse_position_world().into()) { ... }
+  The transparency mask it uses is only worked out the first time it is needed, from the
+  texture itself, and shared by every image showing the same texture from the TextureManager
+  (they all hold the same Arc<TextureEntry>, needs pub mod preload_image;).
+  Textures loaded with masks turned off in the manifest (the cards) hit test their whole rectangle.
 */
+use std::sync::{Arc, OnceLock};
 use macroquad::prelude::*;
 use macroquad::texture::Texture2D;
+use crate::modules::animation::{Easing, Tween};
+use crate::modules::layers::{self, Layer};
+use crate::modules::preload_image::TextureEntry;
+use crate::log_error;
 
 pub struct StillImage {
-    texture: Texture2D,
+    picture: Arc<TextureEntry>, // The texture, its mask and its filename/path, shared with the TextureManager
     x: f32,
     y: f32,
     width: f32,
     height: f32,
-    transparency_mask: Option<Vec<u8>>, // Changed to Option<Vec<u8>> to make it optional
     stretch_enabled: bool, // Flag to control image stretching
     zoom_level: f32, // Zoom factor to scale the image
-    filename: String, // Store the original filename/path
     angle: f32, // Angle of rotation
+    layer: Layer, // Draw layer
+    movement: Option<(Vec2, Vec2, Tween)>, // From, to and progress of a move_to
 }
 
 impl StillImage {
     // Constructor for ImageStill with asset path and x, y location
+    #[allow(unused)]
     pub async fn new(
         asset_path: &str, 
         width: f32, 
@@ -99,63 +124,83 @@ impl StillImage {
         if asset_path.is_empty() {
             // Create an empty/clear image
             let empty_texture = Texture2D::from_rgba8(1, 1, &[0, 0, 0, 0]);
-            let empty_mask = Some(vec![0]); // Single transparent pixel
             
             return Self { 
                 x, 
                 y, 
                 width, 
                 height, 
-                texture: empty_texture, 
-                transparency_mask: empty_mask,
+                picture: TextureEntry::new(empty_texture, TransparencyMask::lazy(), "__empty__"), // Use a special filename
                 stretch_enabled,
                 zoom_level: zoom_level.max(0.1), // Ensure minimum zoom
-                filename: "__empty__".to_string(), // Use a special filename
                 angle: 0.0, // Default angle
+                layer: Layer::Table,
+                movement: None,
             };
         }
         
         // Normal path for valid asset paths
-        let (texture, transparency_mask) = set_texture_main(asset_path).await;
+        let texture = set_texture_main(asset_path).await;
         Self { 
             x, 
             y, 
             width, 
             height, 
-            texture, 
-            transparency_mask,
+            picture: TextureEntry::new(texture, TransparencyMask::lazy(), asset_path), // Store the original filename
             stretch_enabled,
             zoom_level: zoom_level.max(0.1), // Ensure minimum zoom
-            filename: asset_path.to_string(), // Store the original filename
             angle: 0.0, // Default angle
+            layer: Layer::Table,
+            movement: None,
+        }
+    }
+
+    // An image showing a texture that is already loaded (from the TextureManager), no loading or waiting
+    #[allow(unused)]
+    pub fn from_preload(picture: Arc<TextureEntry>, width: f32, height: f32) -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            width,
+            height,
+            picture,
+            stretch_enabled: true,
+            zoom_level: 1.0,
+            angle: 0.0,
+            layer: Layer::Table,
+            movement: None,
         }
     }
 
     // Method to draw the image with current settings
     pub fn draw(&self) {
-        // Get the size to use for drawing
-        let (draw_width, draw_height) = if self.stretch_enabled {
-            (self.width, self.height)
-        } else {
-            // Use original texture size when stretch is disabled
-            (self.texture.width(), self.texture.height())
-        };
-        
-        // Apply zoom factor
-        let final_width = draw_width * self.zoom_level;
-        let final_height = draw_height * self.zoom_level;
-        
-        draw_texture_ex(
-            &self.texture,
-            self.x,
-            self.y,
-            WHITE,
-            DrawTextureParams {
-                rotation: self.angle,
-                dest_size: Some(vec2(final_width, final_height)),
-                ..Default::default()
-            },
-        );
+        // Queue it into the image's layer
+        let (picture, x, y, angle, size) = (self.picture.clone(), self.x, self.y, self.angle, self.size());
+        layers::push(self.layer, move || {
+            draw_texture_ex(
+                &picture.texture,
+                x,
+                y,
+                WHITE,
+                DrawTextureParams {
+                    rotation: angle,
+                    dest_size: Some(size),
+                    ..Default::default()
+                },
+            );
+        });
+    }
+
+    // Draw straight away instead of queuing into the layer, for drawing into a texture (see compose)
+    pub fn draw_now(&self) {
+        let params = DrawTextureParams { rotation: self.angle, dest_size: Some(self.size()), ..Default::default() };
+        draw_texture_ex(&self.picture.texture, self.x, self.y, WHITE, params);
+    }
+
+    // Pick the draw layer (Table unless changed)
+    #[allow(unused)]
+    pub fn set_layer(&mut self, layer: Layer) {
+        self.layer = layer;
     }
 
     // Accessors for image properties
@@ -168,19 +213,51 @@ impl StillImage {
         let (width, height) = if self.stretch_enabled {
             (self.width, self.height)
         } else {
-            (self.texture.width(), self.texture.height())
+            (self.picture.texture.width(), self.picture.texture.height())
         };
         
         vec2(width * self.zoom_level, height * self.zoom_level)
     }
     #[allow(unused)]
     pub fn texture_size(&self) -> Vec2 {
-        vec2(self.texture.width(), self.texture.height())
+        vec2(self.picture.texture.width(), self.picture.texture.height())
     }
     #[allow(unused)]
     pub fn set_position(&mut self, pos: Vec2) {
         self.x = pos[0];
         self.y = pos[1];
+        self.movement = None;
+    }
+    // Glide from where the image is now to `target` over `duration` seconds (call update() every frame)
+    #[allow(unused)]
+    pub fn move_to(&mut self, target: Vec2, duration: f32, easing: Easing) {
+        if duration <= 0.0 {
+            self.set_position(target);
+            return;
+        }
+        self.movement = Some((self.pos(), target, Tween::new(0.0, 1.0, duration, easing)));
+    }
+    // Move along any move_to in progress
+    #[allow(unused)]
+    pub fn update(&mut self, dt: f32) {
+        let Some((from, to, tween)) = self.movement.as_mut() else {
+            return;
+        };
+        tween.update(dt);
+        let pos = from.lerp(*to, tween.value());
+        (self.x, self.y) = (pos.x, pos.y);
+        if tween.is_finished() {
+            self.movement = None;
+        }
+    }
+    #[allow(unused)]
+    pub fn is_moving(&self) -> bool {
+        self.movement.is_some()
+    }
+    #[allow(unused)]
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        self.width = width;
+        self.height = height;
     }
     #[allow(unused)]
     pub fn set_angle(&mut self, x: f32) {
@@ -199,6 +276,7 @@ impl StillImage {
     #[allow(unused)]
     pub fn set_x(&mut self, x: f32) {
         self.x = x;
+        self.movement = None;
     }
 
     // Get and set y position
@@ -210,26 +288,52 @@ impl StillImage {
     #[allow(unused)]
     pub fn set_y(&mut self, y: f32) {
         self.y = y;
+        self.movement = None;
     }
 
     // Get the original filename/path of the loaded image
     #[allow(unused)]
     pub fn get_filename(&self) -> &str {
-        &self.filename
+        &self.picture.path
     }
 
-    // Get the transparency mask (bitmask)
+    // Get the transparency mask (bitmask), working it out if this is the first time
     #[allow(unused)]
-    pub fn get_mask(&self) -> Option<Vec<u8>> {
-        self.transparency_mask.clone()
+    pub fn get_mask(&self) -> Option<&[u8]> {
+        self.picture.mask.get(&self.picture.texture)
+    }
+
+    // True if the point is over a part of the image that isn't fully transparent
+    #[allow(unused)]
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        let size = self.size();
+        if size.x <= 0.0 || size.y <= 0.0 {
+            return false;
+        }
+        // Turn the point back the other way around the middle, where the drawing rotates the image
+        let center = vec2(self.x, self.y) + size / 2.0;
+        let local = Vec2::from_angle(-self.angle).rotate(point - center) + size / 2.0;
+        if local.x < 0.0 || local.y < 0.0 || local.x >= size.x || local.y >= size.y {
+            return false;
+        }
+        let Some(bits) = self.get_mask() else {
+            return true;
+        };
+        let tex_width = self.picture.texture.width() as usize;
+        let tex_height = self.picture.texture.height() as usize;
+        let pixel_x = ((local.x / size.x * tex_width as f32) as usize).min(tex_width - 1);
+        let pixel_y = ((local.y / size.y * tex_height as f32) as usize).min(tex_height - 1);
+        let index = pixel_y * tex_width + pixel_x;
+        bits.get(index / 8).is_some_and(|byte| byte & (1 << (7 - index % 8)) != 0)
     }
 
     #[allow(unused)]
     pub async fn set_texture(&mut self, texture_path: &str) {
-        let (texture, transparency_mask) = set_texture_main(texture_path).await;
-        self.texture = texture;
-        self.transparency_mask = transparency_mask;
-        self.filename = texture_path.to_string(); // Update the filename when texture changes
+        let texture = set_texture_main(texture_path).await;
+        self.picture = TextureEntry::new(texture, TransparencyMask::lazy(), texture_path); // Update the filename when texture changes
     }
     
     // Methods to toggle stretching
@@ -293,7 +397,7 @@ impl StillImage {
     // Check if the image is currently cleared/empty
     #[allow(unused)]
     pub fn is_empty(&self) -> bool {
-        self.texture.width() == 1.0 && self.texture.height() == 1.0
+        self.picture.texture.width() == 1.0 && self.picture.texture.height() == 1.0
     }
     
     // Check if collision should be performed (not empty)
@@ -302,13 +406,11 @@ impl StillImage {
         !self.is_empty()
     }
     
-    // Public method for setting a preloaded texture that accepts the tuple directly
+    // Show a preloaded texture, straight from get_preload(). Only the handle is kept, nothing is copied,
+    // so images that change picture often (card slots) can call it freely.
     #[allow(unused)]
-    pub fn set_preload(&mut self, preloaded: (Texture2D, Option<Vec<u8>>, String)) {
-        let (texture, mask, filename) = preloaded;
-        self.texture = texture;
-        self.transparency_mask = mask;
-        self.filename = filename;
+    pub fn set_preload(&mut self, preloaded: Arc<TextureEntry>) {
+        self.picture = preloaded;
     }
 
     /// Clears the image by setting it to a 1x1 transparent pixel
@@ -316,12 +418,9 @@ impl StillImage {
     pub fn clear(&mut self) {
         // Create a 1x1 transparent pixel texture
         let empty_texture = Texture2D::from_rgba8(1, 1, &[0, 0, 0, 0]);
-        let empty_mask = Some(vec![0]); // Single transparent pixel
         
         // Update the image object with this empty texture
-        self.texture = empty_texture;
-        self.transparency_mask = empty_mask;
-        self.filename = "__empty__".to_string();
+        self.picture = TextureEntry::new(empty_texture, TransparencyMask::lazy(), "__empty__");
     }
 
     /// Method to set a new image
@@ -331,10 +430,47 @@ impl StillImage {
     }
 }
 
-async fn generate_mask(texture_path: &str, width: usize, height: usize) -> Option<Vec<u8>> {
-    let image = load_image(texture_path).await.unwrap();
-    let pixels = image.bytes; // Image pixels in RGBA8 format
-    
+// A texture's transparency mask, only worked out the first time something asks for it.
+// It lives in the texture's TextureEntry, so it is worked out once for every image showing the texture.
+#[derive(Debug)]
+pub struct TransparencyMask {
+    wanted: bool, // Off for textures that never need one, they hit test as a rectangle
+    bits: OnceLock<Option<Vec<u8>>>,
+}
+
+impl TransparencyMask {
+    // Worked out from the texture on first use
+    pub fn lazy() -> Self {
+        Self { wanted: true, bits: OnceLock::new() }
+    }
+
+    // Never worked out, the whole rectangle counts
+    pub fn none() -> Self {
+        Self { wanted: false, bits: OnceLock::new() }
+    }
+
+    // The mask for `texture` (the one this mask belongs to), None if it has no transparency or isn't wanted.
+    // The first call reads the pixels back from the texture, so it is slow once.
+    pub fn get(&self, texture: &Texture2D) -> Option<&[u8]> {
+        if !self.wanted {
+            return None;
+        }
+        self.bits
+            .get_or_init(|| {
+                let image = texture.get_texture_data();
+                mask_from_pixels(&image.bytes, image.width as usize, image.height as usize)
+            })
+            .as_deref()
+    }
+
+    // Bytes held once it has been worked out, 0 before
+    pub fn memory_bytes(&self) -> usize {
+        self.bits.get().and_then(|bits| bits.as_ref()).map_or(0, |bits| bits.len())
+    }
+}
+
+// One bit per pixel, set where the pixel isn't fully transparent. None when nothing is transparent.
+fn mask_from_pixels(pixels: &[u8], width: usize, height: usize) -> Option<Vec<u8>> {
     // Check if the image format has an alpha channel at all (RGBA)
     // If pixels length isn't divisible by 4, it's not RGBA format
     if pixels.len() != width * height * 4 {
@@ -366,7 +502,7 @@ async fn generate_mask(texture_path: &str, width: usize, height: usize) -> Optio
         return None;
     }
  // Only create the mask if we know the image has transparency
- let mut mask = vec![0; (width * height + 7) / 8]; // Create a bitmask with enough bytes
+ let mut mask = vec![0; (width * height).div_ceil(8)]; // Create a bitmask with enough bytes
     // Otherwise, create the transparency mask
     for y in 0..height {
         for x in 0..width {
@@ -388,12 +524,55 @@ async fn generate_mask(texture_path: &str, width: usize, height: usize) -> Optio
     Some(mask)
 }
 
-pub async fn set_texture_main(texture_path: &str) -> (Texture2D, Option<Vec<u8>>) {
-    let texture = load_texture(texture_path).await.unwrap();
-    texture.set_filter(FilterMode::Linear);
-    let tex_width = texture.width() as usize;
-    let tex_height = texture.height() as usize;
-    let transparency_mask = generate_mask(texture_path, tex_width, tex_height).await;
-    return (texture, transparency_mask);
+// Draw images, each at its own position, into a new `width` by `height` texture, on top of
+// `background`. Whatever camera was in use is left as it was.
+pub fn compose(images: &[StillImage], width: f32, height: f32, background: Color) -> Texture2D {
+    let target = render_target(width as u32, height as u32);
+    target.texture.set_filter(FilterMode::Linear);
+    push_camera_state();
+    // y grows downwards like the screen, a render target is stored bottom row first
+    set_camera(&Camera2D {
+        zoom: vec2(2.0 / width, 2.0 / height),
+        target: vec2(width / 2.0, height / 2.0),
+        render_target: Some(target.clone()),
+        ..Default::default()
+    });
+    clear_background(background);
+    for image in images {
+        image.draw_now();
+    }
+    pop_camera_state();
+    target.texture
 }
 
+pub async fn set_texture_main(texture_path: &str) -> Texture2D {
+    try_set_texture_main(texture_path).await.unwrap_or_else(|err| {
+        log_error!("could not load {}: {}", texture_path, err);
+        panic!("could not load {}: {}", texture_path, err)
+    })
+}
+
+// Same as set_texture_main, but hands back the error instead of panicking.
+// Textures come back with linear filtering, the TextureManager applies any other options.
+// The transparency mask isn't made here, see TransparencyMask.
+pub async fn try_set_texture_main(texture_path: &str) -> Result<Texture2D, macroquad::Error> {
+    load_texture(texture_path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::bench;
+
+    #[test]
+    #[ignore]
+    fn bench_mask_generation() {
+        let image = Image::from_file_with_format(include_bytes!("../../assets/Ace-of-spades.png"), Some(ImageFormat::Png)).unwrap();
+        let (width, height) = (image.width as usize, image.height as usize);
+        bench::run("card masks", 1, || mask_from_pixels(&image.bytes, width, height));
+        // The worst case, transparency only in the last pixel so the whole picture is scanned and then masked
+        let mut pixels = vec![255; width * height * 4];
+        pixels[width * height * 4 - 1] = 0;
+        bench::run("card masks, last pixel transparent", 1, || mask_from_pixels(&pixels, width, height));
+    }
+}
@@ -0,0 +1,436 @@
+/*
+By: <tyler>
+Date: 2025-11-23
+Program Details: Key/value storage for saving the game between runs
+
+Every saved thing (the profile list, each profile's stats, settings, ...) lives
+under its own key, and the value is a JSON string. Where it actually goes is up
+to a StorageBackend:
+- FileBackend: each key is a file in the save folder (save/<key>.json), native only
+- LocalStorageBackend: each key is a localStorage entry ("blackjack.<key>"), web only
+- HttpBackend: each key is a URL under a REST endpoint (GET/PUT/DELETE <url>/<key>)
+
+The game always saves to the local backend. If a sync URL is set up, every save
+is also sent to an HttpBackend, and loads compare both copies. Each value is
+stored with the time it was written, and the newest copy wins (last write wins);
+the older side gets overwritten with it. If the server can't be reached the game
+keeps working locally and retries the unsent keys on its own, first 5 seconds
+later, then waiting twice as long after every failure, up to 5 minutes. The
+first success goes back to 5 seconds.
+
+Setting up sync:
+- Native: set the BLACKJACK_SYNC_URL environment variable, or put the address in
+  save/sync_url.json
+- Web: set localStorage "blackjack.sync_url" to the address
+
+Keys should only use letters, numbers, '_' and '-' so they are safe file names
+and URLs.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod web_bridge;
+    pub mod http;
+    pub mod storage;
+In the Cargo.toml file add the following:
+    nanoserde = "0.2.1"
+
+Then add the following with the use commands:
+use crate::modules::storage;
+
+Usage examples:
+1. Turn on cloud sync if the player set it up (once, before loading anything):
+    storage::configure_sync();
+
+2. Save something:
+    storage::save("profiles", &index.serialize_json());
+
+3. Load it back (None if it was never saved):
+    if let Some(text) = storage::load("profiles") {
+        let index = ProfileIndex::deserialize_json(&text);
+    }
+
+4. Delete it:
+    storage::remove("profile_3_stats");
+
+5. Show the sync status, and retry unsent keys once the wait is up (every frame):
+    storage::update();
+    lbl_sync.set_text(storage::sync_status().text());
+    if let Some(seconds) = storage::retry_in() {
+        println!("retrying in {:.0}s", seconds);
+    }
+
+6. Use your own backend for the remote copy:
+    storage::set_remote(Some(Box::new(HttpBackend::new("http://localhost:8080/blackjack"))));
+*/
+use std::cell::RefCell;
+use macroquad::miniquad::date;
+use nanoserde::{DeJson, SerJson};
+use crate::modules::http;
+use crate::{log_debug, log_info, log_warn};
+
+// Seconds to wait before trying an unreachable server again, doubling up to the longest
+const RETRY_FIRST: f64 = 5.0;
+const RETRY_LONGEST: f64 = 300.0;
+
+// Somewhere keys can be saved to and loaded from
+pub trait StorageBackend {
+    fn save(&mut self, key: &str, value: &str) -> Result<(), String>;
+    // Ok(None) means the key has never been saved
+    fn load(&mut self, key: &str) -> Result<Option<String>, String>;
+    fn remove(&mut self, key: &str) -> Result<(), String>;
+}
+
+// One file per key in a folder
+#[cfg(not(target_arch = "wasm32"))]
+pub struct FileBackend {
+    folder: std::path::PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileBackend {
+    pub fn new(folder: &str) -> Self {
+        Self { folder: std::path::PathBuf::from(folder) }
+    }
+
+    fn key_path(&self, key: &str) -> std::path::PathBuf {
+        self.folder.join(format!("{}.json", key))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl StorageBackend for FileBackend {
+    fn save(&mut self, key: &str, value: &str) -> Result<(), String> {
+        std::fs::create_dir_all(&self.folder)
+            .and_then(|_| std::fs::write(self.key_path(key), value))
+            .map_err(|err| err.to_string())
+    }
+
+    fn load(&mut self, key: &str) -> Result<Option<String>, String> {
+        match std::fs::read_to_string(self.key_path(key)) {
+            Ok(text) => Ok(Some(text)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), String> {
+        match std::fs::remove_file(self.key_path(key)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+// The browser's localStorage
+#[cfg(target_arch = "wasm32")]
+pub struct LocalStorageBackend;
+
+#[cfg(target_arch = "wasm32")]
+impl StorageBackend for LocalStorageBackend {
+    fn save(&mut self, key: &str, value: &str) -> Result<(), String> {
+        crate::modules::web_bridge::storage_set(key, value);
+        Ok(())
+    }
+
+    fn load(&mut self, key: &str) -> Result<Option<String>, String> {
+        Ok(crate::modules::web_bridge::storage_get(key))
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), String> {
+        crate::modules::web_bridge::storage_remove(key);
+        Ok(())
+    }
+}
+
+// A REST endpoint: GET, PUT and DELETE <base_url>/<key>
+pub struct HttpBackend {
+    base_url: String,
+}
+
+impl HttpBackend {
+    pub fn new(base_url: &str) -> Self {
+        Self { base_url: base_url.trim().trim_end_matches('/').to_string() }
+    }
+
+    fn key_url(&self, key: &str) -> String {
+        format!("{}/{}", self.base_url, key)
+    }
+}
+
+impl StorageBackend for HttpBackend {
+    fn save(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match http::request("PUT", &self.key_url(key), Some(value))? {
+            response if (200..300).contains(&response.status) => Ok(()),
+            response => Err(format!("server answered {}", response.status)),
+        }
+    }
+
+    fn load(&mut self, key: &str) -> Result<Option<String>, String> {
+        match http::request("GET", &self.key_url(key), None)? {
+            response if response.status == 200 => Ok(Some(response.body)),
+            response if response.status == 404 => Ok(None),
+            response => Err(format!("server answered {}", response.status)),
+        }
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), String> {
+        match http::request("DELETE", &self.key_url(key), None)? {
+            response if (200..300).contains(&response.status) || response.status == 404 => Ok(()),
+            response => Err(format!("server answered {}", response.status)),
+        }
+    }
+}
+
+// How the remote copy is doing, for the indicator on the table
+#[derive(Clone, Debug, PartialEq)]
+pub enum SyncStatus {
+    Off,             // No remote backend set up
+    Synced,          // Everything saved so far made it to the remote
+    Offline(String), // The last attempt failed, holds the error
+}
+
+impl SyncStatus {
+    // Short text for the HUD, empty when sync is off
+    pub fn text(&self) -> String {
+        match self {
+            SyncStatus::Off => String::new(),
+            SyncStatus::Synced => "Cloud: synced".to_string(),
+            SyncStatus::Offline(_) => "Cloud: offline".to_string(),
+        }
+    }
+}
+
+// What actually gets written: the value plus when it was written
+#[derive(SerJson, DeJson)]
+struct Stamped {
+    updated: f64,
+    value: String,
+}
+
+impl Stamped {
+    // Saves from before timestamps were added are plain values, treat them as very old
+    fn parse(text: String) -> Self {
+        Stamped::deserialize_json(&text).unwrap_or(Stamped { updated: 0.0, value: text })
+    }
+}
+
+struct Storage {
+    local: Box<dyn StorageBackend>,
+    remote: Option<Box<dyn StorageBackend>>,
+    status: SyncStatus,
+    unsynced: Vec<String>, // Keys saved locally that the remote hasn't got yet
+    retry_at: f64,
+    retry_delay: f64,      // Wait after the next failure
+}
+
+impl Storage {
+    fn remote_ready(&self) -> bool {
+        self.remote.is_some() && date::now() >= self.retry_at
+    }
+
+    fn went_offline(&mut self, err: String) {
+        log_warn!("cloud sync failed, trying again in {}s: {}", self.retry_delay, err);
+        self.status = SyncStatus::Offline(err);
+        self.retry_at = date::now() + self.retry_delay;
+        self.retry_delay = (self.retry_delay * 2.0).min(RETRY_LONGEST);
+    }
+
+    fn came_online(&mut self) {
+        if let SyncStatus::Offline(_) = self.status {
+            log_info!("cloud sync is back");
+        }
+        self.status = SyncStatus::Synced;
+        self.retry_delay = RETRY_FIRST;
+    }
+
+    fn load_local(&mut self, key: &str) -> Option<Stamped> {
+        match self.local.load(key) {
+            Ok(text) => text.map(Stamped::parse),
+            Err(err) => {
+                log_warn!("could not load {}: {}", key, err);
+                None
+            }
+        }
+    }
+
+    fn save_local(&mut self, key: &str, stamped: &Stamped) {
+        if let Err(err) = self.local.save(key, &stamped.serialize_json()) {
+            log_warn!("could not save {}: {}", key, err);
+        }
+    }
+
+    // Send every key the remote is missing, stopping at the first failure
+    fn push_unsynced(&mut self) {
+        while let Some(key) = self.unsynced.first().cloned() {
+            if !self.remote_ready() {
+                return;
+            }
+            let Some(stamped) = self.load_local(&key) else {
+                self.unsynced.remove(0);
+                continue;
+            };
+            let result = self.remote.as_mut().map_or(Ok(()), |remote| remote.save(&key, &stamped.serialize_json()));
+            match result {
+                Ok(()) => {
+                    log_debug!("synced {}", key);
+                    self.unsynced.remove(0);
+                    self.came_online();
+                }
+                Err(err) => return self.went_offline(err),
+            }
+        }
+    }
+
+    fn save(&mut self, key: &str, value: &str) {
+        let stamped = Stamped { updated: date::now(), value: value.to_string() };
+        self.save_local(key, &stamped);
+        if self.remote.is_some() {
+            if !self.unsynced.iter().any(|unsynced| unsynced == key) {
+                self.unsynced.push(key.to_string());
+            }
+            self.push_unsynced();
+        }
+    }
+
+    // Load the newest copy, and bring the older side up to date
+    fn load(&mut self, key: &str) -> Option<String> {
+        let local = self.load_local(key);
+        if !self.remote_ready() {
+            return local.map(|stamped| stamped.value);
+        }
+        let remote = match self.remote.as_mut().map(|remote| remote.load(key)) {
+            Some(Ok(text)) => text.map(Stamped::parse),
+            Some(Err(err)) => {
+                self.went_offline(err);
+                return local.map(|stamped| stamped.value);
+            }
+            None => None,
+        };
+        self.came_online();
+
+        match (local, remote) {
+            (Some(local), Some(remote)) if remote.updated > local.updated => {
+                self.save_local(key, &remote);
+                Some(remote.value)
+            }
+            (None, Some(remote)) => {
+                self.save_local(key, &remote);
+                Some(remote.value)
+            }
+            (Some(local), remote) => {
+                if remote.is_none_or(|remote| local.updated > remote.updated) {
+                    self.unsynced.push(key.to_string());
+                    self.push_unsynced();
+                }
+                Some(local.value)
+            }
+            (None, None) => None,
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Err(err) = self.local.remove(key) {
+            log_warn!("could not delete {}: {}", key, err);
+        }
+        self.unsynced.retain(|unsynced| unsynced != key);
+        if self.remote_ready() {
+            let result = self.remote.as_mut().map_or(Ok(()), |remote| remote.remove(key));
+            if let Err(err) = result {
+                self.went_offline(err);
+            }
+        }
+    }
+}
+
+fn local_backend() -> Box<dyn StorageBackend> {
+    #[cfg(target_arch = "wasm32")]
+    return Box::new(LocalStorageBackend);
+
+    #[cfg(not(target_arch = "wasm32"))]
+    Box::new(FileBackend::new("save"))
+}
+
+thread_local! {
+    static STORAGE: RefCell<Storage> = RefCell::new(Storage {
+        local: local_backend(),
+        remote: None,
+        status: SyncStatus::Off,
+        unsynced: Vec::new(),
+        retry_at: 0.0,
+        retry_delay: RETRY_FIRST,
+    });
+}
+
+// Store a value under a key, replacing anything already there
+pub fn save(key: &str, value: &str) {
+    STORAGE.with(|storage| storage.borrow_mut().save(key, value));
+}
+
+// Read the value stored under a key
+pub fn load(key: &str) -> Option<String> {
+    STORAGE.with(|storage| storage.borrow_mut().load(key))
+}
+
+// Forget the value stored under a key
+pub fn remove(key: &str) {
+    STORAGE.with(|storage| storage.borrow_mut().remove(key));
+}
+
+// Use another backend for the remote copy, or None to stop syncing
+pub fn set_remote(remote: Option<Box<dyn StorageBackend>>) {
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        storage.status = if remote.is_some() { SyncStatus::Synced } else { SyncStatus::Off };
+        storage.remote = remote;
+        storage.unsynced.clear();
+        storage.retry_at = 0.0;
+        storage.retry_delay = RETRY_FIRST;
+    });
+}
+
+// Turn on the HTTP backend if a sync URL has been set up (see the top of this file)
+pub fn configure_sync() {
+    #[cfg(not(target_arch = "wasm32"))]
+    let from_env = std::env::var("BLACKJACK_SYNC_URL").ok();
+    #[cfg(target_arch = "wasm32")]
+    let from_env: Option<String> = None;
+
+    let url = from_env.or_else(|| {
+        STORAGE.with(|storage| storage.borrow_mut().load_local("sync_url")).map(|stamped| stamped.value)
+    });
+    match url.map(|url| url.trim().to_string()) {
+        Some(url) if !url.is_empty() => {
+            log_info!("syncing saves with {}", url);
+            set_remote(Some(Box::new(HttpBackend::new(&url))))
+        }
+        _ => {
+            log_debug!("cloud sync is off, saving locally only");
+            set_remote(None)
+        }
+    }
+}
+
+pub fn sync_status() -> SyncStatus {
+    STORAGE.with(|storage| storage.borrow().status.clone())
+}
+
+// Send the unsent keys once the wait after a failure is up. Call it every frame,
+// it does nothing while there is nothing to send or the wait isn't over.
+pub fn update() {
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        if !storage.unsynced.is_empty() && storage.remote_ready() {
+            storage.push_unsynced();
+        }
+    });
+}
+
+// Seconds until unsent keys are tried again, None when nothing is waiting
+pub fn retry_in() -> Option<f64> {
+    STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        let waiting = storage.remote.is_some() && !storage.unsynced.is_empty();
+        waiting.then(|| (storage.retry_at - date::now()).max(0.0))
+    })
+}
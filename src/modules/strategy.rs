@@ -0,0 +1,67 @@
+/*
+By: <tyler>
+Date: 2025-11-30
+Program Details: Basic strategy for hitting and standing
+
+The standard chart for a multi-deck game, cut down to the two moves this table
+has. Where the full chart says double, hitting is the next best move.
+    Hard 11 or less       - hit
+    Hard 12               - stand against 4 to 6, otherwise hit
+    Hard 13 to 16         - stand against 2 to 6, otherwise hit
+    Hard 17 or more       - stand
+    Soft 17 or less       - hit
+    Soft 18               - stand against 2 to 8, otherwise hit
+    Soft 19 or more       - stand
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod hand;
+    pub mod strategy;
+
+Then add the following with the use commands:
+use crate::modules::strategy::{basic_strategy, basic_strategy_for, Action};
+
+Usage examples:
+1. Ask what to do against the dealer's face-up card:
+    if basic_strategy(&player_hand, dealer_card) == Action::Hit {
+        // hit
+    }
+
+2. Or from a total worked out some other way (a soft 18 here):
+    let action = basic_strategy_for(18, true, dealer_card);
+*/
+use crate::modules::cards::Card;
+use crate::modules::hand::Hand;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Hit,
+    Stand,
+}
+
+// The basic strategy move for the player's hand against the dealer's up card
+pub fn basic_strategy(player: &Hand, dealer_up: Card) -> Action {
+    basic_strategy_for(player.value(), player.is_soft(), dealer_up)
+}
+
+// The same move from just the hand's total and whether it is soft
+pub fn basic_strategy_for(total: u32, soft: bool, dealer_up: Card) -> Action {
+    // Aces count 11 here, so the dealer's card is 2 - 11
+    let dealer = dealer_up.value();
+    let stand = if soft {
+        match total {
+            0..=17 => false,
+            18 => dealer <= 8,
+            _ => true,
+        }
+    } else {
+        match total {
+            0..=11 => false,
+            12 => (4..=6).contains(&dealer),
+            13..=16 => dealer <= 6,
+            _ => true,
+        }
+    };
+    if stand { Action::Stand } else { Action::Hit }
+}
@@ -0,0 +1,169 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: The basic strategy chart as a colour-coded grid, with the hand being played picked out
+
+Every cell is worked out from strategy::basic_strategy_for, so the chart can
+never disagree with the trainer or the drills. The rows are the player's hand,
+hard totals (8 and under are one row, so are 17 and over) then soft ones (an
+ace and one other card, A,9 covering soft 20 and 21). The columns are the
+dealer's up card from 2 to the ace. Hit cells are red, stand cells green.
+
+The cells are laid out with widget_layout::Grid, ten to a row, and the current
+situation, when there is one, gets a gold outline.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod cards;
+    pub mod layers;
+    pub mod strategy;
+    pub mod widget_layout;
+    pub mod strategy_chart;
+
+Then add the following with the use commands:
+use crate::modules::strategy_chart::StrategyChart;
+
+Usage examples:
+1. Create it before the loop (the cells are worked out once):
+    let mut chart = StrategyChart::new();
+
+2. Draw it with its top left corner at x, y, picking out a hard 16 against a 10:
+    chart.draw(x, y, Some((16, false, 10)));
+*/
+use macroquad::prelude::*;
+use crate::modules::cards::{Card, RANKS, Suit};
+use crate::modules::layers::{self, Layer};
+use crate::modules::strategy::{basic_strategy_for, Action};
+use crate::modules::text_cache::measure_text_cached;
+use crate::modules::widget_layout::{Grid, Placeable};
+
+pub const CELL_SIZE: Vec2 = vec2(46.0, 22.0);
+const GAP: f32 = 2.0;
+const LABEL_WIDTH: f32 = 60.0; // The row names down the left
+const SECTION_GAP: f32 = 12.0; // Between the hard and soft rows
+const TEXT_SIZE: u16 = 18;
+const HIT_COLOR: Color = Color::new(0.7, 0.2, 0.2, 1.0);
+const STAND_COLOR: Color = Color::new(0.15, 0.55, 0.25, 1.0);
+const HARD_TOTALS: std::ops::RangeInclusive<u32> = 8..=17;
+const SOFT_TOTALS: std::ops::RangeInclusive<u32> = 13..=20;
+
+// One square of the chart: a hand against an up card (2 - 11, aces are 11)
+struct Cell {
+    total: u32,
+    soft: bool,
+    dealer: u32,
+    action: Action,
+    rect: Rect,
+}
+
+impl Placeable for Cell {
+    fn size(&self) -> Vec2 {
+        CELL_SIZE
+    }
+
+    fn place(&mut self, x: f32, y: f32) {
+        self.rect = Rect::new(x, y, CELL_SIZE.x, CELL_SIZE.y);
+    }
+}
+
+pub struct StrategyChart {
+    hard: Vec<Cell>,
+    soft: Vec<Cell>,
+}
+
+impl StrategyChart {
+    pub fn new() -> Self {
+        Self { hard: cells(HARD_TOTALS, false), soft: cells(SOFT_TOTALS, true) }
+    }
+
+    // Width and height of the whole chart, row names and column headings included
+    pub fn size() -> Vec2 {
+        let rows = (HARD_TOTALS.count() + SOFT_TOTALS.count() + 1) as f32;
+        vec2(LABEL_WIDTH + 10.0 * (CELL_SIZE.x + GAP), rows * (CELL_SIZE.y + GAP) + SECTION_GAP)
+    }
+
+    // Queue the chart into the overlay layer with its top left corner at x, y. `current` is the
+    // hand being played as (total, soft, dealer up card), its cell is outlined.
+    pub fn draw(&mut self, x: f32, y: f32, current: Option<(u32, bool, u32)>) {
+        let left = x + LABEL_WIDTH;
+        let top = y + CELL_SIZE.y + GAP;
+        let soft_top = top + HARD_TOTALS.count() as f32 * (CELL_SIZE.y + GAP) + SECTION_GAP;
+        Grid::new(left, top, 10).spacing(GAP, GAP).place(&mut self.hard.iter_mut().map(|cell| cell as &mut dyn Placeable).collect::<Vec<_>>());
+        Grid::new(left, soft_top, 10).spacing(GAP, GAP).place(&mut self.soft.iter_mut().map(|cell| cell as &mut dyn Placeable).collect::<Vec<_>>());
+
+        let current = current.map(|(total, soft, dealer)| chart_row(total, soft).map(|(total, soft)| (total, soft, dealer)));
+        let mut squares = Vec::new();
+        let mut highlight = None;
+        for cell in self.hard.iter().chain(&self.soft) {
+            let color = if cell.action == Action::Hit { HIT_COLOR } else { STAND_COLOR };
+            let text = if cell.action == Action::Hit { "H" } else { "S" };
+            squares.push((cell.rect, color, text));
+            if current == Some(Some((cell.total, cell.soft, cell.dealer))) {
+                highlight = Some(cell.rect);
+            }
+        }
+        let mut labels: Vec<(String, Vec2)> = Vec::new();
+        for (i, dealer) in (2..=11).enumerate() {
+            let name = if dealer == 11 { "A".to_string() } else { dealer.to_string() };
+            labels.push((name, vec2(left + i as f32 * (CELL_SIZE.x + GAP) + CELL_SIZE.x / 2.0, y + CELL_SIZE.y * 0.75)));
+        }
+        for cells in [&self.hard, &self.soft] {
+            for cell in cells.iter().filter(|cell| cell.dealer == 2) {
+                labels.push((row_name(cell.total, cell.soft), vec2(x + LABEL_WIDTH / 2.0, cell.rect.y + CELL_SIZE.y * 0.75)));
+            }
+        }
+        layers::push(Layer::Overlay, move || {
+            for (rect, color, text) in &squares {
+                draw_rectangle(rect.x, rect.y, rect.w, rect.h, *color);
+                let width = measure_text_cached(text, None, TEXT_SIZE).width;
+                draw_text(text, rect.x + (rect.w - width) / 2.0, rect.y + rect.h * 0.75, TEXT_SIZE as f32, WHITE);
+            }
+            for (text, center) in &labels {
+                let width = measure_text_cached(text, None, TEXT_SIZE).width;
+                draw_text(text, center.x - width / 2.0, center.y, TEXT_SIZE as f32, GOLD);
+            }
+            if let Some(rect) = highlight {
+                draw_rectangle_lines(rect.x - 2.0, rect.y - 2.0, rect.w + 4.0, rect.h + 4.0, 4.0, GOLD);
+            }
+        });
+    }
+}
+
+// A row of the chart for each total, each with a cell per up card
+fn cells(totals: std::ops::RangeInclusive<u32>, soft: bool) -> Vec<Cell> {
+    let mut cells = Vec::new();
+    for total in totals {
+        for dealer in 2..=11 {
+            let up_card = RANKS.iter().copied().find(|rank| rank.value() == dealer).map(|rank| Card::new(rank, Suit::Spades));
+            let Some(up_card) = up_card else {
+                continue;
+            };
+            let action = basic_strategy_for(total, soft, up_card);
+            cells.push(Cell { total, soft, dealer, action, rect: Rect::default() });
+        }
+    }
+    cells
+}
+
+// The row a hand is played from. Anything over 21 has no row.
+fn chart_row(total: u32, soft: bool) -> Option<(u32, bool)> {
+    match (total, soft) {
+        (22.., _) => None,
+        (_, true) => Some((total.clamp(*SOFT_TOTALS.start(), *SOFT_TOTALS.end()), true)),
+        (_, false) => Some((total.clamp(*HARD_TOTALS.start(), *HARD_TOTALS.end()), false)),
+    }
+}
+
+// "8-", "12", "17+", "A,7"
+fn row_name(total: u32, soft: bool) -> String {
+    if soft {
+        format!("A,{}", total - 11)
+    } else if total == *HARD_TOTALS.start() {
+        format!("{}-", total)
+    } else if total == *HARD_TOTALS.end() {
+        format!("{}+", total)
+    } else {
+        total.to_string()
+    }
+}
@@ -0,0 +1,128 @@
+/*
+By: <tyler>
+Date: 2025-11-29
+Program Details: Hot/cold streak indicator for the HUD
+
+After STREAK_THRESHOLD wins in a row a flickering flame shows with the count,
+after that many losses in a row a blue snowflake does. Underneath is the best
+win streak of this session. Nothing shows while there is no streak.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod animation;
+    pub mod stats;
+    pub mod draw;
+    pub mod streak_indicator;
+
+Then add the following with the use commands:
+use crate::modules::streak_indicator::StreakIndicator;
+
+Usage examples:
+1. Create it before the loop and put it somewhere:
+    let mut streak = StreakIndicator::new();
+    streak.set_position(760.0, 270.0);
+
+2. Keep it in step with the stats and draw it:
+    streak.show(&profile.stats);
+    streak.draw();
+*/
+use macroquad::prelude::*;
+use crate::modules::draw::draw_smooth_circle;
+use crate::modules::animation::animation_time;
+use crate::modules::layers::{self, Layer};
+use crate::modules::stats::Stats;
+
+const ICON_SIZE: f32 = 36.0;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mood {
+    Hot,
+    Cold,
+}
+
+pub struct StreakIndicator {
+    x: f32,
+    y: f32,
+    mood: Option<Mood>,
+    count: u32,
+    session_best: u32,
+}
+
+impl StreakIndicator {
+    pub fn new() -> Self {
+        Self { x: 0.0, y: 0.0, mood: None, count: 0, session_best: 0 }
+    }
+
+    // Top-left corner of the icon
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        self.x = x;
+        self.y = y;
+    }
+
+    pub fn show(&mut self, stats: &Stats) {
+        (self.mood, self.count) = if stats.is_hot() {
+            (Some(Mood::Hot), stats.win_streak())
+        } else if stats.is_cold() {
+            (Some(Mood::Cold), stats.loss_streak())
+        } else {
+            (None, 0)
+        };
+        self.session_best = stats.session_best_streak;
+    }
+
+    pub fn draw(&self) {
+        let Some(mood) = self.mood else {
+            return;
+        };
+        let (x, y, count, session_best) = (self.x, self.y, self.count, self.session_best);
+        let time = animation_time();
+        layers::push(Layer::Hud, move || {
+            let center = vec2(x + ICON_SIZE / 2.0, y + ICON_SIZE / 2.0);
+            let color = match mood {
+                Mood::Hot => {
+                    draw_flame(center, time);
+                    ORANGE
+                }
+                Mood::Cold => {
+                    draw_snowflake(center);
+                    SKYBLUE
+                }
+            };
+            draw_text(&format!("x{}", count), x + ICON_SIZE + 8.0, y + ICON_SIZE * 0.75, 34.0, color);
+            if session_best > 0 {
+                draw_text(&format!("Best this session: {}", session_best), x, y + ICON_SIZE + 20.0, 20.0, WHITE);
+            }
+        });
+    }
+}
+
+// Three stacked teardrops that flicker a little
+fn draw_flame(center: Vec2, time: f32) {
+    let flicker = (time * 12.0).sin() * 0.06 + 1.0;
+    let layers = [(1.0, RED), (0.72, ORANGE), (0.45, YELLOW)];
+    for (scale, color) in layers {
+        let radius = ICON_SIZE * 0.3 * scale;
+        let base = vec2(center.x, center.y + ICON_SIZE * 0.5 - radius);
+        let tip = vec2(center.x + (time * 7.0).sin() * 2.0, base.y - radius * 2.6 * flicker);
+        draw_smooth_circle(base.x, base.y, radius, color);
+        draw_triangle(vec2(base.x - radius, base.y), vec2(base.x + radius, base.y), tip, color);
+    }
+}
+
+// Six spokes with little branches
+fn draw_snowflake(center: Vec2) {
+    let length = ICON_SIZE * 0.5;
+    for i in 0..6 {
+        let angle = i as f32 * std::f32::consts::TAU / 6.0;
+        let direction = vec2(angle.cos(), angle.sin());
+        let end = center + direction * length;
+        draw_line(center.x, center.y, end.x, end.y, 3.0, SKYBLUE);
+        let branch = center + direction * length * 0.6;
+        for side in [-1.0, 1.0] {
+            let twig = Vec2::from_angle(angle + side * 0.7) * length * 0.3;
+            draw_line(branch.x, branch.y, branch.x + twig.x, branch.y + twig.y, 2.0, SKYBLUE);
+        }
+    }
+    draw_smooth_circle(center.x, center.y, 3.0, WHITE);
+}
@@ -41,6 +41,14 @@ You can add a border to the button with:
     btn_text.with_border(RED, 2.0);
 Where the first value is the border color and the second is the thickness.
 
+New buttons start from the style registered in modules::widget_style (text colors,
+font, corner radius and border), so a look shared by every button can be set once:
+    widget_style::set_button_style(WidgetStyle::new(WHITE).with_round(5.0));
+The builders above still override it for one button, or use a whole other style with:
+    btn_text.with_style(&my_style);
+Until a button is given text colors of its own it keeps following the registered
+style's, so a new theme reaches it the next time it is drawn.
+
 To access the button's position:
     let x = btn_text.get_x();
     let y = btn_text.get_y();
@@ -53,17 +61,57 @@ Where the first two values are x and y positions, and the optional values are fo
 To change the button's text:
     btn_text.set_text("New Text");
 
+Captions that are too wide shrink to fit automatically (down to 10px). You can also:
+    btn_text.with_wrap(true);                      // Allow the caption to use two lines
+    btn_text.with_padding(10.0, 10.0, 5.0, 5.0);   // Space kept clear on the left, right, top and bottom
+    btn_text.with_auto_shrink(false);              // Always use the full font size
+The font size is multiplied by the player's text scale (widget_style::set_text_scale).
+A button that isn't moved every frame lays its caption out again with:
+    btn_text.rescale_text();
+
+To explain why a disabled button can't be pressed (shown as a tooltip on hover):
+    btn_text.set_disabled_reason("You can only double on your first two cards");
+    btn_text.clear_disabled_reason();
+
+To find the button from code that doesn't own it (see modules::ui, needs pub mod ui; in mod.rs):
+    btn_text.with_id("btn_text");
+    let info = ui::scene().get::<TextButton>("btn_text"); // Where it was drawn last frame
+    ui::press("btn_text");                                // click() answers true next frame
+
+The button is drawn into the Hud layer (needs pub mod layers; and pub mod tooltip; in mod.rs).
+To put it somewhere else, for example on a dialog:
+    btn_text.with_layer(Layer::Overlay);
+Remember to call layers::flush() at the end of the loop.
+
 Then in the loop you would use:
 if btn_text.click() {
 
 }
 
+Showing, hiding, enabling and disabling a button fades it over a moment instead
+of switching at once (a shown button also grows in a little). Turning on reduced
+motion (animation::set_reduced_motion) makes it instant again. A hidden or
+disabled button can't be clicked from the moment the flag changes.
+
 Note: For buttons with transparent backgrounds (set normal_color with alpha=0), 
 only the text area is clickable, not the entire button area.
+Where buttons overlap, only the one drawn on top (a higher layer, or drawn later
+in the same layer) is hovered and clicked, see modules::ui.
 */
 use macroquad::prelude::*;
+use crate::modules::text_cache::measure_text_cached;
 #[cfg(feature = "scale")]
 use crate::modules::scale::mouse_position_world as mouse_position;
+use crate::modules::input::is_mouse_button_pressed;
+use crate::modules::animation::Fade;
+use crate::modules::layers::{self, Layer};
+use crate::modules::tooltip;
+use crate::modules::cursor::{self, CursorKind};
+use crate::modules::shapes::ShapeBatch;
+use std::rc::Rc;
+use crate::modules::widget_style::{self, WidgetStyle};
+use crate::modules::widget_layout::Placeable;
+use crate::modules::ui::{self, Widget, WidgetInfo};
 
 // Custom struct for ButtonText
 pub struct TextButton {
@@ -78,6 +126,7 @@ pub struct TextButton {
     off_color: Color,
     pub text_color: Color,
     pub hover_text_color: Color, // Added hover text color
+    own_text_colors: bool,       // False follows the registered style's colours as it changes
     pub font_size: u16,
     pub font: Option<Font>, // Store the font directly since Font is Clone
     pub corner_radius: f32, // For rounded corners
@@ -85,29 +134,33 @@ pub struct TextButton {
     pub border_color: Color, // Color of the border
     pub border_thickness: f32, // Thickness of the border
     
-    // Cached values for performance
-    cached_text_width: f32,
-    cached_text_position: Vec2,
+    pub auto_shrink: bool,   // Shrink the font until the caption fits
+    pub wrap: bool,          // Allow the caption to break onto a second line
+    padding: [f32; 4],       // Left, right, top, bottom space inside the button
+
+    // Cached values for performance, rebuilt by relayout()
+    cached_lines: Rc<Vec<(String, Vec2)>>, // Each caption line and where its baseline starts, shared with the queued drawing
+    cached_font_size: u16,
+    cached_text_rect: Rect,
     cached_rect: Rect,
     pub visible: bool,
+    disabled_reason: Option<String>, // Tooltip shown when hovering while disabled
+    pub layer: Layer,                 // Draw layer, Hud unless changed
+    shown: Fade,                      // Follows visible
+    lit: Fade,                        // Follows enabled
+    id: Option<Rc<str>>,              // Registered in modules::ui when drawn
+    owner: u32,                       // Its hits in modules::ui, to tell whether it is the top widget under the pointer
 }
 
 impl TextButton {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(x: f32, y: f32, width: f32, height: f32, text: impl Into<String>, normal_color: Color, hover_color: Color, font_size: u16) -> Self {
         let enabled = true;
         let off_color = lerp_color(normal_color, GRAY, 0.5);
         let text_string = text.into();
-        let text_color = WHITE; // Default text color
+        let style = widget_style::button_style();
         
-        // Pre-calculate and cache values
-        let cached_text_width = measure_text(&text_string, None, font_size, 1.0).width;
-        let cached_text_position = Vec2::new(
-            x + (width / 2.0) - (cached_text_width / 2.0),
-            y + (height / 2.0),
-        );
-        let cached_rect = Rect::new(x, y, width, height);
-        
-        Self {
+        let mut button = Self {
             x,
             y,
             width,
@@ -117,35 +170,99 @@ impl TextButton {
             normal_color,
             hover_color,
             off_color,
-            text_color,
-            hover_text_color: text_color, // Default hover text color to regular text color
+            text_color: style.text_color,
+            hover_text_color: style.hover_text_color,
+            own_text_colors: false,
             font_size,
-            font: None, // Default to None (use system font)
-            corner_radius: 0.0, // Default to no rounded corners
-            border: false, // Default to no border
-            border_color: BLACK, // Default border color
-            border_thickness: 1.0, // Default border thickness
-            cached_text_width,
-            cached_text_position,
-            cached_rect,
+            font: style.font,
+            corner_radius: style.corner_radius,
+            border: style.border.is_some(),
+            border_color: style.border.map_or(BLACK, |(color, _)| color),
+            border_thickness: style.border.map_or(1.0, |(_, thickness)| thickness),
+            auto_shrink: true,
+            wrap: false,
+            padding: [0.0; 4],
+            cached_lines: Rc::new(Vec::new()),
+            cached_font_size: font_size,
+            cached_text_rect: Rect::new(x, y, 0.0, 0.0),
+            cached_rect: Rect::new(x, y, width, height),
             visible: true,
+            disabled_reason: None,
+            layer: Layer::Hud,
+            shown: Fade::new(FADE_SECONDS),
+            lit: Fade::new(FADE_SECONDS),
+            id: None,
+            owner: ui::new_owner(),
+        };
+        // Pre-calculate and cache values
+        button.relayout();
+        button
+    }
+
+    // Method to give the button an ID, so modules::ui can find and press it
+    pub fn with_id(&mut self, id: &str) -> &mut Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    #[allow(unused)]
+    pub fn get_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    // Method to take the text colors, font, corner radius and border from a style
+    #[allow(unused)]
+    pub fn with_style(&mut self, style: &WidgetStyle) -> &mut Self {
+        self.text_color = style.text_color;
+        self.hover_text_color = style.hover_text_color;
+        self.own_text_colors = true;
+        self.font = style.font.clone();
+        self.corner_radius = style.corner_radius;
+        self.border = style.border.is_some();
+        if let Some((color, thickness)) = style.border {
+            self.border_color = color;
+            self.border_thickness = thickness;
         }
+        self.relayout();
+        self
     }
 
     // Method to set custom font - taking Font by value since it implements Clone
     #[allow(unused)]
     pub fn with_font(&mut self, font: Font) -> &mut Self {
-        self.font = Some(font.clone());
-        
-        // Update cached text width with the new font
-        self.cached_text_width = measure_text(&self.text, Some(&font), self.font_size, 1.0).width;
-        
-        // Update text position based on new measurement
-        self.cached_text_position = Vec2::new(
-            self.x + (self.width / 2.0) - (self.cached_text_width / 2.0),
-            self.y + (self.height / 2.0),
-        );
-        
+        self.font = Some(font);
+        self.relayout();
+        self
+    }
+
+    // Method to pick the draw layer
+    #[allow(unused)]
+    pub fn with_layer(&mut self, layer: Layer) -> &mut Self {
+        self.layer = layer;
+        self
+    }
+
+    // Method to turn automatic font shrinking on or off
+    #[allow(unused)]
+    pub fn with_auto_shrink(&mut self, enabled: bool) -> &mut Self {
+        self.auto_shrink = enabled;
+        self.relayout();
+        self
+    }
+
+    // Method to allow the caption to wrap onto two lines
+    #[allow(unused)]
+    pub fn with_wrap(&mut self, enabled: bool) -> &mut Self {
+        self.wrap = enabled;
+        self.relayout();
+        self
+    }
+
+    // Method to keep space clear around the caption on each side
+    #[allow(unused)]
+    pub fn with_padding(&mut self, left: f32, right: f32, top: f32, bottom: f32) -> &mut Self {
+        self.padding = [left, right, top, bottom];
+        self.relayout();
         self
     }
 
@@ -169,6 +286,7 @@ impl TextButton {
     #[allow(unused)]
     pub fn with_hover_text_color(&mut self, color: Color) -> &mut Self {
         self.hover_text_color = color;
+        self.own_text_colors = true;
         self
     }
 
@@ -176,6 +294,7 @@ impl TextButton {
     #[allow(unused)]
     pub fn with_text_color(&mut self, color: Color) -> &mut Self {
         self.text_color = color;
+        self.own_text_colors = true;
         if self.hover_text_color == WHITE { // Only update if it wasn't explicitly set
             self.hover_text_color = color;
         }
@@ -209,23 +328,41 @@ impl TextButton {
     // Setter for the button text - updates cached measurements
     #[allow(unused)]
     pub fn set_text<T: Into<String>>(&mut self, text: T) -> &mut Self {
-        self.text = text.into();
-        
-        // Update cached text measurements
-        self.cached_text_width = match &self.font {
-            Some(font) => measure_text(&self.text, Some(font), self.font_size, 1.0).width,
-            None => measure_text(&self.text, None, self.font_size, 1.0).width,
-        };
-        
-        // Update text position
-        self.cached_text_position = Vec2::new(
-            self.x + (self.width / 2.0) - (self.cached_text_width / 2.0),
-            self.y + (self.height / 2.0),
-        );
-        
+        let text = text.into();
+        if text == self.text {
+            return self;
+        }
+        self.text = text;
+        self.relayout();
         self
     }
+
+    // Getter for the font size the caption is actually drawn at (after shrinking)
+    #[allow(unused)]
+    pub fn get_drawn_font_size(&self) -> u16 {
+        self.cached_font_size
+    }
     
+    // Set the tooltip that explains why the button is disabled
+    #[allow(unused)]
+    pub fn set_disabled_reason(&mut self, reason: &str) -> &mut Self {
+        self.disabled_reason = Some(reason.to_string());
+        self
+    }
+
+    // Remove the disabled tooltip
+    #[allow(unused)]
+    pub fn clear_disabled_reason(&mut self) -> &mut Self {
+        self.disabled_reason = None;
+        self
+    }
+
+    // Getter for the disabled tooltip text
+    #[allow(unused)]
+    pub fn get_disabled_reason(&self) -> Option<&str> {
+        self.disabled_reason.as_deref()
+    }
+
     // Update method to recalculate values when position or size changes
     #[allow(unused)]
     pub fn update_position(&mut self, x: f32, y: f32, width: Option<f32>, height: Option<f32>) -> &mut Self {
@@ -240,21 +377,23 @@ impl TextButton {
             self.height = h;
         }
         
-        // Update cached rectangle
+        // Update cached rectangle and caption layout
         self.cached_rect = Rect::new(self.x, self.y, self.width, self.height);
-        
-        // Update text position
-        self.cached_text_position = Vec2::new(
-            self.x + (self.width / 2.0) - (self.cached_text_width / 2.0),
-            self.y + (self.height / 2.0),
-        );
+        self.relayout();
         
         self
     }
 
     pub fn click(&self) -> bool {
-        if !self.visible {
-            return false; // If not visible, don't process clicks
+        // A hidden button is still drawn while it fades out, it just can't be clicked
+        let shown = self.shown.update(self.visible);
+        let lit = self.lit.update(self.enabled);
+        if let Some(id) = &self.id {
+            let rect = Rect::new(self.x, self.y, self.width, self.height);
+            ui::record(WidgetInfo { id: id.clone(), kind: Self::KIND, rect, text: self.text.clone(), visible: self.visible, enabled: self.enabled });
+        }
+        if shown == 0.0 {
+            return false;
         }
         // Get mouse position
         let (mouse_x, mouse_y) = mouse_position();
@@ -264,200 +403,201 @@ impl TextButton {
         let is_background_transparent = self.normal_color.a == 0.0;
         
         // Determine is_hovered based on background transparency
-        let is_hovered = if is_background_transparent {
+        let hit_rect = if is_background_transparent {
             // If transparent, only detect clicks on the text area
-            let text_height = self.font_size as f32; // Approximate text height
-            let text_rect = Rect::new(
-                self.cached_text_position.x,
-                self.cached_text_position.y - text_height,
-                self.cached_text_width,
-                text_height
-            );
-            text_rect.contains(mouse_pos)
+            self.cached_text_rect
         } else {
             // Otherwise use the full button area
-            self.cached_rect.contains(mouse_pos)
+            self.cached_rect
         };
+        // Only the top widget under the pointer is hovered, whatever order the click() calls run in
+        let is_hovered = hit_rect.contains(mouse_pos) && ui::on_top(self.owner, self.layer, mouse_pos);
+        if self.visible {
+            ui::record_hit(self.owner, self.layer, hit_rect);
+        }
 
-        // Draw the text button (change color on hover)
-        let button_color = if self.enabled {
-            if is_hovered {
-                self.hover_color
-            } else {
-                self.normal_color
-            }
-        } else {
-            self.off_color
-        };
+        // Draw the text button (change color on hover), blending to the disabled look as it fades off
+        let on_color = if is_hovered { self.hover_color } else { self.normal_color };
+        let button_color = with_alpha(blend(self.off_color, on_color, lit), shown);
 
-        // Draw the button with or without rounded corners
-        if self.corner_radius > 0.0 {
-            draw_round_rect(self.x, self.y, self.width, self.height, self.corner_radius, button_color);
-            
-            // Draw rounded border if enabled
-            if self.border {
-                draw_round_rect_lines(self.x, self.y, self.width, self.height, 
-                                     self.corner_radius, self.border_thickness, self.border_color);
-            }
-        } else {
-            draw_rectangle(self.x, self.y, self.width, self.height, button_color);
-            
-            // Draw regular border if enabled
-            if self.border {
-                draw_rectangle_lines(self.x, self.y, self.width, self.height, 
-                                    self.border_thickness, self.border_color);
-            }
-        }
+        // Draw the text with the appropriate font using cached position, dimmed while disabled
+        // Unless the button has its own, the colours come from the style registered now (a theme change)
+        let (text_color, hover_text_color) =
+            if self.own_text_colors { (self.text_color, self.hover_text_color) } else { widget_style::button_text_colors() };
+        let on_text_color = if is_hovered { hover_text_color } else { text_color };
+        let off_text_color = Color::new(text_color.r, text_color.g, text_color.b, 0.5);
+        let current_text_color = with_alpha(blend(off_text_color, on_text_color, lit), shown);
+        let border_color = with_alpha(self.border_color, shown);
+
+        // Grow in from a little smaller around the middle
+        let scale = SHOWN_FROM_SCALE + (1.0 - SHOWN_FROM_SCALE) * shown;
+        let center = self.cached_rect.center();
+        let scaled = move |point: Vec2| center + (point - center) * scale;
 
-        // Draw the text with the appropriate font using cached position
-        let current_text_color = if self.enabled {
-            if is_hovered {
-                self.hover_text_color
-            } else {
-                self.text_color
+        // The drawing itself is queued into the button's layer, so copy what it needs
+        let corner = scaled(vec2(self.x, self.y));
+        let (x, y, width, height) = (corner.x, corner.y, self.width * scale, self.height * scale);
+        let (corner_radius, border, border_thickness) = (self.corner_radius * scale, self.border, self.border_thickness);
+        let lines = self.cached_lines.clone();
+        let font = self.font.clone();
+        let font_size = self.cached_font_size;
+        layers::push(self.layer, move || {
+            // The button and its border as one mesh
+            let mut shapes = ShapeBatch::new();
+            let rect = Rect::new(x, y, width, height);
+            shapes.fill(rect, corner_radius, button_color);
+            if border {
+                shapes.outline(rect, corner_radius, border_thickness, border_color);
             }
-        } else {
-            // Use a dimmed text color for disabled state
-            Color::new(self.text_color.r, self.text_color.g, self.text_color.b, 0.5)
-        };
-        
-        match &self.font {
-            Some(font) => {
+            shapes.draw();
+
+            for (line, position) in lines.iter() {
+                let position = scaled(*position);
                 draw_text_ex(
-                    &self.text,
-                    self.cached_text_position.x,
-                    self.cached_text_position.y,
+                    line,
+                    position.x,
+                    position.y,
                     TextParams {
-                        font: Some(font),
-                        font_size: self.font_size,
+                        font: font.as_ref(),
+                        font_size,
+                        font_scale: scale,
                         color: current_text_color,
                         ..Default::default()
                     },
                 );
-            },
-            None => {
-                // Use the default draw_text function
-                draw_text(
-                    &self.text,
-                    self.cached_text_position.x,
-                    self.cached_text_position.y,
-                    self.font_size.into(),
-                    current_text_color,
-                );
             }
+        });
+
+        if !self.visible {
+            return false; // Fading out, don't process clicks
+        }
+
+        if self.enabled && is_hovered {
+            cursor::want(CursorKind::Pointer);
+        }
+
+        // Explain why it can't be pressed
+        if !self.enabled && is_hovered && let Some(reason) = &self.disabled_reason {
+            tooltip::show(reason, mouse_x, mouse_y);
         }
 
-        // After drawing, check if the button was clicked
-        is_hovered && self.enabled && is_mouse_button_pressed(MouseButton::Left)
+        // After drawing, check if the button was clicked, or pressed through modules::ui
+        let pressed = self.enabled && self.id.as_deref().is_some_and(ui::take_press);
+        (is_hovered && self.enabled && is_mouse_button_pressed(MouseButton::Left)) || pressed
     }
-}
 
-// Function to draw a rectangle with rounded corners - optimized version
-fn draw_round_rect(x: f32, y: f32, w: f32, h: f32, radius: f32, color: Color) {
-    // Precompute corner positions
-    let top_left = Vec2::new(x + radius, y + radius);
-    let top_right = Vec2::new(x + w - radius, y + radius);
-    let bottom_left = Vec2::new(x + radius, y + h - radius);
-    let bottom_right = Vec2::new(x + w - radius, y + h - radius);
-    
-    // Draw center rectangle
-    draw_rectangle(x + radius, y, w - 2.0 * radius, h, color);
-    
-    // Draw the side rectangles
-    draw_rectangle(x, y + radius, radius, h - 2.0 * radius, color);
-    draw_rectangle(x + w - radius, y + radius, radius, h - 2.0 * radius, color);
-    
-    // Draw the four corner circles (could be batched in a real engine)
-    draw_circle(top_left.x, top_left.y, radius, color);     // Top-left
-    draw_circle(top_right.x, top_right.y, radius, color);   // Top-right
-    draw_circle(bottom_left.x, bottom_left.y, radius, color);  // Bottom-left
-    draw_circle(bottom_right.x, bottom_right.y, radius, color); // Bottom-right
-}
+    // Lay the caption out again at the current text scale (see widget_style::set_text_scale)
+    pub fn rescale_text(&mut self) -> &mut Self {
+        self.relayout();
+        self
+    }
 
-// Function to draw rounded rectangle borders - optimized version
-fn draw_round_rect_lines(x: f32, y: f32, w: f32, h: f32, radius: f32, thickness: f32, color: Color) {
-    // Precompute corner positions
-    let top_left = Vec2::new(x + radius, y + radius);
-    let top_right = Vec2::new(x + w - radius, y + radius);
-    let bottom_left = Vec2::new(x + radius, y + h - radius);
-    let bottom_right = Vec2::new(x + w - radius, y + h - radius);
-    
-    // Draw the horizontal and vertical lines
-    draw_line(top_left.x, y, top_right.x, y, thickness, color);          // Top
-    draw_line(bottom_left.x, y + h, bottom_right.x, y + h, thickness, color); // Bottom
-    draw_line(x, top_left.y, x, bottom_left.y, thickness, color);           // Left
-    draw_line(x + w, top_right.y, x + w, bottom_right.y, thickness, color);  // Right
-    
-    // Draw the four corner arcs with fewer segments for better performance
-    let segments = 8; // Reduced from 16 - still looks good but fewer draw calls
-    let step = std::f32::consts::PI / 2.0 / segments as f32;
-    
-    // Pre-calculate sin/cos values for angle offsets to avoid redundant calculations
-    let angles: Vec<(f32, f32)> = (0..=segments)
-        .map(|i| {
-            let angle = i as f32 * step;
-            (angle.cos(), angle.sin())
-        })
-        .collect();
-    
-    // Draw arcs for each corner
-    // Top-left corner: PI to PI*3/2
-    for i in 0..segments {
-        let (cos1, sin1) = angles[i];
-        let (cos2, sin2) = angles[i+1];
-        draw_line(
-            top_left.x - radius * cos1,
-            top_left.y - radius * sin1,
-            top_left.x - radius * cos2,
-            top_left.y - radius * sin2,
-            thickness,
-            color
-        );
+    // Work out the font size, line breaks and text positions for the current caption and size
+    fn relayout(&mut self) {
+        let [pad_left, pad_right, pad_top, pad_bottom] = self.padding;
+        let avail_width = (self.width - pad_left - pad_right).max(0.0);
+        let avail_height = (self.height - pad_top - pad_bottom).max(0.0);
+        let font_size = widget_style::scaled_font_size(self.font_size);
+        let min_size = if self.auto_shrink { MIN_FONT_SIZE.min(font_size) } else { font_size };
+
+        // Biggest size that fits on one line, or on two if wrapping is allowed
+        let mut size = font_size;
+        let mut lines = vec![self.text.clone()];
+        loop {
+            if self.lines_fit(&lines, size, avail_width, avail_height) {
+                break;
+            }
+            let split = if self.wrap { self.split_in_two(size) } else { None };
+            if let Some(split) = split.filter(|split| self.lines_fit(split, size, avail_width, avail_height)) {
+                lines = split;
+                break;
+            }
+            if size <= min_size {
+                // Nothing fits, draw on one line at the smallest size
+                lines = vec![self.text.clone()];
+                break;
+            }
+            size -= 1;
+        }
+
+        // Centre the lines in the padded area. A single line keeps the original
+        // baseline-at-middle placement so existing buttons look the same.
+        let center_x = self.x + pad_left + avail_width / 2.0;
+        let center_y = self.y + pad_top + avail_height / 2.0;
+        let line_height = size as f32 * LINE_SPACING;
+        let first_baseline = if lines.len() == 1 {
+            center_y
+        } else {
+            center_y - line_height * (lines.len() as f32 - 1.0) / 2.0 + size as f32 * 0.3
+        };
+        let mut text_rect: Option<Rect> = None;
+        let placed: Vec<(String, Vec2)> = lines
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let width = self.measure(&line, size);
+                let position = vec2(center_x - width / 2.0, first_baseline + line_height * i as f32);
+                let rect = Rect::new(position.x, position.y - size as f32, width, size as f32);
+                text_rect = Some(text_rect.map_or(rect, |all| all.combine_with(rect)));
+                (line, position)
+            })
+            .collect();
+        self.cached_lines = Rc::new(placed);
+        self.cached_font_size = size;
+        self.cached_text_rect = text_rect.unwrap_or(Rect::new(center_x, center_y, 0.0, 0.0));
     }
-    
-    // Top-right corner: PI*3/2 to PI*2
-    for i in 0..segments {
-        let (cos1, sin1) = angles[i];
-        let (cos2, sin2) = angles[i+1];
-        draw_line(
-            top_right.x + radius * sin1,
-            top_right.y - radius * cos1,
-            top_right.x + radius * sin2,
-            top_right.y - radius * cos2,
-            thickness,
-            color
-        );
+
+    fn measure(&self, text: &str, size: u16) -> f32 {
+        measure_text_cached(text, self.font.as_ref(), size).width
     }
-    
-    // Bottom-left corner: PI/2 to PI
-    for i in 0..segments {
-        let (cos1, sin1) = angles[i];
-        let (cos2, sin2) = angles[i+1];
-        draw_line(
-            bottom_left.x - radius * sin1,
-            bottom_left.y + radius * cos1,
-            bottom_left.x - radius * sin2,
-            bottom_left.y + radius * cos2,
-            thickness, 
-            color
-        );
+
+    fn lines_fit(&self, lines: &[String], size: u16, avail_width: f32, avail_height: f32) -> bool {
+        let height = size as f32 * LINE_SPACING * lines.len() as f32;
+        let fits_height = lines.len() == 1 || height <= avail_height;
+        fits_height && lines.iter().all(|line| self.measure(line, size) <= avail_width)
     }
-    
-    // Bottom-right corner: 0 to PI/2
-    for i in 0..segments {
-        let (cos1, sin1) = angles[i];
-        let (cos2, sin2) = angles[i+1];
-        draw_line(
-            bottom_right.x + radius * cos1,
-            bottom_right.y + radius * sin1,
-            bottom_right.x + radius * cos2,
-            bottom_right.y + radius * sin2,
-            thickness,
-            color
-        );
+
+    // Break the caption at the space that makes the wider of the two lines narrowest
+    fn split_in_two(&self, size: u16) -> Option<Vec<String>> {
+        self.text
+            .match_indices(' ')
+            .map(|(i, _)| (self.text[..i].trim_end().to_string(), self.text[i + 1..].trim_start().to_string()))
+            .min_by(|a, b| {
+                let widest = |(first, second): &(String, String)| self.measure(first, size).max(self.measure(second, size));
+                widest(a).total_cmp(&widest(b))
+            })
+            .map(|(first, second)| vec![first, second])
+    }
+}
+
+impl Placeable for TextButton {
+    fn size(&self) -> Vec2 {
+        vec2(self.width, self.height)
+    }
+
+    fn place(&mut self, x: f32, y: f32) {
+        self.update_position(x, y, None, None);
     }
 }
 
+// Smallest size auto shrink will go down to, and the gap between wrapped lines
+const MIN_FONT_SIZE: u16 = 10;
+const LINE_SPACING: f32 = 1.1;
+
+// How long showing, hiding, enabling and disabling take, and how small a button starts when shown
+const FADE_SECONDS: f32 = 0.15;
+const SHOWN_FROM_SCALE: f32 = 0.9;
+
 fn lerp_color(c1: Color, c2: Color, factor: f32) -> Color {
     Color::new(c1.r * (1.0 - factor) + c2.r * factor, c1.g * (1.0 - factor) + c2.g * factor, c1.b * (1.0 - factor) + c2.b * factor, 1.0)
 }
+
+// Part way from one color to another, alpha included
+fn blend(from: Color, to: Color, factor: f32) -> Color {
+    Color::from_vec(from.to_vec().lerp(to.to_vec(), factor))
+}
+
+// The same color with its alpha multiplied by `factor`
+fn with_alpha(color: Color, factor: f32) -> Color {
+    Color { a: color.a * factor, ..color }
+}
@@ -0,0 +1,59 @@
+/*
+By: <tyler>
+Date: 2025-12-06
+Program Details: Remember how big a piece of text is
+
+measure_text lays the whole string out glyph by glyph every time it is called,
+and a lot of the game measures the same few strings every frame (button
+captions while they shrink to fit, the letters of the rules printed on the felt,
+labels that get the same text set again). This keeps the measurements for the
+default font keyed by the text and font size, so each one is only worked out once.
+Text in a custom font can't be told apart by font, so it is measured every time.
+The cache empties itself when it gets big, text that keeps changing (a count
+down, a bankroll) can't make it grow forever.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod text_cache;
+
+Then add the following with the use commands:
+use crate::modules::text_cache::measure_text_cached;
+
+Usage examples:
+1. Use it anywhere measure_text would be used:
+    let width = measure_text_cached("Deal", None, 30).width;
+    let size = measure_text_cached(&caption, button.font.as_ref(), 24);
+*/
+use macroquad::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+const MAX_ENTRIES: usize = 1024;
+
+thread_local! {
+    // Font size, then text, so a lookup doesn't have to copy the text
+    static SIZES: RefCell<HashMap<u16, HashMap<String, TextDimensions>>> = RefCell::new(HashMap::new());
+    static ENTRIES: Cell<usize> = const { Cell::new(0) };
+}
+
+// measure_text at a scale of 1, remembered for the default font
+pub fn measure_text_cached(text: &str, font: Option<&Font>, font_size: u16) -> TextDimensions {
+    if font.is_some() {
+        return measure_text(text, font, font_size, 1.0);
+    }
+    SIZES.with(|sizes| {
+        let mut sizes = sizes.borrow_mut();
+        if let Some(dimensions) = sizes.get(&font_size).and_then(|texts| texts.get(text)) {
+            return *dimensions;
+        }
+        if ENTRIES.with(|entries| entries.get()) >= MAX_ENTRIES {
+            sizes.clear();
+            ENTRIES.with(|entries| entries.set(0));
+        }
+        let dimensions = measure_text(text, None, font_size, 1.0);
+        sizes.entry(font_size).or_default().insert(text.to_string(), dimensions);
+        ENTRIES.with(|entries| entries.set(entries.get() + 1));
+        dimensions
+    })
+}
@@ -0,0 +1,85 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Colour themes for the table
+
+A theme is the colour of the felt, which is most of what is on screen, and the
+colour button captions light up in when the pointer is over them. set_theme()
+swaps both at once: the felt and the button style registered in
+modules::widget_style change together, so no frame is drawn half in one theme
+and half in the other. The table and the menus read the felt every frame, and
+buttons on the registered style take its caption colours when they are drawn,
+so picking another theme shows straight away. The player's pick is saved in
+their settings (an index into THEMES), the first one is the green the game has
+always had.
+
+Display > Theme opens a picker that shows a theme on a sample of the table
+(a button, a label, a card and a chip) before it is applied.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod widget_style;
+    pub mod theme;
+
+Then add the following with the use commands:
+use crate::modules::theme::{self, THEMES};
+
+Usage examples:
+1. Apply the player's pick:
+    theme::set_theme(settings.theme);
+
+2. Paint with it:
+    clear_background(theme::felt_color());
+
+3. Style one button like a theme that isn't applied, for a preview:
+    btn_sample.with_style(&THEMES[2].button_style());
+
+4. Offer every theme:
+    for (i, theme) in THEMES.iter().enumerate() {
+        draw_rectangle(x + i as f32 * 60.0, y, 50.0, 50.0, theme.felt);
+        draw_text(theme.name, x + i as f32 * 60.0, y + 70.0, 20.0, WHITE);
+    }
+*/
+use macroquad::prelude::*;
+use std::cell::Cell;
+use crate::modules::widget_style::{self, WidgetStyle};
+
+pub struct Theme {
+    pub name: &'static str,
+    pub felt: Color,
+    pub accent: Color, // Button captions under the pointer
+}
+
+impl Theme {
+    // How buttons look in this theme: white captions, rounded corners
+    pub fn button_style(&self) -> WidgetStyle {
+        WidgetStyle::new(WHITE).with_round(5.0).with_hover_text_color(self.accent)
+    }
+}
+
+pub const THEMES: [Theme; 4] = [
+    Theme { name: "Green", felt: Color::new(0.0, 0.39, 0.19, 1.0), accent: WHITE }, // DARKGREEN
+    Theme { name: "Blue", felt: Color::new(0.04, 0.22, 0.42, 1.0), accent: Color::new(0.6, 0.85, 1.0, 1.0) },
+    Theme { name: "Burgundy", felt: Color::new(0.40, 0.06, 0.12, 1.0), accent: Color::new(1.0, 0.84, 0.45, 1.0) },
+    Theme { name: "Charcoal", felt: Color::new(0.17, 0.19, 0.21, 1.0), accent: Color::new(1.0, 0.65, 0.3, 1.0) },
+];
+
+thread_local! {
+    static CURRENT: Cell<usize> = const { Cell::new(0) };
+}
+
+// Use THEMES[index], anything out of range is the first. The felt and the button style change together.
+pub fn set_theme(index: usize) {
+    let index = if index < THEMES.len() { index } else { 0 };
+    CURRENT.with(|current| current.set(index));
+    widget_style::set_button_style(THEMES[index].button_style());
+}
+
+pub fn current() -> &'static Theme {
+    &THEMES[CURRENT.with(Cell::get)]
+}
+
+pub fn felt_color() -> Color {
+    current().felt
+}
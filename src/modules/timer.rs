@@ -0,0 +1,141 @@
+/*
+By: <tyler>
+Date: 2025-12-08
+Program Details: Timers that count frame time instead of reading the clock
+
+A Timer goes off after a number of seconds, once (Timer::after) or over and over
+(Timer::every). It only moves when tick() is given the frame's time, so a paused
+game or a paused timer doesn't use any of it up, and nothing ever has to sit in a
+loop watching get_time(). A Timer that repeats and is ticked by more than one
+period in a frame goes off once and keeps the rest, it doesn't fall behind.
+
+wait_seconds() is the same thing for async code: it waits for frames until the
+time is up, so the window keeps drawing while it waits.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod timer;
+
+Then add the following with the use commands:
+use crate::modules::timer::{wait_seconds, Timer};
+
+Usage examples:
+1. Something once, two seconds from now:
+    let mut timer = Timer::after(2.0);
+    if timer.tick(get_frame_time()) {
+        // time's up
+    }
+
+2. Something every half second:
+    let mut blink = Timer::every(0.5);
+    if blink.tick(get_frame_time()) {
+        show_cursor = !show_cursor;
+    }
+
+3. Pause and carry on:
+    timer.pause();
+    timer.resume();
+
+4. How far along it is, for a countdown bar:
+    let fraction_left = timer.remaining() / timer.duration();
+
+5. Wait in an async function without freezing the window:
+    wait_seconds(0.5).await;
+*/
+use macroquad::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Timer {
+    duration: f32,
+    elapsed: f32,
+    overshoot: f32, // How far past the end the last tick that went off went
+    repeat: bool,
+    paused: bool,
+    finished: bool,
+}
+
+impl Timer {
+    // Goes off once, `seconds` from now
+    pub fn after(seconds: f32) -> Self {
+        Self { duration: seconds.max(0.0), elapsed: 0.0, overshoot: 0.0, repeat: false, paused: false, finished: false }
+    }
+
+    // Goes off every `seconds`, the first time `seconds` from now
+    #[allow(unused)]
+    pub fn every(seconds: f32) -> Self {
+        Self { repeat: true, ..Self::after(seconds) }
+    }
+
+    // Move the timer on by dt seconds, true on the tick it goes off
+    pub fn tick(&mut self, dt: f32) -> bool {
+        if self.paused || self.finished {
+            return false;
+        }
+        self.elapsed += dt;
+        if self.elapsed < self.duration {
+            return false;
+        }
+        self.overshoot = self.elapsed - self.duration;
+        if self.repeat && self.duration > 0.0 {
+            self.elapsed %= self.duration;
+        } else {
+            self.elapsed = self.duration;
+            self.finished = !self.repeat;
+        }
+        true
+    }
+
+    #[allow(unused)]
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    #[allow(unused)]
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    #[allow(unused)]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    // A one off timer that has gone off. Repeating timers never finish.
+    #[allow(unused)]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    // Start counting again from 0, not paused
+    #[allow(unused)]
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.overshoot = 0.0;
+        self.paused = false;
+        self.finished = false;
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    // Seconds until it next goes off, 0 once a one off timer has
+    pub fn remaining(&self) -> f32 {
+        (self.duration - self.elapsed).max(0.0)
+    }
+
+    // Time from the last tick that was past the moment it went off, for whatever comes next to use
+    pub fn overshoot(&self) -> f32 {
+        self.overshoot
+    }
+}
+
+// Let frames go by until `seconds` have passed
+pub async fn wait_seconds(seconds: f32) {
+    let mut timer = Timer::after(seconds);
+    while timer.remaining() > 0.0 {
+        next_frame().await;
+        timer.tick(get_frame_time());
+    }
+}
@@ -0,0 +1,69 @@
+/*
+By: <tyler>
+Date: 2025-11-25
+Program Details: Hover tooltips for widgets
+
+Widgets ask for a tooltip while they are being drawn, but the tooltip itself is
+only drawn by layers::flush() at the end of the frame, on top of the overlay
+layer. Only the last request in a frame is shown.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod tooltip;
+
+Then add the following with the use commands:
+use crate::modules::tooltip;
+
+Usage examples:
+1. From a widget, while the mouse is over it:
+    tooltip::show("You can only double on your first two cards", mouse_x, mouse_y);
+
+2. layers::flush() draws it. Without the layers module call this last in the loop:
+    tooltip::draw();
+
+TextButton already does this for its disabled reason (see set_disabled_reason).
+*/
+use macroquad::prelude::*;
+use crate::modules::text_cache::measure_text_cached;
+use crate::modules::shapes::ShapeBatch;
+use std::cell::RefCell;
+#[cfg(feature = "scale")]
+use crate::modules::scale::VIRTUAL_RESOLUTION;
+
+const FONT_SIZE: f32 = 24.0;
+const PADDING: f32 = 8.0;
+
+thread_local! {
+    static PENDING: RefCell<Option<(String, Vec2)>> = const { RefCell::new(None) };
+}
+
+// Ask for a tooltip next to the given point (usually the mouse) this frame
+pub fn show(text: &str, x: f32, y: f32) {
+    PENDING.with(|pending| *pending.borrow_mut() = Some((text.to_string(), vec2(x, y))));
+}
+
+// Draw this frame's tooltip, if any, and forget it
+pub fn draw() {
+    let Some((text, point)) = PENDING.with(|pending| pending.borrow_mut().take()) else {
+        return;
+    };
+
+    #[cfg(feature = "scale")]
+    let (bounds_width, bounds_height) = VIRTUAL_RESOLUTION.with(|res| *res.borrow());
+    #[cfg(not(feature = "scale"))]
+    let (bounds_width, bounds_height) = (screen_width(), screen_height());
+
+    let size = measure_text_cached(&text, None, FONT_SIZE as u16);
+    let width = size.width + PADDING * 2.0;
+    let height = FONT_SIZE + PADDING;
+    // Above and to the right of the mouse, kept on screen
+    let x = (point.x + 12.0).min(bounds_width - width).max(0.0);
+    let y = (point.y - height - 8.0).max(0.0).min(bounds_height - height);
+
+    let mut shapes = ShapeBatch::new();
+    shapes.fill(Rect::new(x, y, width, height), 0.0, Color::new(0.1, 0.1, 0.1, 0.92));
+    shapes.outline(Rect::new(x, y, width, height), 0.0, 2.0, GOLD);
+    shapes.draw();
+    draw_text(&text, x + PADDING, y + FONT_SIZE * 0.8, FONT_SIZE, WHITE);
+}
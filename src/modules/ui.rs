@@ -0,0 +1,224 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Widget IDs, and a registry of the widgets on screen to find them by
+
+A widget can be given an ID string (TextButton::with_id, Label::with_id). Every
+frame, each widget with an ID that is drawn puts a WidgetInfo in the registry:
+where it is, its text and whether it is shown and enabled. The registry is
+started again by input::begin_frame, so it always holds the scene the last
+frame showed (the table, a panel over it, the profile menu...) and nothing
+from screens that aren't up.
+
+That lets code that doesn't own a widget find it and work it: a highlighter
+can ring a button by its ID, a test can press one, and the developer console
+lists them (`widgets`) and presses them (`press btn_hit`). A press is picked up
+by the widget's own click() on the next frame, as long as it is shown and
+enabled, so the game handles it exactly like a real click.
+
+IDs are the name of the widget's field, btn_hit for the Hit button.
+
+The registry also decides which widget the pointer is over when they overlap.
+Every widget that takes the pointer records where it is each frame
+(record_hit), with or without an ID, and a panel records the screen it covers
+(block). A widget is only hovered if nothing the last frame showed above it (a
+higher layer, a higher depth in the same layer, or later in the same depth)
+covers the pointer, so it doesn't matter in what order the buttons' click()
+calls run: the one drawn on top gets the click.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod layers;
+    pub mod text_button;
+    pub mod label;
+    pub mod ui;
+
+Then add the following with the use commands:
+use crate::modules::ui;
+
+Usage examples:
+1. Give a widget an ID:
+    let mut btn_hit = TextButton::new(0.0, 0.0, 170.0, 65.0, "Hit", BLACK, DARKGRAY, 35);
+    btn_hit.with_id("btn_hit");
+
+2. Look it up from anywhere (None when the last frame didn't show it):
+    let scene = ui::scene();
+    if let Some(hit) = scene.get::<TextButton>("btn_hit") {
+        draw_rectangle_lines(hit.rect.x - 5.0, hit.rect.y - 5.0, hit.rect.w + 10.0, hit.rect.h + 10.0, 3.0, GOLD);
+    }
+
+3. Press it, its click() answers true on the next frame:
+    ui::press("btn_hit");
+
+4. Take the pointer in a widget of your own (owner comes from new_owner() when it is made):
+    ui::record_hit(self.owner, Layer::Hud, rect);
+    let hovered = rect.contains(mouse) && ui::on_top(self.owner, Layer::Hud, mouse);
+
+5. Keep the pointer off everything under a panel:
+    ui::block(Layer::Overlay, layers::BACKGROUND, screen);
+*/
+use macroquad::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use crate::modules::label::Label;
+use crate::modules::layers::{self, Layer};
+use crate::modules::text_button::TextButton;
+
+// A kind of widget that can be registered
+pub trait Widget {
+    const KIND: &'static str;
+}
+
+impl Widget for TextButton {
+    const KIND: &'static str = "button";
+}
+
+impl Widget for Label {
+    const KIND: &'static str = "label";
+}
+
+// What a widget looked like when it was drawn
+#[allow(unused)]
+#[derive(Clone, Debug)]
+pub struct WidgetInfo {
+    pub id: Rc<str>,
+    pub kind: &'static str,
+    pub rect: Rect, // In virtual (world) coordinates
+    pub text: String,
+    pub visible: bool,
+    pub enabled: bool,
+}
+
+// Every widget with an ID one frame drew, in the order they were drawn
+#[derive(Clone, Default)]
+pub struct Scene {
+    widgets: Vec<WidgetInfo>,
+}
+
+impl Scene {
+    // The widget of kind W with this ID
+    #[allow(unused)]
+    pub fn get<W: Widget>(&self, id: &str) -> Option<&WidgetInfo> {
+        self.widgets.iter().find(|widget| widget.kind == W::KIND && &*widget.id == id)
+    }
+
+    #[allow(unused)]
+    pub fn widgets(&self) -> &[WidgetInfo] {
+        &self.widgets
+    }
+}
+
+// Where something that takes the pointer was, and how high up
+#[derive(Clone, Copy, Debug)]
+struct HitArea {
+    owner: u32, // 0 for a panel blocking what is under it
+    layer: usize,
+    depth: i32,
+    rect: Rect,
+}
+
+thread_local! {
+    static NEXT_OWNER: Cell<u32> = const { Cell::new(1) };
+    static HITS: RefCell<Vec<HitArea>> = const { RefCell::new(Vec::new()) };      // This frame's, so far
+    static LAST_HITS: RefCell<Vec<HitArea>> = const { RefCell::new(Vec::new()) }; // The last whole frame's, bottom to top
+    static DRAWING: RefCell<Vec<WidgetInfo>> = const { RefCell::new(Vec::new()) }; // This frame's, so far
+    static SHOWN: RefCell<Scene> = RefCell::new(Scene::default());             // The last whole frame's
+    static QUEUED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };   // Presses for the next frame
+    static PRESSES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };  // Presses for this frame
+}
+
+// Start a new frame: what was drawn becomes the scene, queued presses are handed out. Called by input::begin_frame.
+pub fn begin_frame() {
+    let drawn = DRAWING.with(|drawing| std::mem::take(&mut *drawing.borrow_mut()));
+    SHOWN.with(|shown| shown.borrow_mut().widgets = drawn);
+    let mut hits = HITS.with(|hits| std::mem::take(&mut *hits.borrow_mut()));
+    // Stable, so the same layer and depth keep the order they were drawn in
+    hits.sort_by_key(|hit| (hit.layer, hit.depth));
+    LAST_HITS.with(|last| *last.borrow_mut() = hits);
+    let queued = QUEUED.with(|queued| std::mem::take(&mut *queued.borrow_mut()));
+    PRESSES.with(|presses| *presses.borrow_mut() = queued);
+}
+
+// The widgets the last frame drew
+#[allow(unused)]
+pub fn scene() -> Scene {
+    SHOWN.with(|shown| shown.borrow().clone())
+}
+
+// Press the widget with this ID on the next frame
+#[allow(unused)]
+pub fn press(id: &str) {
+    QUEUED.with(|queued| queued.borrow_mut().push(id.to_string()));
+}
+
+// A widget saying it was drawn this frame
+pub fn record(info: WidgetInfo) {
+    DRAWING.with(|drawing| drawing.borrow_mut().push(info));
+}
+
+// Whether the widget with this ID was pressed for this frame; the press is used up
+pub fn take_press(id: &str) -> bool {
+    PRESSES.with(|presses| {
+        let mut presses = presses.borrow_mut();
+        let found = presses.iter().position(|pressed| pressed == id);
+        found.map(|i| presses.remove(i)).is_some()
+    })
+}
+
+// A number for a new widget to record its hits under
+pub fn new_owner() -> u32 {
+    NEXT_OWNER.with(|next| {
+        let owner = next.get();
+        next.set(owner + 1);
+        owner
+    })
+}
+
+// A widget that takes the pointer was drawn at `rect` this frame
+pub fn record_hit(owner: u32, layer: Layer, rect: Rect) {
+    HITS.with(|hits| hits.borrow_mut().push(HitArea { owner, layer: layers::index(layer), depth: 0, rect }));
+}
+
+// Nothing under `rect` in lower layers or depths should take the pointer (a panel over the table)
+pub fn block(layer: Layer, depth: i32, rect: Rect) {
+    HITS.with(|hits| hits.borrow_mut().push(HitArea { owner: 0, layer: layers::index(layer), depth, rect }));
+}
+
+// Whether the widget is the top one at `point`, going by where everything was last frame.
+// A widget that wasn't there last frame counts as the last one drawn in its layer.
+pub fn on_top(owner: u32, layer: Layer, point: Vec2) -> bool {
+    LAST_HITS.with(|last| {
+        let last = last.borrow();
+        let mine = last.iter().rposition(|hit| hit.owner == owner).unwrap_or(usize::MAX);
+        let my_key = (layers::index(layer), 0, mine);
+        !last
+            .iter()
+            .enumerate()
+            .any(|(i, hit)| hit.owner != owner && hit.rect.contains(point) && (hit.layer, hit.depth, i) > my_key)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_top_widget_takes_the_pointer() {
+        let (table_button, panel_button) = (new_owner(), new_owner());
+        let point = vec2(50.0, 50.0);
+        // The panel's button is recorded first, it is still over the table's
+        record_hit(panel_button, Layer::Overlay, Rect::new(0.0, 0.0, 100.0, 100.0));
+        record_hit(table_button, Layer::Hud, Rect::new(0.0, 0.0, 100.0, 100.0));
+        begin_frame();
+        assert!(on_top(panel_button, Layer::Overlay, point));
+        assert!(!on_top(table_button, Layer::Hud, point));
+        assert!(on_top(table_button, Layer::Hud, vec2(150.0, 50.0)));
+
+        // A panel with nothing on it still covers the table
+        block(Layer::Overlay, layers::BACKGROUND, Rect::new(0.0, 0.0, 200.0, 200.0));
+        begin_frame();
+        assert!(!on_top(table_button, Layer::Hud, vec2(150.0, 50.0)));
+        assert!(on_top(panel_button, Layer::Overlay, point));
+    }
+}
@@ -0,0 +1,63 @@
+/*
+By: <tyler>
+Date: 2025-12-12
+Program Details: Which build is running, and what changed in it
+
+The version comes from Cargo.toml and the git commit from build.rs, both fixed
+when the game is compiled, so a player reporting a bug can say exactly which
+build they were playing (the web build especially, which can be cached). The
+changelog is CHANGELOG.md at the top of the repository, built into the game
+with include_str!, so it is always the one that goes with the build.
+
+CHANGELOG.md is read as: a "## <version>" line starts a release, and each
+"- " line under it is one change. Anything else is left out.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod version;
+
+Then add the following with the use commands:
+use crate::modules::version;
+
+Usage examples:
+1. Show the build in a corner:
+    draw_text(&version::build_text(), 10.0, 20.0, 18.0, LIGHTGRAY); // "v0.1.0 (1a2b3c4)"
+
+2. List what's new, newest release first:
+    for release in version::changelog() {
+        println!("{}", release.version);
+        for change in &release.changes {
+            println!("  {}", change);
+        }
+    }
+*/
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const GIT_HASH: &str = env!("BLACKJACK_GIT_HASH");
+const CHANGELOG: &str = include_str!("../../CHANGELOG.md");
+
+pub struct Release {
+    pub version: &'static str,
+    pub changes: Vec<&'static str>,
+}
+
+// "v0.1.0 (1a2b3c4)"
+pub fn build_text() -> String {
+    format!("v{} ({})", VERSION, GIT_HASH)
+}
+
+// Every release in CHANGELOG.md, in the order written there (newest first)
+pub fn changelog() -> Vec<Release> {
+    let mut releases: Vec<Release> = Vec::new();
+    for line in CHANGELOG.lines().map(str::trim) {
+        if let Some(version) = line.strip_prefix("## ") {
+            releases.push(Release { version: version.trim(), changes: Vec::new() });
+        } else if let Some(change) = line.strip_prefix("- ")
+            && let Some(release) = releases.last_mut()
+        {
+            release.changes.push(change.trim());
+        }
+    }
+    releases
+}
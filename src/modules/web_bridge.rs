@@ -0,0 +1,185 @@
+/*
+By: <tyler>
+Date: 2025-11-19
+Program Details: Rust side of the browser helpers in js/blackjack.js
+
+Only the web build talks to JavaScript. Everything in here is wrapped so the
+rest of the game can call it without caring which platform it is running on.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod web_bridge;
+
+Then add the following with the use commands:
+use crate::modules::web_bridge::download_file;
+
+Usage examples:
+1. Offer some bytes to the player as a download (web only):
+    download_file("screenshot.png", &png_bytes, "image/png");
+
+2. Fill a buffer from the browser's crypto.getRandomValues (web only, use modules::entropy instead):
+    random_bytes(&mut seed_bytes);
+
+3. Use the browser's localStorage (web only, use modules::storage instead):
+    storage_set("profiles", &text);
+    let text = storage_get("profiles");
+
+3. Make a blocking HTTP request (web only, use modules::http instead):
+    let (status, body) = http_request("GET", url, None)?;
+
+4. Put an error page over the canvas after a panic (web only, modules::crash does this):
+    show_error(&report);
+
+5. Show the player some text to copy, or ask them to paste some (web only):
+    show_text("Copy your profile:", &json);
+    let pasted = ask_text("Paste your profile:");
+
+6. Read a sentence out loud with the browser's speech synthesis (web only, use modules::speech instead):
+    speak("Dealer shows an Ace");
+
+On native builds the functions in here do nothing.
+*/
+
+#[cfg(target_arch = "wasm32")]
+unsafe extern "C" {
+    fn blackjack_download_file(
+        name_ptr: *const u8,
+        name_len: usize,
+        data_ptr: *const u8,
+        data_len: usize,
+        mime_ptr: *const u8,
+        mime_len: usize,
+    );
+    fn blackjack_random_bytes(buffer_ptr: *mut u8, buffer_len: usize);
+    fn blackjack_storage_set(key_ptr: *const u8, key_len: usize, value_ptr: *const u8, value_len: usize);
+    fn blackjack_storage_get(key_ptr: *const u8, key_len: usize) -> i32;
+    fn blackjack_take_pending(buffer_ptr: *mut u8, buffer_len: usize);
+    fn blackjack_storage_remove(key_ptr: *const u8, key_len: usize);
+    fn blackjack_http_request(
+        method_ptr: *const u8,
+        method_len: usize,
+        url_ptr: *const u8,
+        url_len: usize,
+        body_ptr: *const u8,
+        body_len: usize,
+    ) -> i32;
+    fn blackjack_pending_len() -> usize;
+    fn blackjack_show_error(report_ptr: *const u8, report_len: usize);
+    fn blackjack_show_text(title_ptr: *const u8, title_len: usize, text_ptr: *const u8, text_len: usize);
+    fn blackjack_ask_text(title_ptr: *const u8, title_len: usize) -> i32;
+    fn blackjack_speak(text_ptr: *const u8, text_len: usize);
+}
+
+// miniquad asks every plugin for its crate version when the page loads,
+// this has to match the version number in js/blackjack.js
+#[cfg(target_arch = "wasm32")]
+#[unsafe(no_mangle)]
+pub extern "C" fn blackjack_crate_version() -> u32 {
+    8
+}
+
+// Hand a file to the browser so it shows up as a download
+#[allow(unused)]
+pub fn download_file(name: &str, data: &[u8], mime: &str) {
+    #[cfg(target_arch = "wasm32")]
+    unsafe {
+        blackjack_download_file(name.as_ptr(), name.len(), data.as_ptr(), data.len(), mime.as_ptr(), mime.len());
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = (name, data, mime);
+}
+
+// Fill the buffer with random bytes from crypto.getRandomValues
+#[cfg(target_arch = "wasm32")]
+pub fn random_bytes(buffer: &mut [u8]) {
+    unsafe {
+        blackjack_random_bytes(buffer.as_mut_ptr(), buffer.len());
+    }
+}
+
+// Store a string in localStorage
+#[cfg(target_arch = "wasm32")]
+pub fn storage_set(key: &str, value: &str) {
+    unsafe {
+        blackjack_storage_set(key.as_ptr(), key.len(), value.as_ptr(), value.len());
+    }
+}
+
+// Read a string from localStorage. JavaScript can't hand back a string directly,
+// so it reports the length first and then copies the bytes into our buffer.
+#[cfg(target_arch = "wasm32")]
+pub fn storage_get(key: &str) -> Option<String> {
+    let len = unsafe { blackjack_storage_get(key.as_ptr(), key.len()) };
+    if len < 0 {
+        return None;
+    }
+    String::from_utf8(take_pending(len as usize)).ok()
+}
+
+// Copy the bytes JavaScript is holding for us into a new buffer
+#[cfg(target_arch = "wasm32")]
+fn take_pending(len: usize) -> Vec<u8> {
+    let mut buffer = vec![0u8; len];
+    unsafe {
+        blackjack_take_pending(buffer.as_mut_ptr(), buffer.len());
+    }
+    buffer
+}
+
+// Delete a key from localStorage
+#[cfg(target_arch = "wasm32")]
+pub fn storage_remove(key: &str) {
+    unsafe {
+        blackjack_storage_remove(key.as_ptr(), key.len());
+    }
+}
+
+// Synchronous XMLHttpRequest, returns the status code and the response body
+#[cfg(target_arch = "wasm32")]
+pub fn http_request(method: &str, url: &str, body: Option<&str>) -> Result<(u16, String), String> {
+    let body = body.unwrap_or("");
+    let status = unsafe {
+        blackjack_http_request(method.as_ptr(), method.len(), url.as_ptr(), url.len(), body.as_ptr(), body.len())
+    };
+    // The response body is waiting in JavaScript either way
+    let text = String::from_utf8_lossy(&take_pending(unsafe { blackjack_pending_len() })).to_string();
+    if status < 0 {
+        return Err("could not reach the server".to_string());
+    }
+    Ok((status as u16, text))
+}
+
+// Cover the canvas with an HTML error page, the game can't draw any more after a panic
+#[cfg(target_arch = "wasm32")]
+pub fn show_error(report: &str) {
+    unsafe {
+        blackjack_show_error(report.as_ptr(), report.len());
+    }
+}
+
+// Show text in a browser dialog where it can be selected and copied, it is put on the clipboard too
+#[cfg(target_arch = "wasm32")]
+pub fn show_text(title: &str, text: &str) {
+    unsafe {
+        blackjack_show_text(title.as_ptr(), title.len(), text.as_ptr(), text.len());
+    }
+}
+
+// Ask the player to type or paste some text, None if they cancelled
+#[cfg(target_arch = "wasm32")]
+pub fn ask_text(title: &str) -> Option<String> {
+    let len = unsafe { blackjack_ask_text(title.as_ptr(), title.len()) };
+    if len < 0 {
+        return None;
+    }
+    String::from_utf8(take_pending(len as usize)).ok()
+}
+
+// Queue a sentence for the browser to say, nothing happens if it can't speak
+#[cfg(target_arch = "wasm32")]
+pub fn speak(text: &str) {
+    unsafe {
+        blackjack_speak(text.as_ptr(), text.len());
+    }
+}
@@ -0,0 +1,272 @@
+/*
+By: <tyler>
+Date: 2025-12-07
+Program Details: Row, Column and Grid helpers for placing groups of widgets
+
+Instead of working out x and y for every button in a group, hand the group to a
+Row (left to right), Column (top to bottom) or Grid (left to right, wrapping
+every `columns` widgets). The sizes come from the widgets themselves each time
+the group is placed, so changing a button's size or font moves the rest of the
+group along with it.
+
+Anything that implements Placeable can go in a group. TextButton, Label and
+Slider do: they report their size and are moved so their top left corner lands
+on the spot they were given.
+
+The position given to Row::new and Column::new is where the group is anchored.
+With the default Align::Start it is the top left corner of the group,
+Align::Center puts the middle of the group there (a row of dialog buttons
+centred on the screen), Align::End puts the far end there. Widgets smaller than
+the largest one in the group are lined up across the group with align_items().
+
+place() returns the rect each widget was put in, so text that goes along with a
+widget (the name of a setting next to its button) can be drawn beside it.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod text_button;
+    pub mod label;
+    pub mod slider;
+    pub mod widget_layout;
+
+Then add the following with the use commands:
+use crate::modules::widget_layout::{Align, Column, Grid, Placeable, Row};
+
+Usage examples:
+1. Three buttons in a row centred under the middle of the screen, 20 apart:
+    Row::new(screen_width / 2.0, 600.0).spacing(20.0).anchor(Align::Center).place(&mut [&mut btn_a, &mut btn_b, &mut btn_c]);
+
+2. A column of settings buttons with the setting names drawn next to them:
+    let rows = Column::new(x + 200.0, y + 95.0).spacing(15.0).place(&mut [&mut btn_music, &mut btn_sfx]);
+    for (name, row) in ["Music:", "Effects:"].iter().zip(rows) {
+        draw_text(name, x + 20.0, row.y + 30.0, 30.0, WHITE);
+    }
+
+3. Chips in two rows of three, each centred in its cell:
+    Grid::new(50.0, 500.0, 3).spacing(10.0, 10.0).align_items(Align::Center).place(&mut chips);
+*/
+use macroquad::prelude::*;
+
+// Something a group can move around
+pub trait Placeable {
+    // Width and height the widget takes up
+    fn size(&self) -> Vec2;
+    // Move the widget so its top left corner is at x, y
+    fn place(&mut self, x: f32, y: f32);
+}
+
+#[allow(unused)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Align {
+    Start,
+    Center,
+    End,
+}
+
+impl Align {
+    // How far into `space` something `size` long starts
+    fn offset(self, space: f32, size: f32) -> f32 {
+        match self {
+            Align::Start => 0.0,
+            Align::Center => (space - size) / 2.0,
+            Align::End => space - size,
+        }
+    }
+}
+
+// Widgets side by side, left to right
+#[derive(Clone, Copy, Debug)]
+pub struct Row {
+    x: f32,
+    y: f32,
+    spacing: f32,
+    anchor: Align,
+    align_items: Align,
+}
+
+impl Row {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y, spacing: 0.0, anchor: Align::Start, align_items: Align::Start }
+    }
+
+    // Gap between neighbouring widgets
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    // Which part of the row sits on x: its left end, its middle or its right end
+    pub fn anchor(mut self, anchor: Align) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    // How shorter widgets line up: along the top, through the middle or along the bottom
+    #[allow(unused)]
+    pub fn align_items(mut self, align: Align) -> Self {
+        self.align_items = align;
+        self
+    }
+
+    pub fn place(&self, items: &mut [&mut dyn Placeable]) -> Vec<Rect> {
+        let sizes: Vec<Vec2> = items.iter().map(|item| item.size()).collect();
+        let total = line_length(sizes.iter().map(|size| size.x), self.spacing);
+        let height = sizes.iter().fold(0.0f32, |tallest, size| tallest.max(size.y));
+        let mut x = self.x - self.anchor.offset(total, 0.0);
+        let mut placed = Vec::with_capacity(items.len());
+        for (item, size) in items.iter_mut().zip(sizes) {
+            let y = self.y + self.align_items.offset(height, size.y);
+            item.place(x, y);
+            placed.push(Rect::new(x, y, size.x, size.y));
+            x += size.x + self.spacing;
+        }
+        placed
+    }
+}
+
+// Widgets stacked, top to bottom
+#[derive(Clone, Copy, Debug)]
+pub struct Column {
+    x: f32,
+    y: f32,
+    spacing: f32,
+    anchor: Align,
+    align_items: Align,
+}
+
+impl Column {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y, spacing: 0.0, anchor: Align::Start, align_items: Align::Start }
+    }
+
+    // Gap between neighbouring widgets
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    // Which part of the column sits on y: its top, its middle or its bottom
+    #[allow(unused)]
+    pub fn anchor(mut self, anchor: Align) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    // How narrower widgets line up: on the left, down the middle or on the right
+    #[allow(unused)]
+    pub fn align_items(mut self, align: Align) -> Self {
+        self.align_items = align;
+        self
+    }
+
+    pub fn place(&self, items: &mut [&mut dyn Placeable]) -> Vec<Rect> {
+        let sizes: Vec<Vec2> = items.iter().map(|item| item.size()).collect();
+        let total = line_length(sizes.iter().map(|size| size.y), self.spacing);
+        let width = sizes.iter().fold(0.0f32, |widest, size| widest.max(size.x));
+        let mut y = self.y - self.anchor.offset(total, 0.0);
+        let mut placed = Vec::with_capacity(items.len());
+        for (item, size) in items.iter_mut().zip(sizes) {
+            let x = self.x + self.align_items.offset(width, size.x);
+            item.place(x, y);
+            placed.push(Rect::new(x, y, size.x, size.y));
+            y += size.y + self.spacing;
+        }
+        placed
+    }
+}
+
+// Widgets in equal cells, filling each row of `columns` cells before starting the next
+#[derive(Clone, Copy, Debug)]
+pub struct Grid {
+    x: f32,
+    y: f32,
+    columns: usize,
+    column_spacing: f32,
+    row_spacing: f32,
+    align_items: Align,
+}
+
+impl Grid {
+    // x, y is the top left corner of the grid
+    #[allow(unused)]
+    pub fn new(x: f32, y: f32, columns: usize) -> Self {
+        Self { x, y, columns: columns.max(1), column_spacing: 0.0, row_spacing: 0.0, align_items: Align::Start }
+    }
+
+    // Gaps between columns and between rows
+    #[allow(unused)]
+    pub fn spacing(mut self, column_spacing: f32, row_spacing: f32) -> Self {
+        self.column_spacing = column_spacing;
+        self.row_spacing = row_spacing;
+        self
+    }
+
+    // Where a widget smaller than its cell sits in it, both ways
+    #[allow(unused)]
+    pub fn align_items(mut self, align: Align) -> Self {
+        self.align_items = align;
+        self
+    }
+
+    // Every cell is as big as the biggest widget
+    #[allow(unused)]
+    pub fn place(&self, items: &mut [&mut dyn Placeable]) -> Vec<Rect> {
+        let sizes: Vec<Vec2> = items.iter().map(|item| item.size()).collect();
+        let cell = sizes.iter().fold(Vec2::ZERO, |biggest, size| biggest.max(*size));
+        let mut placed = Vec::with_capacity(items.len());
+        for (i, (item, size)) in items.iter_mut().zip(sizes).enumerate() {
+            let cell_x = self.x + (i % self.columns) as f32 * (cell.x + self.column_spacing);
+            let cell_y = self.y + (i / self.columns) as f32 * (cell.y + self.row_spacing);
+            let x = cell_x + self.align_items.offset(cell.x, size.x);
+            let y = cell_y + self.align_items.offset(cell.y, size.y);
+            item.place(x, y);
+            placed.push(Rect::new(x, y, size.x, size.y));
+        }
+        placed
+    }
+}
+
+// Length of widgets laid end to end with gaps between them
+fn line_length(lengths: impl Iterator<Item = f32>, spacing: f32) -> f32 {
+    let (total, count) = lengths.fold((0.0, 0), |(total, count), length| (total + length, count + 1));
+    total + spacing * (count.max(1) - 1) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::bench;
+
+    // A widget that is only a size, the layout doesn't need anything else
+    struct Block {
+        size: Vec2,
+        pos: Vec2,
+    }
+
+    impl Placeable for Block {
+        fn size(&self) -> Vec2 {
+            self.size
+        }
+
+        fn place(&mut self, x: f32, y: f32) {
+            self.pos = vec2(x, y);
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_widget_layout() {
+        // As many widgets as a busy panel, in a few different sizes
+        let mut blocks: Vec<Block> = (0..100).map(|i| Block { size: vec2(80.0 + (i % 7) as f32 * 10.0, 40.0 + (i % 3) as f32 * 5.0), pos: Vec2::ZERO }).collect();
+        let count = blocks.len();
+        bench::run("widgets in a row", count, || {
+            let mut items: Vec<&mut dyn Placeable> = blocks.iter_mut().map(|block| block as &mut dyn Placeable).collect();
+            Row::new(0.0, 0.0).spacing(10.0).anchor(Align::Center).align_items(Align::Center).place(&mut items)
+        });
+        bench::run("widgets in a grid", count, || {
+            let mut items: Vec<&mut dyn Placeable> = blocks.iter_mut().map(|block| block as &mut dyn Placeable).collect();
+            Grid::new(0.0, 0.0, 10).spacing(10.0, 10.0).align_items(Align::Center).place(&mut items)
+        });
+    }
+}
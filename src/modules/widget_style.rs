@@ -0,0 +1,148 @@
+/*
+By: <tyler>
+Date: 2025-12-05
+Program Details: Default looks for new buttons and labels
+
+TextButton::new and Label::new start from the style registered here instead of
+their own built-in defaults, so a theme can be set once at startup rather than
+chaining the same builders onto every widget. Buttons and labels each have
+their own style. Registering a style only changes widgets made after it, and
+any widget can still override a part with its own with_* builders. The one
+exception is a button's caption colours: a button that hasn't been given its
+own takes them from the registered style every time it is drawn, so a new
+theme (modules::theme) reaches the buttons already on screen.
+A style holds the text colors, background, font, corner radius and border.
+The colors a button takes in new() (normal and hover) are still per button.
+
+The text scale (85% to 150%, for players who need bigger text) multiplies the
+font size of every button and label. New widgets take it straight away. Ones
+already made pick it up the next time they lay their text out (a button when it
+is moved, a label when its text changes) or when rescale_text() is called on
+them, so call that on the widgets that stay put after changing the scale.
+A button's caption still shrinks or wraps to fit the button, and a label grows,
+so widgets placed with widget_layout make room for the bigger text.
+
+To import you need:
+
+In your mod.rs file located in the modules folder add the following to the end of the file:
+    pub mod widget_style;
+
+Then add the following with the use commands:
+use crate::modules::widget_style::{self, WidgetStyle};
+
+Usage examples:
+1. Before making any widgets, give every button rounded corners and yellow hover text:
+    widget_style::set_button_style(WidgetStyle::new(WHITE).with_round(5.0).with_hover_text_color(YELLOW));
+
+2. Give every label a dark box behind it:
+    widget_style::set_label_style(WidgetStyle::new(WHITE).with_background(Color::new(0.0, 0.0, 0.0, 0.5)));
+
+3. Override one widget, either a single part or a whole other style:
+    btn_exit.with_round(0.0);
+    lbl_title.with_style(&WidgetStyle::new(GOLD).with_border(WHITE, 2.0));
+
+4. Make all the text a quarter bigger, and lay out the widgets already made again:
+    widget_style::set_text_scale(1.25);
+    btn_exit.rescale_text();
+    lbl_title.rescale_text();
+*/
+use macroquad::prelude::*;
+use std::cell::{Cell, RefCell};
+
+// The smallest and biggest text scale
+pub const MIN_TEXT_SCALE: f32 = 0.85;
+pub const MAX_TEXT_SCALE: f32 = 1.5;
+
+#[derive(Clone, Debug)]
+pub struct WidgetStyle {
+    pub text_color: Color,
+    pub hover_text_color: Color,    // Buttons only
+    pub background: Option<Color>,  // Labels only, buttons take theirs in new()
+    pub font: Option<Font>,         // None for the default system font
+    pub corner_radius: f32,
+    pub border: Option<(Color, f32)>, // Color and thickness
+}
+
+impl WidgetStyle {
+    // Plain style with the given text color, no background, square corners and no border
+    pub fn new(text_color: Color) -> Self {
+        Self { text_color, hover_text_color: text_color, background: None, font: None, corner_radius: 0.0, border: None }
+    }
+
+    #[allow(unused)]
+    pub fn with_hover_text_color(mut self, color: Color) -> Self {
+        self.hover_text_color = color;
+        self
+    }
+
+    #[allow(unused)]
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    #[allow(unused)]
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    pub fn with_round(mut self, radius: f32) -> Self {
+        self.corner_radius = radius;
+        self
+    }
+
+    #[allow(unused)]
+    pub fn with_border(mut self, color: Color, thickness: f32) -> Self {
+        self.border = Some((color, thickness));
+        self
+    }
+}
+
+thread_local! {
+    // What the widgets looked like before there were styles: white button text, black label text
+    static BUTTON_STYLE: RefCell<WidgetStyle> = RefCell::new(WidgetStyle::new(WHITE));
+    static LABEL_STYLE: RefCell<WidgetStyle> = RefCell::new(WidgetStyle::new(BLACK));
+    static TEXT_SCALE: Cell<f32> = const { Cell::new(1.0) };
+}
+
+pub fn set_button_style(style: WidgetStyle) {
+    BUTTON_STYLE.with(|current| *current.borrow_mut() = style);
+}
+
+#[allow(unused)]
+pub fn set_label_style(style: WidgetStyle) {
+    LABEL_STYLE.with(|current| *current.borrow_mut() = style);
+}
+
+// The style new buttons start with
+pub fn button_style() -> WidgetStyle {
+    BUTTON_STYLE.with(|current| current.borrow().clone())
+}
+
+// The registered button style's caption colours, normal and under the pointer
+pub fn button_text_colors() -> (Color, Color) {
+    BUTTON_STYLE.with(|current| {
+        let style = current.borrow();
+        (style.text_color, style.hover_text_color)
+    })
+}
+
+// The style new labels start with
+pub fn label_style() -> WidgetStyle {
+    LABEL_STYLE.with(|current| current.borrow().clone())
+}
+
+// Make every button and label's text `scale` times its font size, kept between MIN_TEXT_SCALE and MAX_TEXT_SCALE
+pub fn set_text_scale(scale: f32) {
+    TEXT_SCALE.with(|current| current.set(scale.clamp(MIN_TEXT_SCALE, MAX_TEXT_SCALE)));
+}
+
+pub fn text_scale() -> f32 {
+    TEXT_SCALE.with(Cell::get)
+}
+
+// A widget's font size at the current text scale
+pub fn scaled_font_size(font_size: u16) -> u16 {
+    (font_size as f32 * text_scale()).round() as u16
+}